@@ -1,3 +1,4 @@
+import CryptoKit
 import Foundation
 import os
 
@@ -200,6 +201,78 @@ struct AltoIndexAdapter: NativeImportAdapter, Sendable {
         return cards
     }
 
+    // MARK: - Incremental Re-scan
+
+    /// A stored manifest entry (dataset_files row) sent by JS with a re-import request.
+    struct ManifestEntry: Sendable {
+        let size: Int
+        let mtime: String
+        let hash: String
+    }
+
+    /// One file of an incremental re-scan. `cards` is nil when the file is unchanged
+    /// since the stored manifest, so JS leaves its cards alone.
+    struct ScannedFile: Encodable, Sendable {
+        let path: String
+        let size: Int
+        let mtime: String
+        let hash: String
+        let cards: [CanonicalCard]?
+    }
+
+    /// Re-scan a subdirectory against the stored manifest, parsing only changed files.
+    ///
+    /// Files whose size and mtime match the manifest keep their stored hash and are
+    /// not read. Other files are read and SHA-256 hashed; only those whose content
+    /// differs from the manifest (or that are new — including renamed paths) are parsed.
+    ///
+    /// - Parameters:
+    ///   - dirPath:    Absolute path to the subdirectory on disk.
+    ///   - cardType:   CanonicalCard.card_type for this subdirectory.
+    ///   - subdirName: Human-readable subdirectory name (e.g. "notes").
+    ///   - manifest:   Stored manifest keyed by absolute file path (empty on the first re-scan).
+    /// - Returns: Every .md file in the directory, with cards for the parsed ones.
+    static func scanDirectoryIncremental(
+        dirPath: String,
+        cardType: String,
+        subdirName: String,
+        manifest: [String: ManifestEntry]
+    ) -> [ScannedFile] {
+        let mdFiles = collectMarkdownFiles(in: dirPath)
+        let fm = FileManager.default
+        var files: [ScannedFile] = []
+        var parsedCount = 0
+
+        for (index, filePath) in mdFiles.enumerated() {
+            guard let attrs = try? fm.attributesOfItem(atPath: filePath) else { continue }
+            let size = (attrs[.size] as? NSNumber)?.intValue ?? 0
+            let mtime = isoFormatter.string(from: (attrs[.modificationDate] as? Date) ?? Date())
+            let previous = manifest[filePath]
+
+            // Size + mtime unchanged: trust the stored hash without reading the file
+            if let previous, previous.size == size, previous.mtime == mtime {
+                files.append(ScannedFile(path: filePath, size: size, mtime: mtime, hash: previous.hash, cards: nil))
+                continue
+            }
+
+            guard let data = fm.contents(atPath: filePath) else { continue }
+            let hash = SHA256.hash(data: data).map { String(format: "%02x", $0) }.joined()
+
+            // Touched but identical content: record the new mtime, skip parsing
+            if let previous, previous.hash == hash {
+                files.append(ScannedFile(path: filePath, size: size, mtime: mtime, hash: hash, cards: nil))
+                continue
+            }
+
+            let card = parseFile(path: filePath, cardType: cardType, subdirectory: subdirName, index: index)
+            files.append(ScannedFile(path: filePath, size: size, mtime: mtime, hash: hash, cards: card.map { [$0] } ?? []))
+            parsedCount += 1
+        }
+
+        logger.debug("Incremental re-scan: parsed \(parsedCount) of \(mdFiles.count) .md files in \(subdirName)/")
+        return files
+    }
+
     // MARK: - File Collection
 
     /// Recursively collect all .md files from a directory, skipping CLAUDE.md
@@ -319,6 +319,7 @@ final class BridgeManager: NSObject, ObservableObject {
 
         case "native:request-alto-reimport":
             // JS sends stored directory path for a single-directory re-import (Phase 125 DSET-03)
+            // plus the stored file manifest, so only changed files are re-parsed
             guard let payload = body["payload"] as? [String: Any],
                   let datasetId = payload["datasetId"] as? String,
                   let name = payload["name"] as? String,
@@ -327,7 +328,15 @@ final class BridgeManager: NSObject, ObservableObject {
                 logger.warning("native:request-alto-reimport: invalid payload")
                 break
             }
-            logger.info("native:request-alto-reimport: dataset \(datasetId) at \(path)")
+            var manifest: [String: AltoIndexAdapter.ManifestEntry] = [:]
+            for entry in payload["manifest"] as? [[String: Any]] ?? [] {
+                guard let filePath = entry["path"] as? String,
+                      let size = entry["size"] as? Int,
+                      let mtime = entry["mtime"] as? String,
+                      let hash = entry["hash"] as? String else { continue }
+                manifest[filePath] = AltoIndexAdapter.ManifestEntry(size: size, mtime: mtime, hash: hash)
+            }
+            logger.info("native:request-alto-reimport: dataset \(datasetId) at \(path) (\(manifest.count) known files)")
 
             // Security-scoped resource access for the directory
             let dirURL = URL(fileURLWithPath: path)
@@ -335,20 +344,21 @@ final class BridgeManager: NSObject, ObservableObject {
 
             Task { @MainActor [weak self] in
                 guard let self = self else { return }
-                // Re-read cards from the stored directory path
-                let cards = AltoIndexAdapter.fetchCardsForDirectory(
+                // Re-scan the stored directory path, parsing only changed files
+                let files = AltoIndexAdapter.scanDirectoryIncremental(
                     dirPath: path,
                     cardType: cardType,
-                    subdirName: name
+                    subdirName: name,
+                    manifest: manifest
                 )
 
                 if gained { dirURL.stopAccessingSecurityScopedResource() }
 
                 // Encode the result payload as JSON and send back to JS
                 let encoder = JSONEncoder()
-                guard let cardsData = try? encoder.encode(cards),
-                      let cardsJSON = String(data: cardsData, encoding: .utf8) else {
-                    logger.error("native:request-alto-reimport: failed to encode cards")
+                guard let filesData = try? encoder.encode(files),
+                      let filesJSON = String(data: filesData, encoding: .utf8) else {
+                    logger.error("native:request-alto-reimport: failed to encode files")
                     return
                 }
                 guard let metaData = try? JSONSerialization.data(withJSONObject: ["datasetId": datasetId, "name": name]),
@@ -356,10 +366,10 @@ final class BridgeManager: NSObject, ObservableObject {
                     logger.error("native:request-alto-reimport: failed to encode metadata")
                     return
                 }
-                // Merge cards into meta JSON: replace closing `}` with `, "cards": <cardsJSON> }`
-                let payloadJSON = String(metaJSON.dropLast()) + ",\"cards\":\(cardsJSON)}"
+                // Merge files into meta JSON: replace closing `}` with `, "files": <filesJSON> }`
+                let payloadJSON = String(metaJSON.dropLast()) + ",\"files\":\(filesJSON)}"
                 let js = "window.__isometry.receive({type:'native:alto-reimport-result',payload:\(payloadJSON)});"
-                logger.info("native:request-alto-reimport: sending \(cards.count) cards for dataset \(datasetId)")
+                logger.info("native:request-alto-reimport: sending \(files.count) files for dataset \(datasetId)")
                 _ = try? await self.webView?.evaluateJavaScript(js)
             }
 
@@ -1,7 +1,8 @@
 // Isometry v5 — Phase 37 AuditState
 // Session-only change tracking singleton with subscribe pattern.
 //
-// Tracks insertedIds, updatedIds, and deletedIds as in-memory Sets.
+// Tracks insertedIds, updatedIds, and deletedIds as in-memory Sets, plus
+// which enrichers changed each card.
 // NOT a provider registered with StateCoordinator -- audit toggle is
 // pure CSS overlay, no Worker re-query needed.
 //
@@ -19,6 +20,18 @@ export interface AuditImportResult {
 	insertedIds: string[];
 	updatedIds: string[];
 	deletedIds: string[];
+	/** Card IDs each enricher changed, by enricher id */
+	enriched?: Record<string, string[]>;
+}
+
+/**
+ * Minimal enricher re-run result shape consumed by AuditState.
+ * Matches the subset of the enrich:backfill response needed for change tracking.
+ */
+export interface AuditEnrichmentResult {
+	insertedIds: string[];
+	updatedIds: string[];
+	enriched: Record<string, string[]>;
 }
 
 /**
@@ -36,6 +49,7 @@ export class AuditState {
 	private _enabled = false;
 	private _listeners: Array<() => void> = [];
 	private _cardSourceMap = new Map<string, string>();
+	private _cardEnrichers = new Map<string, Set<string>>();
 
 	// ---------------------------------------------------------------------------
 	// Public API
@@ -86,9 +100,29 @@ export class AuditState {
 		for (const id of result.deletedIds) {
 			this._deletedIds.add(id);
 		}
+		this._addEnriched(result.enriched);
 		this._notify();
 	}
 
+	/**
+	 * Accumulate an enricher re-run (enrich:backfill) into change sets.
+	 * Cards keep the source recorded by their import; re-runs do not change it.
+	 */
+	addEnrichmentResult(result: AuditEnrichmentResult): void {
+		for (const id of result.insertedIds) this._insertedIds.add(id);
+		for (const id of result.updatedIds) this._updatedIds.add(id);
+		this._addEnriched(result.enriched);
+		this._notify();
+	}
+
+	/**
+	 * Get the IDs of the enrichers that changed a card this session.
+	 * @returns Enricher IDs in the order they were first recorded (empty if none).
+	 */
+	getEnrichers(id: string): string[] {
+		return [...(this._cardEnrichers.get(id) ?? [])];
+	}
+
 	/**
 	 * Get the source type for a card ID.
 	 * @returns Source type string, or null if not tracked.
@@ -150,7 +184,7 @@ export class AuditState {
 
 	/**
 	 * Subscribe to audit state changes.
-	 * Callback fires on toggle() and addImportResult().
+	 * Callback fires on toggle(), addImportResult() and addEnrichmentResult().
 	 *
 	 * @returns Unsubscribe function -- call in view destroy() to prevent leaks.
 	 */
@@ -166,6 +200,17 @@ export class AuditState {
 	// Private
 	// ---------------------------------------------------------------------------
 
+	private _addEnriched(enriched: Record<string, string[]> | undefined): void {
+		if (!enriched) return;
+		for (const [enricherId, ids] of Object.entries(enriched)) {
+			for (const id of ids) {
+				const enrichers = this._cardEnrichers.get(id) ?? new Set<string>();
+				enrichers.add(enricherId);
+				this._cardEnrichers.set(id, enrichers);
+			}
+		}
+	}
+
 	private _notify(): void {
 		for (const cb of this._listeners) {
 			cb();
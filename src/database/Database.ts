@@ -11,6 +11,22 @@ export interface Statement<T = unknown> {
 	free(): void;
 }
 
+/**
+ * Forward-only row cursor over a prepared SELECT.
+ * Rows are stepped lazily so large result sets never materialize at once.
+ * The underlying statement is freed automatically once exhausted.
+ */
+export interface RowCursor<T = unknown> {
+	/** Result column names (available before the first row is read) */
+	readonly columns: string[];
+	/** True once the final row has been read or the cursor was closed */
+	readonly done: boolean;
+	/** Read up to `count` rows. Returns an empty array when done. */
+	next(count: number): T[];
+	/** Free the statement early (idempotent). */
+	close(): void;
+}
+
 export class Database {
 	private db: SqlJsDatabase | null = null;
 	private _initialized = false;
@@ -182,6 +198,68 @@ export class Database {
 		};
 	}
 
+	/**
+	 * Open a forward-only cursor over a parameterized SELECT.
+	 * Used by the Worker cursor registry to page large result sets
+	 * (card:list, db:query) across the postMessage boundary.
+	 *
+	 * @param sql SELECT statement with placeholders
+	 * @param params Bound parameters
+	 * @returns RowCursor that steps rows on demand
+	 */
+	openCursor<T = Record<string, unknown>>(sql: string, params: unknown[] = []): RowCursor<T> {
+		if (!this.db) throw new Error('Database not initialized');
+		const stmt = this.db.prepare(sql);
+		let done = false;
+		// One-row lookahead so `done` flips on the page that returns the final row
+		let lookahead: T | null = null;
+
+		const close = () => {
+			if (done) return;
+			done = true;
+			lookahead = null;
+			stmt.free();
+		};
+
+		const advance = () => {
+			if (stmt.step()) {
+				lookahead = stmt.getAsObject() as T;
+			} else {
+				close();
+			}
+		};
+
+		let columns: string[];
+		try {
+			if (params.length > 0) {
+				stmt.bind(params as BindParams);
+			}
+			columns = stmt.getColumnNames();
+			advance();
+		} catch (err) {
+			// close() only runs once step() reports exhaustion — release the statement here
+			stmt.free();
+			throw err;
+		}
+
+		return {
+			columns,
+			get done() {
+				return done;
+			},
+			next: (count: number): T[] => {
+				const rows: T[] = [];
+				while (!done && lookahead !== null && rows.length < count) {
+					rows.push(lookahead);
+					lookahead = null;
+					advance();
+				}
+				return rows;
+			},
+			close,
+		};
+	}
+
 	/**
 	 * Execute a function within a transaction.
 	 * Returns a wrapper function that executes fn inside BEGIN/COMMIT.
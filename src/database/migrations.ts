@@ -0,0 +1,329 @@
+// Isometry v5 — Versioned Schema Migrations
+// Numbered, ordered migrations tracked in the schema_migrations table.
+//
+// Replaces the ad-hoc CREATE TABLE IF NOT EXISTS / ALTER TABLE try-catch
+// patches that used to live in worker.ts initialize().
+//
+// Design:
+//   - Each migration has a unique, strictly increasing version number
+//   - Pending migrations are applied in order, each inside its own transaction
+//   - A migration and its schema_migrations row commit (or roll back) together
+//   - Migrations must be safe on both fresh databases (schema.sql already
+//     contains the column/table) and hydrated checkpoints from older phases
+//
+// Adding a migration: append a new entry to MIGRATIONS with the next version.
+// Never renumber or edit a migration that has shipped.
+
+import type { Database } from './Database';
+import { DATASET_FILES_DDL } from './queries/dataset-files';
+import { ENRICHER_SETTINGS_DDL } from './queries/enricher-settings';
+import { FORMULAS_DDL } from './queries/formulas';
+import { GEOCODE_PLACES_DDL, seedGeocodePlaces } from './queries/geocode';
+import { GRAPH_METRICS_DDL } from './queries/graph-metrics';
+import { MAPPING_PROFILES_DDL } from './queries/mapping-profiles';
+import { CARD_PROPERTIES_DDL } from './queries/properties';
+import { SAVED_SEARCHES_DDL } from './queries/saved-searches';
+import {
+	CARD_VECTOR_TERMS_DDL,
+	CARD_VECTOR_TERMS_TRIGGER_DDL,
+	CARD_VECTORS_DDL,
+	CARD_VECTORS_TRIGGER_DDL,
+} from './queries/similarity';
+import { STORIES_DDL } from './queries/stories';
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/**
+ * A single schema migration step.
+ * `up` runs inside a transaction opened by runMigrations() — it must not
+ * issue BEGIN/COMMIT itself.
+ */
+export interface Migration {
+	/** Unique, strictly increasing version number */
+	version: number;
+	/** Short human-readable name recorded in schema_migrations */
+	name: string;
+	/** Apply the migration */
+	up: (db: Database) => void;
+}
+
+/**
+ * Row shape of the schema_migrations tracking table.
+ */
+export interface AppliedMigration {
+	version: number;
+	name: string;
+	applied_at: string;
+}
+
+/**
+ * Thrown when a migration fails. The failing migration's transaction is
+ * rolled back; earlier migrations in the same run stay committed.
+ */
+export class MigrationError extends Error {
+	readonly version: number;
+	readonly migrationName: string;
+
+	constructor(version: number, migrationName: string, cause: unknown) {
+		const reason = cause instanceof Error ? cause.message : String(cause);
+		super(`Schema migration ${version} (${migrationName}) failed: ${reason}`);
+		this.name = 'MigrationError';
+		this.version = version;
+		this.migrationName = migrationName;
+	}
+}
+
+// ---------------------------------------------------------------------------
+// DDL helpers
+// ---------------------------------------------------------------------------
+
+const SCHEMA_MIGRATIONS_DDL = `CREATE TABLE IF NOT EXISTS schema_migrations (
+  version INTEGER PRIMARY KEY NOT NULL,
+  name TEXT NOT NULL,
+  applied_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+)`;
+
+/**
+ * Run a multi-statement DDL string one statement at a time.
+ */
+function runStatements(db: Database, ddl: string): void {
+	for (const stmt of ddl.split(';').filter((s) => s.trim())) {
+		db.run(stmt);
+	}
+}
+
+/**
+ * Return true if `table` has a column named `column`.
+ * Table and column names are compile-time constants from MIGRATIONS — never user input.
+ */
+function hasColumn(db: Database, table: string, column: string): boolean {
+	const result = db.exec(`PRAGMA table_info(${table})`);
+	const rows = result[0]?.values ?? [];
+	// PRAGMA table_info columns: cid, name, type, notnull, dflt_value, pk
+	return rows.some((row) => row[1] === column);
+}
+
+/**
+ * ALTER TABLE ... ADD COLUMN only when the column is missing.
+ * Fresh databases already have every column from schema.sql.
+ */
+function addColumnIfMissing(db: Database, table: string, column: string, definition: string): void {
+	if (!hasColumn(db, table, column)) {
+		db.run(`ALTER TABLE ${table} ADD COLUMN ${column} ${definition}`);
+	}
+}
+
+// ---------------------------------------------------------------------------
+// Migrations
+// ---------------------------------------------------------------------------
+
+/**
+ * Ordered list of all schema migrations.
+ * Versions 1–5 capture the patches previously applied ad hoc in worker.ts.
+ */
+export const MIGRATIONS: readonly Migration[] = [
+	{
+		version: 1,
+		name: 'create_datasets',
+		up: (db) => {
+			// Phase 88: datasets registry (checkpoints from v1.0 predate it)
+			db.run(`CREATE TABLE IF NOT EXISTS datasets (
+				id TEXT PRIMARY KEY NOT NULL,
+				name TEXT NOT NULL,
+				source_type TEXT NOT NULL,
+				card_count INTEGER NOT NULL DEFAULT 0,
+				connection_count INTEGER NOT NULL DEFAULT 0,
+				file_size_bytes INTEGER,
+				filename TEXT,
+				import_run_id TEXT,
+				source_id TEXT,
+				is_active INTEGER NOT NULL DEFAULT 0,
+				created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+				last_imported_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+			)`);
+			db.run('CREATE UNIQUE INDEX IF NOT EXISTS idx_datasets_name_source ON datasets(name, source_type)');
+			db.run('CREATE INDEX IF NOT EXISTS idx_datasets_active ON datasets(is_active)');
+		},
+	},
+	{
+		version: 2,
+		name: 'create_graph_metrics',
+		up: (db) => {
+			// Phase 114, v9.0
+			runStatements(db, GRAPH_METRICS_DDL);
+		},
+	},
+	{
+		version: 3,
+		name: 'cards_dataset_id',
+		up: (db) => {
+			// Phase 125: per-dataset lifecycle
+			addColumnIfMissing(db, 'cards', 'dataset_id', 'TEXT');
+			db.run('CREATE INDEX IF NOT EXISTS idx_cards_dataset_id ON cards(dataset_id)');
+		},
+	},
+	{
+		version: 4,
+		name: 'datasets_directory_path',
+		up: (db) => {
+			// Phase 125 DSET-03: re-import without re-picking
+			addColumnIfMissing(db, 'datasets', 'directory_path', 'TEXT');
+		},
+	},
+	{
+		version: 5,
+		name: 'cards_folder_hierarchy',
+		up: (db) => {
+			// Enrichment pipeline: folder hierarchy levels
+			for (const col of ['folder_l1', 'folder_l2', 'folder_l3', 'folder_l4']) {
+				addColumnIfMissing(db, 'cards', col, 'TEXT');
+			}
+		},
+	},
+	{
+		version: 6,
+		name: 'create_card_properties',
+		up: (db) => {
+			// Typed user-defined card properties (property_definitions + card_properties EAV)
+			runStatements(db, CARD_PROPERTIES_DDL);
+		},
+	},
+	{
+		version: 7,
+		name: 'create_card_vectors',
+		up: (db) => {
+			// Similar-card search: hashed TF-IDF vectors, bucket postings + invalidation triggers
+			db.run(CARD_VECTORS_DDL);
+			db.run(CARD_VECTORS_TRIGGER_DDL);
+			runStatements(db, CARD_VECTOR_TERMS_DDL);
+			db.run(CARD_VECTOR_TERMS_TRIGGER_DDL);
+		},
+	},
+	{
+		version: 8,
+		name: 'create_saved_searches',
+		up: (db) => {
+			// Saved searches / smart folders (named FilterProvider states)
+			db.run(SAVED_SEARCHES_DDL);
+		},
+	},
+	{
+		version: 9,
+		name: 'create_geocode_places',
+		up: (db) => {
+			// Offline gazetteer for the map view: place name -> coordinates
+			db.run(GEOCODE_PLACES_DDL);
+			seedGeocodePlaces(db);
+		},
+	},
+	{
+		version: 10,
+		name: 'create_formulas',
+		up: (db) => {
+			// Formula fields: named expressions exposed as virtual fx_<name> columns
+			runStatements(db, FORMULAS_DDL);
+		},
+	},
+	{
+		version: 11,
+		name: 'create_stories',
+		up: (db) => {
+			// Stories: ordered slides of captured view states with markdown captions
+			runStatements(db, STORIES_DDL);
+		},
+	},
+	{
+		version: 12,
+		name: 'create_dataset_files',
+		up: (db) => {
+			// Incremental directory re-import: per-dataset file manifest
+			db.run(DATASET_FILES_DDL);
+		},
+	},
+	{
+		version: 13,
+		name: 'create_mapping_profiles',
+		up: (db) => {
+			// Saved CSV/Excel/JSON import mappings
+			db.run(MAPPING_PROFILES_DDL);
+		},
+	},
+	{
+		version: 14,
+		name: 'create_enricher_settings',
+		up: (db) => {
+			// Per-source-type import enricher switches
+			db.run(ENRICHER_SETTINGS_DDL);
+		},
+	},
+];
+
+// ---------------------------------------------------------------------------
+// Runner
+// ---------------------------------------------------------------------------
+
+/**
+ * Read applied migrations from schema_migrations, ordered by version.
+ * Returns an empty array when the tracking table does not exist yet.
+ */
+export function getAppliedMigrations(db: Database): AppliedMigration[] {
+	const exists = db.exec("SELECT name FROM sqlite_master WHERE type = 'table' AND name = 'schema_migrations'");
+	if (!exists[0]?.values.length) return [];
+	return db
+		.prepare<AppliedMigration>('SELECT version, name, applied_at FROM schema_migrations ORDER BY version')
+		.all();
+}
+
+/**
+ * Apply all pending migrations in version order.
+ *
+ * Each migration runs in its own transaction together with the
+ * schema_migrations INSERT, so a failure leaves no partial state for that
+ * migration and it is retried on the next initialization.
+ *
+ * @param db - Initialized Database (fresh or hydrated)
+ * @param migrations - Migration list (defaults to MIGRATIONS; overridable for tests)
+ * @returns Versions applied during this run (empty when already up to date)
+ * @throws MigrationError if a migration fails or the list is misordered
+ */
+export function runMigrations(db: Database, migrations: readonly Migration[] = MIGRATIONS): number[] {
+	db.run(SCHEMA_MIGRATIONS_DDL);
+
+	// Guard against misordered or duplicate versions before touching anything
+	for (let i = 1; i < migrations.length; i++) {
+		const prev = migrations[i - 1]!;
+		const curr = migrations[i]!;
+		if (curr.version <= prev.version) {
+			throw new MigrationError(
+				curr.version,
+				curr.name,
+				`version must be greater than ${prev.version} (${prev.name})`,
+			);
+		}
+	}
+
+	const applied = new Set(getAppliedMigrations(db).map((m) => m.version));
+	const appliedNow: number[] = [];
+
+	for (const migration of migrations) {
+		if (applied.has(migration.version)) continue;
+
+		try {
+			db.transaction(() => {
+				migration.up(db);
+				db.run('INSERT INTO schema_migrations (version, name) VALUES (?, ?)', [
+					migration.version,
+					migration.name,
+				]);
+			})();
+		} catch (err) {
+			throw new MigrationError(migration.version, migration.name, err);
+		}
+
+		appliedNow.push(migration.version);
+	}
+
+	return appliedNow;
+}
@@ -255,11 +255,11 @@ export function deleteCard(db: Database, id: string): void {
 // ---------------------------------------------------------------------------
 
 /**
- * Return all non-deleted cards, with optional filters.
- * Filters are applied with AND logic. All filter values are parameterized.
- * Allowed filters: folder, status, card_type, source, limit.
+ * Build the parameterized SELECT used by listCards().
+ * Shared with the Worker cursor registry so streamed card:list pages
+ * use exactly the same filters and ordering as the one-shot response.
  */
-export function listCards(db: Database, options?: CardListOptions): Card[] {
+export function buildListCardsQuery(options?: CardListOptions): { sql: string; params: unknown[] } {
 	const conditions: string[] = ['deleted_at IS NULL'];
 	const params: unknown[] = [];
 
@@ -287,6 +287,16 @@ export function listCards(db: Database, options?: CardListOptions): Card[] {
 		params.push(options.limit);
 	}
 
+	return { sql, params };
+}
+
+/**
+ * Return all non-deleted cards, with optional filters.
+ * Filters are applied with AND logic. All filter values are parameterized.
+ * Allowed filters: folder, status, card_type, source, limit.
+ */
+export function listCards(db: Database, options?: CardListOptions): Card[] {
+	const { sql, params } = buildListCardsQuery(options);
 	const result = db.exec(sql, params.length > 0 ? (params as import('sql.js').BindParams) : undefined);
 	return execRowsToCards(result);
 }
@@ -1,5 +1,5 @@
 // Isometry v5 — Connection CRUD Operations
-// Implements CONN-01 through CONN-05.
+// Implements CONN-01 through CONN-06.
 //
 // Pattern: Pass Database instance to every function (no module-level state).
 // Use db.run() for mutations (INSERT/UPDATE/DELETE) and db.exec() for SELECT.
@@ -7,7 +7,7 @@
 
 import type { Database } from '../Database';
 import { execRowsToConnections } from './helpers';
-import type { Connection, ConnectionDirection, ConnectionInput } from './types';
+import type { Connection, ConnectionDirection, ConnectionInput, ConnectionUpdate } from './types';
 
 // ---------------------------------------------------------------------------
 // CONN-01: Create a connection between two cards
@@ -79,6 +79,55 @@ export function getConnections(
 	return execRowsToConnections(db.exec(sql, params as import('sql.js').BindParams));
 }
 
+// ---------------------------------------------------------------------------
+// CONN-06: Update a connection's label, weight, or via card
+// ---------------------------------------------------------------------------
+
+/**
+ * Return a single connection by ID, or null if it does not exist.
+ */
+export function getConnection(db: Database, id: string): Connection | null {
+	return execRowsToConnections(db.exec('SELECT * FROM connections WHERE id = ?', [id]))[0] ?? null;
+}
+
+/**
+ * Update label, weight and/or via_card_id on an existing connection in place,
+ * preserving its id and created_at. Returns the updated Connection.
+ *
+ * Only fields present in `updates` are written. Throws if the connection does
+ * not exist. Schema constraints still apply: weight must be within [0, 1],
+ * via_card_id must reference an existing card, and the UNIQUE
+ * (source_id, target_id, via_card_id, label) combination must stay unique.
+ */
+export function updateConnection(db: Database, id: string, updates: ConnectionUpdate): Connection {
+	if (!getConnection(db, id)) {
+		throw new Error(`updateConnection: connection ${id} not found`);
+	}
+
+	const sets: string[] = [];
+	const params: unknown[] = [];
+
+	if (updates.label !== undefined) {
+		sets.push('label = ?');
+		params.push(updates.label);
+	}
+	if (updates.weight !== undefined) {
+		sets.push('weight = ?');
+		params.push(updates.weight);
+	}
+	if (updates.via_card_id !== undefined) {
+		sets.push('via_card_id = ?');
+		params.push(updates.via_card_id);
+	}
+
+	if (sets.length > 0) {
+		params.push(id);
+		db.run(`UPDATE connections SET ${sets.join(', ')} WHERE id = ?`, params as import('sql.js').BindParams);
+	}
+
+	return getConnection(db, id)!;
+}
+
 // ---------------------------------------------------------------------------
 // CONN-04: Delete a connection (hard delete)
 // ---------------------------------------------------------------------------
@@ -0,0 +1,81 @@
+// Isometry v5 — Dataset Files Query Module
+// File manifest of directory datasets, persisted in dataset_files.
+//
+// Pattern: Pass Database instance to every function (no module-level state).
+// Each row records one file a directory import read — path, size, mtime and
+// content hash — plus the source_ids of the cards parsed from it, so an
+// incremental re-import can tell which cards a removed or renamed file owned.
+// The manifest is always replaced as a whole after a committed re-import.
+
+import type { ManifestEntry } from '../../etl/FileManifest';
+import type { Database } from '../Database';
+
+// ---------------------------------------------------------------------------
+// DDL
+// ---------------------------------------------------------------------------
+
+/**
+ * DDL for the dataset_files table. Mirrors schema.sql; applied by the
+ * create_dataset_files migration for checkpoints that predate it.
+ */
+export const DATASET_FILES_DDL = `CREATE TABLE IF NOT EXISTS dataset_files (
+  dataset_id TEXT NOT NULL REFERENCES datasets(id) ON DELETE CASCADE,
+  path TEXT NOT NULL,
+  size_bytes INTEGER NOT NULL,
+  mtime TEXT NOT NULL,
+  content_hash TEXT NOT NULL,
+  source_ids TEXT NOT NULL DEFAULT '[]',
+  PRIMARY KEY (dataset_id, path)
+)`;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+export interface DatasetFile extends ManifestEntry {
+	/** source_ids of the cards parsed from this file */
+	source_ids: string[];
+}
+
+// ---------------------------------------------------------------------------
+// Read / Write
+// ---------------------------------------------------------------------------
+
+/**
+ * Stored manifest of a dataset, ordered by path. Empty when the dataset has
+ * never been re-imported from a directory.
+ */
+export function getDatasetFiles(db: Database, datasetId: string): DatasetFile[] {
+	return db
+		.prepare<{ path: string; size_bytes: number; mtime: string; content_hash: string; source_ids: string }>(
+			`SELECT path, size_bytes, mtime, content_hash, source_ids
+       FROM dataset_files
+       WHERE dataset_id = ?
+       ORDER BY path`,
+		)
+		.all(datasetId)
+		.map((row) => ({
+			path: row.path,
+			size: row.size_bytes,
+			mtime: row.mtime,
+			hash: row.content_hash,
+			source_ids: JSON.parse(row.source_ids) as string[],
+		}));
+}
+
+/**
+ * Replace the stored manifest of a dataset.
+ */
+export function replaceDatasetFiles(db: Database, datasetId: string, files: readonly DatasetFile[]): void {
+	db.transaction(() => {
+		db.run('DELETE FROM dataset_files WHERE dataset_id = ?', [datasetId]);
+		const insert = db.prepare<never>(
+			`INSERT INTO dataset_files (dataset_id, path, size_bytes, mtime, content_hash, source_ids)
+       VALUES (?, ?, ?, ?, ?, ?)`,
+		);
+		for (const file of files) {
+			insert.run(datasetId, file.path, file.size, file.mtime, file.hash, JSON.stringify(file.source_ids));
+		}
+		insert.free();
+	})();
+}
@@ -0,0 +1,68 @@
+// Isometry v5 — Enricher Settings Query Module
+// Per-source-type on/off switches for import enrichers, persisted in enricher_settings.
+//
+// Pattern: Pass Database instance to every function (no module-level state).
+// source_type is a SourceType or '*' for all sources; enrichers without a
+// matching row fall back to their defaultEnabled (see isEnricherEnabled).
+
+import type { EnricherSetting } from '../../etl/enrichment/types';
+import type { Database } from '../Database';
+
+// ---------------------------------------------------------------------------
+// DDL
+// ---------------------------------------------------------------------------
+
+/**
+ * DDL for the enricher_settings table. Mirrors schema.sql; applied by the
+ * create_enricher_settings migration for checkpoints that predate it.
+ */
+export const ENRICHER_SETTINGS_DDL = `CREATE TABLE IF NOT EXISTS enricher_settings (
+  enricher_id TEXT NOT NULL,
+  source_type TEXT NOT NULL,
+  enabled INTEGER NOT NULL,
+  updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+  PRIMARY KEY (enricher_id, source_type)
+)`;
+
+interface EnricherSettingRow {
+	enricher_id: string;
+	source_type: string;
+	enabled: number;
+}
+
+// ---------------------------------------------------------------------------
+// Read
+// ---------------------------------------------------------------------------
+
+/**
+ * List all saved enricher settings ordered by enricher and source type.
+ */
+export function listEnricherSettings(db: Database): EnricherSetting[] {
+	return db
+		.prepare<EnricherSettingRow>(
+			`SELECT enricher_id, source_type, enabled
+       FROM enricher_settings
+       ORDER BY enricher_id, source_type`,
+		)
+		.all()
+		.map((row) => ({ enricher_id: row.enricher_id, source_type: row.source_type, enabled: row.enabled === 1 }));
+}
+
+// ---------------------------------------------------------------------------
+// Write
+// ---------------------------------------------------------------------------
+
+/**
+ * Switch an enricher on or off for a source type ('*' for all sources).
+ * Replaces any earlier setting for the same pair.
+ */
+export function setEnricherSetting(db: Database, setting: EnricherSetting): void {
+	db.run(
+		`INSERT INTO enricher_settings (enricher_id, source_type, enabled, updated_at)
+     VALUES (?, ?, ?, ?)
+     ON CONFLICT(enricher_id, source_type) DO UPDATE SET
+       enabled = excluded.enabled,
+       updated_at = excluded.updated_at`,
+		[setting.enricher_id, setting.source_type, setting.enabled ? 1 : 0, new Date().toISOString()],
+	);
+}
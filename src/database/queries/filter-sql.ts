@@ -0,0 +1,428 @@
+// Isometry v5 — Filter SQL Compilation
+// Pure filter condition / filter state → SQL WHERE clause compilation, shared by
+// the query layer (searchCards, saved searches) and FilterProvider.
+//
+// Pattern: pure functions, no module-level state. Every field and operator is
+// validated against the allowlist before it is interpolated; values always go
+// into params. Custom property and formula fields compile through fieldExpr().
+
+import { validateFilterField, validateOperator } from '../../providers/allowlist';
+import { fieldExpr } from '../../providers/properties';
+import { isRelativeDateExpr, resolveRelativeDate } from '../../providers/relative-dates';
+import { parseSearchQuery } from '../../providers/search-query';
+import type {
+	CompiledFilter,
+	Filter,
+	FilterField,
+	FilterGroup,
+	FilterNode,
+	FilterOperator,
+	MembershipFilter,
+	RangeFilter,
+	RelativeDateExpr,
+} from '../../providers/types';
+
+/** Maximum nesting depth of a filter tree (root group = depth 1). */
+export const MAX_FILTER_TREE_DEPTH = 8;
+
+/**
+ * Serialized filter state — the FilterProvider.toJSON() shape, also stored by
+ * saved searches and story slides.
+ */
+export interface FilterState {
+	filters: Filter[];
+	searchQuery: string | null;
+	/** Phase 24 — axis filter values per field. Optional for backward compat. */
+	axisFilters?: Record<string, string[]>;
+	/** Phase 66 — range filter min/max pairs per field. Optional for backward compat. */
+	rangeFilters?: Record<string, RangeFilter>;
+	/** Symbolic relative date ranges per time field. Optional for backward compat. */
+	relativeDateFilters?: Record<string, RelativeDateExpr>;
+	/** Phase 138 — multi-field OR-semantics membership filter. Optional for backward compat. */
+	membershipFilter?: MembershipFilter | null;
+	/** Boolean filter tree (AND/OR/NOT groups). Optional for backward compat. */
+	filterTree?: FilterGroup | null;
+}
+
+// ---------------------------------------------------------------------------
+// Filter conditions
+// ---------------------------------------------------------------------------
+
+/**
+ * Compile a list of filter conditions to SQL clauses (AND-joined by the caller).
+ * Validates every field and operator — safe for untrusted filter state.
+ * Shared by FilterProvider.compile() and searchCards() (search query filters).
+ *
+ * @throws {Error} "SQL safety violation: ..." for any invalid field or operator
+ */
+export function compileFilterList(filters: readonly Filter[]): { clauses: string[]; params: unknown[] } {
+	const clauses: string[] = [];
+	const params: unknown[] = [];
+	for (const filter of filters) {
+		validateFilterField(filter.field as string);
+		validateOperator(filter.operator as string);
+
+		const { clause, filterParams } = compileOperator(filter.field, filter.operator, filter.value);
+		clauses.push(clause);
+		params.push(...filterParams);
+	}
+	return { clauses, params };
+}
+
+/**
+ * Compile a boolean filter tree to a single parenthesized SQL clause.
+ * Validates every condition — safe for untrusted (JSON-restored) trees.
+ *
+ * Empty groups compile to nothing (null), so an unfinished builder group
+ * never filters anything out. Negated groups compile to `NOT IFNULL((...), 0)`:
+ * a comparison against NULL counts as false, so `NOT folder = 'archive'` also
+ * matches cards without a folder instead of dropping them via SQL's
+ * three-valued logic.
+ *
+ * @throws {Error} "SQL safety violation: ..." for any invalid field or operator
+ */
+export function compileFilterTree(group: FilterGroup): { clause: string; params: unknown[] } | null {
+	const parts: string[] = [];
+	const params: unknown[] = [];
+	for (const child of group.children) {
+		if (isGroupNode(child)) {
+			const compiled = compileFilterTree(child);
+			if (compiled === null) continue;
+			parts.push(compiled.clause);
+			params.push(...compiled.params);
+		} else {
+			const compiled = compileFilterList([child]);
+			parts.push(compiled.clauses[0]!);
+			params.push(...compiled.params);
+		}
+	}
+	if (parts.length === 0) return null;
+
+	const joined = `(${parts.join(group.combinator === 'or' ? ' OR ' : ' AND ')})`;
+	return { clause: group.negate ? `NOT IFNULL(${joined}, 0)` : joined, params };
+}
+
+/**
+ * Compile a single filter condition to a SQL clause + params pair.
+ * Field and operator have already been validated by compileFilterList().
+ */
+function compileOperator(
+	rawField: FilterField,
+	operator: FilterOperator,
+	value: unknown,
+): { clause: string; filterParams: unknown[] } {
+	const field = fieldExpr(rawField);
+	switch (operator) {
+		case 'eq':
+			return { clause: `${field} = ?`, filterParams: [value] };
+
+		case 'neq':
+			return { clause: `${field} != ?`, filterParams: [value] };
+
+		case 'gt':
+			return { clause: `${field} > ?`, filterParams: [value] };
+
+		case 'gte':
+			return { clause: `${field} >= ?`, filterParams: [value] };
+
+		case 'lt':
+			return { clause: `${field} < ?`, filterParams: [value] };
+
+		case 'lte':
+			return { clause: `${field} <= ?`, filterParams: [value] };
+
+		case 'contains':
+			return { clause: `${field} LIKE ?`, filterParams: [`%${value as string}%`] };
+
+		case 'startsWith':
+			return { clause: `${field} LIKE ?`, filterParams: [`${value as string}%`] };
+
+		case 'in': {
+			const values = value as unknown[];
+			const placeholders = values.map(() => '?').join(', ');
+			return { clause: `${field} IN (${placeholders})`, filterParams: values };
+		}
+
+		case 'isNull':
+			return { clause: `${field} IS NULL`, filterParams: [] };
+
+		case 'isNotNull':
+			return { clause: `${field} IS NOT NULL`, filterParams: [] };
+
+		default: {
+			// TypeScript should make this unreachable after validateOperator
+			const _exhaustive: never = operator;
+			throw new Error(`SQL safety violation: unhandled operator "${_exhaustive as string}"`);
+		}
+	}
+}
+
+/** Whether a filter tree node is a nested group (vs. a single condition). */
+export function isGroupNode(node: FilterNode): node is FilterGroup {
+	return (node as Partial<FilterGroup>).kind === 'group';
+}
+
+/** Validate every condition of a tree against the allowlist (no partial state). */
+export function validateFilterTree(group: FilterGroup): void {
+	for (const child of group.children) {
+		if (isGroupNode(child)) {
+			validateFilterTree(child);
+		} else {
+			validateFilterField(child.field as string);
+			validateOperator(child.operator as string);
+		}
+	}
+}
+
+/**
+ * Structural type guard for a filter tree. Rejects unknown combinators,
+ * malformed conditions and nesting deeper than MAX_FILTER_TREE_DEPTH.
+ */
+export function isFilterGroup(value: unknown, depth = 1): value is FilterGroup {
+	if (depth > MAX_FILTER_TREE_DEPTH) return false;
+	if (typeof value !== 'object' || value === null || Array.isArray(value)) return false;
+	const g = value as Record<string, unknown>;
+	if (g['kind'] !== 'group') return false;
+	if (g['combinator'] !== 'and' && g['combinator'] !== 'or') return false;
+	if (typeof g['negate'] !== 'boolean') return false;
+	if (!Array.isArray(g['children'])) return false;
+
+	for (const child of g['children'] as unknown[]) {
+		if (typeof child !== 'object' || child === null) return false;
+		if ((child as Record<string, unknown>)['kind'] === 'group') {
+			if (!isFilterGroup(child, depth + 1)) return false;
+		} else if (!isFilterCondition(child)) {
+			return false;
+		}
+	}
+	return true;
+}
+
+function isFilterCondition(value: unknown): value is Filter {
+	if (typeof value !== 'object' || value === null) return false;
+	const f = value as Record<string, unknown>;
+	return typeof f['field'] === 'string' && typeof f['operator'] === 'string' && 'value' in f;
+}
+
+// ---------------------------------------------------------------------------
+// Type guard for state restoration
+// ---------------------------------------------------------------------------
+
+export function isFilterState(value: unknown): value is FilterState {
+	if (typeof value !== 'object' || value === null) return false;
+	const obj = value as Record<string, unknown>;
+	if (!Array.isArray(obj['filters'])) return false;
+	if (obj['searchQuery'] !== null && typeof obj['searchQuery'] !== 'string') return false;
+
+	for (const item of obj['filters'] as unknown[]) {
+		if (!isFilterCondition(item)) return false;
+	}
+
+	// Phase 24: validate optional axisFilters — if present, must be Record<string, string[]>
+	if ('axisFilters' in obj && obj['axisFilters'] !== undefined) {
+		const af = obj['axisFilters'];
+		if (typeof af !== 'object' || af === null || Array.isArray(af)) return false;
+		for (const values of Object.values(af as Record<string, unknown>)) {
+			if (!Array.isArray(values)) return false;
+			for (const v of values as unknown[]) {
+				if (typeof v !== 'string') return false;
+			}
+		}
+	}
+
+	// Phase 66: validate optional rangeFilters — if present, must be Record<string, {min, max}>
+	if ('rangeFilters' in obj && obj['rangeFilters'] !== undefined) {
+		const rf = obj['rangeFilters'];
+		if (typeof rf !== 'object' || rf === null || Array.isArray(rf)) return false;
+		for (const entry of Object.values(rf as Record<string, unknown>)) {
+			if (typeof entry !== 'object' || entry === null || Array.isArray(entry)) return false;
+			const e = entry as Record<string, unknown>;
+			if (!('min' in e) || !('max' in e)) return false;
+		}
+	}
+
+	// Validate optional relativeDateFilters — if present, must be Record<string, RelativeDateExpr>
+	if ('relativeDateFilters' in obj && obj['relativeDateFilters'] !== undefined) {
+		const rd = obj['relativeDateFilters'];
+		if (typeof rd !== 'object' || rd === null || Array.isArray(rd)) return false;
+		for (const expr of Object.values(rd as Record<string, unknown>)) {
+			if (!isRelativeDateExpr(expr)) return false;
+		}
+	}
+
+	// Phase 138: validate optional membershipFilter — if present (and non-null), must have fields (string[]), min, max
+	if ('membershipFilter' in obj && obj['membershipFilter'] !== undefined && obj['membershipFilter'] !== null) {
+		const mf = obj['membershipFilter'];
+		if (typeof mf !== 'object' || mf === null || Array.isArray(mf)) return false;
+		const m = mf as Record<string, unknown>;
+		if (!Array.isArray(m['fields'])) return false;
+		for (const f of m['fields'] as unknown[]) {
+			if (typeof f !== 'string') return false;
+		}
+		if (!('min' in m) || !('max' in m)) return false;
+	}
+
+	// Filter tree: validate optional filterTree — if present (and non-null), must be a well-formed group
+	if ('filterTree' in obj && obj['filterTree'] !== undefined && obj['filterTree'] !== null) {
+		if (!isFilterGroup(obj['filterTree'])) return false;
+	}
+
+	return true;
+}
+
+// ---------------------------------------------------------------------------
+// Filter state
+// ---------------------------------------------------------------------------
+
+/**
+ * Compile a filter state to a SQL WHERE fragment.
+ *
+ * Returns `{ where, params }` where:
+ *   - `where` always starts with `deleted_at IS NULL`
+ *   - All user values are in `params` (never interpolated into `where`)
+ *   - Field names are interpolated only after allowlist validation
+ *   - Custom property fields (prop_<key>) compile to card_properties subqueries via fieldExpr()
+ *
+ * Validates every field and operator — safe for JSON-restored state.
+ *
+ * @throws {Error} "SQL safety violation: ..." for any invalid field or operator
+ */
+export function compileFilterState(state: FilterState): CompiledFilter {
+	const clauses: string[] = ['deleted_at IS NULL'];
+	const params: unknown[] = [];
+
+	// compileFilterList validates at runtime — handles JSON-restored or otherwise untrusted state
+	const compiledFilters = compileFilterList(state.filters);
+	clauses.push(...compiledFilters.clauses);
+	params.push(...compiledFilters.params);
+
+	// Phase 24 — axis filters: compile after regular filters, before FTS
+	// Deterministic order: iterate insertion order of the record
+	for (const [field, values] of Object.entries(state.axisFilters ?? {})) {
+		if (values.length === 0) continue; // defensive: empty entries should not exist
+		// Runtime validation — guards JSON-restored state
+		validateFilterField(field);
+		const placeholders = values.map(() => '?').join(', ');
+		clauses.push(`${fieldExpr(field)} IN (${placeholders})`);
+		params.push(...values);
+	}
+
+	// Phase 66 — range filters: compile after axis filters, before FTS
+	for (const [field, range] of Object.entries(state.rangeFilters ?? {})) {
+		// Runtime validation — guards JSON-restored state
+		validateFilterField(field);
+		const expr = fieldExpr(field);
+		if (range.min !== null && range.min !== undefined) {
+			clauses.push(`${expr} >= ?`);
+			params.push(range.min);
+		}
+		if (range.max !== null && range.max !== undefined) {
+			clauses.push(`${expr} <= ?`);
+			params.push(range.max);
+		}
+	}
+
+	// Relative date filters: resolved against today, compile after range filters.
+	// Local-midnight instants (start inclusive, end exclusive) — see relative-dates.ts
+	for (const [field, relative] of Object.entries(state.relativeDateFilters ?? {})) {
+		// Runtime validation — guards JSON-restored state
+		validateFilterField(field);
+		const expr = fieldExpr(field);
+		const { start, end } = resolveRelativeDate(relative);
+		if (start !== null) {
+			clauses.push(`${expr} >= ?`);
+			params.push(start);
+		}
+		clauses.push(`${expr} < ?`);
+		params.push(end);
+		if (relative.kind === 'overdue') clauses.push('completed_at IS NULL');
+	}
+
+	// Phase 138 — membership filter: OR-semantics across multiple fields, compile after range filters
+	const membership = state.membershipFilter ?? null;
+	if (membership !== null) {
+		const orParts: string[] = [];
+		const orParams: unknown[] = [];
+		for (const field of membership.fields) {
+			validateFilterField(field);
+			const expr = fieldExpr(field);
+			const conditions: string[] = [];
+			if (membership.min !== null && membership.min !== undefined) {
+				conditions.push(`${expr} >= ?`);
+				orParams.push(membership.min);
+			}
+			if (membership.max !== null && membership.max !== undefined) {
+				conditions.push(`${expr} <= ?`);
+				orParams.push(membership.max);
+			}
+			if (conditions.length > 0) {
+				orParts.push(`(${conditions.join(' AND ')})`);
+			}
+		}
+		if (orParts.length > 0) {
+			clauses.push(`(${orParts.join(' OR ')})`);
+			params.push(...orParams);
+		}
+	}
+
+	// Boolean filter tree: one parenthesized clause, compile after membership filter
+	if (state.filterTree) {
+		const compiledTree = compileFilterTree(state.filterTree);
+		if (compiledTree !== null) {
+			clauses.push(compiledTree.clause);
+			params.push(...compiledTree.params);
+		}
+	}
+
+	// FTS search — uses rowid (not id) per D-004 and Pitfall 5.
+	// Parsed with the search query language: bare terms become prefix matches,
+	// field clauses (status:done, due:<2026-01-01) become regular filter clauses.
+	if (state.searchQuery !== null && state.searchQuery !== '') {
+		const parsed = parseSearchQuery(state.searchQuery, { prefix: true });
+		if (parsed.fts) {
+			clauses.push('rowid IN (SELECT rowid FROM cards_fts WHERE cards_fts MATCH ?)');
+			params.push(parsed.fts);
+		}
+		const searchFilters = compileFilterList(parsed.filters);
+		clauses.push(...searchFilters.clauses);
+		params.push(...searchFilters.params);
+	}
+
+	return { where: clauses.join(' AND '), params };
+}
+
+/**
+ * Validate the filters, tree and relative date fields of a state against the
+ * allowlist, so a restore is all-or-nothing.
+ *
+ * @throws {Error} "SQL safety violation: ..." for any invalid field or operator
+ */
+export function validateFilterState(state: FilterState): void {
+	for (const f of state.filters) {
+		validateFilterField(f.field as string);
+		validateOperator(f.operator as string);
+	}
+	if (state.filterTree) validateFilterTree(state.filterTree);
+	for (const field of Object.keys(state.relativeDateFilters ?? {})) {
+		validateFilterField(field);
+	}
+}
+
+/**
+ * Parse and validate a serialized filter state (FilterProvider.toJSON()).
+ *
+ * @throws {Error} if `json` is not valid JSON, has an invalid shape, or uses invalid fields/operators
+ */
+export function parseFilterState(json: string): FilterState {
+	let parsed: unknown;
+	try {
+		parsed = JSON.parse(json);
+	} catch {
+		throw new Error(`[filter-sql] parseFilterState: invalid JSON — ${json.slice(0, 50)}`);
+	}
+	if (!isFilterState(parsed)) {
+		throw new Error('[filter-sql] parseFilterState: invalid state shape');
+	}
+	validateFilterState(parsed);
+	return parsed;
+}
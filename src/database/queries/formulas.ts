@@ -0,0 +1,162 @@
+// Isometry v5 — Formula Fields Query Module
+// Spreadsheet-style computed fields stored as named expressions.
+//
+// A formula is `name = expression` (e.g. `days_open = julianday(coalesce(completed_at, now)) - julianday(created_at)`),
+// either global (dataset_id NULL) or scoped to one dataset. The expression text
+// is stored verbatim; compilation to SQL happens in providers/formulas.ts and
+// is re-run whenever the set of valid fields changes, so a formula that stops
+// compiling (e.g. its property was deleted) is reported instead of dropped.
+//
+// Pattern: Pass Database instance to every function (no module-level state).
+
+import type { Database } from '../Database';
+
+// ---------------------------------------------------------------------------
+// DDL
+// ---------------------------------------------------------------------------
+
+/**
+ * DDL for the formulas table. Mirrors schema.sql; applied by the
+ * create_formulas migration for checkpoints that predate it.
+ * One definition per (name, dataset) — NULL dataset_id is the global default.
+ */
+export const FORMULAS_DDL = `CREATE TABLE IF NOT EXISTS formulas (
+  id TEXT PRIMARY KEY NOT NULL,
+  name TEXT NOT NULL,
+  label TEXT NOT NULL,
+  dataset_id TEXT REFERENCES datasets(id) ON DELETE CASCADE,
+  expression TEXT NOT NULL,
+  result_type TEXT NOT NULL CHECK (result_type IN ('number', 'text', 'date')),
+  created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+  updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_formulas_name_dataset ON formulas(name, IFNULL(dataset_id, ''));`;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+export type FormulaResultType = 'number' | 'text' | 'date';
+
+export interface FormulaDefinition {
+	id: string;
+	/** Identifier ([a-z][a-z0-9_]*); exposed as the fx_<name> field */
+	name: string;
+	/** Display label (defaults to the name) */
+	label: string;
+	/** Dataset the formula applies to; null applies to every dataset without its own definition */
+	dataset_id: string | null;
+	/** Formula source text, e.g. `julianday(now) - julianday(created_at)` */
+	expression: string;
+	result_type: FormulaResultType;
+	created_at: string;
+	updated_at: string;
+}
+
+export interface FormulaDefinitionInput {
+	name: string;
+	label?: string;
+	dataset_id?: string | null;
+	expression: string;
+	result_type: FormulaResultType;
+}
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+export const FORMULA_RESULT_TYPES: readonly FormulaResultType[] = ['number', 'text', 'date'];
+
+/** Valid formula name: lowercase identifier, max 48 chars (safe to inline in SQL). */
+export const FORMULA_NAME_RE = /^[a-z][a-z0-9_]{0,47}$/;
+
+// ---------------------------------------------------------------------------
+// Read
+// ---------------------------------------------------------------------------
+
+/**
+ * List all formula definitions ordered by name, global definitions first.
+ */
+export function listFormulas(db: Database): FormulaDefinition[] {
+	const stmt = db.prepare<FormulaDefinition>(
+		`SELECT id, name, label, dataset_id, expression, result_type, created_at, updated_at
+     FROM formulas ORDER BY name, dataset_id IS NOT NULL, dataset_id`,
+	);
+	const rows = stmt.all();
+	stmt.free();
+	return rows;
+}
+
+// ---------------------------------------------------------------------------
+// Write
+// ---------------------------------------------------------------------------
+
+/**
+ * Create or replace the formula `name` for a dataset (or globally when
+ * dataset_id is null/omitted). Redefining keeps the row id.
+ *
+ * Only the name, type and emptiness are checked here — expression validity is
+ * the caller's job (the worker handler compiles before saving).
+ *
+ * @throws {Error} on an invalid name, result type or empty expression
+ */
+export function defineFormula(db: Database, input: FormulaDefinitionInput): FormulaDefinition {
+	const name = input.name.trim();
+	if (!FORMULA_NAME_RE.test(name)) {
+		throw new Error(`Invalid formula name "${input.name}": use lowercase letters, digits and underscores`);
+	}
+	if (!FORMULA_RESULT_TYPES.includes(input.result_type)) {
+		throw new Error(`Invalid formula result type "${String(input.result_type)}"`);
+	}
+	const expression = input.expression.trim();
+	if (expression === '') throw new Error('Formula expression is required');
+
+	const label = input.label?.trim() || name;
+	const datasetId = input.dataset_id ?? null;
+	const now = new Date().toISOString();
+	const existing = listFormulas(db).find((f) => f.name === name && f.dataset_id === datasetId);
+
+	if (existing) {
+		db.run('UPDATE formulas SET label = ?, expression = ?, result_type = ?, updated_at = ? WHERE id = ?', [
+			label,
+			expression,
+			input.result_type,
+			now,
+			existing.id,
+		]);
+		return { ...existing, label, expression, result_type: input.result_type, updated_at: now };
+	}
+
+	const definition: FormulaDefinition = {
+		id: crypto.randomUUID(),
+		name,
+		label,
+		dataset_id: datasetId,
+		expression,
+		result_type: input.result_type,
+		created_at: now,
+		updated_at: now,
+	};
+	db.run(
+		`INSERT INTO formulas (id, name, label, dataset_id, expression, result_type, created_at, updated_at)
+     VALUES (?, ?, ?, ?, ?, ?, ?, ?)`,
+		[
+			definition.id,
+			definition.name,
+			definition.label,
+			definition.dataset_id,
+			definition.expression,
+			definition.result_type,
+			definition.created_at,
+			definition.updated_at,
+		],
+	);
+	return definition;
+}
+
+/**
+ * Delete a formula definition by id. No-op for unknown ids.
+ */
+export function deleteFormula(db: Database, id: string): void {
+	db.run('DELETE FROM formulas WHERE id = ?', [id]);
+}
@@ -0,0 +1,191 @@
+// Isometry v5 — Geocoding Query Module
+// Local place-name gazetteer used to place cards on the map from location_name.
+//
+// Pattern: Pass Database instance to every function (no module-level state).
+// Lookups are offline: geocode_places is seeded with a built-in list of major
+// cities by the create_geocode_places migration; callers may add their own
+// places (source = 'user'), which replace built-in rows of the same name.
+//
+// Names match case-insensitively (COLLATE NOCASE) after trimming. Cards with
+// explicit coordinates always win over a gazetteer match.
+
+import type { Database } from '../Database';
+
+// ---------------------------------------------------------------------------
+// DDL
+// ---------------------------------------------------------------------------
+
+/**
+ * DDL for the geocode_places table. Mirrors schema.sql; applied by the
+ * create_geocode_places migration for checkpoints that predate it.
+ */
+export const GEOCODE_PLACES_DDL = `CREATE TABLE IF NOT EXISTS geocode_places (
+  name TEXT PRIMARY KEY NOT NULL COLLATE NOCASE,
+  latitude REAL NOT NULL CHECK (latitude BETWEEN -90 AND 90),
+  longitude REAL NOT NULL CHECK (longitude BETWEEN -180 AND 180),
+  source TEXT NOT NULL DEFAULT 'builtin'
+)`;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+export interface GeocodePlace {
+	/** Place name as matched against cards.location_name (case-insensitive) */
+	name: string;
+	latitude: number;
+	longitude: number;
+	/** 'builtin' for seeded rows, 'user' for places added at runtime */
+	source: string;
+}
+
+// ---------------------------------------------------------------------------
+// Built-in gazetteer
+// ---------------------------------------------------------------------------
+
+/** Major cities seeded into geocode_places: [name, latitude, longitude]. */
+export const BUILTIN_PLACES: ReadonlyArray<readonly [string, number, number]> = [
+	['Amsterdam', 52.3676, 4.9041],
+	['Athens', 37.9838, 23.7275],
+	['Atlanta', 33.749, -84.388],
+	['Auckland', -36.8485, 174.7633],
+	['Austin', 30.2672, -97.7431],
+	['Bangkok', 13.7563, 100.5018],
+	['Barcelona', 41.3874, 2.1686],
+	['Beijing', 39.9042, 116.4074],
+	['Berlin', 52.52, 13.405],
+	['Bogotá', 4.711, -74.0721],
+	['Boston', 42.3601, -71.0589],
+	['Brussels', 50.8503, 4.3517],
+	['Buenos Aires', -34.6037, -58.3816],
+	['Cairo', 30.0444, 31.2357],
+	['Cape Town', -33.9249, 18.4241],
+	['Chicago', 41.8781, -87.6298],
+	['Copenhagen', 55.6761, 12.5683],
+	['Delhi', 28.7041, 77.1025],
+	['Denver', 39.7392, -104.9903],
+	['Dubai', 25.2048, 55.2708],
+	['Dublin', 53.3498, -6.2603],
+	['Edinburgh', 55.9533, -3.1883],
+	['Helsinki', 60.1699, 24.9384],
+	['Hong Kong', 22.3193, 114.1694],
+	['Istanbul', 41.0082, 28.9784],
+	['Jakarta', -6.2088, 106.8456],
+	['Johannesburg', -26.2041, 28.0473],
+	['Lagos', 6.5244, 3.3792],
+	['Lima', -12.0464, -77.0428],
+	['Lisbon', 38.7223, -9.1393],
+	['London', 51.5072, -0.1276],
+	['Los Angeles', 34.0522, -118.2437],
+	['Madrid', 40.4168, -3.7038],
+	['Melbourne', -37.8136, 144.9631],
+	['Mexico City', 19.4326, -99.1332],
+	['Miami', 25.7617, -80.1918],
+	['Milan', 45.4642, 9.19],
+	['Montreal', 45.5019, -73.5674],
+	['Moscow', 55.7558, 37.6173],
+	['Mumbai', 19.076, 72.8777],
+	['Munich', 48.1351, 11.582],
+	['Nairobi', -1.2921, 36.8219],
+	['New York', 40.7128, -74.006],
+	['Oslo', 59.9139, 10.7522],
+	['Paris', 48.8566, 2.3522],
+	['Prague', 50.0755, 14.4378],
+	['Rio de Janeiro', -22.9068, -43.1729],
+	['Rome', 41.9028, 12.4964],
+	['San Francisco', 37.7749, -122.4194],
+	['Santiago', -33.4489, -70.6693],
+	['São Paulo', -23.5558, -46.6396],
+	['Seattle', 47.6062, -122.3321],
+	['Seoul', 37.5665, 126.978],
+	['Shanghai', 31.2304, 121.4737],
+	['Singapore', 1.3521, 103.8198],
+	['Stockholm', 59.3293, 18.0686],
+	['Sydney', -33.8688, 151.2093],
+	['Taipei', 25.033, 121.5654],
+	['Tokyo', 35.6762, 139.6503],
+	['Toronto', 43.6532, -79.3832],
+	['Vancouver', 49.2827, -123.1207],
+	['Vienna', 48.2082, 16.3738],
+	['Warsaw', 52.2297, 21.0122],
+	['Washington', 38.9072, -77.0369],
+	['Zürich', 47.3769, 8.5417],
+];
+
+/**
+ * Seed the built-in gazetteer. Existing rows (including user overrides) are
+ * left untouched, so this is safe to re-run.
+ */
+export function seedGeocodePlaces(db: Database): void {
+	for (const [name, latitude, longitude] of BUILTIN_PLACES) {
+		db.run("INSERT OR IGNORE INTO geocode_places (name, latitude, longitude, source) VALUES (?, ?, ?, 'builtin')", [
+			name,
+			latitude,
+			longitude,
+		]);
+	}
+}
+
+// ---------------------------------------------------------------------------
+// Read / write
+// ---------------------------------------------------------------------------
+
+/**
+ * Look up a place by name (case-insensitive, trimmed).
+ * Returns null when the gazetteer has no match.
+ */
+export function lookupPlace(db: Database, name: string): GeocodePlace | null {
+	const trimmed = name.trim();
+	if (!trimmed) return null;
+	const stmt = db.prepare<GeocodePlace>(
+		'SELECT name, latitude, longitude, source FROM geocode_places WHERE name = ?',
+	);
+	const row = stmt.all(trimmed)[0];
+	stmt.free();
+	return row ?? null;
+}
+
+/**
+ * Add or replace a place. User rows take precedence over built-in rows of the
+ * same name because they replace them.
+ *
+ * @throws Error when the name is empty or the coordinates are out of range
+ */
+export function upsertPlace(db: Database, name: string, latitude: number, longitude: number): void {
+	const trimmed = name.trim();
+	if (!trimmed) throw new Error('Place name must not be empty');
+	if (!(latitude >= -90 && latitude <= 90) || !(longitude >= -180 && longitude <= 180)) {
+		throw new Error(`Coordinates out of range: ${latitude}, ${longitude}`);
+	}
+	db.run("INSERT OR REPLACE INTO geocode_places (name, latitude, longitude, source) VALUES (?, ?, ?, 'user')", [
+		trimmed,
+		latitude,
+		longitude,
+	]);
+}
+
+/**
+ * Write gazetteer coordinates into cards that have a location_name but no
+ * coordinates. Cards with explicit latitude/longitude are never overwritten.
+ * Filled cards get a fresh modified_at, like any other card mutation.
+ *
+ * @param cardIds - Restrict to these cards; omit to fill every card
+ * @returns Number of cards updated
+ */
+export function fillCoordinatesFromPlaces(db: Database, cardIds?: readonly string[]): number {
+	if (cardIds && cardIds.length === 0) return 0;
+	// Card ids travel as one JSON parameter (json_each), like map:cards
+	const scope = cardIds ? ' AND id IN (SELECT value FROM json_each(?))' : '';
+	const sql =
+		'UPDATE cards SET' +
+		' latitude = (SELECT g.latitude FROM geocode_places g WHERE g.name = TRIM(cards.location_name)),' +
+		' longitude = (SELECT g.longitude FROM geocode_places g WHERE g.name = TRIM(cards.location_name)),' +
+		' modified_at = ?' +
+		' WHERE deleted_at IS NULL AND latitude IS NULL AND longitude IS NULL' +
+		' AND EXISTS (SELECT 1 FROM geocode_places g WHERE g.name = TRIM(cards.location_name))' +
+		scope;
+	const now = new Date().toISOString();
+	db.run(sql, cardIds ? [now, JSON.stringify(cardIds)] : [now]);
+	const changes = db.exec('SELECT changes() AS n')[0]?.values[0]?.[0];
+	return typeof changes === 'number' ? changes : 0;
+}
@@ -0,0 +1,145 @@
+// Isometry v5 — Mapping Profiles Query Module
+// Named import mapping profiles for CSV, Excel and JSON, persisted in mapping_profiles.
+//
+// Pattern: Pass Database instance to every function (no module-level state).
+// `rules` is the MappingRules object as JSON; `headers` the normalized header
+// row the profile was made for and `fingerprint` its headerFingerprint(), so a
+// new import can be matched against saved profiles without re-deriving either.
+
+import {
+	headerFingerprint,
+	type MappingProfile,
+	type MappingRules,
+	normalizeHeaders,
+	validateMappingRules,
+} from '../../etl/MappingProfile';
+import type { Database } from '../Database';
+
+// ---------------------------------------------------------------------------
+// DDL
+// ---------------------------------------------------------------------------
+
+/**
+ * DDL for the mapping_profiles table. Mirrors schema.sql; applied by the
+ * create_mapping_profiles migration for checkpoints that predate it.
+ */
+export const MAPPING_PROFILES_DDL = `CREATE TABLE IF NOT EXISTS mapping_profiles (
+  id TEXT PRIMARY KEY NOT NULL,
+  name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+  fingerprint TEXT NOT NULL,
+  headers TEXT NOT NULL DEFAULT '[]',
+  rules TEXT NOT NULL,
+  created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+  updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+)`;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+export interface MappingProfileInput {
+	name: string;
+	/** Header row of the export the profile is made for (normalized on save) */
+	headers: string[];
+	rules: MappingRules;
+}
+
+interface MappingProfileRow {
+	id: string;
+	name: string;
+	fingerprint: string;
+	headers: string;
+	rules: string;
+	created_at: string;
+	updated_at: string;
+}
+
+// ---------------------------------------------------------------------------
+// Read
+// ---------------------------------------------------------------------------
+
+/**
+ * List all mapping profiles ordered by name.
+ */
+export function listMappingProfiles(db: Database): MappingProfile[] {
+	return db
+		.prepare<MappingProfileRow>(
+			`SELECT id, name, fingerprint, headers, rules, created_at, updated_at
+       FROM mapping_profiles
+       ORDER BY name COLLATE NOCASE`,
+		)
+		.all()
+		.map(toProfile);
+}
+
+/**
+ * Get a mapping profile by id, or null if it does not exist.
+ */
+export function getMappingProfile(db: Database, id: string): MappingProfile | null {
+	const [row] = db
+		.prepare<MappingProfileRow>(
+			`SELECT id, name, fingerprint, headers, rules, created_at, updated_at
+       FROM mapping_profiles
+       WHERE id = ?`,
+		)
+		.all(id);
+	return row ? toProfile(row) : null;
+}
+
+function toProfile(row: MappingProfileRow): MappingProfile {
+	return {
+		id: row.id,
+		name: row.name,
+		headers: JSON.parse(row.headers) as string[],
+		fingerprint: row.fingerprint,
+		rules: JSON.parse(row.rules) as MappingRules,
+		created_at: row.created_at,
+		updated_at: row.updated_at,
+	};
+}
+
+// ---------------------------------------------------------------------------
+// Write
+// ---------------------------------------------------------------------------
+
+/**
+ * Save a mapping profile under `name`. Saving an existing name
+ * (case-insensitive) replaces its headers and rules and keeps its id, so
+ * cards keyed by the profile's source_id column keep deduplicating.
+ *
+ * @throws {Error} if the name is empty or the rules are invalid
+ */
+export function saveMappingProfile(db: Database, input: MappingProfileInput): MappingProfile {
+	const trimmed = input.name.trim();
+	if (trimmed === '') throw new Error('Mapping profile name is required');
+	validateMappingRules(input.rules);
+
+	const now = new Date().toISOString();
+	db.run(
+		`INSERT INTO mapping_profiles (id, name, fingerprint, headers, rules, created_at, updated_at)
+     VALUES (?, ?, ?, ?, ?, ?, ?)
+     ON CONFLICT(name) DO UPDATE SET
+       fingerprint = excluded.fingerprint,
+       headers = excluded.headers,
+       rules = excluded.rules,
+       updated_at = excluded.updated_at`,
+		[
+			crypto.randomUUID(),
+			trimmed,
+			headerFingerprint(input.headers),
+			JSON.stringify(normalizeHeaders(input.headers)),
+			JSON.stringify(input.rules),
+			now,
+			now,
+		],
+	);
+
+	return listMappingProfiles(db).find((p) => p.name.toLowerCase() === trimmed.toLowerCase())!;
+}
+
+/**
+ * Delete a mapping profile. No-op for unknown ids.
+ */
+export function deleteMappingProfile(db: Database, id: string): void {
+	db.run('DELETE FROM mapping_profiles WHERE id = ?', [id]);
+}
@@ -0,0 +1,294 @@
+// Isometry v5 — Custom Card Properties Query Module
+// Typed, user-defined card fields stored as EAV rows in card_properties.
+//
+// The cards table is a fixed column contract. Anything beyond it (extra
+// CSV/Excel/JSON columns, user-added fields) lives here:
+//   property_definitions — one row per field (key, label, type, enum values)
+//   card_properties      — one row per (card, field) with a typed value
+//
+// Pattern: Pass Database instance to every function (no module-level state).
+// Values are coerced to the definition's type on write: numbers are stored as
+// REAL, dates as ISO 8601 strings, everything else as TEXT. Empty values
+// delete the row so "no value" is always represented by absence (→ NULL in
+// the prop_<key> field expression).
+
+import type { Database } from '../Database';
+
+// ---------------------------------------------------------------------------
+// DDL
+// ---------------------------------------------------------------------------
+
+/**
+ * DDL for the custom property tables. Mirrors schema.sql; applied by the
+ * create_card_properties migration for checkpoints that predate it.
+ */
+export const CARD_PROPERTIES_DDL = `CREATE TABLE IF NOT EXISTS property_definitions (
+  key TEXT PRIMARY KEY NOT NULL,
+  label TEXT NOT NULL,
+  type TEXT NOT NULL CHECK (type IN ('text', 'number', 'date', 'enum', 'url')),
+  enum_values TEXT,
+  created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+CREATE TABLE IF NOT EXISTS card_properties (
+  card_id TEXT NOT NULL REFERENCES cards(id) ON DELETE CASCADE,
+  key TEXT NOT NULL REFERENCES property_definitions(key) ON DELETE CASCADE,
+  value,
+  PRIMARY KEY (card_id, key)
+);
+CREATE INDEX IF NOT EXISTS idx_card_properties_key_value ON card_properties(key, value);`;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+export type PropertyType = 'text' | 'number' | 'date' | 'enum' | 'url';
+
+/** Stored property value: REAL for number, TEXT for every other type. */
+export type PropertyValue = string | number;
+
+export interface PropertyDefinition {
+	/** Normalized identifier ([a-z][a-z0-9_]*); exposed as the prop_<key> field */
+	key: string;
+	/** Display label (usually the original column header) */
+	label: string;
+	type: PropertyType;
+	/** Allowed values for enum properties; null for other types */
+	enum_values: string[] | null;
+	created_at: string;
+}
+
+export interface PropertyDefinitionInput {
+	/** Optional explicit key; derived from label via normalizePropertyKey() when omitted */
+	key?: string;
+	label: string;
+	type: PropertyType;
+	enum_values?: string[] | null;
+}
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+export const PROPERTY_TYPES: readonly PropertyType[] = ['text', 'number', 'date', 'enum', 'url'];
+
+/** Valid property key: lowercase identifier, max 48 chars (safe to inline in SQL). */
+export const PROPERTY_KEY_RE = /^[a-z][a-z0-9_]{0,47}$/;
+
+/** Enum inference ceiling — more distinct values than this stays 'text'. */
+const MAX_INFERRED_ENUM_VALUES = 12;
+
+const ISO_DATE_RE = /^\d{4}-\d{2}-\d{2}([T ]\d{2}:\d{2}(:\d{2}(\.\d+)?)?(Z|[+-]\d{2}:?\d{2})?)?$/;
+const URL_RE = /^https?:\/\/\S+$/i;
+
+// ---------------------------------------------------------------------------
+// Key normalization and type inference (pure)
+// ---------------------------------------------------------------------------
+
+/**
+ * Derive a property key from a display label.
+ * "Annual Revenue ($)" → "annual_revenue"; "2024 Q1" → "p_2024_q1".
+ * Returns null when the label has no usable characters.
+ */
+export function normalizePropertyKey(label: string): string | null {
+	let key = label
+		.normalize('NFKD')
+		.replace(/[\u0300-\u036f]/g, '')
+		.toLowerCase()
+		.replace(/[^a-z0-9]+/g, '_')
+		.replace(/^_+|_+$/g, '');
+	if (key === '') return null;
+	if (!/^[a-z]/.test(key)) key = `p_${key}`;
+	key = key.slice(0, 48).replace(/_+$/, '');
+	return PROPERTY_KEY_RE.test(key) ? key : null;
+}
+
+/**
+ * Infer the narrowest property type that fits every non-empty sample.
+ * Order: number → date → url → enum (few distinct repeated values) → text.
+ */
+export function inferPropertyType(samples: readonly unknown[]): { type: PropertyType; enum_values: string[] | null } {
+	const values = samples.filter((v) => v !== null && v !== undefined && String(v).trim() !== '');
+	if (values.length === 0) return { type: 'text', enum_values: null };
+
+	if (values.every((v) => toNumber(v) !== null)) return { type: 'number', enum_values: null };
+	if (values.every((v) => v instanceof Date || (typeof v === 'string' && ISO_DATE_RE.test(v.trim())))) {
+		return { type: 'date', enum_values: null };
+	}
+	if (values.every((v) => typeof v === 'string' && URL_RE.test(v.trim()))) return { type: 'url', enum_values: null };
+
+	const distinct = [...new Set(values.map((v) => String(v).trim()))];
+	if (distinct.length <= MAX_INFERRED_ENUM_VALUES && distinct.length < values.length) {
+		return { type: 'enum', enum_values: distinct.sort() };
+	}
+	return { type: 'text', enum_values: null };
+}
+
+/**
+ * Coerce a raw value to the storage representation for `type`.
+ * Returns null for empty input; throws for values that do not fit the type.
+ * Enum values outside `enum_values` are rejected.
+ */
+export function coercePropertyValue(
+	definition: Pick<PropertyDefinition, 'key' | 'type' | 'enum_values'>,
+	raw: unknown,
+): PropertyValue | null {
+	if (raw === null || raw === undefined) return null;
+	if (typeof raw === 'string' && raw.trim() === '') return null;
+
+	switch (definition.type) {
+		case 'number': {
+			const n = toNumber(raw);
+			if (n === null) throw new Error(`Property "${definition.key}" expects a number, got "${String(raw)}"`);
+			return n;
+		}
+		case 'date': {
+			const d = raw instanceof Date ? raw : new Date(String(raw).trim());
+			if (Number.isNaN(d.getTime())) {
+				throw new Error(`Property "${definition.key}" expects a date, got "${String(raw)}"`);
+			}
+			return d.toISOString();
+		}
+		case 'url': {
+			const s = String(raw).trim();
+			if (!URL_RE.test(s)) throw new Error(`Property "${definition.key}" expects an http(s) URL, got "${s}"`);
+			return s;
+		}
+		case 'enum': {
+			const s = String(raw).trim();
+			if (definition.enum_values && !definition.enum_values.includes(s)) {
+				throw new Error(`Property "${definition.key}" does not allow "${s}"`);
+			}
+			return s;
+		}
+		default:
+			return String(raw);
+	}
+}
+
+function toNumber(value: unknown): number | null {
+	if (typeof value === 'number') return Number.isFinite(value) ? value : null;
+	if (typeof value !== 'string') return null;
+	const trimmed = value.trim();
+	if (trimmed === '' || !/^[-+]?(\d+\.?\d*|\.\d+)(e[-+]?\d+)?$/i.test(trimmed)) return null;
+	const n = Number(trimmed);
+	return Number.isFinite(n) ? n : null;
+}
+
+// ---------------------------------------------------------------------------
+// Definitions
+// ---------------------------------------------------------------------------
+
+function rowToDefinition(row: Record<string, unknown>): PropertyDefinition {
+	const enumJson = row['enum_values'] as string | null;
+	return {
+		key: row['key'] as string,
+		label: row['label'] as string,
+		type: row['type'] as PropertyType,
+		enum_values: enumJson ? (JSON.parse(enumJson) as string[]) : null,
+		created_at: row['created_at'] as string,
+	};
+}
+
+/**
+ * List all property definitions ordered by label.
+ */
+export function listPropertyDefinitions(db: Database): PropertyDefinition[] {
+	const stmt = db.prepare<Record<string, unknown>>(
+		'SELECT key, label, type, enum_values, created_at FROM property_definitions ORDER BY label COLLATE NOCASE',
+	);
+	const rows = stmt.all();
+	stmt.free();
+	return rows.map(rowToDefinition);
+}
+
+/**
+ * Return a single property definition, or null if it does not exist.
+ */
+export function getPropertyDefinition(db: Database, key: string): PropertyDefinition | null {
+	const stmt = db.prepare<Record<string, unknown>>(
+		'SELECT key, label, type, enum_values, created_at FROM property_definitions WHERE key = ?',
+	);
+	const row = stmt.all(key)[0];
+	stmt.free();
+	return row ? rowToDefinition(row) : null;
+}
+
+/**
+ * Create a property definition. Throws on invalid key/type or duplicate key.
+ */
+export function createPropertyDefinition(db: Database, input: PropertyDefinitionInput): PropertyDefinition {
+	const key = input.key ?? normalizePropertyKey(input.label);
+	if (!key || !PROPERTY_KEY_RE.test(key)) {
+		throw new Error(`Invalid property key "${key ?? input.label}" — expected [a-z][a-z0-9_]*`);
+	}
+	if (!PROPERTY_TYPES.includes(input.type)) {
+		throw new Error(`Invalid property type "${input.type}"`);
+	}
+	if (getPropertyDefinition(db, key)) {
+		throw new Error(`Property "${key}" already exists`);
+	}
+
+	const enumValues = input.type === 'enum' ? [...new Set(input.enum_values ?? [])] : null;
+	db.run('INSERT INTO property_definitions (key, label, type, enum_values) VALUES (?, ?, ?, ?)', [
+		key,
+		input.label,
+		input.type,
+		enumValues ? JSON.stringify(enumValues) : null,
+	]);
+	return getPropertyDefinition(db, key)!;
+}
+
+/**
+ * Append values to an enum property's allowed set (no-op for known values).
+ */
+export function extendEnumValues(db: Database, key: string, values: readonly string[]): void {
+	const def = getPropertyDefinition(db, key);
+	if (!def || def.type !== 'enum') return;
+	const merged = [...new Set([...(def.enum_values ?? []), ...values])];
+	if (merged.length === (def.enum_values ?? []).length) return;
+	db.run('UPDATE property_definitions SET enum_values = ? WHERE key = ?', [JSON.stringify(merged), key]);
+}
+
+/**
+ * Delete a property definition and (via ON DELETE CASCADE) all of its values.
+ */
+export function deletePropertyDefinition(db: Database, key: string): void {
+	db.run('DELETE FROM property_definitions WHERE key = ?', [key]);
+}
+
+// ---------------------------------------------------------------------------
+// Values
+// ---------------------------------------------------------------------------
+
+/**
+ * Set (or clear, when empty) a card's value for a property.
+ * The value is coerced to the definition's type; throws if the property is
+ * unknown or the value does not fit.
+ */
+export function setCardProperty(db: Database, cardId: string, key: string, raw: unknown): void {
+	const def = getPropertyDefinition(db, key);
+	if (!def) throw new Error(`Property "${key}" not found`);
+
+	const value = coercePropertyValue(def, raw);
+	if (value === null) {
+		db.run('DELETE FROM card_properties WHERE card_id = ? AND key = ?', [cardId, key]);
+	} else {
+		db.run('INSERT OR REPLACE INTO card_properties (card_id, key, value) VALUES (?, ?, ?)', [cardId, key, value]);
+	}
+}
+
+/**
+ * Return all property values for a card keyed by property key.
+ */
+export function getCardProperties(db: Database, cardId: string): Record<string, PropertyValue> {
+	const stmt = db.prepare<{ key: string; value: PropertyValue }>(
+		'SELECT key, value FROM card_properties WHERE card_id = ?',
+	);
+	const rows = stmt.all(cardId);
+	stmt.free();
+	const result: Record<string, PropertyValue> = {};
+	for (const row of rows) {
+		result[row.key] = row.value;
+	}
+	return result;
+}
@@ -0,0 +1,145 @@
+// Isometry v5 — Saved Searches Query Module
+// Named FilterProvider states ("smart folders") persisted in saved_searches.
+//
+// Pattern: Pass Database instance to every function (no module-level state).
+// `state` is the exact FilterProvider.toJSON() string — filters, search query,
+// axis/range filters and membership filter — so a saved search restores the
+// full filter state, not just the text query.
+//
+// Counts are live: countSavedSearch() compiles the stored state through
+// compileFilterState() (allowlist-validated) on every call. A state that
+// no longer compiles (e.g. a deleted custom property) counts as null instead
+// of failing the whole list.
+
+import type { SqlValue } from 'sql.js';
+import type { CompiledFilter } from '../../providers/types';
+import type { Database } from '../Database';
+import { compileFilterState, parseFilterState } from './filter-sql';
+
+// ---------------------------------------------------------------------------
+// DDL
+// ---------------------------------------------------------------------------
+
+/**
+ * DDL for the saved_searches table. Mirrors schema.sql; applied by the
+ * create_saved_searches migration for checkpoints that predate it.
+ */
+export const SAVED_SEARCHES_DDL = `CREATE TABLE IF NOT EXISTS saved_searches (
+  id TEXT PRIMARY KEY NOT NULL,
+  name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+  state TEXT NOT NULL,
+  created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+  updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+)`;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+export interface SavedSearch {
+	id: string;
+	/** Display name, unique case-insensitively */
+	name: string;
+	/** Serialized FilterProvider state (FilterProvider.toJSON()) */
+	state: string;
+	created_at: string;
+	updated_at: string;
+	/** Live count of matching cards; null when the state no longer compiles */
+	count: number | null;
+}
+
+// ---------------------------------------------------------------------------
+// Read
+// ---------------------------------------------------------------------------
+
+/**
+ * List all saved searches ordered by name, each with its live card count.
+ */
+export function listSavedSearches(db: Database): SavedSearch[] {
+	const result = db.exec(
+		'SELECT id, name, state, created_at, updated_at FROM saved_searches ORDER BY name COLLATE NOCASE',
+	);
+	if (!result[0]) return [];
+
+	const { columns, values } = result[0];
+	return values.map((row) => {
+		const obj: Record<string, unknown> = {};
+		columns.forEach((col, i) => {
+			obj[col] = row[i];
+		});
+		const state = obj['state'] as string;
+		return {
+			id: obj['id'] as string,
+			name: obj['name'] as string,
+			state,
+			created_at: obj['created_at'] as string,
+			updated_at: obj['updated_at'] as string,
+			count: countSavedSearch(db, state),
+		};
+	});
+}
+
+/**
+ * Count live cards matching a serialized FilterProvider state.
+ * Returns null when the state is corrupt or references fields that no longer exist.
+ */
+export function countSavedSearch(db: Database, state: string): number | null {
+	let compiled: CompiledFilter;
+	try {
+		compiled = compileFilterState(parseFilterState(state));
+	} catch {
+		return null;
+	}
+
+	try {
+		const result = db.exec(`SELECT COUNT(*) FROM cards WHERE ${compiled.where}`, compiled.params as SqlValue[]);
+		return (result[0]?.values[0]?.[0] as number | undefined) ?? 0;
+	} catch {
+		// e.g. malformed FTS5 expression in a legacy state
+		return null;
+	}
+}
+
+// ---------------------------------------------------------------------------
+// Write
+// ---------------------------------------------------------------------------
+
+/**
+ * Save a search under `name`. Saving an existing name (case-insensitive)
+ * replaces its state and keeps its id.
+ *
+ * @throws {Error} if the name is empty or the state is not a valid FilterProvider state
+ */
+export function saveSearch(db: Database, name: string, state: string): SavedSearch {
+	const trimmed = name.trim();
+	if (trimmed === '') throw new Error('Saved search name is required');
+	// Validate before persisting — a saved search must be runnable
+	parseFilterState(state);
+
+	const now = new Date().toISOString();
+	db.run(
+		`INSERT INTO saved_searches (id, name, state, created_at, updated_at) VALUES (?, ?, ?, ?, ?)
+     ON CONFLICT(name) DO UPDATE SET state = excluded.state, updated_at = excluded.updated_at`,
+		[crypto.randomUUID(), trimmed, state, now, now],
+	);
+
+	return listSavedSearches(db).find((s) => s.name.toLowerCase() === trimmed.toLowerCase())!;
+}
+
+/**
+ * Rename a saved search.
+ *
+ * @throws {Error} if the name is empty or already used by another saved search
+ */
+export function renameSavedSearch(db: Database, id: string, name: string): void {
+	const trimmed = name.trim();
+	if (trimmed === '') throw new Error('Saved search name is required');
+	db.run('UPDATE saved_searches SET name = ?, updated_at = ? WHERE id = ?', [trimmed, new Date().toISOString(), id]);
+}
+
+/**
+ * Delete a saved search. No-op for unknown ids.
+ */
+export function deleteSavedSearch(db: Database, id: string): void {
+	db.run('DELETE FROM saved_searches WHERE id = ?', [id]);
+}
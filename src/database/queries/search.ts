@@ -10,9 +10,12 @@
 //   - BM25 score convention: FTS5 makes scores negative; more negative = better match
 //   - snippet(-1, ...) lets SQLite auto-select the best matching column
 //   - AND c.deleted_at IS NULL in the JOIN — Pitfall 1 from research
+//   - User input goes through parseSearchQuery() — never raw into MATCH
 
 import type { SqlValue } from 'sql.js';
+import { parseSearchQuery } from '../../providers/search-query';
 import type { Database } from '../Database';
+import { compileFilterList } from './filter-sql';
 import { rowToCard } from './helpers';
 import type { SearchResult } from './types';
 
@@ -35,8 +38,14 @@ import type { SearchResult } from './types';
  * Soft-deleted cards are excluded via `AND c.deleted_at IS NULL` on the
  * JOIN target (not on the FTS MATCH predicate — see Pitfall 1 from research).
  *
+ * Query language: `query` is parsed by parseSearchQuery() —
+ * column scopes, prefix/NEAR/OR/NOT, and LATCH field clauses such as
+ * `status:done due:<2026-01-01`. Syntax errors never throw: the parser drops or
+ * quotes the offending token. A query with only field clauses returns the
+ * matching cards by most recently modified (rank 0, empty snippet).
+ *
  * @param db    - Database instance (initialized)
- * @param query - FTS5 query string (supports implicit AND, OR, phrase queries)
+ * @param query - Search query string (search query language)
  * @param limit - Maximum results to return (default 20)
  * @returns     - Array of SearchResult with card, BM25 rank, and snippet
  */
@@ -44,25 +53,49 @@ export function searchCards(db: Database, query: string, limit: number = 20): Se
 	// SRCH-01 guard: empty or whitespace-only query returns no results
 	if (!query.trim()) return [];
 
-	// CRITICAL (SRCH-02): JOIN on rowid, NEVER on id
-	// CRITICAL (SRCH-01): ORDER BY rank (FTS5 virtual column), not ORDER BY bm25(cards_fts)
-	//   - rank is an optimized alias that FTS5 pre-computes; faster than calling bm25() inline
-	//   - Ascending order: FTS5 rank is negative, most-negative = best match comes first
-	// snippet() params: table, column_index, open_mark, close_mark, ellipsis, max_tokens
-	//   - column_index -1: auto-select column with best match (per FTS5 docs)
-	//   - max_tokens 32: ~32 tokens of context surrounding the match
-	const result = db.exec(
-		`SELECT c.*,
+	const parsed = parseSearchQuery(query);
+	if (!parsed.fts && parsed.filters.length === 0) return [];
+
+	// Field clauses compile against the plain cards table (unqualified column
+	// names would be ambiguous next to cards_fts), so apply them via rowid.
+	const { clauses, params: filterParams } = compileFilterList(parsed.filters);
+	const filterWhere =
+		clauses.length > 0 ? `AND c.rowid IN (SELECT rowid FROM cards WHERE ${clauses.join(' AND ')})` : '';
+
+	let result: ReturnType<Database['exec']>;
+	if (parsed.fts) {
+		// CRITICAL (SRCH-02): JOIN on rowid, NEVER on id
+		// CRITICAL (SRCH-01): ORDER BY rank (FTS5 virtual column), not ORDER BY bm25(cards_fts)
+		//   - rank is an optimized alias that FTS5 pre-computes; faster than calling bm25() inline
+		//   - Ascending order: FTS5 rank is negative, most-negative = best match comes first
+		// snippet() params: table, column_index, open_mark, close_mark, ellipsis, max_tokens
+		//   - column_index -1: auto-select column with best match (per FTS5 docs)
+		//   - max_tokens 32: ~32 tokens of context surrounding the match
+		result = db.exec(
+			`SELECT c.*,
             rank,
             snippet(cards_fts, -1, '<mark>', '</mark>', '...', 32) AS snippet_text
      FROM cards_fts
      JOIN cards c ON c.rowid = cards_fts.rowid
      WHERE cards_fts MATCH ?
        AND c.deleted_at IS NULL
+       ${filterWhere}
      ORDER BY rank
      LIMIT ?`,
-		[query, limit],
-	);
+			[parsed.fts, ...(filterParams as SqlValue[]), limit],
+		);
+	} else {
+		// Field clauses only — no FTS ranking available
+		result = db.exec(
+			`SELECT c.*, 0 AS rank, '' AS snippet_text
+     FROM cards c
+     WHERE c.deleted_at IS NULL
+       ${filterWhere}
+     ORDER BY c.modified_at DESC
+     LIMIT ?`,
+			[...(filterParams as SqlValue[]), limit],
+		);
+	}
 
 	if (!result[0]) return [];
 
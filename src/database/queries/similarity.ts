@@ -0,0 +1,369 @@
+// Isometry v5 — Similar Card Search
+// "Find cards like this one" over locally computed TF-IDF vectors.
+//
+// Pattern: Pass Database instance to every function (no module-level state).
+// Everything is computed in the Worker — no network model, no embeddings API.
+//
+// Design:
+//   - Each card's name, tags and content are tokenized and feature-hashed into
+//     VECTOR_DIMENSIONS buckets (FNV-1a). The sparse term-frequency vector is
+//     stored as JSON in card_vectors, one row per card.
+//   - Each vector is also written to card_vector_terms, an inverted index of
+//     (bucket, card_id, tf) postings. A query only scores cards that share at
+//     least one bucket with the target, and reads their postings instead of
+//     parsing every stored vector.
+//   - IDF weights are derived at query time from posting counts, so the index
+//     never needs a global rebuild when the corpus changes.
+//   - Vectors are refreshed lazily: the card_vectors_au trigger deletes a row
+//     when name/content/tags change (card_vectors_ad drops its postings), and
+//     indexCardVectors() fills in missing rows before every similarity query.
+//   - Name and tag terms count double — they describe the card more densely
+//     than body text.
+
+import type { SqlValue } from 'sql.js';
+import type { Database } from '../Database';
+import { rowToCard } from './helpers';
+import type { SimilarCardResult } from './types';
+
+// ---------------------------------------------------------------------------
+// DDL
+// ---------------------------------------------------------------------------
+
+/**
+ * DDL for the card_vectors side table. Mirrors schema.sql; applied by the
+ * create_card_vectors migration for checkpoints that predate it.
+ *
+ * Columns:
+ *   card_id     - FK to cards(id), cascades on delete
+ *   terms       - JSON array of [bucket, tf] pairs, sorted by bucket
+ *   computed_at - ISO 8601 timestamp of vectorization
+ */
+export const CARD_VECTORS_DDL = `CREATE TABLE IF NOT EXISTS card_vectors (
+  card_id TEXT PRIMARY KEY NOT NULL REFERENCES cards(id) ON DELETE CASCADE,
+  terms TEXT NOT NULL,
+  computed_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+)`;
+
+/**
+ * Invalidation trigger: drop a card's vector when its indexed text changes.
+ * Kept separate from CARD_VECTORS_DDL because the trigger body contains ';'.
+ */
+export const CARD_VECTORS_TRIGGER_DDL = `CREATE TRIGGER IF NOT EXISTS card_vectors_au AFTER UPDATE OF name, content, tags ON cards BEGIN
+    DELETE FROM card_vectors WHERE card_id = NEW.id;
+END`;
+
+/**
+ * DDL for the card_vector_terms inverted index. Mirrors schema.sql; applied by
+ * the create_card_vector_terms migration for checkpoints that predate it.
+ *
+ * Columns:
+ *   card_id - FK to cards(id), cascades on delete
+ *   bucket  - hash bucket of a term (0..VECTOR_DIMENSIONS-1)
+ *   tf      - boosted term frequency of the bucket in the card's vector
+ */
+export const CARD_VECTOR_TERMS_DDL = `CREATE TABLE IF NOT EXISTS card_vector_terms (
+  card_id TEXT NOT NULL REFERENCES cards(id) ON DELETE CASCADE,
+  bucket INTEGER NOT NULL,
+  tf INTEGER NOT NULL,
+  PRIMARY KEY (card_id, bucket)
+);
+CREATE INDEX IF NOT EXISTS idx_card_vector_terms_bucket ON card_vector_terms(bucket, card_id);`;
+
+/**
+ * Invalidation trigger: drop a card's postings together with its vector.
+ * Kept separate from CARD_VECTOR_TERMS_DDL because the trigger body contains ';'.
+ */
+export const CARD_VECTOR_TERMS_TRIGGER_DDL = `CREATE TRIGGER IF NOT EXISTS card_vectors_ad AFTER DELETE ON card_vectors BEGIN
+    DELETE FROM card_vector_terms WHERE card_id = OLD.card_id;
+END`;
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/** Number of hash buckets. Collisions are rare enough at this size for ranking. */
+export const VECTOR_DIMENSIONS = 4096;
+
+/** Content beyond this many characters is not vectorized. */
+const MAX_CONTENT_CHARS = 20_000;
+
+/** Weight multiplier for name and tag terms relative to content terms. */
+const FIELD_BOOST = 2;
+
+/** Results scoring below this cosine similarity are dropped as noise. */
+const MIN_SCORE = 0.05;
+
+/** Batch size for vector writes (matches the ETL batchSize pattern). */
+const BATCH_SIZE = 1000;
+
+// Common English function words — they dominate term counts without saying
+// anything about what a card is about.
+const STOPWORDS: ReadonlySet<string> = new Set(
+	(
+		'a about after all also am an and any are as at be because been but by can could did do does for from had ' +
+		'has have he her here him his how i if in into is it its just me more most my no not of on or our out so ' +
+		'some than that the their them then there these they this those to too up us was we were what when where ' +
+		'which while who why will with would you your'
+	).split(' '),
+);
+
+// ---------------------------------------------------------------------------
+// Vectorization (pure)
+// ---------------------------------------------------------------------------
+
+/** Sparse term-frequency vector: [bucket, weight] pairs sorted by bucket. */
+export type TermVector = Array<[number, number]>;
+
+/**
+ * Split text into normalized terms: lowercase, diacritics stripped, stopwords,
+ * single characters and pure numbers removed, trailing plural 's' dropped.
+ */
+export function tokenizeForSimilarity(text: string): string[] {
+	const terms: string[] = [];
+	const words = text
+		.normalize('NFKD')
+		.replace(/[\u0300-\u036f]/g, '')
+		.toLowerCase()
+		.split(/[^\p{L}\p{N}]+/u);
+	for (const word of words) {
+		if (word.length < 2 || STOPWORDS.has(word) || /^\d+$/.test(word)) continue;
+		terms.push(word.length > 3 && word.endsWith('s') && !word.endsWith('ss') ? word.slice(0, -1) : word);
+	}
+	return terms;
+}
+
+/** FNV-1a 32-bit hash of a term, reduced to a bucket index. */
+export function hashTerm(term: string): number {
+	let hash = 0x811c9dc5;
+	for (let i = 0; i < term.length; i++) {
+		hash ^= term.charCodeAt(i);
+		hash = Math.imul(hash, 0x01000193);
+	}
+	return (hash >>> 0) % VECTOR_DIMENSIONS;
+}
+
+/**
+ * Build the hashed term-frequency vector for a card's text fields.
+ * Name and tag terms are boosted by FIELD_BOOST.
+ */
+export function computeTermVector(card: { name: string; content: string | null; tags: string[] }): TermVector {
+	const counts = new Map<number, number>();
+	const add = (text: string, weight: number) => {
+		for (const term of tokenizeForSimilarity(text)) {
+			const bucket = hashTerm(term);
+			counts.set(bucket, (counts.get(bucket) ?? 0) + weight);
+		}
+	};
+
+	add(card.name, FIELD_BOOST);
+	add(card.tags.join(' '), FIELD_BOOST);
+	if (card.content) add(card.content.slice(0, MAX_CONTENT_CHARS), 1);
+
+	return [...counts.entries()].sort((a, b) => a[0] - b[0]);
+}
+
+// ---------------------------------------------------------------------------
+// Index maintenance
+// ---------------------------------------------------------------------------
+
+/**
+ * Vectorize every live card that has no card_vectors row yet (new cards and
+ * cards whose text changed since they were last indexed).
+ *
+ * @returns number of cards vectorized
+ */
+export function indexCardVectors(db: Database): number {
+	const result = db.exec(
+		`SELECT c.id, c.name, c.content, c.tags
+     FROM cards c
+     LEFT JOIN card_vectors v ON v.card_id = c.id
+     WHERE v.card_id IS NULL AND c.deleted_at IS NULL`,
+	);
+	const rows = result[0]?.values ?? [];
+	if (rows.length === 0) return 0;
+
+	const now = new Date().toISOString();
+	const doWrite = db.transaction(() => {
+		for (let i = 0; i < rows.length; i += BATCH_SIZE) {
+			for (const [id, name, content, tags] of rows.slice(i, i + BATCH_SIZE)) {
+				const vector = computeTermVector({
+					name: (name as string | null) ?? '',
+					content: (content as string | null) ?? null,
+					tags: parseTags(tags as string | null),
+				});
+				const terms = JSON.stringify(vector);
+				db.run('INSERT OR REPLACE INTO card_vectors (card_id, terms, computed_at) VALUES (?, ?, ?)', [
+					id as string,
+					terms,
+					now,
+				]);
+				// Postings from the same JSON — one statement per card, not per bucket
+				db.run('DELETE FROM card_vector_terms WHERE card_id = ?', [id as string]);
+				db.run(
+					`INSERT INTO card_vector_terms (card_id, bucket, tf)
+     SELECT ?, json_extract(value, '$[0]'), json_extract(value, '$[1]') FROM json_each(?)`,
+					[id as string, terms],
+				);
+			}
+		}
+	});
+	doWrite();
+	return rows.length;
+}
+
+/**
+ * Delete all stored vectors (and, via card_vectors_ad, their postings).
+ * The next similarity query re-vectorizes the corpus.
+ */
+export function clearCardVectors(db: Database): void {
+	db.run('DELETE FROM card_vectors');
+}
+
+// ---------------------------------------------------------------------------
+// Query
+// ---------------------------------------------------------------------------
+
+/**
+ * Find the cards most similar to `cardId` by TF-IDF cosine similarity.
+ *
+ * Brings the vector index up to date first, so results always reflect the
+ * current card text. Soft-deleted cards are never returned. Returns [] when
+ * the card does not exist, is deleted, or has no indexable text.
+ *
+ * @param db     - Database instance (initialized)
+ * @param cardId - Card to find neighbours for
+ * @param limit  - Maximum results to return (default 10)
+ * @returns      - Similar cards ordered by score descending (0..1]
+ */
+export function findSimilarCards(db: Database, cardId: string, limit: number = 10): SimilarCardResult[] {
+	indexCardVectors(db);
+
+	const target = readPostings(
+		db,
+		`SELECT t.card_id, t.bucket, t.tf
+     FROM card_vector_terms t
+     JOIN cards c ON c.id = t.card_id
+     WHERE t.card_id = ? AND c.deleted_at IS NULL`,
+		[cardId],
+	).get(cardId);
+	if (!target || target.length === 0) return [];
+
+	// Candidates: live cards sharing at least one bucket with the target
+	const targetBuckets = JSON.stringify(target.map(([bucket]) => bucket));
+	const candidates = readPostings(
+		db,
+		`SELECT t.card_id, t.bucket, t.tf
+     FROM card_vector_terms t
+     JOIN cards c ON c.id = t.card_id
+     WHERE c.deleted_at IS NULL AND t.card_id != ?
+       AND t.card_id IN (SELECT card_id FROM card_vector_terms WHERE bucket IN (SELECT value FROM json_each(?)))`,
+		[cardId, targetBuckets],
+	);
+	if (candidates.size === 0) return [];
+
+	// Document frequency for every bucket the scored vectors use → smoothed IDF
+	const buckets = new Set<number>(target.map(([bucket]) => bucket));
+	for (const vector of candidates.values()) {
+		for (const [bucket] of vector) buckets.add(bucket);
+	}
+	const df = new Map<number, number>();
+	const dfResult = db.exec(
+		`SELECT t.bucket, COUNT(*)
+     FROM card_vector_terms t
+     JOIN cards c ON c.id = t.card_id
+     WHERE c.deleted_at IS NULL AND t.bucket IN (SELECT value FROM json_each(?))
+     GROUP BY t.bucket`,
+		[JSON.stringify([...buckets])],
+	);
+	for (const [bucket, count] of dfResult[0]?.values ?? []) df.set(bucket as number, count as number);
+	const countResult = db.exec(
+		'SELECT COUNT(*) FROM card_vectors v JOIN cards c ON c.id = v.card_id WHERE c.deleted_at IS NULL',
+	);
+	const n = (countResult[0]?.values[0]?.[0] as number | undefined) ?? 0;
+	const idf = (bucket: number) => Math.log((n + 1) / ((df.get(bucket) ?? 0) + 1)) + 1;
+
+	const targetWeights = weigh(target, idf);
+	const targetNorm = norm(targetWeights.values());
+	if (targetNorm === 0) return [];
+
+	const scored: Array<{ id: string; score: number }> = [];
+	for (const [id, vector] of candidates) {
+		const weights = weigh(vector, idf);
+		let dot = 0;
+		for (const [bucket, w] of weights) {
+			const t = targetWeights.get(bucket);
+			if (t !== undefined) dot += t * w;
+		}
+		if (dot === 0) continue;
+		const score = dot / (targetNorm * norm(weights.values()));
+		if (score >= MIN_SCORE) scored.push({ id, score });
+	}
+
+	scored.sort((a, b) => b.score - a.score);
+	const top = scored.slice(0, limit);
+	if (top.length === 0) return [];
+
+	const placeholders = top.map(() => '?').join(', ');
+	const cardResult = db.exec(`SELECT * FROM cards WHERE id IN (${placeholders})`, top.map((t) => t.id));
+	if (!cardResult[0]) return [];
+
+	const { columns, values } = cardResult[0];
+	const cards = new Map<string, SimilarCardResult['card']>();
+	for (const row of values) {
+		const obj: Record<string, unknown> = {};
+		columns.forEach((col, i) => {
+			obj[col] = row[i];
+		});
+		const card = rowToCard(obj as Record<string, SqlValue>);
+		cards.set(card.id, card);
+	}
+
+	return top.flatMap(({ id, score }) => {
+		const card = cards.get(id);
+		return card ? [{ card, score }] : [];
+	});
+}
+
+// ---------------------------------------------------------------------------
+// Internal helpers
+// ---------------------------------------------------------------------------
+
+/** Sublinear TF × IDF weights keyed by bucket. */
+function weigh(vector: TermVector, idf: (bucket: number) => number): Map<number, number> {
+	const weights = new Map<number, number>();
+	for (const [bucket, tf] of vector) {
+		weights.set(bucket, (1 + Math.log(tf)) * idf(bucket));
+	}
+	return weights;
+}
+
+/** Group (card_id, bucket, tf) posting rows into one term vector per card. */
+function readPostings(db: Database, sql: string, params: SqlValue[]): Map<string, TermVector> {
+	const vectors = new Map<string, TermVector>();
+	const result = db.exec(sql, params);
+	for (const [id, bucket, tf] of result[0]?.values ?? []) {
+		let vector = vectors.get(id as string);
+		if (!vector) {
+			vector = [];
+			vectors.set(id as string, vector);
+		}
+		vector.push([bucket as number, tf as number]);
+	}
+	return vectors;
+}
+
+function norm(weights: Iterable<number>): number {
+	let sum = 0;
+	for (const w of weights) sum += w * w;
+	return Math.sqrt(sum);
+}
+
+function parseTags(tags: string | null): string[] {
+	if (!tags) return [];
+	try {
+		const parsed: unknown = JSON.parse(tags);
+		return Array.isArray(parsed) ? parsed.map(String) : [];
+	} catch {
+		return [];
+	}
+}
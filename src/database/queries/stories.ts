@@ -0,0 +1,233 @@
+// Isometry v5 — Stories Query Module
+// Stories are ordered slides, each a snapshot of the view state plus a
+// markdown caption, persisted in stories / story_slides.
+//
+// Pattern: Pass Database instance to every function (no module-level state).
+// A slide's `state` stores the active view type and the PAFVProvider,
+// FilterProvider and DensityProvider states as parsed toJSON() objects, plus
+// the selected card ids. Saving a story replaces all of its slides in one
+// transaction — slides are always edited as a whole ordered list.
+//
+// Cards for a slide are live: slideCards() compiles the stored filter state
+// through compileFilterState() (allowlist-validated), like saved searches.
+
+import type { SqlValue } from 'sql.js';
+import type { CompiledFilter, ViewType } from '../../providers/types';
+import type { Database } from '../Database';
+import { compileFilterState, parseFilterState } from './filter-sql';
+
+// ---------------------------------------------------------------------------
+// DDL
+// ---------------------------------------------------------------------------
+
+/**
+ * DDL for the stories and story_slides tables. Mirrors schema.sql; applied by
+ * the create_stories migration for checkpoints that predate it.
+ */
+export const STORIES_DDL = `CREATE TABLE IF NOT EXISTS stories (
+  id TEXT PRIMARY KEY NOT NULL,
+  name TEXT NOT NULL,
+  created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+  updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+CREATE TABLE IF NOT EXISTS story_slides (
+  id TEXT PRIMARY KEY NOT NULL,
+  story_id TEXT NOT NULL REFERENCES stories(id) ON DELETE CASCADE,
+  position INTEGER NOT NULL,
+  caption TEXT NOT NULL DEFAULT '',
+  state TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_story_slides_story ON story_slides(story_id, position);`;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/** View state captured by a slide. Provider states are parsed toJSON() objects. */
+export interface StorySlideState {
+	view: ViewType;
+	pafv: Record<string, unknown>;
+	filter: Record<string, unknown>;
+	density: Record<string, unknown>;
+	/** Selected card ids (SelectionProvider is never persisted elsewhere) */
+	selection: string[];
+}
+
+export interface StorySlide {
+	id: string;
+	/** Markdown caption */
+	caption: string;
+	state: StorySlideState;
+}
+
+export interface Story {
+	id: string;
+	name: string;
+	slides: StorySlide[];
+	created_at: string;
+	updated_at: string;
+}
+
+/** Input for saveStory(). Omit `id` to create a story; slide ids are optional. */
+export interface StoryInput {
+	id?: string;
+	name: string;
+	slides: Array<{ id?: string; caption: string; state: StorySlideState }>;
+}
+
+/** Live cards for a slide's filter state. */
+export interface StorySlideCards {
+	/** Matching card count; null when the filter state no longer compiles */
+	count: number | null;
+	/** First matching cards ordered by name */
+	cards: Array<{ id: string; name: string }>;
+	/** Selected cards that still exist */
+	selected: Array<{ id: string; name: string }>;
+}
+
+// ---------------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------------
+
+function isPlainObject(value: unknown): value is Record<string, unknown> {
+	return typeof value === 'object' && value !== null && !Array.isArray(value);
+}
+
+/**
+ * Type guard for a slide state (shape only — provider setState() validates contents on playback).
+ */
+export function isStorySlideState(value: unknown): value is StorySlideState {
+	return (
+		isPlainObject(value) &&
+		typeof value['view'] === 'string' &&
+		isPlainObject(value['pafv']) &&
+		isPlainObject(value['filter']) &&
+		isPlainObject(value['density']) &&
+		Array.isArray(value['selection']) &&
+		value['selection'].every((id) => typeof id === 'string')
+	);
+}
+
+// ---------------------------------------------------------------------------
+// Read
+// ---------------------------------------------------------------------------
+
+/**
+ * List all stories ordered by name, each with its slides in order.
+ */
+export function listStories(db: Database): Story[] {
+	const storyStmt = db.prepare<Omit<Story, 'slides'>>(
+		'SELECT id, name, created_at, updated_at FROM stories ORDER BY name COLLATE NOCASE, created_at',
+	);
+	const stories = storyStmt.all();
+	storyStmt.free();
+
+	const slideStmt = db.prepare<{ id: string; story_id: string; caption: string; state: string }>(
+		'SELECT id, story_id, caption, state FROM story_slides ORDER BY story_id, position',
+	);
+	const slideRows = slideStmt.all();
+	slideStmt.free();
+
+	const slidesByStory = new Map<string, StorySlide[]>();
+	for (const row of slideRows) {
+		const slides = slidesByStory.get(row.story_id) ?? [];
+		slides.push({ id: row.id, caption: row.caption, state: JSON.parse(row.state) as StorySlideState });
+		slidesByStory.set(row.story_id, slides);
+	}
+
+	return stories.map((story) => ({ ...story, slides: slidesByStory.get(story.id) ?? [] }));
+}
+
+/**
+ * Get one story by id, or null when it does not exist.
+ */
+export function getStory(db: Database, id: string): Story | null {
+	return listStories(db).find((s) => s.id === id) ?? null;
+}
+
+/**
+ * Live cards for a slide: count and the first `limit` matches of its filter
+ * state, plus the still-existing selected cards.
+ */
+export function slideCards(db: Database, state: StorySlideState, limit: number): StorySlideCards {
+	let selected: StorySlideCards['selected'] = [];
+	if (state.selection.length > 0) {
+		const placeholders = state.selection.map(() => '?').join(', ');
+		const stmt = db.prepare<{ id: string; name: string }>(
+			`SELECT id, name FROM cards WHERE id IN (${placeholders}) AND deleted_at IS NULL ORDER BY name`,
+		);
+		selected = stmt.all(...state.selection);
+		stmt.free();
+	}
+
+	let compiled: CompiledFilter;
+	try {
+		compiled = compileFilterState(parseFilterState(JSON.stringify(state.filter)));
+	} catch {
+		return { count: null, cards: [], selected };
+	}
+
+	try {
+		const params = compiled.params as SqlValue[];
+		const countResult = db.exec(`SELECT COUNT(*) FROM cards WHERE ${compiled.where}`, params);
+		const stmt = db.prepare<{ id: string; name: string }>(
+			`SELECT id, name FROM cards WHERE ${compiled.where} ORDER BY name LIMIT ${Math.max(0, Math.floor(limit))}`,
+		);
+		const cards = stmt.all(...params);
+		stmt.free();
+		return { count: (countResult[0]?.values[0]?.[0] as number | undefined) ?? 0, cards, selected };
+	} catch {
+		// e.g. malformed FTS5 expression in a stored search query
+		return { count: null, cards: [], selected };
+	}
+}
+
+// ---------------------------------------------------------------------------
+// Write
+// ---------------------------------------------------------------------------
+
+/**
+ * Create a story, or replace the name and slides of an existing one
+ * (input.id). Slides keep their ids when given; new slides get fresh ids.
+ *
+ * @throws {Error} if the name is empty, a slide state is malformed, or input.id is unknown
+ */
+export function saveStory(db: Database, input: StoryInput): Story {
+	const name = input.name.trim();
+	if (name === '') throw new Error('Story name is required');
+	input.slides.forEach((slide, i) => {
+		if (!isStorySlideState(slide.state)) throw new Error(`Invalid state for story slide ${i + 1}`);
+	});
+
+	const now = new Date().toISOString();
+	const id = input.id ?? crypto.randomUUID();
+
+	db.transaction(() => {
+		if (input.id !== undefined) {
+			const exists = db.exec('SELECT 1 FROM stories WHERE id = ?', [input.id]);
+			if (!exists[0]) throw new Error(`Story not found: ${input.id}`);
+			db.run('UPDATE stories SET name = ?, updated_at = ? WHERE id = ?', [name, now, id]);
+			db.run('DELETE FROM story_slides WHERE story_id = ?', [id]);
+		} else {
+			db.run('INSERT INTO stories (id, name, created_at, updated_at) VALUES (?, ?, ?, ?)', [id, name, now, now]);
+		}
+		input.slides.forEach((slide, position) => {
+			db.run('INSERT INTO story_slides (id, story_id, position, caption, state) VALUES (?, ?, ?, ?, ?)', [
+				slide.id ?? crypto.randomUUID(),
+				id,
+				position,
+				slide.caption,
+				JSON.stringify(slide.state),
+			]);
+		});
+	})();
+
+	return getStory(db, id)!;
+}
+
+/**
+ * Delete a story and its slides. No-op for unknown ids.
+ */
+export function deleteStory(db: Database, id: string): void {
+	db.run('DELETE FROM stories WHERE id = ?', [id]);
+}
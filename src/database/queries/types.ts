@@ -91,6 +91,16 @@ export interface ConnectionInput {
 	weight?: number;
 }
 
+/**
+ * Editable connection fields. Endpoints (source_id/target_id) are immutable —
+ * re-pointing an edge is a delete + create.
+ */
+export interface ConnectionUpdate {
+	via_card_id?: string | null;
+	label?: string | null;
+	weight?: number;
+}
+
 export type ConnectionDirection = 'outgoing' | 'incoming' | 'bidirectional';
 
 // ---------------------------------------------------------------------------
@@ -103,6 +113,12 @@ export interface SearchResult {
 	snippet: string;
 }
 
+export interface SimilarCardResult {
+	card: Card;
+	/** Cosine similarity of TF-IDF vectors, 0..1 (higher = more similar) */
+	score: number;
+}
+
 // ---------------------------------------------------------------------------
 // Graph types (used by Plan 02-04)
 // ---------------------------------------------------------------------------
@@ -228,3 +228,167 @@ CREATE TABLE datasets (
 
 CREATE UNIQUE INDEX idx_datasets_name_source ON datasets(name, source_type);
 CREATE INDEX idx_datasets_active ON datasets(is_active);
+
+-- ============================================================
+-- Dataset File Manifest
+-- Files a directory dataset was imported from. Incremental
+-- re-import only re-parses files whose size/mtime/hash changed
+-- and pairs removed + added paths with equal hashes as renames.
+-- ============================================================
+CREATE TABLE dataset_files (
+    dataset_id TEXT NOT NULL REFERENCES datasets(id) ON DELETE CASCADE,
+    path TEXT NOT NULL,                     -- Absolute file path
+    size_bytes INTEGER NOT NULL,
+    mtime TEXT NOT NULL,                    -- ISO 8601 modification time
+    content_hash TEXT NOT NULL,             -- Hex SHA-256 of the file contents
+    source_ids TEXT NOT NULL DEFAULT '[]',  -- JSON array of card source_ids parsed from the file
+    PRIMARY KEY (dataset_id, path)
+);
+
+-- ============================================================
+-- Custom Card Properties (typed EAV)
+-- User-defined fields beyond the fixed cards columns — e.g. extra
+-- spreadsheet columns from CSV/Excel/JSON imports. Exposed to
+-- filters and axes as virtual `prop_<key>` fields.
+-- ============================================================
+CREATE TABLE property_definitions (
+    key TEXT PRIMARY KEY NOT NULL,          -- Normalized identifier: [a-z][a-z0-9_]*
+    label TEXT NOT NULL,                    -- Display name (original header)
+    type TEXT NOT NULL CHECK (type IN ('text', 'number', 'date', 'enum', 'url')),
+    enum_values TEXT,                       -- JSON array of allowed values (enum only)
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE TABLE card_properties (
+    card_id TEXT NOT NULL REFERENCES cards(id) ON DELETE CASCADE,
+    key TEXT NOT NULL REFERENCES property_definitions(key) ON DELETE CASCADE,
+    value,                                  -- No affinity: REAL for number, TEXT otherwise
+    PRIMARY KEY (card_id, key)
+);
+
+CREATE INDEX idx_card_properties_key_value ON card_properties(key, value);
+
+-- ============================================================
+-- Card Vectors (similar-card search)
+-- Hashed term-frequency vectors over name/content/tags, computed
+-- in the Worker. IDF is derived at query time; rows are deleted
+-- when indexed text changes and rebuilt lazily on the next query.
+-- ============================================================
+CREATE TABLE card_vectors (
+    card_id TEXT PRIMARY KEY NOT NULL REFERENCES cards(id) ON DELETE CASCADE,
+    terms TEXT NOT NULL,                    -- JSON [[bucket, tf], ...] sorted by bucket
+    computed_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE TRIGGER card_vectors_au AFTER UPDATE OF name, content, tags ON cards BEGIN
+    DELETE FROM card_vectors WHERE card_id = NEW.id;
+END;
+
+-- Inverted index over card_vectors: one posting per (card, bucket).
+-- Similarity queries score only cards sharing a bucket with the target.
+CREATE TABLE card_vector_terms (
+    card_id TEXT NOT NULL REFERENCES cards(id) ON DELETE CASCADE,
+    bucket INTEGER NOT NULL,                -- hash bucket (0..4095)
+    tf INTEGER NOT NULL,                    -- boosted term frequency
+    PRIMARY KEY (card_id, bucket)
+);
+
+CREATE INDEX idx_card_vector_terms_bucket ON card_vector_terms(bucket, card_id);
+
+CREATE TRIGGER card_vectors_ad AFTER DELETE ON card_vectors BEGIN
+    DELETE FROM card_vector_terms WHERE card_id = OLD.card_id;
+END;
+
+-- ============================================================
+-- Saved Searches (smart folders)
+-- Named FilterProvider states: filters, search query, axis/range
+-- filters and membership filter, as FilterProvider.toJSON().
+-- ============================================================
+CREATE TABLE saved_searches (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    state TEXT NOT NULL,                    -- FilterProvider.toJSON()
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+-- ============================================================
+-- Mapping Profiles (tabular import mappings)
+-- Named column -> card field mappings with date format, tag
+-- separator, type coercion and card_type rules for CSV, Excel
+-- and JSON imports, suggested by header fingerprint.
+-- ============================================================
+CREATE TABLE mapping_profiles (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    fingerprint TEXT NOT NULL,              -- Sorted normalized headers joined by '|'
+    headers TEXT NOT NULL DEFAULT '[]',     -- JSON array of normalized headers
+    rules TEXT NOT NULL,                    -- JSON MappingRules
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+-- ============================================================
+-- Enricher Settings (import enrichment switches)
+-- Per-source-type on/off for built-in enrichers; source_type
+-- '*' applies to every source without its own row.
+-- ============================================================
+CREATE TABLE enricher_settings (
+    enricher_id TEXT NOT NULL,
+    source_type TEXT NOT NULL,              -- SourceType or '*'
+    enabled INTEGER NOT NULL,               -- 0/1
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    PRIMARY KEY (enricher_id, source_type)
+);
+
+-- ============================================================
+-- Geocode Places (offline gazetteer)
+-- Place name -> coordinates for cards that only carry a
+-- location_name. Seeded with built-in cities by migration 9.
+-- ============================================================
+CREATE TABLE geocode_places (
+    name TEXT PRIMARY KEY NOT NULL COLLATE NOCASE,
+    latitude REAL NOT NULL CHECK (latitude BETWEEN -90 AND 90),
+    longitude REAL NOT NULL CHECK (longitude BETWEEN -180 AND 180),
+    source TEXT NOT NULL DEFAULT 'builtin'  -- 'builtin' | 'user'
+);
+
+-- ============================================================
+-- Formulas (computed fields)
+-- Named expressions compiled to SQL and exposed as virtual
+-- fx_<name> columns. NULL dataset_id = global definition.
+-- ============================================================
+CREATE TABLE formulas (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    label TEXT NOT NULL,
+    dataset_id TEXT REFERENCES datasets(id) ON DELETE CASCADE,
+    expression TEXT NOT NULL,               -- formula source, e.g. julianday(now) - julianday(created_at)
+    result_type TEXT NOT NULL CHECK (result_type IN ('number', 'text', 'date')),
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE UNIQUE INDEX idx_formulas_name_dataset ON formulas(name, IFNULL(dataset_id, ''));
+
+-- ============================================================
+-- Stories (narrated view sequences)
+-- Ordered slides, each a snapshot of the active view and the
+-- PAFV/filter/density/selection state plus a markdown caption.
+-- ============================================================
+CREATE TABLE stories (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE TABLE story_slides (
+    id TEXT PRIMARY KEY NOT NULL,
+    story_id TEXT NOT NULL REFERENCES stories(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,              -- 0-based order within the story
+    caption TEXT NOT NULL DEFAULT '',       -- Markdown
+    state TEXT NOT NULL                     -- JSON: { view, pafv, filter, density, selection }
+);
+
+CREATE INDEX idx_story_slides_story ON story_slides(story_id, position);
@@ -7,10 +7,12 @@
 import type { Database } from '../database/Database';
 import type { Card, Connection } from '../database/queries/types';
 import { CSVExporter } from './exporters/CSVExporter';
+import { ICSExporter, isCalendarCard } from './exporters/ICSExporter';
 import { JSONExporter } from './exporters/JSONExporter';
 import { MarkdownExporter } from './exporters/MarkdownExporter';
+import { OPMLExporter } from './exporters/OPMLExporter';
 
-export type ExportFormat = 'markdown' | 'json' | 'csv';
+export type ExportFormat = 'markdown' | 'json' | 'csv' | 'opml' | 'ics';
 
 /**
  * Options for filtering export data.
@@ -44,8 +46,8 @@ export interface ExportResult {
  * Features:
  * - Queries cards with optional filters (cardIds, cardTypes)
  * - Excludes deleted cards by default
- * - Dispatches to format-specific exporters
- * - Includes connections for markdown/json formats
+ * - Dispatches to format-specific exporters (ics keeps only dated cards and tasks)
+ * - Includes connections for markdown/json/opml formats
  * - Generates timestamped filenames
  *
  * Requirements: ETL-17 (Export orchestration)
@@ -54,13 +56,15 @@ export class ExportOrchestrator {
 	private markdownExporter = new MarkdownExporter();
 	private jsonExporter = new JSONExporter();
 	private csvExporter = new CSVExporter();
+	private opmlExporter = new OPMLExporter();
+	private icsExporter = new ICSExporter();
 
 	constructor(private db: Database) {}
 
 	/**
 	 * Export cards in the specified format.
 	 *
-	 * @param format Export format (markdown, json, csv)
+	 * @param format Export format (markdown, json, csv, opml, ics)
 	 * @param options Optional filters and settings
 	 * @returns Export result with data, filename, format, and count
 	 */
@@ -98,11 +102,11 @@ export class ExportOrchestrator {
 			is_collective: Boolean(row.is_collective),
 		}));
 
-		// Get connections for markdown/json formats
+		// Get connections for markdown/json/opml formats
 		let connections: Connection[] = [];
 		let cardNameMap: Map<string, string> | undefined;
 
-		if ((format === 'markdown' || format === 'json') && cards.length > 0) {
+		if (format !== 'csv' && format !== 'ics' && cards.length > 0) {
 			const connSql = `SELECT * FROM connections WHERE source_id IN (${cards.map(() => '?').join(',')})`;
 			const connStmt = this.db.prepare<Connection>(connSql);
 			connections = connStmt.all(...cards.map((c) => c.id));
@@ -116,6 +120,7 @@ export class ExportOrchestrator {
 		// Dispatch to exporter
 		let data: string;
 		let ext: string;
+		let cardCount = cards.length;
 
 		switch (format) {
 			case 'markdown':
@@ -130,6 +135,18 @@ export class ExportOrchestrator {
 				data = this.csvExporter.export(cards);
 				ext = 'csv';
 				break;
+			case 'opml':
+				data = this.opmlExporter.export(cards, connections);
+				ext = 'opml';
+				break;
+			case 'ics': {
+				// Only dated cards and tasks have a calendar representation
+				const calendarCards = cards.filter(isCalendarCard);
+				data = this.icsExporter.export(calendarCards);
+				ext = 'ics';
+				cardCount = calendarCards.length;
+				break;
+			}
 		}
 
 		// Generate filename with timestamp
@@ -140,7 +157,7 @@ export class ExportOrchestrator {
 			data,
 			filename,
 			format,
-			cardCount: cards.length,
+			cardCount,
 		};
 	}
 }
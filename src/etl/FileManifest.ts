@@ -0,0 +1,110 @@
+// Isometry v5 — File Manifest
+// Per-dataset record of the files a directory import was built from, used to
+// re-import only what changed.
+//
+// Design:
+//   - An entry is (path, size, mtime, content hash); the native side compares
+//     size + mtime against the stored manifest and only reads, hashes and parses
+//     files that differ, so unchanged files arrive without cards
+//   - A file whose path disappeared while a new path with the same content hash
+//     appeared is a rename, not a delete + insert
+//   - Pure functions — persistence lives in database/queries/dataset-files
+
+import type { CanonicalCard } from './types';
+
+/**
+ * One file of a directory dataset.
+ */
+export interface ManifestEntry {
+	/** Absolute file path */
+	path: string;
+	/** File size in bytes */
+	size: number;
+	/** Modification time (ISO 8601) */
+	mtime: string;
+	/** Hex content hash (SHA-256) */
+	hash: string;
+}
+
+/**
+ * A file reported by a directory re-scan. `cards` is present only when the
+ * file was (re)parsed — new, renamed or changed since the stored manifest.
+ */
+export interface ReimportFile extends ManifestEntry {
+	cards?: CanonicalCard[];
+}
+
+/**
+ * Classification of a re-scan against the stored manifest.
+ */
+export interface ManifestDiff {
+	added: string[];
+	modified: string[];
+	removed: string[];
+	renamed: Array<{ from: string; to: string }>;
+	unchanged: string[];
+}
+
+/**
+ * One changed file in a re-import preview, with the card changes it causes.
+ * Paths are relative to the dataset directory; an empty path groups cards that
+ * no longer belong to any scanned file.
+ */
+export interface ReimportFileChange {
+	path: string;
+	/** Path before a rename, null otherwise */
+	previousPath: string | null;
+	change: 'added' | 'modified' | 'removed' | 'renamed';
+	cards: Array<{ id: string; name: string; change: 'new' | 'modified' | 'deleted' | 'renamed' }>;
+}
+
+/**
+ * Classify current files against the previous manifest.
+ * Paths present in both are unchanged when their hashes match, modified otherwise.
+ * Removed and added paths sharing a content hash are paired as renames.
+ */
+export function diffManifest(previous: readonly ManifestEntry[], current: readonly ManifestEntry[]): ManifestDiff {
+	const previousByPath = new Map(previous.map((entry) => [entry.path, entry]));
+	const currentPaths = new Set(current.map((entry) => entry.path));
+	const diff: ManifestDiff = { added: [], modified: [], removed: [], renamed: [], unchanged: [] };
+
+	// Removed paths grouped by hash, in manifest order, for rename pairing
+	const removedByHash = new Map<string, string[]>();
+	for (const entry of previous) {
+		if (currentPaths.has(entry.path)) continue;
+		const paths = removedByHash.get(entry.hash) ?? [];
+		paths.push(entry.path);
+		removedByHash.set(entry.hash, paths);
+	}
+
+	const renamedFrom = new Set<string>();
+	for (const entry of current) {
+		const before = previousByPath.get(entry.path);
+		if (before) {
+			(before.hash === entry.hash ? diff.unchanged : diff.modified).push(entry.path);
+			continue;
+		}
+		const from = removedByHash.get(entry.hash)?.shift();
+		if (from !== undefined) {
+			diff.renamed.push({ from, to: entry.path });
+			renamedFrom.add(from);
+		} else {
+			diff.added.push(entry.path);
+		}
+	}
+
+	for (const entry of previous) {
+		if (!currentPaths.has(entry.path) && !renamedFrom.has(entry.path)) diff.removed.push(entry.path);
+	}
+
+	return diff;
+}
+
+/**
+ * Path relative to the dataset directory, for display.
+ */
+export function relativeFilePath(path: string, directoryPath: string | null): string {
+	if (!directoryPath) return path;
+	const root = directoryPath.endsWith('/') ? directoryPath : `${directoryPath}/`;
+	return path.startsWith(root) ? path.slice(root.length) : path;
+}
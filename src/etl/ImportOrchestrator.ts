@@ -2,18 +2,45 @@
 // Wires parser -> dedup -> writer -> catalog into end-to-end pipeline.
 
 import type { Database } from '../database/Database';
+import { listEnricherSettings } from '../database/queries/enricher-settings';
 import { endTrace, startTrace } from '../profiling/PerfTrace';
 import { CatalogWriter } from './CatalogWriter';
 import { DedupEngine } from './DedupEngine';
 import { AppleNotesParser, type ParsedFile } from './parsers/AppleNotesParser';
+import { BookmarksParser, isBookmarksDocument } from './parsers/BookmarksParser';
 import { CSVParser } from './parsers/CSVParser';
-import { ExcelParser } from './parsers/ExcelParser';
+import { EmailParser } from './parsers/EmailParser';
+import { ExcelParser, type ExcelSheetInfo } from './parsers/ExcelParser';
 import { HTMLParser } from './parsers/HTMLParser';
+import { ICSParser } from './parsers/ICSParser';
 import { JSONParser } from './parsers/JSONParser';
 import { MarkdownParser } from './parsers/MarkdownParser';
+import { ObsidianParser, type VaultFile } from './parsers/ObsidianParser';
+import { OPMLParser } from './parsers/OPMLParser';
+import { VCardParser } from './parsers/VCardParser';
 import { SQLiteWriter } from './SQLiteWriter';
-import { runEnrichmentPipeline } from './enrichment';
-import type { CanonicalCard, CanonicalConnection, ImportResult, ParseError, SourceType } from './types';
+import { resolveEnrichedIds, runEnrichment } from './enrichment';
+import type {
+	CanonicalCard,
+	CanonicalConnection,
+	CanonicalPropertyValue,
+	ImportResult,
+	ParseError,
+	SourceType,
+} from './types';
+
+/**
+ * Outcome of ImportOrchestrator.reenrich().
+ */
+export interface ReenrichResult {
+	/** Cards the enrichers created (e.g. resource cards for links) */
+	insertedIds: string[];
+	/** Existing cards the enrichers changed */
+	updatedIds: string[];
+	connections_created: number;
+	/** Card IDs each enricher created or changed, by enricher id */
+	enriched: Record<string, string[]>;
+}
 
 export interface ImportOptions {
 	isBulkImport?: boolean;
@@ -30,12 +57,19 @@ export class ImportOrchestrator {
 	/** Optional progress callback — fires at each batch boundary during writeCards */
 	onProgress: ((processed: number, total: number, rate: number) => void) | null = null;
 
+	private db: Database;
 	private dedup: DedupEngine;
 	private writer: SQLiteWriter;
 	private catalog: CatalogWriter;
 	private parsers = {
 		apple_notes: new AppleNotesParser(),
 		markdown: new MarkdownParser(),
+		obsidian: new ObsidianParser(),
+		opml: new OPMLParser(),
+		ics: new ICSParser(),
+		vcard: new VCardParser(),
+		email: new EmailParser(),
+		bookmarks: new BookmarksParser(),
 		csv: new CSVParser(),
 		json: new JSONParser(),
 		excel: new ExcelParser(),
@@ -43,6 +77,7 @@ export class ImportOrchestrator {
 	};
 
 	constructor(db: Database) {
+		this.db = db;
 		this.dedup = new DedupEngine(db);
 		this.writer = new SQLiteWriter(db);
 		this.catalog = new CatalogWriter(db);
@@ -66,10 +101,15 @@ export class ImportOrchestrator {
 		let cards: CanonicalCard[] = [];
 		let connections: CanonicalConnection[] = [];
 
+		// Bookmark exports share the .html extension with web clippings; route them
+		// to BookmarksParser so they dedup and catalog as their own source.
+		const resolved: SourceType =
+			source === 'html' && typeof data === 'string' && isBookmarksDocument(data) ? 'bookmarks' : source;
+
 		// Step 1: Parse source data
 		try {
 			startTrace('etl:parse');
-			const parsed = await this.parse(source, data, options);
+			const parsed = await this.parse(resolved, data, options);
 			endTrace('etl:parse');
 			cards = parsed.cards;
 			connections = parsed.connections;
@@ -82,12 +122,13 @@ export class ImportOrchestrator {
 
 		// Step 2: Enrich (derive fields, normalize, split hierarchies)
 		startTrace('etl:enrich');
-		runEnrichmentPipeline(cards, source);
+		const enrichment = runEnrichment(cards, resolved, listEnricherSettings(this.db));
+		connections.push(...enrichment.connections);
 		endTrace('etl:enrich');
 
 		// Step 3: Deduplicate
 		startTrace('etl:dedup');
-		const dedupResult = this.dedup.process(cards, connections, source);
+		const dedupResult = this.dedup.process(cards, connections, resolved);
 		endTrace('etl:dedup');
 
 		// Step 4: Determine if bulk import optimization applies
@@ -105,6 +146,7 @@ export class ImportOrchestrator {
 		startTrace('etl:write');
 		await this.writer.writeCards(dedupResult.toInsert, isBulkImport, progressCallback);
 		await this.writer.updateCards(dedupResult.toUpdate);
+		await this.writer.writeProperties([...dedupResult.toInsert, ...dedupResult.toUpdate]);
 		await this.writer.writeConnections(dedupResult.connections);
 		endTrace('etl:write');
 
@@ -127,6 +169,11 @@ export class ImportOrchestrator {
 			deletedIds: dedupResult.deletedIds,
 			errors_detail: errors,
 		};
+		const enriched = resolveEnrichedIds(enrichment.changed, dedupResult.sourceIdMap, [
+			...result.insertedIds,
+			...result.updatedIds,
+		]);
+		if (Object.keys(enriched).length > 0) result.enriched = enriched;
 
 		// Step 7: Record in catalog
 		const record: {
@@ -137,8 +184,8 @@ export class ImportOrchestrator {
 			completed_at: string;
 			result: ImportResult;
 		} = {
-			source,
-			sourceName: this.getSourceName(source, options?.filename),
+			source: resolved,
+			sourceName: this.getSourceName(resolved, options?.filename),
 			started_at: startTime,
 			completed_at: new Date().toISOString(),
 			result,
@@ -151,6 +198,95 @@ export class ImportOrchestrator {
 		return result;
 	}
 
+	/**
+	 * Re-run enrichers over cards already in the database (enrich:backfill).
+	 * Cards are enriched per source with that source's saved settings, so an
+	 * enricher switched on after an import can catch up without re-importing.
+	 * Only imported cards (with a source and source_id) are considered.
+	 *
+	 * @param enricherIds - Restrict the run to these enrichers (default: all enabled)
+	 */
+	async reenrich(enricherIds?: readonly string[]): Promise<ReenrichResult> {
+		const settings = listEnricherSettings(this.db);
+		const result: ReenrichResult = { insertedIds: [], updatedIds: [], connections_created: 0, enriched: {} };
+
+		const bySource = new Map<string, CanonicalCard[]>();
+		for (const card of this.loadEnrichableCards()) {
+			const group = bySource.get(card.source);
+			if (group) group.push(card);
+			else bySource.set(card.source, [card]);
+		}
+
+		for (const [source, cards] of bySource) {
+			const existingCount = cards.length;
+			const idBySourceId = new Map(cards.map((c) => [c.source_id, c.id]));
+			const run = runEnrichment(cards, source, settings, enricherIds);
+
+			// Existing cards the enrichers changed are rewritten in place
+			const changedSourceIds = new Set(Object.values(run.changed).flat());
+			const changedCards = cards.slice(0, existingCount).filter((c) => changedSourceIds.has(c.source_id));
+			await this.writer.updateCards(changedCards);
+			await this.writer.writeProperties(changedCards);
+
+			// Created cards and new connections go through dedup like an import
+			const dedupResult = this.dedup.process(cards.slice(existingCount), run.connections, source);
+			await this.writer.writeCards(dedupResult.toInsert);
+			await this.writer.writeConnections(dedupResult.connections);
+			for (const [sourceId, id] of dedupResult.sourceIdMap) idBySourceId.set(sourceId, id);
+
+			const insertedIds = dedupResult.toInsert.map((c) => c.id);
+			const updatedIds = changedCards.map((c) => c.id);
+			result.insertedIds.push(...insertedIds);
+			result.updatedIds.push(...updatedIds);
+			result.connections_created += dedupResult.connections.length;
+			const enriched = resolveEnrichedIds(run.changed, idBySourceId, [...insertedIds, ...updatedIds]);
+			for (const [enricherId, ids] of Object.entries(enriched)) {
+				result.enriched[enricherId] = [...(result.enriched[enricherId] ?? []), ...ids];
+			}
+		}
+
+		return result;
+	}
+
+	/**
+	 * Non-deleted imported cards as CanonicalCards, with their custom
+	 * properties keyed by definition label (as enrichers and parsers write them).
+	 */
+	private loadEnrichableCards(): CanonicalCard[] {
+		const rows = this.db
+			.prepare<Record<string, unknown>>(
+				`SELECT * FROM cards
+         WHERE deleted_at IS NULL AND source IS NOT NULL AND source_id IS NOT NULL
+         ORDER BY source, sort_order, created_at`,
+			)
+			.all();
+
+		const properties = new Map<string, Record<string, CanonicalPropertyValue>>();
+		const propertyRows = this.db
+			.prepare<{ card_id: string; label: string; value: string | number }>(
+				`SELECT cp.card_id, pd.label, cp.value
+         FROM card_properties cp
+         JOIN property_definitions pd ON pd.key = cp.key`,
+			)
+			.all();
+		for (const row of propertyRows) {
+			const values = properties.get(row.card_id) ?? {};
+			values[row.label] = row.value;
+			properties.set(row.card_id, values);
+		}
+
+		return rows.map((row) => {
+			const card = {
+				...row,
+				tags: JSON.parse((row['tags'] as string | null) ?? '[]') as string[],
+				is_collective: row['is_collective'] === 1,
+			} as unknown as CanonicalCard;
+			const values = properties.get(card.id);
+			if (values) card.properties = values;
+			return card;
+		});
+	}
+
 	/**
 	 * Parse data based on source type.
 	 */
@@ -172,10 +308,40 @@ export class ImportOrchestrator {
 				return this.parsers.markdown.parse(files, options as any);
 			}
 
+			case 'obsidian': {
+				// Data is JSON array of every vault file (attachments with empty content)
+				const files = typeof data === 'string' ? (JSON.parse(data) as VaultFile[]) : (data as VaultFile[]);
+				return this.parsers.obsidian.parse(files, options as any);
+			}
+
+			case 'opml': {
+				// OPMLParser expects the raw XML text
+				return this.parsers.opml.parse(data as string, options as any);
+			}
+
+			case 'ics': {
+				// ICSParser expects the raw iCalendar text
+				return this.parsers.ics.parse(data as string, options as any);
+			}
+
+			case 'vcard': {
+				// VCardParser expects the raw .vcf text
+				return this.parsers.vcard.parse(data as string, options as any);
+			}
+
+			case 'email': {
+				// EmailParser expects the raw mbox or .eml text
+				return this.parsers.email.parse(data as string, options as any);
+			}
+
+			case 'bookmarks': {
+				// BookmarksParser expects the raw bookmark file HTML
+				return this.parsers.bookmarks.parse(data as string, options as any);
+			}
+
 			case 'csv': {
 				// CSVParser expects ParsedFile[] (path is used for source_id)
-				const files = typeof data === 'string' ? (JSON.parse(data) as ParsedFile[]) : (data as ParsedFile[]);
-				return this.parsers.csv.parse(files, options as any);
+				return this.parsers.csv.parse(this.csvFiles(data, options?.filename), options as any);
 			}
 
 			case 'json': {
@@ -192,22 +358,8 @@ export class ImportOrchestrator {
 			}
 
 			case 'excel': {
-				// ExcelParser expects ArrayBuffer.
-				// Web imports arrive as ArrayBuffer directly.
-				// Native (macOS/iOS) imports arrive as base64-encoded strings
-				// because Swift's evaluateJavaScript cannot pass ArrayBuffer.
-				let buffer: ArrayBuffer;
-				if (typeof data === 'string') {
-					// Decode base64 string to ArrayBuffer
-					const binaryString = atob(data);
-					const bytes = new Uint8Array(binaryString.length);
-					for (let i = 0; i < binaryString.length; i++) {
-						bytes[i] = binaryString.charCodeAt(i);
-					}
-					buffer = bytes.buffer;
-				} else {
-					buffer = data as ArrayBuffer;
-				}
+				// ExcelParser expects ArrayBuffer
+				const buffer = this.excelBuffer(data);
 				return await this.parsers.excel.parse(buffer, options as any);
 			}
 
@@ -238,6 +390,71 @@ export class ImportOrchestrator {
 		}
 	}
 
+	/**
+	 * Header row of an import, for mapping profile suggestion.
+	 * Only the tabular sources (csv, excel, json) have headers; others return [].
+	 * `sheet` selects the Excel sheet (default: first sheet).
+	 */
+	async readHeaders(
+		source: SourceType,
+		data: string | ParsedFile[] | ArrayBuffer,
+		filename?: string,
+		sheet?: string,
+	): Promise<string[]> {
+		switch (source) {
+			case 'csv':
+				return this.parsers.csv.readHeaders(this.csvFiles(data, filename));
+			case 'excel':
+				return this.parsers.excel.readHeaders(this.excelBuffer(data), sheet);
+			case 'json':
+				return typeof data === 'string' && !this._looksLikeAppleNotes(data) ? this.parsers.json.readHeaders(data) : [];
+			default:
+				return [];
+		}
+	}
+
+	/**
+	 * Sheets of an Excel workbook with row counts and header rows, for the
+	 * sheet picker shown before a multi-sheet import.
+	 */
+	async listExcelSheets(data: string | ArrayBuffer): Promise<ExcelSheetInfo[]> {
+		return this.parsers.excel.listSheets(this.excelBuffer(data));
+	}
+
+	/**
+	 * CSV payload to ParsedFile[]: a JSON array of files (directory imports) or
+	 * the raw text of a single file picked in the import dialog.
+	 */
+	private csvFiles(data: string | ParsedFile[] | ArrayBuffer, filename?: string): ParsedFile[] {
+		if (Array.isArray(data)) return data;
+		const text = typeof data === 'string' ? data : new TextDecoder().decode(data);
+		if (text.trimStart().startsWith('[')) {
+			try {
+				const parsed: unknown = JSON.parse(text);
+				if (Array.isArray(parsed)) return parsed as ParsedFile[];
+			} catch {
+				// Not JSON — a CSV whose first header starts with '['
+			}
+		}
+		return [{ path: filename ?? 'import.csv', content: text }];
+	}
+
+	/**
+	 * Excel payload to ArrayBuffer.
+	 * Web imports arrive as ArrayBuffer directly. Native (macOS/iOS) imports
+	 * arrive as base64-encoded strings because Swift's evaluateJavaScript
+	 * cannot pass ArrayBuffer.
+	 */
+	private excelBuffer(data: string | ParsedFile[] | ArrayBuffer): ArrayBuffer {
+		if (typeof data !== 'string') return data as ArrayBuffer;
+		const binaryString = atob(data);
+		const bytes = new Uint8Array(binaryString.length);
+		for (let i = 0; i < binaryString.length; i++) {
+			bytes[i] = binaryString.charCodeAt(i);
+		}
+		return bytes.buffer;
+	}
+
 	/**
 	 * Sniff JSON content to detect Apple Notes (alto-index) format.
 	 *
@@ -305,6 +522,12 @@ export class ImportOrchestrator {
 		const baseNames: Record<SourceType, string> = {
 			apple_notes: 'Apple Notes',
 			markdown: 'Markdown Files',
+			obsidian: 'Obsidian Vault',
+			opml: 'OPML Outline',
+			ics: 'iCalendar',
+			vcard: 'vCard Contacts',
+			email: 'Email Mailbox',
+			bookmarks: 'Browser Bookmarks',
 			excel: 'Excel Spreadsheet',
 			csv: 'CSV File',
 			json: 'JSON Data',
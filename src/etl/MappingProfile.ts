@@ -0,0 +1,665 @@
+// Isometry v5 — Import Mapping Profiles
+// Named, reusable column mappings for the tabular parsers (CSV, Excel, JSON).
+//
+// Design:
+//   - A profile maps source columns onto card fields and carries the rules an
+//     export needs: date format, tag separator, type coercion of the columns
+//     kept as custom properties, and card_type rules
+//   - Profiles are suggested by header fingerprint — the sorted, normalized
+//     column names — so a recurring export is recognized even when its columns
+//     are reordered; near matches (a column added or dropped) score lower
+//   - A column mapped to source_id keys cards by that column instead of by row
+//     position, so re-importing next week's export updates cards in place
+//   - Relationship rules turn foreign-key columns into connections whose
+//     endpoints are card source_ids, resolved to card ids by DedupEngine
+//   - Pure functions — persistence lives in database/queries/mapping-profiles
+
+import type { CardType } from '../database/queries/types';
+import { collectUnmappedProperties } from './parsers/properties';
+import type { CanonicalCard, CanonicalConnection, CanonicalPropertyValue } from './types';
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/** Card field a column can map onto; 'skip' drops the column entirely */
+export type MappedField =
+	| 'name'
+	| 'content'
+	| 'summary'
+	| 'tags'
+	| 'folder'
+	| 'status'
+	| 'priority'
+	| 'url'
+	| 'created_at'
+	| 'modified_at'
+	| 'due_at'
+	| 'completed_at'
+	| 'event_start'
+	| 'event_end'
+	| 'latitude'
+	| 'longitude'
+	| 'location_name'
+	| 'card_type'
+	| 'source_id'
+	| 'skip';
+
+/** Value type an unmapped (custom property) column is coerced to */
+export type CoercionType = 'text' | 'number' | 'boolean' | 'date';
+
+/**
+ * Assigns a card_type when a column's value matches. Matching is
+ * case-insensitive; a rule with neither `equals` nor `contains` matches any
+ * non-empty value.
+ */
+export interface CardTypeRule {
+	column: string;
+	equals?: string;
+	contains?: string;
+	cardType: CardType;
+}
+
+/**
+ * Turns a foreign-key column into connections, e.g. `manager_id` referencing
+ * rows by `employee_id` with label `reports_to`.
+ *
+ * A reference resolves to the row of the same import whose `references`
+ * column holds the value. Failing that — or always, when `targetProfileId`
+ * names another profile — it resolves by key: the source_id column of this
+ * profile (or of the target profile) equal to the value, which also reaches
+ * cards imported earlier.
+ */
+export interface RelationshipRule {
+	/** Column holding the reference */
+	column: string;
+	/** Column identifying the referenced rows */
+	references: string;
+	/** Connection label */
+	label: string;
+	/** Profile whose keyed cards are referenced (default: this profile) */
+	targetProfileId?: string;
+	/** Separator for cells holding several references (default: single reference) */
+	separator?: string;
+}
+
+/**
+ * How rows of an export become cards.
+ */
+export interface MappingRules {
+	/** Source column -> card field. Columns not listed become custom properties */
+	columns: Record<string, MappedField>;
+	/**
+	 * Date pattern for date fields and date coercions, built from YYYY, YY, MM,
+	 * M, DD, D, HH, H, mm, ss (e.g. 'DD/MM/YYYY'), or 'unix' / 'unix_ms'.
+	 * Patterns are read as UTC. Absent: ISO 8601 and anything Date accepts.
+	 */
+	dateFormat?: string;
+	/** Separator between tags in one cell (default: comma or semicolon) */
+	tagSeparator?: string;
+	/** Custom property column -> value type */
+	coercions?: Record<string, CoercionType>;
+	/** First matching rule wins; a card_type column with a valid value takes precedence */
+	cardTypeRules?: CardTypeRule[];
+	/** card_type when nothing else assigns one (default: 'note') */
+	defaultCardType?: CardType;
+	/** Foreign-key columns that become connections between rows */
+	relationships?: RelationshipRule[];
+}
+
+/**
+ * A saved mapping profile.
+ */
+export interface MappingProfile {
+	id: string;
+	/** Display name, unique case-insensitively */
+	name: string;
+	/** Normalized headers of the export the profile was made for */
+	headers: string[];
+	/** headerFingerprint(headers) */
+	fingerprint: string;
+	rules: MappingRules;
+	created_at: string;
+	updated_at: string;
+}
+
+/**
+ * A profile suggested for a set of headers. Score is the Jaccard similarity of
+ * the normalized header sets; 1 is an exact fingerprint match.
+ */
+export interface MappingSuggestion {
+	profile: MappingProfile;
+	score: number;
+}
+
+/**
+ * Per-parser card defaults for rows mapped through a profile.
+ */
+export interface MappedRowContext {
+	/** Row position, used for sort_order */
+	index: number;
+	source: string;
+	mimeType: string | null;
+	/** source_id when the profile maps no source_id column */
+	sourceId: string;
+	defaultTimestamp?: string;
+}
+
+const MAPPED_FIELDS: ReadonlySet<string> = new Set<MappedField>([
+	'name',
+	'content',
+	'summary',
+	'tags',
+	'folder',
+	'status',
+	'priority',
+	'url',
+	'created_at',
+	'modified_at',
+	'due_at',
+	'completed_at',
+	'event_start',
+	'event_end',
+	'latitude',
+	'longitude',
+	'location_name',
+	'card_type',
+	'source_id',
+	'skip',
+]);
+
+const CARD_TYPES: ReadonlySet<string> = new Set<CardType>([
+	'note',
+	'task',
+	'event',
+	'resource',
+	'person',
+	'reference',
+	'message',
+	'media',
+]);
+
+const COERCION_TYPES: ReadonlySet<string> = new Set<CoercionType>(['text', 'number', 'boolean', 'date']);
+
+/** Minimum score for a near (non-exact) fingerprint match to be suggested */
+const SUGGESTION_THRESHOLD = 0.75;
+
+const DATE_TOKEN_RE = /YYYY|YY|MM|M|DD|D|HH|H|mm|ss/g;
+const TRUE_VALUES = new Set(['true', 'yes', 'y', '1', 'x', '\u2713']);
+const FALSE_VALUES = new Set(['false', 'no', 'n', '0', '']);
+
+// ---------------------------------------------------------------------------
+// Fingerprints
+// ---------------------------------------------------------------------------
+
+/**
+ * Header name for comparison: lowercased, with runs of whitespace, '_' and '-'
+ * collapsed to one space ("Due_Date" and "due date" are the same column).
+ */
+export function normalizeHeader(header: string): string {
+	return header
+		.toLowerCase()
+		.replace(/[\s_-]+/g, ' ')
+		.trim();
+}
+
+/**
+ * Order-independent fingerprint of a header row.
+ */
+export function headerFingerprint(headers: readonly string[]): string {
+	return normalizeHeaders(headers).join('|');
+}
+
+/**
+ * Best saved profile for a header row: an exact fingerprint match, otherwise
+ * the most similar profile scoring at least 0.75. Null when nothing is close.
+ */
+export function suggestMappingProfile(
+	headers: readonly string[],
+	profiles: readonly MappingProfile[],
+): MappingSuggestion | null {
+	const current = normalizeHeaders(headers);
+	if (current.length === 0) return null;
+	const fingerprint = current.join('|');
+
+	let best: MappingSuggestion | null = null;
+	for (const profile of profiles) {
+		if (profile.fingerprint === fingerprint) return { profile, score: 1 };
+		const saved = new Set(profile.headers);
+		const shared = current.filter((header) => saved.has(header)).length;
+		const score = shared / (current.length + saved.size - shared);
+		if (score >= SUGGESTION_THRESHOLD && (!best || score > best.score)) best = { profile, score };
+	}
+	return best;
+}
+
+/**
+ * Sorted, de-duplicated normalized headers.
+ * Blank and PapaParse `__`-prefixed headers are ignored.
+ */
+export function normalizeHeaders(headers: readonly string[]): string[] {
+	const set = new Set<string>();
+	for (const header of headers) {
+		if (header.startsWith('__')) continue;
+		const normalized = normalizeHeader(header);
+		if (normalized) set.add(normalized);
+	}
+	return [...set].sort();
+}
+
+// ---------------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------------
+
+/**
+ * Check that a rules object only uses known fields, card types and coercions.
+ *
+ * @throws {Error} naming the first invalid entry
+ */
+export function validateMappingRules(rules: MappingRules): void {
+	if (!rules || typeof rules.columns !== 'object' || rules.columns === null) {
+		throw new Error('Mapping rules need a columns object');
+	}
+	for (const [column, field] of Object.entries(rules.columns)) {
+		if (!MAPPED_FIELDS.has(field)) throw new Error(`Unknown card field "${field}" for column "${column}"`);
+	}
+	for (const [column, type] of Object.entries(rules.coercions ?? {})) {
+		if (!COERCION_TYPES.has(type)) throw new Error(`Unknown value type "${type}" for column "${column}"`);
+	}
+	for (const rule of rules.cardTypeRules ?? []) {
+		if (!CARD_TYPES.has(rule.cardType)) throw new Error(`Unknown card type "${rule.cardType}"`);
+	}
+	if (rules.defaultCardType !== undefined && !CARD_TYPES.has(rules.defaultCardType)) {
+		throw new Error(`Unknown card type "${rules.defaultCardType}"`);
+	}
+	for (const rule of rules.relationships ?? []) {
+		if (!rule.column?.trim() || !rule.references?.trim()) {
+			throw new Error('Relationships need a column and the column it references');
+		}
+		if (!rule.label?.trim()) throw new Error(`Relationship on column "${rule.column}" needs a label`);
+	}
+}
+
+// ---------------------------------------------------------------------------
+// Row mapping
+// ---------------------------------------------------------------------------
+
+/**
+ * Map one row (CSV record, sheet row or JSON object) onto a card.
+ *
+ * - Columns are found by normalized header, so case and separator changes in
+ *   the export do not break the profile
+ * - Several columns mapped to content are joined with blank lines; several
+ *   mapped to tags are merged
+ * - Unparseable dates fall back like a missing value (created_at to the
+ *   default timestamp, modified_at to created_at, the rest to null)
+ */
+export function mapRowWithProfile(
+	row: Record<string, unknown>,
+	profile: MappingProfile,
+	context: MappedRowContext,
+): CanonicalCard {
+	const { rules } = profile;
+	const keys = new Map<string, string>();
+	for (const key of Object.keys(row)) {
+		const normalized = normalizeHeader(key);
+		if (!keys.has(normalized)) keys.set(normalized, key);
+	}
+	const keyOf = (column: string): string | undefined =>
+		column in row ? column : keys.get(normalizeHeader(column));
+
+	// Field -> row keys mapped onto it, in profile order
+	const fields = new Map<MappedField, string[]>();
+	const mappedKeys: string[] = [];
+	for (const [column, field] of Object.entries(rules.columns)) {
+		const key = keyOf(column);
+		if (key === undefined) continue;
+		mappedKeys.push(key);
+		fields.set(field, [...(fields.get(field) ?? []), key]);
+	}
+
+	const values = (field: MappedField): unknown[] => (fields.get(field) ?? []).map((key) => row[key]);
+	const text = (field: MappedField): string | null => {
+		for (const value of values(field)) {
+			const str = toText(value);
+			if (str) return str;
+		}
+		return null;
+	};
+	const date = (field: MappedField): string | null => {
+		for (const value of values(field)) {
+			const parsed = parseDateValue(value, rules.dateFormat);
+			if (parsed) return parsed;
+		}
+		return null;
+	};
+	const number = (field: MappedField): number | null => {
+		for (const value of values(field)) {
+			const parsed = toNumber(value);
+			if (parsed !== null) return parsed;
+		}
+		return null;
+	};
+
+	const contentParts = values('content')
+		.map((value) => (isStructured(value) ? JSON.stringify(value) : toText(value)))
+		.filter((part): part is string => !!part);
+	const content = contentParts.length > 0 ? contentParts.join('\n\n') : null;
+	const createdAt = date('created_at') ?? context.defaultTimestamp ?? new Date().toISOString();
+	const key = text('source_id');
+
+	const properties = coerceProperties(row, collectUnmappedProperties(row, mappedKeys), rules, keyOf);
+
+	return {
+		id: crypto.randomUUID(),
+		card_type: resolveCardType(row, rules, text('card_type'), keyOf),
+		name: text('name') ?? `Row ${context.index + 1}`,
+		content,
+		summary: text('summary') ?? (content ? content.slice(0, 200) : null),
+
+		latitude: number('latitude'),
+		longitude: number('longitude'),
+		location_name: text('location_name'),
+
+		created_at: createdAt,
+		modified_at: date('modified_at') ?? createdAt,
+		due_at: date('due_at'),
+		completed_at: date('completed_at'),
+		event_start: date('event_start'),
+		event_end: date('event_end'),
+
+		folder: text('folder'),
+		tags: splitTags(values('tags'), rules.tagSeparator),
+		status: text('status'),
+
+		priority: Math.round(number('priority') ?? 0),
+		sort_order: context.index,
+
+		url: text('url'),
+		mime_type: context.mimeType,
+		is_collective: false,
+
+		source: context.source,
+		// Profile-scoped so keys from unrelated exports of the same source never collide
+		source_id: key ? `${profile.id}:${key}` : context.sourceId,
+		source_url: null,
+
+		deleted_at: null,
+		...(properties ? { properties } : {}),
+	};
+}
+
+/**
+ * Connections for the profile's relationship rules across all rows of one
+ * import. Rows pair each source record with the card mapped from it.
+ *
+ * - Endpoints are card source_ids; DedupEngine resolves them to card ids and
+ *   drops connections whose target never resolves
+ * - Self-references and repeated (source, target, label) triples are skipped
+ */
+export function extractRelationships(
+	rows: ReadonlyArray<{ row: Record<string, unknown>; card: CanonicalCard }>,
+	profile: MappingProfile,
+): CanonicalConnection[] {
+	const rules = profile.rules.relationships ?? [];
+	if (rules.length === 0) return [];
+
+	const now = new Date().toISOString();
+	const keyColumn = Object.entries(profile.rules.columns).find(([, field]) => field === 'source_id')?.[0];
+	const connections: CanonicalConnection[] = [];
+	const seen = new Set<string>();
+
+	// references column -> cell value -> source_id of the first row holding it
+	const indexes = new Map<string, Map<string, string>>();
+	const indexOf = (column: string): Map<string, string> => {
+		let index = indexes.get(column);
+		if (!index) {
+			index = new Map();
+			for (const { row, card } of rows) {
+				const value = toText(cellOf(row, column));
+				if (value && !index.has(value)) index.set(value, card.source_id);
+			}
+			indexes.set(column, index);
+		}
+		return index;
+	};
+
+	for (const rule of rules) {
+		const ownKey = keyColumn !== undefined && normalizeHeader(keyColumn) === normalizeHeader(rule.references);
+		for (const { row, card } of rows) {
+			const cell = cellOf(row, rule.column);
+			const values = Array.isArray(cell)
+				? cell.map(toText)
+				: rule.separator
+					? (toText(cell)?.split(rule.separator) ?? [])
+					: [toText(cell)];
+
+			for (const raw of values) {
+				const value = raw?.trim();
+				if (!value) continue;
+				const target = rule.targetProfileId
+					? `${rule.targetProfileId}:${value}`
+					: (indexOf(rule.references).get(value) ?? (ownKey ? `${profile.id}:${value}` : undefined));
+				if (target === undefined || target === card.source_id) continue;
+
+				const dedupKey = `${card.source_id}\u0000${target}\u0000${rule.label}`;
+				if (seen.has(dedupKey)) continue;
+				seen.add(dedupKey);
+				connections.push({
+					id: crypto.randomUUID(),
+					source_id: card.source_id,
+					target_id: target,
+					via_card_id: null,
+					label: rule.label,
+					weight: 1,
+					created_at: now,
+				});
+			}
+		}
+	}
+	return connections;
+}
+
+/**
+ * Cell of `column` in a row, matching headers by normalized name.
+ */
+function cellOf(row: Record<string, unknown>, column: string): unknown {
+	if (column in row) return row[column];
+	const wanted = normalizeHeader(column);
+	const key = Object.keys(row).find((k) => normalizeHeader(k) === wanted);
+	return key === undefined ? undefined : row[key];
+}
+
+/**
+ * Parse a cell into an ISO 8601 timestamp using the profile's date format.
+ * Returns null for empty or unparseable values.
+ */
+export function parseDateValue(value: unknown, format?: string): string | null {
+	if (value instanceof Date) return Number.isNaN(value.getTime()) ? null : value.toISOString();
+
+	if (typeof value === 'number' || format === 'unix' || format === 'unix_ms') {
+		const num = typeof value === 'number' ? value : Number(String(value ?? '').trim());
+		if (!Number.isFinite(num) || String(value ?? '').trim() === '') return null;
+		// Without an explicit unit, values past 1e11 are milliseconds
+		const ms = format === 'unix_ms' || (format !== 'unix' && Math.abs(num) > 1e11) ? num : num * 1000;
+		const parsed = new Date(ms);
+		return Number.isNaN(parsed.getTime()) ? null : parsed.toISOString();
+	}
+
+	if (typeof value !== 'string' || !value.trim()) return null;
+	const input = value.trim();
+
+	if (!format) {
+		const parsed = new Date(input);
+		return Number.isNaN(parsed.getTime()) ? null : parsed.toISOString();
+	}
+	return parseWithPattern(input, format);
+}
+
+/**
+ * Match a date against a token pattern. A pattern without a time part also
+ * accepts a trailing "HH:mm[:ss]" after a space or 'T'.
+ */
+function parseWithPattern(input: string, format: string): string | null {
+	const tokens: string[] = [];
+	let source = '';
+	let last = 0;
+	for (const match of format.matchAll(DATE_TOKEN_RE)) {
+		source += escapeRegExp(format.slice(last, match.index));
+		// Day, month and time fields accept a missing leading zero
+		source += match[0] === 'YYYY' ? '(\\d{4})' : match[0] === 'YY' ? '(\\d{2})' : '(\\d{1,2})';
+		tokens.push(match[0]);
+		last = match.index! + match[0].length;
+	}
+	source += escapeRegExp(format.slice(last));
+	const hasTime = tokens.some((token) => token === 'HH' || token === 'H');
+	if (!hasTime) source += '(?:[ T](\\d{1,2}):(\\d{2})(?::(\\d{2}))?)?';
+
+	const match = new RegExp(`^${source}$`).exec(input);
+	if (!match) {
+		// Cells already in ISO 8601 (e.g. a re-export) still parse
+		if (!/^\d{4}-\d{2}-\d{2}/.test(input)) return null;
+		const parsed = new Date(input);
+		return Number.isNaN(parsed.getTime()) ? null : parsed.toISOString();
+	}
+
+	let year = Number.NaN;
+	let month = 1;
+	let day = 1;
+	let hour = 0;
+	let minute = 0;
+	let second = 0;
+	tokens.forEach((token, i) => {
+		const num = Number(match[i + 1]);
+		if (token === 'YYYY') year = num;
+		else if (token === 'YY') year = num < 70 ? 2000 + num : 1900 + num;
+		else if (token === 'MM' || token === 'M') month = num;
+		else if (token === 'DD' || token === 'D') day = num;
+		else if (token === 'HH' || token === 'H') hour = num;
+		else if (token === 'mm') minute = num;
+		else second = num;
+	});
+	if (!hasTime && match[tokens.length + 1] !== undefined) {
+		hour = Number(match[tokens.length + 1]);
+		minute = Number(match[tokens.length + 2]);
+		second = Number(match[tokens.length + 3] ?? 0);
+	}
+
+	if (Number.isNaN(year) || hour > 23 || minute > 59 || second > 59) return null;
+	const parsed = new Date(Date.UTC(year, month - 1, day, hour, minute, second));
+	// Date.UTC rolls over (31/02 -> 03/03); reject instead
+	if (parsed.getUTCMonth() !== month - 1 || parsed.getUTCDate() !== day) return null;
+	return parsed.toISOString();
+}
+
+function escapeRegExp(text: string): string {
+	return text.replace(/[.*+?^${}()|[\]\\/]/g, '\\$&');
+}
+
+function isStructured(value: unknown): boolean {
+	return value !== null && typeof value === 'object' && !(value instanceof Date);
+}
+
+/**
+ * Trimmed string form of a cell, null when empty or not a scalar.
+ */
+function toText(value: unknown): string | null {
+	if (value instanceof Date) return Number.isNaN(value.getTime()) ? null : value.toISOString();
+	if (typeof value === 'string') return value.trim() || null;
+	if (typeof value === 'number' || typeof value === 'boolean') return String(value);
+	return null;
+}
+
+/**
+ * Number from a cell, ignoring grouping commas, spaces and currency/percent signs.
+ */
+function toNumber(value: unknown): number | null {
+	if (typeof value === 'number') return Number.isFinite(value) ? value : null;
+	if (typeof value === 'boolean') return value ? 1 : 0;
+	if (typeof value !== 'string') return null;
+	const cleaned = value.replace(/[^\d.eE+-]/g, '');
+	if (!cleaned) return null;
+	const num = Number(cleaned);
+	return Number.isFinite(num) ? num : null;
+}
+
+function toBoolean(value: unknown): boolean | null {
+	if (typeof value === 'boolean') return value;
+	if (typeof value === 'number') return value !== 0;
+	if (typeof value !== 'string') return null;
+	const lower = value.trim().toLowerCase();
+	if (TRUE_VALUES.has(lower)) return true;
+	if (FALSE_VALUES.has(lower)) return false;
+	return null;
+}
+
+/**
+ * Merge tag cells: arrays as-is, strings split on the profile separator.
+ * Duplicates are dropped, first occurrence wins.
+ */
+function splitTags(values: unknown[], separator?: string): string[] {
+	const tags: string[] = [];
+	for (const value of values) {
+		const parts = Array.isArray(value)
+			? value.map(String)
+			: typeof value === 'string'
+				? separator
+					? value.split(separator)
+					: value.split(/[,;]/)
+				: [];
+		for (const part of parts) {
+			const tag = part.trim();
+			if (tag && !tags.includes(tag)) tags.push(tag);
+		}
+	}
+	return tags;
+}
+
+/**
+ * card_type from a card_type column holding a valid type, else the first
+ * matching rule, else the profile default.
+ */
+function resolveCardType(
+	row: Record<string, unknown>,
+	rules: MappingRules,
+	explicit: string | null,
+	keyOf: (column: string) => string | undefined,
+): CardType {
+	const lower = explicit?.toLowerCase();
+	if (lower && CARD_TYPES.has(lower)) return lower as CardType;
+
+	for (const rule of rules.cardTypeRules ?? []) {
+		const key = keyOf(rule.column);
+		const cell = key === undefined ? null : toText(row[key])?.toLowerCase();
+		if (!cell) continue;
+		if (rule.equals !== undefined && cell !== rule.equals.toLowerCase()) continue;
+		if (rule.contains !== undefined && !cell.includes(rule.contains.toLowerCase())) continue;
+		return rule.cardType;
+	}
+	return rules.defaultCardType ?? 'note';
+}
+
+/**
+ * Apply the profile's coercions to captured properties. Values that do not
+ * coerce become null rather than keeping a mistyped value.
+ */
+function coerceProperties(
+	row: Record<string, unknown>,
+	properties: Record<string, CanonicalPropertyValue> | undefined,
+	rules: MappingRules,
+	keyOf: (column: string) => string | undefined,
+): Record<string, CanonicalPropertyValue> | undefined {
+	if (!properties || !rules.coercions) return properties;
+	for (const [column, type] of Object.entries(rules.coercions)) {
+		const key = keyOf(column);
+		if (key === undefined || !(key in properties)) continue;
+		// Coerce the raw cell — capture has already turned Date cells into ISO strings
+		const value = row[key];
+		if (value === null || value === undefined) continue;
+		if (type === 'number') properties[key] = toNumber(value);
+		else if (type === 'boolean') properties[key] = toBoolean(value);
+		else if (type === 'date') properties[key] = parseDateValue(value, rules.dateFormat);
+		else properties[key] = toText(value);
+	}
+	return properties;
+}
@@ -7,6 +7,15 @@
 // - P24 (FTS overhead): Trigger disable/rebuild for bulk imports
 
 import type { Database } from '../database/Database';
+import {
+	coercePropertyValue,
+	createPropertyDefinition,
+	extendEnumValues,
+	getPropertyDefinition,
+	inferPropertyType,
+	normalizePropertyKey,
+	type PropertyDefinition,
+} from '../database/queries/properties';
 import { endTrace, getTraces, startTrace } from '../profiling/PerfTrace';
 import { ENRICHED_FIELD_NAMES } from './enrichment/types';
 import type { CanonicalCard, CanonicalConnection } from './types';
@@ -180,6 +189,79 @@ export class SQLiteWriter {
 		}
 	}
 
+	/**
+	 * Write custom property values captured by tabular parsers (CanonicalCard.properties).
+	 *
+	 * Headers are normalized to property keys. Unknown keys get a definition whose
+	 * type is inferred from this import's values; existing definitions keep their
+	 * type (enum sets are extended). Values that do not fit the type are skipped
+	 * rather than failing the import. Empty values clear the card's value.
+	 *
+	 * Must run after writeCards/updateCards — rows reference cards(id).
+	 *
+	 * @returns Number of property definitions created by this call
+	 */
+	async writeProperties(cards: CanonicalCard[]): Promise<number> {
+		// Group raw values by normalized key (first header seen supplies the label)
+		const columns = new Map<string, { label: string; values: Array<{ cardId: string; raw: unknown }> }>();
+		for (const card of cards) {
+			if (!card.properties) continue;
+			for (const [header, raw] of Object.entries(card.properties)) {
+				const key = normalizePropertyKey(header);
+				if (!key) continue;
+				let column = columns.get(key);
+				if (!column) {
+					column = { label: header.trim(), values: [] };
+					columns.set(key, column);
+				}
+				column.values.push({ cardId: card.id, raw });
+			}
+		}
+		if (columns.size === 0) return 0;
+
+		let created = 0;
+		this.db.transaction(() => {
+			const upsert = this.db.prepare<never>(
+				'INSERT OR REPLACE INTO card_properties (card_id, key, value) VALUES (?, ?, ?)',
+			);
+			const clear = this.db.prepare<never>('DELETE FROM card_properties WHERE card_id = ? AND key = ?');
+
+			for (const [key, column] of columns) {
+				let definition: PropertyDefinition | null = getPropertyDefinition(this.db, key);
+				if (!definition) {
+					const inferred = inferPropertyType(column.values.map((v) => v.raw));
+					definition = createPropertyDefinition(this.db, { key, label: column.label, ...inferred });
+					created++;
+				} else if (definition.type === 'enum') {
+					const incoming = column.values
+						.map((v) => (v.raw === null ? '' : String(v.raw).trim()))
+						.filter((v) => v !== '');
+					extendEnumValues(this.db, key, incoming);
+					definition = getPropertyDefinition(this.db, key)!;
+				}
+
+				for (const { cardId, raw } of column.values) {
+					let value: ReturnType<typeof coercePropertyValue>;
+					try {
+						value = coercePropertyValue(definition, raw);
+					} catch {
+						continue; // Value does not fit the property type — keep the card, drop the value
+					}
+					if (value === null) {
+						clear.run(cardId, key);
+					} else {
+						upsert.run(cardId, key, value);
+					}
+				}
+			}
+
+			upsert.free();
+			clear.free();
+		})();
+
+		return created;
+	}
+
 	/**
 	 * Run FTS optimize on the cards_fts index.
 	 * Merges FTS segments for better query performance.
@@ -0,0 +1,69 @@
+// Isometry v5 — Contact Details Enricher
+// Finds email addresses and phone numbers in a card's content and stores them
+// as comma-separated "Emails" and "Phones" properties.
+//
+// Design:
+//   - Emails are lowercased and de-duplicated in order of appearance.
+//   - A phone number is a run of digit groups (optional +country code and
+//     parenthesized area code) with 10 to 15 digits — enough to skip dates,
+//     times, amounts and short reference numbers.
+//   - Opt-in: adds custom properties to the cards where it finds something.
+
+import type { CanonicalCard } from '../types';
+import { setDerivedProperties } from './properties';
+import type { Enricher, EnrichmentContext } from './types';
+
+const EMAIL_PATTERN = /[a-z0-9._%+-]+@[a-z0-9-]+(?:\.[a-z0-9-]+)*\.[a-z]{2,}/gi;
+
+const PHONE_PATTERN = /(?:\+\d{1,3}[\s.-]?)?(?:\(\d{1,4}\)[\s.-]?)?\d{2,4}(?:[\s.-]\d{2,4}){1,4}/g;
+
+/** ISO dates ("2026-03-04 10") otherwise pass as digit groups */
+const ISO_DATE_PREFIX = /^\d{4}-\d{2}-\d{2}\b/;
+
+/** Digit count range of a phone number (E.164 allows at most 15). */
+const MIN_PHONE_DIGITS = 10;
+const MAX_PHONE_DIGITS = 15;
+
+/**
+ * Email addresses in a text, lowercased and unique.
+ */
+export function extractEmails(text: string): string[] {
+	return [...new Set((text.match(EMAIL_PATTERN) ?? []).map((email) => email.toLowerCase()))];
+}
+
+/**
+ * Phone numbers in a text, as written, unique by their digits.
+ */
+export function extractPhones(text: string): string[] {
+	const byDigits = new Map<string, string>();
+	for (const match of text.match(PHONE_PATTERN) ?? []) {
+		if (ISO_DATE_PREFIX.test(match)) continue;
+		const digits = match.replace(/\D/g, '');
+		if (digits.length < MIN_PHONE_DIGITS || digits.length > MAX_PHONE_DIGITS) continue;
+		if (!byDigits.has(digits)) byDigits.set(digits, match.trim());
+	}
+	return [...byDigits.values()];
+}
+
+/**
+ * ContactDetailsEnricher surfaces emails and phone numbers mentioned in content.
+ */
+export const contactDetailsEnricher: Enricher = {
+	id: 'contact-details',
+	description: 'Email addresses and phone numbers mentioned in the content',
+	appliesTo: '*',
+	defaultEnabled: false,
+
+	enrich(cards: CanonicalCard[], context?: EnrichmentContext): CanonicalCard[] {
+		for (const card of cards) {
+			if (!card.content) continue;
+			const values: Record<string, string> = {};
+			const emails = extractEmails(card.content);
+			const phones = extractPhones(card.content);
+			if (emails.length > 0) values['Emails'] = emails.join(', ');
+			if (phones.length > 0) values['Phones'] = phones.join(', ');
+			if (setDerivedProperties(card, values)) context?.markChanged(card);
+		}
+		return cards;
+	},
+};
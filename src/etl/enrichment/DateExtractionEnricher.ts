@@ -0,0 +1,165 @@
+// Isometry v5 — Date Extraction Enricher
+// Reads natural-language deadlines ("due Friday", "by March 3", "deadline: in
+// 2 weeks") from a card's name and content into due_at.
+//
+// Design:
+//   - A date phrase only counts after a cue word (due, by, deadline, before,
+//     until), so dates merely mentioned in notes are ignored. "by" is common in
+//     prose, so its date must follow on the same line with no ':'/'on' between.
+//   - A possessive date ("by Monday's team", "due to today's rain") is a
+//     noun modifier, not a deadline.
+//   - Relative phrases resolve against the card's created_at (UTC day), so
+//     re-running gives the same answer as the original import.
+//   - A date without a year is its next occurrence on or after the reference day.
+//   - Never overwrites a due_at the source already carried.
+//   - Opt-in: guesses from free text.
+
+import type { CanonicalCard } from '../types';
+import type { Enricher, EnrichmentContext } from './types';
+
+const DAY_MS = 86_400_000;
+
+const CUE_PATTERN = /\b(?:(?:due|deadline|before|until)\b\s*(?::\s*|on\s+)?|by[ \t]+)/gi;
+
+/** "'s" right after a date phrase: the date modifies a noun ("Monday's team"). */
+const POSSESSIVE = /^['\u2019]s\b/i;
+
+const WEEKDAYS = ['sun', 'mon', 'tue', 'wed', 'thu', 'fri', 'sat'];
+const MONTHS = ['jan', 'feb', 'mar', 'apr', 'may', 'jun', 'jul', 'aug', 'sep', 'oct', 'nov', 'dec'];
+
+const WEEKDAY = '(sunday|monday|tuesday|wednesday|thursday|friday|saturday|sun|mon|tues|tue|wed|thurs|thu|fri|sat)';
+const MONTH =
+	'(january|february|march|april|may|june|july|august|september|october|november|december|' +
+	'jan|feb|mar|apr|jun|jul|aug|sept|sep|oct|nov|dec)';
+
+const COUNTS: Record<string, number> = { a: 1, an: 1, one: 1, two: 2, three: 3, four: 4, five: 5 };
+
+const TODAY = /^today\b/i;
+const TOMORROW = /^tomorrow\b/i;
+const NEXT_WEEK = /^next\s+week\b/i;
+const WEEKDAY_PHRASE = new RegExp(`^(?:(next|this)\\s+)?${WEEKDAY}\\b`, 'i');
+const IN_PHRASE = /^in\s+(\d{1,3}|an?|one|two|three|four|five)\s+(day|week|month)s?\b/i;
+const ISO_DATE = /^(\d{4})-(\d{2})-(\d{2})\b/;
+const MONTH_DAY = new RegExp(`^${MONTH}\\.?\\s+(\\d{1,2})(?:st|nd|rd|th)?\\b(?:,?\\s+(\\d{4})\\b)?`, 'i');
+const DAY_MONTH = new RegExp(`^(\\d{1,2})(?:st|nd|rd|th)?\\s+(?:of\\s+)?${MONTH}\\b\\.?(?:,?\\s+(\\d{4})\\b)?`, 'i');
+
+/** UTC date, or null when the parts do not form a real calendar day. */
+function utcDate(year: number, month: number, day: number): Date | null {
+	const date = new Date(Date.UTC(year, month, day));
+	return date.getUTCMonth() === month && date.getUTCDate() === day ? date : null;
+}
+
+/** Same day `count` months later, clamped to the last day of a shorter month (Jan 31 + 1 month = Feb 28). */
+function addMonths(reference: Date, count: number): Date | null {
+	const first = new Date(Date.UTC(reference.getUTCFullYear(), reference.getUTCMonth() + count, 1));
+	const year = first.getUTCFullYear();
+	const month = first.getUTCMonth();
+	const lastDay = new Date(Date.UTC(year, month + 1, 0)).getUTCDate();
+	return utcDate(year, month, Math.min(reference.getUTCDate(), lastDay));
+}
+
+/** Month/day without a year: this year's date, or next year's if already past. */
+function nextOccurrence(month: number, day: number, reference: Date): Date | null {
+	const year = reference.getUTCFullYear();
+	const date = utcDate(year, month, day);
+	if (date && date.getTime() >= reference.getTime()) return date;
+	return utcDate(year + 1, month, day);
+}
+
+function monthIndex(name: string): number {
+	return MONTHS.indexOf(name.slice(0, 3).toLowerCase());
+}
+
+/** A parsed date phrase and the length of text it consumed. */
+interface DatePhrase {
+	date: Date | null;
+	length: number;
+}
+
+/** Parse a date phrase at the start of `text`, relative to a UTC-midnight reference. */
+function parseDatePhrase(text: string, reference: Date): DatePhrase | null {
+	const offset = (days: number) => new Date(reference.getTime() + days * DAY_MS);
+
+	let m = TODAY.exec(text);
+	if (m) return { date: reference, length: m[0].length };
+	m = TOMORROW.exec(text);
+	if (m) return { date: offset(1), length: m[0].length };
+	m = NEXT_WEEK.exec(text);
+	if (m) return { date: offset(7), length: m[0].length };
+
+	m = WEEKDAY_PHRASE.exec(text);
+	if (m) {
+		const target = WEEKDAYS.indexOf(m[2]!.slice(0, 3).toLowerCase());
+		const days = (target - reference.getUTCDay() + 7) % 7;
+		return { date: offset(days === 0 ? 7 : days), length: m[0].length };
+	}
+
+	m = IN_PHRASE.exec(text);
+	if (m) {
+		const count = COUNTS[m[1]!.toLowerCase()] ?? Number(m[1]);
+		const unit = m[2]!.toLowerCase();
+		if (unit === 'day') return { date: offset(count), length: m[0].length };
+		if (unit === 'week') return { date: offset(count * 7), length: m[0].length };
+		return { date: addMonths(reference, count), length: m[0].length };
+	}
+
+	m = ISO_DATE.exec(text);
+	if (m) return { date: utcDate(Number(m[1]), Number(m[2]) - 1, Number(m[3])), length: m[0].length };
+
+	m = MONTH_DAY.exec(text);
+	if (m) {
+		const month = monthIndex(m[1]!);
+		const date = m[3] ? utcDate(Number(m[3]), month, Number(m[2])) : nextOccurrence(month, Number(m[2]), reference);
+		return { date, length: m[0].length };
+	}
+
+	m = DAY_MONTH.exec(text);
+	if (m) {
+		const month = monthIndex(m[2]!);
+		const date = m[3] ? utcDate(Number(m[3]), month, Number(m[1])) : nextOccurrence(month, Number(m[1]), reference);
+		return { date, length: m[0].length };
+	}
+
+	return null;
+}
+
+/**
+ * Find the first cued deadline in a text ("due tomorrow", "by 3 March").
+ *
+ * @param text - Free text to scan
+ * @param reference - ISO timestamp relative phrases resolve against
+ * @returns ISO timestamp at UTC midnight of the deadline, or null
+ */
+export function extractDueDate(text: string, reference: string): string | null {
+	const parsed = new Date(reference);
+	if (Number.isNaN(parsed.getTime())) return null;
+	const day = new Date(Date.UTC(parsed.getUTCFullYear(), parsed.getUTCMonth(), parsed.getUTCDate()));
+
+	for (const cue of text.matchAll(CUE_PATTERN)) {
+		const rest = text.slice(cue.index + cue[0].length);
+		const phrase = parseDatePhrase(rest, day);
+		if (phrase?.date && !POSSESSIVE.test(rest.slice(phrase.length))) return phrase.date.toISOString();
+	}
+	return null;
+}
+
+/**
+ * DateExtractionEnricher fills due_at from deadlines written in the text.
+ */
+export const dateExtractionEnricher: Enricher = {
+	id: 'date-extraction',
+	description: 'Due dates written in the text ("due Friday", "by March 3")',
+	appliesTo: '*',
+	defaultEnabled: false,
+
+	enrich(cards: CanonicalCard[], context?: EnrichmentContext): CanonicalCard[] {
+		for (const card of cards) {
+			if (card.due_at !== null) continue;
+			const dueAt = extractDueDate(`${card.name}\n${card.content ?? ''}`, card.created_at);
+			if (!dueAt) continue;
+			card.due_at = dueAt;
+			context?.markChanged(card);
+		}
+		return cards;
+	},
+};
@@ -0,0 +1,76 @@
+// Isometry v5 — Language Enricher
+// Detects the language of a card's content into a "Language" property (ISO 639-1).
+//
+// Design:
+//   - Stopword voting: each language scores the number of distinct common
+//     function words of its own that occur in the text.
+//   - Needs MIN_WORDS words and a winner with at least MIN_HITS stopwords that
+//     strictly beats the runner-up; otherwise the card is left untouched.
+//   - Covers English, Spanish, French, German, Italian, Portuguese and Dutch.
+//   - Opt-in: adds a custom property to every card it applies to.
+
+import type { CanonicalCard } from '../types';
+import { setDerivedProperties } from './properties';
+import type { Enricher, EnrichmentContext } from './types';
+
+/** Shorter texts are too ambiguous to classify. */
+const MIN_WORDS = 5;
+
+/** Distinct stopwords the winning language must match. */
+const MIN_HITS = 2;
+
+const STOPWORDS: Record<string, readonly string[]> = {
+	en: ['the', 'and', 'is', 'are', 'was', 'of', 'to', 'with', 'that', 'this', 'for', 'you', 'have', 'not', 'be', 'it'],
+	es: ['el', 'los', 'las', 'que', 'y', 'es', 'por', 'con', 'para', 'una', 'del', 'se', 'est\u00e1', 'pero', 'muy'],
+	fr: ['le', 'les', 'des', 'et', 'est', 'une', 'dans', 'pour', 'pas', 'sur', 'avec', 'du', 'ce', 'il', 'nous'],
+	de: ['der', 'die', 'das', 'und', 'ist', 'nicht', 'ein', 'eine', 'mit', 'zu', 'den', 'von', 'auf', 'ich', 'sie'],
+	it: ['il', 'lo', 'gli', 'che', 'di', '\u00e8', 'per', 'una', 'non', 'con', 'della', 'sono', 'un', 'ma', 'anche'],
+	pt: ['o', 'os', 'que', 'de', '\u00e9', 'n\u00e3o', 'uma', 'com', 'para', 'do', 'da', 'em', 'um', 'mas', 'muito'],
+	nl: ['de', 'het', 'een', 'en', 'van', 'is', 'niet', 'dat', 'op', 'te', 'met', 'voor', 'zijn', 'ik', 'ook'],
+};
+
+/**
+ * Detect the language of a text. Returns an ISO 639-1 code, or null when the
+ * text is too short or no language clearly wins.
+ */
+export function detectLanguage(text: string): string | null {
+	const words = text.toLowerCase().match(/\p{L}+/gu) ?? [];
+	if (words.length < MIN_WORDS) return null;
+	const present = new Set(words);
+
+	let best: string | null = null;
+	let bestHits = 0;
+	let runnerUpHits = 0;
+	for (const [language, stopwords] of Object.entries(STOPWORDS)) {
+		const hits = stopwords.filter((word) => present.has(word)).length;
+		if (hits > bestHits) {
+			runnerUpHits = bestHits;
+			bestHits = hits;
+			best = language;
+		} else if (hits > runnerUpHits) {
+			runnerUpHits = hits;
+		}
+	}
+
+	return bestHits >= MIN_HITS && bestHits > runnerUpHits ? best : null;
+}
+
+/**
+ * LanguageEnricher tags each card with the detected language of its content.
+ */
+export const languageEnricher: Enricher = {
+	id: 'language',
+	description: 'Language of the content (ISO 639-1 code)',
+	appliesTo: '*',
+	defaultEnabled: false,
+
+	enrich(cards: CanonicalCard[], context?: EnrichmentContext): CanonicalCard[] {
+		for (const card of cards) {
+			const language = card.content ? detectLanguage(card.content) : null;
+			if (language && setDerivedProperties(card, { Language: language })) {
+				context?.markChanged(card);
+			}
+		}
+		return cards;
+	},
+};
@@ -0,0 +1,45 @@
+// Isometry v5 — Reading Time Enricher
+// Counts the words of a card's content into "Word Count" and "Reading Time (min)".
+//
+// Design:
+//   - Words are runs of letters/digits (apostrophes and hyphens inside a word
+//     keep it whole), so Markdown punctuation and bullets are not counted.
+//   - Reading time rounds up at 200 words per minute; cards without words are
+//     left untouched.
+//   - Opt-in: adds two custom properties to every card it applies to.
+
+import type { CanonicalCard } from '../types';
+import { setDerivedProperties } from './properties';
+import type { Enricher, EnrichmentContext } from './types';
+
+/** Average silent reading speed used for the estimate. */
+const WORDS_PER_MINUTE = 200;
+
+/**
+ * Count the words of a text.
+ */
+export function countWords(text: string): number {
+	return text.match(/[\p{L}\p{N}]+(?:['\u2019-][\p{L}\p{N}]+)*/gu)?.length ?? 0;
+}
+
+/**
+ * ReadingTimeEnricher derives word count and reading time from content.
+ */
+export const readingTimeEnricher: Enricher = {
+	id: 'reading-time',
+	description: 'Word count and reading time of the content',
+	appliesTo: '*',
+	defaultEnabled: false,
+
+	enrich(cards: CanonicalCard[], context?: EnrichmentContext): CanonicalCard[] {
+		for (const card of cards) {
+			const words = card.content ? countWords(card.content) : 0;
+			if (words === 0) continue;
+			const minutes = Math.ceil(words / WORDS_PER_MINUTE);
+			if (setDerivedProperties(card, { 'Word Count': words, 'Reading Time (min)': minutes })) {
+				context?.markChanged(card);
+			}
+		}
+		return cards;
+	},
+};
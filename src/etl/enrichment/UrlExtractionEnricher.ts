@@ -0,0 +1,109 @@
+// Isometry v5 — URL Extraction Enricher
+// Turns web links found in a card's content into resource cards, connected to
+// the card that mentions them with a 'links_to' connection.
+//
+// Design:
+//   - One resource card per distinct URL (source_id "url:<href>"), shared by
+//     every card of the same source that links to it.
+//   - Resource cards already in the batch (earlier runs) are reused, so
+//     re-running only adds connections DedupEngine has not seen.
+//   - Trailing sentence punctuation is not part of a URL.
+//   - Opt-in: creates cards the source did not contain.
+
+import type { CanonicalCard } from '../types';
+import type { Enricher, EnrichmentContext } from './types';
+
+const URL_PATTERN = /\bhttps?:\/\/[^\s<>"'`()[\]{}]+/gi;
+
+const TRAILING_PUNCTUATION = /[.,;:!?*_~]+$/;
+
+const SOURCE_ID_PREFIX = 'url:';
+
+/**
+ * Distinct http(s) URLs in a text, in order of appearance.
+ */
+export function extractUrls(text: string): string[] {
+	const urls = new Set<string>();
+	for (const match of text.match(URL_PATTERN) ?? []) {
+		const href = match.replace(TRAILING_PUNCTUATION, '');
+		try {
+			urls.add(new URL(href).href);
+		} catch {
+			// Not a parseable URL (e.g. "http://" alone) — skip
+		}
+	}
+	return [...urls];
+}
+
+/**
+ * Build the resource card for a URL found in `linkingCard`.
+ */
+function resourceCard(href: string, linkingCard: CanonicalCard): CanonicalCard {
+	const url = new URL(href);
+	const path = url.pathname.replace(/\/+$/, '');
+
+	return {
+		id: crypto.randomUUID(),
+		card_type: 'resource',
+		name: `${url.hostname}${path}`,
+		content: null,
+		summary: null,
+
+		latitude: null,
+		longitude: null,
+		location_name: null,
+
+		created_at: linkingCard.created_at,
+		modified_at: linkingCard.created_at,
+		due_at: null,
+		completed_at: null,
+		event_start: null,
+		event_end: null,
+
+		folder: null,
+		tags: [],
+		status: null,
+
+		priority: 0,
+		sort_order: 0,
+
+		url: href,
+		mime_type: null,
+		is_collective: false,
+
+		source: linkingCard.source,
+		source_id: `${SOURCE_ID_PREFIX}${href}`,
+		source_url: href,
+
+		deleted_at: null,
+	};
+}
+
+/**
+ * UrlExtractionEnricher creates linked resource cards for URLs in content.
+ */
+export const urlExtractionEnricher: Enricher = {
+	id: 'url-extraction',
+	description: 'Resource cards for web links in the content',
+	appliesTo: '*',
+	defaultEnabled: false,
+
+	enrich(cards: CanonicalCard[], context?: EnrichmentContext): CanonicalCard[] {
+		const bySourceId = new Map(cards.map((card) => [card.source_id, card]));
+		const linkingCards = cards.filter((card) => card.content && !card.source_id.startsWith(SOURCE_ID_PREFIX));
+
+		for (const card of linkingCards) {
+			for (const href of extractUrls(card.content!)) {
+				const sourceId = `${SOURCE_ID_PREFIX}${href}`;
+				if (!bySourceId.has(sourceId)) {
+					const resource = resourceCard(href, card);
+					cards.push(resource);
+					bySourceId.set(sourceId, resource);
+					context?.markChanged(resource);
+				}
+				context?.connect(card.source_id, sourceId, 'links_to');
+			}
+		}
+		return cards;
+	},
+};
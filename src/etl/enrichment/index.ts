@@ -1,15 +1,46 @@
 // Isometry v5 — Enrichment Pipeline Barrel Export
 // Importing this module registers all built-in enrichers.
 
-export { runEnrichmentPipeline, registerEnricher, unregisterEnricher, getRegisteredEnricherIds } from './registry';
-export type { Enricher, EnrichedFields } from './types';
+export {
+	runEnrichmentPipeline,
+	runEnrichment,
+	registerEnricher,
+	unregisterEnricher,
+	getRegisteredEnricherIds,
+	listEnrichers,
+	isEnricherEnabled,
+	resolveEnrichedIds,
+} from './registry';
+export type {
+	Enricher,
+	EnrichedFields,
+	EnricherInfo,
+	EnricherSetting,
+	EnrichmentContext,
+	EnrichmentRunResult,
+} from './types';
 export { ENRICHED_FIELD_NAMES } from './types';
 export { folderHierarchyEnricher, splitFolderPath } from './FolderHierarchyEnricher';
+export { dateExtractionEnricher, extractDueDate } from './DateExtractionEnricher';
+export { urlExtractionEnricher, extractUrls } from './UrlExtractionEnricher';
+export { contactDetailsEnricher, extractEmails, extractPhones } from './ContactDetailsEnricher';
+export { readingTimeEnricher, countWords } from './ReadingTimeEnricher';
+export { languageEnricher, detectLanguage } from './LanguageEnricher';
 
 // ---------------------------------------------------------------------------
 // Auto-register built-in enrichers on import
 // ---------------------------------------------------------------------------
 import { registerEnricher } from './registry';
 import { folderHierarchyEnricher } from './FolderHierarchyEnricher';
+import { dateExtractionEnricher } from './DateExtractionEnricher';
+import { urlExtractionEnricher } from './UrlExtractionEnricher';
+import { contactDetailsEnricher } from './ContactDetailsEnricher';
+import { readingTimeEnricher } from './ReadingTimeEnricher';
+import { languageEnricher } from './LanguageEnricher';
 
 registerEnricher(folderHierarchyEnricher);
+registerEnricher(dateExtractionEnricher);
+registerEnricher(urlExtractionEnricher);
+registerEnricher(contactDetailsEnricher);
+registerEnricher(readingTimeEnricher);
+registerEnricher(languageEnricher);
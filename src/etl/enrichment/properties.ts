@@ -0,0 +1,22 @@
+// Isometry v5 — Enricher Property Helper
+// Writes derived values into CanonicalCard.properties, which SQLiteWriter turns
+// into typed card_properties rows like any other unmapped source column.
+
+import type { CanonicalCard, CanonicalPropertyValue } from '../types';
+
+/**
+ * Set derived properties on a card, keyed by display label.
+ * Returns true when any value differs from what the card already carries, so
+ * re-running an enricher over enriched cards reports no change.
+ */
+export function setDerivedProperties(card: CanonicalCard, values: Record<string, CanonicalPropertyValue>): boolean {
+	const properties = { ...card.properties };
+	let changed = false;
+	for (const [label, value] of Object.entries(values)) {
+		if (properties[label] === value) continue;
+		properties[label] = value;
+		changed = true;
+	}
+	if (changed) card.properties = properties;
+	return changed;
+}
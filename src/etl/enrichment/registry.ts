@@ -4,10 +4,12 @@
 // Design:
 //   - Enrichers run in registration order (first registered = first to run).
 //   - Source-type filtering: only enrichers matching the source type execute.
+//   - Saved settings switch enrichers on/off per source type; callers load
+//     them (database/queries/enricher-settings) and pass them in.
 //   - Pipeline is a pure function composition — no database access.
 
-import type { CanonicalCard, SourceType } from '../types';
-import type { Enricher } from './types';
+import type { CanonicalCard, CanonicalConnection, SourceType } from '../types';
+import type { Enricher, EnricherInfo, EnricherSetting, EnrichmentRunResult } from './types';
 
 // ---------------------------------------------------------------------------
 // Registry
@@ -40,6 +42,32 @@ export function getRegisteredEnricherIds(): string[] {
 	return _enrichers.map((e) => e.id);
 }
 
+/** Registered enrichers in run order, for the enricher settings UI. */
+export function listEnrichers(): EnricherInfo[] {
+	return _enrichers.map((e) => ({
+		id: e.id,
+		description: e.description,
+		appliesTo: e.appliesTo,
+		defaultEnabled: e.defaultEnabled ?? true,
+	}));
+}
+
+/**
+ * Whether an enricher runs for a source type: it must apply to the source,
+ * then a source-specific setting wins over a '*' setting, which wins over the
+ * enricher's default.
+ */
+export function isEnricherEnabled(
+	enricher: Pick<Enricher, 'id' | 'appliesTo' | 'defaultEnabled'>,
+	sourceType: SourceType | string,
+	settings: readonly EnricherSetting[] = [],
+): boolean {
+	if (enricher.appliesTo !== '*' && !enricher.appliesTo.includes(sourceType as SourceType)) return false;
+	const own = settings.filter((s) => s.enricher_id === enricher.id);
+	const setting = own.find((s) => s.source_type === sourceType) ?? own.find((s) => s.source_type === '*');
+	return setting?.enabled ?? enricher.defaultEnabled ?? true;
+}
+
 // ---------------------------------------------------------------------------
 // Pipeline Execution
 // ---------------------------------------------------------------------------
@@ -52,10 +80,72 @@ export function getRegisteredEnricherIds(): string[] {
  * @returns The enriched cards (same array reference)
  */
 export function runEnrichmentPipeline(cards: CanonicalCard[], sourceType: SourceType | string): CanonicalCard[] {
+	return runEnrichment(cards, sourceType).cards;
+}
+
+/**
+ * Run the enabled enrichers on a card array and collect what they did.
+ *
+ * @param cards - Parsed canonical cards (mutated in-place; created cards are appended)
+ * @param sourceType - The source type being imported (filters enrichers)
+ * @param settings - Saved per-source settings (enrichers without one use their default)
+ * @param only - Restrict the run to these enricher IDs (re-runs of selected enrichers)
+ */
+export function runEnrichment(
+	cards: CanonicalCard[],
+	sourceType: SourceType | string,
+	settings: readonly EnricherSetting[] = [],
+	only?: readonly string[],
+): EnrichmentRunResult {
+	const connections: CanonicalConnection[] = [];
+	const changed: Record<string, string[]> = {};
+	const createdAt = new Date().toISOString();
+
 	for (const enricher of _enrichers) {
-		if (enricher.appliesTo === '*' || enricher.appliesTo.includes(sourceType as SourceType)) {
-			enricher.enrich(cards);
+		if (only && !only.includes(enricher.id)) continue;
+		if (!isEnricherEnabled(enricher, sourceType, settings)) continue;
+
+		const changedIds = new Set<string>();
+		enricher.enrich(cards, {
+			sourceType,
+			connect: (sourceId, targetId, label) => {
+				connections.push({
+					id: crypto.randomUUID(),
+					source_id: sourceId,
+					target_id: targetId,
+					via_card_id: null,
+					label,
+					weight: 1,
+					created_at: createdAt,
+				});
+			},
+			markChanged: (card) => changedIds.add(card.source_id),
+		});
+		if (changedIds.size > 0) changed[enricher.id] = [...changedIds];
+	}
+
+	return { cards, connections, changed };
+}
+
+/**
+ * Turn a run's changes (by source_id) into the IDs of cards that were written:
+ * source_ids resolve through `idBySourceId` (DedupResult.sourceIdMap) and cards
+ * dedup skipped are dropped. Enrichers without a written card are omitted.
+ */
+export function resolveEnrichedIds(
+	changed: Record<string, string[]>,
+	idBySourceId: ReadonlyMap<string, string>,
+	writtenIds: readonly string[],
+): Record<string, string[]> {
+	const written = new Set(writtenIds);
+	const enriched: Record<string, string[]> = {};
+	for (const [enricherId, sourceIds] of Object.entries(changed)) {
+		const ids: string[] = [];
+		for (const sourceId of sourceIds) {
+			const id = idBySourceId.get(sourceId);
+			if (id && written.has(id)) ids.push(id);
 		}
+		if (ids.length > 0) enriched[enricherId] = ids;
 	}
-	return cards;
+	return enriched;
 }
@@ -7,8 +7,10 @@
 //   - Enrichers may add fields to cards (e.g., folder_l1..folder_l4 from folder).
 //   - Pipeline runs enrichers in registration order.
 //   - Enrichers must be idempotent — re-running on already-enriched cards is safe.
+//   - Enrichers that derive data the source did not carry are opt-in
+//     (defaultEnabled: false) and switched on per source type.
 
-import type { CanonicalCard, SourceType } from '../types';
+import type { CanonicalCard, CanonicalConnection, SourceType } from '../types';
 
 // ---------------------------------------------------------------------------
 // Enricher Interface
@@ -35,14 +37,68 @@ export interface Enricher {
 	 */
 	readonly appliesTo: readonly SourceType[] | '*';
 
+	/**
+	 * Whether the enricher runs for a source type without a saved setting.
+	 * Defaults to true.
+	 */
+	readonly defaultEnabled?: boolean;
+
 	/**
 	 * Transform cards. May mutate in-place for performance.
 	 * Must be idempotent — safe to run on already-enriched cards.
+	 * Cards the enricher creates are pushed onto the same array.
 	 *
 	 * @param cards - Array of parsed canonical cards
+	 * @param context - Source type, connection sink and change reporting
+	 *   (omitted when an enricher is called directly, outside the pipeline)
 	 * @returns Enriched cards (may be same array reference)
 	 */
-	enrich(cards: CanonicalCard[]): CanonicalCard[];
+	enrich(cards: CanonicalCard[], context?: EnrichmentContext): CanonicalCard[];
+}
+
+/**
+ * Per-run context handed to each enricher. The pipeline creates one per
+ * enricher, so reported changes are attributed to it.
+ */
+export interface EnrichmentContext {
+	/** Source type of the cards being enriched */
+	readonly sourceType: string;
+	/** Add a connection between two cards, by source_id */
+	connect(sourceId: string, targetId: string, label: string): void;
+	/** Report that the enricher changed (or created) a card */
+	markChanged(card: CanonicalCard): void;
+}
+
+/**
+ * Saved on/off switch for an enricher, for one source type or '*' for all.
+ * A source-specific setting wins over '*'.
+ */
+export interface EnricherSetting {
+	enricher_id: string;
+	source_type: string;
+	enabled: boolean;
+}
+
+/**
+ * Registered enricher as listed for the settings UI.
+ */
+export interface EnricherInfo {
+	id: string;
+	description: string;
+	appliesTo: readonly SourceType[] | '*';
+	defaultEnabled: boolean;
+}
+
+/**
+ * Outcome of one pipeline run.
+ */
+export interface EnrichmentRunResult {
+	/** The input array, including cards the enrichers created */
+	cards: CanonicalCard[];
+	/** Connections added by enrichers; endpoints are card source_ids */
+	connections: CanonicalConnection[];
+	/** source_ids of the cards each enricher changed, by enricher id */
+	changed: Record<string, string[]>;
 }
 
 /**
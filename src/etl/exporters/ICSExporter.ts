@@ -0,0 +1,129 @@
+// Isometry v5 — ICSExporter
+// Export dated cards as an iCalendar (RFC 5545) document.
+//
+// Purpose: Round-trip calendar data (can be re-imported via ICSParser)
+// Format: One VEVENT per card with event_start, one VTODO per task or card with due_at
+
+import type { Card } from '../../database/queries/types';
+import { escapeText, foldLine } from '../parsers/contentLines';
+
+const DATE_ONLY_RE = /^\d{4}-\d{2}-\d{2}$/;
+/** Inverse of the 0-3 scale ICSParser maps PRIORITY onto */
+const PRIORITIES: Record<number, number> = { 3: 1, 2: 5, 1: 9 };
+
+/**
+ * Whether a card has anything to put on a calendar.
+ */
+export function isCalendarCard(card: Card): boolean {
+	return Boolean(card.event_start || card.due_at || card.card_type === 'task');
+}
+
+/**
+ * Exports cards to iCalendar.
+ *
+ * Features:
+ * - event_start / event_end -> DTSTART / DTEND (all-day dates as VALUE=DATE)
+ * - due_at / completed_at / status / priority -> VTODO DUE / COMPLETED / STATUS / PRIORITY
+ * - location_name, latitude/longitude, tags, url -> LOCATION, GEO, CATEGORIES, URL
+ * - Cards imported from .ics keep their UID; others get `${id}@isometry`
+ * - Lines folded at 75 octets with CRLF endings
+ */
+export class ICSExporter {
+	/**
+	 * Export cards to iCalendar. Cards without dates that are not tasks are skipped.
+	 *
+	 * @param cards Cards to export
+	 * @param calendarName Calendar display name (X-WR-CALNAME)
+	 * @returns iCalendar document
+	 */
+	export(cards: Card[], calendarName = 'Isometry Export'): string {
+		const stamp = formatDateTime(new Date().toISOString())!;
+		const lines = [
+			'BEGIN:VCALENDAR',
+			'VERSION:2.0',
+			'PRODID:-//Isometry//Isometry Export//EN',
+			'CALSCALE:GREGORIAN',
+			`X-WR-CALNAME:${escapeText(calendarName)}`,
+		];
+
+		for (const card of cards) {
+			if (!isCalendarCard(card)) continue;
+			lines.push(...(card.event_start ? this.event(card, stamp) : this.todo(card, stamp)));
+		}
+
+		lines.push('END:VCALENDAR');
+		return `${lines.map(foldLine).join('\r\n')}\r\n`;
+	}
+
+	private event(card: Card, stamp: string): string[] {
+		const status = card.status?.toUpperCase();
+		return [
+			'BEGIN:VEVENT',
+			...this.common(card, stamp),
+			...dateProperty('DTSTART', card.event_start),
+			...dateProperty('DTEND', card.event_end),
+			...(status === 'CONFIRMED' || status === 'TENTATIVE' || status === 'CANCELLED' ? [`STATUS:${status}`] : []),
+			'END:VEVENT',
+		];
+	}
+
+	private todo(card: Card, stamp: string): string[] {
+		const done = card.completed_at !== null || card.status === 'done';
+		const status = done ? 'COMPLETED' : card.status === 'cancelled' ? 'CANCELLED' : 'NEEDS-ACTION';
+		const priority = PRIORITIES[card.priority];
+		const completed = card.completed_at ? formatDateTime(card.completed_at) : null;
+		return [
+			'BEGIN:VTODO',
+			...this.common(card, stamp),
+			...dateProperty('DUE', card.due_at),
+			...(completed ? [`COMPLETED:${completed}`] : []),
+			`STATUS:${status}`,
+			...(priority ? [`PRIORITY:${priority}`] : []),
+			'END:VTODO',
+		];
+	}
+
+	/**
+	 * Properties shared by VEVENT and VTODO.
+	 */
+	private common(card: Card, stamp: string): string[] {
+		const lines = [
+			`UID:${escapeText(card.source === 'ics' && card.source_id ? card.source_id : `${card.id}@isometry`)}`,
+			`DTSTAMP:${stamp}`,
+			`SUMMARY:${escapeText(card.name)}`,
+		];
+		if (card.content) lines.push(`DESCRIPTION:${escapeText(card.content)}`);
+		if (card.location_name) lines.push(`LOCATION:${escapeText(card.location_name)}`);
+		if (card.latitude !== null && card.longitude !== null) lines.push(`GEO:${card.latitude};${card.longitude}`);
+		if (card.tags && card.tags.length > 0) lines.push(`CATEGORIES:${card.tags.map(escapeText).join(',')}`);
+		if (card.url) lines.push(`URL:${card.url}`);
+		const created = formatDateTime(card.created_at);
+		const modified = formatDateTime(card.modified_at);
+		if (created) lines.push(`CREATED:${created}`);
+		if (modified) lines.push(`LAST-MODIFIED:${modified}`);
+		return lines;
+	}
+}
+
+/**
+ * A DATE property for `YYYY-MM-DD` values, a UTC DATE-TIME otherwise.
+ * Unparseable values are omitted.
+ */
+function dateProperty(name: string, value: string | null): string[] {
+	if (!value) return [];
+	if (DATE_ONLY_RE.test(value)) return [`${name};VALUE=DATE:${value.replace(/-/g, '')}`];
+	const dateTime = formatDateTime(value);
+	return dateTime ? [`${name}:${dateTime}`] : [];
+}
+
+/**
+ * ISO 8601 to a UTC DATE-TIME (`20260105T090000Z`), null if unparseable.
+ */
+function formatDateTime(value: string): string | null {
+	const date = new Date(value);
+	if (Number.isNaN(date.getTime())) return null;
+	return date
+		.toISOString()
+		.replace(/\.\d{3}Z$/, 'Z')
+		.replace(/[-:]/g, '');
+}
@@ -0,0 +1,114 @@
+// Isometry v5 — OPMLExporter
+// Export cards as an OPML 2.0 outline following `contains` connections.
+//
+// Purpose: Round-trip outlines (can be re-imported via OPMLParser)
+// Format: Nested <outline> elements; roots are cards with no exported parent
+
+import type { Card, Connection } from '../../database/queries/types';
+
+/**
+ * Exports cards to OPML, walking the `contains` hierarchy TreeView uses.
+ *
+ * Features:
+ * - Children ordered by sort_order, then name
+ * - A card with several parents is nested under the first one only
+ * - Cycles are broken: each card is written once; cards only reachable
+ *   through a cycle become roots
+ * - name -> text, content -> _note, tags -> category, url -> url (type="link"),
+ *   created_at -> created (RFC 822), completed_at -> _complete
+ */
+export class OPMLExporter {
+	/**
+	 * Export cards to OPML.
+	 *
+	 * @param cards Cards to export
+	 * @param connections Connections among them (non-`contains` labels are ignored)
+	 * @param title Outline title for <head>
+	 * @returns OPML 2.0 document
+	 */
+	export(cards: Card[], connections: Connection[] = [], title = 'Isometry Export'): string {
+		const byId = new Map(cards.map((card) => [card.id, card]));
+		const parentOf = new Map<string, string>();
+		const children = new Map<string, Card[]>();
+
+		for (const conn of connections) {
+			if (conn.label !== 'contains' || conn.source_id === conn.target_id) continue;
+			const parent = byId.get(conn.source_id);
+			const child = byId.get(conn.target_id);
+			if (!parent || !child || parentOf.has(child.id)) continue;
+			parentOf.set(child.id, parent.id);
+			const list = children.get(parent.id);
+			if (list) list.push(child);
+			else children.set(parent.id, [child]);
+		}
+
+		const written = new Set<string>();
+		const lines: string[] = [];
+		const write = (card: Card, depth: number) => {
+			written.add(card.id);
+			const indent = '  '.repeat(depth + 2);
+			const kids = sortCards(children.get(card.id) ?? []).filter((c) => !written.has(c.id));
+			if (kids.length === 0) {
+				lines.push(`${indent}<outline ${this.attributes(card)}/>`);
+				return;
+			}
+			lines.push(`${indent}<outline ${this.attributes(card)}>`);
+			for (const kid of kids) {
+				if (!written.has(kid.id)) write(kid, depth + 1);
+			}
+			lines.push(`${indent}</outline>`);
+		};
+
+		for (const root of sortCards(cards.filter((c) => !parentOf.has(c.id)))) write(root, 0);
+		// Cards whose every ancestor chain loops back on itself
+		for (const card of sortCards(cards)) {
+			if (!written.has(card.id)) write(card, 0);
+		}
+
+		return [
+			'<?xml version="1.0" encoding="UTF-8"?>',
+			'<opml version="2.0">',
+			'  <head>',
+			`    <title>${escapeXml(title)}</title>`,
+			`    <dateCreated>${new Date().toUTCString()}</dateCreated>`,
+			'  </head>',
+			'  <body>',
+			...lines,
+			'  </body>',
+			'</opml>',
+			'',
+		].join('\n');
+	}
+
+	/**
+	 * Outline attributes for one card.
+	 */
+	private attributes(card: Card): string {
+		const attrs: Array<[string, string]> = [['text', card.name]];
+		if (card.content) attrs.push(['_note', card.content]);
+		if (card.url) attrs.push(['type', 'link'], ['url', card.url]);
+		if (card.tags && card.tags.length > 0) attrs.push(['category', card.tags.join(',')]);
+		const created = new Date(card.created_at);
+		if (!Number.isNaN(created.getTime())) attrs.push(['created', created.toUTCString()]);
+		if (card.completed_at) attrs.push(['_complete', 'true']);
+		return attrs.map(([name, value]) => `${name}="${escapeXml(value)}"`).join(' ');
+	}
+}
+
+function sortCards(cards: Card[]): Card[] {
+	return [...cards].sort((a, b) => a.sort_order - b.sort_order || a.name.localeCompare(b.name));
+}
+
+/**
+ * Escape text for XML attribute values and text nodes. Newlines are kept as
+ * character references so multi-line notes survive attribute normalization.
+ */
+function escapeXml(text: string): string {
+	return text
+		.replace(/&/g, '&amp;')
+		.replace(/</g, '&lt;')
+		.replace(/>/g, '&gt;')
+		.replace(/"/g, '&quot;')
+		.replace(/\r?\n/g, '&#10;')
+		.replace(/\t/g, '&#9;');
+}
@@ -10,21 +10,59 @@ export { DedupEngine } from './DedupEngine';
 export type { ExportFormat, ExportOptions, ExportResult } from './ExportOrchestrator';
 // Export Orchestrator (ETL-17)
 export { ExportOrchestrator } from './ExportOrchestrator';
+export type { ManifestDiff, ManifestEntry, ReimportFile, ReimportFileChange } from './FileManifest';
+// File manifest (incremental directory re-import)
+export { diffManifest, relativeFilePath } from './FileManifest';
+export type {
+	CardTypeRule,
+	CoercionType,
+	MappedField,
+	MappedRowContext,
+	MappingProfile,
+	MappingRules,
+	MappingSuggestion,
+	RelationshipRule,
+} from './MappingProfile';
+// Import mapping profiles (CSV / Excel / JSON)
+export {
+	extractRelationships,
+	headerFingerprint,
+	mapRowWithProfile,
+	normalizeHeader,
+	normalizeHeaders,
+	parseDateValue,
+	suggestMappingProfile,
+	validateMappingRules,
+} from './MappingProfile';
 export { CSVExporter } from './exporters/CSVExporter';
+export { ICSExporter, isCalendarCard } from './exporters/ICSExporter';
 export { type JSONExportData, JSONExporter } from './exporters/JSONExporter';
 // Exporters (ETL-14, ETL-15, ETL-16)
 export { MarkdownExporter } from './exporters/MarkdownExporter';
+export { OPMLExporter } from './exporters/OPMLExporter';
 export type { ImportOptions } from './ImportOrchestrator';
 // Import Orchestrator (ETL-12)
 export { ImportOrchestrator } from './ImportOrchestrator';
 export type { AppleNotesParseResult, ParsedFile } from './parsers/AppleNotesParser';
 // Parsers (all sources)
 export { AppleNotesParser } from './parsers/AppleNotesParser';
+export type { BookmarksParseOptions, BookmarksParseResult } from './parsers/BookmarksParser';
+export { BookmarksParser, isBookmarksDocument } from './parsers/BookmarksParser';
 export { CSVParser } from './parsers/CSVParser';
+export type { EmailParseOptions, EmailParseResult } from './parsers/EmailParser';
+export { EmailParser } from './parsers/EmailParser';
+export type { ExcelSheetInfo } from './parsers/ExcelParser';
 export { ExcelParser } from './parsers/ExcelParser';
 export { HTMLParser } from './parsers/HTMLParser';
+export type { ICSParseOptions, ICSParseResult } from './parsers/ICSParser';
+export { ICSParser } from './parsers/ICSParser';
 export { JSONParser } from './parsers/JSONParser';
 export { MarkdownParser } from './parsers/MarkdownParser';
+export type { ObsidianParseResult, VaultFile } from './parsers/ObsidianParser';
+export { ObsidianParser } from './parsers/ObsidianParser';
+export { OPMLParser } from './parsers/OPMLParser';
+export type { VCardParseOptions, VCardParseResult } from './parsers/VCardParser';
+export { VCardParser } from './parsers/VCardParser';
 // Database Writer (ETL-11)
 export { SQLiteWriter } from './SQLiteWriter';
 export type {
@@ -32,6 +70,7 @@ export type {
 	AltoNoteFrontmatter,
 	CanonicalCard,
 	CanonicalConnection,
+	CanonicalPropertyValue,
 	ImportResult,
 	ParseError,
 	SourceType,
@@ -0,0 +1,220 @@
+// Isometry v5 — Bookmarks Parser
+// Parses Netscape bookmark files (the HTML export of every browser, Raindrop,
+// Pinboard) and Pocket exports into resource cards.
+//
+// Features:
+// - Regex tokenizer (Worker-safe, no DOM dependencies)
+// - One resource card per <A HREF>; link text is the name, <DD> the content
+// - Nested <H3> folders become the folder path ("Bookmarks Bar/Dev/Rust"),
+//   which FolderHierarchyEnricher splits into folder_l1..folder_l4
+// - Pocket's <h1> sections ("Unread", "Read Archive") become the folder
+// - ADD_DATE / time_added (unix seconds) is the creation time, LAST_MODIFIED the modification time
+// - TAGS / tags attribute becomes tags
+//
+// Source ids are the bookmark URL, so re-importing an export updates cards in place.
+// A URL bookmarked in several folders keeps its first occurrence.
+
+import type { CanonicalCard, CanonicalConnection, ParseError } from '../types';
+import { decodeEntities } from './entities';
+
+export interface BookmarksParseOptions {
+	/** Source filename (unused for ids, accepted for orchestrator symmetry) */
+	filename?: string;
+	/** Default timestamp for bookmarks without ADD_DATE */
+	defaultTimestamp?: string;
+}
+
+export interface BookmarksParseResult {
+	cards: CanonicalCard[];
+	connections: CanonicalConnection[];
+	errors: ParseError[];
+}
+
+const TOKEN_RE = /<(h1|h3)\b([^>]*)>([\s\S]*?)<\/\1>|<a\b([^>]*)>([\s\S]*?)<\/a>|<(\/?)dl\b[^>]*>|<dd>([^<]*)/gi;
+const ATTR_RE = /([\w:.-]+)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))/g;
+
+/**
+ * True for Netscape bookmark files and Pocket exports, which arrive with the
+ * same .html extension as web clippings.
+ */
+export function isBookmarksDocument(html: string): boolean {
+	return /<!DOCTYPE\s+NETSCAPE-Bookmark-file/i.test(html) || /<a\b[^>]*\btime_added\s*=/i.test(html);
+}
+
+/**
+ * BookmarksParser transforms a bookmark export into resource cards.
+ */
+export class BookmarksParser {
+	/**
+	 * Parse a bookmark export.
+	 *
+	 * @param html - Netscape bookmark file or Pocket export HTML
+	 * @param options - Optional parsing configuration
+	 * @returns Parse result with cards, connections, and errors
+	 */
+	parse(html: string, options?: BookmarksParseOptions): BookmarksParseResult {
+		const cards: CanonicalCard[] = [];
+		const errors: ParseError[] = [];
+
+		if (!isBookmarksDocument(html)) {
+			throw new Error('Not a bookmarks document');
+		}
+
+		// Netscape files nest <H3> folders in <DL> lists; Pocket has flat <h1> sections
+		const nested = /<dl\b/i.test(html);
+		const fallbackTime = options?.defaultTimestamp ?? new Date().toISOString();
+		const seen = new Set<string>();
+
+		// Stack of open lists: their folder name and next child position
+		const stack: Array<{ name: string | null; count: number }> = [{ name: null, count: 0 }];
+		let pendingFolder: string | null = null;
+		let lastCard: CanonicalCard | null = null;
+		let index = 0;
+
+		for (const match of html.matchAll(TOKEN_RE)) {
+			const heading = match[1]?.toLowerCase();
+			if (heading) {
+				lastCard = null;
+				const name = this.text(match[3]!).replace(/\//g, '\u2215') || null;
+				if (heading === 'h3') {
+					pendingFolder = name;
+				} else if (!nested) {
+					stack[0] = { name, count: 0 };
+				}
+				continue;
+			}
+
+			if (match[7] !== undefined) {
+				// <DD> describes the bookmark right before it
+				if (lastCard) {
+					const description = decodeEntities(match[7]).trim() || null;
+					lastCard.content = description;
+					lastCard.summary = description ? description.slice(0, 200) : null;
+				}
+				continue;
+			}
+
+			if (match[4] === undefined) {
+				lastCard = null;
+				if (match[6] === '/') {
+					if (stack.length > 1) stack.pop();
+				} else {
+					stack.push({ name: pendingFolder, count: 0 });
+					pendingFolder = null;
+				}
+				continue;
+			}
+
+			const position = index++;
+			lastCard = null;
+			try {
+				const attrs = this.parseAttributes(match[4]);
+				const url = attrs['href']?.trim();
+				if (!url || url.startsWith('place:')) continue; // Firefox smart folders
+				if (seen.has(url)) continue;
+				seen.add(url);
+
+				const list = stack[stack.length - 1]!;
+				const folder = stack.map((entry) => entry.name).filter((name): name is string => name !== null);
+				lastCard = this.bookmarkCard(attrs, url, this.text(match[5]!), {
+					folder: folder.length > 0 ? folder.join('/') : null,
+					sortOrder: list.count++,
+					fallbackTime,
+				});
+				cards.push(lastCard);
+			} catch (error) {
+				errors.push({
+					index: position,
+					source_id: null,
+					message: error instanceof Error ? error.message : String(error),
+				});
+			}
+		}
+
+		return { cards, connections: [], errors };
+	}
+
+	/**
+	 * Build the card for one <A> bookmark.
+	 */
+	private bookmarkCard(
+		attrs: Record<string, string>,
+		url: string,
+		title: string,
+		placement: { folder: string | null; sortOrder: number; fallbackTime: string },
+	): CanonicalCard {
+		const created = this.parseTimestamp(attrs['add_date'] ?? attrs['time_added']) ?? placement.fallbackTime;
+		const modified = this.parseTimestamp(attrs['last_modified']) ?? created;
+		const tags = (attrs['tags'] ?? '')
+			.split(',')
+			.map((t) => t.trim())
+			.filter(Boolean);
+
+		return {
+			id: crypto.randomUUID(),
+			card_type: 'resource',
+			name: title || url,
+			content: null,
+			summary: null,
+
+			latitude: null,
+			longitude: null,
+			location_name: null,
+
+			created_at: created,
+			modified_at: modified,
+			due_at: null,
+			completed_at: null,
+			event_start: null,
+			event_end: null,
+
+			folder: placement.folder,
+			tags,
+			status: null,
+
+			priority: 0,
+			sort_order: placement.sortOrder,
+
+			url,
+			mime_type: null,
+			is_collective: false,
+
+			source: 'bookmarks',
+			source_id: url,
+			source_url: null,
+
+			deleted_at: null,
+		};
+	}
+
+	/**
+	 * Inner markup of a heading or link to plain text.
+	 */
+	private text(markup: string): string {
+		return decodeEntities(markup.replace(/<[^>]+>/g, ''))
+			.replace(/\s+/g, ' ')
+			.trim();
+	}
+
+	/**
+	 * Attribute string to a decoded, lowercased-name -> value map.
+	 */
+	private parseAttributes(source: string): Record<string, string> {
+		const attrs: Record<string, string> = {};
+		for (const match of source.matchAll(ATTR_RE)) {
+			attrs[match[1]!.toLowerCase()] = decodeEntities(match[2] ?? match[3] ?? match[4] ?? '');
+		}
+		return attrs;
+	}
+
+	/**
+	 * Unix timestamp to ISO 8601, null if missing or zero.
+	 * Some exporters write milliseconds or microseconds instead of seconds.
+	 */
+	private parseTimestamp(value: string | undefined): string | null {
+		let seconds = Number(value);
+		if (!value || !Number.isFinite(seconds) || seconds <= 0) return null;
+		while (seconds > 1e11) seconds /= 1000;
+		return new Date(Math.floor(seconds) * 1000).toISOString().replace(/\.000Z$/, 'Z');
+	}
+}
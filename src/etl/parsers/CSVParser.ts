@@ -6,11 +6,16 @@
 // - UTF-8 BOM stripping
 // - Column auto-detection via synonym matching
 // - Explicit column mapping override
+// - Saved mapping profiles (column mapping, date format, tags, coercion, card_type rules)
+// - Profile relationship rules: foreign-key columns become connections, across all files
 // - Ragged row handling (missing columns)
 // - TSV auto-detection
+// - Unmapped columns captured as custom card properties
 
 import * as Papa from 'papaparse';
+import { extractRelationships, type MappingProfile, mapRowWithProfile } from '../MappingProfile';
 import type { CanonicalCard, CanonicalConnection, ParseError } from '../types';
+import { collectUnmappedProperties } from './properties';
 
 export interface ParsedFile {
 	path: string;
@@ -27,6 +32,8 @@ export interface CSVColumnMapping {
 export interface CSVParseOptions {
 	/** Explicit column mapping (overrides auto-detection) */
 	columnMapping?: CSVColumnMapping;
+	/** Saved mapping profile (overrides columnMapping and auto-detection) */
+	profile?: MappingProfile;
 	/** Default timestamp for rows without dates */
 	defaultTimestamp?: string;
 }
@@ -64,13 +71,15 @@ export class CSVParser {
 		const cards: CanonicalCard[] = [];
 		const connections: CanonicalConnection[] = [];
 		const errors: ParseError[] = [];
+		// Profile-mapped rows of every file, for relationship extraction
+		const mappedRows: Array<{ row: Record<string, string>; card: CanonicalCard }> = [];
 
 		for (let i = 0; i < files.length; i++) {
 			const file = files[i];
 			if (!file) continue;
 
 			try {
-				const result = this.parseFile(file, options);
+				const result = this.parseFile(file, options, mappedRows);
 				cards.push(...result.cards);
 				connections.push(...result.connections);
 			} catch (error) {
@@ -83,24 +92,44 @@ export class CSVParser {
 			}
 		}
 
+		if (options?.profile) {
+			connections.push(...extractRelationships(mappedRows, options.profile));
+		}
+
 		return { cards, connections, errors };
 	}
 
+	/**
+	 * Header row of the first non-empty file, for mapping profile suggestion.
+	 */
+	readHeaders(files: ParsedFile[]): string[] {
+		for (const file of files) {
+			const content = this.stripBom(file.content);
+			if (!content.trim()) continue;
+			const result = Papa.parse<Record<string, string>>(content, {
+				header: true,
+				skipEmptyLines: true,
+				delimiter: '',
+				preview: 1,
+				worker: false,
+			});
+			return result.meta.fields ?? [];
+		}
+		return [];
+	}
+
 	/**
 	 * Parse a single CSV file.
 	 */
 	private parseFile(
 		file: ParsedFile,
 		options?: CSVParseOptions,
+		mappedRows?: Array<{ row: Record<string, string>; card: CanonicalCard }>,
 	): { cards: CanonicalCard[]; connections: CanonicalConnection[] } {
 		const cards: CanonicalCard[] = [];
 		const connections: CanonicalConnection[] = [];
 
-		// Strip UTF-8 BOM if present
-		let content = file.content;
-		if (content.charCodeAt(0) === 0xfeff) {
-			content = content.slice(1);
-		}
+		const content = this.stripBom(file.content);
 
 		// Handle empty content
 		if (!content.trim()) {
@@ -115,6 +144,24 @@ export class CSVParser {
 			worker: false, // Synchronous (already in Worker context)
 		});
 
+		const profile = options?.profile;
+		if (profile) {
+			for (let i = 0; i < parseResult.data.length; i++) {
+				const row = parseResult.data[i];
+				if (!row) continue;
+				const card = mapRowWithProfile(row, profile, {
+					index: i,
+					source: 'csv',
+					mimeType: 'text/csv',
+					sourceId: `${file.path}:${i}`,
+					...(options?.defaultTimestamp ? { defaultTimestamp: options.defaultTimestamp } : {}),
+				});
+				cards.push(card);
+				mappedRows?.push({ row, card });
+			}
+			return { cards, connections };
+		}
+
 		// Determine column mapping
 		const columnMap = options?.columnMapping || this.autoDetectColumns(parseResult.meta.fields || []);
 
@@ -130,6 +177,13 @@ export class CSVParser {
 		return { cards, connections };
 	}
 
+	/**
+	 * Strip a UTF-8 BOM if present.
+	 */
+	private stripBom(content: string): string {
+		return content.charCodeAt(0) === 0xfeff ? content.slice(1) : content;
+	}
+
 	/**
 	 * Auto-detect column mapping from header row.
 	 */
@@ -183,6 +237,14 @@ export class CSVParser {
 		// Parse tags (split on comma or semicolon)
 		const tags = this.parseTags(tagsRaw);
 
+		// Columns outside the mapping become custom properties
+		const properties = collectUnmappedProperties(row, [
+			columnMap.name,
+			columnMap.content,
+			columnMap.tags,
+			columnMap.created_at,
+		]);
+
 		return {
 			id: crypto.randomUUID(),
 			card_type: 'note',
@@ -217,6 +279,7 @@ export class CSVParser {
 			source_url: null,
 
 			deleted_at: null,
+			...(properties ? { properties } : {}),
 		};
 	}
 
@@ -0,0 +1,491 @@
+// Isometry v5 — Email Parser
+// Parses mbox mailboxes and single .eml messages (RFC 5322 / MIME) into
+// message cards with their thread and correspondent graph.
+//
+// Features:
+// - mbox split on `From ` separator lines (mboxrd `>From ` unescaped)
+// - Subject -> name, text body -> content (HTML-only bodies flattened to text),
+//   Date -> created_at
+// - In-Reply-To / References -> `reply_to` connections to the parent message
+// - From -> `from`, To / Cc -> `to` connections to person cards (one per address)
+// - Attachments -> media/resource cards via createAttachmentCard, joined by
+//   `attachment` connections
+// - base64 / quoted-printable transfer encodings, charsets, RFC 2047 encoded words
+//
+// Source ids are Message-IDs, so threads span imports: a reply imported later
+// links to a parent imported earlier.
+
+import type { CanonicalCard, CanonicalConnection, ParseError } from '../types';
+import { createAttachmentCard } from './attachments';
+import { decodeEntities } from './entities';
+
+export interface EmailParseOptions {
+	/** Source filename — mailbox name (folder) and source id prefix for messages without a Message-ID */
+	filename?: string;
+	/** Default timestamp for messages without a Date header */
+	defaultTimestamp?: string;
+}
+
+export interface EmailParseResult {
+	cards: CanonicalCard[];
+	connections: CanonicalConnection[];
+	errors: ParseError[];
+}
+
+/** A MIME entity: headers (lowercased names) and raw body */
+interface MimePart {
+	headers: Map<string, string>;
+	body: string;
+}
+
+interface Mailbox {
+	name: string | null;
+	address: string;
+}
+
+/**
+ * EmailParser transforms an mbox file or .eml message into message cards.
+ */
+export class EmailParser {
+	/**
+	 * Parse an mbox mailbox or a single message.
+	 *
+	 * @param raw - mbox or .eml text
+	 * @param options - Optional parsing configuration
+	 * @returns Parse result with cards, connections, and errors
+	 */
+	parse(raw: string, options?: EmailParseOptions): EmailParseResult {
+		const cards: CanonicalCard[] = [];
+		const connections: CanonicalConnection[] = [];
+		const errors: ParseError[] = [];
+
+		const messages = this.splitMailbox(raw.replace(/\r\n?/g, '\n'));
+		const folder = options?.filename?.replace(/\.(mbox|mbx|eml)$/i, '') ?? null;
+		const prefix = options?.filename ?? 'mailbox';
+		const fallbackTime = options?.defaultTimestamp ?? new Date().toISOString();
+		const now = new Date().toISOString();
+		const people = new Map<string, CanonicalCard>(); // source_id -> person card
+
+		const connect = (source: string, target: string, label: string, weight = 1) => {
+			connections.push({
+				id: crypto.randomUUID(),
+				source_id: source, // Resolved to card UUIDs by DedupEngine
+				target_id: target,
+				via_card_id: null,
+				label,
+				weight,
+				created_at: now,
+			});
+		};
+
+		for (let i = 0; i < messages.length; i++) {
+			try {
+				const message = this.parsePart(messages[i]!);
+				if (message.headers.size === 0) throw new Error('Message has no headers');
+				const card = this.messageCard(message, `${prefix}#${i}`, folder, fallbackTime);
+				cards.push(card);
+
+				// Thread: the direct parent is In-Reply-To, else the last References entry
+				const parent =
+					this.messageIds(message.headers.get('in-reply-to'))[0] ??
+					this.messageIds(message.headers.get('references')).pop();
+				if (parent && parent !== card.source_id) connect(card.source_id, parent, 'reply_to');
+
+				// Correspondents
+				const recipients: Array<[string, number]> = [
+					['from', 1],
+					['to', 1],
+					['cc', 0.5],
+				];
+				for (const [header, weight] of recipients) {
+					for (const mailbox of this.mailboxes(message.headers.get(header))) {
+						const person = this.personCard(mailbox, card.created_at);
+						const known = people.get(person.source_id);
+						if (!known) people.set(person.source_id, person);
+						// A display name seen later beats a bare address
+						else if (mailbox.name && known.name === mailbox.address) known.name = mailbox.name;
+						connect(card.source_id, person.source_id, header === 'from' ? 'from' : 'to', weight);
+					}
+				}
+
+				// Attachments
+				const attachments = this.leafParts(message).filter((part) => this.attachmentName(part) !== null);
+				attachments.forEach((part, index) => {
+					const filename = this.attachmentName(part)!;
+					const { type } = this.contentType(part);
+					const attachment = createAttachmentCard({
+						filename,
+						// Generic types defer to the filename extension
+						mimeType: type === 'application/octet-stream' ? null : type,
+						source: 'email',
+						sourceId: `${card.source_id}#${index}:${filename}`,
+						folder,
+						timestamp: card.created_at,
+						sortOrder: index,
+					});
+					cards.push(attachment);
+					connect(card.source_id, attachment.source_id, 'attachment');
+				});
+			} catch (error) {
+				errors.push({
+					index: i,
+					source_id: null,
+					message: error instanceof Error ? error.message : String(error),
+				});
+			}
+		}
+
+		cards.push(...people.values());
+		return { cards, connections, errors };
+	}
+
+	/**
+	 * Split an mbox into raw messages; anything else is one message.
+	 */
+	private splitMailbox(text: string): string[] {
+		if (!text.startsWith('From ')) return text.trim() ? [text] : [];
+		// A separator is a `From ` line at the start or after a blank line
+		return text
+			.split(/(?:^|\n\n)From [^\n]*\n/)
+			.filter((message) => message.trim() !== '')
+			.map((message) => message.replace(/^>(>*From )/gm, '$1'));
+	}
+
+	/**
+	 * Split a MIME entity into unfolded headers and body.
+	 */
+	private parsePart(text: string): MimePart {
+		const split = text.search(/\n\n/);
+		const head = split === -1 ? text : text.slice(0, split);
+		const body = split === -1 ? '' : text.slice(split + 2);
+		const headers = new Map<string, string>();
+		for (const line of head.replace(/\n[ \t]+/g, ' ').split('\n')) {
+			const colon = line.indexOf(':');
+			if (colon <= 0) continue;
+			const name = line.slice(0, colon).trim().toLowerCase();
+			// First occurrence wins (later ones are usually Received-style repeats)
+			if (!headers.has(name)) headers.set(name, line.slice(colon + 1).trim());
+		}
+		return { headers, body };
+	}
+
+	/**
+	 * Build the message card.
+	 */
+	private messageCard(
+		message: MimePart,
+		fallbackId: string,
+		folder: string | null,
+		fallbackTime: string,
+	): CanonicalCard {
+		const subject = decodeWords(message.headers.get('subject') ?? '').trim();
+		const body = this.bodyText(message);
+		const date = this.parseDate(message.headers.get('date'));
+		const labels = decodeWords(message.headers.get('x-gmail-labels') ?? '')
+			.split(',')
+			.map((label) => label.trim())
+			.filter(Boolean);
+
+		return {
+			id: crypto.randomUUID(),
+			card_type: 'message',
+			name: subject || '(no subject)',
+			content: body,
+			summary: body ? body.replace(/\s+/g, ' ').trim().slice(0, 200) : null,
+
+			latitude: null,
+			longitude: null,
+			location_name: null,
+
+			created_at: date ?? fallbackTime,
+			modified_at: date ?? fallbackTime,
+			due_at: null,
+			completed_at: null,
+			event_start: null,
+			event_end: null,
+
+			folder,
+			tags: [...new Set(labels)],
+			status: null,
+
+			priority: 0,
+			sort_order: 0,
+
+			url: null,
+			mime_type: 'message/rfc822',
+			is_collective: false,
+
+			source: 'email',
+			source_id: this.messageIds(message.headers.get('message-id'))[0] ?? fallbackId,
+			source_url: null,
+
+			deleted_at: null,
+		};
+	}
+
+	/**
+	 * Build the person card for a correspondent, keyed by address.
+	 */
+	private personCard(mailbox: Mailbox, timestamp: string): CanonicalCard {
+		return {
+			id: crypto.randomUUID(),
+			card_type: 'person',
+			name: mailbox.name || mailbox.address,
+			content: null,
+			summary: null,
+
+			latitude: null,
+			longitude: null,
+			location_name: null,
+
+			created_at: timestamp,
+			modified_at: timestamp,
+			due_at: null,
+			completed_at: null,
+			event_start: null,
+			event_end: null,
+
+			folder: null,
+			tags: [],
+			status: null,
+
+			priority: 0,
+			sort_order: 0,
+
+			url: `mailto:${mailbox.address}`,
+			mime_type: null,
+			is_collective: false,
+
+			source: 'email',
+			source_id: `mailto:${mailbox.address}`,
+			source_url: null,
+
+			deleted_at: null,
+		};
+	}
+
+	/**
+	 * The message text: the first text/plain part, else the first text/html
+	 * part flattened to text. Attachments are never used as the body.
+	 */
+	private bodyText(message: MimePart): string | null {
+		const parts = this.leafParts(message).filter((part) => this.attachmentName(part) === null);
+		const plain = parts.find((part) => this.contentType(part).type === 'text/plain');
+		if (plain) return this.decodeBody(plain).trim() || null;
+		const html = parts.find((part) => this.contentType(part).type === 'text/html');
+		return html ? htmlToText(this.decodeBody(html)) || null : null;
+	}
+
+	/**
+	 * Non-multipart entities of a message, depth first.
+	 */
+	private leafParts(part: MimePart): MimePart[] {
+		const { type, params } = this.contentType(part);
+		const boundary = params['boundary'];
+		if (!type.startsWith('multipart/') || !boundary) return [part];
+
+		const delimiter = `--${boundary}`;
+		const sections = part.body.split('\n');
+		const children: string[] = [];
+		let current: string[] | null = null;
+		for (const line of sections) {
+			if (line.startsWith(delimiter)) {
+				if (current) children.push(current.join('\n'));
+				// The closing delimiter (`--boundary--`) ends the multipart body
+				current = line.trimEnd() === `${delimiter}--` ? null : [];
+				if (!current) break;
+			} else if (current) {
+				current.push(line);
+			}
+		}
+		return children.flatMap((child) => this.leafParts(this.parsePart(child)));
+	}
+
+	/**
+	 * Filename of an attachment part, or null for inline body parts.
+	 */
+	private attachmentName(part: MimePart): string | null {
+		const disposition = parseHeaderParams(part.headers.get('content-disposition') ?? '');
+		const { type, params } = this.contentType(part);
+		const filename = disposition.params['filename'] ?? params['name'];
+		if (filename) return decodeWords(filename).trim() || 'attachment';
+		if (disposition.value === 'attachment') return 'attachment';
+		return type.startsWith('text/') || type.startsWith('multipart/') ? null : 'attachment';
+	}
+
+	private contentType(part: MimePart): { type: string; params: Record<string, string> } {
+		const { value, params } = parseHeaderParams(part.headers.get('content-type') ?? 'text/plain');
+		return { type: value || 'text/plain', params };
+	}
+
+	/**
+	 * Undo the transfer encoding and decode the charset.
+	 */
+	private decodeBody(part: MimePart): string {
+		const encoding = (part.headers.get('content-transfer-encoding') ?? '').toLowerCase();
+		const charset = this.contentType(part).params['charset'];
+		if (encoding === 'base64') return decodeBytes(base64Bytes(part.body), charset);
+		if (encoding === 'quoted-printable') return decodeBytes(quotedPrintableBytes(part.body), charset);
+		return part.body;
+	}
+
+	/**
+	 * `<id>` tokens of Message-ID / In-Reply-To / References, without brackets.
+	 */
+	private messageIds(value: string | undefined): string[] {
+		if (!value) return [];
+		const ids = [...value.matchAll(/<([^<>\s]+)>/g)].map((m) => m[1]!);
+		if (ids.length > 0) return ids;
+		// Some clients omit the angle brackets around a lone id
+		const bare = value.trim();
+		return bare && !/\s/.test(bare) ? [bare] : [];
+	}
+
+	/**
+	 * Addresses of an address-list header (`Name <a@b>, "Last, First" <c@d>, e@f`).
+	 */
+	private mailboxes(value: string | undefined): Mailbox[] {
+		if (!value) return [];
+		const mailboxes: Mailbox[] = [];
+		for (const entry of splitAddresses(decodeWords(value))) {
+			const angle = entry.match(/^(.*)<([^<>]+)>\s*$/);
+			const address = (angle ? angle[2]! : entry).trim().toLowerCase();
+			if (!/^[^\s@]+@[^\s@]+$/.test(address)) continue;
+			const name = angle ? angle[1]!.trim().replace(/^"(.*)"$/, '$1').trim() : '';
+			mailboxes.push({ name: name || null, address });
+		}
+		return mailboxes;
+	}
+
+	/**
+	 * RFC 5322 date to ISO 8601, null if unparseable.
+	 */
+	private parseDate(value: string | undefined): string | null {
+		if (!value) return null;
+		const date = new Date(value.replace(/\([^)]*\)/g, '').trim());
+		if (Number.isNaN(date.getTime())) return null;
+		return date.toISOString().replace(/\.000Z$/, 'Z');
+	}
+}
+
+// ---------------------------------------------------------------------------
+// MIME helpers
+// ---------------------------------------------------------------------------
+
+/**
+ * `value; key=val; key2="quoted"` -> lowercased value and params.
+ * RFC 2231 `key*=charset''encoded` params are percent-decoded.
+ */
+function parseHeaderParams(header: string): { value: string; params: Record<string, string> } {
+	const [value = '', ...rest] = header.split(/;(?=(?:[^"]*"[^"]*")*[^"]*$)/);
+	const params: Record<string, string> = {};
+	for (const param of rest) {
+		const eq = param.indexOf('=');
+		if (eq === -1) continue;
+		const key = param.slice(0, eq).trim().toLowerCase();
+		let val = param
+			.slice(eq + 1)
+			.trim()
+			.replace(/^"(.*)"$/, '$1');
+		if (key.endsWith('*')) {
+			const encoded = val.match(/^([^']*)'[^']*'(.*)$/);
+			try {
+				val = decodeURIComponent(encoded ? encoded[2]! : val);
+			} catch {
+				// Keep the raw value when it is not valid percent-encoding
+			}
+			params[key.slice(0, -1)] = val;
+		} else {
+			params[key] ??= val;
+		}
+	}
+	return { value: value.trim().toLowerCase(), params };
+}
+
+/**
+ * Split an address list on commas outside quotes and angle brackets.
+ * Group syntax (`team: a@b, c@d;`) contributes its members.
+ */
+function splitAddresses(value: string): string[] {
+	const entries: string[] = [];
+	let current = '';
+	let inQuotes = false;
+	let inAngle = false;
+	for (const ch of value) {
+		if (ch === '"') inQuotes = !inQuotes;
+		else if (!inQuotes && ch === '<') inAngle = true;
+		else if (!inQuotes && ch === '>') inAngle = false;
+		if (!inQuotes && !inAngle && (ch === ',' || ch === ';')) {
+			entries.push(current);
+			current = '';
+		} else if (!inQuotes && !inAngle && ch === ':') {
+			current = ''; // Group display name
+		} else {
+			current += ch;
+		}
+	}
+	entries.push(current);
+	return entries.map((entry) => entry.trim()).filter(Boolean);
+}
+
+/**
+ * Decode RFC 2047 encoded words (`=?utf-8?B?...?=`, `=?iso-8859-1?Q?...?=`).
+ * Whitespace between adjacent encoded words is dropped.
+ */
+function decodeWords(value: string): string {
+	return value
+		.replace(/(\?=)\s+(=\?)/g, '$1$2')
+		.replace(/=\?([^?]+)\?([bq])\?([^?]*)\?=/gi, (_match, charset: string, encoding: string, text: string) => {
+			const bytes =
+				encoding.toLowerCase() === 'b' ? base64Bytes(text) : quotedPrintableBytes(text.replace(/_/g, ' '));
+			return decodeBytes(bytes, charset);
+		});
+}
+
+function base64Bytes(text: string): Uint8Array {
+	try {
+		const binary = atob(text.replace(/[^A-Za-z0-9+/=]/g, ''));
+		return Uint8Array.from(binary, (ch) => ch.charCodeAt(0));
+	} catch {
+		return new Uint8Array();
+	}
+}
+
+function quotedPrintableBytes(text: string): Uint8Array {
+	const source = text.replace(/=\n/g, '');
+	const bytes: number[] = [];
+	const encoder = new TextEncoder();
+	for (let i = 0; i < source.length; i++) {
+		const hex = source.slice(i + 1, i + 3);
+		if (source[i] === '=' && /^[0-9A-F]{2}$/i.test(hex)) {
+			bytes.push(Number.parseInt(hex, 16));
+			i += 2;
+		} else {
+			bytes.push(...encoder.encode(source[i]));
+		}
+	}
+	return new Uint8Array(bytes);
+}
+
+function decodeBytes(bytes: Uint8Array, charset = 'utf-8'): string {
+	try {
+		return new TextDecoder(charset).decode(bytes);
+	} catch {
+		return new TextDecoder().decode(bytes);
+	}
+}
+
+/**
+ * Flatten an HTML body to text (Worker-safe, regex based).
+ */
+function htmlToText(html: string): string {
+	const text = html
+		.replace(/<(script|style|head)\b[\s\S]*?<\/\1>/gi, '')
+		.replace(/<br\s*\/?>/gi, '\n')
+		.replace(/<\/(p|div|li|tr|h[1-6])>/gi, '\n')
+		.replace(/<[^>]+>/g, '');
+	return decodeEntities(text)
+		.replace(/[ \t]+\n/g, '\n')
+		.replace(/\n{3,}/g, '\n\n')
+		.trim();
+}
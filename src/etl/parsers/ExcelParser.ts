@@ -8,8 +8,15 @@
 // - Dynamic import of xlsx (not top-level) to defer ~1MB bundle load
 // - cellDates: true to avoid Excel date serial number confusion
 // - Date objects converted to ISO 8601 strings
+//
+// Multi-sheet workbooks import as one dataset: one folder per sheet, rows keyed
+// by the sheet's id column, and lookup columns holding another sheet's ids
+// (Orders.CustomerID -> Customers.CustomerID) turned into connections.
 
+import type { WorkBook, WorkSheet } from 'xlsx';
+import { extractRelationships, type MappingProfile, mapRowWithProfile, normalizeHeader } from '../MappingProfile';
 import type { CanonicalCard, CanonicalConnection, ParseError } from '../types';
+import { collectUnmappedProperties } from './properties';
 
 /**
  * Field mapping options for ExcelParser.
@@ -28,6 +35,20 @@ export interface ExcelParseOptions {
 	source?: string;
 	/** Specific sheet name to parse (default: first sheet) */
 	sheet?: string;
+	/** Several sheets to import together as one dataset (overrides sheet) */
+	sheets?: string[];
+	/** Saved mapping profile (overrides fieldMapping and auto-detection; single-sheet imports only) */
+	profile?: MappingProfile;
+}
+
+/**
+ * A workbook sheet as shown in the sheet picker.
+ */
+export interface ExcelSheetInfo {
+	name: string;
+	/** Data rows (excluding the header row) */
+	rows: number;
+	headers: string[];
 }
 
 /**
@@ -51,6 +72,26 @@ const HEADER_SYNONYMS = {
 	folder: ['folder', 'category', 'group'],
 };
 
+const XLSX_MIME_TYPE = 'application/vnd.openxmlformats-officedocument.spreadsheetml.sheet';
+
+/** A sheet of a multi-sheet import with its key column and key values */
+interface SheetTable {
+	name: string;
+	headers: string[];
+	rows: Record<string, unknown>[];
+	/** Column holding unique, non-empty row ids, if the sheet has one */
+	key: string | null;
+	keys: Set<string>;
+}
+
+/** A column of one sheet holding the key values of another */
+interface SheetLookup {
+	from: SheetTable;
+	column: string;
+	to: SheetTable;
+	label: string;
+}
+
 /**
  * ExcelParser transforms Excel files into canonical cards.
  * Uses dynamic import to load xlsx library only when needed (bundle optimization).
@@ -82,8 +123,13 @@ export class ExcelParser {
 				cellDates: true, // Convert Excel date serial numbers to Date objects
 			});
 
+			// Several selected sheets import together as one dataset
+			if (options?.sheets && options.sheets.length > 1) {
+				return this.parseSheets(this.xlsx, workbook, options.sheets, options);
+			}
+
 			// Select sheet (explicit option or first sheet)
-			const sheetName = options?.sheet ?? workbook.SheetNames[0];
+			const sheetName = options?.sheets?.[0] ?? options?.sheet ?? workbook.SheetNames[0];
 			if (!sheetName) {
 				return { cards, connections, errors };
 			}
@@ -102,6 +148,7 @@ export class ExcelParser {
 			const rows = this.xlsx.utils.sheet_to_json(sheet) as Record<string, unknown>[];
 
 			// Process each row
+			const mappedRows: Array<{ row: Record<string, unknown>; card: CanonicalCard }> = [];
 			for (let i = 0; i < rows.length; i++) {
 				const row = rows[i];
 				if (!row || typeof row !== 'object') {
@@ -109,8 +156,16 @@ export class ExcelParser {
 				}
 
 				try {
-					const card = this.parseRow(row, i, options);
+					const card = options?.profile
+						? mapRowWithProfile(row, options.profile, {
+								index: i,
+								source: options.source ?? 'excel',
+								mimeType: XLSX_MIME_TYPE,
+								sourceId: String(i),
+							})
+						: this.parseRow(row, i, options);
 					cards.push(card);
+					if (options?.profile) mappedRows.push({ row, card });
 				} catch (error) {
 					errors.push({
 						index: i,
@@ -119,6 +174,11 @@ export class ExcelParser {
 					});
 				}
 			}
+
+			// Profile relationship rules turn foreign-key columns into connections
+			if (options?.profile) {
+				connections.push(...extractRelationships(mappedRows, options.profile));
+			}
 		} catch (error) {
 			// Workbook parsing error
 			errors.push({
@@ -131,6 +191,210 @@ export class ExcelParser {
 		return { cards, connections, errors };
 	}
 
+	/**
+	 * Header row of the selected sheet, for mapping profile suggestion.
+	 * Empty when the workbook cannot be read.
+	 */
+	async readHeaders(buffer: ArrayBuffer, sheetName?: string): Promise<string[]> {
+		if (!this.xlsx) {
+			this.xlsx = await import('xlsx');
+		}
+		try {
+			const workbook = this.xlsx.read(buffer, { type: 'array', sheetRows: 1 });
+			const sheet = workbook.Sheets[sheetName ?? workbook.SheetNames[0] ?? ''];
+			return sheet ? this.sheetHeaders(this.xlsx, sheet) : [];
+		} catch {
+			return [];
+		}
+	}
+
+	/**
+	 * Sheets of the workbook with their row counts and header rows, for the
+	 * sheet picker. Empty when the workbook cannot be read.
+	 */
+	async listSheets(buffer: ArrayBuffer): Promise<ExcelSheetInfo[]> {
+		if (!this.xlsx) {
+			this.xlsx = await import('xlsx');
+		}
+		const xlsx = this.xlsx;
+		try {
+			const workbook = xlsx.read(buffer, { type: 'array' });
+			return workbook.SheetNames.map((name) => {
+				const sheet = workbook.Sheets[name];
+				if (!sheet) return { name, rows: 0, headers: [] };
+				return { name, rows: xlsx.utils.sheet_to_json(sheet).length, headers: this.sheetHeaders(xlsx, sheet) };
+			});
+		} catch {
+			return [];
+		}
+	}
+
+	/**
+	 * Header row of a sheet.
+	 */
+	private sheetHeaders(xlsx: typeof import('xlsx'), sheet: WorkSheet): string[] {
+		const [header] = xlsx.utils.sheet_to_json<unknown[]>(sheet, { header: 1 });
+		return (header ?? []).filter((cell) => cell !== null && cell !== undefined).map(String);
+	}
+
+	/**
+	 * Parse several sheets as one dataset. Each sheet becomes a folder, rows of a
+	 * sheet with a key column get `<sheet>:<key>` source ids so re-imports update
+	 * them in place, and lookup columns become connections to the rows they name.
+	 */
+	private parseSheets(
+		xlsx: typeof import('xlsx'),
+		workbook: WorkBook,
+		sheetNames: string[],
+		options: ExcelParseOptions,
+	): ParseResult {
+		const cards: CanonicalCard[] = [];
+		const connections: CanonicalConnection[] = [];
+		const errors: ParseError[] = [];
+
+		const tables: SheetTable[] = [];
+		for (const name of sheetNames) {
+			const sheet = workbook.Sheets[name];
+			if (!sheet) {
+				errors.push({ index: 0, source_id: null, message: `Sheet "${name}" not found in workbook` });
+				continue;
+			}
+			const rows = xlsx.utils.sheet_to_json(sheet) as Record<string, unknown>[];
+			const headers = this.sheetHeaders(xlsx, sheet);
+			const key = this.findKeyColumn(name, headers, rows);
+			const keys = new Set(key ? rows.map((row) => this.cellKey(row[key]) ?? '') : []);
+			tables.push({ name, headers, rows, key, keys });
+		}
+
+		for (const table of tables) {
+			const nameColumn = table.headers.find((h) => normalizeHeader(h).replace(/ /g, '').endsWith('name'));
+			for (let i = 0; i < table.rows.length; i++) {
+				const row = table.rows[i];
+				if (!row || typeof row !== 'object') {
+					continue;
+				}
+
+				const sourceId = this.rowSourceId(table, row, i);
+				try {
+					const card = this.parseRow(row, i, options);
+					card.source_id = sourceId;
+					card.folder = table.name;
+					// Sheets without a title column are named by a *Name column or their key
+					if (!(this.findField(row, 'name', options.fieldMapping?.name) in row)) {
+						const named = nameColumn ? this.extractString(row[nameColumn]) : null;
+						card.name = named ?? `${table.name} ${sourceId.slice(table.name.length + 1)}`;
+					}
+					cards.push(card);
+				} catch (error) {
+					errors.push({
+						index: i,
+						source_id: sourceId,
+						message: error instanceof Error ? error.message : String(error),
+					});
+				}
+			}
+		}
+
+		const now = new Date().toISOString();
+		for (const lookup of this.findSheetLookups(tables)) {
+			for (let i = 0; i < lookup.from.rows.length; i++) {
+				const row = lookup.from.rows[i];
+				if (!row) continue;
+				const value = this.cellKey(row[lookup.column]);
+				if (value === null || !lookup.to.keys.has(value)) continue;
+				connections.push({
+					id: crypto.randomUUID(),
+					source_id: this.rowSourceId(lookup.from, row, i),
+					target_id: `${lookup.to.name}:${value}`,
+					via_card_id: null,
+					label: lookup.label,
+					weight: 1,
+					created_at: now,
+				});
+			}
+		}
+
+		return { cards, connections, errors };
+	}
+
+	/**
+	 * Key column of a sheet: an `id` or `<sheet>id` column (CustomerID on
+	 * Customers), else a first column named like an id (OrderID, order_id) — only
+	 * when its values are unique and non-empty.
+	 */
+	private findKeyColumn(sheetName: string, headers: string[], rows: Record<string, unknown>[]): string | null {
+		if (rows.length === 0) return null;
+		const singular = this.singularName(sheetName).replace(/ /g, '');
+		const candidates = headers.filter((h) => {
+			const compact = normalizeHeader(h).replace(/ /g, '');
+			return compact === 'id' || compact === `${singular}id`;
+		});
+		const first = headers[0];
+		if (first && (/(?:^|[\s_-])id$/i.test(first) || /[a-z](?:ID|Id)$/.test(first))) candidates.push(first);
+
+		return (
+			candidates.find((column) => {
+				const values = new Set<string>();
+				for (const row of rows) {
+					const value = this.cellKey(row[column]);
+					if (value === null || values.has(value)) return false;
+					values.add(value);
+				}
+				return true;
+			}) ?? null
+		);
+	}
+
+	/**
+	 * Columns that reference another sheet's keys: the same header as that
+	 * sheet's key (Orders.CustomerID -> Customers.CustomerID), or `<sheet>id`
+	 * when that sheet's key is a bare `id`. Labelled with the singular sheet name.
+	 */
+	private findSheetLookups(tables: SheetTable[]): SheetLookup[] {
+		const lookups: SheetLookup[] = [];
+		for (const from of tables) {
+			for (const column of from.headers) {
+				if (column === from.key) continue;
+				const compact = normalizeHeader(column).replace(/ /g, '');
+				const to = tables.find((t) => {
+					if (t === from || !t.key) return false;
+					const key = normalizeHeader(t.key).replace(/ /g, '');
+					return (compact === key && key !== 'id') || compact === `${this.singularName(t.name).replace(/ /g, '')}id`;
+				});
+				if (to) lookups.push({ from, column, to, label: this.singularName(to.name) });
+			}
+		}
+		return lookups;
+	}
+
+	/**
+	 * Source id of a row in a multi-sheet import.
+	 */
+	private rowSourceId(table: SheetTable, row: Record<string, unknown>, index: number): string {
+		return `${table.name}:${table.key ? this.cellKey(row[table.key]) : index}`;
+	}
+
+	/**
+	 * Sheet name as a singular, normalized noun ("Order_Details" -> "order detail").
+	 */
+	private singularName(sheetName: string): string {
+		const name = normalizeHeader(sheetName);
+		if (name.endsWith('ies')) return `${name.slice(0, -3)}y`;
+		if (name.endsWith('s') && !name.endsWith('ss')) return name.slice(0, -1);
+		return name;
+	}
+
+	/**
+	 * Cell value as a key string, or null for blank cells.
+	 */
+	private cellKey(value: unknown): string | null {
+		if (typeof value === 'number' || typeof value === 'boolean') return String(value);
+		if (value instanceof Date) return value.toISOString();
+		if (typeof value !== 'string') return null;
+		const trimmed = value.trim();
+		return trimmed.length > 0 ? trimmed : null;
+	}
+
 	/**
 	 * Parse a single Excel row into a CanonicalCard.
 	 */
@@ -151,6 +415,9 @@ export class ExcelParser {
 		const created_at = this.extractDate(row[createdField]);
 		const folder = this.extractString(row[folderField]);
 
+		// Fields outside the mapping become custom properties
+		const properties = collectUnmappedProperties(row, [nameField, contentField, tagsField, createdField, folderField]);
+
 		const card: CanonicalCard = {
 			id: crypto.randomUUID(),
 			card_type: 'note',
@@ -177,7 +444,7 @@ export class ExcelParser {
 			sort_order: index,
 
 			url: null,
-			mime_type: 'application/vnd.openxmlformats-officedocument.spreadsheetml.sheet',
+			mime_type: XLSX_MIME_TYPE,
 			is_collective: false,
 
 			source: source,
@@ -185,6 +452,7 @@ export class ExcelParser {
 			source_url: null,
 
 			deleted_at: null,
+			...(properties ? { properties } : {}),
 		};
 
 		return card;
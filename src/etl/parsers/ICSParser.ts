@@ -0,0 +1,396 @@
+// Isometry v5 — iCalendar Parser
+// Parses .ics files (RFC 5545) into event and task cards, the pure-TS
+// counterpart of the native CalendarAdapter / RemindersAdapter.
+//
+// Features:
+// - VEVENT -> event cards (event_start, event_end, location_name, GEO)
+// - VTODO -> task cards (due_at, completed_at, status, priority)
+// - TZID times resolved through Intl; floating times read as UTC
+// - All-day values kept as dates (`YYYY-MM-DD`)
+// - RRULE / RDATE / EXDATE expanded within a window, one card per occurrence,
+//   with RECURRENCE-ID overrides replacing their instance
+// - VALARM / VTIMEZONE and unknown components ignored
+//
+// Source ids are the event UID; occurrences of a recurring event use
+// `${uid}#${occurrenceStart}` so re-imports update each instance in place.
+
+import type { CanonicalCard, CanonicalConnection, ParseError } from '../types';
+import { type ContentLine, parseContentLine, splitValue, unescapeText, unfoldLines } from './contentLines';
+import {
+	expandRecurrence,
+	type ICalDate,
+	parseDuration,
+	parseICalDate,
+	parseRRule,
+	shiftDate,
+	toEpoch,
+	toIso,
+} from './recurrence';
+
+export interface ICSParseOptions {
+	/** Source filename — folder fallback when the calendar has no X-WR-CALNAME */
+	filename?: string;
+	/** Expansion window start (ISO 8601), default one year before now */
+	windowStart?: string;
+	/** Expansion window end (ISO 8601), default two years after now */
+	windowEnd?: string;
+	/** Default timestamp when a component has no CREATED / DTSTAMP */
+	defaultTimestamp?: string;
+}
+
+export interface ICSParseResult {
+	cards: CanonicalCard[];
+	connections: CanonicalConnection[];
+	errors: ParseError[];
+}
+
+/** A parsed VEVENT / VTODO with its properties (nested components excluded) */
+interface Component {
+	type: 'VEVENT' | 'VTODO';
+	index: number;
+	props: ContentLine[];
+}
+
+const YEAR_MS = 365 * 86_400_000;
+/** Occurrences kept per recurring event */
+const MAX_OCCURRENCES = 1000;
+
+/**
+ * ICSParser transforms an iCalendar document into event and task cards.
+ */
+export class ICSParser {
+	/**
+	 * Parse an iCalendar document.
+	 *
+	 * @param ics - iCalendar text
+	 * @param options - Optional parsing configuration
+	 * @returns Parse result with cards, connections, and errors
+	 */
+	parse(ics: string, options?: ICSParseOptions): ICSParseResult {
+		const cards: CanonicalCard[] = [];
+		const errors: ParseError[] = [];
+
+		if (!/^BEGIN:VCALENDAR/im.test(ics)) {
+			throw new Error('Not an iCalendar document');
+		}
+
+		const { components, calendarName } = this.readComponents(ics);
+		const folder = calendarName ?? options?.filename?.replace(/\.(ics|ical|ifb)$/i, '') ?? null;
+		const now = Date.now();
+		const window = {
+			start: this.parseWindow(options?.windowStart) ?? now - YEAR_MS,
+			end: this.parseWindow(options?.windowEnd) ?? now + 2 * YEAR_MS,
+		};
+		const fallbackTime = options?.defaultTimestamp ?? new Date(now).toISOString();
+
+		// RECURRENCE-ID overrides, keyed by UID then original start
+		const overrides = new Map<string, Map<number, Component>>();
+		for (const component of components) {
+			const uid = this.text(component, 'UID');
+			const recurrenceId = this.date(component, 'RECURRENCE-ID');
+			if (component.type !== 'VEVENT' || !uid || !recurrenceId) continue;
+			const byStart = overrides.get(uid) ?? new Map<number, Component>();
+			byStart.set(toEpoch(recurrenceId), component);
+			overrides.set(uid, byStart);
+		}
+
+		for (const component of components) {
+			try {
+				const uid = this.text(component, 'UID') ?? `${options?.filename ?? 'calendar'}#${component.index}`;
+				if (component.type === 'VTODO') {
+					cards.push(this.taskCard(component, uid, folder, fallbackTime));
+					continue;
+				}
+
+				const recurrenceId = this.date(component, 'RECURRENCE-ID');
+				if (recurrenceId) {
+					// Overrides are emitted in place of their instance; orphans stand alone
+					const master = components.some(
+						(c) => c !== component && this.text(c, 'UID') === uid && !this.first(c, 'RECURRENCE-ID'),
+					);
+					if (!master) {
+						cards.push(this.eventCard(component, `${uid}#${toIso(recurrenceId)}`, folder, fallbackTime));
+					}
+					continue;
+				}
+
+				cards.push(...this.eventCards(component, uid, folder, fallbackTime, window, overrides.get(uid)));
+			} catch (error) {
+				errors.push({
+					index: component.index,
+					source_id: this.text(component, 'UID'),
+					message: error instanceof Error ? error.message : String(error),
+				});
+			}
+		}
+
+		return { cards, connections: [], errors };
+	}
+
+	/**
+	 * Split the document into VEVENT / VTODO components.
+	 */
+	private readComponents(ics: string): { components: Component[]; calendarName: string | null } {
+		const components: Component[] = [];
+		const stack: string[] = [];
+		let current: Component | null = null;
+		let calendarName: string | null = null;
+
+		for (const raw of unfoldLines(ics)) {
+			const line = parseContentLine(raw);
+			if (!line) continue;
+			const value = line.value.trim().toUpperCase();
+
+			if (line.name === 'BEGIN') {
+				stack.push(value);
+				if ((value === 'VEVENT' || value === 'VTODO') && stack.length === 2) {
+					current = { type: value, index: components.length, props: [] };
+				}
+			} else if (line.name === 'END') {
+				const closed = stack.pop();
+				if (current && closed === current.type && stack.length === 1) {
+					components.push(current);
+					current = null;
+				}
+			} else if (current && stack.length === 2) {
+				current.props.push(line);
+			} else if (stack.length === 1 && line.name === 'X-WR-CALNAME') {
+				calendarName = unescapeText(line.value).trim() || null;
+			}
+		}
+
+		return { components, calendarName };
+	}
+
+	/**
+	 * Cards for one VEVENT: a single card, or one per occurrence when it recurs.
+	 */
+	private eventCards(
+		component: Component,
+		uid: string,
+		folder: string | null,
+		fallbackTime: string,
+		window: { start: number; end: number },
+		overrides: Map<number, Component> | undefined,
+	): CanonicalCard[] {
+		const start = this.date(component, 'DTSTART');
+		const rruleValue = this.first(component, 'RRULE')?.value;
+		const rdates = this.dates(component, 'RDATE');
+		if (!start || (!rruleValue && rdates.length === 0)) {
+			return [this.eventCard(component, uid, folder, fallbackTime)];
+		}
+
+		const end = this.eventEnd(component, start);
+		const durationMs = toEpoch(end) - toEpoch(start);
+		const exdates = new Set(this.dates(component, 'EXDATE').map(toEpoch));
+		const rule = rruleValue ? parseRRule(rruleValue, start) : null;
+
+		let occurrences = rule
+			? expandRecurrence(start, rule, {
+					windowStart: window.start,
+					windowEnd: window.end,
+					durationMs,
+					exdates,
+					limit: MAX_OCCURRENCES,
+				})
+			: [start];
+		// RDATEs add instances outside the rule
+		const extra = rdates.filter((d) => {
+			const epoch = toEpoch(d);
+			return !exdates.has(epoch) && epoch + durationMs >= window.start && epoch <= window.end;
+		});
+		if (extra.length > 0) {
+			const byEpoch = new Map([...occurrences, ...extra].map((d) => [toEpoch(d), d]));
+			occurrences = [...byEpoch.keys()].sort((a, b) => a - b).map((epoch) => byEpoch.get(epoch)!);
+		}
+
+		return occurrences.map((occurrence) => {
+			const sourceId = `${uid}#${toIso(occurrence)}`;
+			const override = overrides?.get(toEpoch(occurrence));
+			if (override) return this.eventCard(override, sourceId, folder, fallbackTime);
+			const occurrenceEnd = start.dateOnly
+				? shiftDate(occurrence, Math.round(durationMs / 86_400_000))
+				: shiftDate(occurrence, 0, durationMs);
+			return this.eventCard(component, sourceId, folder, fallbackTime, { start: occurrence, end: occurrenceEnd });
+		});
+	}
+
+	/**
+	 * Build an event card from a VEVENT, optionally at an occurrence's times.
+	 */
+	private eventCard(
+		component: Component,
+		sourceId: string,
+		folder: string | null,
+		fallbackTime: string,
+		occurrence?: { start: ICalDate; end: ICalDate },
+	): CanonicalCard {
+		const start = occurrence?.start ?? this.date(component, 'DTSTART');
+		const end = occurrence?.end ?? (start ? this.eventEnd(component, start) : null);
+		const description = this.text(component, 'DESCRIPTION');
+		const geo = this.first(component, 'GEO')?.value.split(/[;,]/).map(Number);
+		const hasGeo = geo?.length === 2 && geo.every((n) => Number.isFinite(n));
+		const attendees = component.props.filter((p) => p.name === 'ATTENDEE').length;
+		const { created, modified } = this.timestamps(component, fallbackTime);
+
+		return {
+			id: crypto.randomUUID(),
+			card_type: 'event',
+			name: this.text(component, 'SUMMARY') || 'Untitled Event',
+			content: description,
+			summary: description ? description.slice(0, 200) : null,
+
+			latitude: hasGeo ? geo![0]! : null,
+			longitude: hasGeo ? geo![1]! : null,
+			location_name: this.text(component, 'LOCATION'),
+
+			created_at: created,
+			modified_at: modified,
+			due_at: null,
+			completed_at: null,
+			event_start: start ? toIso(start) : null,
+			event_end: end ? toIso(end) : null,
+
+			folder,
+			tags: this.categories(component),
+			status: this.text(component, 'STATUS')?.toLowerCase() ?? null,
+
+			priority: 0,
+			sort_order: 0,
+
+			url: this.text(component, 'URL'),
+			mime_type: null,
+			is_collective: attendees > 1,
+
+			source: 'ics',
+			source_id: sourceId,
+			source_url: null,
+
+			deleted_at: null,
+		};
+	}
+
+	/**
+	 * Build a task card from a VTODO.
+	 */
+	private taskCard(component: Component, sourceId: string, folder: string | null, fallbackTime: string): CanonicalCard {
+		const description = this.text(component, 'DESCRIPTION');
+		const start = this.date(component, 'DTSTART');
+		const duration = parseDuration(this.first(component, 'DURATION')?.value ?? '');
+		const due =
+			this.date(component, 'DUE') ?? (start && duration ? shiftDate(start, duration.days, duration.ms) : null);
+		const completed = this.date(component, 'COMPLETED');
+		const status = this.text(component, 'STATUS')?.toUpperCase();
+		const { created, modified } = this.timestamps(component, fallbackTime);
+
+		return {
+			id: crypto.randomUUID(),
+			card_type: 'task',
+			name: this.text(component, 'SUMMARY') || 'Untitled Task',
+			content: description,
+			summary: description ? description.slice(0, 200) : null,
+
+			latitude: null,
+			longitude: null,
+			location_name: this.text(component, 'LOCATION'),
+
+			created_at: created,
+			modified_at: modified,
+			due_at: due ? toIso(due) : null,
+			completed_at: completed ? toIso(completed) : null,
+			event_start: null,
+			event_end: null,
+
+			folder,
+			tags: this.categories(component),
+			status: completed || status === 'COMPLETED' ? 'done' : status === 'CANCELLED' ? 'cancelled' : 'active',
+
+			priority: this.priority(this.first(component, 'PRIORITY')?.value),
+			sort_order: 0,
+
+			url: this.text(component, 'URL'),
+			mime_type: null,
+			is_collective: false,
+
+			source: 'ics',
+			source_id: sourceId,
+			source_url: null,
+
+			deleted_at: null,
+		};
+	}
+
+	/**
+	 * DTEND, else DTSTART + DURATION, else the end of the start day for
+	 * all-day events and the start itself for timed ones.
+	 */
+	private eventEnd(component: Component, start: ICalDate): ICalDate {
+		const end = this.date(component, 'DTEND');
+		if (end) return end;
+		const duration = parseDuration(this.first(component, 'DURATION')?.value ?? '');
+		if (duration) return shiftDate(start, duration.days, start.dateOnly ? 0 : duration.ms);
+		return start.dateOnly ? shiftDate(start, 1) : start;
+	}
+
+	/**
+	 * CREATED / LAST-MODIFIED, falling back to DTSTAMP, then the default.
+	 */
+	private timestamps(component: Component, fallbackTime: string): { created: string; modified: string } {
+		const stamp = this.date(component, 'DTSTAMP');
+		const created = this.date(component, 'CREATED') ?? stamp;
+		const modified = this.date(component, 'LAST-MODIFIED') ?? stamp ?? created;
+		const createdIso = created ? toIso(created) : fallbackTime;
+		return { created: createdIso, modified: modified ? toIso(modified) : createdIso };
+	}
+
+	/**
+	 * iCalendar PRIORITY (1 = highest, 9 = lowest, 0 = undefined) to the
+	 * 0-3 scale RemindersAdapter uses.
+	 */
+	private priority(value: string | undefined): number {
+		const n = Number(value ?? 0);
+		if (n >= 1 && n <= 4) return 3;
+		if (n === 5) return 2;
+		if (n >= 6 && n <= 9) return 1;
+		return 0;
+	}
+
+	private categories(component: Component): string[] {
+		const tags = component.props
+			.filter((p) => p.name === 'CATEGORIES')
+			.flatMap((p) => splitValue(p.value, ','))
+			.map((t) => t.trim())
+			.filter(Boolean);
+		return [...new Set(tags)];
+	}
+
+	private first(component: Component, name: string): ContentLine | undefined {
+		return component.props.find((p) => p.name === name);
+	}
+
+	private text(component: Component, name: string): string | null {
+		const line = this.first(component, name);
+		return line ? unescapeText(line.value).trim() || null : null;
+	}
+
+	private date(component: Component, name: string): ICalDate | null {
+		const line = this.first(component, name);
+		return line ? parseICalDate(line.value, line.params['TZID']) : null;
+	}
+
+	/**
+	 * All values of a multi-valued date property (EXDATE, RDATE), across lines.
+	 */
+	private dates(component: Component, name: string): ICalDate[] {
+		return component.props
+			.filter((p) => p.name === name)
+			.flatMap((p) => p.value.split(',').map((v) => parseICalDate(v, p.params['TZID'])))
+			.filter((d): d is ICalDate => d !== null);
+	}
+
+	private parseWindow(value: string | undefined): number | null {
+		if (!value) return null;
+		const epoch = new Date(value).getTime();
+		return Number.isNaN(epoch) ? null : epoch;
+	}
+}
@@ -5,7 +5,9 @@
 //   - ETL-06: JSON parser with field auto-detection
 //   - ETL-08: Support for nested JSON structures
 
+import { extractRelationships, type MappingProfile, mapRowWithProfile } from '../MappingProfile';
 import type { CanonicalCard, CanonicalConnection, ParseError } from '../types';
+import { collectUnmappedProperties } from './properties';
 
 /**
  * Field mapping options for JSONParser.
@@ -22,6 +24,8 @@ export interface JSONParseOptions {
 	};
 	/** Source identifier for imported cards (default: 'json') */
 	source?: string;
+	/** Saved mapping profile (overrides fieldMapping and auto-detection) */
+	profile?: MappingProfile;
 }
 
 /**
@@ -72,6 +76,7 @@ export class JSONParser {
 			const items = Array.isArray(data) ? data : [data];
 
 			// Process each item
+			const mappedRows: Array<{ row: Record<string, unknown>; card: CanonicalCard }> = [];
 			for (let i = 0; i < items.length; i++) {
 				const item = items[i];
 				if (!item || typeof item !== 'object') {
@@ -79,8 +84,16 @@ export class JSONParser {
 				}
 
 				try {
-					const card = this.parseItem(item, i, options);
+					const card = options?.profile
+						? mapRowWithProfile(item, options.profile, {
+								index: i,
+								source: options.source ?? 'json',
+								mimeType: 'application/json',
+								sourceId: String(i),
+							})
+						: this.parseItem(item, i, options);
 					cards.push(card);
+					if (options?.profile) mappedRows.push({ row: item, card });
 				} catch (error) {
 					errors.push({
 						index: i,
@@ -89,6 +102,11 @@ export class JSONParser {
 					});
 				}
 			}
+
+			// Profile relationship rules turn foreign-key columns into connections
+			if (options?.profile) {
+				connections.push(...extractRelationships(mappedRows, options.profile));
+			}
 		} catch (error) {
 			// Invalid JSON
 			errors.push({
@@ -101,6 +119,25 @@ export class JSONParser {
 		return { cards, connections, errors };
 	}
 
+	/**
+	 * Keys of the records in a JSON document (union, in first-seen order), for
+	 * mapping profile suggestion. Empty when the input is not valid JSON.
+	 */
+	readHeaders(input: string): string[] {
+		let data: unknown;
+		try {
+			data = this.extractNestedArray(JSON.parse(input), []);
+		} catch {
+			return [];
+		}
+		const keys = new Set<string>();
+		for (const item of Array.isArray(data) ? data : [data]) {
+			if (!item || typeof item !== 'object' || Array.isArray(item)) continue;
+			for (const key of Object.keys(item)) keys.add(key);
+		}
+		return [...keys];
+	}
+
 	/**
 	 * Extract nested arrays from common wrapper keys.
 	 * Checks: data.items, data, items, records, cards
@@ -182,6 +219,9 @@ export class JSONParser {
 		const created_at = this.extractString(item[createdField]) || new Date().toISOString();
 		const folder = this.extractString(item[folderField]);
 
+		// Fields outside the mapping become custom properties
+		const properties = collectUnmappedProperties(item, [nameField, contentField, tagsField, createdField, folderField]);
+
 		const card: CanonicalCard = {
 			id: crypto.randomUUID(),
 			card_type: 'note',
@@ -216,6 +256,7 @@ export class JSONParser {
 			source_url: null,
 
 			deleted_at: null,
+			...(properties ? { properties } : {}),
 		};
 
 		return card;
@@ -0,0 +1,203 @@
+// Isometry v5 — OPML Parser
+// Parses OPML outlines (OmniOutliner, Workflowy, Dynalist, feed lists) into
+// nested cards joined by `contains` connections, the hierarchy TreeView draws.
+//
+// Features:
+// - Regex tokenizer (Worker-safe, no DOM dependencies)
+// - One card per <outline>; `text` (or `title`) is the name, `_note` the content
+// - Parents -> children as `contains` connections, sibling order as sort_order
+// - Outlines with children are collective cards
+// - `url` / `htmlUrl` / `xmlUrl` outlines become resource cards
+// - `category` becomes tags, `created` (RFC 822) the creation time
+// - Head <title> becomes the folder of every card
+//
+// Source ids are the outline's index path within the document (e.g. "plan.opml#0.2.1"),
+// so re-importing the same file updates cards in place.
+
+import type { CanonicalCard, CanonicalConnection, ParseError } from '../types';
+import { decodeEntities } from './entities';
+
+export interface OPMLParseOptions {
+	/** Source filename — prefixes source ids so outlines from different files do not collide */
+	filename?: string;
+	/** Default timestamp when the document head has no dates */
+	defaultTimestamp?: string;
+}
+
+export interface OPMLParseResult {
+	cards: CanonicalCard[];
+	connections: CanonicalConnection[];
+	errors: ParseError[];
+}
+
+const TAG_RE = /<(\/?)(outline|body)\b([^>]*?)(\/?)>/gi;
+const ATTR_RE = /([\w:.-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')/g;
+
+/**
+ * OPMLParser transforms an OPML document into a card hierarchy.
+ */
+export class OPMLParser {
+	/**
+	 * Parse an OPML document.
+	 *
+	 * @param opml - OPML XML text
+	 * @param options - Optional parsing configuration
+	 * @returns Parse result with cards, connections, and errors
+	 */
+	parse(opml: string, options?: OPMLParseOptions): OPMLParseResult {
+		const cards: CanonicalCard[] = [];
+		const connections: CanonicalConnection[] = [];
+		const errors: ParseError[] = [];
+
+		if (!/<opml\b/i.test(opml)) {
+			throw new Error('Not an OPML document');
+		}
+
+		const head = this.parseHead(opml);
+		const docKey = options?.filename ?? head.title ?? 'outline';
+		const folder = head.title ?? options?.filename?.replace(/\.(opml|xml)$/i, '') ?? null;
+		const docTime = head.modified ?? head.created ?? options?.defaultTimestamp ?? new Date().toISOString();
+		const now = new Date().toISOString();
+
+		// Stack of open outlines: their card, index path and next child position
+		const stack: Array<{ card: CanonicalCard; path: string; childCount: number }> = [];
+		let rootCount = 0;
+		let inBody = false;
+
+		for (const match of opml.matchAll(TAG_RE)) {
+			const closing = match[1] === '/';
+			const tag = match[2]!.toLowerCase();
+			if (tag === 'body') {
+				inBody = !closing;
+				continue;
+			}
+			if (tag !== 'outline' || !inBody) continue;
+
+			if (closing) {
+				stack.pop();
+				continue;
+			}
+
+			const parent = stack[stack.length - 1];
+			const position = parent ? parent.childCount++ : rootCount++;
+			const path = parent ? `${parent.path}.${position}` : String(position);
+			const attrs = this.parseAttributes(match[3] ?? '');
+			const card = this.outlineCard(attrs, `${docKey}#${path}`, position, folder, {
+				created: head.created,
+				modified: docTime,
+			});
+			cards.push(card);
+
+			if (parent) {
+				parent.card.is_collective = true;
+				connections.push({
+					id: crypto.randomUUID(),
+					source_id: parent.card.source_id, // Resolved to card UUIDs by DedupEngine
+					target_id: card.source_id,
+					via_card_id: null,
+					label: 'contains',
+					weight: 1,
+					created_at: now,
+				});
+			}
+			// Self-closing outlines have no children and no closing tag
+			if (match[4] !== '/') stack.push({ card, path, childCount: 0 });
+		}
+
+		return { cards, connections, errors };
+	}
+
+	/**
+	 * Build the card for one <outline>.
+	 */
+	private outlineCard(
+		attrs: Record<string, string>,
+		sourceId: string,
+		position: number,
+		folder: string | null,
+		docDates: { created: string | null; modified: string },
+	): CanonicalCard {
+		const name = (attrs['text'] ?? attrs['title'] ?? '').trim();
+		const url = attrs['url'] ?? attrs['htmlUrl'] ?? attrs['xmlUrl'] ?? null;
+		const note = attrs['_note']?.trim() || null;
+		const created = this.parseDate(attrs['created']) ?? docDates.created ?? docDates.modified;
+		const tags = (attrs['category'] ?? '')
+			.split(',')
+			.map((t) => t.trim().replace(/^\//, ''))
+			.filter(Boolean);
+
+		return {
+			id: crypto.randomUUID(),
+			card_type: url ? 'resource' : 'note',
+			name: name || url || 'Untitled',
+			content: note,
+			summary: note ? note.slice(0, 200) : null,
+
+			latitude: null,
+			longitude: null,
+			location_name: null,
+
+			created_at: created,
+			modified_at: docDates.modified,
+			due_at: null,
+			completed_at: attrs['_complete'] === 'true' ? docDates.modified : null,
+			event_start: null,
+			event_end: null,
+
+			folder,
+			tags,
+			status: null,
+
+			priority: 0,
+			sort_order: position,
+
+			url,
+			mime_type: null,
+			is_collective: false,
+
+			source: 'opml',
+			source_id: sourceId,
+			source_url: null,
+
+			deleted_at: null,
+		};
+	}
+
+	/**
+	 * Title and dates from <head>.
+	 */
+	private parseHead(opml: string): { title: string | null; created: string | null; modified: string | null } {
+		const headEnd = opml.search(/<body\b/i);
+		const head = headEnd === -1 ? opml : opml.slice(0, headEnd);
+		const text = (tag: string) => {
+			const match = head.match(new RegExp(`<${tag}>([\\s\\S]*?)</${tag}>`, 'i'));
+			return match?.[1] ? decodeEntities(match[1]).trim() || null : null;
+		};
+		return {
+			title: text('title'),
+			created: this.parseDate(text('dateCreated') ?? undefined),
+			modified: this.parseDate(text('dateModified') ?? undefined),
+		};
+	}
+
+	/**
+	 * Attribute string to a decoded name -> value map.
+	 */
+	private parseAttributes(source: string): Record<string, string> {
+		const attrs: Record<string, string> = {};
+		for (const match of source.matchAll(ATTR_RE)) {
+			attrs[match[1]!] = decodeEntities(match[2] ?? match[3] ?? '');
+		}
+		return attrs;
+	}
+
+	/**
+	 * RFC 822 (OPML spec) or ISO 8601 date to ISO 8601, null if unparseable.
+	 */
+	private parseDate(value: string | undefined): string | null {
+		if (!value) return null;
+		const date = new Date(value);
+		if (Number.isNaN(date.getTime())) return null;
+		return date.toISOString().replace(/\.000Z$/, 'Z');
+	}
+}
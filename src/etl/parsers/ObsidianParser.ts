@@ -0,0 +1,467 @@
+// Isometry v5 — Obsidian Vault Parser
+// Imports a whole Obsidian vault (notes + attachments) with the link graph
+// resolved the way Obsidian resolves it.
+//
+// Features:
+// - Notes: frontmatter title > filename (Obsidian titles are filenames)
+// - Aliases (`aliases` / `alias`) resolve links and are kept as a property
+// - Links: [[note]], [[note#heading]], [[note#^block]], [[note|display]],
+//   [text](note.md) — connection label 'links_to'
+// - Embeds: ![[note]], ![[image.png]], ![alt](image.png) — label 'embeds'
+// - Wikilinks inside frontmatter values (Obsidian properties) are links too
+// - Tags: frontmatter plus inline #tags, nested tags (#a/b) kept whole
+// - Non-markdown files become `media` cards so embeds resolve to them
+// - Hidden folders (.obsidian, .trash) are skipped
+//
+// Link resolution: paths ("folder/note") match exactly, relative to the
+// linking note, or as a path suffix; bare names match by basename, then by
+// alias. Ambiguous names prefer the linking note's folder, then the shortest
+// path. Unresolved links are dropped (Obsidian shows them as "not created").
+
+import matter from 'gray-matter';
+import type { CanonicalCard, CanonicalConnection, ParseError } from '../types';
+import { mimeTypeFor } from './attachments';
+import { collectUnmappedProperties } from './properties';
+import { isHiddenVaultPath, type VaultFile } from './vault';
+
+export type { VaultFile } from './vault';
+
+export interface ObsidianParseOptions {
+	/** Default timestamp for files without frontmatter dates or mtime */
+	defaultTimestamp?: string;
+}
+
+export interface ObsidianParseResult {
+	cards: CanonicalCard[];
+	connections: CanonicalConnection[];
+	errors: ParseError[];
+}
+
+/** Frontmatter keys mapped onto card fields rather than custom properties. */
+const MAPPED_FRONTMATTER_KEYS = [
+	'title',
+	'tags',
+	'tag',
+	'aliases',
+	'alias',
+	'created',
+	'modified',
+	'date',
+	'cssclasses',
+	'cssclass',
+];
+
+const WIKILINK_RE = /(!?)\[\[([^[\]\n]+?)\]\]/g;
+const MD_LINK_RE = /(!?)\[[^\]\n]*\]\(<?([^)>\s]+)>?(?:\s+"[^"]*")?\)/g;
+/** Obsidian tag: letters/digits/_/-/ (nesting), at least one non-digit. */
+const TAG_RE = /(?<![\p{L}\p{N}_/#&])#([\p{L}\p{N}_\-/]*[\p{L}_\-/][\p{L}\p{N}_\-/]*)/gu;
+
+interface VaultNote {
+	file: VaultFile;
+	data: Record<string, unknown>;
+	body: string;
+	aliases: string[];
+}
+
+interface LinkRef {
+	target: string;
+	embed: boolean;
+}
+
+/**
+ * ObsidianParser transforms an Obsidian vault (all files, vault-relative
+ * paths) into note and media cards plus typed link/embed connections.
+ */
+export class ObsidianParser {
+	/**
+	 * Parse every file of a vault.
+	 *
+	 * @param files - Vault files with vault-relative paths
+	 * @param options - Optional parsing configuration
+	 * @returns Parse result with cards, connections, and errors
+	 */
+	parse(files: VaultFile[], options?: ObsidianParseOptions): ObsidianParseResult {
+		const cards: CanonicalCard[] = [];
+		const connections: CanonicalConnection[] = [];
+		const errors: ParseError[] = [];
+		const defaultTime = options?.defaultTimestamp || new Date().toISOString();
+
+		// Pass 1: split notes from attachments and read frontmatter (aliases
+		// must be known before any link can be resolved)
+		const notes: Array<{ note: VaultNote; index: number }> = [];
+		const attachments: Array<{ file: VaultFile; index: number }> = [];
+		for (let i = 0; i < files.length; i++) {
+			const file = files[i];
+			if (!file || isHiddenVaultPath(file.path)) continue;
+
+			if (!/\.md$/i.test(file.path)) {
+				attachments.push({ file, index: i });
+				continue;
+			}
+			try {
+				const { data, content } = matter(file.content);
+				notes.push({ note: { file, data, body: content, aliases: this.extractAliases(data) }, index: i });
+			} catch (error) {
+				errors.push({
+					index: i,
+					source_id: file.path,
+					message: error instanceof Error ? error.message : String(error),
+				});
+			}
+		}
+
+		const resolver = new VaultResolver(
+			notes.map((n) => n.note),
+			attachments.map((a) => a.file.path),
+		);
+
+		// Pass 2: cards and resolved connections
+		for (const { note, index } of notes) {
+			cards.push(this.noteCard(note, index, defaultTime));
+
+			const seen = new Set<string>();
+			for (const ref of this.extractLinks(note)) {
+				const target = resolver.resolve(ref.target, note.file.path);
+				if (!target || target === note.file.path) continue;
+				const label = ref.embed ? 'embeds' : 'links_to';
+				const key = `${target}|${label}`;
+				if (seen.has(key)) continue;
+				seen.add(key);
+				connections.push({
+					id: crypto.randomUUID(),
+					source_id: note.file.path, // Resolved to card UUIDs by DedupEngine
+					target_id: target,
+					via_card_id: null,
+					label,
+					weight: 0.5,
+					created_at: new Date().toISOString(),
+				});
+			}
+		}
+
+		for (const { file, index } of attachments) {
+			cards.push(this.attachmentCard(file, index, defaultTime));
+		}
+
+		return { cards, connections, errors };
+	}
+
+	/**
+	 * Build the card for a note.
+	 */
+	private noteCard(note: VaultNote, index: number, defaultTime: string): CanonicalCard {
+		const { file, data, body } = note;
+		const fileTime = file.modified ?? defaultTime;
+		const properties = collectUnmappedProperties(data, MAPPED_FRONTMATTER_KEYS) ?? {};
+		if (note.aliases.length > 0) properties['aliases'] = note.aliases.join(', ');
+		const title = data['title'];
+
+		return {
+			id: crypto.randomUUID(),
+			card_type: 'note',
+			name: typeof title === 'string' && title.trim() ? title.trim() : basename(file.path),
+			content: body.trim() || null,
+			summary: this.generateSummary(body),
+
+			latitude: null,
+			longitude: null,
+			location_name: null,
+
+			created_at: this.extractTimestamp(data, ['created', 'date'], fileTime),
+			modified_at: this.extractTimestamp(data, ['modified'], fileTime),
+			due_at: null,
+			completed_at: null,
+			event_start: null,
+			event_end: null,
+
+			folder: dirname(file.path),
+			tags: this.extractTags(data, body),
+			status: null,
+
+			priority: 0,
+			sort_order: index,
+
+			url: null,
+			mime_type: 'text/markdown',
+			is_collective: false,
+
+			source: 'obsidian',
+			source_id: file.path,
+			source_url: null,
+
+			deleted_at: null,
+			...(Object.keys(properties).length > 0 ? { properties } : {}),
+		};
+	}
+
+	/**
+	 * Build the media card for an attachment (metadata only, no content).
+	 */
+	private attachmentCard(file: VaultFile, index: number, defaultTime: string): CanonicalCard {
+		const filename = file.path.split('/').pop() ?? file.path;
+		const timestamp = file.modified ?? defaultTime;
+
+		return {
+			id: crypto.randomUUID(),
+			card_type: 'media',
+			name: filename,
+			content: null,
+			summary: null,
+
+			latitude: null,
+			longitude: null,
+			location_name: null,
+
+			created_at: timestamp,
+			modified_at: timestamp,
+			due_at: null,
+			completed_at: null,
+			event_start: null,
+			event_end: null,
+
+			folder: dirname(file.path),
+			tags: [],
+			status: null,
+
+			priority: 0,
+			sort_order: index,
+
+			url: null,
+			mime_type: mimeTypeFor(filename),
+			is_collective: false,
+
+			source: 'obsidian',
+			source_id: file.path,
+			source_url: null,
+
+			deleted_at: null,
+		};
+	}
+
+	/**
+	 * Aliases from `aliases` / `alias` as a list or comma-separated string.
+	 */
+	private extractAliases(data: Record<string, unknown>): string[] {
+		const raw = data['aliases'] ?? data['alias'];
+		const values = Array.isArray(raw) ? raw : typeof raw === 'string' ? raw.split(',') : [];
+		return values.filter((v): v is string => typeof v === 'string').map((v) => v.trim()).filter(Boolean);
+	}
+
+	/**
+	 * Frontmatter tags (list or comma/space string) plus inline #tags, in order.
+	 */
+	private extractTags(data: Record<string, unknown>, body: string): string[] {
+		const tags = new Set<string>();
+
+		const raw = data['tags'] ?? data['tag'];
+		const values = Array.isArray(raw) ? raw : typeof raw === 'string' ? raw.split(/[,\s]+/) : [];
+		for (const value of values) {
+			if (typeof value !== 'string') continue;
+			const tag = value.trim().replace(/^#/, '');
+			if (tag) tags.add(tag);
+		}
+
+		for (const match of stripCode(body).matchAll(TAG_RE)) {
+			const tag = match[1]?.replace(/\/+$/, '');
+			if (tag) tags.add(tag);
+		}
+
+		return Array.from(tags);
+	}
+
+	/**
+	 * Link and embed references from the body and frontmatter values.
+	 */
+	private extractLinks(note: VaultNote): LinkRef[] {
+		const refs: LinkRef[] = [];
+
+		const scan = (text: string, markdownLinks: boolean) => {
+			for (const match of text.matchAll(WIKILINK_RE)) {
+				// [[target#heading|display]] — only the target part resolves
+				const target = (match[2] ?? '').split('|')[0]!.split('#')[0]!.trim();
+				if (target) refs.push({ target, embed: match[1] === '!' });
+			}
+			if (!markdownLinks) return;
+			for (const match of text.matchAll(MD_LINK_RE)) {
+				const href = match[2] ?? '';
+				// External URLs and in-page anchors are not vault links
+				if (/^[a-z][a-z0-9+.-]*:/i.test(href) || href.startsWith('#')) continue;
+				const target = safeDecode(href.split('#')[0]!).trim();
+				if (target) refs.push({ target, embed: match[1] === '!' });
+			}
+		};
+
+		for (const value of frontmatterStrings(note.data)) scan(value, false);
+		scan(stripCode(note.body), true);
+
+		return refs;
+	}
+
+	/**
+	 * Extract timestamp from the first present frontmatter field.
+	 */
+	private extractTimestamp(data: Record<string, unknown>, fields: string[], defaultValue: string): string {
+		for (const field of fields) {
+			const value = data[field];
+			if (typeof value === 'string' && value.trim()) return value.trim();
+			// gray-matter parses unquoted YAML dates as Date objects
+			if (value instanceof Date && !Number.isNaN(value.getTime())) {
+				return value.toISOString().replace(/\.000Z$/, 'Z');
+			}
+		}
+		return defaultValue;
+	}
+
+	/**
+	 * Summary: first 200 chars without headings or block ids.
+	 */
+	private generateSummary(body: string): string | null {
+		const text = body
+			.replace(/^#.*$/gm, '')
+			.replace(/\s\^[\w-]+$/gm, '')
+			.trim();
+		if (!text) return null;
+		return text.slice(0, 200);
+	}
+}
+
+// ---------------------------------------------------------------------------
+// Link resolution
+// ---------------------------------------------------------------------------
+
+/**
+ * Resolves link targets to vault paths (the cards' source_id).
+ */
+class VaultResolver {
+	/** lowercased path without .md -> path */
+	private notePaths = new Map<string, string>();
+	/** lowercased basename -> paths */
+	private noteNames = new Map<string, string[]>();
+	/** lowercased alias -> paths */
+	private noteAliases = new Map<string, string[]>();
+	/** lowercased path -> path */
+	private attachmentPaths = new Map<string, string>();
+	/** lowercased filename (with extension) -> paths */
+	private attachmentNames = new Map<string, string[]>();
+
+	constructor(notes: VaultNote[], attachmentPaths: string[]) {
+		for (const note of notes) {
+			const path = note.file.path;
+			const key = stripMd(path).toLowerCase();
+			this.notePaths.set(key, path);
+			pushTo(this.noteNames, key.split('/').pop()!, path);
+			for (const alias of note.aliases) pushTo(this.noteAliases, alias.toLowerCase(), path);
+		}
+		for (const path of attachmentPaths) {
+			this.attachmentPaths.set(path.toLowerCase(), path);
+			pushTo(this.attachmentNames, path.split('/').pop()!.toLowerCase(), path);
+		}
+	}
+
+	/**
+	 * Resolve a link target (no heading/display part) from the note at `fromPath`.
+	 *
+	 * @returns Vault path of the target, or null when unresolved
+	 */
+	resolve(target: string, fromPath: string): string | null {
+		const cleaned = target.replace(/^\.?\//, '');
+		const fromDir = dirname(fromPath);
+
+		// "image.png" is an attachment; "v1.2 notes" or "Some.Thing" may still be a note
+		if (/\.[a-z0-9]+$/i.test(cleaned) && !/\.md$/i.test(cleaned)) {
+			const attachment = lookup(this.attachmentPaths, this.attachmentNames, cleaned.toLowerCase(), fromDir);
+			if (attachment) return attachment;
+		}
+
+		const key = stripMd(cleaned).toLowerCase();
+		return (
+			lookup(this.notePaths, this.noteNames, key, fromDir) ??
+			(key.includes('/') ? null : closest(this.noteAliases.get(key) ?? [], fromDir))
+		);
+	}
+}
+
+/**
+ * Path links: vault-absolute, then relative to the linking note, then path
+ * suffix. Bare names: by basename.
+ */
+function lookup(
+	paths: Map<string, string>,
+	names: Map<string, string[]>,
+	key: string,
+	fromDir: string | null,
+): string | null {
+	if (!key.includes('/')) return closest(names.get(key) ?? [], fromDir);
+
+	const exact = paths.get(key) ?? paths.get(joinPath(fromDir, key));
+	if (exact) return exact;
+	const suffixMatches: string[] = [];
+	for (const [candidate, path] of paths) {
+		if (candidate.endsWith(`/${key}`)) suffixMatches.push(path);
+	}
+	return closest(suffixMatches, fromDir);
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+function stripMd(path: string): string {
+	return path.replace(/\.md$/i, '');
+}
+
+function basename(path: string): string {
+	return stripMd(path.split('/').pop() || 'Untitled');
+}
+
+function dirname(path: string): string | null {
+	const index = path.lastIndexOf('/');
+	return index > 0 ? path.slice(0, index) : null;
+}
+
+/** Join a relative link onto a folder, collapsing `.` and `..` (lowercased). */
+function joinPath(dir: string | null, relative: string): string {
+	const parts = dir ? dir.toLowerCase().split('/') : [];
+	for (const segment of relative.split('/')) {
+		if (segment === '..') parts.pop();
+		else if (segment !== '.' && segment !== '') parts.push(segment);
+	}
+	return parts.join('/');
+}
+
+/** Same folder as the linking note first, then fewest segments, then A-Z. */
+function closest(paths: string[], fromDir: string | null): string | null {
+	if (paths.length <= 1) return paths[0] ?? null;
+	const sameFolder = paths.find((p) => dirname(p) === fromDir);
+	if (sameFolder) return sameFolder;
+	return [...paths].sort((a, b) => a.split('/').length - b.split('/').length || a.localeCompare(b))[0] ?? null;
+}
+
+function pushTo(index: Map<string, string[]>, key: string, path: string): void {
+	const list = index.get(key);
+	if (list) list.push(path);
+	else index.set(key, [path]);
+}
+
+/** Blank out fenced and inline code — links and tags in code are literal. */
+function stripCode(text: string): string {
+	return text.replace(/^(```|~~~)[\s\S]*?^\1/gm, '').replace(/`[^`\n]*`/g, '');
+}
+
+function safeDecode(text: string): string {
+	try {
+		return decodeURI(text);
+	} catch {
+		return text;
+	}
+}
+
+/** All string values in frontmatter, including inside lists. */
+function frontmatterStrings(data: Record<string, unknown>): string[] {
+	const strings: string[] = [];
+	for (const value of Object.values(data)) {
+		if (typeof value === 'string') strings.push(value);
+		else if (Array.isArray(value)) strings.push(...value.filter((v): v is string => typeof v === 'string'));
+	}
+	return strings;
+}
@@ -0,0 +1,403 @@
+// Isometry v5 — vCard Parser
+// Parses .vcf address books (vCard 2.1 / 3.0 / 4.0) into person cards.
+//
+// Features:
+// - One person card per BEGIN:VCARD block; FN (or N) is the name, NOTE the content
+// - ORG -> folder, plus one collective organization card per company joined by
+//   `works_at` connections (KIND:org vCards stand in for their company)
+// - First ADR -> location_name; GEO (3.0 `lat;lon`, 4.0 `geo:lat,lon`) -> latitude/longitude
+// - RELATED (4.0) and X-ABRELATEDNAMES (Apple) -> `related` connections, matched
+//   by UID or by name within the file
+// - EMAIL / TEL / TITLE / ROLE / BDAY / NICKNAME -> card properties
+// - vCard 2.1 QUOTED-PRINTABLE values decoded
+//
+// Source ids are the vCard UID, so re-importing an updated export updates
+// contacts in place through DedupEngine.
+
+import type { CanonicalCard, CanonicalConnection, CanonicalPropertyValue, ParseError } from '../types';
+import { type ContentLine, parseContentLine, splitValue, unescapeText, unfoldLines } from './contentLines';
+import { parseICalDate, toIso } from './recurrence';
+
+export interface VCardParseOptions {
+	/** Source filename — source id prefix for vCards without a UID */
+	filename?: string;
+	/** Default timestamp for vCards without REV */
+	defaultTimestamp?: string;
+}
+
+export interface VCardParseResult {
+	cards: CanonicalCard[];
+	connections: CanonicalConnection[];
+	errors: ParseError[];
+}
+
+/** One BEGIN:VCARD ... END:VCARD block */
+interface Contact {
+	index: number;
+	props: ContentLine[];
+}
+
+/** Properties copied to card_properties: vCard name -> property label */
+const PROPERTY_FIELDS: Record<string, string> = {
+	EMAIL: 'Email',
+	TEL: 'Phone',
+	TITLE: 'Job Title',
+	ROLE: 'Role',
+	NICKNAME: 'Nickname',
+};
+
+/**
+ * VCardParser transforms a vCard file into person and organization cards.
+ */
+export class VCardParser {
+	/**
+	 * Parse a vCard file (one or more contacts).
+	 *
+	 * @param vcf - vCard text
+	 * @param options - Optional parsing configuration
+	 * @returns Parse result with cards, connections, and errors
+	 */
+	parse(vcf: string, options?: VCardParseOptions): VCardParseResult {
+		const cards: CanonicalCard[] = [];
+		const connections: CanonicalConnection[] = [];
+		const errors: ParseError[] = [];
+
+		if (!/^BEGIN:VCARD/im.test(vcf)) {
+			throw new Error('Not a vCard document');
+		}
+
+		const contacts = this.readContacts(vcf);
+		const fallbackTime = options?.defaultTimestamp ?? new Date().toISOString();
+		const now = new Date().toISOString();
+		const prefix = options?.filename ?? 'contacts';
+
+		// First pass: person cards, and name/UID lookups for relationship targets
+		const people: Array<{ card: CanonicalCard; contact: Contact }> = [];
+		const byName = new Map<string, string>(); // lowercased name -> source_id
+		const organizations = new Map<string, string>(); // lowercased org name -> source_id
+		for (const contact of contacts) {
+			try {
+				const card = this.contactCard(contact, prefix, fallbackTime);
+				cards.push(card);
+				people.push({ card, contact });
+				byName.set(card.name.toLowerCase(), card.source_id);
+				if (this.kind(contact) === 'org') {
+					organizations.set(card.name.toLowerCase(), card.source_id);
+				}
+			} catch (error) {
+				errors.push({
+					index: contact.index,
+					source_id: this.text(contact, 'UID'),
+					message: error instanceof Error ? error.message : String(error),
+				});
+			}
+		}
+
+		// Second pass: organizations and relationships
+		const connect = (source: string, target: string, label: string) => {
+			if (source === target) return;
+			connections.push({
+				id: crypto.randomUUID(),
+				source_id: source, // Resolved to card UUIDs by DedupEngine
+				target_id: target,
+				via_card_id: null,
+				label,
+				weight: 1,
+				created_at: now,
+			});
+		};
+
+		for (const { card, contact } of people) {
+			if (card.folder && this.kind(contact) !== 'org') {
+				const key = card.folder.toLowerCase();
+				let orgId = organizations.get(key);
+				if (!orgId) {
+					const org = this.organizationCard(card.folder, card.modified_at);
+					cards.push(org);
+					orgId = org.source_id;
+					organizations.set(key, orgId);
+				}
+				connect(card.source_id, orgId, 'works_at');
+			}
+
+			for (const target of this.relatedTargets(contact, byName)) {
+				connect(card.source_id, target, 'related');
+			}
+		}
+
+		return { cards, connections, errors };
+	}
+
+	/**
+	 * Split the file into vCard blocks, decoding quoted-printable values.
+	 */
+	private readContacts(vcf: string): Contact[] {
+		const contacts: Contact[] = [];
+		let current: Contact | null = null;
+		let depth = 0;
+
+		const lines = unfoldLines(vcf);
+		for (let i = 0; i < lines.length; i++) {
+			let raw = lines[i]!;
+			// vCard 2.1 quoted-printable soft line breaks end with `=`
+			if (/ENCODING=QUOTED-PRINTABLE/i.test(raw.slice(0, raw.indexOf(':')))) {
+				while (raw.endsWith('=') && i + 1 < lines.length) raw = raw.slice(0, -1) + lines[++i];
+			}
+			const line = parseContentLine(raw);
+			if (!line) continue;
+
+			if (line.name === 'BEGIN' && line.value.trim().toUpperCase() === 'VCARD') {
+				depth++;
+				if (depth === 1) current = { index: contacts.length, props: [] };
+			} else if (line.name === 'END' && line.value.trim().toUpperCase() === 'VCARD') {
+				depth--;
+				if (depth === 0 && current) {
+					contacts.push(current);
+					current = null;
+				}
+			} else if (current && depth === 1) {
+				if (line.params['ENCODING']?.toUpperCase() === 'QUOTED-PRINTABLE') {
+					line.value = decodeQuotedPrintable(line.value, line.params['CHARSET']);
+				}
+				current.props.push(line);
+			}
+		}
+
+		return contacts;
+	}
+
+	/**
+	 * Build the card for one vCard.
+	 */
+	private contactCard(contact: Contact, prefix: string, fallbackTime: string): CanonicalCard {
+		const org = this.first(contact, 'ORG');
+		const company = org ? splitValue(org.value, ';')[0]!.trim() || null : null;
+		const email = this.text(contact, 'EMAIL');
+		const name = this.text(contact, 'FN') || this.structuredName(contact) || company || email;
+		if (!name) throw new Error('vCard has no name');
+
+		const note = this.text(contact, 'NOTE');
+		const address = this.preferred(contact, 'ADR');
+		const geo = this.geo(contact, address);
+		const kind = this.kind(contact);
+		const rev = this.timestamp(this.text(contact, 'REV'));
+		const uid = this.text(contact, 'UID')?.replace(/^urn:uuid:/i, '');
+
+		return {
+			id: crypto.randomUUID(),
+			card_type: 'person',
+			name,
+			content: note,
+			summary: note ? note.slice(0, 200) : null,
+
+			latitude: geo?.[0] ?? null,
+			longitude: geo?.[1] ?? null,
+			location_name: address ? this.formatAddress(address) : null,
+
+			created_at: rev ?? fallbackTime,
+			modified_at: rev ?? fallbackTime,
+			due_at: null,
+			completed_at: null,
+			event_start: null,
+			event_end: null,
+
+			folder: kind === 'org' ? name : company,
+			tags: this.values(contact, 'CATEGORIES'),
+			status: null,
+
+			priority: 0,
+			sort_order: 0,
+
+			url: this.text(contact, 'URL'),
+			mime_type: null,
+			is_collective: kind === 'org' || kind === 'group',
+
+			source: 'vcard',
+			source_id: uid || (email ? `${prefix}#${name} <${email}>` : `${prefix}#${name}`),
+			source_url: null,
+
+			deleted_at: null,
+
+			properties: this.properties(contact),
+		};
+	}
+
+	/**
+	 * A collective card for a company named by ORG without its own vCard.
+	 */
+	private organizationCard(name: string, timestamp: string): CanonicalCard {
+		return {
+			id: crypto.randomUUID(),
+			card_type: 'person',
+			name,
+			content: null,
+			summary: null,
+
+			latitude: null,
+			longitude: null,
+			location_name: null,
+
+			created_at: timestamp,
+			modified_at: timestamp,
+			due_at: null,
+			completed_at: null,
+			event_start: null,
+			event_end: null,
+
+			folder: name,
+			tags: [],
+			status: null,
+
+			priority: 0,
+			sort_order: 0,
+
+			url: null,
+			mime_type: null,
+			is_collective: true,
+
+			source: 'vcard',
+			source_id: `org:${name.toLowerCase()}`,
+			source_url: null,
+
+			deleted_at: null,
+		};
+	}
+
+	/**
+	 * Source ids of related contacts: `urn:uuid:` / `uid:` references are
+	 * passed through (DedupEngine resolves earlier imports too), names are
+	 * matched within the file.
+	 */
+	private relatedTargets(contact: Contact, byName: Map<string, string>): string[] {
+		const targets: string[] = [];
+		for (const line of contact.props) {
+			if (line.name !== 'RELATED' && line.name !== 'X-ABRELATEDNAMES') continue;
+			const value = unescapeText(line.value).trim();
+			const reference = value.match(/^(?:urn:uuid:|uid:)(.+)$/i);
+			const target = reference ? reference[1]! : byName.get(value.toLowerCase());
+			if (target && !targets.includes(target)) targets.push(target);
+		}
+		return targets;
+	}
+
+	/**
+	 * Contact details kept as card properties. Repeated fields are joined.
+	 */
+	private properties(contact: Contact): Record<string, CanonicalPropertyValue> | undefined {
+		const properties: Record<string, CanonicalPropertyValue> = {};
+		for (const [vcardName, key] of Object.entries(PROPERTY_FIELDS)) {
+			const values = contact.props
+				.filter((p) => p.name === vcardName)
+				.map((p) => unescapeText(p.value).replace(/^(mailto|tel):/i, '').trim())
+				.filter(Boolean);
+			if (values.length > 0) properties[key] = [...new Set(values)].join(', ');
+		}
+		const birthday = this.text(contact, 'BDAY');
+		if (birthday) properties['Birthday'] = this.timestamp(birthday)?.slice(0, 10) ?? birthday;
+		return Object.keys(properties).length > 0 ? properties : undefined;
+	}
+
+	/**
+	 * `N` (family;given;additional;prefix;suffix) as a display name.
+	 */
+	private structuredName(contact: Contact): string | null {
+		const n = this.first(contact, 'N');
+		if (!n) return null;
+		const [family = '', given = '', additional = '', prefix = '', suffix = ''] = splitValue(n.value, ';');
+		return [prefix, given, additional, family, suffix].map((p) => p.trim()).filter(Boolean).join(' ') || null;
+	}
+
+	/**
+	 * ADR (po box;extended;street;locality;region;postal code;country) as one line.
+	 */
+	private formatAddress(address: ContentLine): string | null {
+		const label = address.params['LABEL'];
+		const parts = splitValue(address.value, ';')
+			.map((p) => p.replace(/\s*\n\s*/g, ', ').trim())
+			.filter(Boolean);
+		return parts.length > 0 ? parts.join(', ') : label ? unescapeText(label).trim() || null : null;
+	}
+
+	/**
+	 * Coordinates from GEO, or the address's GEO parameter (vCard 4.0).
+	 */
+	private geo(contact: Contact, address: ContentLine | undefined): [number, number] | null {
+		const value = this.first(contact, 'GEO')?.value ?? address?.params['GEO'];
+		if (!value) return null;
+		const coords = value
+			.replace(/^geo:/i, '')
+			.split(/[;,]/)
+			.slice(0, 2)
+			.map(Number);
+		return coords.length === 2 && coords.every((n) => Number.isFinite(n)) ? [coords[0]!, coords[1]!] : null;
+	}
+
+	/**
+	 * The instance of a property marked preferred (TYPE=pref or PREF=1), else the first.
+	 */
+	private preferred(contact: Contact, name: string): ContentLine | undefined {
+		const lines = contact.props.filter((p) => p.name === name);
+		return (
+			lines.find((p) => p.params['PREF'] === '1' || /(^|,)pref(,|$)/i.test(p.params['TYPE'] ?? '')) ?? lines[0]
+		);
+	}
+
+	/**
+	 * KIND (4.0) or X-ABShowAs (Apple) lowercased: individual, org, group.
+	 */
+	private kind(contact: Contact): string {
+		const kind = this.text(contact, 'KIND') ?? this.text(contact, 'X-ADDRESSBOOKSERVER-KIND');
+		if (kind) return kind.toLowerCase();
+		return this.text(contact, 'X-ABSHOWAS')?.toUpperCase() === 'COMPANY' ? 'org' : 'individual';
+	}
+
+	/**
+	 * REV / BDAY (`20260105T090000Z`, `2026-01-05T09:00:00Z`, `2026-01-05`) to ISO 8601.
+	 */
+	private timestamp(value: string | null): string | null {
+		if (!value) return null;
+		const compact = parseICalDate(value.replace(/[-:]/g, '').replace(/\.\d+/, ''));
+		if (compact) return toIso(compact);
+		const date = new Date(value);
+		return Number.isNaN(date.getTime()) ? null : date.toISOString().replace(/\.000Z$/, 'Z');
+	}
+
+	private first(contact: Contact, name: string): ContentLine | undefined {
+		return contact.props.find((p) => p.name === name);
+	}
+
+	private text(contact: Contact, name: string): string | null {
+		const line = this.first(contact, name);
+		return line ? unescapeText(line.value).trim() || null : null;
+	}
+
+	private values(contact: Contact, name: string): string[] {
+		const values = contact.props
+			.filter((p) => p.name === name)
+			.flatMap((p) => splitValue(p.value, ','))
+			.map((v) => v.trim())
+			.filter(Boolean);
+		return [...new Set(values)];
+	}
+}
+
+/**
+ * Decode a quoted-printable value (vCard 2.1) in the given charset.
+ */
+function decodeQuotedPrintable(value: string, charset = 'utf-8'): string {
+	const bytes: number[] = [];
+	for (let i = 0; i < value.length; i++) {
+		const hex = value.slice(i + 1, i + 3);
+		if (value[i] === '=' && /^[0-9A-F]{2}$/i.test(hex)) {
+			bytes.push(Number.parseInt(hex, 16));
+			i += 2;
+		} else {
+			bytes.push(...new TextEncoder().encode(value[i]));
+		}
+	}
+	try {
+		return new TextDecoder(charset).decode(new Uint8Array(bytes));
+	} catch {
+		return new TextDecoder().decode(new Uint8Array(bytes));
+	}
+}
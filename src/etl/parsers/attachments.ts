@@ -1,7 +1,102 @@
 // Isometry v5 — Phase 8 Attachment Parsers
-// Helpers for extracting data from alto-index attachment types.
+// Helpers for extracting data from alto-index attachment types, and for
+// turning file attachments (vault media, email parts) into cards.
 
-import type { AltoAttachment } from '../types';
+import type { AltoAttachment, CanonicalCard } from '../types';
+
+const ATTACHMENT_MIME_TYPES: Record<string, string> = {
+	png: 'image/png',
+	jpg: 'image/jpeg',
+	jpeg: 'image/jpeg',
+	gif: 'image/gif',
+	webp: 'image/webp',
+	svg: 'image/svg+xml',
+	bmp: 'image/bmp',
+	avif: 'image/avif',
+	heic: 'image/heic',
+	pdf: 'application/pdf',
+	mp3: 'audio/mpeg',
+	wav: 'audio/wav',
+	m4a: 'audio/mp4',
+	ogg: 'audio/ogg',
+	flac: 'audio/flac',
+	mp4: 'video/mp4',
+	webm: 'video/webm',
+	mov: 'video/quicktime',
+	mkv: 'video/x-matroska',
+	canvas: 'application/json',
+	txt: 'text/plain',
+	csv: 'text/csv',
+	ics: 'text/calendar',
+	vcf: 'text/vcard',
+	zip: 'application/zip',
+	doc: 'application/msword',
+	docx: 'application/vnd.openxmlformats-officedocument.wordprocessingml.document',
+	xls: 'application/vnd.ms-excel',
+	xlsx: 'application/vnd.openxmlformats-officedocument.spreadsheetml.sheet',
+	ppt: 'application/vnd.ms-powerpoint',
+	pptx: 'application/vnd.openxmlformats-officedocument.presentationml.presentation',
+};
+
+/**
+ * MIME type for a filename by extension, `application/octet-stream` when unknown.
+ */
+export function mimeTypeFor(filename: string): string {
+	const ext = filename.includes('.') ? (filename.split('.').pop() ?? '').toLowerCase() : '';
+	return ATTACHMENT_MIME_TYPES[ext] ?? 'application/octet-stream';
+}
+
+/**
+ * Build the card for a file attachment (metadata only, no content).
+ * Images, audio and video become `media` cards; other files `resource` cards.
+ */
+export function createAttachmentCard(attachment: {
+	filename: string;
+	mimeType?: string | null;
+	source: string;
+	sourceId: string;
+	folder: string | null;
+	timestamp: string;
+	sortOrder?: number;
+}): CanonicalCard {
+	const mimeType = attachment.mimeType || mimeTypeFor(attachment.filename);
+
+	return {
+		id: crypto.randomUUID(),
+		card_type: /^(image|audio|video)\//.test(mimeType) ? 'media' : 'resource',
+		name: attachment.filename,
+		content: null,
+		summary: null,
+
+		latitude: null,
+		longitude: null,
+		location_name: null,
+
+		created_at: attachment.timestamp,
+		modified_at: attachment.timestamp,
+		due_at: null,
+		completed_at: null,
+		event_start: null,
+		event_end: null,
+
+		folder: attachment.folder,
+		tags: [],
+		status: null,
+
+		priority: 0,
+		sort_order: attachment.sortOrder ?? 0,
+
+		url: null,
+		mime_type: mimeType,
+		is_collective: false,
+
+		source: attachment.source,
+		source_id: attachment.sourceId,
+		source_url: null,
+
+		deleted_at: null,
+	};
+}
 
 /**
  * Extract hashtag names from hashtag attachments.
@@ -0,0 +1,143 @@
+// Isometry v5 — iCalendar / vCard Content Lines
+// The line syntax shared by RFC 5545 (iCalendar) and RFC 6350 (vCard):
+// folded lines, `group.NAME;PARAM=value:VALUE`, and backslash-escaped text.
+// Worker-safe string handling only.
+
+/**
+ * One unfolded content line.
+ */
+export interface ContentLine {
+	/** vCard property group (`item1` in `item1.EMAIL`), null when absent */
+	group: string | null;
+	/** Property name, uppercased */
+	name: string;
+	/** Parameters keyed by uppercased name; quoted values are unquoted */
+	params: Record<string, string>;
+	/** Raw value (still escaped) */
+	value: string;
+}
+
+/**
+ * Split text into logical lines, joining folded continuations (a line
+ * starting with a space or tab continues the previous one).
+ */
+export function unfoldLines(text: string): string[] {
+	return text
+		.replace(/\r\n?/g, '\n')
+		.replace(/\n[ \t]/g, '')
+		.split('\n')
+		.filter((line) => line.trim() !== '');
+}
+
+/**
+ * Parse one unfolded line. Returns null for lines without a `:` separator.
+ */
+export function parseContentLine(line: string): ContentLine | null {
+	// Find the name/params vs value separator, skipping colons inside quoted params
+	let inQuotes = false;
+	let colon = -1;
+	for (let i = 0; i < line.length; i++) {
+		const ch = line[i];
+		if (ch === '"') inQuotes = !inQuotes;
+		else if (ch === ':' && !inQuotes) {
+			colon = i;
+			break;
+		}
+	}
+	if (colon <= 0) return null;
+
+	const [head = '', ...paramParts] = splitOutsideQuotes(line.slice(0, colon), ';');
+	const dot = head.lastIndexOf('.');
+	const params: Record<string, string> = {};
+	for (const part of paramParts) {
+		const eq = part.indexOf('=');
+		// vCard 2.1 bare parameters (`TEL;CELL:`) are types
+		const key = (eq === -1 ? 'TYPE' : part.slice(0, eq)).trim().toUpperCase();
+		const value = (eq === -1 ? part : part.slice(eq + 1)).replace(/"/g, '').trim();
+		params[key] = params[key] ? `${params[key]},${value}` : value;
+	}
+
+	return {
+		group: dot > 0 ? head.slice(0, dot) : null,
+		name: (dot > 0 ? head.slice(dot + 1) : head).trim().toUpperCase(),
+		params,
+		value: line.slice(colon + 1),
+	};
+}
+
+/**
+ * Unescape a TEXT value (`\n`, `\,`, `\;`, `\\`).
+ */
+export function unescapeText(value: string): string {
+	return value.replace(/\\([nN,;\\])/g, (_match, ch: string) => (ch === 'n' || ch === 'N' ? '\n' : ch));
+}
+
+/**
+ * Split an escaped value on an unescaped separator, unescaping each part.
+ */
+export function splitValue(value: string, separator: ',' | ';'): string[] {
+	const parts: string[] = [];
+	let current = '';
+	for (let i = 0; i < value.length; i++) {
+		const ch = value[i]!;
+		if (ch === '\\' && i + 1 < value.length) {
+			current += ch + value[i + 1];
+			i++;
+		} else if (ch === separator) {
+			parts.push(unescapeText(current));
+			current = '';
+		} else {
+			current += ch;
+		}
+	}
+	parts.push(unescapeText(current));
+	return parts;
+}
+
+/**
+ * Escape a TEXT value for writing.
+ */
+export function escapeText(value: string): string {
+	return value.replace(/\\/g, '\\\\').replace(/;/g, '\\;').replace(/,/g, '\\,').replace(/\r?\n/g, '\\n');
+}
+
+/**
+ * Fold a content line at 75 octets (UTF-8), never splitting a character.
+ */
+export function foldLine(line: string): string {
+	const encoder = new TextEncoder();
+	const chunks: string[] = [];
+	let current = '';
+	let octets = 0;
+	for (const ch of line) {
+		const size = encoder.encode(ch).length;
+		// Continuation lines spend one octet on the leading space
+		const limit = chunks.length === 0 ? 75 : 74;
+		if (octets + size > limit) {
+			chunks.push(current);
+			current = '';
+			octets = 0;
+		}
+		current += ch;
+		octets += size;
+	}
+	chunks.push(current);
+	return chunks.join('\r\n ');
+}
+
+function splitOutsideQuotes(text: string, separator: string): string[] {
+	const parts: string[] = [];
+	let current = '';
+	let inQuotes = false;
+	for (const ch of text) {
+		if (ch === '"') inQuotes = !inQuotes;
+		if (ch === separator && !inQuotes) {
+			parts.push(current);
+			current = '';
+		} else {
+			current += ch;
+		}
+	}
+	parts.push(current);
+	return parts;
+}
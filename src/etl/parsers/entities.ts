@@ -0,0 +1,21 @@
+// Isometry v5 — XML / HTML Entity Decoding
+// Shared by the regex-based markup parsers (OPML, email HTML bodies, bookmarks).
+
+const NAMED_ENTITIES: Record<string, string> = { amp: '&', lt: '<', gt: '>', quot: '"', apos: "'", nbsp: ' ' };
+
+/**
+ * Decode numeric character references and the common named entities.
+ * Unknown names and invalid code points are left as written.
+ */
+export function decodeEntities(text: string): string {
+	return text.replace(/&(#x[0-9a-f]+|#\d+|[a-z]+);/gi, (entity, code: string) => {
+		const lower = code.toLowerCase();
+		if (lower.startsWith('#')) {
+			const codePoint = lower.startsWith('#x')
+				? Number.parseInt(lower.slice(2), 16)
+				: Number.parseInt(lower.slice(1), 10);
+			return codePoint <= 0x10ffff ? String.fromCodePoint(codePoint) : entity;
+		}
+		return NAMED_ENTITIES[lower] ?? entity;
+	});
+}
@@ -0,0 +1,82 @@
+// Isometry v5 — Unmapped Column Capture
+// Shared by the tabular parsers (CSV, Excel, JSON) to keep columns that did
+// not map onto a cards field as custom card properties, and by ObsidianParser
+// for frontmatter keys.
+
+import type { CanonicalPropertyValue } from '../types';
+
+/**
+ * cards column names — never captured as properties, so re-importing an
+ * Isometry CSV/JSON export does not mirror every column into card_properties.
+ */
+const CARD_COLUMNS: ReadonlySet<string> = new Set([
+	'id',
+	'card_type',
+	'name',
+	'content',
+	'summary',
+	'latitude',
+	'longitude',
+	'location_name',
+	'created_at',
+	'modified_at',
+	'due_at',
+	'completed_at',
+	'event_start',
+	'event_end',
+	'folder',
+	'tags',
+	'status',
+	'priority',
+	'sort_order',
+	'url',
+	'mime_type',
+	'is_collective',
+	'source',
+	'source_id',
+	'source_url',
+	'deleted_at',
+]);
+
+/**
+ * Collect the scalar fields of `row` whose key is not in `mappedFields`.
+ *
+ * - Date values become ISO 8601 strings
+ * - Objects/arrays are skipped (not representable as a single typed value)
+ * - Blank keys, `__`-prefixed keys (PapaParse's `__parsed_extra`) and cards
+ *   column names are skipped
+ *
+ * @returns Record keyed by original header, or undefined when nothing is left
+ */
+export function collectUnmappedProperties(
+	row: Record<string, unknown>,
+	mappedFields: Iterable<string | undefined>,
+): Record<string, CanonicalPropertyValue> | undefined {
+	const mapped = new Set<string>();
+	for (const field of mappedFields) {
+		if (field) mapped.add(field);
+	}
+
+	const properties: Record<string, CanonicalPropertyValue> = {};
+	let count = 0;
+	for (const [key, value] of Object.entries(row)) {
+		if (mapped.has(key) || !key.trim() || key.startsWith('__') || CARD_COLUMNS.has(key.toLowerCase())) continue;
+
+		let scalar: CanonicalPropertyValue;
+		if (value instanceof Date) {
+			if (Number.isNaN(value.getTime())) continue;
+			scalar = value.toISOString();
+		} else if (value === null || value === undefined) {
+			scalar = null;
+		} else if (typeof value === 'string' || typeof value === 'number' || typeof value === 'boolean') {
+			scalar = value;
+		} else {
+			continue;
+		}
+
+		properties[key] = scalar;
+		count++;
+	}
+
+	return count > 0 ? properties : undefined;
+}
@@ -0,0 +1,401 @@
+// Isometry v5 — iCalendar Dates and Recurrence
+// DATE / DATE-TIME values with TZID resolution, DURATION, and RRULE expansion
+// for ICSParser.
+//
+// Expansion walks wall-clock dates in the event's own zone, then converts each
+// occurrence to UTC, so "every Monday 09:00 Europe/Berlin" stays at 09:00
+// across DST changes.
+
+/**
+ * A local date and time, as written in the calendar.
+ */
+export interface WallTime {
+	year: number;
+	/** 1-12 */
+	month: number;
+	day: number;
+	hour: number;
+	minute: number;
+	second: number;
+}
+
+/**
+ * A parsed DATE or DATE-TIME value.
+ */
+export interface ICalDate {
+	wall: WallTime;
+	/** VALUE=DATE (all-day) */
+	dateOnly: boolean;
+	/** 'UTC', an IANA zone from TZID, or null for floating times (read as UTC) */
+	zone: string | null;
+}
+
+export type Frequency = 'DAILY' | 'WEEKLY' | 'MONTHLY' | 'YEARLY';
+
+export interface RecurrenceRule {
+	freq: Frequency;
+	interval: number;
+	count: number | null;
+	/** Epoch ms, inclusive */
+	until: number | null;
+	/** Weekdays (0 = Sunday) with optional month-relative ordinal (1 = first, -1 = last) */
+	byDay: Array<{ weekday: number; ordinal: number | null }>;
+	byMonthDay: number[];
+	byMonth: number[];
+	/** Week start weekday (0 = Sunday), default Monday */
+	weekStart: number;
+}
+
+export interface ExpansionOptions {
+	/** Window start, epoch ms — occurrences ending before it are dropped */
+	windowStart: number;
+	/** Window end, epoch ms — occurrences starting after it are dropped */
+	windowEnd: number;
+	/** Event length in ms, used to keep occurrences overlapping the window start */
+	durationMs: number;
+	/** Occurrence starts (epoch ms) removed by EXDATE */
+	exdates: Set<number>;
+	/** Maximum occurrences returned */
+	limit: number;
+}
+
+const WEEKDAYS: Record<string, number> = { SU: 0, MO: 1, TU: 2, WE: 3, TH: 4, FR: 5, SA: 6 };
+const DATE_RE = /^(\d{4})(\d{2})(\d{2})(?:T(\d{2})(\d{2})(\d{2})(Z)?)?$/;
+const DURATION_RE = /^([+-])?P(?:(\d+)W)?(?:(\d+)D)?(?:T(?:(\d+)H)?(?:(\d+)M)?(?:(\d+)S)?)?$/;
+const DAY_MS = 86_400_000;
+/** Periods walked before giving up on a rule that never matches (e.g. BYMONTHDAY=31;BYMONTH=2) */
+const MAX_PERIODS = 50_000;
+
+// ---------------------------------------------------------------------------
+// Dates
+// ---------------------------------------------------------------------------
+
+/**
+ * Parse a DATE (`20260105`) or DATE-TIME (`20260105T090000`, `...Z`) value.
+ * Returns null for anything else.
+ */
+export function parseICalDate(value: string, tzid?: string): ICalDate | null {
+	const match = value.trim().match(DATE_RE);
+	if (!match) return null;
+	const dateOnly = match[4] === undefined;
+	return {
+		wall: {
+			year: Number(match[1]),
+			month: Number(match[2]),
+			day: Number(match[3]),
+			hour: dateOnly ? 0 : Number(match[4]),
+			minute: dateOnly ? 0 : Number(match[5]),
+			second: dateOnly ? 0 : Number(match[6]),
+		},
+		dateOnly,
+		zone: match[7] ? 'UTC' : dateOnly ? null : resolveZone(tzid),
+	};
+}
+
+/**
+ * Epoch ms for a calendar value. Floating times, all-day dates and unknown
+ * zones are read as UTC.
+ */
+export function toEpoch(date: ICalDate): number {
+	const utc = wallToUtc(date.wall);
+	if (!date.zone || date.zone === 'UTC') return utc;
+	// Two passes settle the offset across DST transitions
+	const first = zoneOffset(utc, date.zone);
+	const epoch = utc - first;
+	const second = zoneOffset(epoch, date.zone);
+	return second === first ? epoch : utc - second;
+}
+
+/**
+ * ISO 8601 for a calendar value: `YYYY-MM-DD` for all-day dates, UTC
+ * `YYYY-MM-DDTHH:MM:SSZ` otherwise.
+ */
+export function toIso(date: ICalDate): string {
+	if (date.dateOnly) {
+		const { year, month, day } = date.wall;
+		return `${String(year).padStart(4, '0')}-${pad(month)}-${pad(day)}`;
+	}
+	return new Date(toEpoch(date)).toISOString().replace(/\.\d{3}Z$/, 'Z');
+}
+
+/**
+ * Shift a calendar value by whole days and/or milliseconds. Day shifts move
+ * the wall clock (keeping all-day values all-day); millisecond shifts move
+ * the instant.
+ */
+export function shiftDate(date: ICalDate, days: number, ms = 0): ICalDate {
+	const wall = addDays(date.wall, days);
+	if (ms === 0) return { ...date, wall };
+	const epoch = toEpoch({ ...date, wall }) + ms;
+	return { wall: utcToWall(epoch), dateOnly: false, zone: 'UTC' };
+}
+
+/**
+ * Parse a DURATION (`P1W`, `PT1H30M`, `-P1D`) to days plus milliseconds.
+ */
+export function parseDuration(value: string): { days: number; ms: number } | null {
+	const match = value.trim().match(DURATION_RE);
+	if (!match || value.trim().replace(/^[+-]/, '') === 'P') return null;
+	const sign = match[1] === '-' ? -1 : 1;
+	const n = (i: number) => Number(match[i] ?? 0);
+	return {
+		days: sign * (n(2) * 7 + n(3)),
+		ms: sign * ((n(4) * 60 + n(5)) * 60 + n(6)) * 1000,
+	};
+}
+
+// ---------------------------------------------------------------------------
+// Recurrence
+// ---------------------------------------------------------------------------
+
+/**
+ * Parse an RRULE value. Returns null for rules this expander does not handle
+ * (sub-daily frequencies, missing FREQ).
+ */
+export function parseRRule(value: string, start: ICalDate): RecurrenceRule | null {
+	const parts = new Map<string, string>();
+	for (const part of value.split(';')) {
+		const eq = part.indexOf('=');
+		if (eq > 0) parts.set(part.slice(0, eq).trim().toUpperCase(), part.slice(eq + 1).trim().toUpperCase());
+	}
+
+	const freq = parts.get('FREQ');
+	if (freq !== 'DAILY' && freq !== 'WEEKLY' && freq !== 'MONTHLY' && freq !== 'YEARLY') return null;
+
+	const list = (key: string) =>
+		(parts.get(key) ?? '')
+			.split(',')
+			.filter(Boolean)
+			.map(Number)
+			.filter((n) => Number.isInteger(n) && n !== 0);
+
+	const byDay: RecurrenceRule['byDay'] = [];
+	for (const token of (parts.get('BYDAY') ?? '').split(',')) {
+		const match = token.match(/^([+-]?\d{1,2})?(SU|MO|TU|WE|TH|FR|SA)$/);
+		if (match) byDay.push({ weekday: WEEKDAYS[match[2]!]!, ordinal: match[1] ? Number(match[1]) : null });
+	}
+
+	// UNTIL is UTC or, for floating/all-day starts, in the start's own zone
+	const untilValue = parts.get('UNTIL');
+	const untilDate = untilValue ? parseICalDate(untilValue) : null;
+	const until = untilDate
+		? untilDate.dateOnly
+			? toEpoch({ ...start, wall: { ...untilDate.wall, hour: 23, minute: 59, second: 59 }, dateOnly: false })
+			: toEpoch(untilDate.zone === 'UTC' ? untilDate : { ...untilDate, zone: start.zone })
+		: null;
+
+	const interval = Number(parts.get('INTERVAL') ?? 1);
+	const count = parts.has('COUNT') ? Number(parts.get('COUNT')) : null;
+
+	return {
+		freq,
+		interval: Number.isInteger(interval) && interval > 0 ? interval : 1,
+		count: count !== null && Number.isInteger(count) && count > 0 ? count : null,
+		until,
+		byDay,
+		byMonthDay: list('BYMONTHDAY'),
+		byMonth: list('BYMONTH').filter((m) => m >= 1 && m <= 12),
+		weekStart: WEEKDAYS[parts.get('WKST') ?? 'MO'] ?? 1,
+	};
+}
+
+/**
+ * Expand a rule into occurrence starts within the window, in order.
+ * DTSTART is always the first occurrence; COUNT and UNTIL are honored
+ * before EXDATE removal, as RFC 5545 specifies.
+ */
+export function expandRecurrence(start: ICalDate, rule: RecurrenceRule, options: ExpansionOptions): ICalDate[] {
+	const occurrences: ICalDate[] = [];
+	const startEpoch = toEpoch(start);
+	let generated = 0;
+
+	// Returns false once the rule (or the window) is exhausted
+	const emit = (date: ICalDate, epoch: number): boolean => {
+		if (rule.until !== null && epoch > rule.until) return false;
+		if (epoch > options.windowEnd) return false;
+		generated++;
+		if (rule.count !== null && generated > rule.count) return false;
+		if (!options.exdates.has(epoch) && epoch + options.durationMs >= options.windowStart) {
+			occurrences.push(date);
+		}
+		return occurrences.length < options.limit;
+	};
+
+	if (!emit(start, startEpoch)) return occurrences;
+
+	for (let period = 0; period < MAX_PERIODS; period++) {
+		for (const day of periodDays(start.wall, rule, period)) {
+			const date: ICalDate = {
+				...start,
+				wall: { ...day, hour: start.wall.hour, minute: start.wall.minute, second: start.wall.second },
+			};
+			const epoch = toEpoch(date);
+			if (epoch <= startEpoch) continue;
+			if (!emit(date, epoch)) return occurrences;
+		}
+	}
+	return occurrences;
+}
+
+/**
+ * Candidate dates for the nth period of a rule, ascending.
+ */
+function periodDays(start: WallTime, rule: RecurrenceRule, period: number): WallTime[] {
+	const step = period * rule.interval;
+	const inMonths = (day: WallTime) => rule.byMonth.length === 0 || rule.byMonth.includes(day.month);
+
+	switch (rule.freq) {
+		case 'DAILY': {
+			const day = addDays(start, step);
+			const weekday = weekdayOf(day);
+			if (!inMonths(day)) return [];
+			if (rule.byDay.length > 0 && !rule.byDay.some((d) => d.weekday === weekday)) return [];
+			if (rule.byMonthDay.length > 0 && !monthDays(day.year, day.month, rule, day.day).includes(day.day)) return [];
+			return [day];
+		}
+		case 'WEEKLY': {
+			const weekFirst = addDays(start, -((weekdayOf(start) - rule.weekStart + 7) % 7) + step * 7);
+			const weekdays = rule.byDay.length > 0 ? rule.byDay.map((d) => d.weekday) : [weekdayOf(start)];
+			const offsets = [...new Set(weekdays.map((wd) => (wd - rule.weekStart + 7) % 7))].sort((a, b) => a - b);
+			return offsets.map((offset) => addDays(weekFirst, offset)).filter(inMonths);
+		}
+		case 'MONTHLY': {
+			const monthIndex = start.month - 1 + step;
+			const year = start.year + Math.floor(monthIndex / 12);
+			const month = (monthIndex % 12) + 1;
+			if (rule.byMonth.length > 0 && !rule.byMonth.includes(month)) return [];
+			return monthDays(year, month, rule, start.day).map((day) => ({ ...start, year, month, day }));
+		}
+		case 'YEARLY': {
+			const year = start.year + step;
+			let months = [start.month];
+			if (rule.byMonth.length > 0) months = [...rule.byMonth].sort((a, b) => a - b);
+			else if (rule.byDay.length > 0) months = range(1, 12);
+			return months.flatMap((month) =>
+				monthDays(year, month, rule, start.day).map((day) => ({ ...start, year, month, day })),
+			);
+		}
+	}
+}
+
+/**
+ * Days of a month selected by BYMONTHDAY / BYDAY (intersected when both are
+ * set), or the start's day of month when neither is. BYDAY ordinals count
+ * within the month.
+ */
+function monthDays(year: number, month: number, rule: RecurrenceRule, defaultDay: number): number[] {
+	const length = daysInMonth(year, month);
+	let days: number[] | null = null;
+
+	if (rule.byMonthDay.length > 0) {
+		days = rule.byMonthDay.map((d) => (d > 0 ? d : length + d + 1)).filter((d) => d >= 1 && d <= length);
+	}
+
+	if (rule.byDay.length > 0) {
+		const firstWeekday = weekdayOf({ year, month, day: 1, hour: 0, minute: 0, second: 0 });
+		const selected: number[] = [];
+		for (const { weekday, ordinal } of rule.byDay) {
+			const matches = range(1, length).filter((d) => (firstWeekday + d - 1) % 7 === weekday);
+			if (ordinal === null) selected.push(...matches);
+			else {
+				const day = ordinal > 0 ? matches[ordinal - 1] : matches[matches.length + ordinal];
+				if (day !== undefined) selected.push(day);
+			}
+		}
+		days = days ? days.filter((d) => selected.includes(d)) : selected;
+	}
+
+	// RFC 5545: a start on the 31st skips months without one (no clamping)
+	days ??= defaultDay <= length ? [defaultDay] : [];
+	return [...new Set(days)].sort((a, b) => a - b);
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+const formatters = new Map<string, Intl.DateTimeFormat>();
+
+/**
+ * Map a TZID to an IANA zone Intl understands. Vendor-prefixed ids
+ * (`/mozilla.org/20070129_1/Europe/London`) keep their last two segments;
+ * anything unresolvable becomes null (floating).
+ */
+function resolveZone(tzid: string | undefined): string | null {
+	if (!tzid) return null;
+	const candidates = [tzid, tzid.split('/').filter(Boolean).slice(-2).join('/')];
+	for (const candidate of candidates) {
+		if (candidate.toUpperCase() === 'UTC' || candidate.toUpperCase() === 'GMT') return 'UTC';
+		if (formatterFor(candidate)) return candidate;
+	}
+	return null;
+}
+
+function formatterFor(zone: string): Intl.DateTimeFormat | null {
+	const cached = formatters.get(zone);
+	if (cached) return cached;
+	try {
+		const formatter = new Intl.DateTimeFormat('en-US', {
+			timeZone: zone,
+			hourCycle: 'h23',
+			year: 'numeric',
+			month: 'numeric',
+			day: 'numeric',
+			hour: 'numeric',
+			minute: 'numeric',
+			second: 'numeric',
+		});
+		formatters.set(zone, formatter);
+		return formatter;
+	} catch {
+		return null;
+	}
+}
+
+/**
+ * Offset of a zone from UTC at an instant, in ms.
+ */
+function zoneOffset(epoch: number, zone: string): number {
+	const formatter = formatterFor(zone);
+	if (!formatter) return 0;
+	const parts = formatter.formatToParts(new Date(epoch));
+	const get = (type: Intl.DateTimeFormatPartTypes) => Number(parts.find((p) => p.type === type)?.value ?? 0);
+	const local = Date.UTC(get('year'), get('month') - 1, get('day'), get('hour') % 24, get('minute'), get('second'));
+	return local - Math.floor(epoch / 1000) * 1000;
+}
+
+function wallToUtc(wall: WallTime): number {
+	return Date.UTC(wall.year, wall.month - 1, wall.day, wall.hour, wall.minute, wall.second);
+}
+
+function utcToWall(epoch: number): WallTime {
+	const date = new Date(epoch);
+	return {
+		year: date.getUTCFullYear(),
+		month: date.getUTCMonth() + 1,
+		day: date.getUTCDate(),
+		hour: date.getUTCHours(),
+		minute: date.getUTCMinutes(),
+		second: date.getUTCSeconds(),
+	};
+}
+
+function addDays(wall: WallTime, days: number): WallTime {
+	return days === 0 ? wall : utcToWall(wallToUtc(wall) + days * DAY_MS);
+}
+
+function weekdayOf(wall: WallTime): number {
+	return new Date(Date.UTC(wall.year, wall.month - 1, wall.day)).getUTCDay();
+}
+
+function daysInMonth(year: number, month: number): number {
+	return new Date(Date.UTC(year, month, 0)).getUTCDate();
+}
+
+function range(from: number, to: number): number[] {
+	return Array.from({ length: to - from + 1 }, (_, i) => from + i);
+}
+
+function pad(n: number): string {
+	return String(n).padStart(2, '0');
+}
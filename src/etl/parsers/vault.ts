@@ -0,0 +1,47 @@
+// Isometry v5 — Obsidian Vault Files
+// Vault file shape shared by ObsidianParser (worker) and the vault picker
+// (main thread). Kept free of parser dependencies so the main bundle does not
+// pull in gray-matter.
+
+import type { ParsedFile } from './MarkdownParser';
+
+/**
+ * A vault file. Attachments only need their path (content may be empty);
+ * `modified` (ISO 8601 file mtime) keeps re-imports from updating untouched files.
+ */
+export interface VaultFile extends ParsedFile {
+	modified?: string;
+}
+
+/**
+ * Any dot-prefixed path segment (.obsidian config, .trash, .git).
+ */
+export function isHiddenVaultPath(path: string): boolean {
+	return path.split('/').some((segment) => segment.startsWith('.'));
+}
+
+/**
+ * Turn the files of a picked vault folder (`<input webkitdirectory>`) into
+ * VaultFile[] with vault-relative paths. Only markdown is read; attachments
+ * carry their path and mtime. Hidden folders are skipped.
+ *
+ * @returns Vault name (the picked folder) and its files
+ */
+export async function readVaultFiles(picked: ArrayLike<File>): Promise<{ name: string; files: VaultFile[] }> {
+	let name = 'Vault';
+	const files: VaultFile[] = [];
+	for (const file of Array.from(picked)) {
+		const relative = file.webkitRelativePath || file.name;
+		const slash = relative.indexOf('/');
+		if (slash > 0) name = relative.slice(0, slash);
+		const path = slash > 0 ? relative.slice(slash + 1) : relative;
+		if (isHiddenVaultPath(path)) continue;
+
+		files.push({
+			path,
+			content: /\.md$/i.test(path) ? await file.text() : '',
+			modified: new Date(file.lastModified).toISOString().replace(/\.\d{3}Z$/, 'Z'),
+		});
+	}
+	return { name, files };
+}
@@ -18,6 +18,12 @@ import type { CardType } from '../database/queries/types';
 export type SourceType =
 	| 'apple_notes'
 	| 'markdown'
+	| 'obsidian'
+	| 'opml'
+	| 'ics'
+	| 'vcard'
+	| 'email'
+	| 'bookmarks'
 	| 'excel'
 	| 'csv'
 	| 'json'
@@ -85,8 +91,21 @@ export interface CanonicalCard {
 
 	// Lifecycle
 	deleted_at: string | null;
+
+	// Custom properties (tabular sources, vault frontmatter, contact details)
+	/**
+	 * Source columns that did not map onto a cards column, keyed by original
+	 * header. SQLiteWriter.writeProperties() turns these into typed
+	 * card_properties rows. Absent when the source had no extra columns.
+	 */
+	properties?: Record<string, CanonicalPropertyValue>;
 }
 
+/**
+ * Raw value of an unmapped source column, before type coercion.
+ */
+export type CanonicalPropertyValue = string | number | boolean | null;
+
 /**
  * CanonicalConnection: Maps 1:1 to connections table columns.
  *
@@ -146,6 +165,8 @@ export interface ImportResult {
 	deletedIds: string[];
 	/** Detailed error information (empty if errors === 0) */
 	errors_detail: ParseError[];
+	/** IDs of written cards each opt-in enricher changed, by enricher id (absent when none did) */
+	enriched?: Record<string, string[]>;
 }
 
 // ---------------------------------------------------------------------------
@@ -38,7 +38,22 @@ export type {
 	Connection,
 	ConnectionDirection,
 	ConnectionInput,
+	ConnectionUpdate,
+	FormulaDefinition,
+	FormulaDefinitionInput,
+	FormulaInfo,
+	FormulaResultType,
+	PropertyDefinition,
+	PropertyDefinitionInput,
+	PropertyType,
+	PropertyValue,
+	SavedSearch,
 	SearchResult,
+	SimilarCardResult,
+	Story,
+	StoryInput,
+	StorySlide,
+	StorySlideState,
 } from './worker';
 
 // ---------------------------------------------------------------------------
@@ -56,8 +71,13 @@ export { Database } from './database/Database';
 // Query module exports (used by worker handlers, also available for testing)
 export * as cardQueries from './database/queries/cards';
 export * as connectionQueries from './database/queries/connections';
+export * as formulaQueries from './database/queries/formulas';
 export * as graphQueries from './database/queries/graph';
+export * as propertyQueries from './database/queries/properties';
+export * as savedSearchQueries from './database/queries/saved-searches';
 export * as searchQueries from './database/queries/search';
+export * as similarityQueries from './database/queries/similarity';
+export * as storyQueries from './database/queries/stories';
 export { patchFetchForWasm } from './database/wasm-compat';
 // ---------------------------------------------------------------------------
 // ETL Pipeline (Phase 8)
@@ -87,9 +107,14 @@ export type {
 	CompiledFilter,
 	CompiledQuery,
 	Filter,
+	FilterCombinator,
 	FilterField,
+	FilterGroup,
+	FilterNode,
 	FilterOperator,
 	PersistableProvider,
+	RelativeDateExpr,
+	RelativeDateUnit,
 	SortDirection,
 	TimeGranularity,
 	ViewFamily,
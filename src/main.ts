@@ -18,20 +18,27 @@ if (typeof window['requestIdleCallback'] === 'undefined') {
 
 import { Announcer, motionProvider } from './accessibility';
 import { AuditLegend, AuditOverlay, auditState } from './audit';
-import type { SourceType } from './etl/types';
+import type { MappingSuggestion } from './etl/MappingProfile';
+import type { ExcelSheetInfo } from './etl/parsers/ExcelParser';
+import { readVaultFiles } from './etl/parsers/vault';
+import type { ImportResult, SourceType } from './etl/types';
 import { MutationManager } from './mutations';
 import { base64ToUint8Array, initNativeBridge, waitForLaunchPayload } from './native/NativeBridge';
 import { CommandPalette, CommandRegistry } from './palette';
 import type { ThemeMode, ViewType } from './providers';
 import {
+	compiledFormulasOf,
 	DensityProvider,
 	FilterProvider,
+	formulaColumns,
 	PAFVProvider,
+	propertyColumnInfo,
 	QueryBuilder,
 	SchemaProvider,
 	SelectionProvider,
 	StateCoordinator,
 	StateManager,
+	setCompiledFormulas,
 	setLatchSchemaProvider,
 	setSchemaProvider,
 	ThemeProvider,
@@ -50,12 +57,17 @@ import { AlgorithmExplorer } from './ui/AlgorithmExplorer';
 import { LayoutPresetManager } from './presets/LayoutPresetManager';
 import { createPresetCommands } from './presets/presetCommands';
 import { PresetSuggestionToast } from './presets/PresetSuggestionToast';
+import { SavedSearchManager } from './searches/SavedSearchManager';
+import { createSavedSearchCommands } from './searches/savedSearchCommands';
+import { StoryManager } from './stories/StoryManager';
 import { TourEngine } from './tour/TourEngine';
 import { TourPromptToast } from './tour/TourPromptToast';
 import { AppDialog } from './ui/AppDialog';
 import { CalcExplorer } from './ui/CalcExplorer';
 import type { DataExplorerPanel } from './ui/DataExplorerPanel';
 import { DiffPreviewDialog } from './ui/DiffPreviewDialog';
+import { EnrichersDialog } from './ui/EnrichersDialog';
+import { FormulasExplorer } from './ui/FormulasExplorer';
 import type { AltoDiscoveryPayload, AltoImportProgressEvent } from './ui/DirectoryDiscoverySheet';
 import { DirectoryDiscoverySheet } from './ui/DirectoryDiscoverySheet';
 import { ImportToast } from './ui/ImportToast';
@@ -63,15 +75,14 @@ import { LatchExplorers } from './ui/LatchExplorers';
 import { migrateNotebookContent, NotebookExplorer } from './ui/NotebookExplorer';
 import { ProjectionExplorer } from './ui/ProjectionExplorer';
 import { PropertiesExplorer } from './ui/PropertiesExplorer';
+import { SheetPickerDialog } from './ui/SheetPickerDialog';
+import { StoriesExplorer } from './ui/StoriesExplorer';
 import { CommandBar } from './ui/CommandBar';
 import { DockNav } from './ui/DockNav';
 import { viewOrder } from './ui/section-defs';
 import { VisualExplorer } from './ui/VisualExplorer';
 import { PanelRegistry } from './ui/panels/PanelRegistry';
 import { PanelManager } from './ui/panels/PanelManager';
-import { MAPS_PANEL_META, mapsPanelFactory } from './ui/panels/MapsPanelStub';
-import { FORMULAS_PANEL_META, formulasPanelFactory } from './ui/panels/FormulasPanelStub';
-import { STORIES_PANEL_META, storiesPanelFactory } from './ui/panels/StoriesPanelStub';
 import { register, registerAllStubs, getCanvasFactory } from './superwidget/registry';
 import { SuperWidget } from './superwidget/SuperWidget';
 import { ExplorerCanvas } from './superwidget/ExplorerCanvas';
@@ -88,7 +99,9 @@ import {
 	GridView,
 	KanbanView,
 	ListView,
+	MapView,
 	NetworkView,
+	shouldUseMorph,
 	TimelineView,
 	TreeView,
 	ViewManager,
@@ -158,10 +171,11 @@ async function main(): Promise<void> {
 	const bridgeConfig = {
 		...(wasmBinary !== undefined && { wasmBinary }),
 		...(dbData !== undefined && { dbData }),
-		onSchema: (schema: {
-			cards: import('./worker/protocol').ColumnInfo[];
-			connections: import('./worker/protocol').ColumnInfo[];
-		}) => schemaProvider.initialize(schema),
+		onSchema: (schema: import('./worker/protocol').WorkerReadyMessage['schema']) => {
+			// Formula fields compile to SQL on this thread too (FilterProvider.compile)
+			setCompiledFormulas(schema.formulas ?? []);
+			schemaProvider.initialize(schema);
+		},
 	};
 	const bridge = createWorkerBridge(bridgeConfig);
 	await bridge.isReady;
@@ -172,6 +186,14 @@ async function main(): Promise<void> {
 	// 2a-71: Wire SchemaProvider to latch.ts for dynamic LATCH family lookup (DYNM-01..04).
 	setLatchSchemaProvider(schemaProvider);
 
+	// Formula fields: re-register compiled fx_<name> SQL and columns from a formula:list result.
+	// Used by the Formulas panel and after imports / property changes.
+	const applyFormulas = (formulas: readonly import('./worker/protocol').FormulaInfo[]): void => {
+		const compiled = compiledFormulasOf(formulas);
+		setCompiledFormulas(compiled);
+		schemaProvider.setFormulaColumns(formulaColumns(compiled));
+	};
+
 	// Phase 73: Restore LATCH overrides and disabled fields from ui_state (UCFG-03)
 	{
 		const overridesRow = (await bridge.send('ui:get', { key: 'latch:overrides' })) as { value?: string };
@@ -344,6 +366,8 @@ async function main(): Promise<void> {
 	let viewManager: ViewManager;
 	let calcExplorer: CalcExplorer | null = null;
 	let algorithmExplorer: AlgorithmExplorer | null = null;
+	let formulasExplorer: FormulasExplorer | null = null;
+	let storiesExplorer: StoriesExplorer | null = null;
 	// Menu action forward declarations (captured by commandBarConfig closure)
 	let importFileHandler: (() => void) | null = null;
 	let importNativeHandler: (() => void) | null = null;
@@ -364,6 +388,7 @@ async function main(): Promise<void> {
 			return nv;
 		},
 		tree: () => new TreeView({ bridge }),
+		map: () => new MapView({ bridge, selectionProvider: selection }),
 		supergrid: () => {
 			// Create SuperGridSelectionLike adapter over the existing SelectionProvider.
 			// SuperGrid operates on cell-level card ID arrays; SelectionProvider operates on flat card ID sets.
@@ -694,6 +719,30 @@ async function main(): Promise<void> {
 		catalogGrid?.refresh();
 	}
 
+	// Import enricher settings; "Re-run" applies the enabled enrichers to cards already imported
+	async function manageEnrichers(): Promise<void> {
+		const { enrichers, settings } = await bridge.listEnrichers();
+		const rerun = await EnrichersDialog.show({
+			enrichers,
+			settings,
+			onChange: (setting) => bridge.setEnricherSetting(setting),
+		});
+		if (!rerun) return;
+
+		const result = await bridge.backfillEnrichment();
+		const insertedIds = result.insertedIds ?? [];
+		const updatedIds = result.updatedIds ?? [];
+		auditState.addEnrichmentResult({ insertedIds, updatedIds, enriched: result.enriched ?? {} });
+		toast.showMessage(
+			insertedIds.length + updatedIds.length === 0
+				? 'Enrichers found nothing new'
+				: `Enriched ${updatedIds.length} cards, created ${insertedIds.length}`,
+		);
+		await refreshPropertyColumns();
+		coordinator.scheduleUpdate();
+		void refreshDataExplorer();
+	}
+
 	async function handleDatasetSwitch(datasetId: string, datasetName: string): Promise<void> {
 		// Show loading state immediately in command bar
 		commandBar.setSubtitle('Loading\u2026');
@@ -794,20 +843,25 @@ async function main(): Promise<void> {
 		const detail = (
 			e as CustomEvent<{
 				datasetId: string;
-				cards: import('./etl/types').CanonicalCard[];
+				cards?: import('./etl/types').CanonicalCard[];
+				files?: import('./etl/FileManifest').ReimportFile[];
 				name: string;
 			}>
 		).detail;
 
 		void (async () => {
-			// Step 1: Send cards to Worker for dedup (no write yet)
+			// Step 1: Send cards (or the incremental file re-scan) to Worker for dedup (no write yet)
 			const diffResult = await bridge.send('datasets:reimport', {
 				datasetId: detail.datasetId,
-				cards: detail.cards,
+				...(detail.files ? { files: detail.files } : { cards: detail.cards ?? [] }),
 			});
 
 			// Step 2: Check for zero changes
-			const totalChanges = diffResult.toInsert.length + diffResult.toUpdate.length + diffResult.deletedIds.length;
+			const totalChanges =
+				diffResult.toInsert.length +
+				diffResult.toUpdate.length +
+				diffResult.deletedIds.length +
+				diffResult.renamed.length;
 
 			if (totalChanges === 0) {
 				// Zero changes — show brief toast, no modal
@@ -833,7 +887,9 @@ async function main(): Promise<void> {
 				toUpdate: diffResult.toUpdate,
 				deletedIds: diffResult.deletedIds,
 				deletedNames: diffResult.deletedNames,
+				renamed: diffResult.renamed,
 				unchanged: diffResult.unchanged,
+				files: diffResult.files,
 			});
 
 			if (committed) {
@@ -845,6 +901,9 @@ async function main(): Promise<void> {
 				// Show completion toast
 				toast.showSuccess(result);
 
+				// A re-import may introduce custom properties — re-sync prop_<key> columns first
+				await refreshPropertyColumns();
+
 				// Refresh catalog and views
 				catalogGrid?.refresh();
 				coordinator.scheduleUpdate();
@@ -858,8 +917,7 @@ async function main(): Promise<void> {
 	// Map dock item composite keys to PanelRegistry panel IDs for explorer toggle routing
 	const dockToPanelMap: Record<string, string> = {
 		'activate:notebook': 'notebook',
-		// Stub panels (Phase 149)
-		'activate:stories': 'stories-stub',
+		'activate:stories': 'stories',
 	};
 
 	const dockNav = new DockNav({
@@ -944,8 +1002,8 @@ async function main(): Promise<void> {
 					return;
 				}
 				if (itemKey === 'formula') {
-					panelManager.toggle('formulas-stub');
-					dockNav.setItemPressed('analyze:formula', panelManager.isVisible('formulas-stub'));
+					panelManager.toggle('formulas');
+					dockNav.setItemPressed('analyze:formula', panelManager.isVisible('formulas'));
 					return;
 				}
 			}
@@ -972,7 +1030,7 @@ async function main(): Promise<void> {
 				}
 			}
 
-			// PanelRegistry-only panels (notebook, stories-stub, maps-stub)
+			// PanelRegistry-only panels (notebook, stories)
 			const compositeKey = `${sectionKey}:${itemKey}`;
 			const panelId = dockToPanelMap[compositeKey];
 			if (panelId) {
@@ -1073,7 +1131,7 @@ async function main(): Promise<void> {
 			// Web: create ephemeral file input for manual file selection
 			const input = document.createElement('input');
 			input.type = 'file';
-			input.accept = '.json,.csv,.xlsx,.xls,.md,.html,.htm';
+			input.accept = '.json,.csv,.xlsx,.xls,.md,.html,.htm,.opml,.ics,.vcf,.eml,.mbox';
 			input.style.display = 'none';
 			input.addEventListener('change', async () => {
 				const file = input.files?.[0];
@@ -1094,6 +1152,12 @@ async function main(): Promise<void> {
 					md: 'markdown',
 					html: 'html',
 					htm: 'html',
+					opml: 'opml',
+					ics: 'ics',
+					vcf: 'vcard',
+					vcard: 'vcard',
+					eml: 'email',
+					mbox: 'email',
 				};
 				const source = sourceMap[ext] ?? 'json';
 
@@ -1231,6 +1295,35 @@ async function main(): Promise<void> {
 		getActiveDatasetId: () => sm.getActiveDatasetId(),
 	});
 
+	// 14a-2b. Saved searches (smart folders): Filters panel section + palette commands
+	const savedSearchManager = new SavedSearchManager(bridge, filter);
+	await savedSearchManager.refresh();
+	createSavedSearchCommands({
+		manager: savedSearchManager,
+		registry: commandRegistry,
+		palette: commandPalette,
+		actionToast,
+	});
+
+	// 14a-2c. Stories: ordered slides of captured view states (Stories panel).
+	//         Slide view switches skip the content fade when ViewManager will morph.
+	const storyManager = new StoryManager({
+		bridge,
+		pafv,
+		filter,
+		density,
+		selection,
+		switchView: async (viewType) => {
+			const morph = shouldUseMorph(pafv.getState().viewType, viewType);
+			dockNav.setActiveItem('visualize', viewType);
+			if (!morph) viewContentEl.style.opacity = '0';
+			await viewManager.switchTo(viewType, () => safeViewFactory(viewType)());
+			viewContentEl.style.opacity = '1';
+		},
+		onApplied: () => coordinator.scheduleUpdate(),
+	});
+	await storyManager.refresh();
+
 	// 14a-3. Create PresetSuggestionToast for dataset-switch preset associations (Phase 133)
 	presetSuggestionToast = new PresetSuggestionToast(document.body);
 	presetSuggestionToast.setOnApply((name) => {
@@ -1369,6 +1462,8 @@ async function main(): Promise<void> {
 					bridge,
 					coordinator,
 					schema: schemaProvider,
+					savedSearches: savedSearchManager,
+					filterBuilder: true,
 				});
 				latchExplorers.mount(container);
 				// Phase 73: Remount LatchExplorers when LATCH overrides change (UCFG-04)
@@ -1502,9 +1597,75 @@ async function main(): Promise<void> {
 		}),
 	);
 
-	panelRegistry.register(MAPS_PANEL_META, mapsPanelFactory);
-	panelRegistry.register(FORMULAS_PANEL_META, formulasPanelFactory);
-	panelRegistry.register(STORIES_PANEL_META, storiesPanelFactory);
+	panelRegistry.register(
+		{
+			id: 'formulas',
+			name: 'Formulas',
+			icon: 'code',
+			description: 'Formulas Explorer',
+			dependencies: [],
+			defaultEnabled: false,
+		},
+		() => ({
+			mount(container: HTMLElement): void {
+				container.textContent = '';
+				formulasExplorer = new FormulasExplorer({
+					bridge,
+					container,
+					onFormulasChange: (formulas) => {
+						applyFormulas(formulas);
+						coordinator.scheduleUpdate();
+					},
+				});
+				void formulasExplorer.mount();
+			},
+			update(): void {
+				// FormulasExplorer reloads on its own define/delete actions
+			},
+			destroy(): void {
+				formulasExplorer?.destroy();
+				formulasExplorer = null;
+			},
+		}),
+	);
+
+	panelRegistry.register(
+		{
+			id: 'stories',
+			name: 'Stories',
+			icon: 'book-open',
+			description: 'Stories Explorer',
+			dependencies: [],
+			defaultEnabled: false,
+		},
+		() => ({
+			mount(container: HTMLElement): void {
+				container.textContent = '';
+				storiesExplorer = new StoriesExplorer({
+					manager: storyManager,
+					bridge,
+					container,
+					download: (filename, html) => {
+						const blob = new Blob([html], { type: 'text/html;charset=utf-8' });
+						const url = URL.createObjectURL(blob);
+						const a = document.createElement('a');
+						a.href = url;
+						a.download = filename;
+						a.click();
+						URL.revokeObjectURL(url);
+					},
+				});
+				storiesExplorer.mount();
+			},
+			update(): void {
+				// StoriesExplorer re-renders on StoryManager subscription
+			},
+			destroy(): void {
+				storiesExplorer?.destroy();
+				storiesExplorer = null;
+			},
+		}),
+	);
 
 	// Phase 167: Register canvas factories — stubs for view/editor, real ExplorerCanvas for explorer
 	registerAllStubs();
@@ -1553,6 +1714,12 @@ async function main(): Promise<void> {
 						md: 'markdown',
 						html: 'html',
 						htm: 'html',
+						opml: 'opml',
+						ics: 'ics',
+						vcf: 'vcard',
+						vcard: 'vcard',
+						eml: 'email',
+						mbox: 'email',
 					};
 					const source = sourceMap[ext] ?? 'json';
 					const binaryFormats = new Set(['xlsx', 'xls']);
@@ -1563,6 +1730,23 @@ async function main(): Promise<void> {
 						void refreshDataExplorer();
 					})();
 				},
+				onManageEnrichers: () => {
+					void manageEnrichers();
+				},
+				onImportVault: (files: File[]) => {
+					// Same 25MB guard as single-file import, applied to the vault's notes
+					const markdownBytes = files.filter((f) => /\.md$/i.test(f.name)).reduce((sum, f) => sum + f.size, 0);
+					if (markdownBytes > 25 * 1024 * 1024) {
+						toast.showError('Vault too large (max 25MB of notes)');
+						return;
+					}
+					void (async () => {
+						const vault = await readVaultFiles(files);
+						await bridge.importFile('obsidian', JSON.stringify(vault.files), { filename: vault.name });
+						coordinator.scheduleUpdate();
+						void refreshDataExplorer();
+					})();
+				},
 				onSelectCard: (cardId: string) => {
 					selection.select(cardId);
 				},
@@ -1694,6 +1878,8 @@ async function main(): Promise<void> {
 						const dirName = sourceType.startsWith('alto_index_')
 							? sourceType.replace('alto_index_', '')
 							: sourceType;
+						// Stored manifest lets Swift skip files whose size/mtime/hash are unchanged
+						const manifest = await bridge.send('datasets:files', { datasetId });
 						window.webkit!.messageHandlers.nativeBridge.postMessage({
 							id: crypto.randomUUID(),
 							type: 'native:request-alto-reimport',
@@ -1702,6 +1888,7 @@ async function main(): Promise<void> {
 								name: dirName,
 								cardType: dirName,
 								path: directoryPath,
+								manifest,
 							},
 							timestamp: Date.now(),
 						});
@@ -1730,7 +1917,7 @@ async function main(): Promise<void> {
 			{ id: 'properties', container: propertiesChildEl, slot: 'top' },
 			{ id: 'projection', container: projectionChildEl, slot: 'top' },
 			{ id: 'latch', container: latchFiltersChildEl, slot: 'bottom' },
-			{ id: 'formulas-stub', container: formulasChildEl, slot: 'bottom' },
+			{ id: 'formulas', container: formulasChildEl, slot: 'bottom' },
 		],
 		groups: [
 			{ name: 'integrate', panelIds: ['properties'] },
@@ -1742,7 +1929,7 @@ async function main(): Promise<void> {
 			const anyVisible = panelManager!.isVisible('properties')
 				|| panelManager!.isVisible('projection')
 				|| panelManager!.isVisible('latch')
-				|| panelManager!.isVisible('formulas-stub');
+				|| panelManager!.isVisible('formulas');
 			superWidget.setSidecarVisible(anyVisible);
 		},
 	});
@@ -1755,7 +1942,7 @@ async function main(): Promise<void> {
 	if (panelManager.isVisible('latch')) {
 		dockNav.setItemPressed('analyze:filter', true);
 	}
-	if (panelManager.isVisible('formulas-stub')) {
+	if (panelManager.isVisible('formulas')) {
 		dockNav.setItemPressed('analyze:formula', true);
 	}
 
@@ -1812,8 +1999,73 @@ async function main(): Promise<void> {
 	// 16. Wire AuditState to import results (Phase 37) + sample data import guard (SMPL-07)
 	//     Wrap bridge.importFile and bridge.importNative to intercept ImportResult
 	//     and feed it to auditState. This avoids modifying WorkerBridge.ts.
+	//     Tabular imports may define new custom properties — re-sync prop_<key> columns,
+	//     and the fx_<name> formulas that may now (or no longer) compile against them.
+	const refreshPropertyColumns = async (): Promise<void> => {
+		const definitions = await bridge.listProperties();
+		schemaProvider.setPropertyColumns(definitions.map(propertyColumnInfo));
+		applyFormulas(await bridge.listFormulas());
+	};
+	// Tabular imports: offer the saved mapping profile whose headers match the file.
+	// A failed suggestion never blocks the import — it falls back to auto-detection.
+	const chooseMappingProfile = async (
+		source: SourceType,
+		data: string | ArrayBuffer,
+		filename?: string,
+		sheet?: string,
+	): Promise<string | undefined> => {
+		let suggestion: MappingSuggestion | null = null;
+		try {
+			({ suggestion } = await bridge.suggestMappingProfile(source, data, filename, sheet));
+		} catch (err) {
+			console.warn('[Import] Mapping profile suggestion failed:', err);
+		}
+		if (!suggestion) return undefined;
+		const useProfile = await AppDialog.show({
+			variant: 'confirm',
+			title: 'Mapping Profile',
+			message: `This file's columns match the mapping profile \u201c${suggestion.profile.name}\u201d. Import with it?`,
+			confirmLabel: 'Use Profile',
+			cancelLabel: 'Auto-detect Columns',
+		});
+		return useProfile ? suggestion.profile.id : undefined;
+	};
+	// Workbooks with several sheets: pick which sheets import together, with row counts.
+	// Resolves undefined for single-sheet workbooks (no picker), null when cancelled.
+	const chooseExcelSheets = async (
+		data: string | ArrayBuffer,
+		filename?: string,
+	): Promise<string[] | null | undefined> => {
+		let sheets: ExcelSheetInfo[] = [];
+		try {
+			sheets = await bridge.listExcelSheets(data);
+		} catch (err) {
+			console.warn('[Import] Reading workbook sheets failed:', err);
+		}
+		if (sheets.length < 2) return undefined;
+		return SheetPickerDialog.show({ filename: filename ?? 'Workbook', sheets });
+	};
+	const emptyImportResult = (): ImportResult => ({
+		inserted: 0,
+		updated: 0,
+		unchanged: 0,
+		skipped: 0,
+		errors: 0,
+		connections_created: 0,
+		insertedIds: [],
+		updatedIds: [],
+		deletedIds: [],
+		errors_detail: [],
+	});
 	const originalImportFile = bridge.importFile.bind(bridge);
 	bridge.importFile = async (source, data, options) => {
+		let importOptions = options;
+		if (source === 'excel' && options?.sheets === undefined) {
+			const sheets = await chooseExcelSheets(data, options?.filename);
+			// Cancelled in the sheet picker — nothing is imported
+			if (sheets === null) return emptyImportResult();
+			if (sheets !== undefined) importOptions = { ...options, sheets };
+		}
 		// SMPL-07: Prompt to clear sample data before first real import
 		if (sampleDataLoaded) {
 			const clearIt = await AppDialog.show({
@@ -1828,7 +2080,15 @@ async function main(): Promise<void> {
 				sampleDataLoaded = false;
 			}
 		}
-		const result = await originalImportFile(source, data, options);
+		// Mapping profiles map one header row — not offered for multi-sheet imports
+		const sheets = importOptions?.sheets;
+		const tabular = source === 'csv' || source === 'excel' || source === 'json';
+		if (tabular && importOptions?.profileId === undefined && (sheets === undefined || sheets.length === 1)) {
+			const profileId = await chooseMappingProfile(source, data, importOptions?.filename, sheets?.[0]);
+			if (profileId !== undefined) importOptions = { ...importOptions, profileId };
+		}
+		const result = await originalImportFile(source, data, importOptions);
+		await refreshPropertyColumns();
 		// SGDF-05: Track source type for ProjectionExplorer Reset button
 		activeSourceType = source;
 		dockNav.updateRecommendations(activeSourceType);
@@ -8,6 +8,7 @@ export {
 	deleteCardMutation,
 	deleteConnectionMutation,
 	updateCardMutation,
+	updateConnectionMutation,
 } from './inverses';
 export type { MutationBridge, UndoRedoToast } from './MutationManager';
 export { MutationManager } from './MutationManager';
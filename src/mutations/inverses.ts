@@ -8,7 +8,7 @@
 // RESEARCH Pitfall 3: DELETE requires full row — deleteCardMutation takes full Card, not just ID
 // RESEARCH Pitfall 4: Batch inverse order reversed — forward [A,B,C] → inverse [undoC,undoB,undoA]
 
-import type { Card, CardInput, Connection, ConnectionInput } from '../database/queries/types';
+import type { Card, CardInput, Connection, ConnectionInput, ConnectionUpdate } from '../database/queries/types';
 import type { Mutation, MutationCommand } from './types';
 
 // ---------------------------------------------------------------------------
@@ -316,6 +316,69 @@ export function createConnectionMutation(input: ConnectionInput): Mutation {
 	};
 }
 
+// ---------------------------------------------------------------------------
+// updateConnectionMutation
+// ---------------------------------------------------------------------------
+
+/** Connection columns editable in place (endpoints are immutable). */
+const CONNECTION_UPDATE_FIELDS = ['label', 'weight', 'via_card_id'] as const;
+
+/**
+ * Update a connection mutation.
+ * Only includes fields present in `after`; inverse restores old values from `before`.
+ * id and created_at are untouched, so editing an edge keeps its history.
+ *
+ * MUT-02: forward + inverse computed at creation time (before state captured now)
+ */
+export function updateConnectionMutation(before: Connection, after: ConnectionUpdate): Mutation {
+	const forwardSets: string[] = [];
+	const forwardParams: unknown[] = [];
+	const inverseSets: string[] = [];
+	const inverseParams: unknown[] = [];
+
+	for (const field of CONNECTION_UPDATE_FIELDS) {
+		if (after[field] === undefined) continue;
+		forwardSets.push(`${field} = ?`);
+		forwardParams.push(after[field]);
+		inverseSets.push(`${field} = ?`);
+		inverseParams.push(before[field]);
+	}
+
+	const description = `Update connection ${before.source_id} → ${before.target_id}`;
+
+	if (forwardSets.length === 0) {
+		// No-op mutation (identity)
+		return {
+			id: crypto.randomUUID(),
+			timestamp: Date.now(),
+			description,
+			forward: [],
+			inverse: [],
+		};
+	}
+
+	forwardParams.push(before.id);
+	inverseParams.push(before.id);
+
+	return {
+		id: crypto.randomUUID(),
+		timestamp: Date.now(),
+		description,
+		forward: [
+			{
+				sql: `UPDATE connections SET ${forwardSets.join(', ')} WHERE id = ?`,
+				params: forwardParams,
+			},
+		],
+		inverse: [
+			{
+				sql: `UPDATE connections SET ${inverseSets.join(', ')} WHERE id = ?`,
+				params: inverseParams,
+			},
+		],
+	};
+}
+
 // ---------------------------------------------------------------------------
 // deleteConnectionMutation
 // ---------------------------------------------------------------------------
@@ -16,6 +16,7 @@
 // Always convert to base64 first.
 
 import type { CardType } from '../database/queries/types';
+import type { ReimportFile } from '../etl/FileManifest';
 import type { CanonicalCard, SourceType } from '../etl/types';
 import type { WorkerBridge } from '../worker/WorkerBridge';
 
@@ -106,10 +107,26 @@ const MUTATING_TYPES = new Set<string>([
 	'card:delete',
 	'card:undelete',
 	'connection:create',
+	'connection:update',
 	'connection:delete',
 	'db:exec',
 	'etl:import',
 	'etl:import-native', // Native adapter imports (Phase 33)
+	'property:define',
+	'property:delete',
+	'property:set',
+	'saved-search:save',
+	'saved-search:rename',
+	'saved-search:delete',
+	'mapping-profile:save',
+	'mapping-profile:delete',
+	'enrich:backfill',
+	'enrich:set',
+	'geocode:fill',
+	'formula:define',
+	'formula:delete',
+	'story:save',
+	'story:delete',
 ]);
 
 // ---------------------------------------------------------------------------
@@ -295,11 +312,22 @@ export function initNativeBridge(bridge: WorkerBridge): void {
 			case 'native:alto-reimport-result': {
 				const payload = message.payload as {
 					datasetId: string;
-					cards: CanonicalCard[];
+					cards?: CanonicalCard[];
+					files?: Array<Omit<ReimportFile, 'cards'> & { cards?: Record<string, unknown>[] }>;
 					name: string;
 				};
-				console.log('[NativeBridge] alto-reimport-result: dataset', payload.datasetId, payload.cards.length, 'cards');
-				window.dispatchEvent(new CustomEvent('alto-reimport-result', { detail: payload }));
+				// Incremental re-scan: only changed files carry cards (same nil-key normalization as chunks)
+				const files = payload.files?.map(
+					({ cards, ...entry }): ReimportFile => (cards ? { ...entry, cards: cards.map(normalizeNativeCard) } : entry),
+				);
+				console.log(
+					'[NativeBridge] alto-reimport-result: dataset',
+					payload.datasetId,
+					files ? `${files.length} files` : `${payload.cards?.length ?? 0} cards`,
+				);
+				window.dispatchEvent(
+					new CustomEvent('alto-reimport-result', { detail: { ...payload, ...(files ? { files } : {}) } }),
+				);
 				break;
 			}
 
@@ -3,11 +3,15 @@
 //
 // Mount/destroy lifecycle follows HelpOverlay pattern.
 // Dual-path search: synchronous fuzzy for commands, debounced async for card search.
+// Card search input uses the shared search query language (parseSearchQuery);
+// syntax errors show as a hint under the input instead of failing the search.
 //
 // Requirements: CMDK-01, CMDK-03, CMDK-04, CMDK-05, CMDK-06
 
 import '../styles/command-palette.css';
 import { COMBOBOX_ATTRS } from '../accessibility/combobox-contract';
+import { parseSearchQuery } from '../providers/search-query';
+import { isCancelledError } from '../worker/protocol';
 import type { PaletteCommand } from './CommandRegistry';
 import { type CommandRegistry, getRecentCommands, pushRecent } from './CommandRegistry';
 
@@ -25,7 +29,7 @@ export interface PaletteSearchResult {
 // Category display order and icons
 // ---------------------------------------------------------------------------
 
-const CATEGORY_ORDER: ReadonlyArray<string> = ['Recents', 'Views', 'Actions', 'Cards', 'Settings', 'Presets'];
+const CATEGORY_ORDER: ReadonlyArray<string> = ['Recents', 'Views', 'Actions', 'Cards', 'Searches', 'Settings', 'Presets'];
 
 const CATEGORY_ICONS: Record<string, string> = {
 	Recents: '\u23F1', // stopwatch
@@ -60,6 +64,7 @@ export class CommandPalette {
 	// DOM references
 	private _overlayEl: HTMLElement | null = null;
 	private _inputEl: HTMLInputElement | null = null;
+	private _hintEl: HTMLElement | null = null;
 	private _listboxEl: HTMLElement | null = null;
 
 	// State
@@ -121,8 +126,15 @@ export class CommandPalette {
 			listbox.setAttribute(attr, value);
 		}
 
+		// Search syntax hint (hidden until the query has a syntax error)
+		const hint = document.createElement('div');
+		hint.className = 'command-palette__hint';
+		hint.setAttribute('aria-live', 'polite');
+		hint.hidden = true;
+
 		// Assemble DOM
 		card.appendChild(input);
+		card.appendChild(hint);
 		card.appendChild(listbox);
 		overlay.appendChild(card);
 		container.appendChild(overlay);
@@ -130,6 +142,7 @@ export class CommandPalette {
 		// Store references
 		this._overlayEl = overlay;
 		this._inputEl = input;
+		this._hintEl = hint;
 		this._listboxEl = listbox;
 
 		// Backdrop click handler: click on overlay (not card) closes palette
@@ -168,6 +181,7 @@ export class CommandPalette {
 
 		// Clear input
 		this._inputEl.value = '';
+		this._setSyntaxHint(null);
 
 		// Populate with recents + visible commands
 		const recents = getRecentCommands(this._registry);
@@ -231,6 +245,7 @@ export class CommandPalette {
 		// Null out all references
 		this._overlayEl = null;
 		this._inputEl = null;
+		this._hintEl = null;
 		this._listboxEl = null;
 		this._keydownHandler = null;
 		this._inputHandler = null;
@@ -278,6 +293,7 @@ export class CommandPalette {
 		if (this._promptMode) return;
 		// Synchronous path: fuzzy-filter static commands
 		if (query === '') {
+			this._setSyntaxHint(null);
 			// Empty query: show recents + all visible
 			const recents = getRecentCommands(this._registry);
 			const visible = this._registry.getVisible();
@@ -297,36 +313,46 @@ export class CommandPalette {
 			this._cardSearchTimer = null;
 		}
 
-		if (query.length >= 2) {
+		// Card search syntax: report the first problem, skip the search when nothing is searchable
+		const parsed = parseSearchQuery(query);
+		this._setSyntaxHint(parsed.errors[0]?.message ?? null);
+		const searchable = parsed.fts !== '' || parsed.filters.length > 0;
+
+		if (query.length >= 2 && searchable) {
 			this._cardSearchGeneration++;
 			const generation = this._cardSearchGeneration;
 
 			this._cardSearchTimer = setTimeout(() => {
-				void this._searchCards(query, 5).then((results) => {
-					// Race condition guard: discard stale results
-					if (generation !== this._cardSearchGeneration) return;
-					if (!this._visible) return;
-
-					this._pendingCards = results.map((r) => ({
-						id: `card:${r.card.id}`,
-						label: r.card.name,
-						category: 'Cards' as const,
-						execute: () => {
-							// Navigate to card in List view (default best view)
-							// Dispatches a custom event that main.ts can listen to
-							window.dispatchEvent(
-								new CustomEvent('isometry:navigate-to-card', {
-									detail: { cardId: r.card.id },
-								}),
-							);
-						},
-					}));
-
-					// Re-render with current commands + card results
-					const currentQuery = this._inputEl?.value ?? '';
-					const cmds = currentQuery === '' ? this._registry.getVisible() : this._registry.search(currentQuery);
-					this._renderResults(cmds, this._pendingCards);
-				});
+				void this._searchCards(query, 5)
+					.then((results) => {
+						// Race condition guard: discard stale results
+						if (generation !== this._cardSearchGeneration) return;
+						if (!this._visible) return;
+
+						this._pendingCards = results.map((r) => ({
+							id: `card:${r.card.id}`,
+							label: r.card.name,
+							category: 'Cards' as const,
+							execute: () => {
+								// Navigate to card in List view (default best view)
+								// Dispatches a custom event that main.ts can listen to
+								window.dispatchEvent(
+									new CustomEvent('isometry:navigate-to-card', {
+										detail: { cardId: r.card.id },
+									}),
+								);
+							},
+						}));
+
+						// Re-render with current commands + card results
+						const currentQuery = this._inputEl?.value ?? '';
+						const cmds = currentQuery === '' ? this._registry.getVisible() : this._registry.search(currentQuery);
+						this._renderResults(cmds, this._pendingCards);
+					})
+					.catch((err: unknown) => {
+						// Superseded by a newer keystroke — the newer search owns the results
+						if (!isCancelledError(err)) console.error('[CommandPalette] card search failed:', err);
+					});
 			}, 200);
 		} else {
 			this._pendingCards = [];
@@ -390,6 +416,13 @@ export class CommandPalette {
 		}
 	}
 
+	/** Show (or hide, when null) the card search syntax hint. */
+	private _setSyntaxHint(message: string | null): void {
+		if (!this._hintEl) return;
+		this._hintEl.textContent = message ?? '';
+		this._hintEl.hidden = message === null;
+	}
+
 	private _exitPromptMode(): void {
 		this._promptMode = false;
 		this._promptOnConfirm = null;
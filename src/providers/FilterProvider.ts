@@ -4,7 +4,9 @@
 // Design:
 //   - Internal state: filters array + searchQuery (never entity data)
 //   - _axisFilters Map: per-axis selected values for Phase 24 filter dropdowns
+//   - _filterTree: optional nested AND/OR/NOT group, AND-joined with everything else
 //   - compile() is synchronous and pure — no side effects, no async
+//     (relative date filters resolve against the current date at compile time)
 //   - Runtime allowlist validation in both addFilter() and compile()
 //     (compile-time validation for typed callers, runtime for JSON-restored state)
 //   - Subscriber notifications batched via queueMicrotask (CONTEXT.md locked decision)
@@ -13,23 +15,28 @@
 //
 // Requirements: PROV-01, PROV-02, PROV-11, FILT-03, FILT-05
 
+import {
+	compileFilterState,
+	type FilterState,
+	isFilterGroup,
+	isFilterState,
+	isGroupNode,
+	validateFilterState,
+	validateFilterTree,
+} from '../database/queries/filter-sql';
 import { validateFilterField, validateOperator } from './allowlist';
-import type { CompiledFilter, Filter, FilterField, FilterOperator, MembershipFilter, PersistableProvider, RangeFilter } from './types';
-
-// ---------------------------------------------------------------------------
-// Internal state shape
-// ---------------------------------------------------------------------------
-
-interface FilterState {
-	filters: Filter[];
-	searchQuery: string | null;
-	/** Phase 24 — axis filter values per field. Optional for backward compat. */
-	axisFilters?: Record<string, string[]>;
-	/** Phase 66 — range filter min/max pairs per field. Optional for backward compat. */
-	rangeFilters?: Record<string, RangeFilter>;
-	/** Phase 138 — multi-field OR-semantics membership filter. Optional for backward compat. */
-	membershipFilter?: MembershipFilter | null;
-}
+import { isRelativeDateExpr } from './relative-dates';
+import type {
+	CompiledFilter,
+	Filter,
+	FilterGroup,
+	MembershipFilter,
+	PersistableProvider,
+	RangeFilter,
+	RelativeDateExpr,
+} from './types';
+
+export { MAX_FILTER_TREE_DEPTH } from '../database/queries/filter-sql';
 
 // ---------------------------------------------------------------------------
 // FilterProvider
@@ -50,8 +57,12 @@ export class FilterProvider implements PersistableProvider {
 	private _axisFilters: Map<string, string[]> = new Map();
 	/** Phase 66 — range filter min/max pairs for histogram scrubbers (LTPB-01) */
 	private _rangeFilters: Map<string, RangeFilter> = new Map();
+	/** Relative date filters per time field, resolved at compile() time */
+	private _relativeDateFilters: Map<string, RelativeDateExpr> = new Map();
 	/** Phase 138 — multi-field OR-semantics membership filter (TFLT-03) */
 	private _membershipFilter: MembershipFilter | null = null;
+	/** Nested AND/OR/NOT filter group edited by the filter builder */
+	private _filterTree: FilterGroup | null = null;
 
 	private readonly _subscribers = new Set<() => void>();
 	private _pendingNotify = false;
@@ -93,7 +104,9 @@ export class FilterProvider implements PersistableProvider {
 			this._searchQuery !== null ||
 			this._axisFilters.size > 0 ||
 			this._rangeFilters.size > 0 ||
-			this._membershipFilter !== null
+			this._relativeDateFilters.size > 0 ||
+			this._membershipFilter !== null ||
+			this.hasFilterTree()
 		);
 	}
 
@@ -106,7 +119,9 @@ export class FilterProvider implements PersistableProvider {
 		this._searchQuery = null;
 		this._axisFilters.clear();
 		this._rangeFilters.clear();
+		this._relativeDateFilters.clear();
 		this._membershipFilter = null;
+		this._filterTree = null;
 		this._scheduleNotify();
 	}
 
@@ -205,6 +220,58 @@ export class FilterProvider implements PersistableProvider {
 		return this._rangeFilters.has(field);
 	}
 
+	// ---------------------------------------------------------------------------
+	// Relative date filter API
+	// ---------------------------------------------------------------------------
+
+	/**
+	 * Set a relative date filter ("last 14 days", "next quarter", "overdue") for
+	 * a time field. Stored symbolically and resolved at compile() time, so
+	 * persisted state stays relative. Replaces any existing relative filter for
+	 * the field; null removes it.
+	 *
+	 * @throws {Error} "SQL safety violation: ..." for unknown field
+	 * @throws {Error} if the expression is malformed
+	 */
+	setRelativeDateFilter(field: string, expr: RelativeDateExpr | null): void {
+		validateFilterField(field);
+		if (expr === null) {
+			this._relativeDateFilters.delete(field);
+		} else {
+			if (!isRelativeDateExpr(expr)) {
+				throw new Error('[FilterProvider] setRelativeDateFilter: invalid relative date expression');
+			}
+			this._relativeDateFilters.set(field, { ...expr });
+		}
+		this._scheduleNotify();
+	}
+
+	/**
+	 * Returns a copy of the relative date filter for a field, or null when none is set.
+	 */
+	getRelativeDateFilter(field: string): RelativeDateExpr | null {
+		const expr = this._relativeDateFilters.get(field);
+		return expr ? { ...expr } : null;
+	}
+
+	/**
+	 * Remove the relative date filter for a single field.
+	 *
+	 * @throws {Error} "SQL safety violation: ..." for unknown field
+	 */
+	clearRelativeDateFilter(field: string): void {
+		validateFilterField(field);
+		this._relativeDateFilters.delete(field);
+		this._scheduleNotify();
+	}
+
+	/**
+	 * Returns true when a relative date filter is set for the given field.
+	 */
+	hasRelativeDateFilter(field: string): boolean {
+		return this._relativeDateFilters.has(field);
+	}
+
 	// ---------------------------------------------------------------------------
 	// Phase 138 — Membership filter API (TFLT-03)
 	// ---------------------------------------------------------------------------
@@ -247,6 +314,52 @@ export class FilterProvider implements PersistableProvider {
 		return this._membershipFilter !== null;
 	}
 
+	// ---------------------------------------------------------------------------
+	// Boolean filter tree API
+	// ---------------------------------------------------------------------------
+
+	/**
+	 * Replace the boolean filter tree. The tree is AND-joined with all other
+	 * filter state. Every condition is validated against the allowlist before
+	 * anything is stored; empty groups are kept (the builder edits them) but
+	 * compile to nothing.
+	 *
+	 * @throws {Error} "SQL safety violation: ..." for unknown field or operator
+	 * @throws {Error} if the tree is malformed or nested deeper than MAX_FILTER_TREE_DEPTH
+	 */
+	setFilterTree(tree: FilterGroup | null): void {
+		if (tree !== null) {
+			if (!isFilterGroup(tree)) {
+				throw new Error('[FilterProvider] setFilterTree: invalid filter tree');
+			}
+			validateFilterTree(tree);
+		}
+		this._filterTree = tree === null ? null : cloneFilterGroup(tree);
+		this._scheduleNotify();
+	}
+
+	/**
+	 * Returns a deep copy of the filter tree, or null when none is set.
+	 */
+	getFilterTree(): FilterGroup | null {
+		return this._filterTree === null ? null : cloneFilterGroup(this._filterTree);
+	}
+
+	/**
+	 * Remove the filter tree.
+	 */
+	clearFilterTree(): void {
+		this._filterTree = null;
+		this._scheduleNotify();
+	}
+
+	/**
+	 * Returns true when the filter tree contains at least one condition.
+	 */
+	hasFilterTree(): boolean {
+		return this._filterTree !== null && countFilterConditions(this._filterTree) > 0;
+	}
+
 	/**
 	 * Set or clear the full-text search query.
 	 *
@@ -276,88 +389,16 @@ export class FilterProvider implements PersistableProvider {
 	 *   - `where` always starts with `deleted_at IS NULL`
 	 *   - All user values are in `params` (never interpolated into `where`)
 	 *   - Field names are interpolated only after allowlist validation
+	 *   - Custom property fields (prop_<key>) compile to card_properties subqueries via fieldExpr()
 	 *
 	 * Also validates field/operator at compile time — handles the case where
 	 * state was restored from JSON and may contain values that bypassed addFilter().
+	 * Compilation itself lives in compileFilterState() (database/queries/filter-sql).
 	 *
 	 * @throws {Error} "SQL safety violation: ..." for any invalid field or operator
 	 */
 	compile(): CompiledFilter {
-		const clauses: string[] = ['deleted_at IS NULL'];
-		const params: unknown[] = [];
-
-		for (const filter of this._filters) {
-			// Runtime validation — handles JSON-restored or otherwise untrusted state
-			validateFilterField(filter.field as string);
-			validateOperator(filter.operator as string);
-
-			const { clause, filterParams } = compileOperator(filter.field, filter.operator, filter.value);
-			clauses.push(clause);
-			params.push(...filterParams);
-		}
-
-		// Phase 24 — axis filters: compile after regular filters, before FTS
-		// Deterministic order: iterate insertion order of the Map
-		for (const [field, values] of this._axisFilters.entries()) {
-			if (values.length === 0) continue; // defensive: empty entries should not exist
-			// Runtime validation — guards JSON-restored state
-			validateFilterField(field);
-			const placeholders = values.map(() => '?').join(', ');
-			clauses.push(`${field} IN (${placeholders})`);
-			params.push(...values);
-		}
-
-		// Phase 66 — range filters: compile after axis filters, before FTS
-		for (const [field, range] of this._rangeFilters.entries()) {
-			// Runtime validation — guards JSON-restored state
-			validateFilterField(field);
-			if (range.min !== null && range.min !== undefined) {
-				clauses.push(`${field} >= ?`);
-				params.push(range.min);
-			}
-			if (range.max !== null && range.max !== undefined) {
-				clauses.push(`${field} <= ?`);
-				params.push(range.max);
-			}
-		}
-
-		// Phase 138 — membership filter: OR-semantics across multiple fields, compile after range filters
-		if (this._membershipFilter !== null) {
-			const orParts: string[] = [];
-			const orParams: unknown[] = [];
-			for (const field of this._membershipFilter.fields) {
-				validateFilterField(field);
-				const conditions: string[] = [];
-				if (this._membershipFilter.min !== null && this._membershipFilter.min !== undefined) {
-					conditions.push(`${field} >= ?`);
-					orParams.push(this._membershipFilter.min);
-				}
-				if (this._membershipFilter.max !== null && this._membershipFilter.max !== undefined) {
-					conditions.push(`${field} <= ?`);
-					orParams.push(this._membershipFilter.max);
-				}
-				if (conditions.length > 0) {
-					orParts.push(`(${conditions.join(' AND ')})`);
-				}
-			}
-			if (orParts.length > 0) {
-				clauses.push(`(${orParts.join(' OR ')})`);
-				params.push(...orParams);
-			}
-		}
-
-		// FTS search — uses rowid (not id) per D-004 and Pitfall 5
-		if (this._searchQuery !== null && this._searchQuery !== '') {
-			clauses.push('rowid IN (SELECT rowid FROM cards_fts WHERE cards_fts MATCH ?)');
-			const ftsQuery = this._searchQuery
-				.trim()
-				.split(/\s+/)
-				.map((t) => `"${t}"*`)
-				.join(' ');
-			params.push(ftsQuery);
-		}
-
-		return { where: clauses.join(' AND '), params };
+		return compileFilterState(this._state());
 	}
 
 	// ---------------------------------------------------------------------------
@@ -397,14 +438,20 @@ export class FilterProvider implements PersistableProvider {
 	 * Phase 24: includes axisFilters as Record<string, string[]>.
 	 */
 	toJSON(): string {
-		const state: FilterState = {
+		return JSON.stringify(this._state());
+	}
+
+	/** Snapshot of the current state in its serialized shape (shared by toJSON() and compile()). */
+	private _state(): FilterState {
+		return {
 			filters: [...this._filters],
 			searchQuery: this._searchQuery,
 			axisFilters: Object.fromEntries(this._axisFilters),
 			rangeFilters: Object.fromEntries(this._rangeFilters),
+			relativeDateFilters: Object.fromEntries(this._relativeDateFilters),
 			membershipFilter: this._membershipFilter,
+			filterTree: this._filterTree,
 		};
-		return JSON.stringify(state);
 	}
 
 	/**
@@ -421,10 +468,7 @@ export class FilterProvider implements PersistableProvider {
 		}
 
 		// Validate all restored filters before applying (no partial state)
-		for (const f of state.filters) {
-			validateFilterField(f.field as string);
-			validateOperator(f.operator as string);
-		}
+		validateFilterState(state);
 
 		this._filters = [...state.filters];
 		this._searchQuery = state.searchQuery;
@@ -445,11 +489,34 @@ export class FilterProvider implements PersistableProvider {
 			}
 		}
 
+		// Restore relative date filters — default to empty Map if missing (backward compat)
+		this._relativeDateFilters.clear();
+		if (state.relativeDateFilters !== undefined) {
+			for (const [field, expr] of Object.entries(state.relativeDateFilters)) {
+				this._relativeDateFilters.set(field, { ...expr });
+			}
+		}
+
 		// Phase 138: restore membership filter — default to null if missing (backward compat)
 		this._membershipFilter = state.membershipFilter ?? null;
+
+		// Filter tree — default to null if missing (backward compat)
+		this._filterTree = state.filterTree ? cloneFilterGroup(state.filterTree) : null;
 		// Do NOT notify subscribers — per CONTEXT.md "skip animation on restore"
 	}
 
+	/**
+	 * Replace the whole filter state and notify subscribers.
+	 * Used for user-initiated restores (e.g. running a saved search), unlike
+	 * setState() which is silent for StateManager boot restore.
+	 *
+	 * @throws {Error} if the state shape is corrupt or contains invalid fields/operators
+	 */
+	applyState(state: unknown): void {
+		this.setState(state);
+		this._scheduleNotify();
+	}
+
 	/**
 	 * Reset to empty state (no filters, no search, no axis filters).
 	 * Called by StateManager when JSON restoration fails.
@@ -459,7 +526,9 @@ export class FilterProvider implements PersistableProvider {
 		this._searchQuery = null;
 		this._axisFilters.clear();
 		this._rangeFilters.clear();
+		this._relativeDateFilters.clear();
 		this._membershipFilter = null;
+		this._filterTree = null;
 	}
 
 	// ---------------------------------------------------------------------------
@@ -486,115 +555,25 @@ export class FilterProvider implements PersistableProvider {
 }
 
 // ---------------------------------------------------------------------------
-// Pure SQL compilation helpers
+// Filter tree helpers
 // ---------------------------------------------------------------------------
 
 /**
- * Compile a single filter condition to a SQL clause + params pair.
- * Field has already been validated by addFilter() and compile().
+ * Count the conditions in a filter tree (groups themselves are not counted).
  */
-function compileOperator(
-	field: FilterField,
-	operator: FilterOperator,
-	value: unknown,
-): { clause: string; filterParams: unknown[] } {
-	switch (operator) {
-		case 'eq':
-			return { clause: `${field} = ?`, filterParams: [value] };
-
-		case 'neq':
-			return { clause: `${field} != ?`, filterParams: [value] };
-
-		case 'gt':
-			return { clause: `${field} > ?`, filterParams: [value] };
-
-		case 'gte':
-			return { clause: `${field} >= ?`, filterParams: [value] };
-
-		case 'lt':
-			return { clause: `${field} < ?`, filterParams: [value] };
-
-		case 'lte':
-			return { clause: `${field} <= ?`, filterParams: [value] };
-
-		case 'contains':
-			return { clause: `${field} LIKE ?`, filterParams: [`%${value as string}%`] };
-
-		case 'startsWith':
-			return { clause: `${field} LIKE ?`, filterParams: [`${value as string}%`] };
-
-		case 'in': {
-			const values = value as unknown[];
-			const placeholders = values.map(() => '?').join(', ');
-			return { clause: `${field} IN (${placeholders})`, filterParams: values };
-		}
-
-		case 'isNull':
-			return { clause: `${field} IS NULL`, filterParams: [] };
-
-		case 'isNotNull':
-			return { clause: `${field} IS NOT NULL`, filterParams: [] };
-
-		default: {
-			// TypeScript should make this unreachable after validateOperator
-			const _exhaustive: never = operator;
-			throw new Error(`SQL safety violation: unhandled operator "${_exhaustive as string}"`);
-		}
+export function countFilterConditions(group: FilterGroup): number {
+	let count = 0;
+	for (const child of group.children) {
+		count += isGroupNode(child) ? countFilterConditions(child) : 1;
 	}
+	return count;
 }
 
-// ---------------------------------------------------------------------------
-// Type guard for state restoration
-// ---------------------------------------------------------------------------
-
-function isFilterState(value: unknown): value is FilterState {
-	if (typeof value !== 'object' || value === null) return false;
-	const obj = value as Record<string, unknown>;
-	if (!Array.isArray(obj['filters'])) return false;
-	if (obj['searchQuery'] !== null && typeof obj['searchQuery'] !== 'string') return false;
-
-	for (const item of obj['filters'] as unknown[]) {
-		if (typeof item !== 'object' || item === null) return false;
-		const f = item as Record<string, unknown>;
-		if (typeof f['field'] !== 'string') return false;
-		if (typeof f['operator'] !== 'string') return false;
-		if (!('value' in f)) return false;
-	}
-
-	// Phase 24: validate optional axisFilters — if present, must be Record<string, string[]>
-	if ('axisFilters' in obj && obj['axisFilters'] !== undefined) {
-		const af = obj['axisFilters'];
-		if (typeof af !== 'object' || af === null || Array.isArray(af)) return false;
-		for (const values of Object.values(af as Record<string, unknown>)) {
-			if (!Array.isArray(values)) return false;
-			for (const v of values as unknown[]) {
-				if (typeof v !== 'string') return false;
-			}
-		}
-	}
-
-	// Phase 66: validate optional rangeFilters — if present, must be Record<string, {min, max}>
-	if ('rangeFilters' in obj && obj['rangeFilters'] !== undefined) {
-		const rf = obj['rangeFilters'];
-		if (typeof rf !== 'object' || rf === null || Array.isArray(rf)) return false;
-		for (const entry of Object.values(rf as Record<string, unknown>)) {
-			if (typeof entry !== 'object' || entry === null || Array.isArray(entry)) return false;
-			const e = entry as Record<string, unknown>;
-			if (!('min' in e) || !('max' in e)) return false;
-		}
-	}
-
-	// Phase 138: validate optional membershipFilter — if present (and non-null), must have fields (string[]), min, max
-	if ('membershipFilter' in obj && obj['membershipFilter'] !== undefined && obj['membershipFilter'] !== null) {
-		const mf = obj['membershipFilter'];
-		if (typeof mf !== 'object' || mf === null || Array.isArray(mf)) return false;
-		const m = mf as Record<string, unknown>;
-		if (!Array.isArray(m['fields'])) return false;
-		for (const f of m['fields'] as unknown[]) {
-			if (typeof f !== 'string') return false;
-		}
-		if (!('min' in m) || !('max' in m)) return false;
-	}
-
-	return true;
+function cloneFilterGroup(group: FilterGroup): FilterGroup {
+	return {
+		kind: 'group',
+		combinator: group.combinator,
+		negate: group.negate,
+		children: group.children.map((child) => (isGroupNode(child) ? cloneFilterGroup(child) : { ...child })),
+	};
 }
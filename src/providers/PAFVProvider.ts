@@ -84,6 +84,7 @@ const VIEW_DEFAULTS: Record<ViewType, PAFVState> = {
 		rowAxes: [{ field: 'folder', direction: 'asc' }],
 		sortOverrides: [],
 	},
+	map: { viewType: 'map', xAxis: null, yAxis: null, groupBy: null, colAxes: [], rowAxes: [] },
 };
 
 const DEFAULT_VIEW_TYPE: ViewType = 'list';
@@ -9,8 +9,13 @@
 //     ensuring schema is available synchronously after `await bridge.isReady`.
 //
 // Requirements: SCHM-03, SCHM-04, SCHM-05, SCHM-07
+//
+// Custom properties: user-defined card fields (card_properties EAV) are exposed
+// as virtual prop_<key> columns alongside PRAGMA columns and graph metrics.
+// Formula fields (computed expressions) are exposed the same way as fx_<name>.
 
-import type { ColumnInfo, LatchFamily } from '../worker/protocol';
+import type { ColumnInfo, LatchFamily, WorkerReadyMessage } from '../worker/protocol';
+import { formulaColumns } from './formulas';
 
 /**
  * SchemaProvider manages runtime database schema metadata.
@@ -41,6 +46,12 @@ export class SchemaProvider {
 	// Phase 116: Graph metric columns (dynamically injected after graph:compute)
 	private _graphMetricColumns: ColumnInfo[] = [];
 
+	// Custom card properties (virtual prop_<key> columns, replaced on every property change)
+	private _propertyColumns: ColumnInfo[] = [];
+
+	// Formula fields (virtual fx_<name> columns, replaced on every formula change)
+	private _formulaColumns: ColumnInfo[] = [];
+
 	// -----------------------------------------------------------------------
 	// Initialization
 	// -----------------------------------------------------------------------
@@ -51,11 +62,16 @@ export class SchemaProvider {
 	 *
 	 * Called by WorkerBridge onSchema callback, which fires BEFORE isReady resolves.
 	 */
-	initialize(schema: { cards: ColumnInfo[]; connections: ColumnInfo[] }): void {
+	initialize(schema: WorkerReadyMessage['schema']): void {
 		this._cards = [...schema.cards];
 		this._connections = [...schema.connections];
+		this._propertyColumns = [...(schema.properties ?? [])];
+		this._formulaColumns = formulaColumns(schema.formulas ?? []);
 		this._validCardColumns = new Set(this._cards.map((c) => c.name));
 		this._validConnectionColumns = new Set(this._connections.map((c) => c.name));
+		for (const col of [...this._propertyColumns, ...this._formulaColumns]) {
+			this._validCardColumns.add(col.name);
+		}
 		this._initialized = true;
 		this._scheduleNotify();
 	}
@@ -111,7 +127,11 @@ export class SchemaProvider {
 	 * ignoring any user override.
 	 */
 	getHeuristicFamily(field: string): LatchFamily | undefined {
-		return this._cards.find((c) => c.name === field)?.latchFamily;
+		const column =
+			this._cards.find((c) => c.name === field) ??
+			this._propertyColumns.find((c) => c.name === field) ??
+			this._formulaColumns.find((c) => c.name === field);
+		return column?.latchFamily;
 	}
 
 	/**
@@ -189,6 +209,57 @@ export class SchemaProvider {
 		return this._graphMetricColumns.length > 0;
 	}
 
+	// -----------------------------------------------------------------------
+	// Custom property columns
+	// -----------------------------------------------------------------------
+
+	/**
+	 * Replace the set of custom property columns (prop_<key>).
+	 * Called after property:define / property:delete and after imports that
+	 * may have created new properties. Built with propertyColumnInfo().
+	 */
+	setPropertyColumns(columns: readonly ColumnInfo[]): void {
+		for (const col of this._propertyColumns) {
+			this._validCardColumns.delete(col.name);
+		}
+		this._propertyColumns = [...columns];
+		for (const col of this._propertyColumns) {
+			this._validCardColumns.add(col.name);
+		}
+		this._scheduleNotify();
+	}
+
+	/** Returns the current custom property columns (readonly copy). */
+	getPropertyColumns(): readonly ColumnInfo[] {
+		return [...this._propertyColumns];
+	}
+
+	// -----------------------------------------------------------------------
+	// Formula columns
+	// -----------------------------------------------------------------------
+
+	/**
+	 * Replace the set of formula columns (fx_<name>).
+	 * Called after formula:define / formula:delete and after imports or
+	 * property changes that can make a formula (un)compilable.
+	 * Built with formulaColumns().
+	 */
+	setFormulaColumns(columns: readonly ColumnInfo[]): void {
+		for (const col of this._formulaColumns) {
+			this._validCardColumns.delete(col.name);
+		}
+		this._formulaColumns = [...columns];
+		for (const col of this._formulaColumns) {
+			this._validCardColumns.add(col.name);
+		}
+		this._scheduleNotify();
+	}
+
+	/** Returns the current formula columns (readonly copy). */
+	getFormulaColumns(): readonly ColumnInfo[] {
+		return [...this._formulaColumns];
+	}
+
 	// -----------------------------------------------------------------------
 	// Column accessors
 	// -----------------------------------------------------------------------
@@ -219,8 +290,8 @@ export class SchemaProvider {
 	 */
 	getFilterableColumns(): readonly ColumnInfo[] {
 		const base = this._cards.filter((c) => !this._disabledFields.has(c.name));
-		const metrics = this._graphMetricColumns.filter((c) => !this._disabledFields.has(c.name));
-		return [...base, ...metrics];
+		const virtual = this._virtualColumns().filter((c) => !this._disabledFields.has(c.name));
+		return [...base, ...virtual];
 	}
 
 	/**
@@ -234,13 +305,13 @@ export class SchemaProvider {
 				...c,
 				latchFamily: this._latchOverrides.get(c.name) ?? c.latchFamily,
 			}));
-		const metrics = this._graphMetricColumns
+		const virtual = this._virtualColumns()
 			.filter((c) => !this._disabledFields.has(c.name))
 			.map((c) => ({
 				...c,
 				latchFamily: this._latchOverrides.get(c.name) ?? c.latchFamily,
 			}));
-		return [...base, ...metrics];
+		return [...base, ...virtual];
 	}
 
 	/**
@@ -252,11 +323,11 @@ export class SchemaProvider {
 			...c,
 			latchFamily: this._latchOverrides.get(c.name) ?? c.latchFamily,
 		}));
-		const metrics = this._graphMetricColumns.map((c) => ({
+		const virtual = this._virtualColumns().map((c) => ({
 			...c,
 			latchFamily: this._latchOverrides.get(c.name) ?? c.latchFamily,
 		}));
-		return [...base, ...metrics];
+		return [...base, ...virtual];
 	}
 
 	/**
@@ -265,15 +336,15 @@ export class SchemaProvider {
 	 */
 	getNumericColumns(): readonly ColumnInfo[] {
 		const base = this._cards.filter((c) => c.isNumeric && !this._disabledFields.has(c.name));
-		const metrics = this._graphMetricColumns.filter((c) => c.isNumeric && !this._disabledFields.has(c.name));
-		return [...base, ...metrics];
+		const virtual = this._virtualColumns().filter((c) => c.isNumeric && !this._disabledFields.has(c.name));
+		return [...base, ...virtual];
 	}
 
 	/**
 	 * Returns all card columns belonging to the specified LATCH family.
 	 */
 	getFieldsByFamily(family: LatchFamily): readonly ColumnInfo[] {
-		const allCols = [...this._cards, ...this._graphMetricColumns];
+		const allCols = [...this._cards, ...this._virtualColumns()];
 		return allCols.filter((c) => {
 			if (this._disabledFields.has(c.name)) return false;
 			const effective = this._latchOverrides.get(c.name) ?? c.latchFamily;
@@ -287,7 +358,7 @@ export class SchemaProvider {
 	 */
 	getLatchFamilies(): Map<LatchFamily, string[]> {
 		const result = new Map<LatchFamily, string[]>();
-		const allCols = [...this._cards, ...this._graphMetricColumns];
+		const allCols = [...this._cards, ...this._virtualColumns()];
 		for (const col of allCols) {
 			if (this._disabledFields.has(col.name)) continue;
 			const effective = this._latchOverrides.get(col.name) ?? col.latchFamily;
@@ -320,6 +391,11 @@ export class SchemaProvider {
 	// Private
 	// -----------------------------------------------------------------------
 
+	/** Non-PRAGMA card columns: graph metrics, then custom properties, then formulas. */
+	private _virtualColumns(): ColumnInfo[] {
+		return [...this._graphMetricColumns, ...this._propertyColumns, ...this._formulaColumns];
+	}
+
 	/**
 	 * Schedule subscriber notification via queueMicrotask.
 	 * Multiple mutations within the same microtask are batched into one notification.
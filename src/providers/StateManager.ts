@@ -419,6 +419,7 @@ export class StateManager {
 	 * - filters[]: entries with unknown field are dropped
 	 * - axisFilters{}: keys referencing unknown columns are dropped
 	 * - rangeFilters{}: keys referencing unknown columns are dropped
+	 * - relativeDateFilters{}: keys referencing unknown columns are dropped (when present)
 	 */
 	private _migrateFilterState(state: unknown): unknown {
 		const s = state as Record<string, unknown>;
@@ -445,7 +446,13 @@ export class StateManager {
 				: {};
 		const rangeFilters = Object.fromEntries(Object.entries(rawRangeFilters).filter(([k]) => isValid(k)));
 
-		return { ...s, filters, axisFilters, rangeFilters };
+		const migrated: Record<string, unknown> = { ...s, filters, axisFilters, rangeFilters };
+		if (typeof s['relativeDateFilters'] === 'object' && s['relativeDateFilters'] !== null) {
+			migrated['relativeDateFilters'] = Object.fromEntries(
+				Object.entries(s['relativeDateFilters'] as Record<string, unknown>).filter(([k]) => isValid(k)),
+			);
+		}
+		return migrated;
 	}
 
 	/**
@@ -34,7 +34,7 @@ export interface DefaultMapping {
 
 /**
  * Frozen static Map from source type key to DefaultMapping.
- * Covers all 9 SourceType values, 11 alto_index_* dataset-specific entries,
+ * Covers all 14 SourceType values, 11 alto_index_* dataset-specific entries,
  * plus 'alto_index' catch-all (D-02, D-06).
  *
  * alto_index_* entries are matched exactly before the startsWith('alto_index')
@@ -45,6 +45,12 @@ export const VIEW_DEFAULTS_REGISTRY: ReadonlyMap<string, DefaultMapping> = Objec
 		// --- Format-based source types ---
 		['apple_notes', { colAxes: ['folder', 'card_type'], rowAxes: ['title', 'name'] }],
 		['markdown', { colAxes: ['folder', 'card_type'], rowAxes: ['title', 'name'] }],
+		['obsidian', { colAxes: ['folder', 'card_type'], rowAxes: ['title', 'name'] }],
+		['opml', { colAxes: ['folder', 'card_type'], rowAxes: ['title', 'name'] }],
+		['ics', { colAxes: ['folder', 'card_type'], rowAxes: ['title', 'name'] }],
+		['vcard', { colAxes: ['folder', 'card_type'], rowAxes: ['name'] }],
+		['email', { colAxes: ['folder', 'card_type'], rowAxes: ['created_at', 'name'] }],
+		['bookmarks', { colAxes: ['folder_l1', 'folder_l2'], rowAxes: ['name'] }],
 		['excel', { colAxes: ['card_type', 'folder'], rowAxes: ['name', 'title'] }],
 		['csv', { colAxes: ['card_type', 'folder'], rowAxes: ['name', 'title'] }],
 		['json', { colAxes: ['card_type', 'folder'], rowAxes: ['name', 'title'] }],
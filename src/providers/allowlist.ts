@@ -66,6 +66,22 @@ export function setValidColumnNames(names: Set<string> | null): void {
 	_validColumnNames = names;
 }
 
+/**
+ * Module-level numeric column names for Worker-side value typing.
+ * Same role as _validColumnNames, for isNumericFilterField().
+ */
+let _numericColumnNames: Set<string> | null = null;
+
+/**
+ * Wire the numeric (INTEGER/REAL, number property, number formula) column
+ * names for Worker-side isNumericFilterField() without a SchemaProvider.
+ *
+ * Priority: SchemaProvider > numericColumnNames > frozen fallback set.
+ */
+export function setNumericColumnNames(names: Set<string> | null): void {
+	_numericColumnNames = names;
+}
+
 // ---------------------------------------------------------------------------
 // Frozen allowlist sets
 // ---------------------------------------------------------------------------
@@ -95,6 +111,13 @@ export const ALLOWED_FILTER_FIELDS: ReadonlySet<FilterField> = Object.freeze(
 	]),
 );
 
+/**
+ * Numeric columns among ALLOWED_FILTER_FIELDS (boot-time fallback for isNumericFilterField).
+ */
+export const NUMERIC_FILTER_FIELDS: ReadonlySet<FilterField> = Object.freeze(
+	new Set<FilterField>(['latitude', 'longitude', 'priority', 'sort_order']),
+);
+
 /**
  * Symbolic operator names allowed in filter conditions.
  * See types.ts FilterOperator for the corresponding compile-time union.
@@ -130,6 +153,7 @@ export const ALLOWED_AXIS_FIELDS: ReadonlySet<AxisField> = Object.freeze(
 		'priority',
 		'sort_order',
 		'name',
+		'location_name',
 	]),
 );
 
@@ -159,6 +183,24 @@ export function isValidFilterField(field: string): field is FilterField {
 	return (ALLOWED_FILTER_FIELDS as Set<string>).has(field);
 }
 
+/**
+ * Returns true if `field` holds numbers, so typed filter values should bind as
+ * numbers. Property and formula fields have no column affinity in SQLite —
+ * text '2024' never equals the number 2024 — so only numeric fields may coerce.
+ *
+ * Delegates to SchemaProvider.getNumericColumns() when wired, then the
+ * Worker-side numeric set, then NUMERIC_FILTER_FIELDS.
+ */
+export function isNumericFilterField(field: string): boolean {
+	if (_schemaProvider) {
+		return _schemaProvider.getNumericColumns().some((c) => c.name === field);
+	}
+	if (_numericColumnNames) {
+		return _numericColumnNames.has(field);
+	}
+	return (NUMERIC_FILTER_FIELDS as Set<string>).has(field);
+}
+
 /**
  * Type guard: returns true if `op` is an allowlisted filter operator.
  *
@@ -0,0 +1,596 @@
+// Isometry v5 — Formula Fields
+// A small, safe expression language for spreadsheet-style computed fields,
+// compiled to SQL and exposed as virtual `fx_<name>` columns.
+//
+// Syntax:
+//   days_open = julianday(coalesce(completed_at, now)) - julianday(created_at)
+//   42, 1.5, 'text' ('' escapes a quote), true, false, null
+//   now / today            current UTC timestamp / date (ISO 8601 text)
+//   + - * / %              arithmetic ('/' is always real division)
+//   ||                     text concatenation
+//   = == != <> < <= > >=   comparison (1 / 0 / null)
+//   and, or, not           boolean logic
+//   fn(a, b, ...)          whitelisted scalar functions (see FUNCTIONS)
+//   created_at, prop_x     card fields, validated against the allowlist
+//
+// Design:
+//   - compileFormula() is a recursive-descent parser emitting fully parenthesized SQL; user
+//     text only reaches SQL as validated numbers, re-quoted string literals,
+//     whitelisted function names and fields returned by the caller's resolver
+//   - Fields are resolved by a callback (the Worker passes one that checks
+//     isValidFilterField and maps prop_<key> through fieldExpr), so this module
+//     does not depend on properties.ts — properties.ts depends on it
+//   - Formulas cannot reference other formulas (no fx_ fields in expressions),
+//     which rules out cycles without a dependency graph
+//   - A formula name may be defined globally and per dataset; formulaExpr()
+//     switches on cards.dataset_id, falling back to the global definition
+//   - The compiled registry is module-level state set on both threads:
+//     Worker (refreshValidColumnNames) and main thread (onSchema / refresh),
+//     the same way allowlist.ts holds the valid column set
+
+import {
+	FORMULA_NAME_RE,
+	type FormulaDefinition,
+	type FormulaDefinitionInput,
+	type FormulaResultType,
+} from '../database/queries/formulas';
+import type { ColumnInfo, LatchFamily } from '../worker/protocol';
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/** A formula whose expression compiled — what the query builders need. */
+export interface CompiledFormula {
+	name: string;
+	label: string;
+	datasetId: string | null;
+	/** Compiled SQL expression (fully parenthesized, references cards.<column>) */
+	sql: string;
+	resultType: FormulaResultType;
+}
+
+/** A stored formula plus its compile result, as listed by formula:list. */
+export interface FormulaInfo extends FormulaDefinition {
+	/** Virtual field name (fx_<name>) */
+	field: string;
+	/** Compiled SQL; null when the expression does not compile */
+	sql: string | null;
+	/** Compile error shown in the Formulas panel; null when the formula is valid */
+	error: string | null;
+}
+
+/**
+ * Maps a field name used in a formula to its SQL expression, or null when the
+ * field is unknown or not allowed.
+ */
+export type FormulaFieldResolver = (name: string) => string | null;
+
+/** Thrown by compileFormula() / parseFormulaDefinition() for invalid input. */
+export class FormulaError extends Error {
+	/** 0-based character offset of the offending token in the expression */
+	readonly position: number;
+
+	constructor(message: string, position: number) {
+		super(message);
+		this.name = 'FormulaError';
+		this.position = position;
+	}
+}
+
+// ---------------------------------------------------------------------------
+// Field names
+// ---------------------------------------------------------------------------
+
+/** Prefix distinguishing formula fields from physical and property columns. */
+export const FORMULA_FIELD_PREFIX = 'fx_';
+
+/** Field name for a formula: 'days_open' → 'fx_days_open'. */
+export function formulaField(name: string): string {
+	return `${FORMULA_FIELD_PREFIX}${name}`;
+}
+
+/**
+ * Returns the formula name for an `fx_<name>` field, or null when `field`
+ * is not a (well-formed) formula field.
+ */
+export function formulaNameOf(field: string): string | null {
+	if (!field.startsWith(FORMULA_FIELD_PREFIX)) return null;
+	const name = field.slice(FORMULA_FIELD_PREFIX.length);
+	return FORMULA_NAME_RE.test(name) ? name : null;
+}
+
+/** True for well-formed `fx_<name>` field names. */
+export function isFormulaField(field: string): boolean {
+	return formulaNameOf(field) !== null;
+}
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/** Longest accepted expression, in characters. */
+const MAX_EXPRESSION_LENGTH = 1000;
+
+/** Deepest accepted nesting of parentheses / unary operators. */
+const MAX_DEPTH = 32;
+
+/** Whitelisted scalar functions: SQL name and accepted argument counts. */
+const FUNCTIONS: Readonly<Record<string, { sql: string; min: number; max: number }>> = {
+	julianday: { sql: 'julianday', min: 1, max: 5 },
+	date: { sql: 'date', min: 1, max: 5 },
+	datetime: { sql: 'datetime', min: 1, max: 5 },
+	strftime: { sql: 'strftime', min: 2, max: 6 },
+	coalesce: { sql: 'coalesce', min: 2, max: 16 },
+	ifnull: { sql: 'ifnull', min: 2, max: 2 },
+	nullif: { sql: 'nullif', min: 2, max: 2 },
+	abs: { sql: 'abs', min: 1, max: 1 },
+	round: { sql: 'round', min: 1, max: 2 },
+	// min/max need 2+ arguments — the one-argument forms are aggregates
+	min: { sql: 'min', min: 2, max: 16 },
+	max: { sql: 'max', min: 2, max: 16 },
+	length: { sql: 'length', min: 1, max: 1 },
+	lower: { sql: 'lower', min: 1, max: 1 },
+	upper: { sql: 'upper', min: 1, max: 1 },
+	trim: { sql: 'trim', min: 1, max: 2 },
+	substr: { sql: 'substr', min: 2, max: 3 },
+	replace: { sql: 'replace', min: 3, max: 3 },
+	instr: { sql: 'instr', min: 2, max: 2 },
+};
+
+/** Conditional functions compiled to CASE: if(cond, then, else). */
+const CONDITIONALS: ReadonlySet<string> = new Set(['if', 'iif']);
+
+const KEYWORD_SQL: Readonly<Record<string, string>> = {
+	true: '1',
+	false: '0',
+	null: 'NULL',
+	now: "strftime('%Y-%m-%dT%H:%M:%SZ', 'now')",
+	today: "date('now')",
+};
+
+const COMPARISON_SQL: Readonly<Record<string, string>> = {
+	'=': '=',
+	'==': '=',
+	'!=': '!=',
+	'<>': '!=',
+	'<': '<',
+	'<=': '<=',
+	'>': '>',
+	'>=': '>=',
+};
+
+/** Operators in match order (two-character operators first). */
+const OPERATORS = ['||', '==', '!=', '<>', '<=', '>=', '=', '<', '>', '+', '-', '*', '/', '%', '(', ')', ','];
+
+const NUMBER_RE = /^(\d+\.?\d*|\.\d+)([eE][+-]?\d+)?/;
+const IDENT_RE = /^[A-Za-z_][A-Za-z0-9_]*/;
+
+// ---------------------------------------------------------------------------
+// Tokenizer
+// ---------------------------------------------------------------------------
+
+type Token =
+	| { kind: 'number'; text: string; position: number }
+	| { kind: 'string'; value: string; position: number }
+	| { kind: 'ident'; text: string; position: number }
+	| { kind: 'op'; text: string; position: number }
+	| { kind: 'end'; position: number };
+
+function tokenize(input: string): Token[] {
+	const tokens: Token[] = [];
+	let i = 0;
+	while (i < input.length) {
+		const ch = input[i]!;
+		if (/\s/.test(ch)) {
+			i++;
+			continue;
+		}
+		const rest = input.slice(i);
+
+		if (ch === "'") {
+			let value = '';
+			let j = i + 1;
+			for (;;) {
+				if (j >= input.length) throw new FormulaError('Unterminated string', i);
+				if (input[j] === "'") {
+					if (input[j + 1] === "'") {
+						value += "'";
+						j += 2;
+						continue;
+					}
+					break;
+				}
+				value += input[j];
+				j++;
+			}
+			tokens.push({ kind: 'string', value, position: i });
+			i = j + 1;
+			continue;
+		}
+
+		const number = NUMBER_RE.exec(rest);
+		if (number) {
+			tokens.push({ kind: 'number', text: number[0], position: i });
+			i += number[0].length;
+			continue;
+		}
+
+		const ident = IDENT_RE.exec(rest);
+		if (ident) {
+			tokens.push({ kind: 'ident', text: ident[0], position: i });
+			i += ident[0].length;
+			continue;
+		}
+
+		const op = OPERATORS.find((o) => rest.startsWith(o));
+		if (op) {
+			tokens.push({ kind: 'op', text: op, position: i });
+			i += op.length;
+			continue;
+		}
+
+		throw new FormulaError(`Unexpected character "${ch}"`, i);
+	}
+	tokens.push({ kind: 'end', position: input.length });
+	return tokens;
+}
+
+// ---------------------------------------------------------------------------
+// Parser + code generation
+// ---------------------------------------------------------------------------
+
+/**
+ * Recursive-descent parser that emits SQL directly (no separate AST pass —
+ * every production maps to exactly one parenthesized SQL fragment).
+ *
+ * Precedence (loosest first): or, and, not, comparison, ||, + -, * / %, unary -.
+ */
+class FormulaParser {
+	private readonly _tokens: Token[];
+	private readonly _resolve: FormulaFieldResolver;
+	private _pos = 0;
+	private _depth = 0;
+
+	constructor(tokens: Token[], resolve: FormulaFieldResolver) {
+		this._tokens = tokens;
+		this._resolve = resolve;
+	}
+
+	parse(): string {
+		if (this._peek().kind === 'end') throw new FormulaError('Formula expression is empty', 0);
+		const sql = this._or();
+		const next = this._peek();
+		if (next.kind !== 'end') throw new FormulaError(`Unexpected ${describe(next)}`, next.position);
+		return sql;
+	}
+
+	private _or(): string {
+		let left = this._and();
+		while (this._keyword('or')) left = `(${left} OR ${this._and()})`;
+		return left;
+	}
+
+	private _and(): string {
+		let left = this._not();
+		while (this._keyword('and')) left = `(${left} AND ${this._not()})`;
+		return left;
+	}
+
+	private _not(): string {
+		if (this._keyword('not')) return this._nested(() => `(NOT ${this._not()})`);
+		return this._comparison();
+	}
+
+	private _comparison(): string {
+		const left = this._concat();
+		const next = this._peek();
+		if (next.kind === 'op' && COMPARISON_SQL[next.text]) {
+			this._pos++;
+			const right = this._concat();
+			const after = this._peek();
+			if (after.kind === 'op' && COMPARISON_SQL[after.text]) {
+				throw new FormulaError('Chained comparisons are not supported; combine them with "and"', after.position);
+			}
+			return `(${left} ${COMPARISON_SQL[next.text]} ${right})`;
+		}
+		return left;
+	}
+
+	private _concat(): string {
+		let left = this._additive();
+		while (this._op('||')) left = `(${left} || ${this._additive()})`;
+		return left;
+	}
+
+	private _additive(): string {
+		let left = this._multiplicative();
+		for (;;) {
+			if (this._op('+')) left = `(${left} + ${this._multiplicative()})`;
+			else if (this._op('-')) left = `(${left} - ${this._multiplicative()})`;
+			else return left;
+		}
+	}
+
+	private _multiplicative(): string {
+		let left = this._unary();
+		for (;;) {
+			if (this._op('*')) left = `(${left} * ${this._unary()})`;
+			else if (this._op('/')) left = `(CAST(${left} AS REAL) / ${this._unary()})`;
+			else if (this._op('%')) left = `(${left} % ${this._unary()})`;
+			else return left;
+		}
+	}
+
+	private _unary(): string {
+		if (this._op('-')) return this._nested(() => `(-${this._unary()})`);
+		return this._primary();
+	}
+
+	private _primary(): string {
+		const token = this._next();
+		switch (token.kind) {
+			case 'number':
+				return token.text;
+			case 'string':
+				return `'${token.value.replace(/'/g, "''")}'`;
+			case 'op':
+				if (token.text === '(') {
+					const inner = this._nested(() => this._or());
+					this._expect(')');
+					return inner;
+				}
+				throw new FormulaError(`Unexpected ${describe(token)}`, token.position);
+			case 'end':
+				throw new FormulaError('Unexpected end of formula', token.position);
+			case 'ident':
+				return this._identifier(token);
+		}
+	}
+
+	private _identifier(token: Extract<Token, { kind: 'ident' }>): string {
+		const lower = token.text.toLowerCase();
+		const next = this._peek();
+
+		if (next.kind === 'op' && next.text === '(') {
+			this._pos++;
+			const args = this._nested(() => this._arguments());
+			if (CONDITIONALS.has(lower)) {
+				if (args.length !== 3) {
+					throw new FormulaError(`${lower}() expects 3 arguments (condition, then, else)`, token.position);
+				}
+				return `(CASE WHEN ${args[0]} THEN ${args[1]} ELSE ${args[2]} END)`;
+			}
+			const fn = FUNCTIONS[lower];
+			if (!fn) throw new FormulaError(`Unknown function "${token.text}"`, token.position);
+			if (args.length < fn.min || args.length > fn.max) {
+				const expected = fn.min === fn.max ? `${fn.min}` : `${fn.min} to ${fn.max}`;
+				throw new FormulaError(`${lower}() expects ${expected} arguments, got ${args.length}`, token.position);
+			}
+			return `${fn.sql}(${args.join(', ')})`;
+		}
+
+		const keyword = KEYWORD_SQL[lower];
+		if (keyword !== undefined) return keyword;
+		if (['and', 'or', 'not'].includes(lower)) {
+			throw new FormulaError(`Unexpected "${token.text}"`, token.position);
+		}
+
+		if (isFormulaField(token.text)) {
+			throw new FormulaError(`Formulas cannot reference other formulas ("${token.text}")`, token.position);
+		}
+		const sql = this._resolve(token.text);
+		if (sql === null) throw new FormulaError(`Unknown field "${token.text}"`, token.position);
+		return sql;
+	}
+
+	private _arguments(): string[] {
+		const args: string[] = [];
+		if (this._op(')')) return args;
+		for (;;) {
+			args.push(this._or());
+			if (this._op(')')) return args;
+			this._expect(',');
+		}
+	}
+
+	private _nested(fn: () => string): string;
+	private _nested(fn: () => string[]): string[];
+	private _nested(fn: () => string | string[]): string | string[] {
+		if (++this._depth > MAX_DEPTH) throw new FormulaError('Formula is nested too deeply', this._peek().position);
+		try {
+			return fn();
+		} finally {
+			this._depth--;
+		}
+	}
+
+	private _peek(): Token {
+		return this._tokens[this._pos]!;
+	}
+
+	private _next(): Token {
+		const token = this._tokens[this._pos]!;
+		if (token.kind !== 'end') this._pos++;
+		return token;
+	}
+
+	private _op(text: string): boolean {
+		const token = this._peek();
+		if (token.kind === 'op' && token.text === text) {
+			this._pos++;
+			return true;
+		}
+		return false;
+	}
+
+	private _keyword(word: string): boolean {
+		const token = this._peek();
+		if (token.kind === 'ident' && token.text.toLowerCase() === word) {
+			this._pos++;
+			return true;
+		}
+		return false;
+	}
+
+	private _expect(text: string): void {
+		const token = this._peek();
+		if (!this._op(text)) throw new FormulaError(`Expected "${text}" but found ${describe(token)}`, token.position);
+	}
+}
+
+function describe(token: Token): string {
+	switch (token.kind) {
+		case 'end':
+			return 'end of formula';
+		case 'string':
+			return 'text';
+		default:
+			return `"${token.text}"`;
+	}
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/**
+ * Compile a formula expression to a SQL expression.
+ *
+ * @param expression - Formula source (right-hand side of `name = ...`)
+ * @param resolveField - Maps allowed field names to SQL; return null to reject
+ * @throws {FormulaError} with the offending position on any syntax or field error
+ *
+ * @example
+ * compileFormula('julianday(now) - julianday(created_at)', (f) => `cards.${f}`)
+ * // → "(julianday(strftime('%Y-%m-%dT%H:%M:%SZ', 'now')) - julianday(cards.created_at))"
+ */
+export function compileFormula(expression: string, resolveField: FormulaFieldResolver): string {
+	if (expression.length > MAX_EXPRESSION_LENGTH) {
+		throw new FormulaError(`Formula is longer than ${MAX_EXPRESSION_LENGTH} characters`, MAX_EXPRESSION_LENGTH);
+	}
+	return new FormulaParser(tokenize(expression), resolveField).parse();
+}
+
+/**
+ * Split a `name = expression` definition typed in the Formulas panel.
+ * Positions in thrown errors refer to the full input.
+ *
+ * @throws {FormulaError} when there is no `=` or the name is not a valid identifier
+ */
+export function parseFormulaDefinition(input: string): Pick<FormulaDefinitionInput, 'name' | 'expression'> {
+	const match = /^\s*([^=\s]*)\s*=(?!=)/.exec(input);
+	if (!match) throw new FormulaError('Write formulas as "name = expression"', 0);
+	const name = match[1]!;
+	if (!FORMULA_NAME_RE.test(name)) {
+		throw new FormulaError(
+			`Invalid formula name "${name}": use lowercase letters, digits and underscores`,
+			input.indexOf(name),
+		);
+	}
+	const expression = input.slice(match[0].length).trim();
+	if (expression === '') throw new FormulaError('Formula expression is empty', input.length);
+	return { name, expression };
+}
+
+// ---------------------------------------------------------------------------
+// Registry — compiled formulas available to fieldExpr()
+// ---------------------------------------------------------------------------
+
+let _compiled: CompiledFormula[] = [];
+let _exprByName: Map<string, string> = new Map();
+
+/**
+ * Replace the compiled formula registry.
+ * Worker: after every refreshValidColumnNames(). Main thread: from the ready
+ * message schema and after formula / import changes.
+ */
+export function setCompiledFormulas(formulas: readonly CompiledFormula[]): void {
+	_compiled = [...formulas];
+	_exprByName = new Map();
+
+	const byName = new Map<string, CompiledFormula[]>();
+	for (const formula of _compiled) {
+		const list = byName.get(formula.name) ?? [];
+		list.push(formula);
+		byName.set(formula.name, list);
+	}
+
+	for (const [name, list] of byName) {
+		const global = list.find((f) => f.datasetId === null);
+		const scoped = list.filter((f) => f.datasetId !== null);
+		if (scoped.length === 0) {
+			_exprByName.set(name, global!.sql);
+			continue;
+		}
+		const branches = scoped.map((f) => `WHEN '${f.datasetId!.replace(/'/g, "''")}' THEN ${f.sql}`).join(' ');
+		_exprByName.set(name, `(CASE cards.dataset_id ${branches} ELSE ${global ? global.sql : 'NULL'} END)`);
+	}
+}
+
+/** Current compiled formulas (readonly copy). */
+export function getCompiledFormulas(): readonly CompiledFormula[] {
+	return [..._compiled];
+}
+
+/**
+ * SQL expression for an `fx_<name>` field, or null when `field` is not a
+ * currently compiled formula. Called by fieldExpr() before any other mapping.
+ */
+export function formulaExpr(field: string): string | null {
+	const name = formulaNameOf(field);
+	return name === null ? null : (_exprByName.get(name) ?? null);
+}
+
+/** Compiled formulas from formula:list results (invalid formulas are skipped). */
+export function compiledFormulasOf(formulas: readonly FormulaInfo[]): CompiledFormula[] {
+	return formulas
+		.filter((f) => f.sql !== null)
+		.map((f) => ({
+			name: f.name,
+			label: f.label,
+			datasetId: f.dataset_id,
+			sql: f.sql!,
+			resultType: f.result_type,
+		}));
+}
+
+// ---------------------------------------------------------------------------
+// Schema metadata
+// ---------------------------------------------------------------------------
+
+/** LATCH family heuristic per result type. */
+const FORMULA_LATCH: Record<FormulaResultType, LatchFamily> = {
+	number: 'Hierarchy',
+	text: 'Alphabet',
+	date: 'Time',
+};
+
+/**
+ * Build schema metadata for one formula so SchemaProvider and the Worker
+ * allowlist can treat it like a column.
+ */
+export function formulaColumnInfo(formula: Pick<CompiledFormula, 'name' | 'label' | 'resultType'>): ColumnInfo {
+	return {
+		name: formulaField(formula.name),
+		type: formula.resultType === 'number' ? 'REAL' : 'TEXT',
+		notnull: false,
+		latchFamily: FORMULA_LATCH[formula.resultType],
+		isNumeric: formula.resultType === 'number',
+		formulaType: formula.resultType,
+		label: formula.label,
+	};
+}
+
+/**
+ * One column per formula name. When a name has global and per-dataset
+ * definitions, the global one supplies the label and type.
+ */
+export function formulaColumns(formulas: readonly CompiledFormula[]): ColumnInfo[] {
+	const byName = new Map<string, CompiledFormula>();
+	for (const formula of formulas) {
+		const current = byName.get(formula.name);
+		if (!current || (current.datasetId !== null && formula.datasetId === null)) byName.set(formula.name, formula);
+	}
+	return [...byName.values()].map(formulaColumnInfo);
+}
@@ -19,12 +19,42 @@ export {
 export { DensityProvider } from './DensityProvider';
 // Providers
 export { FilterProvider } from './FilterProvider';
+// Formula fields (fx_<name>)
+export type { CompiledFormula, FormulaFieldResolver, FormulaInfo } from './formulas';
+export {
+	compileFormula,
+	compiledFormulasOf,
+	FORMULA_FIELD_PREFIX,
+	FormulaError,
+	formulaColumnInfo,
+	formulaColumns,
+	formulaExpr,
+	formulaField,
+	formulaNameOf,
+	isFormulaField,
+	parseFormulaDefinition,
+	setCompiledFormulas,
+} from './formulas';
 export { setLatchSchemaProvider } from './latch';
 export { PAFVProvider } from './PAFVProvider';
+// Custom property fields (prop_<key>)
+export {
+	fieldExpr,
+	isPropertyField,
+	PROPERTY_FIELD_PREFIX,
+	propertyColumnInfo,
+	propertyField,
+	propertyKeyOf,
+} from './properties';
 // QueryBuilder types
 export type { CardQueryOptions, CompiledQuery } from './QueryBuilder';
 export { QueryBuilder } from './QueryBuilder';
+// Relative date expressions ("last 14 days", "next quarter", "overdue")
+export { formatRelativeDate, parseRelativeDate, resolveRelativeDate } from './relative-dates';
 export { SchemaProvider } from './SchemaProvider';
+// Search query language
+export type { ParsedSearchQuery, ParseSearchOptions, SearchSyntaxError } from './search-query';
+export { matchesSearchTerms, parseSearchQuery } from './search-query';
 export { SelectionProvider } from './SelectionProvider';
 export { StateCoordinator } from './StateCoordinator';
 export { StateManager } from './StateManager';
@@ -37,9 +67,14 @@ export type {
 	CompiledDensity,
 	CompiledFilter,
 	Filter,
+	FilterCombinator,
 	FilterField,
+	FilterGroup,
+	FilterNode,
 	FilterOperator,
 	PersistableProvider,
+	RelativeDateExpr,
+	RelativeDateUnit,
 	SortDirection,
 	ThemeMode,
 	TimeGranularity,
@@ -4,8 +4,9 @@
 // Requirements: PROP-02, DYNM-01, DYNM-02, DYNM-03, DYNM-04
 //
 // Design:
-//   - Maps all 9 AxisField values to their LATCH family letter
-//   - Location (L) has no AxisField members (location fields are FilterField-only)
+//   - Maps all 10 AxisField values to their LATCH family letter
+//   - Location (L) has one AxisField member (location_name, for place-name grouping);
+//     latitude/longitude are FilterField-only
 //   - All constants frozen via Object.freeze for immutability
 //   - LATCH_COLORS uses CSS custom property references from design-tokens.css
 //
@@ -107,9 +108,10 @@ export function setLatchSchemaProvider(sp: SchemaProvider | null): void {
  * - Time (T): created_at, modified_at, due_at
  * - Category (C): folder, status, card_type
  * - Hierarchy (H): priority, sort_order
+ * - Location (L): location_name (place-name grouping)
  *
- * Location (L) has no AxisField members -- location fields
- * (latitude, longitude, location_name) are FilterField-only.
+ * latitude/longitude are FilterField-only -- they are filtered by range
+ * (LocationExplorer), never grouped.
  */
 export const LATCH_FAMILIES_FALLBACK: Readonly<Record<string, LatchFamily>> = Object.freeze({
 	name: 'A',
@@ -121,6 +123,7 @@ export const LATCH_FAMILIES_FALLBACK: Readonly<Record<string, LatchFamily>> = Ob
 	card_type: 'C',
 	priority: 'H',
 	sort_order: 'H',
+	location_name: 'L',
 });
 
 /**
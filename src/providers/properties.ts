@@ -0,0 +1,86 @@
+// Isometry v5 — Custom Property Fields
+// Maps user-defined card properties (card_properties EAV) onto virtual
+// `prop_<key>` fields so they flow through the same filter/axis pipeline
+// as physical cards columns.
+//
+// Design:
+//   - Field names are `prop_` + a key matching PROPERTY_KEY_RE, so they pass
+//     the same [a-zA-Z0-9_] column-name rule as PRAGMA-derived columns.
+//   - fieldExpr() is the single place a property field becomes SQL: a
+//     correlated scalar subquery usable in SELECT, WHERE, GROUP BY and ORDER BY
+//     without changing the FROM clause.
+//   - Keys are inlined only after PROPERTY_KEY_RE validation; values are
+//     always bound parameters.
+//   - Allowlist membership is still decided by SchemaProvider / the Worker's
+//     valid column set — this module only translates names to expressions.
+//   - Formula fields (fx_<name>, see formulas.ts) are resolved here too, so
+//     every fieldExpr() caller supports them without changes.
+
+import { PROPERTY_KEY_RE, type PropertyDefinition, type PropertyType } from '../database/queries/properties';
+import type { ColumnInfo, LatchFamily } from '../worker/protocol';
+import { formulaExpr } from './formulas';
+
+/** Prefix distinguishing property fields from physical cards columns. */
+export const PROPERTY_FIELD_PREFIX = 'prop_';
+
+/** Field name for a property key: 'revenue' → 'prop_revenue'. */
+export function propertyField(key: string): string {
+	return `${PROPERTY_FIELD_PREFIX}${key}`;
+}
+
+/**
+ * Returns the property key for a `prop_<key>` field, or null when `field`
+ * is not a (well-formed) property field.
+ */
+export function propertyKeyOf(field: string): string | null {
+	if (!field.startsWith(PROPERTY_FIELD_PREFIX)) return null;
+	const key = field.slice(PROPERTY_FIELD_PREFIX.length);
+	return PROPERTY_KEY_RE.test(key) ? key : null;
+}
+
+/** True for well-formed `prop_<key>` field names. */
+export function isPropertyField(field: string): boolean {
+	return propertyKeyOf(field) !== null;
+}
+
+/**
+ * SQL expression for a filter/axis field.
+ * Formula fields compile to their registered SQL expression, property fields
+ * to a correlated subquery against card_properties; every other field is
+ * returned unchanged (caller has already validated it).
+ *
+ * CRITICAL: call validateFilterField/validateAxisField on the raw field name
+ * first — this function does not consult the allowlist.
+ */
+export function fieldExpr(field: string): string {
+	const formula = formulaExpr(field);
+	if (formula !== null) return formula;
+	const key = propertyKeyOf(field);
+	if (key === null) return field;
+	return `(SELECT value FROM card_properties WHERE card_properties.card_id = cards.id AND card_properties.key = '${key}')`;
+}
+
+/** LATCH family heuristic per property type (dates → Time, enums → Category, ...). */
+const PROPERTY_LATCH: Record<PropertyType, LatchFamily> = {
+	text: 'Alphabet',
+	url: 'Alphabet',
+	number: 'Hierarchy',
+	date: 'Time',
+	enum: 'Category',
+};
+
+/**
+ * Build schema metadata for a property definition so SchemaProvider and the
+ * Worker allowlist can treat it like a column.
+ */
+export function propertyColumnInfo(def: Pick<PropertyDefinition, 'key' | 'label' | 'type'>): ColumnInfo {
+	return {
+		name: propertyField(def.key),
+		type: def.type === 'number' ? 'REAL' : 'TEXT',
+		notnull: false,
+		latchFamily: PROPERTY_LATCH[def.type],
+		isNumeric: def.type === 'number',
+		propertyType: def.type,
+		label: def.label,
+	};
+}
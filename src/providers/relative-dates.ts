@@ -0,0 +1,196 @@
+// Isometry v5 — Relative Date Expressions
+// Parse, format and resolve symbolic date ranges ("last 14 days", "next quarter",
+// "overdue") used by FilterProvider relative date filters.
+//
+// Design:
+//   - Expressions are stored symbolically (RelativeDateExpr) and resolved at
+//     compile() time, so saved filter state never goes stale overnight
+//   - Ranges resolve to local calendar days, bound as the UTC instants of local
+//     midnight (toISOString): start inclusive, end exclusive. Card columns hold
+//     UTC timestamps ('2026-03-09T10:00:00Z'), so "today" means the user's day,
+//     not the UTC day — same as the Time section presets
+//   - Weeks start on Sunday, matching the Time section presets
+//   - parseRelativeDate() never throws — unknown text returns null
+
+import type { RelativeDateExpr, RelativeDateUnit } from './types';
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+const UNITS: readonly RelativeDateUnit[] = ['day', 'week', 'month', 'quarter', 'year'];
+
+/** Upper bound for rolling amounts — keeps resolved dates inside the Date range. */
+const MAX_AMOUNT = 9999;
+
+type PeriodOffset = -1 | 0 | 1;
+
+const PERIOD_WORDS: Readonly<Record<string, PeriodOffset>> = { last: -1, previous: -1, this: 0, current: 0, next: 1 };
+
+const DAY_WORDS: Readonly<Record<string, PeriodOffset>> = { yesterday: -1, today: 0, tomorrow: 1 };
+
+// ---------------------------------------------------------------------------
+// Parsing / formatting
+// ---------------------------------------------------------------------------
+
+/**
+ * Parse a relative date phrase. Case-insensitive; words may be separated by
+ * spaces or hyphens.
+ *
+ * Accepted forms:
+ *   today, yesterday, tomorrow, overdue
+ *   this|last|next <unit>           calendar period  ("next quarter")
+ *   last|past|next <n> <unit>[s]    rolling window   ("last 14 days")
+ *
+ * @returns the expression, or null when the text is not a relative date
+ */
+export function parseRelativeDate(text: string): RelativeDateExpr | null {
+	const words = text.trim().toLowerCase().split(/[\s-]+/);
+	if (words.length === 1) {
+		const word = words[0]!;
+		if (word === 'overdue') return { kind: 'overdue' };
+		const offset = DAY_WORDS[word];
+		return offset === undefined ? null : { kind: 'period', offset, unit: 'day' };
+	}
+
+	if (words.length === 2) {
+		const offset = PERIOD_WORDS[words[0]!];
+		const unit = toUnit(words[1]!, false);
+		return offset === undefined || unit === null ? null : { kind: 'period', offset, unit };
+	}
+
+	if (words.length === 3) {
+		const [dir, count, unitWord] = words as [string, string, string];
+		const direction = dir === 'last' || dir === 'past' ? 'past' : dir === 'next' ? 'future' : null;
+		const amount = /^\d+$/.test(count) ? Number(count) : Number.NaN;
+		const unit = toUnit(unitWord, true);
+		if (direction === null || unit === null || !(amount >= 1 && amount <= MAX_AMOUNT)) return null;
+		return { kind: 'rolling', direction, amount, unit };
+	}
+
+	return null;
+}
+
+/**
+ * Human-readable label for an expression, e.g. "Last 14 days", "Next quarter", "Overdue".
+ * The output round-trips through parseRelativeDate().
+ */
+export function formatRelativeDate(expr: RelativeDateExpr): string {
+	switch (expr.kind) {
+		case 'overdue':
+			return 'Overdue';
+		case 'rolling': {
+			const dir = expr.direction === 'past' ? 'Last' : 'Next';
+			return `${dir} ${expr.amount} ${expr.unit}${expr.amount === 1 ? '' : 's'}`;
+		}
+		case 'period': {
+			if (expr.unit === 'day') return ['Yesterday', 'Today', 'Tomorrow'][expr.offset + 1]!;
+			return `${['Last', 'This', 'Next'][expr.offset + 1]!} ${expr.unit}`;
+		}
+	}
+}
+
+/**
+ * Structural type guard for persisted expressions (JSON-restored FilterProvider state).
+ */
+export function isRelativeDateExpr(value: unknown): value is RelativeDateExpr {
+	if (typeof value !== 'object' || value === null || Array.isArray(value)) return false;
+	const e = value as Record<string, unknown>;
+	switch (e['kind']) {
+		case 'overdue':
+			return true;
+		case 'period':
+			return isUnit(e['unit']) && (e['offset'] === -1 || e['offset'] === 0 || e['offset'] === 1);
+		case 'rolling':
+			return (
+				isUnit(e['unit']) &&
+				(e['direction'] === 'past' || e['direction'] === 'future') &&
+				Number.isInteger(e['amount']) &&
+				(e['amount'] as number) >= 1 &&
+				(e['amount'] as number) <= MAX_AMOUNT
+			);
+		default:
+			return false;
+	}
+}
+
+// ---------------------------------------------------------------------------
+// Resolution
+// ---------------------------------------------------------------------------
+
+/**
+ * Resolve an expression to a concrete date range relative to `now`.
+ *
+ * @returns `{ start, end }` as ISO instants of local midnight — start inclusive,
+ *          end exclusive. `start` is null for open-ended ranges (overdue).
+ */
+export function resolveRelativeDate(
+	expr: RelativeDateExpr,
+	now: Date = new Date(),
+): { start: string | null; end: string } {
+	const today = new Date(now.getFullYear(), now.getMonth(), now.getDate());
+
+	switch (expr.kind) {
+		case 'overdue':
+			return { start: null, end: today.toISOString() };
+
+		case 'rolling': {
+			if (expr.direction === 'past') {
+				const end = addUnits(today, 1, 'day');
+				return { start: addUnits(end, -expr.amount, expr.unit).toISOString(), end: end.toISOString() };
+			}
+			return { start: today.toISOString(), end: addUnits(today, expr.amount, expr.unit).toISOString() };
+		}
+
+		case 'period': {
+			const start = addUnits(periodStart(today, expr.unit), expr.offset, expr.unit);
+			return { start: start.toISOString(), end: addUnits(start, 1, expr.unit).toISOString() };
+		}
+	}
+}
+
+// ---------------------------------------------------------------------------
+// Internal helpers
+// ---------------------------------------------------------------------------
+
+function isUnit(value: unknown): value is RelativeDateUnit {
+	return UNITS.includes(value as RelativeDateUnit);
+}
+
+/** Map a unit word to a unit; plural forms are accepted only where `allowPlural`. */
+function toUnit(word: string, allowPlural: boolean): RelativeDateUnit | null {
+	const singular = allowPlural && word.endsWith('s') ? word.slice(0, -1) : word;
+	return isUnit(singular) ? singular : null;
+}
+
+/** Start of the calendar period containing `date`. */
+function periodStart(date: Date, unit: RelativeDateUnit): Date {
+	switch (unit) {
+		case 'day':
+			return date;
+		case 'week':
+			return new Date(date.getFullYear(), date.getMonth(), date.getDate() - date.getDay());
+		case 'month':
+			return new Date(date.getFullYear(), date.getMonth(), 1);
+		case 'quarter':
+			return new Date(date.getFullYear(), Math.floor(date.getMonth() / 3) * 3, 1);
+		case 'year':
+			return new Date(date.getFullYear(), 0, 1);
+	}
+}
+
+/**
+ * Add `n` units to a local date. Month-based units clamp the day of month
+ * (Jan 31 + 1 month = Feb 28/29) instead of overflowing into the next month.
+ */
+function addUnits(date: Date, n: number, unit: RelativeDateUnit): Date {
+	if (unit === 'day' || unit === 'week') {
+		const days = unit === 'week' ? n * 7 : n;
+		return new Date(date.getFullYear(), date.getMonth(), date.getDate() + days);
+	}
+	const months = unit === 'month' ? n : unit === 'quarter' ? n * 3 : n * 12;
+	const target = new Date(date.getFullYear(), date.getMonth() + months, 1);
+	const lastDay = new Date(target.getFullYear(), target.getMonth() + 1, 0).getDate();
+	return new Date(target.getFullYear(), target.getMonth(), Math.min(date.getDate(), lastDay));
+}
+
@@ -0,0 +1,389 @@
+// Isometry v5 — Search Query Language
+// Small parser for the search box syntax shared by searchCards(), FilterProvider,
+// SuperSearchInput and CommandPalette.
+//
+// Syntax:
+//   budget report          implicit AND (each term quoted — never raw FTS5 syntax)
+//   "exact phrase"         phrase match
+//   plan*                  prefix match
+//   alpha OR beta          either term
+//   -draft / NOT draft     exclude term (needs at least one positive term)
+//   NEAR(alpha beta, 5)    proximity (distance optional, default 10)
+//   name:roadmap           FTS column scope — name, content, folder, tags
+//   status:done            LATCH filter → FilterProvider clause (eq)
+//   due:<2026-01-01        comparison operators: > >= < <= != (or !value)
+//   status:todo,doing      membership (in)
+//   has:due / -has:due     IS NOT NULL / IS NULL
+//
+// Design:
+//   - parseSearchQuery() never throws; problems are reported in `errors` with
+//     the character offset, and the offending token is searched as plain text
+//     (or dropped when that would still be invalid FTS5)
+//   - FTS5 output only ever contains quoted strings, column names from a
+//     fixed set, and the operators OR / NOT / NEAR — user text cannot inject syntax
+//   - Filter fields are validated against the allowlist (SchemaProvider when
+//     wired, the Worker's column set otherwise), so custom prop_<key> fields work
+//   - Numeric-looking values bind as numbers only for numeric fields; text
+//     fields keep the string ('02139', a text property holding '2024')
+
+import { isNumericFilterField, isValidFilterField } from './allowlist';
+import type { Filter, FilterOperator } from './types';
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+export interface SearchSyntaxError {
+	/** Human-readable description, safe to show in the UI */
+	message: string;
+	/** 0-based character offset of the offending token in the input */
+	position: number;
+}
+
+export interface ParsedSearchQuery {
+	/** FTS5 MATCH expression; '' when the query has no full-text part */
+	fts: string;
+	/** LATCH filter clauses for FilterProvider (already allowlist-validated) */
+	filters: Filter[];
+	/** Positive plain-text terms and phrases, for client-side matching and highlighting */
+	terms: string[];
+	/** Syntax problems found while parsing (the rest of the query still compiles) */
+	errors: SearchSyntaxError[];
+}
+
+export interface ParseSearchOptions {
+	/**
+	 * Treat every bare term as a prefix (search-as-you-type). Phrases are never
+	 * prefixed. Default false.
+	 */
+	prefix?: boolean;
+}
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/** cards_fts columns that can scope a term (`name:foo`). */
+const FTS_COLUMNS: ReadonlySet<string> = new Set(['name', 'content', 'folder', 'tags']);
+
+/** Short field names accepted in filter clauses. */
+const FIELD_ALIASES: Readonly<Record<string, string>> = {
+	type: 'card_type',
+	due: 'due_at',
+	created: 'created_at',
+	modified: 'modified_at',
+	completed: 'completed_at',
+	start: 'event_start',
+	end: 'event_end',
+	location: 'location_name',
+};
+
+const DEFAULT_NEAR_DISTANCE = 10;
+
+const FIELD_NAME_RE = /^[A-Za-z_][A-Za-z0-9_]*$/;
+const NUMBER_RE = /^-?(\d+\.?\d*|\.\d+)$/;
+
+/** Operator prefixes in match order (two-character operators first). */
+const COMPARISONS: ReadonlyArray<[string, FilterOperator]> = [
+	['>=', 'gte'],
+	['<=', 'lte'],
+	['!=', 'neq'],
+	['>', 'gt'],
+	['<', 'lt'],
+	['!', 'neq'],
+];
+
+const NEGATED: Readonly<Partial<Record<FilterOperator, FilterOperator>>> = {
+	eq: 'neq',
+	neq: 'eq',
+	gt: 'lte',
+	gte: 'lt',
+	lt: 'gte',
+	lte: 'gt',
+	isNull: 'isNotNull',
+	isNotNull: 'isNull',
+};
+
+// ---------------------------------------------------------------------------
+// Parser
+// ---------------------------------------------------------------------------
+
+type Item = { kind: 'fts'; expr: string; negated: boolean; position: number } | { kind: 'or'; position: number };
+
+/**
+ * Parse a search box string into a safe FTS5 expression plus filter clauses.
+ *
+ * @example
+ * parseSearchQuery('roadmap -draft status:done due:<2026-01-01')
+ * // → { fts: '"roadmap" NOT "draft"',
+ * //     filters: [{ field: 'status', operator: 'eq', value: 'done' },
+ * //               { field: 'due_at', operator: 'lt', value: '2026-01-01' }], ... }
+ */
+export function parseSearchQuery(input: string, options: ParseSearchOptions = {}): ParsedSearchQuery {
+	const prefixAll = options.prefix ?? false;
+	const items: Item[] = [];
+	const filters: Filter[] = [];
+	const terms: string[] = [];
+	const errors: SearchSyntaxError[] = [];
+
+	let pos = 0;
+	let pendingNot = false;
+
+	const pushTerm = (text: string, position: number, negated: boolean, prefix: boolean, column?: string): void => {
+		const trimmed = text.trim();
+		if (!trimmed) return;
+		const quoted = `${quote(trimmed)}${prefix ? '*' : ''}`;
+		items.push({ kind: 'fts', expr: column ? `${column} : ${quoted}` : quoted, negated, position });
+		if (!negated) terms.push(trimmed);
+	};
+
+	while (pos < input.length) {
+		if (/\s/.test(input[pos]!)) {
+			pos++;
+			continue;
+		}
+
+		const start = pos;
+		let negated = pendingNot;
+		pendingNot = false;
+		if (input[pos] === '-' && pos + 1 < input.length && !/\s/.test(input[pos + 1]!)) {
+			negated = !negated;
+			pos++;
+		}
+
+		// NEAR(term term, N)
+		if (input.startsWith('NEAR(', pos)) {
+			const close = input.indexOf(')', pos);
+			if (close === -1) {
+				errors.push({ message: 'Unclosed NEAR( — add ")"', position: start });
+				pos += 'NEAR('.length;
+				continue;
+			}
+			const body = input.slice(pos + 'NEAR('.length, close);
+			pos = close + 1;
+			const near = compileNear(body);
+			if (typeof near === 'string') {
+				items.push({ kind: 'fts', expr: near, negated, position: start });
+			} else {
+				errors.push({ message: near.error, position: start });
+			}
+			continue;
+		}
+
+		// "quoted phrase" (optionally followed by * for a prefix phrase)
+		if (input[pos] === '"') {
+			const close = input.indexOf('"', pos + 1);
+			if (close === -1) {
+				errors.push({ message: 'Unterminated quote', position: pos });
+				pushTerm(input.slice(pos + 1), start, negated, false);
+				break;
+			}
+			const phrase = input.slice(pos + 1, close);
+			pos = close + 1;
+			const prefix = input[pos] === '*';
+			if (prefix) pos++;
+			pushTerm(phrase, start, negated, prefix);
+			continue;
+		}
+
+		// Bare word — may embed a quoted value (`folder:"Work Stuff"`)
+		let end = pos;
+		let inQuote = false;
+		while (end < input.length && (inQuote || !/\s/.test(input[end]!))) {
+			if (input[end] === '"') inQuote = !inQuote;
+			end++;
+		}
+		const word = input.slice(pos, end);
+		pos = end;
+		if (inQuote) errors.push({ message: 'Unterminated quote', position: start });
+
+		if (!negated && word === 'OR') {
+			items.push({ kind: 'or', position: start });
+			continue;
+		}
+		if (!negated && word === 'AND') continue;
+		if (word === 'NOT') {
+			pendingNot = !negated;
+			continue;
+		}
+
+		const colon = word.indexOf(':');
+		const fieldName = colon > 0 ? word.slice(0, colon) : '';
+		const rawValue = word.slice(colon + 1);
+		// `scheme://` is a URL, not a field clause
+		if (fieldName && FIELD_NAME_RE.test(fieldName) && !rawValue.startsWith('//')) {
+			const lower = fieldName.toLowerCase();
+
+			if (FTS_COLUMNS.has(lower)) {
+				const prefix = rawValue.endsWith('*') || (prefixAll && !rawValue.startsWith('"'));
+				pushTerm(unquote(rawValue.replace(/\*$/, '')), start, negated, prefix, lower);
+				continue;
+			}
+
+			const clause = compileFilter(lower, rawValue, negated);
+			if ('error' in clause) {
+				errors.push({ message: clause.error, position: start });
+				if (clause.fallback) pushTerm(unquote(word), start, negated, prefixAll);
+			} else {
+				filters.push(...clause.filters);
+			}
+			continue;
+		}
+
+		const prefix = word.endsWith('*') || prefixAll;
+		pushTerm(unquote(word.replace(/\*+$/, '')), start, negated, prefix);
+	}
+
+	if (pendingNot) errors.push({ message: 'NOT needs a term after it', position: input.length });
+
+	return { fts: compileFts(items, errors), filters, terms, errors };
+}
+
+/**
+ * True when `text` contains every positive term of a parsed query
+ * (case-insensitive). Used for client-side matching of rendered cells.
+ */
+export function matchesSearchTerms(text: string, parsed: ParsedSearchQuery): boolean {
+	const lower = text.toLowerCase();
+	return parsed.terms.every((t) => lower.includes(t.toLowerCase()));
+}
+
+// ---------------------------------------------------------------------------
+// Internal helpers
+// ---------------------------------------------------------------------------
+
+/** FTS5 string literal: wrap in double quotes, doubling embedded quotes. */
+function quote(text: string): string {
+	return `"${text.replace(/"/g, '""')}"`;
+}
+
+function unquote(text: string): string {
+	return text.replace(/"/g, '');
+}
+
+/**
+ * Assemble items into one FTS5 expression. OR binds adjacent positive items;
+ * all exclusions are applied to the whole positive expression.
+ */
+function compileFts(items: Item[], errors: SearchSyntaxError[]): string {
+	const groups: string[][] = [];
+	const negatives: string[] = [];
+	let orPending: { position: number } | null = null;
+
+	for (const item of items) {
+		if (item.kind === 'or') {
+			if (groups.length === 0 || orPending) {
+				errors.push({ message: 'OR needs a term on both sides', position: item.position });
+			} else {
+				orPending = { position: item.position };
+			}
+			continue;
+		}
+		if (item.negated) {
+			negatives.push(item.expr);
+			continue;
+		}
+		if (orPending) {
+			groups[groups.length - 1]!.push(item.expr);
+			orPending = null;
+		} else {
+			groups.push([item.expr]);
+		}
+	}
+	if (orPending) errors.push({ message: 'OR needs a term on both sides', position: orPending.position });
+
+	if (groups.length === 0) {
+		if (negatives.length > 0) {
+			const first = items.find((i) => i.kind === 'fts' && i.negated);
+			errors.push({ message: 'Exclusions need at least one search term', position: first?.position ?? 0 });
+		}
+		return '';
+	}
+
+	const rendered = groups.map((g) => (g.length === 1 ? g[0]! : `(${g.join(' OR ')})`));
+	if (negatives.length === 0) {
+		// A lone OR group needs no parentheses
+		return groups.length === 1 ? groups[0]!.join(' OR ') : rendered.join(' ');
+	}
+
+	const positive = rendered.length === 1 ? rendered[0]! : `(${rendered.join(' ')})`;
+	const negative = negatives.length === 1 ? negatives[0]! : `(${negatives.join(' OR ')})`;
+	return `${positive} NOT ${negative}`;
+}
+
+/** Compile the inside of NEAR( ... ) — returns the FTS5 expression or an error. */
+function compileNear(body: string): string | { error: string } {
+	const comma = body.lastIndexOf(',');
+	const termPart = comma === -1 ? body : body.slice(0, comma);
+	const distancePart = comma === -1 ? '' : body.slice(comma + 1).trim();
+
+	let distance = DEFAULT_NEAR_DISTANCE;
+	if (distancePart) {
+		if (!/^\d+$/.test(distancePart)) return { error: `NEAR distance must be a whole number, got "${distancePart}"` };
+		distance = Number(distancePart);
+	}
+
+	const nearTerms = (termPart.match(/"[^"]*"|[^\s"]+/g) ?? []).map((t) => unquote(t).trim()).filter(Boolean);
+	if (nearTerms.length < 2) return { error: 'NEAR needs at least two terms' };
+
+	return `NEAR(${nearTerms.map(quote).join(' ')}, ${distance})`;
+}
+
+/**
+ * Compile a `field:value` token into filter clauses.
+ * `fallback` tells the caller to search the token as plain text instead.
+ */
+function compileFilter(
+	fieldName: string,
+	rawValue: string,
+	negated: boolean,
+): { filters: Filter[] } | { error: string; fallback: boolean } {
+	if (fieldName === 'has') {
+		const target = resolveField(rawValue.toLowerCase());
+		if (!target) return { error: `Unknown field "${rawValue}"`, fallback: false };
+		return { filters: [{ field: target, operator: negated ? 'isNull' : 'isNotNull', value: null }] };
+	}
+
+	const field = resolveField(fieldName);
+	if (!field) return { error: `Unknown field "${fieldName}"`, fallback: true };
+
+	let operator: FilterOperator = 'eq';
+	let value = rawValue;
+	for (const [symbol, op] of COMPARISONS) {
+		if (value.startsWith(symbol)) {
+			operator = op;
+			value = value.slice(symbol.length);
+			break;
+		}
+	}
+	value = unquote(value).trim();
+	if (!value) return { error: `Missing value for "${fieldName}:"`, fallback: false };
+
+	// Membership list (eq only): status:todo,doing
+	if (operator === 'eq' && value.includes(',')) {
+		const values = value
+			.split(',')
+			.map((v) => v.trim())
+			.filter(Boolean)
+			.map((v) => toFilterValue(field, v));
+		if (negated) return { filters: values.map((v) => ({ field, operator: 'neq' as const, value: v })) };
+		return { filters: [{ field, operator: 'in', value: values }] };
+	}
+
+	const finalOperator = negated ? (NEGATED[operator] ?? operator) : operator;
+	return { filters: [{ field, operator: finalOperator, value: toFilterValue(field, value) }] };
+}
+
+/** Resolve an alias or raw column name to an allowlisted filter field, or null. */
+function resolveField(name: string): string | null {
+	const field = FIELD_ALIASES[name] ?? name;
+	return isValidFilterField(field) ? field : null;
+}
+
+/**
+ * Numeric-looking values bind as numbers on numeric fields only — property and
+ * formula values have no affinity, so a number never equals a stored string.
+ */
+function toFilterValue(field: string, value: string): string | number {
+	return NUMBER_RE.test(value) && isNumericFilterField(field) ? Number(value) : value;
+}
@@ -76,7 +76,8 @@ export type KnownAxisField =
 	| 'card_type'
 	| 'priority'
 	| 'sort_order'
-	| 'name';
+	| 'name'
+	| 'location_name';
 
 /**
  * Allowlisted columns for ORDER BY and GROUP BY.
@@ -137,7 +138,8 @@ export type ViewType =
 	| 'gallery'
 	| 'network'
 	| 'tree'
-	| 'supergrid';
+	| 'supergrid'
+	| 'map';
 
 /** Top-level view family: LATCH-based or GRAPH-based. */
 export type ViewFamily = 'latch' | 'graph';
@@ -175,6 +177,43 @@ export interface MembershipFilter {
 	max: unknown;
 }
 
+/** Calendar unit for relative date filters. */
+export type RelativeDateUnit = 'day' | 'week' | 'month' | 'quarter' | 'year';
+
+/**
+ * A relative date expression, stored symbolically and resolved against the
+ * current date at compile() time so persisted filters never go stale.
+ *
+ * - rolling: the last/next `amount` units counted in whole days from today
+ *   ("last 14 days" = the 14 days ending today)
+ * - period:  a calendar period relative to the current one
+ *   (-1 = last week, 0 = this month, 1 = next quarter; day unit = yesterday/today/tomorrow)
+ * - overdue: before today and not completed
+ */
+export type RelativeDateExpr =
+	| { kind: 'rolling'; direction: 'past' | 'future'; amount: number; unit: RelativeDateUnit }
+	| { kind: 'period'; offset: -1 | 0 | 1; unit: RelativeDateUnit }
+	| { kind: 'overdue' };
+
+/** Boolean combinator joining the children of a FilterGroup. */
+export type FilterCombinator = 'and' | 'or';
+
+/**
+ * A nested boolean filter group: children joined with AND/OR, optionally
+ * negated as a whole (NOT). Children are conditions or further groups.
+ * Used by FilterProvider.setFilterTree() for queries the flat filter list
+ * cannot express, e.g. `(status = todo OR priority > 2) AND NOT folder = archive`.
+ */
+export interface FilterGroup {
+	kind: 'group';
+	combinator: FilterCombinator;
+	negate: boolean;
+	children: FilterNode[];
+}
+
+/** A node of a filter tree — a single condition or a nested group. */
+export type FilterNode = Filter | FilterGroup;
+
 /**
  * Compiled output of FilterProvider — a SQL WHERE fragment + parameter array.
  * Always starts with `deleted_at IS NULL`.
@@ -0,0 +1,117 @@
+// Isometry v5 — Saved Searches
+// SavedSearchManager: main-thread cache of saved searches (smart folders).
+//
+// A saved search is a named FilterProvider.toJSON() snapshot persisted in the
+// saved_searches table. Running one restores the full filter state — filters,
+// search query, axis/range filters and membership filter — via
+// FilterProvider.applyState(), which notifies subscribers like any other
+// filter change.
+//
+// Counts come from the Worker with every list; call refresh() after data
+// changes (LatchExplorers does so on StateCoordinator ticks).
+
+import type { SavedSearch } from '../database/queries/saved-searches';
+import type { FilterProvider } from '../providers/FilterProvider';
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+interface Bridge {
+	send(cmd: string, args: Record<string, unknown>): Promise<unknown>;
+}
+
+// ---------------------------------------------------------------------------
+// SavedSearchManager
+// ---------------------------------------------------------------------------
+
+export class SavedSearchManager {
+	private readonly _bridge: Bridge;
+	private readonly _filter: FilterProvider;
+	private _searches: SavedSearch[] = [];
+	private readonly _subscribers = new Set<() => void>();
+	/** Incremented per refresh() so stale list responses are discarded */
+	private _refreshSeq = 0;
+
+	constructor(bridge: Bridge, filter: FilterProvider) {
+		this._bridge = bridge;
+		this._filter = filter;
+	}
+
+	/**
+	 * Reload saved searches (with live counts) from the Worker and notify subscribers.
+	 */
+	async refresh(): Promise<void> {
+		const seq = ++this._refreshSeq;
+		const searches = (await this._bridge.send('saved-search:list', {})) as SavedSearch[];
+		if (seq !== this._refreshSeq) return;
+		this._searches = searches;
+		this._notify();
+	}
+
+	/**
+	 * Saved searches ordered by name, as of the last refresh().
+	 */
+	list(): readonly SavedSearch[] {
+		return this._searches;
+	}
+
+	/**
+	 * Save the current FilterProvider state under `name`.
+	 * An existing search with the same name (case-insensitive) is overwritten.
+	 */
+	async saveCurrent(name: string): Promise<SavedSearch> {
+		const saved = (await this._bridge.send('saved-search:save', {
+			name,
+			state: this._filter.toJSON(),
+		})) as SavedSearch;
+		await this.refresh();
+		return saved;
+	}
+
+	async rename(id: string, name: string): Promise<void> {
+		await this._bridge.send('saved-search:rename', { id, name });
+		await this.refresh();
+	}
+
+	async delete(id: string): Promise<void> {
+		await this._bridge.send('saved-search:delete', { id });
+		await this.refresh();
+	}
+
+	/**
+	 * Restore a saved search into the FilterProvider.
+	 * Returns the applied search, or null when the id is unknown or its state no
+	 * longer validates (e.g. it references a deleted custom property).
+	 */
+	apply(id: string): SavedSearch | null {
+		const search = this._searches.find((s) => s.id === id);
+		if (!search) return null;
+		try {
+			this._filter.applyState(JSON.parse(search.state));
+		} catch {
+			return null;
+		}
+		return search;
+	}
+
+	/**
+	 * True when the FilterProvider currently holds exactly this search's state.
+	 */
+	isActive(id: string): boolean {
+		const search = this._searches.find((s) => s.id === id);
+		return search !== undefined && search.state === this._filter.toJSON();
+	}
+
+	/**
+	 * Subscribe to list changes. Returns an unsubscribe function.
+	 */
+	subscribe(callback: () => void): () => void {
+		this._subscribers.add(callback);
+		return () => this._subscribers.delete(callback);
+	}
+
+	private _notify(): void {
+		this._subscribers.forEach((cb) => cb());
+	}
+}
@@ -0,0 +1,84 @@
+// Isometry v5 — Saved Searches
+// Saved search commands factory: wires SavedSearchManager into the command palette.
+//
+// Creates run/delete PaletteCommands from the saved search list, plus a
+// "Save Current Filters as Search" command. Commands refresh whenever the
+// manager's list changes (save, delete, rename, count refresh).
+
+import type { CommandPalette } from '../palette/CommandPalette';
+import type { CommandRegistry } from '../palette/CommandRegistry';
+import type { ActionToast } from '../ui/ActionToast';
+import type { SavedSearchManager } from './SavedSearchManager';
+
+// ---------------------------------------------------------------------------
+// createSavedSearchCommands
+// ---------------------------------------------------------------------------
+
+export interface SavedSearchCommandsDeps {
+	manager: SavedSearchManager;
+	registry: CommandRegistry;
+	palette: CommandPalette;
+	actionToast: ActionToast;
+}
+
+/**
+ * Registers saved search commands in the command registry.
+ *
+ * Generates:
+ * - "Run Search: {name} ({count})" for every saved search
+ * - "Delete Search: {name}" for every saved search
+ * - "Save Current Filters as Search" (always present)
+ *
+ * @returns Unsubscribe function that stops refreshing commands on list changes
+ */
+export function createSavedSearchCommands(deps: SavedSearchCommandsDeps): () => void {
+	const { manager, registry, palette, actionToast } = deps;
+
+	function refreshCommands(): void {
+		registry.unregisterByPrefix('saved-search:');
+
+		for (const search of manager.list()) {
+			const { id, name, count } = search;
+
+			registry.register({
+				id: `saved-search:run:${id}`,
+				label: count === null ? `Run Search: ${name}` : `Run Search: ${name} (${count})`,
+				category: 'Searches',
+				execute: () => {
+					if (manager.apply(id)) {
+						actionToast.show(`Showing \u201C${name}\u201D`);
+					} else {
+						actionToast.show(`Search \u201C${name}\u201D can no longer be applied`);
+					}
+				},
+			});
+
+			registry.register({
+				id: `saved-search:delete:${id}`,
+				label: `Delete Search: ${name}`,
+				category: 'Searches',
+				execute: () => {
+					void manager.delete(id).then(() => {
+						actionToast.show(`Search \u201C${name}\u201D deleted`);
+					});
+				},
+			});
+		}
+
+		registry.register({
+			id: 'saved-search:save',
+			label: 'Save Current Filters as Search',
+			category: 'Searches',
+			execute: () => {
+				palette.promptForInput('Name this search\u2026', (inputName) => {
+					void manager.saveCurrent(inputName).then((saved) => {
+						actionToast.show(`Search \u201C${saved.name}\u201D saved`);
+					});
+				});
+			},
+		});
+	}
+
+	refreshCommands();
+	return manager.subscribe(refreshCommands);
+}
@@ -0,0 +1,223 @@
+// Isometry v5 — Stories
+// StoryManager: main-thread cache of stories plus slide capture and playback.
+//
+// A slide snapshots the active view type and the PAFVProvider, FilterProvider,
+// DensityProvider and SelectionProvider states (capture()). Applying a slide
+// validates every stored state first, then switches the view — ViewManager.switchTo()
+// picks a morph or crossfade from views/transitions.ts — then restores the
+// provider states and asks the host to re-render (onApplied). A slide that no
+// longer validates changes nothing.
+//
+// Every edit (add/move/remove/caption) saves the story's full slide list via
+// story:save and refreshes the cache; the Worker is the source of truth.
+
+import type { Story, StoryInput, StorySlide, StorySlideState } from '../database/queries/stories';
+import { DensityProvider } from '../providers/DensityProvider';
+import { FilterProvider } from '../providers/FilterProvider';
+import { PAFVProvider } from '../providers/PAFVProvider';
+import type { SelectionProvider } from '../providers/SelectionProvider';
+import type { ViewType } from '../providers/types';
+import type { WorkerBridge } from '../worker/WorkerBridge';
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/** WorkerBridge subset used by StoryManager (mockable in tests). */
+export type StoriesBridge = Pick<WorkerBridge, 'listStories' | 'saveStory' | 'deleteStory'>;
+
+export interface StoryManagerConfig {
+	bridge: StoriesBridge;
+	pafv: PAFVProvider;
+	filter: FilterProvider;
+	density: DensityProvider;
+	selection: SelectionProvider;
+	/** Switch the active view (main.ts wires ViewManager.switchTo) */
+	switchView: (viewType: ViewType) => Promise<void>;
+	/** Called after a slide's provider states are restored (schedule a re-render) */
+	onApplied: () => void;
+}
+
+// ---------------------------------------------------------------------------
+// StoryManager
+// ---------------------------------------------------------------------------
+
+export class StoryManager {
+	private readonly _config: StoryManagerConfig;
+	private _stories: Story[] = [];
+	private readonly _subscribers = new Set<() => void>();
+	/** Incremented per refresh() so stale list responses are discarded */
+	private _refreshSeq = 0;
+
+	constructor(config: StoryManagerConfig) {
+		this._config = config;
+	}
+
+	/**
+	 * Reload stories from the Worker and notify subscribers.
+	 */
+	async refresh(): Promise<void> {
+		const seq = ++this._refreshSeq;
+		const stories = await this._config.bridge.listStories();
+		if (seq !== this._refreshSeq) return;
+		this._stories = stories;
+		this._notify();
+	}
+
+	/**
+	 * Stories ordered by name, as of the last refresh().
+	 */
+	list(): readonly Story[] {
+		return this._stories;
+	}
+
+	get(id: string): Story | null {
+		return this._stories.find((s) => s.id === id) ?? null;
+	}
+
+	// -----------------------------------------------------------------------
+	// Story editing
+	// -----------------------------------------------------------------------
+
+	/**
+	 * Create a story. With `withCurrentSlide`, its first slide captures the current view.
+	 */
+	async create(name: string, withCurrentSlide = true): Promise<Story> {
+		const slides = withCurrentSlide ? [{ caption: '', state: this.capture() }] : [];
+		return this._save({ name, slides });
+	}
+
+	async rename(id: string, name: string): Promise<Story> {
+		return this._edit(id, () => ({ name }));
+	}
+
+	async delete(id: string): Promise<void> {
+		await this._config.bridge.deleteStory(id);
+		await this.refresh();
+	}
+
+	/**
+	 * Append a slide capturing the current view state.
+	 */
+	async addSlide(storyId: string, caption = ''): Promise<Story> {
+		const state = this.capture();
+		return this._edit(storyId, (story) => ({ slides: [...story.slides, { caption, state }] }));
+	}
+
+	async setCaption(storyId: string, slideId: string, caption: string): Promise<Story> {
+		return this._editSlides(storyId, (slides) => slides.map((s) => (s.id === slideId ? { ...s, caption } : s)));
+	}
+
+	/**
+	 * Replace a slide's captured state with the current view state (caption kept).
+	 */
+	async recapture(storyId: string, slideId: string): Promise<Story> {
+		const state = this.capture();
+		return this._editSlides(storyId, (slides) => slides.map((s) => (s.id === slideId ? { ...s, state } : s)));
+	}
+
+	/**
+	 * Move a slide by `delta` positions (clamped to the story bounds).
+	 */
+	async moveSlide(storyId: string, slideId: string, delta: number): Promise<Story> {
+		return this._editSlides(storyId, (slides) => {
+			const from = slides.findIndex((s) => s.id === slideId);
+			if (from === -1) return slides;
+			const to = Math.max(0, Math.min(slides.length - 1, from + delta));
+			const next = [...slides];
+			const [moved] = next.splice(from, 1);
+			next.splice(to, 0, moved!);
+			return next;
+		});
+	}
+
+	async removeSlide(storyId: string, slideId: string): Promise<Story> {
+		return this._editSlides(storyId, (slides) => slides.filter((s) => s.id !== slideId));
+	}
+
+	// -----------------------------------------------------------------------
+	// Capture / playback
+	// -----------------------------------------------------------------------
+
+	/**
+	 * Snapshot the active view type and provider states.
+	 */
+	capture(): StorySlideState {
+		const { pafv, filter, density, selection } = this._config;
+		return {
+			view: pafv.getState().viewType,
+			pafv: JSON.parse(pafv.toJSON()) as Record<string, unknown>,
+			filter: JSON.parse(filter.toJSON()) as Record<string, unknown>,
+			density: JSON.parse(density.toJSON()) as Record<string, unknown>,
+			selection: selection.getSelectedIds(),
+		};
+	}
+
+	/**
+	 * Restore a slide: switch view (animated by ViewManager), then restore the
+	 * provider states and selection. Returns false — with the view and every
+	 * provider untouched — when a stored state no longer validates (e.g. it
+	 * references a deleted custom property).
+	 */
+	async applySlide(slide: StorySlide): Promise<boolean> {
+		const { pafv, filter, density, selection } = this._config;
+		const { state } = slide;
+
+		// Validate all three states on scratch providers before changing anything
+		try {
+			new FilterProvider().setState(state.filter);
+			new DensityProvider().setState(state.density);
+			new PAFVProvider().setState(state.pafv);
+		} catch {
+			return false;
+		}
+
+		if (pafv.getState().viewType !== state.view) {
+			await this._config.switchView(state.view);
+		}
+
+		filter.applyState(state.filter);
+		density.setState(state.density);
+		pafv.setState(state.pafv);
+
+		if (state.selection.length > 0) {
+			selection.selectAll(state.selection);
+		} else {
+			selection.clear();
+		}
+		this._config.onApplied();
+		return true;
+	}
+
+	/**
+	 * Subscribe to list changes. Returns an unsubscribe function.
+	 */
+	subscribe(callback: () => void): () => void {
+		this._subscribers.add(callback);
+		return () => this._subscribers.delete(callback);
+	}
+
+	// -----------------------------------------------------------------------
+	// Private
+	// -----------------------------------------------------------------------
+
+	private async _save(input: StoryInput): Promise<Story> {
+		const saved = await this._config.bridge.saveStory(input);
+		await this.refresh();
+		return saved;
+	}
+
+	private async _edit(id: string, change: (story: Story) => Partial<StoryInput>): Promise<Story> {
+		const story = this.get(id);
+		if (!story) throw new Error(`Story not found: ${id}`);
+		return this._save({ id: story.id, name: story.name, slides: story.slides, ...change(story) });
+	}
+
+	private async _editSlides(id: string, change: (slides: StorySlide[]) => StorySlide[]): Promise<Story> {
+		return this._edit(id, (story) => ({ slides: change(story.slides) }));
+	}
+
+	private _notify(): void {
+		this._subscribers.forEach((cb) => cb());
+	}
+}
@@ -0,0 +1,220 @@
+// Isometry v5 — Stories
+// Standalone HTML export: one self-contained file (inline CSS and a few lines
+// of inline script for arrow-key navigation) that can be shared without the app.
+//
+// Each slide shows its markdown caption (marked -> DOMPurify, same pipeline as
+// NotebookExplorer), a summary of the captured view state (view, axes,
+// filters) and the live matching/selected cards from story:export. Everything
+// except the sanitized caption is HTML-escaped.
+
+import DOMPurify from 'dompurify';
+import { marked } from 'marked';
+import type { Story, StorySlideCards, StorySlideState } from '../database/queries/stories';
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/** Captions are prose — no images, inputs or raw containers in a shared file. */
+const CAPTION_SANITIZE_CONFIG = {
+	ALLOWED_TAGS: [
+		'h1',
+		'h2',
+		'h3',
+		'h4',
+		'p',
+		'br',
+		'hr',
+		'ul',
+		'ol',
+		'li',
+		'blockquote',
+		'pre',
+		'code',
+		'strong',
+		'em',
+		'del',
+		'a',
+		'table',
+		'thead',
+		'tbody',
+		'tr',
+		'th',
+		'td',
+	],
+	ALLOWED_ATTR: ['href', 'title'],
+	ALLOW_DATA_ATTR: false,
+};
+
+const STYLES = `
+body { margin: 0; font: 15px/1.5 -apple-system, BlinkMacSystemFont, 'Segoe UI', sans-serif; color: #1d1d1f; background: #f5f5f7; }
+header { padding: 24px 32px 0; }
+h1 { margin: 0; font-size: 24px; }
+.story-meta { color: #6e6e73; font-size: 13px; }
+.slide { display: none; margin: 24px 32px; padding: 24px 32px; background: #fff; border-radius: 12px; box-shadow: 0 1px 3px rgba(0, 0, 0, 0.12); }
+.slide.active { display: block; }
+.slide-number { color: #6e6e73; font-size: 12px; text-transform: uppercase; letter-spacing: 0.05em; }
+.caption { font-size: 17px; }
+.state { display: grid; grid-template-columns: max-content 1fr; gap: 4px 16px; margin: 16px 0; font-size: 13px; }
+.state dt { color: #6e6e73; }
+.state dd { margin: 0; }
+.cards { columns: 2; font-size: 13px; padding-left: 20px; }
+.selected { font-weight: 600; }
+nav { display: flex; gap: 8px; justify-content: center; align-items: center; padding-bottom: 24px; }
+nav button { font: inherit; padding: 4px 16px; }
+@media print { .slide { display: block; break-after: page; box-shadow: none; } nav { display: none; } }
+`;
+
+const SCRIPT = `
+(function () {
+  var slides = document.querySelectorAll('.slide');
+  var status = document.getElementById('status');
+  var index = 0;
+  function show(i) {
+    index = Math.max(0, Math.min(slides.length - 1, i));
+    slides.forEach(function (s, n) { s.classList.toggle('active', n === index); });
+    status.textContent = slides.length ? (index + 1) + ' / ' + slides.length : '0 / 0';
+  }
+  document.getElementById('prev').onclick = function () { show(index - 1); };
+  document.getElementById('next').onclick = function () { show(index + 1); };
+  document.addEventListener('keydown', function (e) {
+    if (e.key === 'ArrowLeft') show(index - 1);
+    if (e.key === 'ArrowRight' || e.key === ' ') show(index + 1);
+  });
+  show(0);
+})();
+`;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+function escapeHtml(text: string): string {
+	return text
+		.replace(/&/g, '&amp;')
+		.replace(/</g, '&lt;')
+		.replace(/>/g, '&gt;')
+		.replace(/"/g, '&quot;')
+		.replace(/'/g, '&#39;');
+}
+
+/**
+ * Markdown caption to sanitized HTML (also used by the Stories panel stage).
+ */
+export function renderCaptionHtml(markdown: string): string {
+	if (markdown.trim() === '') return '';
+	return DOMPurify.sanitize(marked.parse(markdown) as string, CAPTION_SANITIZE_CONFIG);
+}
+
+function axisField(value: unknown): string | null {
+	if (typeof value !== 'object' || value === null) return null;
+	const field = (value as { field?: unknown }).field;
+	return typeof field === 'string' ? field : null;
+}
+
+function axisList(value: unknown): string[] {
+	return Array.isArray(value) ? value.map(axisField).filter((f): f is string => f !== null) : [];
+}
+
+/**
+ * Human-readable rows (label, value) describing a slide's captured state.
+ */
+export function describeSlideState(state: StorySlideState): Array<[string, string]> {
+	const rows: Array<[string, string]> = [['View', state.view.charAt(0).toUpperCase() + state.view.slice(1)]];
+
+	const { pafv, filter } = state;
+	const columns = axisList(pafv['colAxes']);
+	const rowAxes = axisList(pafv['rowAxes']);
+	if (columns.length > 0) rows.push(['Columns', columns.join(', ')]);
+	if (rowAxes.length > 0) rows.push(['Rows', rowAxes.join(', ')]);
+	const groupBy = axisField(pafv['groupBy']);
+	if (groupBy) rows.push(['Grouped by', groupBy]);
+
+	const search = filter['searchQuery'];
+	if (typeof search === 'string' && search !== '') rows.push(['Search', search]);
+
+	const conditions = Array.isArray(filter['filters']) ? (filter['filters'] as Array<Record<string, unknown>>) : [];
+	const filterText = conditions.map(
+		(f) => `${String(f['field'])} ${String(f['operator'])} ${JSON.stringify(f['value'])}`,
+	);
+	for (const [field, values] of Object.entries((filter['axisFilters'] ?? {}) as Record<string, unknown>)) {
+		if (Array.isArray(values) && values.length > 0) filterText.push(`${field} in ${values.join(', ')}`);
+	}
+	for (const [field, range] of Object.entries((filter['rangeFilters'] ?? {}) as Record<string, unknown>)) {
+		const { min, max } = (range ?? {}) as { min?: unknown; max?: unknown };
+		filterText.push(`${field} ${String(min ?? '')}\u2013${String(max ?? '')}`);
+	}
+	if (filter['filterTree']) filterText.push('custom filter group');
+	if (filterText.length > 0) rows.push(['Filters', filterText.join('; ')]);
+
+	return rows;
+}
+
+// ---------------------------------------------------------------------------
+// Export
+// ---------------------------------------------------------------------------
+
+/**
+ * File name for a story export: the story name slugified, `.html`.
+ */
+export function storyExportFilename(story: Pick<Story, 'name'>): string {
+	const slug = story.name
+		.toLowerCase()
+		.replace(/[^a-z0-9]+/g, '-')
+		.replace(/^-+|-+$/g, '');
+	return `${slug || 'story'}.html`;
+}
+
+/**
+ * Render a story as a standalone HTML document.
+ *
+ * @param story - The story to export
+ * @param slides - Live cards per slide, parallel to story.slides (from story:export)
+ */
+export function renderStoryHtml(story: Story, slides: readonly StorySlideCards[]): string {
+	const sections = story.slides.map((slide, i) => {
+		const cards = slides[i] ?? { count: null, cards: [], selected: [] };
+		const selectedIds = new Set(cards.selected.map((c) => c.id));
+		const state = describeSlideState(slide.state)
+			.map(([label, value]) => `<dt>${escapeHtml(label)}</dt><dd>${escapeHtml(value)}</dd>`)
+			.join('');
+		const count = cards.count === null ? 'Filter no longer applies' : `${cards.count} matching cards`;
+		const selected =
+			cards.selected.length > 0
+				? `<dt>Selected</dt><dd>${escapeHtml(cards.selected.map((c) => c.name).join(', '))}</dd>`
+				: '';
+		const list = cards.cards
+			.map((c) => `<li${selectedIds.has(c.id) ? ' class="selected"' : ''}>${escapeHtml(c.name)}</li>`)
+			.join('');
+
+		return `<section class="slide" aria-label="Slide ${i + 1}">
+<div class="slide-number">Slide ${i + 1} of ${story.slides.length}</div>
+<div class="caption">${renderCaptionHtml(slide.caption)}</div>
+<dl class="state">${state}<dt>Cards</dt><dd>${escapeHtml(count)}</dd>${selected}</dl>
+${list ? `<ul class="cards">${list}</ul>` : ''}
+</section>`;
+	});
+
+	const title = escapeHtml(story.name);
+	return `<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>${title}</title>
+<style>${STYLES}</style>
+</head>
+<body>
+<header>
+<h1>${title}</h1>
+<div class="story-meta">${story.slides.length} slides \u00B7 exported ${escapeHtml(new Date().toISOString().slice(0, 10))}</div>
+</header>
+<main>
+${sections.join('\n')}
+</main>
+<nav><button id="prev" type="button">Previous</button><span id="status"></span><button id="next" type="button">Next</button></nav>
+<script>${SCRIPT}</script>
+</body>
+</html>
+`;
+}
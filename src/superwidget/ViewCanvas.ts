@@ -26,6 +26,7 @@ export const VIEW_DISPLAY_NAMES: Record<ViewType, string> = {
   network: 'Network Graph',
   tree: 'Tree',
   supergrid: 'SuperGrid',
+  map: 'Map',
 };
 
 // ---------------------------------------------------------------------------
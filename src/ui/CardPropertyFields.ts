@@ -1,13 +1,22 @@
 // Isometry v5 — Phase 93 Plan 02
 // CardPropertyFields: typed property input panel with 24 fields in 5 collapsible groups,
 // tag chip editor with datalist autocomplete, per-field undo via updateCardMutation,
-// and inline validation error feedback.
+// and inline validation error feedback. A Connections group edits label, weight and
+// via card of the card's edges with undo via updateConnectionMutation. A Related group
+// lists similar cards (search:similar) and opens one on click.
 //
 // Requirements: PROP-01, PROP-02, PROP-03, PROP-04, PROP-05, PROP-06, PROP-08
 
 import '../styles/card-editor-panel.css';
-import type { Card, CardInput, CardType } from '../database/queries/types';
-import { updateCardMutation } from '../mutations/inverses';
+import type {
+	Card,
+	CardInput,
+	CardType,
+	Connection,
+	ConnectionUpdate,
+	SimilarCardResult,
+} from '../database/queries/types';
+import { updateCardMutation, updateConnectionMutation } from '../mutations/inverses';
 import type { MutationManager } from '../mutations/MutationManager';
 import { coerceFieldValue, isCoercionError } from '../utils/card-coerce';
 import type { WorkerBridge } from '../worker/WorkerBridge';
@@ -19,6 +28,8 @@ import type { WorkerBridge } from '../worker/WorkerBridge';
 export interface CardPropertyFieldsConfig {
 	mutations: MutationManager;
 	bridge: WorkerBridge;
+	/** Called when a related card is clicked (e.g. select it) */
+	onOpenCard?: (cardId: string) => void;
 }
 
 // ---------------------------------------------------------------------------
@@ -102,6 +113,34 @@ const CARD_TYPE_LABELS: Record<CardType, string> = {
 	media: 'Media',
 };
 
+/** A connection of the current card plus display names of its endpoints and via card */
+interface ConnectionRow {
+	connection: Connection;
+	/** Name of the card at the other end of the edge */
+	otherName: string;
+	/** True when the current card is the source */
+	outgoing: boolean;
+	viaName: string | null;
+}
+
+const CONNECTIONS_QUERY = `SELECT c.id, c.source_id, c.target_id, c.via_card_id, c.label, c.weight, c.created_at,
+  s.name AS source_name, t.name AS target_name, v.name AS via_name
+FROM connections c
+JOIN cards s ON s.id = c.source_id
+JOIN cards t ON t.id = c.target_id
+LEFT JOIN cards v ON v.id = c.via_card_id
+WHERE (c.source_id = ? OR c.target_id = ?) AND s.deleted_at IS NULL AND t.deleted_at IS NULL
+ORDER BY c.created_at`;
+
+/** Live cards sharing a via card name, offered by the via picker */
+const VIA_CANDIDATES_QUERY = `SELECT id, name, folder, created_at FROM cards
+WHERE name = ? AND deleted_at IS NULL
+ORDER BY created_at
+LIMIT 50`;
+
+/** Number of similar cards shown in the Related group */
+const RELATED_LIMIT = 8;
+
 // ---------------------------------------------------------------------------
 // CardPropertyFields
 // ---------------------------------------------------------------------------
@@ -114,16 +153,25 @@ const CARD_TYPE_LABELS: Record<CardType, string> = {
 export class CardPropertyFields {
 	private readonly _mutations: MutationManager;
 	private readonly _bridge: WorkerBridge;
+	private readonly _onOpenCard: ((cardId: string) => void) | undefined;
 
 	// DOM
 	private _rootEl: HTMLElement | null = null;
 	private _tagDatalistEl: HTMLDataListElement | null = null;
 	private _tagsContainerEl: HTMLElement | null = null;
 	private _tagInputEl: HTMLInputElement | null = null;
+	private _connectionsBodyEl: HTMLElement | null = null;
+	private _relatedBodyEl: HTMLElement | null = null;
 
 	// State
 	private _snapshot: Card | null = null;
 	private _tagSuggestions: string[] = [];
+	private _connections: ConnectionRow[] = [];
+	/** Incremented per update() so stale connection loads are discarded */
+	private _connectionsLoadSeq = 0;
+	/** Incremented per update() so stale related-card loads are discarded */
+	private _relatedLoadSeq = 0;
+	private _unsubscribeMutations: (() => void) | null = null;
 
 	// Input and error element registries, keyed by field name
 	private _inputElements: Map<string, HTMLInputElement | HTMLSelectElement> = new Map();
@@ -131,10 +179,14 @@ export class CardPropertyFields {
 
 	// Bound event handlers (for cleanup)
 	private _boundHandlers: Array<{ el: EventTarget; type: string; handler: EventListenerOrEventListenerObject }> = [];
+	// Connection row handlers are rebuilt on every load, so they are tracked separately
+	private _connectionHandlers: Array<{ el: EventTarget; type: string; handler: EventListenerOrEventListenerObject }> =
+		[];
 
 	constructor(config: CardPropertyFieldsConfig) {
 		this._mutations = config.mutations;
 		this._bridge = config.bridge;
+		this._onOpenCard = config.onOpenCard;
 	}
 
 	// -----------------------------------------------------------------------
@@ -156,7 +208,32 @@ export class CardPropertyFields {
 			this._rootEl.appendChild(groupEl);
 		}
 
+		// Connections group — rows are rendered per card in _renderConnections()
+		const connectionsGroupEl = this._createGroup('Connections', 'connections', []);
+		this._connectionsBodyEl = connectionsGroupEl.querySelector<HTMLElement>('.cpf-group__body');
+		this._rootEl.appendChild(connectionsGroupEl);
+
+		// Related group — similar cards, rendered per card in _renderRelated()
+		const relatedGroupEl = this._createGroup('Related', 'related', []);
+		const relatedBodyEl = relatedGroupEl.querySelector<HTMLElement>('.cpf-group__body');
+		this._relatedBodyEl = relatedBodyEl;
+		if (relatedBodyEl) {
+			// Delegated: rows are rebuilt on every load
+			this._addListener(relatedBodyEl, 'click', (e: Event) => {
+				const rowEl = (e.target as HTMLElement).closest<HTMLElement>('.cpf-related');
+				const cardId = rowEl?.dataset['cardId'];
+				if (cardId) this._onOpenCard?.(cardId);
+			});
+		}
+		this._rootEl.appendChild(relatedGroupEl);
+
 		container.appendChild(this._rootEl);
+
+		// Reload connection rows after every mutation — undo/redo of a connection
+		// edit must not leave the rows (and the `before` of the next edit) stale
+		this._unsubscribeMutations = this._mutations.subscribe(() => {
+			if (this._snapshot) void this._loadConnections(this._snapshot.id);
+		});
 	}
 
 	update(card: Card): void {
@@ -192,6 +269,12 @@ export class CardPropertyFields {
 
 		// Load tag autocomplete suggestions (async fire-and-forget)
 		void this._loadTagSuggestions();
+
+		// Load this card's connections (async fire-and-forget)
+		void this._loadConnections(card.id);
+
+		// Load similar cards (async fire-and-forget)
+		void this._loadRelated(card.id);
 	}
 
 	destroy(): void {
@@ -200,6 +283,9 @@ export class CardPropertyFields {
 			el.removeEventListener(type, handler);
 		}
 		this._boundHandlers = [];
+		this._clearConnectionHandlers();
+		this._unsubscribeMutations?.();
+		this._unsubscribeMutations = null;
 
 		// Remove from DOM
 		this._rootEl?.remove();
@@ -213,8 +299,13 @@ export class CardPropertyFields {
 		this._tagDatalistEl = null;
 		this._tagsContainerEl = null;
 		this._tagInputEl = null;
+		this._connectionsBodyEl = null;
+		this._relatedBodyEl = null;
 		this._snapshot = null;
 		this._tagSuggestions = [];
+		this._connections = [];
+		this._connectionsLoadSeq++;
+		this._relatedLoadSeq++;
 	}
 
 	// -----------------------------------------------------------------------
@@ -647,6 +738,313 @@ export class CardPropertyFields {
 		this._snapshot = { ...this._snapshot, [field]: coerced } as Card;
 	}
 
+	// -----------------------------------------------------------------------
+	// Connections — label / weight / via card editing
+	// -----------------------------------------------------------------------
+
+	private async _loadConnections(cardId: string): Promise<void> {
+		const seq = ++this._connectionsLoadSeq;
+		try {
+			const result = await this._bridge.send('db:query', { sql: CONNECTIONS_QUERY, params: [cardId, cardId] });
+			// Card changed (or panel destroyed) while the query was in flight
+			if (seq !== this._connectionsLoadSeq) return;
+
+			this._connections = result.rows.map((r: Record<string, unknown>) => {
+				const outgoing = r['source_id'] === cardId;
+				return {
+					connection: {
+						id: r['id'] as string,
+						source_id: r['source_id'] as string,
+						target_id: r['target_id'] as string,
+						via_card_id: (r['via_card_id'] as string | null) ?? null,
+						label: (r['label'] as string | null) ?? null,
+						weight: r['weight'] as number,
+						created_at: r['created_at'] as string,
+					},
+					otherName: (outgoing ? r['target_name'] : r['source_name']) as string,
+					outgoing,
+					viaName: (r['via_name'] as string | null) ?? null,
+				};
+			});
+		} catch {
+			if (seq !== this._connectionsLoadSeq) return;
+			this._connections = [];
+		}
+		this._renderConnections();
+	}
+
+	private _renderConnections(): void {
+		const bodyEl = this._connectionsBodyEl;
+		if (!bodyEl) return;
+
+		this._clearConnectionHandlers();
+		bodyEl.innerHTML = '';
+
+		if (this._connections.length === 0) {
+			const emptyEl = document.createElement('p');
+			emptyEl.className = 'cpf-conn__empty';
+			emptyEl.textContent = 'No connections';
+			bodyEl.appendChild(emptyEl);
+			return;
+		}
+
+		for (const row of this._connections) {
+			bodyEl.appendChild(this._createConnectionRow(row));
+		}
+	}
+
+	private _createConnectionRow(row: ConnectionRow): HTMLElement {
+		const connEl = document.createElement('div');
+		connEl.className = 'cpf-conn';
+		connEl.dataset['connectionId'] = row.connection.id;
+
+		const endpointEl = document.createElement('div');
+		endpointEl.className = 'cpf-conn__endpoint';
+		endpointEl.textContent = `${row.outgoing ? '\u2192' : '\u2190'} ${row.otherName}`; // → / ←
+		connEl.appendChild(endpointEl);
+
+		const errorEl = document.createElement('p');
+		errorEl.className = 'cpf-row__error';
+		errorEl.setAttribute('aria-live', 'polite');
+		errorEl.style.display = 'none';
+
+		// Label
+		const labelInput = document.createElement('input');
+		labelInput.type = 'text';
+		labelInput.className = 'cpf-input';
+		labelInput.placeholder = 'Label';
+		labelInput.value = row.connection.label ?? '';
+		this._addConnectionListener(labelInput, 'blur', () => {
+			const value = labelInput.value.trim();
+			void this._commitConnection(row, { label: value === '' ? null : value }, labelInput, errorEl);
+		});
+		connEl.appendChild(this._createConnectionField('Label', labelInput));
+
+		// Weight (0..1)
+		const weightInput = document.createElement('input');
+		weightInput.type = 'number';
+		weightInput.className = 'cpf-input';
+		weightInput.min = '0';
+		weightInput.max = '1';
+		weightInput.step = '0.1';
+		weightInput.value = String(row.connection.weight);
+		this._addConnectionListener(weightInput, 'blur', () => {
+			const weight = Number(weightInput.value);
+			if (weightInput.value.trim() === '' || !Number.isFinite(weight) || weight < 0 || weight > 1) {
+				this._showConnectionError(weightInput, errorEl, 'Weight must be between 0 and 1');
+				weightInput.value = String(row.connection.weight);
+				return;
+			}
+			void this._commitConnection(row, { weight }, weightInput, errorEl);
+		});
+		connEl.appendChild(this._createConnectionField('Weight', weightInput));
+
+		// Via card — resolved by name (picker when several cards share it); empty clears it
+		const viaInput = document.createElement('input');
+		viaInput.type = 'text';
+		viaInput.className = 'cpf-input';
+		viaInput.placeholder = 'Via card name';
+		viaInput.value = row.viaName ?? '';
+		this._addConnectionListener(viaInput, 'blur', () => {
+			void this._commitVia(row, viaInput, errorEl);
+		});
+		connEl.appendChild(this._createConnectionField('Via', viaInput));
+
+		connEl.appendChild(errorEl);
+		return connEl;
+	}
+
+	private _createConnectionField(label: string, inputEl: HTMLInputElement): HTMLElement {
+		const rowEl = document.createElement('div');
+		rowEl.className = 'cpf-row';
+
+		const labelEl = document.createElement('label');
+		labelEl.className = 'cpf-row__label';
+		labelEl.textContent = label;
+
+		const controlEl = document.createElement('div');
+		controlEl.className = 'cpf-row__control';
+		controlEl.appendChild(inputEl);
+
+		rowEl.appendChild(labelEl);
+		rowEl.appendChild(controlEl);
+		return rowEl;
+	}
+
+	private async _commitVia(row: ConnectionRow, inputEl: HTMLInputElement, errorEl: HTMLElement): Promise<void> {
+		const name = inputEl.value.trim();
+		if (name === (row.viaName ?? '')) return;
+
+		if (name === '') {
+			await this._commitConnection(row, { via_card_id: null }, inputEl, errorEl, null);
+			return;
+		}
+
+		let candidates: Array<Record<string, unknown>>;
+		try {
+			const result = await this._bridge.send('db:query', { sql: VIA_CANDIDATES_QUERY, params: [name] });
+			candidates = result.rows;
+		} catch {
+			candidates = [];
+		}
+
+		if (candidates.length === 0) {
+			this._showConnectionError(inputEl, errorEl, `No card named "${name}"`);
+			inputEl.value = row.viaName ?? '';
+			return;
+		}
+
+		if (candidates.length === 1) {
+			await this._commitConnection(row, { via_card_id: candidates[0]!['id'] as string }, inputEl, errorEl, name);
+			return;
+		}
+
+		this._showViaPicker(row, inputEl, errorEl, name, candidates);
+	}
+
+	/**
+	 * Several cards share the typed name — let the user pick one by id instead
+	 * of guessing. Options are told apart by folder and creation date.
+	 */
+	private _showViaPicker(
+		row: ConnectionRow,
+		inputEl: HTMLInputElement,
+		errorEl: HTMLElement,
+		name: string,
+		candidates: Array<Record<string, unknown>>,
+	): void {
+		const controlEl = inputEl.parentElement;
+		if (!controlEl) return;
+		controlEl.querySelector('.cpf-conn__via-picker')?.remove();
+
+		const pickerEl = document.createElement('select');
+		pickerEl.className = 'cpf-input cpf-conn__via-picker';
+		pickerEl.setAttribute('aria-label', `Choose the card named ${name}`);
+
+		const promptEl = document.createElement('option');
+		promptEl.value = '';
+		promptEl.textContent = `${candidates.length} cards named "${name}"\u2026`; // …
+		pickerEl.appendChild(promptEl);
+
+		for (const candidate of candidates) {
+			const optionEl = document.createElement('option');
+			optionEl.value = candidate['id'] as string;
+			const folder = (candidate['folder'] as string | null) ?? 'No folder';
+			const created = String(candidate['created_at'] ?? '').slice(0, 10);
+			optionEl.textContent = `${name} \u2014 ${folder}, ${created}`; // —
+			pickerEl.appendChild(optionEl);
+		}
+
+		this._addConnectionListener(pickerEl, 'change', () => {
+			const viaId = pickerEl.value;
+			if (viaId === '') return;
+			pickerEl.remove();
+			void this._commitConnection(row, { via_card_id: viaId }, inputEl, errorEl, name);
+		});
+		controlEl.appendChild(pickerEl);
+		pickerEl.focus();
+	}
+
+	private async _commitConnection(
+		row: ConnectionRow,
+		updates: ConnectionUpdate,
+		inputEl: HTMLInputElement,
+		errorEl: HTMLElement,
+		viaName?: string | null,
+	): Promise<void> {
+		inputEl.classList.remove('cpf-input--error');
+		errorEl.style.display = 'none';
+
+		// No-op if every value matches the current connection
+		const before = row.connection;
+		const changed = (Object.keys(updates) as Array<keyof ConnectionUpdate>).some((k) => updates[k] !== before[k]);
+		if (!changed) return;
+
+		try {
+			await this._mutations.execute(updateConnectionMutation(before, updates));
+		} catch (err) {
+			// e.g. UNIQUE(source_id, target_id, via_card_id, label) violation
+			const message = err instanceof Error ? err.message : String(err);
+			this._showConnectionError(inputEl, errorEl, message);
+			// Revert input to last committed value
+			if ('via_card_id' in updates) inputEl.value = row.viaName ?? '';
+			else if ('weight' in updates) inputEl.value = String(before.weight);
+			else inputEl.value = before.label ?? '';
+			return;
+		}
+
+		row.connection = { ...before, ...updates } as Connection;
+		if (viaName !== undefined) row.viaName = viaName;
+	}
+
+	private _showConnectionError(inputEl: HTMLInputElement, errorEl: HTMLElement, message: string): void {
+		inputEl.classList.add('cpf-input--error');
+		errorEl.textContent = message;
+		errorEl.style.display = '';
+	}
+
+	private _addConnectionListener(el: EventTarget, type: string, handler: EventListenerOrEventListenerObject): void {
+		el.addEventListener(type, handler);
+		this._connectionHandlers.push({ el, type, handler });
+	}
+
+	private _clearConnectionHandlers(): void {
+		for (const { el, type, handler } of this._connectionHandlers) {
+			el.removeEventListener(type, handler);
+		}
+		this._connectionHandlers = [];
+	}
+
+	// -----------------------------------------------------------------------
+	// Related — similar cards by TF-IDF score
+	// -----------------------------------------------------------------------
+
+	private async _loadRelated(cardId: string): Promise<void> {
+		const seq = ++this._relatedLoadSeq;
+		let related: SimilarCardResult[];
+		try {
+			related = await this._bridge.findSimilarCards(cardId, RELATED_LIMIT);
+		} catch {
+			related = [];
+		}
+		// Card changed (or panel destroyed) while the query was in flight
+		if (seq !== this._relatedLoadSeq) return;
+		this._renderRelated(related);
+	}
+
+	private _renderRelated(related: SimilarCardResult[]): void {
+		const bodyEl = this._relatedBodyEl;
+		if (!bodyEl) return;
+		bodyEl.innerHTML = '';
+
+		if (related.length === 0) {
+			const emptyEl = document.createElement('p');
+			emptyEl.className = 'cpf-conn__empty';
+			emptyEl.textContent = 'No similar cards';
+			bodyEl.appendChild(emptyEl);
+			return;
+		}
+
+		for (const { card, score } of related) {
+			const rowEl = document.createElement('button');
+			rowEl.type = 'button';
+			rowEl.className = 'cpf-related';
+			rowEl.dataset['cardId'] = card.id;
+
+			const nameEl = document.createElement('span');
+			nameEl.className = 'cpf-related__name';
+			nameEl.textContent = card.name;
+
+			const scoreEl = document.createElement('span');
+			scoreEl.className = 'cpf-related__score';
+			scoreEl.textContent = `${Math.round(score * 100)}%`;
+
+			rowEl.appendChild(nameEl);
+			rowEl.appendChild(scoreEl);
+			bodyEl.appendChild(rowEl);
+		}
+	}
+
 	// -----------------------------------------------------------------------
 	// Utility: register event listener for cleanup
 	// -----------------------------------------------------------------------
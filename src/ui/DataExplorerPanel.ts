@@ -9,6 +9,7 @@
 //   - Catalog section body is a mount point for SuperGrid (Plan 03 mounts real SuperGrid)
 //   - Apps section is a stub with "Coming soon"
 //   - HTML5 drag-and-drop on Import zone (no library dependency)
+//   - Optional Obsidian vault import via a directory picker (webkitdirectory)
 //   - DB Utilities shows stats (card_count, connection_count, db_size_bytes) and action buttons
 
 import type { CollapsibleSectionConfig } from './CollapsibleSection';
@@ -21,12 +22,14 @@ import '../styles/data-explorer.css';
 
 export interface DataExplorerPanelConfig {
 	onImportFile: () => void;
-	onExport: (format: 'csv' | 'json' | 'markdown') => void;
+	onExport: (format: 'csv' | 'json' | 'markdown' | 'opml' | 'ics') => void;
 	onExportDatabase: () => void;
 	onVacuum: () => Promise<void>;
 	onFileDrop: (file: File) => void;
 	onSelectCard: (cardId: string) => void;
 	onPickAltoDirectory: () => void; // DISC-01: trigger native directory picker
+	onImportVault?: (files: File[]) => void; // Obsidian vault: every file of the picked folder
+	onManageEnrichers?: () => void; // Import enricher settings + re-run
 }
 
 // ---------------------------------------------------------------------------
@@ -47,7 +50,7 @@ const DEXP_SECTIONS: CollapsibleSectionConfig[] = [
 /**
  * DataExplorerPanel is the top-level UI shell for the Data Explorer sidebar.
  * It contains 4 CollapsibleSection panels:
- *   - Import/Export: import button, drag-drop zone, 3 export format buttons
+ *   - Import/Export: import button, drag-drop zone, 5 export format buttons
  *   - Catalog: mount point for SuperGrid (Plan 03 mounts the real SuperGrid)
  *   - Apps: stub with "Coming soon"
  *   - DB Utilities: stat rows + vacuum + export DB buttons
@@ -243,7 +246,7 @@ export class DataExplorerPanel {
 	 * Build the Import / Export section content.
 	 * - Import File CTA button
 	 * - Drag-and-drop zone with HTML5 drag events
-	 * - 3 ghost-style export buttons (CSV, JSON, Markdown)
+	 * - 5 ghost-style export buttons (CSV, JSON, Markdown, OPML, iCalendar)
 	 */
 	private _buildImportExportSection(section: CollapsibleSection): void {
 		const body = section.getBodyEl();
@@ -264,7 +267,7 @@ export class DataExplorerPanel {
 		// Hidden file input for click-to-browse (WKWebView fallback — DND-04)
 		const fileInput = document.createElement('input');
 		fileInput.type = 'file';
-		fileInput.accept = '.csv,.json,.md,.txt,.yaml,.yml,.xlsx,.xls';
+		fileInput.accept = '.csv,.json,.md,.txt,.yaml,.yml,.xlsx,.xls,.opml,.ics,.vcf,.eml,.mbox';
 		fileInput.style.display = 'none';
 		fileInput.addEventListener('change', () => {
 			const file = fileInput.files?.[0];
@@ -283,6 +286,28 @@ export class DataExplorerPanel {
 		browseBtn.addEventListener('click', () => fileInput.click());
 		wrapper.appendChild(browseBtn);
 
+		// Import Obsidian Vault — hidden directory input, whole folder at once
+		const onImportVault = this._config.onImportVault;
+		if (onImportVault) {
+			const vaultInput = document.createElement('input');
+			vaultInput.type = 'file';
+			vaultInput.webkitdirectory = true;
+			vaultInput.style.display = 'none';
+			vaultInput.addEventListener('change', () => {
+				const files = Array.from(vaultInput.files ?? []);
+				if (files.length > 0) onImportVault(files);
+				vaultInput.value = '';
+			});
+			wrapper.appendChild(vaultInput);
+
+			const vaultBtn = document.createElement('button');
+			vaultBtn.type = 'button';
+			vaultBtn.className = 'data-explorer__import-btn';
+			vaultBtn.textContent = 'Import Obsidian Vault...';
+			vaultBtn.addEventListener('click', () => vaultInput.click());
+			wrapper.appendChild(vaultBtn);
+		}
+
 		// Choose Alto-Index Folder button (DISC-01) -- only in native WKWebView context
 		if (window.webkit?.messageHandlers?.nativeBridge) {
 			const altoBtn = document.createElement('button');
@@ -351,10 +376,12 @@ export class DataExplorerPanel {
 		const exportRow = document.createElement('div');
 		exportRow.className = 'data-explorer__export-row';
 
-		const exportFormats: Array<{ label: string; format: 'csv' | 'json' | 'markdown' }> = [
+		const exportFormats: Array<{ label: string; format: 'csv' | 'json' | 'markdown' | 'opml' | 'ics' }> = [
 			{ label: 'CSV', format: 'csv' },
 			{ label: 'JSON', format: 'json' },
 			{ label: 'Markdown', format: 'markdown' },
+			{ label: 'OPML', format: 'opml' },
+			{ label: 'iCalendar', format: 'ics' },
 		];
 
 		for (const { label, format } of exportFormats) {
@@ -430,6 +457,7 @@ export class DataExplorerPanel {
 	 * - 3 stat rows: Cards, Connections, Database size
 	 * - Vacuum / Optimize button (destructive)
 	 * - Export Database button (ghost)
+	 * - Enrichers button (ghost, when onManageEnrichers is set)
 	 */
 	private _buildDbUtilitiesSection(section: CollapsibleSection): void {
 		const body = section.getBodyEl();
@@ -501,6 +529,17 @@ export class DataExplorerPanel {
 
 		actionsContainer.appendChild(vacuumBtn);
 		actionsContainer.appendChild(exportDbBtn);
+
+		// Enrichers button (ghost) — only when the host handles it
+		const onManageEnrichers = this._config.onManageEnrichers;
+		if (onManageEnrichers) {
+			const enrichersBtn = document.createElement('button');
+			enrichersBtn.type = 'button';
+			enrichersBtn.className = 'data-explorer__export-db-btn data-explorer__enrichers-btn';
+			enrichersBtn.textContent = 'Enrichers\u2026';
+			enrichersBtn.addEventListener('click', () => onManageEnrichers());
+			actionsContainer.appendChild(enrichersBtn);
+		}
 		body.appendChild(actionsContainer);
 
 		// Recent Cards heading
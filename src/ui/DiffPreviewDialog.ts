@@ -1,7 +1,9 @@
 // Isometry v5 — Phase 125 Plan 02
 // DiffPreviewDialog: modal showing new/modified/deleted card diffs before re-import commit.
+// Incremental directory re-imports group the card changes by file instead of by kind.
 // Requirements: DSET-04
 
+import type { ReimportFileChange } from '../etl/FileManifest';
 import '../styles/diff-preview.css';
 
 export interface DiffPreviewData {
@@ -10,9 +12,37 @@ export interface DiffPreviewData {
 	toUpdate: Array<{ id: string; name: string }>;
 	deletedIds: string[];
 	deletedNames: string[];
+	/** Cards carried over from a renamed file */
+	renamed?: Array<{ id: string; name: string }>;
 	unchanged: number;
+	/** Changed files of an incremental re-scan — when present, sections are per file */
+	files?: ReimportFileChange[];
 }
 
+/** One collapsible section: a change kind, or a changed file */
+interface DiffSection {
+	id: string;
+	kind: string;
+	label: string;
+	/** Card names; `kind` marks each card's change inside a file section */
+	items: Array<{ name: string; kind?: string }>;
+}
+
+const FILE_CHANGE_LABELS: Record<ReimportFileChange['change'], string> = {
+	added: 'Added',
+	modified: 'Modified',
+	removed: 'Removed',
+	renamed: 'Renamed',
+};
+
+/** Section kind (border color) per file change, matching the card badges */
+const FILE_CHANGE_KINDS: Record<ReimportFileChange['change'], string> = {
+	added: 'new',
+	modified: 'modified',
+	removed: 'deleted',
+	renamed: 'renamed',
+};
+
 export const DiffPreviewDialog = {
 	/**
 	 * Show diff preview modal. Resolves true if user clicks "Commit Changes",
@@ -40,6 +70,7 @@ export const DiffPreviewDialog = {
 				{ kind: 'new', count: data.toInsert.length, label: 'new' },
 				{ kind: 'modified', count: data.toUpdate.length, label: 'modified' },
 				{ kind: 'deleted', count: data.deletedIds.length, label: 'deleted' },
+				{ kind: 'renamed', count: data.renamed?.length ?? 0, label: 'renamed' },
 			];
 
 			for (const b of badges) {
@@ -55,21 +86,36 @@ export const DiffPreviewDialog = {
 			const sectionsEl = document.createElement('div');
 			sectionsEl.className = 'dset-diff-sections';
 
-			const sections = [
-				{ kind: 'new', label: 'New', count: data.toInsert.length, names: data.toInsert.map((c) => c.name) },
-				{ kind: 'modified', label: 'Modified', count: data.toUpdate.length, names: data.toUpdate.map((c) => c.name) },
-				{ kind: 'deleted', label: 'Deleted', count: data.deletedIds.length, names: data.deletedNames },
-			];
+			const sections: DiffSection[] =
+				data.files && data.files.length > 0
+					? data.files.map((file, i) => ({
+							id: `file-${i}`,
+							kind: FILE_CHANGE_KINDS[file.change],
+							label:
+								`${FILE_CHANGE_LABELS[file.change]} \u2014 ${file.path || 'No longer in directory'}` +
+								(file.previousPath ? ` (was ${file.previousPath})` : ''),
+							items: file.cards.map((c) => ({ name: c.name, kind: c.change })),
+						}))
+					: [
+							{ id: 'new', kind: 'new', label: 'New', items: data.toInsert.map((c) => ({ name: c.name })) },
+							{
+								id: 'modified',
+								kind: 'modified',
+								label: 'Modified',
+								items: data.toUpdate.map((c) => ({ name: c.name })),
+							},
+							{ id: 'deleted', kind: 'deleted', label: 'Deleted', items: data.deletedNames.map((name) => ({ name })) },
+						];
 
 			for (const sec of sections) {
-				if (sec.count === 0) continue; // Skip empty sections entirely
+				if (sec.items.length === 0) continue; // Skip empty sections entirely
 
 				const sectionEl = document.createElement('div');
 				sectionEl.className = 'dset-diff-section';
 				sectionEl.dataset['kind'] = sec.kind;
 
-				const bodyId = `diff-body-${sec.kind}`;
-				const sectionId = `diff-section-${sec.kind}`;
+				const bodyId = `diff-body-${sec.id}`;
+				const sectionId = `diff-section-${sec.id}`;
 
 				// Section header button
 				const headerBtn = document.createElement('button');
@@ -84,20 +130,21 @@ export const DiffPreviewDialog = {
 				chevron.textContent = '\u25B6'; // ▶
 
 				const labelSpan = document.createElement('span');
-				labelSpan.textContent = `${sec.label} (${sec.count})`;
+				labelSpan.textContent = `${sec.label} (${sec.items.length})`;
 
 				headerBtn.appendChild(chevron);
 				headerBtn.appendChild(labelSpan);
 
-				// Section body (card name list)
+				// Section body (card name list; per-file lists mark each card's change)
 				const bodyEl = document.createElement('ul');
 				bodyEl.className = 'dset-diff-section__body';
 				bodyEl.id = bodyId;
 				bodyEl.setAttribute('role', 'list');
 
-				for (const name of sec.names) {
+				for (const item of sec.items) {
 					const li = document.createElement('li');
-					li.textContent = name;
+					li.textContent = item.name;
+					if (item.kind) li.dataset['kind'] = item.kind;
 					bodyEl.appendChild(li);
 				}
 
@@ -0,0 +1,191 @@
+// Isometry v5 — EnrichersDialog
+// Modal listing the import enrichers with an on/off switch per source type, and
+// a Re-run action that applies the enabled enrichers to cards already imported.
+
+import { isEnricherEnabled } from '../etl/enrichment/registry';
+import type { EnricherInfo, EnricherSetting } from '../etl/enrichment/types';
+import '../styles/enrichers.css';
+
+export interface EnrichersDialogData {
+	enrichers: EnricherInfo[];
+	settings: EnricherSetting[];
+	/** Persist a switch as soon as a checkbox changes */
+	onChange: (setting: EnricherSetting) => Promise<void> | void;
+}
+
+/** Source types offered in the picker; '*' sets the default for every source */
+const SOURCE_OPTIONS: ReadonlyArray<[string, string]> = [
+	['*', 'All sources'],
+	['apple_notes', 'Apple Notes'],
+	['markdown', 'Markdown'],
+	['obsidian', 'Obsidian'],
+	['opml', 'OPML'],
+	['ics', 'Calendar (ICS)'],
+	['vcard', 'Contacts (vCard)'],
+	['email', 'Email'],
+	['bookmarks', 'Bookmarks'],
+	['csv', 'CSV'],
+	['excel', 'Excel'],
+	['json', 'JSON'],
+	['html', 'HTML'],
+	['native_reminders', 'Reminders'],
+	['native_calendar', 'Calendar'],
+	['native_notes', 'Notes'],
+];
+
+/** Whether an enricher is on for the picked source ('*': the all-sources setting or default) */
+function isChecked(enricher: EnricherInfo, sourceType: string, settings: readonly EnricherSetting[]): boolean {
+	if (sourceType !== '*') return isEnricherEnabled(enricher, sourceType, settings);
+	const all = settings.find((s) => s.enricher_id === enricher.id && s.source_type === '*');
+	return all?.enabled ?? enricher.defaultEnabled;
+}
+
+export const EnrichersDialog = {
+	/**
+	 * Show the enricher settings. Switches persist immediately through onChange.
+	 * Resolves true when the user asks to re-run the enrichers, false on Close/Escape.
+	 */
+	show(data: EnrichersDialogData): Promise<boolean> {
+		return new Promise<boolean>((resolve) => {
+			const settings = [...data.settings];
+
+			const dialog = document.createElement('dialog');
+			dialog.className = 'enrichers-modal';
+			dialog.setAttribute('aria-labelledby', 'enrichers-title');
+			dialog.setAttribute('aria-describedby', 'enrichers-message');
+			dialog.setAttribute('aria-modal', 'true');
+
+			// Title
+			const titleEl = document.createElement('h2');
+			titleEl.id = 'enrichers-title';
+			titleEl.className = 'enrichers-modal__title';
+			titleEl.textContent = 'Import Enrichers';
+
+			const messageEl = document.createElement('p');
+			messageEl.id = 'enrichers-message';
+			messageEl.className = 'enrichers-modal__message';
+			messageEl.textContent =
+				'Enrichers derive extra fields while importing. Changes apply to the next import, or re-run them now.';
+
+			// Source type picker
+			const sourceLabel = document.createElement('label');
+			sourceLabel.className = 'enrichers-modal__source';
+			sourceLabel.textContent = 'Source';
+			const sourceSelect = document.createElement('select');
+			for (const [value, label] of SOURCE_OPTIONS) {
+				const option = document.createElement('option');
+				option.value = value;
+				option.textContent = label;
+				sourceSelect.appendChild(option);
+			}
+			sourceLabel.appendChild(sourceSelect);
+
+			// One checkbox row per enricher
+			const listEl = document.createElement('ul');
+			listEl.className = 'enrichers-list';
+			listEl.setAttribute('role', 'list');
+
+			const checkboxes: HTMLInputElement[] = [];
+			for (const enricher of data.enrichers) {
+				const li = document.createElement('li');
+				li.className = 'enrichers-list__item';
+
+				const label = document.createElement('label');
+				label.className = 'enrichers-list__label';
+
+				const checkbox = document.createElement('input');
+				checkbox.type = 'checkbox';
+				checkbox.value = enricher.id;
+				checkboxes.push(checkbox);
+
+				const descriptionSpan = document.createElement('span');
+				descriptionSpan.className = 'enrichers-list__description';
+				descriptionSpan.textContent = enricher.description;
+
+				label.appendChild(checkbox);
+				label.appendChild(descriptionSpan);
+				li.appendChild(label);
+				listEl.appendChild(li);
+
+				checkbox.addEventListener('change', () => {
+					const setting: EnricherSetting = {
+						enricher_id: enricher.id,
+						source_type: sourceSelect.value,
+						enabled: checkbox.checked,
+					};
+					const idx = settings.findIndex(
+						(s) => s.enricher_id === setting.enricher_id && s.source_type === setting.source_type,
+					);
+					if (idx >= 0) settings[idx] = setting;
+					else settings.push(setting);
+					void data.onChange(setting);
+				});
+			}
+
+			// Checkboxes reflect the picked source; enrichers limited to other sources are disabled
+			const syncCheckboxes = (): void => {
+				const sourceType = sourceSelect.value;
+				data.enrichers.forEach((enricher, i) => {
+					const checkbox = checkboxes[i]!;
+					const applies =
+						sourceType === '*' ||
+						enricher.appliesTo === '*' ||
+						(enricher.appliesTo as readonly string[]).includes(sourceType);
+					checkbox.disabled = !applies;
+					checkbox.checked = applies && isChecked(enricher, sourceType, settings);
+				});
+			};
+			sourceSelect.addEventListener('change', syncCheckboxes);
+			syncCheckboxes();
+
+			// Actions row (reuses app-dialog pattern)
+			const actionsEl = document.createElement('div');
+			actionsEl.className = 'app-dialog__actions';
+
+			const closeBtn = document.createElement('button');
+			closeBtn.type = 'button';
+			closeBtn.className = 'app-dialog__btn app-dialog__btn--cancel';
+			closeBtn.textContent = 'Close';
+
+			const rerunBtn = document.createElement('button');
+			rerunBtn.type = 'button';
+			rerunBtn.className = 'app-dialog__btn app-dialog__btn--confirm';
+			rerunBtn.textContent = 'Re-run on Imported Cards';
+
+			actionsEl.appendChild(closeBtn);
+			actionsEl.appendChild(rerunBtn);
+
+			// Assemble dialog
+			dialog.appendChild(titleEl);
+			dialog.appendChild(messageEl);
+			dialog.appendChild(sourceLabel);
+			dialog.appendChild(listEl);
+			dialog.appendChild(actionsEl);
+			document.body.appendChild(dialog);
+
+			// Cleanup helper
+			const cleanup = (result: boolean) => {
+				dialog.close();
+				dialog.remove();
+				resolve(result);
+			};
+
+			rerunBtn.addEventListener('click', () => cleanup(true));
+			closeBtn.addEventListener('click', () => cleanup(false));
+
+			// Escape = close
+			dialog.addEventListener('cancel', (e) => {
+				e.preventDefault();
+				cleanup(false);
+			});
+
+			// Backdrop click = close
+			dialog.addEventListener('click', (e) => {
+				if (e.target === dialog) cleanup(false);
+			});
+
+			dialog.showModal();
+			closeBtn.focus();
+		});
+	},
+};
@@ -0,0 +1,421 @@
+// Isometry v5 — FilterBuilder
+// Visual editor for FilterProvider's boolean filter tree (AND/OR/NOT groups).
+//
+// Design:
+//   - mount/destroy lifecycle matching the other explorer components
+//   - Edits a local draft tree; every edit commits a pruned copy through
+//     FilterProvider.setFilterTree() (allowlist validation stays in the provider)
+//   - Unfinished conditions (no value yet) stay in the draft but are not committed
+//   - Structural edits re-render; value typing commits after a 300ms debounce
+//     without re-rendering so the input keeps focus
+//   - External tree changes (saved search, clear all, undo of state) reload the draft
+//   - Fields come from SchemaProvider.getFilterableColumns(), with the frozen
+//     allowlist as boot-time fallback
+
+import '../styles/filter-builder.css';
+
+import { ALLOWED_FILTER_FIELDS } from '../providers/allowlist';
+import { type FilterProvider, MAX_FILTER_TREE_DEPTH } from '../providers/FilterProvider';
+import type { SchemaProvider } from '../providers/SchemaProvider';
+import type { FilterCombinator, FilterGroup, FilterNode, FilterOperator } from '../providers/types';
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+export interface FilterBuilderConfig {
+	filter: FilterProvider;
+	/** Optional SchemaProvider for the field list (falls back to the allowlist). */
+	schema?: SchemaProvider | undefined;
+}
+
+/** Draft condition — value kept as typed text until committed. */
+interface DraftCondition {
+	kind: 'condition';
+	field: string;
+	operator: FilterOperator;
+	text: string;
+}
+
+interface DraftGroup {
+	kind: 'group';
+	combinator: FilterCombinator;
+	negate: boolean;
+	children: Array<DraftCondition | DraftGroup>;
+}
+
+interface FieldOption {
+	name: string;
+	label: string;
+	numeric: boolean;
+}
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+const OPERATOR_LABELS: ReadonlyArray<[FilterOperator, string]> = [
+	['eq', '='],
+	['neq', '\u2260'],
+	['gt', '>'],
+	['gte', '\u2265'],
+	['lt', '<'],
+	['lte', '\u2264'],
+	['contains', 'contains'],
+	['startsWith', 'starts with'],
+	['in', 'is one of'],
+	['isNull', 'is empty'],
+	['isNotNull', 'is not empty'],
+];
+
+/** Operators that take no value input. */
+const VALUELESS_OPERATORS: ReadonlySet<FilterOperator> = new Set(['isNull', 'isNotNull']);
+
+const COMBINATOR_LABELS: ReadonlyArray<[FilterCombinator, string]> = [
+	['and', 'All of'],
+	['or', 'Any of'],
+];
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+function fieldDisplayName(field: string): string {
+	return field
+		.split('_')
+		.map((w) => w.charAt(0).toUpperCase() + w.slice(1))
+		.join(' ');
+}
+
+function emptyGroup(): DraftGroup {
+	return { kind: 'group', combinator: 'and', negate: false, children: [] };
+}
+
+function toDraft(group: FilterGroup): DraftGroup {
+	return {
+		kind: 'group',
+		combinator: group.combinator,
+		negate: group.negate,
+		children: group.children.map((child: FilterNode) => {
+			if ('kind' in child && child.kind === 'group') return toDraft(child);
+			const condition = child as Exclude<FilterNode, FilterGroup>;
+			const value = condition.value;
+			const text = Array.isArray(value) ? value.join(', ') : value === null || value === undefined ? '' : String(value);
+			return { kind: 'condition', field: condition.field, operator: condition.operator, text };
+		}),
+	};
+}
+
+// ---------------------------------------------------------------------------
+// FilterBuilder
+// ---------------------------------------------------------------------------
+
+export class FilterBuilder {
+	private readonly _config: FilterBuilderConfig;
+	private _rootEl: HTMLElement | null = null;
+	private _draft: DraftGroup = emptyGroup();
+	/** JSON of the tree last committed to (or loaded from) the provider */
+	private _committedJson = 'null';
+	private _unsubFilter: (() => void) | null = null;
+	private _debounceTimer: ReturnType<typeof setTimeout> | null = null;
+
+	constructor(config: FilterBuilderConfig) {
+		this._config = config;
+	}
+
+	// ---------------------------------------------------------------------------
+	// Lifecycle
+	// ---------------------------------------------------------------------------
+
+	mount(container: HTMLElement): void {
+		const root = document.createElement('div');
+		root.className = 'filter-builder';
+		this._rootEl = root;
+		container.appendChild(root);
+
+		this._loadFromProvider();
+		this._render();
+
+		this._unsubFilter = this._config.filter.subscribe(() => {
+			const json = JSON.stringify(this._config.filter.getFilterTree());
+			if (json === this._committedJson) return;
+			this._loadFromProvider();
+			this._render();
+		});
+	}
+
+	destroy(): void {
+		if (this._unsubFilter) {
+			this._unsubFilter();
+			this._unsubFilter = null;
+		}
+		if (this._debounceTimer !== null) {
+			clearTimeout(this._debounceTimer);
+			this._debounceTimer = null;
+		}
+		if (this._rootEl) {
+			this._rootEl.remove();
+			this._rootEl = null;
+		}
+	}
+
+	// ---------------------------------------------------------------------------
+	// Draft <-> provider
+	// ---------------------------------------------------------------------------
+
+	private _loadFromProvider(): void {
+		const tree = this._config.filter.getFilterTree();
+		this._committedJson = JSON.stringify(tree);
+		this._draft = tree ? toDraft(tree) : emptyGroup();
+	}
+
+	/** Commit the completed part of the draft. An empty tree clears the provider's tree. */
+	private _commit(): void {
+		const tree = this._buildGroup(this._draft);
+		const next = tree.children.length > 0 ? tree : null;
+		const json = JSON.stringify(next);
+		if (json === this._committedJson) return;
+		this._committedJson = json;
+		this._config.filter.setFilterTree(next);
+	}
+
+	/** Convert a draft group to a FilterGroup, dropping unfinished conditions and empty groups. */
+	private _buildGroup(group: DraftGroup): FilterGroup {
+		const numeric = new Set(
+			this._getFieldOptions()
+				.filter((f) => f.numeric)
+				.map((f) => f.name),
+		);
+		const children: FilterNode[] = [];
+		for (const child of group.children) {
+			if (child.kind === 'group') {
+				const built = this._buildGroup(child);
+				if (built.children.length > 0) children.push(built);
+				continue;
+			}
+			if (VALUELESS_OPERATORS.has(child.operator)) {
+				children.push({ field: child.field, operator: child.operator, value: null });
+				continue;
+			}
+			const convert = (raw: string): string | number => {
+				const n = Number(raw);
+				return numeric.has(child.field) && raw !== '' && Number.isFinite(n) ? n : raw;
+			};
+			if (child.operator === 'in') {
+				const values = child.text
+					.split(',')
+					.map((v) => v.trim())
+					.filter((v) => v !== '');
+				if (values.length > 0) children.push({ field: child.field, operator: 'in', value: values.map(convert) });
+				continue;
+			}
+			const text = child.text.trim();
+			if (text !== '') children.push({ field: child.field, operator: child.operator, value: convert(text) });
+		}
+		return { kind: 'group', combinator: group.combinator, negate: group.negate, children };
+	}
+
+	private _getFieldOptions(): FieldOption[] {
+		const schema = this._config.schema;
+		if (schema?.initialized) {
+			return schema.getFilterableColumns().map((c) => ({
+				name: c.name,
+				label: c.label ?? fieldDisplayName(c.name),
+				numeric: c.isNumeric,
+			}));
+		}
+		return [...ALLOWED_FILTER_FIELDS].map((name) => ({ name, label: fieldDisplayName(name), numeric: false }));
+	}
+
+	// ---------------------------------------------------------------------------
+	// Rendering
+	// ---------------------------------------------------------------------------
+
+	private _render(): void {
+		const root = this._rootEl;
+		if (!root) return;
+		root.textContent = '';
+		root.appendChild(this._renderGroup(this._draft, null, 1));
+	}
+
+	/** Structural edit: re-render, then commit. */
+	private _edit(): void {
+		this._render();
+		this._commit();
+	}
+
+	private _renderGroup(group: DraftGroup, parent: DraftGroup | null, depth: number): HTMLElement {
+		const el = document.createElement('div');
+		el.className = 'filter-builder__group';
+		el.classList.toggle('filter-builder__group--negated', group.negate);
+		el.setAttribute('role', 'group');
+
+		// Header: NOT toggle, combinator, remove (nested groups only)
+		const header = document.createElement('div');
+		header.className = 'filter-builder__group-header';
+
+		const notBtn = document.createElement('button');
+		notBtn.type = 'button';
+		notBtn.className = 'filter-builder__not';
+		notBtn.textContent = 'Not';
+		notBtn.title = 'Match cards that do NOT match this group';
+		notBtn.setAttribute('aria-pressed', group.negate ? 'true' : 'false');
+		notBtn.addEventListener('click', () => {
+			group.negate = !group.negate;
+			this._edit();
+		});
+		header.appendChild(notBtn);
+
+		const combinatorSelect = document.createElement('select');
+		combinatorSelect.className = 'filter-builder__combinator';
+		combinatorSelect.setAttribute('aria-label', 'Match');
+		for (const [value, label] of COMBINATOR_LABELS) {
+			const opt = document.createElement('option');
+			opt.value = value;
+			opt.textContent = label;
+			if (value === group.combinator) opt.selected = true;
+			combinatorSelect.appendChild(opt);
+		}
+		combinatorSelect.addEventListener('change', () => {
+			group.combinator = combinatorSelect.value as FilterCombinator;
+			this._edit();
+		});
+		header.appendChild(combinatorSelect);
+
+		if (parent) {
+			header.appendChild(
+				this._removeButton('Remove group', () => {
+					parent.children.splice(parent.children.indexOf(group), 1);
+					this._edit();
+				}),
+			);
+		}
+		el.appendChild(header);
+
+		// Children
+		const childrenEl = document.createElement('div');
+		childrenEl.className = 'filter-builder__children';
+		for (const child of group.children) {
+			childrenEl.appendChild(
+				child.kind === 'group' ? this._renderGroup(child, group, depth + 1) : this._renderCondition(child, group),
+			);
+		}
+		if (group.children.length === 0) {
+			const empty = document.createElement('div');
+			empty.className = 'filter-builder__empty';
+			empty.textContent = parent ? 'Empty group' : 'No conditions';
+			childrenEl.appendChild(empty);
+		}
+		el.appendChild(childrenEl);
+
+		// Actions
+		const actions = document.createElement('div');
+		actions.className = 'filter-builder__actions';
+		actions.appendChild(
+			this._addButton('+ Condition', () => {
+				const field = this._getFieldOptions()[0]?.name ?? 'name';
+				group.children.push({ kind: 'condition', field, operator: 'eq', text: '' });
+				this._edit();
+			}),
+		);
+		if (depth < MAX_FILTER_TREE_DEPTH) {
+			actions.appendChild(
+				this._addButton('+ Group', () => {
+					group.children.push({ ...emptyGroup(), combinator: group.combinator === 'and' ? 'or' : 'and' });
+					this._edit();
+				}),
+			);
+		}
+		el.appendChild(actions);
+
+		return el;
+	}
+
+	private _renderCondition(condition: DraftCondition, parent: DraftGroup): HTMLElement {
+		const row = document.createElement('div');
+		row.className = 'filter-builder__condition';
+
+		// Field select — keeps fields no longer in the schema so restored trees stay visible
+		const fieldSelect = document.createElement('select');
+		fieldSelect.className = 'filter-builder__field';
+		fieldSelect.setAttribute('aria-label', 'Field');
+		const fields = this._getFieldOptions();
+		if (!fields.some((f) => f.name === condition.field)) {
+			fields.push({ name: condition.field, label: fieldDisplayName(condition.field), numeric: false });
+		}
+		for (const field of fields) {
+			const opt = document.createElement('option');
+			opt.value = field.name;
+			opt.textContent = field.label;
+			if (field.name === condition.field) opt.selected = true;
+			fieldSelect.appendChild(opt);
+		}
+		fieldSelect.addEventListener('change', () => {
+			condition.field = fieldSelect.value;
+			this._edit();
+		});
+		row.appendChild(fieldSelect);
+
+		const operatorSelect = document.createElement('select');
+		operatorSelect.className = 'filter-builder__operator';
+		operatorSelect.setAttribute('aria-label', 'Operator');
+		for (const [value, label] of OPERATOR_LABELS) {
+			const opt = document.createElement('option');
+			opt.value = value;
+			opt.textContent = label;
+			if (value === condition.operator) opt.selected = true;
+			operatorSelect.appendChild(opt);
+		}
+		operatorSelect.addEventListener('change', () => {
+			condition.operator = operatorSelect.value as FilterOperator;
+			this._edit();
+		});
+		row.appendChild(operatorSelect);
+
+		if (!VALUELESS_OPERATORS.has(condition.operator)) {
+			const input = document.createElement('input');
+			input.type = 'text';
+			input.className = 'filter-builder__value';
+			input.value = condition.text;
+			input.placeholder = condition.operator === 'in' ? 'a, b, c' : 'Value';
+			input.setAttribute('aria-label', 'Value');
+			input.addEventListener('input', () => {
+				condition.text = input.value;
+				if (this._debounceTimer !== null) clearTimeout(this._debounceTimer);
+				this._debounceTimer = setTimeout(() => {
+					this._debounceTimer = null;
+					this._commit();
+				}, 300);
+			});
+			row.appendChild(input);
+		}
+
+		row.appendChild(
+			this._removeButton('Remove condition', () => {
+				parent.children.splice(parent.children.indexOf(condition), 1);
+				this._edit();
+			}),
+		);
+
+		return row;
+	}
+
+	private _addButton(label: string, onClick: () => void): HTMLButtonElement {
+		const btn = document.createElement('button');
+		btn.type = 'button';
+		btn.className = 'filter-builder__add';
+		btn.textContent = label;
+		btn.addEventListener('click', onClick);
+		return btn;
+	}
+
+	private _removeButton(label: string, onClick: () => void): HTMLButtonElement {
+		const btn = document.createElement('button');
+		btn.type = 'button';
+		btn.className = 'filter-builder__remove';
+		btn.textContent = '\u00D7';
+		btn.setAttribute('aria-label', label);
+		btn.addEventListener('click', onClick);
+		return btn;
+	}
+}
@@ -0,0 +1,394 @@
+// Isometry v5 — Formulas Explorer
+// FormulasExplorer: define spreadsheet-style formula fields ("Formulas" panel).
+//
+// Design:
+//   - Renders inside the sidecar bottom slot (replaces the "Coming soon" stub)
+//   - One text input for `name = expression`, a result type and a scope
+//     (all datasets or one dataset); parsed with parseFormulaDefinition()
+//   - Live preview (debounced) via formula:preview: compile error with its
+//     position, or the value for a few recent cards
+//   - Saved formulas are listed with their scope, type and current compile
+//     error (a formula breaks when e.g. its property is deleted)
+//   - onFormulasChange() fires with the full list after every define/delete so
+//     main.ts can refresh the fx_<name> registry and SchemaProvider columns
+//   - CSS classes for all visual styling (no inline colors)
+
+import { FORMULA_RESULT_TYPES, type FormulaResultType } from '../database/queries/formulas';
+import { FormulaError, type FormulaInfo, parseFormulaDefinition } from '../providers/formulas';
+import type { WorkerBridge } from '../worker/WorkerBridge';
+import '../styles/formulas-explorer.css';
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/** WorkerBridge subset used by FormulasExplorer (mockable in tests). */
+export type FormulasBridge = Pick<
+	WorkerBridge,
+	'listFormulas' | 'defineFormula' | 'deleteFormula' | 'previewFormula' | 'send'
+>;
+
+/** Constructor dependencies for FormulasExplorer. */
+export interface FormulasExplorerConfig {
+	bridge: FormulasBridge;
+	container: HTMLElement;
+	/** Called with every formula (valid or not) after the list changes */
+	onFormulasChange: (formulas: FormulaInfo[]) => void;
+}
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+const PREVIEW_DEBOUNCE_MS = 300;
+
+const PLACEHOLDER = 'days_open = julianday(coalesce(completed_at, now)) - julianday(created_at)';
+
+/** Select value for the global scope (datasets use their id). */
+const ALL_DATASETS = '';
+
+// ---------------------------------------------------------------------------
+// FormulasExplorer
+// ---------------------------------------------------------------------------
+
+export class FormulasExplorer {
+	private readonly _bridge: FormulasBridge;
+	private readonly _container: HTMLElement;
+	private readonly _onFormulasChange: (formulas: FormulaInfo[]) => void;
+
+	private _formulas: FormulaInfo[] = [];
+	private _datasets: Array<{ id: string; name: string }> = [];
+
+	private _wrapperEl: HTMLElement | null = null;
+	private _listEl: HTMLUListElement | null = null;
+	private _inputEl: HTMLInputElement | null = null;
+	private _typeSelect: HTMLSelectElement | null = null;
+	private _scopeSelect: HTMLSelectElement | null = null;
+	private _previewEl: HTMLElement | null = null;
+	private _saveButton: HTMLButtonElement | null = null;
+	private _previewTimer: ReturnType<typeof setTimeout> | null = null;
+	private _previewSeq = 0;
+
+	constructor(config: FormulasExplorerConfig) {
+		this._bridge = config.bridge;
+		this._container = config.container;
+		this._onFormulasChange = config.onFormulasChange;
+	}
+
+	// -----------------------------------------------------------------------
+	// Lifecycle
+	// -----------------------------------------------------------------------
+
+	/**
+	 * Build the panel DOM, then load datasets and saved formulas.
+	 */
+	async mount(): Promise<void> {
+		const wrapper = document.createElement('div');
+		wrapper.className = 'formulas-explorer';
+
+		const form = document.createElement('form');
+		form.className = 'formulas-explorer__form';
+		form.addEventListener('submit', (e) => {
+			e.preventDefault();
+			void this._save();
+		});
+
+		const input = document.createElement('input');
+		input.type = 'text';
+		input.className = 'formulas-explorer__input';
+		input.placeholder = PLACEHOLDER;
+		input.spellcheck = false;
+		input.setAttribute('aria-label', 'Formula');
+		input.addEventListener('input', () => this._schedulePreview());
+		form.appendChild(input);
+
+		const options = document.createElement('div');
+		options.className = 'formulas-explorer__options';
+
+		const typeSelect = document.createElement('select');
+		typeSelect.className = 'formulas-explorer__type';
+		typeSelect.setAttribute('aria-label', 'Result type');
+		for (const type of FORMULA_RESULT_TYPES) {
+			const option = document.createElement('option');
+			option.value = type;
+			option.textContent = type;
+			typeSelect.appendChild(option);
+		}
+		options.appendChild(typeSelect);
+
+		const scopeSelect = document.createElement('select');
+		scopeSelect.className = 'formulas-explorer__scope';
+		scopeSelect.setAttribute('aria-label', 'Applies to');
+		scopeSelect.addEventListener('change', () => this._schedulePreview());
+		options.appendChild(scopeSelect);
+
+		const save = document.createElement('button');
+		save.type = 'submit';
+		save.className = 'formulas-explorer__save';
+		save.textContent = 'Save';
+		options.appendChild(save);
+
+		form.appendChild(options);
+		wrapper.appendChild(form);
+
+		const preview = document.createElement('div');
+		preview.className = 'formulas-explorer__preview';
+		preview.setAttribute('role', 'status');
+		wrapper.appendChild(preview);
+
+		const list = document.createElement('ul');
+		list.className = 'formulas-explorer__list';
+		wrapper.appendChild(list);
+
+		this._container.appendChild(wrapper);
+		this._wrapperEl = wrapper;
+		this._inputEl = input;
+		this._typeSelect = typeSelect;
+		this._scopeSelect = scopeSelect;
+		this._saveButton = save;
+		this._previewEl = preview;
+		this._listEl = list;
+
+		try {
+			const datasets = await this._bridge.send('datasets:query', {});
+			this._datasets = datasets.map((d) => ({ id: d.id, name: d.name }));
+		} catch {
+			this._datasets = [];
+		}
+		this._renderScopes();
+		await this._reload(false);
+	}
+
+	/** Remove the panel DOM and cancel any pending preview. */
+	destroy(): void {
+		if (this._previewTimer !== null) clearTimeout(this._previewTimer);
+		this._previewTimer = null;
+		this._wrapperEl?.remove();
+		this._wrapperEl = null;
+		this._listEl = null;
+		this._inputEl = null;
+		this._typeSelect = null;
+		this._scopeSelect = null;
+		this._saveButton = null;
+		this._previewEl = null;
+	}
+
+	/** Saved formulas as last loaded (readonly copy). */
+	getFormulas(): readonly FormulaInfo[] {
+		return [...this._formulas];
+	}
+
+	// -----------------------------------------------------------------------
+	// Private — data
+	// -----------------------------------------------------------------------
+
+	private async _reload(notify: boolean): Promise<void> {
+		this._formulas = await this._bridge.listFormulas();
+		this._renderList();
+		if (notify) this._onFormulasChange([...this._formulas]);
+	}
+
+	private async _save(): Promise<void> {
+		const input = this._inputEl;
+		if (!input) return;
+
+		let parsed: ReturnType<typeof parseFormulaDefinition>;
+		try {
+			parsed = parseFormulaDefinition(input.value);
+		} catch (err) {
+			this._showError(err);
+			return;
+		}
+
+		const datasetId = this._scopeSelect?.value || null;
+		const resultType = (this._typeSelect?.value ?? 'number') as FormulaResultType;
+		if (this._saveButton) this._saveButton.disabled = true;
+		try {
+			await this._bridge.defineFormula({ ...parsed, dataset_id: datasetId, result_type: resultType });
+			input.value = '';
+			this._setPreview('formulas-explorer__preview', '');
+			await this._reload(true);
+		} catch (err) {
+			this._showError(err);
+		} finally {
+			if (this._saveButton) this._saveButton.disabled = false;
+		}
+	}
+
+	private async _delete(formula: FormulaInfo): Promise<void> {
+		try {
+			await this._bridge.deleteFormula(formula.id);
+			await this._reload(true);
+		} catch (err) {
+			this._showError(err);
+		}
+	}
+
+	/** Load a saved formula back into the form for editing. */
+	private _edit(formula: FormulaInfo): void {
+		if (!this._inputEl) return;
+		this._inputEl.value = `${formula.name} = ${formula.expression}`;
+		if (this._typeSelect) this._typeSelect.value = formula.result_type;
+		if (this._scopeSelect) this._scopeSelect.value = formula.dataset_id ?? ALL_DATASETS;
+		this._inputEl.focus();
+		this._schedulePreview();
+	}
+
+	// -----------------------------------------------------------------------
+	// Private — preview
+	// -----------------------------------------------------------------------
+
+	private _schedulePreview(): void {
+		if (this._previewTimer !== null) clearTimeout(this._previewTimer);
+		this._previewTimer = setTimeout(() => {
+			this._previewTimer = null;
+			void this._preview();
+		}, PREVIEW_DEBOUNCE_MS);
+	}
+
+	private async _preview(): Promise<void> {
+		const text = this._inputEl?.value ?? '';
+		if (text.trim() === '') {
+			this._setPreview('formulas-explorer__preview', '');
+			return;
+		}
+
+		let expression: string;
+		let offset: number;
+		try {
+			expression = parseFormulaDefinition(text).expression;
+			offset = text.indexOf(expression, text.indexOf('=') + 1);
+		} catch (err) {
+			this._showError(err);
+			return;
+		}
+
+		const seq = ++this._previewSeq;
+		const result = await this._bridge.previewFormula(expression, this._scopeSelect?.value || null);
+		if (seq !== this._previewSeq || !this._previewEl) return;
+
+		if (result.error !== null) {
+			const at = result.position !== null ? ` (at ${offset + result.position + 1})` : '';
+			this._setPreview('formulas-explorer__preview formulas-explorer__preview--error', `${result.error}${at}`);
+			return;
+		}
+		if (result.rows.length === 0) {
+			this._setPreview('formulas-explorer__preview', 'Valid formula \u00B7 no cards to preview');
+			return;
+		}
+
+		this._setPreview('formulas-explorer__preview', '');
+		const table = document.createElement('dl');
+		table.className = 'formulas-explorer__sample';
+		for (const row of result.rows) {
+			const name = document.createElement('dt');
+			name.textContent = row.name;
+			const value = document.createElement('dd');
+			value.textContent = row.value === null ? '\u2014' : String(row.value);
+			table.append(name, value);
+		}
+		this._previewEl.appendChild(table);
+	}
+
+	private _showError(err: unknown): void {
+		const message = err instanceof Error ? err.message : String(err);
+		const at = err instanceof FormulaError ? ` (at ${err.position + 1})` : '';
+		this._setPreview('formulas-explorer__preview formulas-explorer__preview--error', `${message}${at}`);
+	}
+
+	private _setPreview(className: string, text: string): void {
+		if (!this._previewEl) return;
+		this._previewEl.className = className;
+		this._previewEl.textContent = text;
+	}
+
+	// -----------------------------------------------------------------------
+	// Private — rendering
+	// -----------------------------------------------------------------------
+
+	private _renderScopes(): void {
+		const select = this._scopeSelect;
+		if (!select) return;
+		select.textContent = '';
+		const all = document.createElement('option');
+		all.value = ALL_DATASETS;
+		all.textContent = 'All datasets';
+		select.appendChild(all);
+		for (const dataset of this._datasets) {
+			const option = document.createElement('option');
+			option.value = dataset.id;
+			option.textContent = dataset.name;
+			select.appendChild(option);
+		}
+	}
+
+	private _renderList(): void {
+		const list = this._listEl;
+		if (!list) return;
+		list.textContent = '';
+
+		if (this._formulas.length === 0) {
+			const empty = document.createElement('li');
+			empty.className = 'formulas-explorer__empty';
+			empty.textContent = 'No formulas yet';
+			list.appendChild(empty);
+			return;
+		}
+
+		for (const formula of this._formulas) {
+			const item = document.createElement('li');
+			item.className = 'formulas-explorer__item';
+			if (formula.error !== null) item.classList.add('formulas-explorer__item--error');
+			item.dataset['id'] = formula.id;
+
+			const head = document.createElement('div');
+			head.className = 'formulas-explorer__item-head';
+
+			const name = document.createElement('code');
+			name.className = 'formulas-explorer__name';
+			name.textContent = formula.field;
+			head.appendChild(name);
+
+			const meta = document.createElement('span');
+			meta.className = 'formulas-explorer__meta';
+			meta.textContent = `${formula.result_type} \u00B7 ${this._scopeName(formula.dataset_id)}`;
+			head.appendChild(meta);
+
+			const edit = document.createElement('button');
+			edit.type = 'button';
+			edit.className = 'formulas-explorer__edit';
+			edit.textContent = 'Edit';
+			edit.addEventListener('click', () => this._edit(formula));
+			head.appendChild(edit);
+
+			const remove = document.createElement('button');
+			remove.type = 'button';
+			remove.className = 'formulas-explorer__delete';
+			remove.textContent = '\u00D7';
+			remove.setAttribute('aria-label', `Delete formula ${formula.name}`);
+			remove.addEventListener('click', () => void this._delete(formula));
+			head.appendChild(remove);
+
+			item.appendChild(head);
+
+			const expression = document.createElement('code');
+			expression.className = 'formulas-explorer__expression';
+			expression.textContent = formula.expression;
+			item.appendChild(expression);
+
+			if (formula.error !== null) {
+				const error = document.createElement('div');
+				error.className = 'formulas-explorer__error';
+				error.textContent = formula.error;
+				item.appendChild(error);
+			}
+
+			list.appendChild(item);
+		}
+	}
+
+	private _scopeName(datasetId: string | null): string {
+		if (datasetId === null) return 'All datasets';
+		return this._datasets.find((d) => d.id === datasetId)?.name ?? 'Unknown dataset';
+	}
+}
@@ -13,6 +13,7 @@
 
 import * as d3 from 'd3';
 import type { FilterProvider } from '../providers/FilterProvider';
+import { isCancelledError } from '../worker/protocol';
 import type { WorkerBridgeLike } from './LatchExplorers';
 
 // ---------------------------------------------------------------------------
@@ -161,18 +162,25 @@ export class HistogramScrubber {
 
 		try {
 			const { where, params } = filter.compile();
-			const response = (await bridge.send('histogram:query', {
-				field,
-				fieldType,
-				bins,
-				where,
-				params,
-			})) as { bins: BinDatum[] };
+			// Latest-wins per field: rapid filter changes drop superseded queries in the Worker
+			const response = (await bridge.send(
+				'histogram:query',
+				{
+					field,
+					fieldType,
+					bins,
+					where,
+					params,
+				},
+				{ channel: `histogram:${field}` },
+			)) as { bins: BinDatum[] };
 
 			this._clearError();
 			this._bins = response.bins;
 			this._render(response.bins);
 		} catch (err) {
+			// A newer fetch for this field superseded this one — nothing to show
+			if (isCancelledError(err)) return;
 			console.error(`[HistogramScrubber] ${field} (${fieldType}):`, err);
 			this._showError('Failed to load data');
 		}
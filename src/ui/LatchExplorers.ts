@@ -5,24 +5,36 @@
 //
 // Design:
 //   - 5 CollapsibleSection sub-sections (L, A, T, C, H) inside .latch-explorers root
-//   - Location (L): empty state placeholder
+//   - Location (L): LocationExplorer map (box/radius selection -> latitude/longitude
+//     range filters) and location_name chip pills -> FilterProvider.setAxisFilter()
 //   - Alphabet (A): text search input with 300ms debounce -> FilterProvider.addFilter({contains})
-//   - Time (T): preset range buttons (Today, This Week, This Month, This Year) -> FilterProvider.addFilter({gte/lte})
+//   - Time (T): preset buttons (Today, This Week, This Month, This Year) and a relative date input
+//     ("last 14 days", "next quarter", "overdue") -> FilterProvider.setRelativeDateFilter()
+//     — stored symbolically, so restored/saved state never goes stale
 //   - Category (C): chip pills for folder, status, card_type -> FilterProvider.setAxisFilter()
 //   - Hierarchy (H): chip pills for priority, sort_order -> FilterProvider.setAxisFilter()
 //   - Count badges update reactively via FilterProvider.subscribe()
 //   - "Clear all" button visible only when filters active
 //   - Coordinator subscription sets dirty flag for lazy distinct value + count re-fetch
+//   - Optional Saved Searches section (smart folders) above the LATCH sections:
+//     run / delete saved FilterProvider states, save the current one, live counts
+//   - Optional Advanced Filters section below the LATCH sections: FilterBuilder
+//     for nested AND/OR/NOT filter trees
 
 import '../styles/latch-explorers.css';
 
 import * as d3 from 'd3';
-import type { FilterProvider } from '../providers/FilterProvider';
+import { countFilterConditions, type FilterProvider } from '../providers/FilterProvider';
 import { LATCH_LABELS, LATCH_ORDER, type LatchFamily } from '../providers/latch';
+import { formatRelativeDate, parseRelativeDate } from '../providers/relative-dates';
 import type { SchemaProvider } from '../providers/SchemaProvider';
-import type { AxisField, Filter, FilterField } from '../providers/types';
+import type { AxisField, Filter, FilterField, RelativeDateExpr, RelativeDateUnit } from '../providers/types';
+import type { SavedSearchManager } from '../searches/SavedSearchManager';
+import type { SendOptions } from '../worker/protocol';
 import { CollapsibleSection } from './CollapsibleSection';
+import { FilterBuilder } from './FilterBuilder';
 import { HistogramScrubber } from './HistogramScrubber';
+import { LocationExplorer } from './LocationExplorer';
 
 // ---------------------------------------------------------------------------
 // Types
@@ -35,7 +47,7 @@ export interface StateCoordinatorLike {
 
 /** Narrow interface for WorkerBridge — only needs send. */
 export interface WorkerBridgeLike {
-	send(type: string, payload: unknown): Promise<unknown>;
+	send(type: string, payload: unknown, options?: SendOptions): Promise<unknown>;
 }
 
 export interface LatchExplorersConfig {
@@ -44,6 +56,10 @@ export interface LatchExplorersConfig {
 	coordinator: StateCoordinatorLike;
 	/** Optional SchemaProvider for dynamic LATCH family field lists (DYNM-09). */
 	schema?: SchemaProvider | undefined;
+	/** Optional saved searches — renders a Saved Searches section when provided. */
+	savedSearches?: SavedSearchManager | undefined;
+	/** Renders an Advanced Filters section (AND/OR/NOT filter builder) when true. */
+	filterBuilder?: boolean | undefined;
 }
 
 // ---------------------------------------------------------------------------
@@ -53,9 +69,23 @@ export interface LatchExplorersConfig {
 // Phase 71 DYNM-13: CATEGORY_FIELDS, HIERARCHY_FIELDS, TIME_FIELDS removed — dead code.
 // LatchExplorers._getFieldsForFamily() uses SchemaProvider or inline fallback literals instead.
 
+/** Coordinate columns driven by the Location map selection. */
+const COORDINATE_FIELDS = ['latitude', 'longitude'] as const;
+
+/** Place-name column grouped as chip pills in the Location section. */
+const PLACE_FIELD: AxisField = 'location_name';
+
 const TIME_PRESETS = ['Today', 'This Week', 'This Month', 'This Year'] as const;
 type TimePreset = (typeof TIME_PRESETS)[number];
 
+/** Calendar period behind each Time preset button (the current day/week/month/year). */
+const TIME_PRESET_UNITS: Record<TimePreset, RelativeDateUnit> = {
+	Today: 'day',
+	'This Week': 'week',
+	'This Month': 'month',
+	'This Year': 'year',
+};
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -67,41 +97,10 @@ function fieldDisplayName(field: string): string {
 		.join(' ');
 }
 
-function computeRange(preset: TimePreset): { start: string; end: string } {
-	const now = new Date();
-	const startOfDay = new Date(now.getFullYear(), now.getMonth(), now.getDate());
-
-	switch (preset) {
-		case 'Today':
-			return {
-				start: startOfDay.toISOString(),
-				end: new Date(startOfDay.getTime() + 86400000).toISOString(),
-			};
-		case 'This Week': {
-			const dayOfWeek = startOfDay.getDay();
-			const weekStart = new Date(startOfDay.getTime() - dayOfWeek * 86400000);
-			return {
-				start: weekStart.toISOString(),
-				end: new Date(weekStart.getTime() + 7 * 86400000).toISOString(),
-			};
-		}
-		case 'This Month': {
-			const monthStart = new Date(now.getFullYear(), now.getMonth(), 1);
-			const nextMonthStart = new Date(now.getFullYear(), now.getMonth() + 1, 1);
-			return {
-				start: monthStart.toISOString(),
-				end: nextMonthStart.toISOString(),
-			};
-		}
-		case 'This Year': {
-			const yearStart = new Date(now.getFullYear(), 0, 1);
-			const nextYearStart = new Date(now.getFullYear() + 1, 0, 1);
-			return {
-				start: yearStart.toISOString(),
-				end: nextYearStart.toISOString(),
-			};
-		}
-	}
+/** The preset button matching a relative date expression, if any. */
+function presetFor(expr: RelativeDateExpr | null): TimePreset | null {
+	if (expr?.kind !== 'period' || expr.offset !== 0) return null;
+	return TIME_PRESETS.find((p) => TIME_PRESET_UNITS[p] === expr.unit) ?? null;
 }
 
 /** Chip datum for category/hierarchy chip pills. */
@@ -142,12 +141,21 @@ export class LatchExplorers {
 	// Per-field state for chip pill lists (Phase 67)
 	private _chipContainers = new Map<string, HTMLElement>();
 
-	// Per-field active preset tracking for time sections
-	private _activePresets = new Map<string, TimePreset | null>();
-
 	// Per-field histogram scrubbers (Phase 66)
 	private _histograms = new Map<string, HistogramScrubber>();
 
+	// Location section map
+	private _location: LocationExplorer | null = null;
+
+	// Saved Searches section — kept out of _sections (indexed by LATCH_ORDER)
+	private _savedSection: CollapsibleSection | null = null;
+	private _savedListEl: HTMLElement | null = null;
+	private _unsubSaved: (() => void) | null = null;
+
+	// Advanced Filters section — also kept out of _sections
+	private _builderSection: CollapsibleSection | null = null;
+	private _builder: FilterBuilder | null = null;
+
 	constructor(config: LatchExplorersConfig) {
 		this._config = config;
 		this._schema = config.schema;
@@ -198,6 +206,11 @@ export class LatchExplorers {
 		this._clearAllBtn = clearAllBtn;
 		root.appendChild(clearAllBtn);
 
+		// Saved Searches section (smart folders) — only when a manager is wired
+		if (this._config.savedSearches) {
+			this._mountSavedSearches(root, this._config.savedSearches);
+		}
+
 		// Create 5 CollapsibleSection sub-sections
 		for (const family of LATCH_ORDER) {
 			const section = new CollapsibleSection({
@@ -215,6 +228,11 @@ export class LatchExplorers {
 			this._populateFamilyBody(family, body);
 		}
 
+		// Advanced Filters section (boolean filter tree builder)
+		if (this._config.filterBuilder) {
+			this._mountFilterBuilder(root);
+		}
+
 		container.appendChild(root);
 
 		// Subscribe to FilterProvider for reactive badge + clear button updates
@@ -227,6 +245,8 @@ export class LatchExplorers {
 		this._unsubCoordinator = coordinator.subscribe(() => {
 			this._valuesDirty = true;
 			this.update();
+			// Saved search counts depend on card data too
+			void this._config.savedSearches?.refresh();
 		});
 
 		// Fetch initial distinct values
@@ -238,6 +258,7 @@ export class LatchExplorers {
 			this._valuesDirty = false;
 			void this._fetchAllDistinctValues();
 			this._histograms.forEach((h) => h.update());
+			this._location?.update();
 		}
 	}
 
@@ -251,6 +272,10 @@ export class LatchExplorers {
 			this._unsubCoordinator();
 			this._unsubCoordinator = null;
 		}
+		if (this._unsubSaved) {
+			this._unsubSaved();
+			this._unsubSaved = null;
+		}
 
 		// Clear debounce timer
 		if (this._debounceTimer !== null) {
@@ -261,14 +286,22 @@ export class LatchExplorers {
 		// Destroy all histogram scrubbers
 		this._histograms.forEach((h) => h.destroy());
 		this._histograms.clear();
+		this._location?.destroy();
+		this._location = null;
 
 		// Destroy all CollapsibleSections
 		for (const section of this._sections) {
 			section.destroy();
 		}
 		this._sections = [];
+		this._savedSection?.destroy();
+		this._savedSection = null;
+		this._savedListEl = null;
+		this._builder?.destroy();
+		this._builder = null;
+		this._builderSection?.destroy();
+		this._builderSection = null;
 		this._chipContainers.clear();
-		this._activePresets.clear();
 
 		// Remove root from DOM
 		if (this._rootEl) {
@@ -303,10 +336,11 @@ export class LatchExplorers {
 	}
 
 	private _populateLocation(body: HTMLElement): void {
-		const empty = document.createElement('div');
-		empty.className = 'latch-explorers__empty';
-		empty.textContent = 'No location properties available';
-		body.appendChild(empty);
+		this._location = new LocationExplorer({ filter: this._config.filter, bridge: this._config.bridge });
+		this._location.mount(body);
+
+		// Place-name grouping
+		this._createChipGroup(body, PLACE_FIELD);
 	}
 
 	private _populateAlphabet(body: HTMLElement): void {
@@ -328,7 +362,6 @@ export class LatchExplorers {
 
 	private _populateTime(body: HTMLElement): void {
 		for (const field of this._getFieldsForFamily('Time')) {
-			this._activePresets.set(field, null);
 			const group = document.createElement('div');
 			group.className = 'latch-explorers__field-group';
 
@@ -354,6 +387,23 @@ export class LatchExplorers {
 
 			group.appendChild(presetsContainer);
 
+			// Relative date input — Enter applies, empty input clears
+			const relativeInput = document.createElement('input');
+			relativeInput.type = 'text';
+			relativeInput.className = 'latch-explorers__relative-input';
+			relativeInput.dataset['field'] = field;
+			relativeInput.placeholder = 'e.g. last 14 days, next quarter, overdue';
+			relativeInput.setAttribute('aria-label', `${fieldDisplayName(field)} relative date`);
+			relativeInput.addEventListener('input', () => {
+				relativeInput.classList.remove('latch-explorers__relative-input--invalid');
+				relativeInput.removeAttribute('aria-invalid');
+			});
+			relativeInput.addEventListener('keydown', (e) => {
+				if (e.key !== 'Enter') return;
+				this._handleRelativeInput(field, relativeInput, presetsContainer);
+			});
+			group.appendChild(relativeInput);
+
 			// Phase 66: mount histogram scrubber after presets
 			const histogram = new HistogramScrubber({
 				field,
@@ -512,9 +562,37 @@ export class LatchExplorers {
 
 	private _handleTimePresetClick(field: string, preset: TimePreset, presetsContainer: HTMLElement): void {
 		const { filter } = this._config;
-		const currentActive = this._activePresets.get(field);
+		const active = presetFor(filter.getRelativeDateFilter(field));
+
+		this._removeAbsoluteTimeFilters(field);
+
+		// Toggle off if clicking the already-active preset
+		filter.setRelativeDateFilter(
+			field,
+			active === preset ? null : { kind: 'period', offset: 0, unit: TIME_PRESET_UNITS[preset] },
+		);
+		this._updateTimePresetUI(presetsContainer, field);
+	}
+
+	private _handleRelativeInput(field: string, input: HTMLInputElement, presetsContainer: HTMLElement): void {
+		const { filter } = this._config;
+		const text = input.value.trim();
+		const expr = text === '' ? null : parseRelativeDate(text);
+		if (text !== '' && expr === null) {
+			input.classList.add('latch-explorers__relative-input--invalid');
+			input.setAttribute('aria-invalid', 'true');
+			return;
+		}
+
+		this._removeAbsoluteTimeFilters(field);
+		filter.setRelativeDateFilter(field, expr);
+		if (expr !== null) input.value = formatRelativeDate(expr);
+		this._updateTimePresetUI(presetsContainer, field);
+	}
 
-		// Remove any existing gte/lte filters for this field (reverse iteration)
+	/** Remove absolute gte/lte filters for a time field (written by older preset versions). */
+	private _removeAbsoluteTimeFilters(field: string): void {
+		const { filter } = this._config;
 		const filters = filter.getFilters();
 		for (let i = filters.length - 1; i >= 0; i--) {
 			const f = filters[i]!;
@@ -522,24 +600,11 @@ export class LatchExplorers {
 				filter.removeFilter(i);
 			}
 		}
-
-		// Toggle off if clicking the already-active preset
-		if (currentActive === preset) {
-			this._activePresets.set(field, null);
-			this._updateTimePresetUI(presetsContainer, field);
-			return;
-		}
-
-		// Compute and apply new range
-		const range = computeRange(preset);
-		filter.addFilter({ field: field as FilterField, operator: 'gte', value: range.start });
-		filter.addFilter({ field: field as FilterField, operator: 'lte', value: range.end });
-		this._activePresets.set(field, preset);
-		this._updateTimePresetUI(presetsContainer, field);
 	}
 
 	private _updateTimePresetUI(presetsContainer: HTMLElement, field: string): void {
-		const active = this._activePresets.get(field);
+		const expr = this._config.filter.getRelativeDateFilter(field);
+		const active = presetFor(expr);
 		const buttons = presetsContainer.querySelectorAll<HTMLButtonElement>('.latch-explorers__time-preset');
 		for (const btn of buttons) {
 			if (btn.dataset['preset'] === active) {
@@ -548,6 +613,12 @@ export class LatchExplorers {
 				btn.classList.remove('latch-explorers__time-preset--active');
 			}
 		}
+
+		// Show non-preset expressions in the relative input (unless the user is typing)
+		const input = presetsContainer.parentElement?.querySelector<HTMLInputElement>('.latch-explorers__relative-input');
+		if (input && document.activeElement !== input) {
+			input.value = expr && active === null ? formatRelativeDate(expr) : '';
+		}
 	}
 
 	private _handleClearAll(): void {
@@ -555,9 +626,18 @@ export class LatchExplorers {
 		const timeFields = this._getFieldsForFamily('Time');
 		const hierarchyFields = this._getFieldsForFamily('Hierarchy');
 
-		// Clear all axis filters (chip-based)
+		// Clear all axis filters (chip-based, including place names)
 		filter.clearAllAxisFilters();
 
+		// Clear the Location map selection and its coordinate range filters
+		this._location?.clearSelection();
+		for (const field of COORDINATE_FIELDS) {
+			filter.clearRangeFilter(field);
+		}
+
+		// Clear the advanced filter tree
+		if (this._builder) filter.clearFilterTree();
+
 		// Remove all time range filters (gte/lte on time fields)
 		const filters = filter.getFilters();
 		for (let i = filters.length - 1; i >= 0; i--) {
@@ -581,25 +661,183 @@ export class LatchExplorers {
 			filter.clearRangeFilter(field);
 		}
 
-		// Reset active presets
+		// Clear relative date filters (Time presets and relative input)
 		for (const field of timeFields) {
-			this._activePresets.set(field, null);
+			filter.clearRelativeDateFilter(field);
 		}
 
-		// Clear search input
-		const searchInput = this._rootEl?.querySelector('.latch-explorers__search-input') as HTMLInputElement | null;
+		// Clear search input (not the saved-search name input, which shares the class)
+		const searchInput = this._rootEl?.querySelector(
+			'.latch-explorers__search-input:not(.latch-explorers__saved-name)',
+		) as HTMLInputElement | null;
 		if (searchInput) searchInput.value = '';
 	}
 
+	// ---------------------------------------------------------------------------
+	// Saved Searches (smart folders)
+	// ---------------------------------------------------------------------------
+
+	private _mountSavedSearches(root: HTMLElement, manager: SavedSearchManager): void {
+		const section = new CollapsibleSection({
+			title: 'Saved Searches',
+			icon: '',
+			storageKey: 'latch-saved-searches',
+		});
+		section.mount(root);
+		this._savedSection = section;
+
+		const body = section.getBodyEl();
+		if (!body) return;
+
+		const group = document.createElement('div');
+		group.className = 'latch-explorers__field-group';
+
+		const listEl = document.createElement('div');
+		listEl.className = 'latch-explorers__saved-list';
+		listEl.setAttribute('role', 'list');
+		listEl.setAttribute('aria-label', 'Saved searches');
+		this._savedListEl = listEl;
+
+		// Delegated click handler — rows are rebuilt on every list change
+		listEl.addEventListener('click', (e) => {
+			const target = e.target as Element;
+			const deleteBtn = target.closest<HTMLButtonElement>('.latch-explorers__saved-delete');
+			if (deleteBtn?.dataset['id']) {
+				void manager.delete(deleteBtn.dataset['id']);
+				return;
+			}
+			const runBtn = target.closest<HTMLButtonElement>('.latch-explorers__saved-run');
+			if (runBtn?.dataset['id']) manager.apply(runBtn.dataset['id']);
+		});
+		group.appendChild(listEl);
+
+		// "Save current filters" name input — Enter saves
+		const nameInput = document.createElement('input');
+		nameInput.type = 'text';
+		nameInput.className = 'latch-explorers__search-input latch-explorers__saved-name';
+		nameInput.placeholder = 'Save current filters as...';
+		nameInput.setAttribute('aria-label', 'Save current filters as');
+		nameInput.addEventListener('keydown', (e) => {
+			if (e.key !== 'Enter') return;
+			const name = nameInput.value.trim();
+			if (name === '') return;
+			nameInput.value = '';
+			void manager.saveCurrent(name);
+		});
+		group.appendChild(nameInput);
+
+		body.appendChild(group);
+
+		this._unsubSaved = manager.subscribe(() => this._renderSavedSearches());
+		this._renderSavedSearches();
+		void manager.refresh();
+	}
+
+	private _renderSavedSearches(): void {
+		const manager = this._config.savedSearches;
+		const listEl = this._savedListEl;
+		if (!manager || !listEl) return;
+
+		const searches = manager.list();
+		this._savedSection?.setCount(searches.length);
+		listEl.textContent = '';
+
+		if (searches.length === 0) {
+			const empty = document.createElement('div');
+			empty.className = 'latch-explorers__empty';
+			empty.textContent = 'No saved searches';
+			listEl.appendChild(empty);
+			return;
+		}
+
+		for (const search of searches) {
+			const row = document.createElement('div');
+			row.className = 'latch-explorers__saved-row';
+			row.setAttribute('role', 'listitem');
+
+			const runBtn = document.createElement('button');
+			runBtn.type = 'button';
+			runBtn.className = 'latch-explorers__chip latch-explorers__saved-run';
+			runBtn.dataset['id'] = search.id;
+			runBtn.title = search.count === null ? 'This search no longer applies to the current schema' : search.name;
+
+			const nameEl = document.createElement('span');
+			nameEl.className = 'latch-explorers__saved-label';
+			nameEl.textContent = search.name;
+			runBtn.appendChild(nameEl);
+
+			const countEl = document.createElement('span');
+			countEl.className = 'latch-explorers__chip-count';
+			countEl.textContent = search.count === null ? '\u2014' : String(search.count);
+			runBtn.appendChild(countEl);
+
+			const deleteBtn = document.createElement('button');
+			deleteBtn.type = 'button';
+			deleteBtn.className = 'latch-explorers__saved-delete';
+			deleteBtn.dataset['id'] = search.id;
+			deleteBtn.textContent = '\u00D7';
+			deleteBtn.setAttribute('aria-label', `Delete saved search ${search.name}`);
+
+			row.appendChild(runBtn);
+			row.appendChild(deleteBtn);
+			listEl.appendChild(row);
+		}
+
+		this._syncSavedSearchStates();
+		this._updateBuilderCount();
+	}
+
+	private _syncSavedSearchStates(): void {
+		const manager = this._config.savedSearches;
+		if (!manager || !this._savedListEl) return;
+		const buttons = this._savedListEl.querySelectorAll<HTMLButtonElement>('.latch-explorers__saved-run');
+		for (const btn of buttons) {
+			const active = manager.isActive(btn.dataset['id'] ?? '');
+			btn.classList.toggle('latch-explorers__chip--active', active);
+			btn.setAttribute('aria-pressed', active ? 'true' : 'false');
+		}
+	}
+
+	// ---------------------------------------------------------------------------
+	// Advanced Filters (boolean filter tree)
+	// ---------------------------------------------------------------------------
+
+	private _mountFilterBuilder(root: HTMLElement): void {
+		const section = new CollapsibleSection({
+			title: 'Advanced Filters',
+			icon: '',
+			storageKey: 'latch-filter-builder',
+			defaultCollapsed: true,
+		});
+		section.mount(root);
+		this._builderSection = section;
+
+		const body = section.getBodyEl();
+		if (!body) return;
+
+		this._builder = new FilterBuilder({ filter: this._config.filter, schema: this._schema });
+		this._builder.mount(body);
+		this._updateBuilderCount();
+	}
+
+	private _updateBuilderCount(): void {
+		if (!this._builderSection) return;
+		const tree = this._config.filter.getFilterTree();
+		this._builderSection.setCount(tree ? countFilterConditions(tree) : 0);
+	}
+
 	// ---------------------------------------------------------------------------
 	// Filter subscription callback
 	// ---------------------------------------------------------------------------
 
 	private _onFilterChange(): void {
+		// Drop a stale map selection once its range filters are gone (undo, saved search, reset)
+		if (!this._hasLocationRange()) this._location?.clearSelection();
 		this._updateBadgeCounts();
 		this._updateClearAllVisibility();
 		this._syncChipStates();
 		this._syncTimePresetStates();
+		this._syncSavedSearchStates();
 	}
 
 	private _updateBadgeCounts(): void {
@@ -619,7 +857,8 @@ export class LatchExplorers {
 	private _computeFamilyFilterCount(family: LatchFamily, filter: FilterProvider, filters: readonly Filter[]): number {
 		switch (family) {
 			case 'L':
-				return 0;
+				// Map selection (one filter over both coordinates) + place-name chips
+				return (this._hasLocationRange() ? 1 : 0) + (filter.hasAxisFilter(PLACE_FIELD) ? 1 : 0);
 			case 'A': {
 				// Count name contains filters
 				return filters.some((f) => f.field === 'name' && f.operator === 'contains') ? 1 : 0;
@@ -628,7 +867,9 @@ export class LatchExplorers {
 				// Count time fields that have gte/lte filters or range filters
 				let count = 0;
 				for (const field of this._getFieldsForFamily('Time')) {
-					const hasPreset = filters.some((f) => f.field === field && (f.operator === 'gte' || f.operator === 'lte'));
+					const hasPreset =
+						filter.hasRelativeDateFilter(field) ||
+						filters.some((f) => f.field === field && (f.operator === 'gte' || f.operator === 'lte'));
 					const hasRange = filter.hasRangeFilter(field);
 					if (hasPreset || hasRange) count++;
 				}
@@ -661,21 +902,29 @@ export class LatchExplorers {
 		const timeFields = this._getFieldsForFamily('Time');
 		const categoryFields = this._getFieldsForFamily('Category');
 		const hierarchyFields = this._getFieldsForFamily('Hierarchy');
-		const hasAxisFilters = [...categoryFields, ...hierarchyFields].some((f) => filter.hasAxisFilter(f));
-		const hasTimeFilters = filters.some(
-			(f) => timeFields.includes(f.field as AxisField) && (f.operator === 'gte' || f.operator === 'lte'),
-		);
+		const hasAxisFilters = [...categoryFields, ...hierarchyFields, PLACE_FIELD].some((f) => filter.hasAxisFilter(f));
+		const hasTimeFilters =
+			timeFields.some((f) => filter.hasRelativeDateFilter(f)) ||
+			filters.some((f) => timeFields.includes(f.field as AxisField) && (f.operator === 'gte' || f.operator === 'lte'));
 		const hasNameFilter = filters.some((f) => f.field === 'name' && f.operator === 'contains');
 		// Phase 66: check for active range filters from histogram scrubbers
-		const hasRangeFilters = [...timeFields, ...hierarchyFields].some((f) => filter.hasRangeFilter(f));
+		const hasRangeFilters =
+			[...timeFields, ...hierarchyFields].some((f) => filter.hasRangeFilter(f)) || this._hasLocationRange();
+
+		const hasFilterTree = this._builder !== null && filter.hasFilterTree();
 
-		const anyActive = hasAxisFilters || hasTimeFilters || hasNameFilter || hasRangeFilters;
+		const anyActive = hasAxisFilters || hasTimeFilters || hasNameFilter || hasRangeFilters || hasFilterTree;
 		this._clearAllBtn.style.display = anyActive ? '' : 'none';
 	}
 
+	private _hasLocationRange(): boolean {
+		const { filter } = this._config;
+		return COORDINATE_FIELDS.some((f) => filter.hasRangeFilter(f));
+	}
+
 	private _syncChipStates(): void {
 		const { filter } = this._config;
-		for (const field of [...this._getFieldsForFamily('Category'), ...this._getFieldsForFamily('Hierarchy')]) {
+		for (const field of this._chipContainers.keys()) {
 			const containerEl = this._chipContainers.get(field);
 			if (!containerEl) continue;
 
@@ -698,17 +947,8 @@ export class LatchExplorers {
 	}
 
 	private _syncTimePresetStates(): void {
-		const { filter } = this._config;
-		const filters = filter.getFilters();
 		const timeFields = this._getFieldsForFamily('Time');
 
-		for (const field of timeFields) {
-			const hasTimeFilter = filters.some((f) => f.field === field && (f.operator === 'gte' || f.operator === 'lte'));
-			if (!hasTimeFilter) {
-				this._activePresets.set(field, null);
-			}
-		}
-
 		// Update UI for all time preset containers
 		if (!this._rootEl) return;
 		for (const field of timeFields) {
@@ -727,7 +967,7 @@ export class LatchExplorers {
 
 	private async _fetchAllDistinctValues(): Promise<void> {
 		const { bridge } = this._config;
-		const allFields = [...this._getFieldsForFamily('Category'), ...this._getFieldsForFamily('Hierarchy')];
+		const allFields = [...this._chipContainers.keys()];
 
 		const promises = allFields.map(async (field) => {
 			const chips = await fetchDistinctValuesWithCounts(bridge, field);
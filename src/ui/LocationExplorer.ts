@@ -0,0 +1,546 @@
+// Isometry v5 — LATCH Location explorer
+// LocationExplorer: offline D3 geo map of card points with box/radius selection.
+//
+// Design:
+//   - Self-contained component with mount/update/destroy lifecycle (mirrors HistogramScrubber)
+//   - Fetches aggregated points via WorkerBridge location:query
+//   - Offline rendering: equirectangular projection with sphere outline and
+//     graticule only — no tiles, no network
+//   - Projection is fitted to the unfiltered extent of all located cards, so the
+//     frame stays put while the filter changes which points are drawn
+//   - Box mode: d3.brush -> setRangeFilter('latitude'/'longitude', min, max)
+//   - Radius mode: drag from a center outwards; the circle compiles to the
+//     latitude/longitude range filters of its bounding box
+//   - clearSelection() for programmatic reset (Clear All button)
+//   - CSS classes for all visual styling (no inline colors)
+
+import * as d3 from 'd3';
+import type { FilterProvider } from '../providers/FilterProvider';
+import { isCancelledError } from '../worker/protocol';
+import type { WorkerBridgeLike } from './LatchExplorers';
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+export interface LocationExplorerConfig {
+	filter: FilterProvider;
+	bridge: WorkerBridgeLike;
+}
+
+/** A latitude/longitude bounding box in degrees. */
+export interface GeoBounds {
+	south: number;
+	west: number;
+	north: number;
+	east: number;
+}
+
+export type LocationSelectMode = 'box' | 'radius';
+
+interface PointDatum {
+	latitude: number;
+	longitude: number;
+	count: number;
+	locationName: string | null;
+}
+
+interface LocationResponse {
+	points: PointDatum[];
+	bounds: GeoBounds | null;
+	total: number;
+}
+
+type Selection =
+	| { kind: 'box'; bounds: GeoBounds }
+	| { kind: 'radius'; latitude: number; longitude: number; radiusKm: number };
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+const WIDTH = 240;
+const HEIGHT = 140;
+const PADDING = 6;
+
+/** Minimum padding around the data extent, in degrees (keeps a single point from filling the map). */
+const MIN_MARGIN_DEG = 0.5;
+
+const EARTH_RADIUS_KM = 6371;
+const KM_PER_DEGREE_LAT = (Math.PI * EARTH_RADIUS_KM) / 180;
+
+// ---------------------------------------------------------------------------
+// Geo helpers
+// ---------------------------------------------------------------------------
+
+/**
+ * Bounding box of a circle on the sphere, clamped to valid coordinates.
+ * Longitude spans the whole world when the circle reaches a pole.
+ */
+export function radiusToBounds(latitude: number, longitude: number, radiusKm: number): GeoBounds {
+	const dLat = radiusKm / KM_PER_DEGREE_LAT;
+	const south = Math.max(-90, latitude - dLat);
+	const north = Math.min(90, latitude + dLat);
+	if (south === -90 || north === 90) return { south, west: -180, north, east: 180 };
+
+	const dLng = dLat / Math.cos((latitude * Math.PI) / 180);
+	return {
+		south,
+		west: Math.max(-180, longitude - dLng),
+		north,
+		east: Math.min(180, longitude + dLng),
+	};
+}
+
+/** Pad an extent by 10% (at least MIN_MARGIN_DEG) and clamp it to the globe. */
+function padBounds(b: GeoBounds): GeoBounds {
+	const latPad = Math.max(MIN_MARGIN_DEG, (b.north - b.south) * 0.1);
+	const lngPad = Math.max(MIN_MARGIN_DEG, (b.east - b.west) * 0.1);
+	return {
+		south: Math.max(-90, b.south - latPad),
+		west: Math.max(-180, b.west - lngPad),
+		north: Math.min(90, b.north + latPad),
+		east: Math.min(180, b.east + lngPad),
+	};
+}
+
+// ---------------------------------------------------------------------------
+// LocationExplorer
+// ---------------------------------------------------------------------------
+
+export class LocationExplorer {
+	private readonly _config: LocationExplorerConfig;
+	private _wrapperEl: HTMLElement | null = null;
+	private _summaryEl: HTMLElement | null = null;
+	private _errorEl: HTMLElement | null = null;
+	private _svg: d3.Selection<SVGSVGElement, unknown, null, undefined> | null = null;
+	private _outlinePath: d3.Selection<SVGPathElement, unknown, null, undefined> | null = null;
+	private _graticulePath: d3.Selection<SVGPathElement, unknown, null, undefined> | null = null;
+	private _pointsG: d3.Selection<SVGGElement, unknown, null, undefined> | null = null;
+	private _radiusCircle: d3.Selection<SVGCircleElement, unknown, null, undefined> | null = null;
+	private _brush: d3.BrushBehavior<unknown> | null = null;
+	private _brushG: d3.Selection<SVGGElement, unknown, null, undefined> | null = null;
+	private _radiusOverlay: d3.Selection<SVGRectElement, unknown, null, undefined> | null = null;
+	private _projection: d3.GeoProjection = d3.geoEquirectangular();
+	private _frameKey = '';
+	private _mode: LocationSelectMode = 'box';
+	private _selection: Selection | null = null;
+	private _isBrushing = false;
+	private _radiusCenter: [number, number] | null = null;
+
+	constructor(config: LocationExplorerConfig) {
+		this._config = config;
+	}
+
+	// -----------------------------------------------------------------------
+	// Lifecycle
+	// -----------------------------------------------------------------------
+
+	mount(container: HTMLElement): void {
+		const wrapper = document.createElement('div');
+		wrapper.className = 'latch-explorers__location-map';
+		this._wrapperEl = wrapper;
+
+		// Box / Radius mode toggle
+		const modes = document.createElement('div');
+		modes.className = 'latch-explorers__location-modes';
+		for (const mode of ['box', 'radius'] as const) {
+			const btn = document.createElement('button');
+			btn.type = 'button';
+			btn.className = 'latch-explorers__location-mode';
+			btn.dataset['mode'] = mode;
+			btn.textContent = mode === 'box' ? 'Box' : 'Radius';
+			btn.addEventListener('click', () => this.setMode(mode));
+			modes.appendChild(btn);
+		}
+		wrapper.appendChild(modes);
+
+		// SVG with viewBox for responsive sizing
+		this._svg = d3
+			.select(wrapper)
+			.append('svg')
+			.attr('viewBox', `0 0 ${WIDTH} ${HEIGHT}`)
+			.attr('preserveAspectRatio', 'xMidYMid meet')
+			.attr('role', 'img')
+			.attr('aria-label', 'Card locations');
+
+		this._outlinePath = this._svg.append('path').attr('class', 'latch-explorers__location-outline');
+		this._graticulePath = this._svg.append('path').attr('class', 'latch-explorers__location-graticule');
+		this._pointsG = this._svg.append('g').attr('class', 'latch-explorers__location-points');
+		this._radiusCircle = this._svg
+			.append('circle')
+			.attr('class', 'latch-explorers__location-radius')
+			.style('display', 'none');
+
+		// Box mode: 2D brush
+		this._brushG = this._svg.append('g').attr('class', 'brush');
+		this._brush = d3
+			.brush()
+			.extent([
+				[0, 0],
+				[WIDTH, HEIGHT],
+			])
+			.on('end', (event: d3.D3BrushEvent<unknown>) => this._onBrushEnd(event));
+		this._brushG.call(this._brush);
+
+		// Radius mode: drag from center outwards on a transparent overlay
+		this._radiusOverlay = this._svg
+			.append('rect')
+			.attr('class', 'latch-explorers__location-overlay')
+			.attr('width', WIDTH)
+			.attr('height', HEIGHT)
+			.style('display', 'none');
+		this._radiusOverlay.call(
+			d3
+				.drag<SVGRectElement, unknown>()
+				.on('start', (event: d3.D3DragEvent<SVGRectElement, unknown, unknown>) => this._onRadiusStart(event))
+				.on('drag', (event: d3.D3DragEvent<SVGRectElement, unknown, unknown>) => this._onRadiusDrag(event))
+				.on('end', (event: d3.D3DragEvent<SVGRectElement, unknown, unknown>) => this._onRadiusEnd(event)),
+		);
+
+		const summary = document.createElement('div');
+		summary.className = 'latch-explorers__location-summary';
+		this._summaryEl = summary;
+		wrapper.appendChild(summary);
+
+		container.appendChild(wrapper);
+		this._syncModeUI();
+
+		void this._fetchAndRender();
+	}
+
+	update(): void {
+		void this._fetchAndRender();
+	}
+
+	destroy(): void {
+		if (this._svg) {
+			this._svg.remove();
+			this._svg = null;
+		}
+		if (this._wrapperEl) {
+			this._wrapperEl.remove();
+			this._wrapperEl = null;
+		}
+		this._brush = null;
+		this._brushG = null;
+		this._radiusOverlay = null;
+		this._radiusCircle = null;
+		this._pointsG = null;
+		this._outlinePath = null;
+		this._graticulePath = null;
+		this._summaryEl = null;
+		this._errorEl = null;
+		this._selection = null;
+		this._frameKey = '';
+	}
+
+	// -----------------------------------------------------------------------
+	// Selection API
+	// -----------------------------------------------------------------------
+
+	/** Switch between bounding-box and radius selection. Keeps the current filter. */
+	setMode(mode: LocationSelectMode): void {
+		this._mode = mode;
+		this._syncModeUI();
+	}
+
+	getMode(): LocationSelectMode {
+		return this._mode;
+	}
+
+	/** Filter to cards inside a bounding box (latitude/longitude range filters). */
+	selectBox(bounds: GeoBounds): void {
+		this._selection = { kind: 'box', bounds };
+		this._applyBounds(bounds);
+		this._drawSelection();
+	}
+
+	/**
+	 * Filter to cards within `radiusKm` of a point. Compiles to the range
+	 * filters of the circle's bounding box.
+	 */
+	selectRadius(latitude: number, longitude: number, radiusKm: number): void {
+		this._selection = { kind: 'radius', latitude, longitude, radiusKm };
+		this._applyBounds(radiusToBounds(latitude, longitude, radiusKm));
+		this._drawSelection();
+	}
+
+	/**
+	 * Programmatically clear the drawn selection (visual only).
+	 * Does NOT call clearRangeFilter -- the caller handles filter state.
+	 */
+	clearSelection(): void {
+		if (this._selection === null) return;
+		this._selection = null;
+		this._drawSelection();
+	}
+
+	// -----------------------------------------------------------------------
+	// Data fetching
+	// -----------------------------------------------------------------------
+
+	private async _fetchAndRender(): Promise<void> {
+		const { filter, bridge } = this._config;
+
+		try {
+			const { where, params } = filter.compile();
+			// Latest-wins: rapid filter changes drop superseded queries in the Worker
+			const response = (await bridge.send(
+				'location:query',
+				{ where, params },
+				{ channel: 'location' },
+			)) as LocationResponse;
+
+			this._clearError();
+			this._render(response);
+		} catch (err) {
+			if (isCancelledError(err)) return;
+			console.error('[LocationExplorer]', err);
+			this._showError('Failed to load locations');
+		}
+	}
+
+	// -----------------------------------------------------------------------
+	// Error state
+	// -----------------------------------------------------------------------
+
+	private _showError(message: string): void {
+		if (!this._wrapperEl) return;
+		if (!this._errorEl) {
+			const errorEl = document.createElement('div');
+			errorEl.className = 'latch-explorers__scrubber-error';
+
+			const msgSpan = document.createElement('span');
+			msgSpan.className = 'latch-explorers__scrubber-error-msg';
+			errorEl.appendChild(msgSpan);
+
+			const retryBtn = document.createElement('button');
+			retryBtn.className = 'latch-explorers__scrubber-retry';
+			retryBtn.type = 'button';
+			retryBtn.textContent = 'Retry';
+			retryBtn.addEventListener('click', () => {
+				this._clearError();
+				void this._fetchAndRender();
+			});
+			errorEl.appendChild(retryBtn);
+
+			this._wrapperEl.appendChild(errorEl);
+			this._errorEl = errorEl;
+		}
+
+		const msgSpan = this._errorEl.querySelector<HTMLElement>('.latch-explorers__scrubber-error-msg');
+		if (msgSpan) msgSpan.textContent = message;
+		this._errorEl.style.display = '';
+	}
+
+	private _clearError(): void {
+		if (this._errorEl) this._errorEl.style.display = 'none';
+	}
+
+	// -----------------------------------------------------------------------
+	// D3 rendering
+	// -----------------------------------------------------------------------
+
+	private _render(response: LocationResponse): void {
+		if (!this._svg || !this._pointsG || !this._wrapperEl || !this._summaryEl) return;
+
+		// No located cards at all — nothing to frame
+		if (!response.bounds) {
+			this._wrapperEl.classList.add('latch-explorers__location-map--empty');
+			this._summaryEl.textContent = 'No cards with coordinates';
+			this._pointsG.selectAll('*').remove();
+			return;
+		}
+		this._wrapperEl.classList.remove('latch-explorers__location-map--empty');
+
+		// Refit only when the data extent changes; a refit invalidates pixel selections
+		const frameKey = JSON.stringify(response.bounds);
+		if (frameKey !== this._frameKey) {
+			this._frameKey = frameKey;
+			this._fitProjection(response.bounds);
+			this._drawSelection();
+		}
+
+		const n = response.total;
+		this._summaryEl.textContent = `${n} located card${n === 1 ? '' : 's'}`;
+
+		const projection = this._projection;
+		const maxCount = d3.max(response.points, (p) => p.count) ?? 1;
+		const r = d3.scaleSqrt().domain([1, Math.max(2, maxCount)]).range([2, 7]);
+
+		// Data join (D-003 mandatory key function)
+		this._pointsG
+			.selectAll<SVGCircleElement, PointDatum>('circle.latch-explorers__location-point')
+			.data(response.points, (d) => `${d.latitude},${d.longitude}`)
+			.join(
+				(enter) =>
+					enter
+						.append('circle')
+						.attr('class', 'latch-explorers__location-point')
+						.each(function () {
+							this.appendChild(document.createElementNS('http://www.w3.org/2000/svg', 'title'));
+						}),
+				(update) => update,
+				(exit) => exit.remove(),
+			)
+			.attr('cx', (d) => projection([d.longitude, d.latitude])?.[0] ?? 0)
+			.attr('cy', (d) => projection([d.longitude, d.latitude])?.[1] ?? 0)
+			.attr('r', (d) => r(d.count))
+			.each(function (d) {
+				const title = this.querySelector('title');
+				const place = d.locationName ?? `${d.latitude.toFixed(3)}, ${d.longitude.toFixed(3)}`;
+				if (title) title.textContent = `${place}: ${d.count}`;
+			});
+
+		// Keep the interaction layers above the points
+		this._radiusCircle?.raise();
+		this._brushG?.raise();
+		this._radiusOverlay?.raise();
+	}
+
+	private _fitProjection(bounds: GeoBounds): void {
+		const b = padBounds(bounds);
+		const extent: d3.GeoGeometryObjects = {
+			type: 'MultiPoint',
+			coordinates: [
+				[b.west, b.south],
+				[b.east, b.north],
+			],
+		};
+		this._projection = d3.geoEquirectangular().fitExtent(
+			[
+				[PADDING, PADDING],
+				[WIDTH - PADDING, HEIGHT - PADDING],
+			],
+			extent,
+		);
+
+		const path = d3.geoPath(this._projection);
+		this._outlinePath?.attr('d', path({ type: 'Sphere' }) ?? '');
+		this._graticulePath?.attr('d', path(d3.geoGraticule10()) ?? '');
+	}
+
+	/** Redraw the stored selection (radius circle or brush box) in the current projection. */
+	private _drawSelection(): void {
+		const sel = this._selection;
+		const projection = this._projection;
+
+		if (this._radiusCircle) {
+			if (sel?.kind === 'radius') {
+				const center = projection([sel.longitude, sel.latitude]) ?? [0, 0];
+				const edge = projection([sel.longitude, sel.latitude + sel.radiusKm / KM_PER_DEGREE_LAT]) ?? center;
+				this._radiusCircle
+					.attr('cx', center[0])
+					.attr('cy', center[1])
+					.attr('r', Math.abs(center[1] - edge[1]))
+					.style('display', null);
+			} else {
+				this._radiusCircle.style('display', 'none');
+			}
+		}
+
+		if (this._brushG && this._brush) {
+			this._isBrushing = true;
+			if (sel?.kind === 'box') {
+				const nw = projection([sel.bounds.west, sel.bounds.north]) ?? [0, 0];
+				const se = projection([sel.bounds.east, sel.bounds.south]) ?? [WIDTH, HEIGHT];
+				this._brushG.call(this._brush.move, [nw, se]);
+			} else {
+				this._brushG.call(this._brush.move, null);
+			}
+			this._isBrushing = false;
+		}
+	}
+
+	private _syncModeUI(): void {
+		const radius = this._mode === 'radius';
+		this._brushG?.style('display', radius ? 'none' : null);
+		this._radiusOverlay?.style('display', radius ? null : 'none');
+		this._wrapperEl?.querySelectorAll<HTMLButtonElement>('.latch-explorers__location-mode').forEach((btn) => {
+			const active = btn.dataset['mode'] === this._mode;
+			btn.classList.toggle('latch-explorers__location-mode--active', active);
+			btn.setAttribute('aria-pressed', String(active));
+		});
+	}
+
+	// -----------------------------------------------------------------------
+	// Filter output
+	// -----------------------------------------------------------------------
+
+	private _applyBounds(bounds: GeoBounds): void {
+		const { filter } = this._config;
+		filter.setRangeFilter('latitude', bounds.south, bounds.north);
+		filter.setRangeFilter('longitude', bounds.west, bounds.east);
+	}
+
+	private _clearFilter(): void {
+		const { filter } = this._config;
+		this._selection = null;
+		filter.clearRangeFilter('latitude');
+		filter.clearRangeFilter('longitude');
+	}
+
+	// -----------------------------------------------------------------------
+	// Brush / drag handlers
+	// -----------------------------------------------------------------------
+
+	private _onBrushEnd(event: d3.D3BrushEvent<unknown>): void {
+		// Skip programmatic brush moves (from _drawSelection)
+		if (this._isBrushing) return;
+
+		const selection = event.selection as [[number, number], [number, number]] | null;
+		if (selection === null) {
+			// User clicked away to clear
+			this._clearFilter();
+			return;
+		}
+
+		const [[x0, y0], [x1, y1]] = selection;
+		const nw = this._projection.invert?.([x0, y0]);
+		const se = this._projection.invert?.([x1, y1]);
+		if (!nw || !se) return;
+
+		this._selection = {
+			kind: 'box',
+			bounds: {
+				south: Math.max(-90, se[1]),
+				west: Math.max(-180, nw[0]),
+				north: Math.min(90, nw[1]),
+				east: Math.min(180, se[0]),
+			},
+		};
+		this._applyBounds(this._selection.bounds);
+	}
+
+	private _onRadiusStart(event: d3.D3DragEvent<SVGRectElement, unknown, unknown>): void {
+		this._radiusCenter = [event.x, event.y];
+		this._radiusCircle?.attr('cx', event.x).attr('cy', event.y).attr('r', 0).style('display', null);
+	}
+
+	private _onRadiusDrag(event: d3.D3DragEvent<SVGRectElement, unknown, unknown>): void {
+		if (!this._radiusCenter) return;
+		const [cx, cy] = this._radiusCenter;
+		this._radiusCircle?.attr('r', Math.hypot(event.x - cx, event.y - cy));
+	}
+
+	private _onRadiusEnd(event: d3.D3DragEvent<SVGRectElement, unknown, unknown>): void {
+		const start = this._radiusCenter;
+		this._radiusCenter = null;
+		if (!start) return;
+
+		// A click without a drag clears, like clicking away from a brush
+		if (Math.hypot(event.x - start[0], event.y - start[1]) < 2) {
+			this._clearFilter();
+			this._drawSelection();
+			return;
+		}
+
+		const center = this._projection.invert?.(start);
+		const edge = this._projection.invert?.([event.x, event.y]);
+		if (!center || !edge) return;
+
+		const radiusKm = d3.geoDistance(center, edge) * EARTH_RADIUS_KM;
+		this.selectRadius(center[1], center[0], radiusKm);
+	}
+}
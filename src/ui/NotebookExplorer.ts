@@ -332,6 +332,7 @@ export class NotebookExplorer {
 		this._propertyFields = new CardPropertyFields({
 			mutations: this._mutations,
 			bridge: this._bridge,
+			onOpenCard: (cardId) => this._selection.select(cardId),
 		});
 		this._propertyContainerEl = document.createElement('div');
 		this._propertyFields.mount(this._propertyContainerEl);
@@ -4,12 +4,14 @@
 // Requirements: PROP-01, PROP-03, PROP-04, PROP-05, INTG-03
 //
 // Design:
-//   - Displays all 9 AxisField values grouped into 5 LATCH columns (L, A, T, C, H)
+//   - Displays all 10 AxisField values grouped into 5 LATCH columns (L, A, T, C, H)
 //   - Each column header is clickable to collapse/expand independently
 //   - Per-property toggle checkbox enables/disables axis availability
 //   - Single click on property name enters inline edit mode (span-to-input swap)
 //   - D3 selection.join for property rows within each column body (INTG-03)
 //   - Subscribable: external components react to toggle state changes
+//   - Custom card properties (prop_<key>) and formulas (fx_<name>) show their
+//     label and a type badge
 
 import { select } from 'd3-selection';
 import type { AliasProvider } from '../providers/AliasProvider';
@@ -19,7 +21,7 @@ import type { LatchFamily } from '../providers/latch';
 import { getLatchFamily, LATCH_LABELS, LATCH_ORDER, toFullName, toLetter } from '../providers/latch';
 import type { SchemaProvider } from '../providers/SchemaProvider';
 import type { AxisField } from '../providers/types';
-import type { LatchFamily as SchemaLatchFamily } from '../worker/protocol';
+import type { ColumnInfo, LatchFamily as SchemaLatchFamily } from '../worker/protocol';
 import type { WorkerBridgeLike } from './LatchExplorers';
 import '../styles/properties-explorer.css';
 import { AppDialog } from './AppDialog';
@@ -471,11 +473,15 @@ export class PropertiesExplorer {
 						// Name span
 						const nameSpan = document.createElement('span');
 						nameSpan.className = 'properties-explorer__property-name';
-						nameSpan.textContent = self._config.alias.getAlias(field);
+						nameSpan.textContent = self._displayName(field);
 						nameSpan.addEventListener('click', () => {
 							self._handleNameClick(field, row);
 						});
 						row.appendChild(nameSpan);
+
+						// Custom property type badge
+						const typeBadge = self._createTypeBadge(field);
+						if (typeBadge) row.appendChild(typeBadge);
 					}),
 				(update) =>
 					update.each(function (field: AxisField) {
@@ -509,11 +515,15 @@ export class PropertiesExplorer {
 							// Name span
 							const nameSpan = document.createElement('span');
 							nameSpan.className = 'properties-explorer__property-name';
-							nameSpan.textContent = self._config.alias.getAlias(field);
+							nameSpan.textContent = self._displayName(field);
 							nameSpan.addEventListener('click', () => {
 								self._handleNameClick(field, row);
 							});
 							row.appendChild(nameSpan);
+
+							// Custom property type badge
+							const typeBadge = self._createTypeBadge(field);
+							if (typeBadge) row.appendChild(typeBadge);
 						}
 					}),
 				(exit) => exit.remove(),
@@ -667,6 +677,49 @@ export class PropertiesExplorer {
 		}
 	}
 
+	// -----------------------------------------------------------------------
+	// Private — Custom property display
+	// -----------------------------------------------------------------------
+
+	/**
+	 * Display name for a field: user alias first, then the custom property or
+	 * formula label (prop_<key> / fx_<name> fields), then the raw field name.
+	 */
+	private _displayName(field: AxisField): string {
+		const alias = this._config.alias.getAlias(field);
+		if (alias !== field) return alias;
+		return this._propertyColumn(field)?.label ?? field;
+	}
+
+	/**
+	 * Type badge for custom property and formula rows (e.g. "number", "enum",
+	 * "fx date"); null for cards columns.
+	 */
+	private _createTypeBadge(field: AxisField): HTMLElement | null {
+		const column = this._propertyColumn(field);
+		if (column?.formulaType) {
+			const badge = document.createElement('span');
+			badge.className = 'properties-explorer__type-badge';
+			badge.textContent = `fx ${column.formulaType}`;
+			badge.title = `Formula (${column.formulaType})`;
+			return badge;
+		}
+		const propertyType = column?.propertyType;
+		if (!propertyType) return null;
+		const badge = document.createElement('span');
+		badge.className = 'properties-explorer__type-badge';
+		badge.textContent = propertyType;
+		badge.title = `Custom ${propertyType} property`;
+		return badge;
+	}
+
+	/** Virtual column metadata for prop_<key> and fx_<name> fields. */
+	private _propertyColumn(field: AxisField): ColumnInfo | undefined {
+		const schema = this._config.schema;
+		if (!schema) return undefined;
+		return [...schema.getPropertyColumns(), ...schema.getFormulaColumns()].find((c) => c.name === field);
+	}
+
 	// -----------------------------------------------------------------------
 	// Private — LATCH chip badge + family change (UCFG-01)
 	// -----------------------------------------------------------------------
@@ -795,7 +848,7 @@ export class PropertiesExplorer {
 		this._editingField = field;
 		this._editCommitted = false;
 
-		const currentAlias = this._config.alias.getAlias(field);
+		const currentAlias = this._displayName(field);
 
 		// Remove the name span
 		const nameSpan = row.querySelector('.properties-explorer__property-name');
@@ -870,10 +923,10 @@ export class PropertiesExplorer {
 		this._editingField = null;
 
 		const trimmed = value.trim();
-		if (trimmed && trimmed !== field) {
+		if (trimmed && trimmed !== field && trimmed !== this._propertyColumn(field)?.label) {
 			this._config.alias.setAlias(field, trimmed);
 		} else {
-			// Empty or same as original field name: clear alias
+			// Empty or same as original field name / property label: clear alias
 			this._config.alias.clearAlias(field);
 		}
 
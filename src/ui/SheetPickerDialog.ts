@@ -0,0 +1,142 @@
+// Isometry v5 — SheetPickerDialog
+// Modal listing the sheets of an Excel workbook with their row counts, so the
+// user picks which sheets import together as one dataset before anything is written.
+
+import type { ExcelSheetInfo } from '../etl/parsers/ExcelParser';
+import '../styles/sheet-picker.css';
+
+export interface SheetPickerData {
+	filename: string;
+	sheets: ExcelSheetInfo[];
+}
+
+/** Header names shown per sheet before truncating */
+const HEADER_PREVIEW_COUNT = 5;
+
+export const SheetPickerDialog = {
+	/**
+	 * Show the sheet picker. Sheets with rows start checked.
+	 * Resolves with the checked sheet names in workbook order, or null on Cancel/Escape.
+	 */
+	show(data: SheetPickerData): Promise<string[] | null> {
+		return new Promise<string[] | null>((resolve) => {
+			const dialog = document.createElement('dialog');
+			dialog.className = 'sheet-picker-modal';
+			dialog.setAttribute('aria-labelledby', 'sheet-picker-title');
+			dialog.setAttribute('aria-describedby', 'sheet-picker-message');
+			dialog.setAttribute('aria-modal', 'true');
+
+			// Title
+			const titleEl = document.createElement('h2');
+			titleEl.id = 'sheet-picker-title';
+			titleEl.className = 'sheet-picker-modal__title';
+			titleEl.textContent = `Import Sheets \u2014 ${data.filename}`;
+
+			const messageEl = document.createElement('p');
+			messageEl.id = 'sheet-picker-message';
+			messageEl.className = 'sheet-picker-modal__message';
+			messageEl.textContent =
+				'Each sheet becomes a folder. Columns that hold another sheet\u2019s ids become connections.';
+
+			// One checkbox row per sheet
+			const listEl = document.createElement('ul');
+			listEl.className = 'sheet-picker-list';
+			listEl.setAttribute('role', 'list');
+
+			const checkboxes: HTMLInputElement[] = [];
+			for (const sheet of data.sheets) {
+				const li = document.createElement('li');
+				li.className = 'sheet-picker-list__item';
+
+				const label = document.createElement('label');
+				label.className = 'sheet-picker-list__label';
+
+				const checkbox = document.createElement('input');
+				checkbox.type = 'checkbox';
+				checkbox.value = sheet.name;
+				checkbox.checked = sheet.rows > 0;
+				checkboxes.push(checkbox);
+
+				const nameSpan = document.createElement('span');
+				nameSpan.className = 'sheet-picker-list__name';
+				nameSpan.textContent = sheet.name;
+
+				const countSpan = document.createElement('span');
+				countSpan.className = 'sheet-picker-list__count';
+				countSpan.textContent = `${sheet.rows} ${sheet.rows === 1 ? 'row' : 'rows'}`;
+
+				label.appendChild(checkbox);
+				label.appendChild(nameSpan);
+				label.appendChild(countSpan);
+				li.appendChild(label);
+
+				if (sheet.headers.length > 0) {
+					const headersEl = document.createElement('div');
+					headersEl.className = 'sheet-picker-list__headers';
+					const shown = sheet.headers.slice(0, HEADER_PREVIEW_COUNT).join(', ');
+					headersEl.textContent = sheet.headers.length > HEADER_PREVIEW_COUNT ? `${shown}, \u2026` : shown;
+					li.appendChild(headersEl);
+				}
+
+				listEl.appendChild(li);
+			}
+
+			// Actions row (reuses app-dialog pattern)
+			const actionsEl = document.createElement('div');
+			actionsEl.className = 'app-dialog__actions';
+
+			const cancelBtn = document.createElement('button');
+			cancelBtn.type = 'button';
+			cancelBtn.className = 'app-dialog__btn app-dialog__btn--cancel';
+			cancelBtn.textContent = 'Cancel';
+
+			const importBtn = document.createElement('button');
+			importBtn.type = 'button';
+			importBtn.className = 'app-dialog__btn app-dialog__btn--confirm';
+
+			actionsEl.appendChild(cancelBtn);
+			actionsEl.appendChild(importBtn);
+
+			// Import button reflects the selection; nothing checked disables it
+			const selected = (): string[] => checkboxes.filter((c) => c.checked).map((c) => c.value);
+			const updateImportBtn = (): void => {
+				const count = selected().length;
+				importBtn.textContent = count === 1 ? 'Import 1 Sheet' : `Import ${count} Sheets`;
+				importBtn.disabled = count === 0;
+			};
+			for (const checkbox of checkboxes) checkbox.addEventListener('change', updateImportBtn);
+			updateImportBtn();
+
+			// Assemble dialog
+			dialog.appendChild(titleEl);
+			dialog.appendChild(messageEl);
+			dialog.appendChild(listEl);
+			dialog.appendChild(actionsEl);
+			document.body.appendChild(dialog);
+
+			// Cleanup helper
+			const cleanup = (result: string[] | null) => {
+				dialog.close();
+				dialog.remove();
+				resolve(result);
+			};
+
+			importBtn.addEventListener('click', () => cleanup(selected()));
+			cancelBtn.addEventListener('click', () => cleanup(null));
+
+			// Escape = cancel
+			dialog.addEventListener('cancel', (e) => {
+				e.preventDefault();
+				cleanup(null);
+			});
+
+			// Backdrop click = cancel
+			dialog.addEventListener('click', (e) => {
+				if (e.target === dialog) cleanup(null);
+			});
+
+			dialog.showModal();
+			importBtn.focus();
+		});
+	},
+};
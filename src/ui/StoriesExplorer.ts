@@ -0,0 +1,445 @@
+// Isometry v5 — Stories Explorer
+// StoriesExplorer: build and present stories — ordered slides of captured
+// view states with markdown captions ("Stories" panel).
+//
+// Design:
+//   - Renders inside the sidecar bottom slot (replaces the "Coming soon" stub)
+//   - Story picker + name field; "Add slide" captures the current view via
+//     StoryManager.capture() (view type, PAFV, filters, density, selection)
+//   - Slide list: caption (markdown, saved on change), show, move up/down,
+//     update (recapture) and remove
+//   - Player: previous/next and timed autoplay. Slides are applied through
+//     StoryManager.applySlide() (ViewManager morphs or crossfades the view);
+//     the caption stage crossfades with crossfadeTransition()
+//   - Export writes a standalone HTML file via story:export + renderStoryHtml()
+//   - CSS classes for all visual styling (no inline colors)
+
+import type { Story } from '../database/queries/stories';
+import type { StoryManager } from '../stories/StoryManager';
+import { renderCaptionHtml, renderStoryHtml, storyExportFilename } from '../stories/storyExport';
+import { crossfadeTransition } from '../views/transitions';
+import type { WorkerBridge } from '../worker/WorkerBridge';
+import '../styles/stories-explorer.css';
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/** Constructor dependencies for StoriesExplorer. */
+export interface StoriesExplorerConfig {
+	manager: StoryManager;
+	bridge: Pick<WorkerBridge, 'exportStory'>;
+	container: HTMLElement;
+	/** Save an exported file (main.ts triggers a Blob download) */
+	download: (filename: string, html: string) => void;
+}
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/** Autoplay dwell time per slide. */
+const SLIDE_INTERVAL_MS = 6000;
+
+const CAPTION_FADE_MS = 300;
+
+// ---------------------------------------------------------------------------
+// StoriesExplorer
+// ---------------------------------------------------------------------------
+
+export class StoriesExplorer {
+	private readonly _manager: StoryManager;
+	private readonly _bridge: Pick<WorkerBridge, 'exportStory'>;
+	private readonly _container: HTMLElement;
+	private readonly _download: (filename: string, html: string) => void;
+
+	private _storyId: string | null = null;
+	/** Index of the slide last shown by the player; -1 before playback */
+	private _current = -1;
+	private _playing = false;
+	private _autoplayTimer: ReturnType<typeof setTimeout> | null = null;
+	private _unsubscribe: (() => void) | null = null;
+
+	private _wrapperEl: HTMLElement | null = null;
+	private _storySelect: HTMLSelectElement | null = null;
+	private _nameInput: HTMLInputElement | null = null;
+	private _storyControls: HTMLElement | null = null;
+	private _positionEl: HTMLElement | null = null;
+	private _playButton: HTMLButtonElement | null = null;
+	private _stageEl: HTMLElement | null = null;
+	private _slidesEl: HTMLOListElement | null = null;
+	private _statusEl: HTMLElement | null = null;
+
+	constructor(config: StoriesExplorerConfig) {
+		this._manager = config.manager;
+		this._bridge = config.bridge;
+		this._container = config.container;
+		this._download = config.download;
+	}
+
+	// -----------------------------------------------------------------------
+	// Lifecycle
+	// -----------------------------------------------------------------------
+
+	/**
+	 * Build the panel DOM and subscribe to story list changes.
+	 */
+	mount(): void {
+		const wrapper = document.createElement('div');
+		wrapper.className = 'stories-explorer';
+
+		// Story picker
+		const header = document.createElement('div');
+		header.className = 'stories-explorer__header';
+
+		const storySelect = document.createElement('select');
+		storySelect.className = 'stories-explorer__story';
+		storySelect.setAttribute('aria-label', 'Story');
+		storySelect.addEventListener('change', () => this._selectStory(storySelect.value || null));
+		header.appendChild(storySelect);
+
+		header.appendChild(
+			this._button('stories-explorer__new', 'New story', () => void this._run(() => this._createStory())),
+		);
+		wrapper.appendChild(header);
+
+		// Controls for the selected story
+		const controls = document.createElement('div');
+		controls.className = 'stories-explorer__controls';
+
+		const nameInput = document.createElement('input');
+		nameInput.type = 'text';
+		nameInput.className = 'stories-explorer__name';
+		nameInput.setAttribute('aria-label', 'Story name');
+		nameInput.addEventListener('change', () => {
+			const id = this._storyId;
+			if (id && nameInput.value.trim() !== '') void this._run(() => this._manager.rename(id, nameInput.value));
+		});
+		controls.appendChild(nameInput);
+
+		const actions = document.createElement('div');
+		actions.className = 'stories-explorer__actions';
+		actions.append(
+			this._button('stories-explorer__add', 'Add slide', () => this._withStory((id) => this._manager.addSlide(id))),
+			this._button('stories-explorer__export', 'Export HTML', () => this._withStory((id) => this._export(id))),
+			this._button('stories-explorer__delete', 'Delete story', () => this._withStory((id) => this._deleteStory(id))),
+		);
+		controls.appendChild(actions);
+
+		// Player
+		const player = document.createElement('div');
+		player.className = 'stories-explorer__player';
+		const prev = this._button('stories-explorer__prev', '\u2039', () => void this._step(-1));
+		prev.setAttribute('aria-label', 'Previous slide');
+		player.appendChild(prev);
+		const play = this._button('stories-explorer__play', 'Play', () => this._toggleAutoplay());
+		player.appendChild(play);
+		const next = this._button('stories-explorer__next', '\u203A', () => void this._step(1));
+		next.setAttribute('aria-label', 'Next slide');
+		player.appendChild(next);
+		const position = document.createElement('span');
+		position.className = 'stories-explorer__position';
+		player.appendChild(position);
+		controls.appendChild(player);
+
+		const stage = document.createElement('div');
+		stage.className = 'stories-explorer__stage';
+		stage.setAttribute('aria-live', 'polite');
+		controls.appendChild(stage);
+
+		const slides = document.createElement('ol');
+		slides.className = 'stories-explorer__slides';
+		controls.appendChild(slides);
+		wrapper.appendChild(controls);
+
+		const status = document.createElement('div');
+		status.className = 'stories-explorer__status';
+		status.setAttribute('role', 'status');
+		wrapper.appendChild(status);
+
+		this._container.appendChild(wrapper);
+		this._wrapperEl = wrapper;
+		this._storySelect = storySelect;
+		this._nameInput = nameInput;
+		this._storyControls = controls;
+		this._positionEl = position;
+		this._playButton = play;
+		this._stageEl = stage;
+		this._slidesEl = slides;
+		this._statusEl = status;
+
+		this._unsubscribe = this._manager.subscribe(() => this._render());
+		this._render();
+	}
+
+	/** Stop playback, unsubscribe and remove the panel DOM. */
+	destroy(): void {
+		this._stopAutoplay();
+		this._unsubscribe?.();
+		this._unsubscribe = null;
+		this._wrapperEl?.remove();
+		this._wrapperEl = null;
+		this._storySelect = null;
+		this._nameInput = null;
+		this._storyControls = null;
+		this._positionEl = null;
+		this._playButton = null;
+		this._stageEl = null;
+		this._slidesEl = null;
+		this._statusEl = null;
+	}
+
+	// -----------------------------------------------------------------------
+	// Private — story actions
+	// -----------------------------------------------------------------------
+
+	private _story(): Story | null {
+		return this._storyId ? this._manager.get(this._storyId) : null;
+	}
+
+	private _selectStory(id: string | null): void {
+		this._stopAutoplay();
+		this._storyId = id;
+		this._current = -1;
+		this._setStage('');
+		this._render();
+	}
+
+	private async _createStory(): Promise<void> {
+		const story = await this._manager.create(`Story ${this._manager.list().length + 1}`);
+		this._selectStory(story.id);
+		this._nameInput?.select();
+	}
+
+	private async _deleteStory(id: string): Promise<void> {
+		await this._manager.delete(id);
+		this._selectStory(null);
+	}
+
+	private async _export(id: string): Promise<void> {
+		const { story, slides } = await this._bridge.exportStory(id);
+		this._download(storyExportFilename(story), renderStoryHtml(story, slides));
+	}
+
+	// -----------------------------------------------------------------------
+	// Private — playback
+	// -----------------------------------------------------------------------
+
+	private async _step(delta: number): Promise<void> {
+		const story = this._story();
+		if (!story || story.slides.length === 0) return;
+		const index = Math.max(0, Math.min(story.slides.length - 1, this._current + delta));
+		if (index === this._current) {
+			this._stopAutoplay();
+			return;
+		}
+		await this._show(index);
+	}
+
+	private async _show(index: number): Promise<void> {
+		const slide = this._story()?.slides[index];
+		if (!slide) return;
+		this._current = index;
+		this._renderPosition();
+		this._highlightCurrent();
+		const applied = await this._manager.applySlide(slide);
+		this._setStatus(applied ? '' : `Slide ${index + 1} no longer applies to this data`);
+		await this._crossfadeCaption(slide.caption);
+	}
+
+	private _toggleAutoplay(): void {
+		if (this._playing) {
+			this._stopAutoplay();
+			return;
+		}
+		const story = this._story();
+		if (!story || story.slides.length === 0) return;
+		this._playing = true;
+		if (this._playButton) this._playButton.textContent = 'Pause';
+		const start = this._current >= story.slides.length - 1 ? 0 : this._current + 1;
+		void this._show(start).then(() => this._scheduleNext());
+	}
+
+	private _scheduleNext(): void {
+		if (!this._playing) return;
+		this._autoplayTimer = setTimeout(() => {
+			this._autoplayTimer = null;
+			const count = this._story()?.slides.length ?? 0;
+			if (this._current >= count - 1) {
+				this._stopAutoplay();
+				return;
+			}
+			void this._show(this._current + 1).then(() => this._scheduleNext());
+		}, SLIDE_INTERVAL_MS);
+	}
+
+	private _stopAutoplay(): void {
+		if (this._autoplayTimer !== null) clearTimeout(this._autoplayTimer);
+		this._autoplayTimer = null;
+		this._playing = false;
+		if (this._playButton) this._playButton.textContent = 'Play';
+	}
+
+	private async _crossfadeCaption(caption: string): Promise<void> {
+		const stage = this._stageEl;
+		if (!stage) return;
+		await crossfadeTransition(
+			stage,
+			() => {
+				const incoming = stage.lastElementChild as HTMLElement | null;
+				if (incoming) incoming.innerHTML = renderCaptionHtml(caption);
+			},
+			CAPTION_FADE_MS,
+		);
+	}
+
+	// -----------------------------------------------------------------------
+	// Private — rendering
+	// -----------------------------------------------------------------------
+
+	private _render(): void {
+		const select = this._storySelect;
+		if (!select) return;
+		const stories = this._manager.list();
+		if (this._storyId && !stories.some((s) => s.id === this._storyId)) this._storyId = null;
+		if (!this._storyId && stories.length > 0) this._storyId = stories[0]!.id;
+
+		select.textContent = '';
+		if (stories.length === 0) {
+			const none = document.createElement('option');
+			none.value = '';
+			none.textContent = 'No stories yet';
+			select.appendChild(none);
+		}
+		for (const story of stories) {
+			const option = document.createElement('option');
+			option.value = story.id;
+			option.textContent = story.name;
+			select.appendChild(option);
+		}
+		select.value = this._storyId ?? '';
+		select.disabled = stories.length === 0;
+
+		const story = this._story();
+		if (this._storyControls) this._storyControls.hidden = story === null;
+		if (this._nameInput && document.activeElement !== this._nameInput) this._nameInput.value = story?.name ?? '';
+		if (story && this._current >= story.slides.length) this._current = story.slides.length - 1;
+		this._renderPosition();
+		this._renderSlides(story);
+	}
+
+	private _renderPosition(): void {
+		if (!this._positionEl) return;
+		const count = this._story()?.slides.length ?? 0;
+		this._positionEl.textContent = count === 0 ? 'No slides' : `${this._current + 1} / ${count}`;
+	}
+
+	private _renderSlides(story: Story | null): void {
+		const list = this._slidesEl;
+		if (!list) return;
+		list.textContent = '';
+		if (!story) return;
+
+		if (story.slides.length === 0) {
+			const empty = document.createElement('li');
+			empty.className = 'stories-explorer__empty';
+			empty.textContent = 'Set up a view, then add it as a slide';
+			list.appendChild(empty);
+			return;
+		}
+
+		story.slides.forEach((slide, index) => {
+			const item = document.createElement('li');
+			item.className = 'stories-explorer__slide';
+			item.dataset['id'] = slide.id;
+
+			const head = document.createElement('div');
+			head.className = 'stories-explorer__slide-head';
+			const view = document.createElement('span');
+			view.className = 'stories-explorer__slide-view';
+			view.textContent = slide.state.view;
+			head.appendChild(view);
+
+			head.appendChild(this._button('stories-explorer__show', 'Show', () => void this._show(index)));
+			const up = this._button('stories-explorer__up', '\u2191', () =>
+				this._withStory((id) => this._manager.moveSlide(id, slide.id, -1)),
+			);
+			up.disabled = index === 0;
+			up.setAttribute('aria-label', 'Move slide up');
+			head.appendChild(up);
+			const down = this._button('stories-explorer__down', '\u2193', () =>
+				this._withStory((id) => this._manager.moveSlide(id, slide.id, 1)),
+			);
+			down.disabled = index === story.slides.length - 1;
+			down.setAttribute('aria-label', 'Move slide down');
+			head.appendChild(down);
+			const update = this._button('stories-explorer__recapture', 'Update', () =>
+				this._withStory((id) => this._manager.recapture(id, slide.id)),
+			);
+			update.title = 'Replace with the current view';
+			head.appendChild(update);
+			const remove = this._button('stories-explorer__remove', '\u00D7', () =>
+				this._withStory((id) => this._manager.removeSlide(id, slide.id)),
+			);
+			remove.setAttribute('aria-label', `Remove slide ${index + 1}`);
+			head.appendChild(remove);
+			item.appendChild(head);
+
+			const caption = document.createElement('textarea');
+			caption.className = 'stories-explorer__caption';
+			caption.rows = 2;
+			caption.placeholder = 'Caption (Markdown)';
+			caption.value = slide.caption;
+			caption.setAttribute('aria-label', `Caption for slide ${index + 1}`);
+			caption.addEventListener('change', () =>
+				this._withStory((id) => this._manager.setCaption(id, slide.id, caption.value)),
+			);
+			item.appendChild(caption);
+
+			list.appendChild(item);
+		});
+		this._highlightCurrent();
+	}
+
+	private _highlightCurrent(): void {
+		this._slidesEl?.querySelectorAll('.stories-explorer__slide').forEach((el, i) => {
+			el.classList.toggle('stories-explorer__slide--current', i === this._current);
+		});
+	}
+
+	private _setStage(html: string): void {
+		if (this._stageEl) this._stageEl.innerHTML = html;
+	}
+
+	private _setStatus(text: string): void {
+		if (this._statusEl) this._statusEl.textContent = text;
+	}
+
+	// -----------------------------------------------------------------------
+	// Private — helpers
+	// -----------------------------------------------------------------------
+
+	private _button(className: string, label: string, onClick: () => void): HTMLButtonElement {
+		const button = document.createElement('button');
+		button.type = 'button';
+		button.className = className;
+		button.textContent = label;
+		button.addEventListener('click', onClick);
+		return button;
+	}
+
+	/** Run an action against the selected story, reporting failures in the status line. */
+	private _withStory(action: (storyId: string) => Promise<unknown>): void {
+		const id = this._storyId;
+		if (id) void this._run(() => action(id));
+	}
+
+	private async _run(action: () => Promise<unknown>): Promise<void> {
+		try {
+			await action();
+			this._setStatus('');
+		} catch (err) {
+			this._setStatus(err instanceof Error ? err.message : String(err));
+		}
+	}
+}
@@ -149,7 +149,7 @@ export const DOCK_DEFS: readonly DockSectionDef[] = [
 		items: [
 			{ key: 'supergrid', label: 'SuperGrids', icon: 'table-2' },
 			{ key: 'timeline', label: 'Timelines', icon: 'clock' },
-			{ key: 'network', label: 'Maps', icon: 'map' },
+			{ key: 'map', label: 'Maps', icon: 'map' },
 			{ key: 'tree', label: 'Charts', icon: 'trending-up' },
 			{ key: 'graph', label: 'Graphs', icon: 'share-2' },
 		],
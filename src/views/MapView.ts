@@ -0,0 +1,480 @@
+// Isometry v5 — Map View
+// Geographic view plotting cards by latitude/longitude on an offline basemap.
+//
+// Design:
+//   - Implements IView: mount() once, render() on each data update, destroy() before replacement
+//   - Positions come from the Worker via bridge.send('map:query') — card coordinates
+//     first, then the geocode_places gazetteer via location_name
+//   - Basemap is the bundled world countries TopoJSON (views/map/basemap.ts); no tiles
+//   - Markers cluster on a screen-space grid at low zoom; clicking a cluster zooms in
+//   - Connections are drawn as great-circle arcs (d3.geoPath resamples LineStrings
+//     along the sphere)
+//   - Selection goes through SelectionProvider, same click semantics as NetworkView
+//   - D3 key function `d => d.id` is MANDATORY on every .data() call (VIEW-09)
+
+import '../styles/map-view.css';
+
+import * as d3 from 'd3';
+import type { SelectionProvider } from '../providers/SelectionProvider';
+import type { WorkerResponses } from '../worker/protocol';
+import { type CountryFeature, loadBasemap } from './map/basemap';
+import { clusterPoints, type MapMarker, type MapPoint } from './map/cluster';
+import type { CardDatum, IView, WorkerBridgeLike } from './types';
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+const DEFAULT_WIDTH = 800;
+const DEFAULT_HEIGHT = 500;
+const PADDING = 16;
+const MAX_ZOOM = 64;
+
+/** Cluster grid cell size in screen pixels at zoom level 0 */
+const CLUSTER_CELL = 40;
+/** Zoom level (log2 of scale) from which every card is drawn individually */
+const CLUSTER_MAX_LEVEL = 4;
+
+const POINT_RADIUS = 5;
+const MIN_CLUSTER_RADIUS = 8;
+const MAX_CLUSTER_RADIUS = 22;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+type MapConnection = WorkerResponses['map:query']['connections'][number];
+
+/** Arc between two markers, keyed by the unordered marker pair */
+interface ArcDatum {
+	id: string;
+	source: [number, number];
+	target: [number, number];
+	count: number;
+	label: string;
+}
+
+/** Config accepted by MapView constructor */
+export interface MapViewConfig {
+	bridge: WorkerBridgeLike;
+	selectionProvider?: SelectionProvider;
+	/** Basemap loader; defaults to the bundled countries TopoJSON (injectable for tests) */
+	loadBasemap?: () => Promise<CountryFeature[] | null>;
+}
+
+// ---------------------------------------------------------------------------
+// MapView
+// ---------------------------------------------------------------------------
+
+/**
+ * Geographic map view.
+ *
+ * Cards with a resolvable position render as circles; nearby cards merge into
+ * numbered cluster markers until the user zooms in. Cards that could not be
+ * placed are counted in the status bar.
+ *
+ * @implements IView
+ */
+export class MapView implements IView {
+	private readonly bridge: WorkerBridgeLike;
+	private readonly selectionProvider: SelectionProvider | null;
+	private readonly loadBasemap: () => Promise<CountryFeature[] | null>;
+
+	// DOM references (null until mounted)
+	private svg: d3.Selection<SVGSVGElement, unknown, null, undefined> | null = null;
+	private mapLayer: d3.Selection<SVGGElement, unknown, null, undefined> | null = null;
+	private countriesGroup: d3.Selection<SVGGElement, unknown, null, undefined> | null = null;
+	private arcsGroup: d3.Selection<SVGGElement, unknown, null, undefined> | null = null;
+	private markersGroup: d3.Selection<SVGGElement, unknown, null, undefined> | null = null;
+	private statusEl: HTMLDivElement | null = null;
+	private zoom: d3.ZoomBehavior<SVGSVGElement, unknown> | null = null;
+
+	// Geometry
+	private projection: d3.GeoProjection = d3.geoNaturalEarth1();
+	private path: d3.GeoPath = d3.geoPath(this.projection);
+	private width = DEFAULT_WIDTH;
+	private height = DEFAULT_HEIGHT;
+
+	// State
+	private points: MapPoint[] = [];
+	private connections: MapConnection[] = [];
+	private markers: MapMarker[] = [];
+	private scale = 1;
+	private clusterLevel = 0;
+	private fitted = false;
+	private lastCards: CardDatum[] = [];
+	private destroyed = false;
+
+	// Subscription cleanup
+	private unsubscribeSelection: (() => void) | null = null;
+
+	constructor(config: MapViewConfig) {
+		this.bridge = config.bridge;
+		this.selectionProvider = config.selectionProvider ?? null;
+		this.loadBasemap = config.loadBasemap ?? (() => loadBasemap());
+	}
+
+	// ---------------------------------------------------------------------------
+	// IView: mount
+	// ---------------------------------------------------------------------------
+
+	/**
+	 * Mount the map into the given container.
+	 * Draws the sphere and graticule immediately; countries follow once the
+	 * basemap asset has loaded.
+	 */
+	mount(container: HTMLElement): void {
+		this.destroyed = false;
+		this.width = container.clientWidth || DEFAULT_WIDTH;
+		this.height = container.clientHeight || DEFAULT_HEIGHT;
+
+		this.projection = d3.geoNaturalEarth1().fitExtent(
+			[
+				[PADDING, PADDING],
+				[this.width - PADDING, this.height - PADDING],
+			],
+			{ type: 'Sphere' },
+		);
+		this.path = d3.geoPath(this.projection);
+
+		this.svg = d3
+			.select<HTMLElement, unknown>(container)
+			.append<SVGSVGElement>('svg')
+			.attr('class', 'map-view')
+			.attr('viewBox', `0 0 ${this.width} ${this.height}`)
+			.attr('width', '100%')
+			.attr('height', '100%')
+			.attr('role', 'img')
+			.attr('aria-label', 'Map view, 0 cards');
+
+		this.mapLayer = this.svg.append<SVGGElement>('g').attr('class', 'map-view__layer');
+		this.mapLayer.append('path').attr('class', 'map-view__sphere').attr('d', this.path({ type: 'Sphere' }));
+		this.mapLayer.append('path').attr('class', 'map-view__graticule').attr('d', this.path(d3.geoGraticule10()));
+		this.countriesGroup = this.mapLayer.append<SVGGElement>('g').attr('class', 'map-view__countries');
+		this.arcsGroup = this.mapLayer.append<SVGGElement>('g').attr('class', 'map-view__arcs');
+		this.markersGroup = this.mapLayer.append<SVGGElement>('g').attr('class', 'map-view__markers');
+
+		this.statusEl = document.createElement('div');
+		this.statusEl.className = 'map-view__status';
+		this.statusEl.setAttribute('role', 'status');
+		container.style.position = 'relative';
+		container.appendChild(this.statusEl);
+
+		const zoom = d3
+			.zoom<SVGSVGElement, unknown>()
+			.scaleExtent([1, MAX_ZOOM])
+			.translateExtent([
+				[0, 0],
+				[this.width, this.height],
+			])
+			.on('zoom', (event: d3.D3ZoomEvent<SVGSVGElement, unknown>) => {
+				this._onZoom(event.transform);
+			});
+		this.svg.call(zoom);
+		this.zoom = zoom;
+
+		if (this.selectionProvider) {
+			this.unsubscribeSelection = this.selectionProvider.subscribe(() => {
+				this._updateSelectionHighlights();
+			});
+		}
+
+		void this.loadBasemap().then((features) => {
+			if (this.destroyed || !features) return;
+			this._drawCountries(features);
+		});
+	}
+
+	// ---------------------------------------------------------------------------
+	// IView: render
+	// ---------------------------------------------------------------------------
+
+	/**
+	 * Render cards on the map.
+	 *
+	 * Workflow:
+	 *   1. Fetch positions + placed connections via map:query
+	 *   2. Fit the view to the points on the first non-empty render
+	 *   3. Cluster for the current zoom level and draw markers + arcs
+	 *
+	 * @param cards - Array of CardDatum to render. Empty array = clear markers.
+	 */
+	async render(cards: CardDatum[]): Promise<void> {
+		if (this.destroyed || !this.svg) return;
+		this.lastCards = cards;
+		this.svg.attr('aria-label', `Map view, ${cards.length} cards`);
+
+		let result: WorkerResponses['map:query'] = { points: [], connections: [], unlocated: cards.length };
+		if (cards.length > 0) {
+			try {
+				result = (await this.bridge.send('map:query', {
+					cardIds: cards.map((c) => c.id),
+				})) as WorkerResponses['map:query'];
+			} catch (err) {
+				console.error('[MapView] map:query failed:', err);
+			}
+		}
+		if (this.destroyed) return;
+
+		this.points = result.points;
+		this.connections = result.connections;
+		this._updateStatus(result);
+
+		if (!this.fitted && this.points.length > 0) {
+			this.fitted = true;
+			this._fitToPoints();
+		}
+		this._drawMarkers();
+	}
+
+	// ---------------------------------------------------------------------------
+	// IView: destroy
+	// ---------------------------------------------------------------------------
+
+	/** Remove DOM, zoom listeners and the selection subscription. */
+	destroy(): void {
+		this.destroyed = true;
+		this.unsubscribeSelection?.();
+		this.unsubscribeSelection = null;
+		this.svg?.on('.zoom', null);
+		this.svg?.remove();
+		this.statusEl?.remove();
+		this.svg = null;
+		this.mapLayer = null;
+		this.countriesGroup = null;
+		this.arcsGroup = null;
+		this.markersGroup = null;
+		this.statusEl = null;
+		this.zoom = null;
+		this.points = [];
+		this.connections = [];
+		this.markers = [];
+	}
+
+	// ---------------------------------------------------------------------------
+	// Public API
+	// ---------------------------------------------------------------------------
+
+	/** Current markers (clusters and single points) — exposed for tests and minimap. */
+	getMarkers(): readonly MapMarker[] {
+		return this.markers;
+	}
+
+	/**
+	 * Zoom to a marker. Clusters zoom in until they split; single points
+	 * are centered at the current scale.
+	 */
+	zoomToMarker(markerId: string): void {
+		const marker = this.markers.find((m) => m.id === markerId);
+		if (!marker) return;
+		const k = marker.cardIds.length > 1 ? Math.min(MAX_ZOOM, this.scale * 2) : this.scale;
+		this._zoomTo(marker.x, marker.y, k);
+	}
+
+	// ---------------------------------------------------------------------------
+	// Drawing
+	// ---------------------------------------------------------------------------
+
+	private _drawCountries(features: CountryFeature[]): void {
+		if (!this.countriesGroup) return;
+		this.countriesGroup
+			.selectAll<SVGPathElement, CountryFeature>('path.map-view__country')
+			.data(features, (d) => d.id)
+			.join('path')
+			.attr('class', 'map-view__country')
+			.attr('d', (d) => this.path(d));
+	}
+
+	/** Re-cluster for the current zoom level and redraw markers and arcs. */
+	private _drawMarkers(): void {
+		if (!this.markersGroup || !this.arcsGroup) return;
+
+		const cellSize = this.clusterLevel >= CLUSTER_MAX_LEVEL ? 0 : CLUSTER_CELL / 2 ** this.clusterLevel;
+		this.markers = clusterPoints(this.points, (p) => this.projection([p.longitude, p.latitude]), cellSize);
+
+		const maxCount = Math.max(2, ...this.markers.map((m) => m.cardIds.length));
+		const clusterRadius = d3.scaleSqrt().domain([2, maxCount]).range([MIN_CLUSTER_RADIUS, MAX_CLUSTER_RADIUS]);
+		const radius = (m: MapMarker) => (m.cardIds.length > 1 ? clusterRadius(m.cardIds.length) : POINT_RADIUS);
+		const k = this.scale;
+
+		this.arcsGroup
+			.selectAll<SVGPathElement, ArcDatum>('path.map-view__arc')
+			.data(this._buildArcs(), (d) => d.id)
+			.join(
+				(enter) => enter.append('path').attr('class', 'map-view__arc').call((p) => p.append('title')),
+				(update) => update,
+				(exit) => exit.remove(),
+			)
+			.attr('d', (d) => this.path({ type: 'LineString', coordinates: [d.source, d.target] }))
+			.attr('stroke-width', (d) => Math.min(4, 1 + Math.log2(d.count)) / k)
+			.select('title')
+			.text((d) => d.label);
+
+		const self = this;
+		this.markersGroup
+			.selectAll<SVGGElement, MapMarker>('g.map-view__marker')
+			.data(this.markers, (d) => d.id)
+			.join(
+				(enter) => {
+					const g = enter.append<SVGGElement>('g').attr('class', 'map-view__marker');
+					g.append('circle');
+					g.append('text').attr('class', 'map-view__count').attr('text-anchor', 'middle').attr('dy', '0.35em');
+					g.append('title');
+					g.on('click', (event: MouseEvent, d) => {
+						event.stopPropagation();
+						self._onMarkerClick(event, d);
+					});
+					return g;
+				},
+				(update) => update,
+				(exit) => exit.remove(),
+			)
+			.classed('map-view__marker--cluster', (d) => d.cardIds.length > 1)
+			.classed('map-view__marker--geocoded', (d) => d.geocoded)
+			.attr('data-id', (d) => d.id)
+			.attr('transform', (d) => `translate(${d.x},${d.y}) scale(${1 / k})`)
+			.call((g) => g.select('circle').attr('r', radius))
+			.call((g) => g.select('text').text((d) => (d.cardIds.length > 1 ? String(d.cardIds.length) : '')))
+			.call((g) =>
+				g.select('title').text((d) => {
+					if (d.cardIds.length > 1) return `${d.cardIds.length} cards`;
+					const name = this.lastCards.find((c) => c.id === d.id)?.name ?? d.id;
+					return d.geocoded ? `${name} (from place name)` : name;
+				}),
+			);
+
+		this._updateSelectionHighlights();
+	}
+
+	/**
+	 * Aggregate connections onto the markers that contain their endpoints.
+	 * Connections inside one cluster are dropped; parallel ones are merged.
+	 */
+	private _buildArcs(): ArcDatum[] {
+		const markerOf = new Map<string, MapMarker>();
+		for (const marker of this.markers) {
+			for (const id of marker.cardIds) markerOf.set(id, marker);
+		}
+
+		const arcs = new Map<string, ArcDatum>();
+		for (const connection of this.connections) {
+			const a = markerOf.get(connection.sourceId);
+			const b = markerOf.get(connection.targetId);
+			if (!a || !b || a === b) continue;
+			const id = a.id < b.id ? `${a.id}|${b.id}` : `${b.id}|${a.id}`;
+			const existing = arcs.get(id);
+			if (existing) {
+				existing.count++;
+				existing.label = `${existing.count} connections`;
+				continue;
+			}
+			const source = this.projection.invert?.([a.x, a.y]);
+			const target = this.projection.invert?.([b.x, b.y]);
+			if (!source || !target) continue;
+			arcs.set(id, { id, source, target, count: 1, label: connection.label || 'Connection' });
+		}
+		return [...arcs.values()];
+	}
+
+	private _updateSelectionHighlights(): void {
+		if (!this.markersGroup) return;
+		const selected = new Set(this.selectionProvider?.getSelectedIds() ?? []);
+		this.markersGroup
+			.selectAll<SVGGElement, MapMarker>('g.map-view__marker')
+			.classed('map-view__marker--selected', (d) => d.cardIds.some((id) => selected.has(id)));
+	}
+
+	private _updateStatus(result: WorkerResponses['map:query']): void {
+		if (!this.statusEl) return;
+		this.statusEl.replaceChildren();
+
+		const geocoded = result.points.filter((p) => p.geocoded);
+		const parts = [`${result.points.length} placed`];
+		if (geocoded.length > 0) parts.push(`${geocoded.length} from place names`);
+		if (result.unlocated > 0) parts.push(`${result.unlocated} without location`);
+
+		const text = document.createElement('span');
+		text.textContent = parts.join(' \u00B7 ');
+		this.statusEl.appendChild(text);
+
+		if (geocoded.length > 0) {
+			const save = document.createElement('button');
+			save.type = 'button';
+			save.className = 'map-view__geocode';
+			save.textContent = 'Save coordinates';
+			save.title = 'Write coordinates looked up from place names into the cards';
+			save.addEventListener('click', () => {
+				void this._saveGeocoded(geocoded.map((p) => p.id));
+			});
+			this.statusEl.appendChild(save);
+		}
+	}
+
+	private async _saveGeocoded(cardIds: string[]): Promise<void> {
+		try {
+			await this.bridge.send('geocode:fill', { cardIds });
+		} catch (err) {
+			console.error('[MapView] geocode:fill failed:', err);
+			return;
+		}
+		await this.render(this.lastCards);
+	}
+
+	// ---------------------------------------------------------------------------
+	// Interaction
+	// ---------------------------------------------------------------------------
+
+	private _onMarkerClick(event: MouseEvent, marker: MapMarker): void {
+		if (marker.cardIds.length > 1) {
+			this.zoomToMarker(marker.id);
+			return;
+		}
+		if (!this.selectionProvider) return;
+		if (event.shiftKey || event.metaKey || event.ctrlKey) {
+			this.selectionProvider.toggle(marker.id);
+		} else {
+			this.selectionProvider.select(marker.id);
+		}
+	}
+
+	private _onZoom(transform: d3.ZoomTransform): void {
+		this.mapLayer?.attr('transform', transform.toString());
+		this.scale = transform.k;
+		const level = Math.floor(Math.log2(transform.k));
+		if (level !== this.clusterLevel) {
+			this.clusterLevel = level;
+			this._drawMarkers();
+			return;
+		}
+		// Same level: keep markers and arcs a constant on-screen size
+		this.markersGroup
+			?.selectAll<SVGGElement, MapMarker>('g.map-view__marker')
+			.attr('transform', (d) => `translate(${d.x},${d.y}) scale(${1 / transform.k})`);
+		this.arcsGroup
+			?.selectAll<SVGPathElement, ArcDatum>('path.map-view__arc')
+			.attr('stroke-width', (d) => Math.min(4, 1 + Math.log2(d.count)) / transform.k);
+	}
+
+	private _zoomTo(x: number, y: number, k: number): void {
+		if (!this.svg || !this.zoom) return;
+		const transform = d3.zoomIdentity
+			.translate(this.width / 2, this.height / 2)
+			.scale(k)
+			.translate(-x, -y);
+		this.svg.call(this.zoom.transform, transform);
+	}
+
+	/** Zoom so every point is visible (never zooms out past the whole world). */
+	private _fitToPoints(): void {
+		const projected = this.points
+			.map((p) => this.projection([p.longitude, p.latitude]))
+			.filter((xy): xy is [number, number] => xy !== null);
+		if (projected.length === 0) return;
+
+		const [x0, x1] = d3.extent(projected, (xy) => xy[0]) as [number, number];
+		const [y0, y1] = d3.extent(projected, (xy) => xy[1]) as [number, number];
+		const span = Math.max(x1 - x0, y1 - y0, 1);
+		const k = Math.max(1, Math.min(2 ** CLUSTER_MAX_LEVEL, (0.8 * Math.min(this.width, this.height)) / span));
+		this._zoomTo((x0 + x1) / 2, (y0 + y1) / 2, k);
+	}
+}
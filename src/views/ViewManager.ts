@@ -83,6 +83,11 @@ const VIEW_EMPTY_MESSAGES: Record<string, ViewEmptyMessage> = {
 		heading: 'No data to project',
 		description: 'Import data to see the SuperGrid projection.',
 	},
+	map: {
+		icon: '\uD83D\uDDFA',
+		heading: 'No cards to map',
+		description: 'Cards need coordinates or a known place name to appear on the map.',
+	},
 };
 
 // ---------------------------------------------------------------------------
@@ -9,6 +9,7 @@ export { GridView } from './GridView';
 export { KanbanView } from './KanbanView';
 // Views
 export { ListView } from './ListView';
+export { MapView } from './MapView';
 export { NetworkView } from './NetworkView';
 export { ProductionSuperGrid as SuperGrid } from './pivot/ProductionSuperGrid';
 export { TimelineView } from './TimelineView';
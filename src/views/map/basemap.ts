@@ -0,0 +1,150 @@
+// Isometry v5 — Map View Basemap
+// Offline world basemap: the world-atlas countries TopoJSON, copied into the
+// build's assets/ folder by vite-plugin-static-copy and decoded here.
+//
+// Design:
+//   - No network tiles — the basemap ships with the app and works offline
+//   - Minimal TopoJSON → GeoJSON decoder (Polygon / MultiPolygon /
+//     GeometryCollection only) instead of a topojson-client dependency
+//   - The decoded basemap is cached for the session; a failed load resolves
+//     to null so the map still renders its sphere and graticule
+
+// ---------------------------------------------------------------------------
+// TopoJSON types (subset used by world-atlas)
+// ---------------------------------------------------------------------------
+
+type Position = [number, number];
+
+interface TopoTransform {
+	scale: [number, number];
+	translate: [number, number];
+}
+
+interface TopoPolygon {
+	type: 'Polygon';
+	arcs: number[][];
+	id?: string | number;
+	properties?: Record<string, unknown>;
+}
+
+interface TopoMultiPolygon {
+	type: 'MultiPolygon';
+	arcs: number[][][];
+	id?: string | number;
+	properties?: Record<string, unknown>;
+}
+
+interface TopoCollection {
+	type: 'GeometryCollection';
+	geometries: TopoGeometry[];
+}
+
+type TopoGeometry = TopoPolygon | TopoMultiPolygon | TopoCollection | { type: null };
+
+export interface Topology {
+	type: 'Topology';
+	arcs: Position[][];
+	transform?: TopoTransform;
+	objects: Record<string, TopoGeometry>;
+}
+
+// ---------------------------------------------------------------------------
+// GeoJSON output
+// ---------------------------------------------------------------------------
+
+export interface CountryFeature {
+	type: 'Feature';
+	id: string;
+	properties: { name: string };
+	geometry: { type: 'Polygon'; coordinates: Position[][] } | { type: 'MultiPolygon'; coordinates: Position[][][] };
+}
+
+/** Default asset path, relative to index.html (same convention as the sql.js WASM). */
+export const BASEMAP_URL = './assets/countries-110m.json';
+
+// ---------------------------------------------------------------------------
+// Decoder
+// ---------------------------------------------------------------------------
+
+/**
+ * Decode the polygons of one TopoJSON object into GeoJSON country features.
+ * Null and non-polygon geometries are skipped.
+ */
+export function topologyToFeatures(topology: Topology, objectName: string): CountryFeature[] {
+	const object = topology.objects[objectName];
+	if (!object) return [];
+
+	const arcs = decodeArcs(topology);
+	const ring = (indices: number[]): Position[] => {
+		const points: Position[] = [];
+		for (const index of indices) {
+			const arc = index < 0 ? [...(arcs[~index] ?? [])].reverse() : (arcs[index] ?? []);
+			// Consecutive arcs share their joining point
+			points.push(...(points.length > 0 ? arc.slice(1) : arc));
+		}
+		return points;
+	};
+
+	const features: CountryFeature[] = [];
+	const visit = (geometry: TopoGeometry): void => {
+		if (geometry.type === 'GeometryCollection') {
+			for (const child of geometry.geometries) visit(child);
+			return;
+		}
+		if (geometry.type !== 'Polygon' && geometry.type !== 'MultiPolygon') return;
+
+		const id = geometry.id === undefined ? String(features.length) : String(geometry.id);
+		const name = typeof geometry.properties?.['name'] === 'string' ? geometry.properties['name'] : '';
+		const shape: CountryFeature['geometry'] =
+			geometry.type === 'Polygon'
+				? { type: 'Polygon', coordinates: geometry.arcs.map(ring) }
+				: { type: 'MultiPolygon', coordinates: geometry.arcs.map((polygon) => polygon.map(ring)) };
+		features.push({ type: 'Feature', id, properties: { name }, geometry: shape });
+	};
+	visit(object);
+	return features;
+}
+
+/** Resolve quantized, delta-encoded arcs to absolute longitude/latitude. */
+function decodeArcs(topology: Topology): Position[][] {
+	const transform = topology.transform;
+	if (!transform) return topology.arcs;
+	const [sx, sy] = transform.scale;
+	const [tx, ty] = transform.translate;
+	return topology.arcs.map((arc) => {
+		let x = 0;
+		let y = 0;
+		return arc.map(([dx, dy]): Position => {
+			x += dx;
+			y += dy;
+			return [x * sx + tx, y * sy + ty];
+		});
+	});
+}
+
+// ---------------------------------------------------------------------------
+// Loader
+// ---------------------------------------------------------------------------
+
+let cached: Promise<CountryFeature[] | null> | null = null;
+
+/**
+ * Load and decode the bundled countries basemap (cached per session).
+ * Resolves to null when the asset is missing or malformed.
+ */
+export function loadBasemap(url: string = BASEMAP_URL): Promise<CountryFeature[] | null> {
+	if (!cached) {
+		cached = fetch(url)
+			.then((response) => {
+				if (!response.ok) throw new Error(`HTTP ${response.status}`);
+				return response.json() as Promise<Topology>;
+			})
+			.then((topology) => topologyToFeatures(topology, 'countries'))
+			.catch((err: unknown) => {
+				console.warn('[MapView] Basemap unavailable, drawing graticule only:', err);
+				cached = null; // allow a retry on the next mount
+				return null;
+			});
+	}
+	return cached;
+}
@@ -0,0 +1,68 @@
+// Isometry v5 — Map View Clustering
+// Screen-space grid clustering for map markers at low zoom.
+//
+// Points are bucketed by their projected position into square cells of
+// `cellSize` pixels. A bucket with one point stays a plain marker keyed by
+// its card id; larger buckets become one cluster marker at the mean position
+// of their members, keyed by the cell so D3 joins stay stable while panning.
+
+export interface MapPoint {
+	id: string;
+	latitude: number;
+	longitude: number;
+	/** Position came from the geocode_places gazetteer, not the card itself */
+	geocoded: boolean;
+}
+
+export interface MapMarker {
+	/** Card id for single points, `cluster:<col>:<row>` for clusters */
+	id: string;
+	/** Projected position (unzoomed screen coordinates) */
+	x: number;
+	y: number;
+	/** Member card ids (length 1 for single points) */
+	cardIds: string[];
+	/** True when every member position was geocoded */
+	geocoded: boolean;
+}
+
+/**
+ * Group points into markers.
+ *
+ * @param points - Points to cluster
+ * @param project - Maps a point to unzoomed screen coordinates, or null if not visible
+ * @param cellSize - Grid cell size in unzoomed pixels; 0 disables clustering
+ */
+export function clusterPoints(
+	points: readonly MapPoint[],
+	project: (point: MapPoint) => [number, number] | null,
+	cellSize: number,
+): MapMarker[] {
+	const buckets = new Map<string, { x: number; y: number; points: MapPoint[] }>();
+	for (const point of points) {
+		const xy = project(point);
+		if (!xy) continue;
+		const key = cellSize > 0 ? `cluster:${Math.floor(xy[0] / cellSize)}:${Math.floor(xy[1] / cellSize)}` : point.id;
+		const bucket = buckets.get(key);
+		if (bucket) {
+			bucket.x += xy[0];
+			bucket.y += xy[1];
+			bucket.points.push(point);
+		} else {
+			buckets.set(key, { x: xy[0], y: xy[1], points: [point] });
+		}
+	}
+
+	const markers: MapMarker[] = [];
+	for (const [key, bucket] of buckets) {
+		const n = bucket.points.length;
+		markers.push({
+			id: n === 1 ? bucket.points[0]!.id : key,
+			x: bucket.x / n,
+			y: bucket.y / n,
+			cardIds: bucket.points.map((p) => p.id),
+			geocoded: bucket.points.every((p) => p.geocoded),
+		});
+	}
+	return markers;
+}
@@ -11,7 +11,7 @@
 // Requirements: CONV-01
 
 import type { AxisMapping } from '../../providers/types';
-import type { CellDatum } from '../../worker/protocol';
+import type { CellDatum, SuperGridQueryConfig } from '../../worker/protocol';
 import type { SuperGridBridgeLike, SuperGridDensityLike, SuperGridFilterLike, SuperGridProviderLike } from '../types';
 import type { DataAdapter, FetchDataResult } from './DataAdapter';
 import { getCellKey } from './PivotMockData';
@@ -165,13 +165,18 @@ export class BridgeDataAdapter implements DataAdapter {
 			}
 		}
 
-		const cells = await this._bridge.superGridQuery({
+		const config: SuperGridQueryConfig = {
 			rowAxes,
 			colAxes,
 			where,
 			params,
 			granularity: densityState.axisGranularity,
-		});
+		};
+		// DYNM-10: schema Time fields (date properties and formulas included) get strftime bucketing
+		const timeFields = this._timeFields();
+		if (timeFields !== null) config.timeFields = timeFields;
+
+		const cells = await this._bridge.superGridQuery(config);
 
 		// Convert CellDatum[] to Map<string, number|null> using getCellKey format
 		// Key format: rowPath.join('|')::colPath.join('|') — matches PivotGrid's getCellKey
@@ -216,6 +221,21 @@ export class BridgeDataAdapter implements DataAdapter {
 		return this._coordinator.subscribe(cb);
 	}
 
+	/** Time-family field names from the wired SchemaProvider (null until one is wired). */
+	private _timeFields(): string[] | null {
+		if (
+			this._schema === null ||
+			typeof this._schema !== 'object' ||
+			typeof (this._schema as { getFieldsByFamily?: unknown }).getFieldsByFamily !== 'function'
+		) {
+			return null;
+		}
+		const schemaWithFamilies = this._schema as {
+			getFieldsByFamily(family: 'Time'): ReadonlyArray<{ name: string }>;
+		};
+		return schemaWithFamilies.getFieldsByFamily('Time').map((col) => col.name);
+	}
+
 	getProviderContext(): Record<string, unknown> {
 		return {
 			bridge: this._bridge,
@@ -12,6 +12,7 @@
 
 import '../../styles/pivot.css';
 import styles from '../../styles/pivot.module.css';
+import { isCancelledError } from '../../worker/protocol';
 import type { DataAdapter } from './DataAdapter';
 import { MockDataAdapter } from './MockDataAdapter';
 import { PivotConfigPanel } from './PivotConfigPanel';
@@ -308,6 +309,8 @@ export class PivotTable {
 				);
 			})
 			.catch((err: unknown) => {
+				// Superseded by a newer fetch — the newer render owns the grid
+				if (isCancelledError(err)) return;
 				console.error('[PivotTable] fetchData failed:', err);
 				this._showErrorBanner();
 			});
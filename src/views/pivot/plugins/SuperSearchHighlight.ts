@@ -8,9 +8,11 @@
 //   - Non-matching cells when search active: remove .search-match, set opacity 0.35
 //   - Empty term: remove all .search-match classes and reset opacity
 //   - destroy: clean up all highlights across the document
+//   - Matching uses the same parsed terms as SuperSearchInput (parseSearchQuery)
 //
 // Requirements: SRCH-02
 
+import { matchesSearchTerms, parseSearchQuery } from '../../../providers/search-query';
 import type { PluginHook, RenderContext } from './PluginTypes';
 import type { SearchState } from './SuperSearchInput';
 
@@ -45,7 +47,9 @@ export function createSuperSearchHighlightPlugin(searchState: SearchState): Plug
 			const cells = root.querySelectorAll<HTMLElement>('.pv-data-cell');
 			const term = searchState.term;
 
-			if (!term) {
+			const parsed = parseSearchQuery(term);
+
+			if (!term || parsed.terms.length === 0) {
 				// No active search — remove all highlights
 				for (const cell of cells) {
 					cell.classList.remove('search-match');
@@ -54,11 +58,8 @@ export function createSuperSearchHighlightPlugin(searchState: SearchState): Plug
 				return;
 			}
 
-			const lowerTerm = term.toLowerCase();
-
 			for (const cell of cells) {
-				const text = cell.textContent ?? '';
-				if (text.toLowerCase().includes(lowerTerm)) {
+				if (matchesSearchTerms(cell.textContent ?? '', parsed)) {
 					cell.classList.add('search-match');
 					cell.style.opacity = '';
 				} else {
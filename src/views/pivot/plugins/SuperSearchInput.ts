@@ -8,9 +8,13 @@
 //   - afterRender creates .pv-search-toolbar with input[type="search"] and clear button
 //   - Input events are debounced 300ms to avoid excessive re-renders
 //   - Shared SearchState allows highlight plugin to read the current term
+//   - Terms use the shared search query language (parseSearchQuery): quoted
+//     phrases and multiple terms match with AND; field clauses (status:done)
+//     only apply to card searches, not cell keys. Syntax errors mark the input invalid.
 //
 // Requirements: SRCH-01
 
+import { matchesSearchTerms, parseSearchQuery } from '../../../providers/search-query';
 import type { CellPlacement, PluginHook, RenderContext } from './PluginTypes';
 
 // ---------------------------------------------------------------------------
@@ -56,8 +60,11 @@ export function createSuperSearchInputPlugin(searchState: SearchState, onSearchC
 			if (!searchState.term) {
 				return cells;
 			}
-			const lowerTerm = searchState.term.toLowerCase();
-			return cells.filter((cell) => cell.key.toLowerCase().includes(lowerTerm));
+			const parsed = parseSearchQuery(searchState.term);
+			if (parsed.terms.length === 0) {
+				return cells;
+			}
+			return cells.filter((cell) => matchesSearchTerms(cell.key, parsed));
 		},
 
 		afterRender(root: HTMLElement, _ctx: RenderContext): void {
@@ -87,10 +94,25 @@ export function createSuperSearchInputPlugin(searchState: SearchState, onSearchC
 			clearBtn.setAttribute('aria-label', 'Clear search');
 			clearBtn.style.display = searchState.term ? '' : 'none';
 
+			// Syntax feedback: invalid state + first error as tooltip
+			const showSyntax = (value: string): void => {
+				const error = value ? parseSearchQuery(value).errors[0] : undefined;
+				input.classList.toggle('pv-search-input--invalid', error !== undefined);
+				if (error) {
+					input.setAttribute('aria-invalid', 'true');
+					input.title = error.message;
+				} else {
+					input.removeAttribute('aria-invalid');
+					input.removeAttribute('title');
+				}
+			};
+			showSyntax(input.value);
+
 			// Debounced input handler
 			input.addEventListener('input', () => {
-				// Show/hide clear button immediately
+				// Show/hide clear button and syntax state immediately
 				clearBtn.style.display = input.value ? '' : 'none';
+				showSyntax(input.value);
 
 				// Cancel previous debounce
 				if (_debounceTimer !== null) {
@@ -115,6 +137,7 @@ export function createSuperSearchInputPlugin(searchState: SearchState, onSearchC
 				}
 				input.value = '';
 				clearBtn.style.display = 'none';
+				showSyntax('');
 				searchState.term = '';
 				for (const listener of searchState.listeners) {
 					listener('');
@@ -12,6 +12,7 @@
 // Requirements: REND-02
 
 import { validateAxisField } from '../../providers/allowlist';
+import { fieldExpr } from '../../providers/properties';
 import type { AggregationMode, AxisField, AxisMapping, TimeGranularity } from '../../providers/types';
 
 // ---------------------------------------------------------------------------
@@ -59,10 +60,14 @@ const STRFTIME_PATTERNS: Record<string, (field: string) => string> = {
 
 /**
  * Compile an axis field expression.
- * If the field is a time field, wraps it in COALESCE(strftime(...), '__NO_DATE__').
+ * If the field is a time field, wraps its qualified expression in
+ * COALESCE(strftime(...), '__NO_DATE__').
  * When granularity is null/undefined and the field is a time field, auto-defaults
  * to 'month' (Phase 136 TIME-02, D-06, D-07).
- * Non-time fields always return the raw field name unchanged.
+ * Non-time fields always return the qualified expression unchanged.
+ *
+ * The time-field check uses the raw field name: prop_<key> and fx_<key> fields
+ * are qualified into subqueries that never appear in timeFieldSet.
  *
  * CRITICAL: validateAxisField(field) MUST be called BEFORE this function.
  * The strftime expression is NOT in the allowlist — validation must happen
@@ -70,6 +75,7 @@ const STRFTIME_PATTERNS: Record<string, (field: string) => string> = {
  */
 function compileAxisExpr(
 	field: string,
+	qualified: string,
 	granularity: TimeGranularity | null | undefined,
 	timeFieldSet?: Set<string>,
 ): string {
@@ -78,9 +84,9 @@ function compileAxisExpr(
 		// Auto-default to 'month' when granularity is null/undefined (D-06, D-07)
 		const effectiveGranularity: TimeGranularity = granularity ?? 'month';
 		const pattern = STRFTIME_PATTERNS[effectiveGranularity];
-		if (pattern) return `COALESCE(${pattern(field)}, '${NO_DATE_SENTINEL}')`;
+		if (pattern) return `COALESCE(${pattern(qualified)}, '${NO_DATE_SENTINEL}')`;
 	}
-	return field;
+	return qualified;
 }
 
 /**
@@ -231,15 +237,17 @@ export function buildSuperGridQuery(config: SuperGridQueryConfig): CompiledSuper
 		validateAxisField(s.field); // throws "SQL safety violation:..." if invalid
 	}
 
-	// Phase 116 helper: prefix metric columns with graph_metrics. table qualifier
-	const qualifyField = (field: string): string => (activeMetrics.has(field) ? `graph_metrics.${field}` : field);
+	// Phase 116 helper: prefix metric columns with graph_metrics. table qualifier;
+	// custom property fields (prop_<key>) become card_properties subqueries
+	const qualifyField = (field: string): string =>
+		activeMetrics.has(field) ? `graph_metrics.${field}` : fieldExpr(field);
 
 	// Build SELECT fields: compile each axis field (may wrap in strftime for time fields)
 	// Raw field name used as alias (e.g., `strftime('%Y-%m', created_at) AS created_at`)
 	const allAxes = [...colAxes, ...rowAxes];
 	const selectParts = allAxes.map((ax) => {
 		const qualified = qualifyField(ax.field);
-		const expr = compileAxisExpr(qualified, granularity, timeFieldSet);
+		const expr = compileAxisExpr(ax.field, qualified, granularity, timeFieldSet);
 		// If expression differs from raw field name, use field name as alias for downstream consumers
 		return expr !== ax.field ? `${expr} AS ${ax.field}` : qualified;
 	});
@@ -260,7 +268,7 @@ export function buildSuperGridQuery(config: SuperGridQueryConfig): CompiledSuper
 	// Build GROUP BY clause using qualified/compiled expressions
 	const groupByExprs = allAxes.map((ax) => {
 		const qualified = qualifyField(ax.field);
-		return compileAxisExpr(qualified, granularity, timeFieldSet);
+		return compileAxisExpr(ax.field, qualified, granularity, timeFieldSet);
 	});
 	const groupByClause = groupByExprs.length > 0 ? `GROUP BY ${groupByExprs.join(', ')}` : '';
 
@@ -271,13 +279,13 @@ export function buildSuperGridQuery(config: SuperGridQueryConfig): CompiledSuper
 	const effectiveTimeFieldsForOrder = timeFieldSet ?? ALLOWED_TIME_FIELDS_FALLBACK;
 	const axisOrderByParts = allAxes.map((ax) => {
 		const qualified = qualifyField(ax.field);
-		const expr = compileAxisExpr(qualified, granularity, timeFieldSet);
+		const expr = compileAxisExpr(ax.field, qualified, granularity, timeFieldSet);
 		if (effectiveTimeFieldsForOrder.has(ax.field)) {
 			return compileTimeAxisOrderBy(expr, ax.direction.toUpperCase());
 		}
 		return `${expr} ${ax.direction.toUpperCase()}`;
 	});
-	const overrideParts = sortOverrides.map((s) => `${s.field} ${s.direction.toUpperCase()}`);
+	const overrideParts = sortOverrides.map((s) => `${qualifyField(s.field)} ${s.direction.toUpperCase()}`);
 	const orderByParts = [...axisOrderByParts, ...overrideParts];
 	const orderByClause = orderByParts.length > 0 ? `ORDER BY ${orderByParts.join(', ')}` : '';
 
@@ -289,7 +297,7 @@ export function buildSuperGridQuery(config: SuperGridQueryConfig): CompiledSuper
 		aggExpr = 'COUNT(*) AS count';
 	} else {
 		const aggFn = aggregation.toUpperCase();
-		aggExpr = `${aggFn}(${displayField}) AS count`;
+		aggExpr = `${aggFn}(${qualifyField(displayField)}) AS count`;
 	}
 
 	// Phase 116: Use explicit table prefixes for card_ids/card_names when JOIN is active
@@ -383,7 +391,8 @@ export function buildSuperGridCalcQuery(config: {
 	// Phase 116: Determine active metric columns for LEFT JOIN
 	const activeMetrics = new Set(config.metricsColumns?.filter((c) => ALLOWED_METRIC_COLUMNS.has(c)) ?? []);
 	const needsJoin = activeMetrics.size > 0;
-	const qualifyField = (field: string): string => (activeMetrics.has(field) ? `graph_metrics.${field}` : field);
+	const qualifyField = (field: string): string =>
+		activeMetrics.has(field) ? `graph_metrics.${field}` : fieldExpr(field);
 
 	// Validate all row axis fields against the allowlist (D-003 SQL safety)
 	for (const axis of rowAxes) {
@@ -400,14 +409,14 @@ export function buildSuperGridCalcQuery(config: {
 	// Row axis fields for group key
 	for (const ax of rowAxes) {
 		const qualified = qualifyField(ax.field);
-		const expr = compileAxisExpr(qualified, granularity, timeFieldSet);
+		const expr = compileAxisExpr(ax.field, qualified, granularity, timeFieldSet);
 		selectParts.push(expr !== ax.field ? `${expr} AS ${ax.field}` : qualified);
 	}
 
 	// Column axis fields for group key (Phase 68: per-column footer aggregation)
 	for (const ax of colAxes) {
 		const qualified = qualifyField(ax.field);
-		const expr = compileAxisExpr(qualified, granularity, timeFieldSet);
+		const expr = compileAxisExpr(ax.field, qualified, granularity, timeFieldSet);
 		selectParts.push(expr !== ax.field ? `${expr} AS ${ax.field}` : qualified);
 	}
 
@@ -456,7 +465,7 @@ export function buildSuperGridCalcQuery(config: {
 	const allGroupAxes = [...rowAxes, ...colAxes];
 	const groupByExprs = allGroupAxes.map((ax) => {
 		const qualified = qualifyField(ax.field);
-		return compileAxisExpr(qualified, granularity, timeFieldSet);
+		return compileAxisExpr(ax.field, qualified, granularity, timeFieldSet);
 	});
 	const groupByClause = groupByExprs.length > 0 ? `GROUP BY ${groupByExprs.join(', ')}` : '';
 
@@ -465,7 +474,7 @@ export function buildSuperGridCalcQuery(config: {
 	const effectiveTimeFieldsForOrder = timeFieldSet ?? ALLOWED_TIME_FIELDS_FALLBACK;
 	const orderByParts = rowAxes.map((ax) => {
 		const qualified = qualifyField(ax.field);
-		const expr = compileAxisExpr(qualified, granularity, timeFieldSet);
+		const expr = compileAxisExpr(ax.field, qualified, granularity, timeFieldSet);
 		if (effectiveTimeFieldsForOrder.has(ax.field)) {
 			return compileTimeAxisOrderBy(expr, ax.direction.toUpperCase());
 		}
@@ -15,6 +15,7 @@
 
 import { endTrace, startTrace } from '../profiling/PerfTrace';
 import type {
+	CancelReason,
 	CanonicalCard,
 	Card,
 	CardInput,
@@ -24,12 +25,30 @@ import type {
 	Connection,
 	ConnectionDirection,
 	ConnectionInput,
+	ConnectionUpdate,
+	CursorPage,
+	CursorSource,
+	EnricherSetting,
+	ExcelSheetInfo,
+	FormulaDefinitionInput,
+	FormulaInfo,
 	ImportResult,
+	MappingProfile,
+	MappingProfileInput,
 	PendingRequest,
+	PropertyDefinition,
+	PropertyDefinitionInput,
+	PropertyValue,
+	SavedSearch,
 	SearchResult,
+	SendOptions,
+	SimilarCardResult,
 	SourceType,
+	Story,
+	StoryInput,
 	SuperGridQueryConfig,
 	WorkerBridgeConfig,
+	WorkerCancelMessage,
 	WorkerMessage,
 	WorkerNotification,
 	WorkerPayloads,
@@ -42,6 +61,7 @@ import {
 	DEFAULT_WORKER_CONFIG,
 	ETL_TIMEOUT,
 	GRAPH_ALGO_TIMEOUT,
+	isCancelledMessage,
 	isInitErrorMessage,
 	isNotification,
 	isReadyMessage,
@@ -89,6 +109,9 @@ export class WorkerBridge {
 	/** Map of pending requests by correlation ID */
 	private readonly pending: Map<string, PendingRequest> = new Map();
 
+	/** Latest pending request ID per latest-wins channel key */
+	private readonly channels: Map<string, string> = new Map();
+
 	/** Whether the worker has signaled ready */
 	private ready = false;
 
@@ -232,6 +255,17 @@ export class WorkerBridge {
 		return this.send('connection:get', { cardId, direction });
 	}
 
+	/**
+	 * Update a connection's label, weight and/or via card in place.
+	 * Bypasses undo — UI edits should go through updateConnectionMutation.
+	 * @param id - Connection UUID
+	 * @param updates - Fields to change
+	 * @returns The updated Connection
+	 */
+	async updateConnection(id: string, updates: ConnectionUpdate): Promise<Connection> {
+		return this.send('connection:update', { id, updates });
+	}
+
 	/**
 	 * Delete a connection.
 	 * @param id - Connection UUID
@@ -240,12 +274,237 @@ export class WorkerBridge {
 		return this.send('connection:delete', { id });
 	}
 
+	// ---------------------------------------------------------------------------
+	// Custom Card Properties
+	// ---------------------------------------------------------------------------
+
+	/**
+	 * List all custom property definitions.
+	 * Map through propertyColumnInfo() to refresh SchemaProvider.setPropertyColumns().
+	 */
+	async listProperties(): Promise<PropertyDefinition[]> {
+		return this.send('property:list', {});
+	}
+
+	/**
+	 * Define a new typed custom property (exposed as the prop_<key> field).
+	 * @param input - Label, type and optional key / enum values
+	 * @returns The created definition
+	 */
+	async defineProperty(input: PropertyDefinitionInput): Promise<PropertyDefinition> {
+		return this.send('property:define', { input });
+	}
+
+	/**
+	 * Delete a custom property and all of its card values.
+	 * @param key - Property key (without the prop_ prefix)
+	 */
+	async deleteProperty(key: string): Promise<void> {
+		return this.send('property:delete', { key });
+	}
+
+	/**
+	 * Set a card's value for a custom property. Empty values clear it.
+	 * Rejects when the value does not fit the property type.
+	 */
+	async setCardProperty(cardId: string, key: string, value: unknown): Promise<void> {
+		return this.send('property:set', { cardId, key, value });
+	}
+
+	/**
+	 * Get a card's custom property values keyed by property key.
+	 */
+	async getCardProperties(cardId: string): Promise<Record<string, PropertyValue>> {
+		return this.send('property:get', { cardId });
+	}
+
+	// ---------------------------------------------------------------------------
+	// Saved Searches
+	// ---------------------------------------------------------------------------
+
+	/**
+	 * List saved searches ordered by name, with live card counts.
+	 */
+	async listSavedSearches(): Promise<SavedSearch[]> {
+		return this.send('saved-search:list', {});
+	}
+
+	/**
+	 * Save a FilterProvider state under a name (replaces an existing search with that name).
+	 * @param name - Display name
+	 * @param state - FilterProvider.toJSON()
+	 */
+	async saveSearch(name: string, state: string): Promise<SavedSearch> {
+		return this.send('saved-search:save', { name, state });
+	}
+
+	/**
+	 * Rename a saved search.
+	 */
+	async renameSavedSearch(id: string, name: string): Promise<void> {
+		return this.send('saved-search:rename', { id, name });
+	}
+
+	/**
+	 * Delete a saved search.
+	 */
+	async deleteSavedSearch(id: string): Promise<void> {
+		return this.send('saved-search:delete', { id });
+	}
+
+	// ---------------------------------------------------------------------------
+	// Import Mapping Profiles
+	// ---------------------------------------------------------------------------
+
+	/**
+	 * List import mapping profiles ordered by name.
+	 */
+	async listMappingProfiles(): Promise<MappingProfile[]> {
+		return this.send('mapping-profile:list', {});
+	}
+
+	/**
+	 * Save a mapping profile (replaces an existing profile with that name).
+	 */
+	async saveMappingProfile(input: MappingProfileInput): Promise<MappingProfile> {
+		return this.send('mapping-profile:save', { input });
+	}
+
+	/**
+	 * Delete a mapping profile.
+	 */
+	async deleteMappingProfile(id: string): Promise<void> {
+		return this.send('mapping-profile:delete', { id });
+	}
+
+	/**
+	 * Read the header row of an import and suggest a saved profile for it.
+	 * Uses the ETL timeout — Excel workbooks are parsed to find the header row.
+	 *
+	 * @param source - Source type the data would be imported as
+	 * @param data - File content, as passed to importFile
+	 * @param filename - Source filename
+	 * @param sheet - Excel sheet whose header row to read (default: first sheet)
+	 */
+	async suggestMappingProfile(
+		source: SourceType,
+		data: string | ArrayBuffer,
+		filename?: string,
+		sheet?: string,
+	): Promise<WorkerResponses['mapping-profile:suggest']> {
+		const payload: WorkerPayloads['mapping-profile:suggest'] = { source, data };
+		if (filename !== undefined) payload.filename = filename;
+		if (sheet !== undefined) payload.sheet = sheet;
+		return this.send('mapping-profile:suggest', payload, ETL_TIMEOUT);
+	}
+
+	// ---------------------------------------------------------------------------
+	// Import Enrichers
+	// ---------------------------------------------------------------------------
+
+	/**
+	 * List the registered enrichers with their saved per-source settings.
+	 */
+	async listEnrichers(): Promise<WorkerResponses['enrich:list']> {
+		return this.send('enrich:list', {});
+	}
+
+	/**
+	 * Switch an enricher on or off for a source type ('*' for all sources).
+	 */
+	async setEnricherSetting(setting: EnricherSetting): Promise<void> {
+		return this.send('enrich:set', setting);
+	}
+
+	/**
+	 * Re-run enrichers over every imported card.
+	 * Uses the ETL timeout — a re-run touches the whole database.
+	 *
+	 * @param enricherIds - Enrichers to re-run (default: all enabled)
+	 */
+	async backfillEnrichment(enricherIds?: string[]): Promise<WorkerResponses['enrich:backfill']> {
+		const payload: WorkerPayloads['enrich:backfill'] = {};
+		if (enricherIds !== undefined) payload.enricherIds = enricherIds;
+		return this.send('enrich:backfill', payload, ETL_TIMEOUT);
+	}
+
+	// ---------------------------------------------------------------------------
+	// Formula Fields
+	// ---------------------------------------------------------------------------
+
+	/**
+	 * List formula definitions with their compile results.
+	 * Pass through compiledFormulasOf() to refresh the fx_<name> registry.
+	 */
+	async listFormulas(): Promise<FormulaInfo[]> {
+		return this.send('formula:list', {});
+	}
+
+	/**
+	 * Define (or redefine) a formula for a dataset, or globally when dataset_id is null.
+	 * Rejects when the expression does not compile.
+	 */
+	async defineFormula(input: FormulaDefinitionInput): Promise<FormulaInfo> {
+		return this.send('formula:define', { input });
+	}
+
+	/**
+	 * Delete a formula definition.
+	 */
+	async deleteFormula(id: string): Promise<void> {
+		return this.send('formula:delete', { id });
+	}
+
+	/**
+	 * Compile an expression without saving it and evaluate it on a few cards.
+	 */
+	async previewFormula(
+		expression: string,
+		datasetId: string | null = null,
+	): Promise<WorkerResponses['formula:preview']> {
+		return this.send('formula:preview', { expression, datasetId });
+	}
+
+	// ---------------------------------------------------------------------------
+	// Stories
+	// ---------------------------------------------------------------------------
+
+	/**
+	 * List stories ordered by name, each with its ordered slides.
+	 */
+	async listStories(): Promise<Story[]> {
+		return this.send('story:list', {});
+	}
+
+	/**
+	 * Create a story (no id) or replace an existing story's name and slides.
+	 */
+	async saveStory(input: StoryInput): Promise<Story> {
+		return this.send('story:save', { input });
+	}
+
+	/**
+	 * Delete a story and its slides.
+	 */
+	async deleteStory(id: string): Promise<void> {
+		return this.send('story:delete', { id });
+	}
+
+	/**
+	 * Fetch a story with live cards per slide for the standalone HTML export.
+	 */
+	async exportStory(id: string): Promise<WorkerResponses['story:export']> {
+		return this.send('story:export', { id });
+	}
+
 	// ---------------------------------------------------------------------------
 	// Search Operations (SRCH-01..04)
 	// ---------------------------------------------------------------------------
 
 	/**
 	 * Full-text search over cards.
+	 * Search-as-you-type: each call supersedes the previous one on the
+	 * 'search:cards' channel, which then rejects with CANCELLED.
 	 * @param query - FTS5 query string
 	 * @param limit - Maximum results (default 20)
 	 * @returns BM25-ranked results with snippets
@@ -253,7 +512,19 @@ export class WorkerBridge {
 	async searchCards(query: string, limit?: number): Promise<SearchResult[]> {
 		const payload: WorkerPayloads['search:cards'] = { query };
 		if (limit !== undefined) payload.limit = limit;
-		return this.send('search:cards', payload);
+		return this.send('search:cards', payload, { channel: 'search:cards' });
+	}
+
+	/**
+	 * Cards most similar to `cardId` by locally computed TF-IDF vectors.
+	 * @param cardId - Card to find neighbours for
+	 * @param limit - Maximum results (default 10)
+	 * @returns Similar cards ordered by score descending
+	 */
+	async findSimilarCards(cardId: string, limit?: number): Promise<SimilarCardResult[]> {
+		const payload: WorkerPayloads['search:similar'] = { cardId };
+		if (limit !== undefined) payload.limit = limit;
+		return this.send('search:similar', payload);
 	}
 
 	// ---------------------------------------------------------------------------
@@ -359,6 +630,72 @@ export class WorkerBridge {
 		return this.send('db:exec', { sql, params });
 	}
 
+	// ---------------------------------------------------------------------------
+	// Cursor Streaming (paged card:list / db:query)
+	// ---------------------------------------------------------------------------
+
+	/**
+	 * Stream cards page by page instead of one large postMessage array.
+	 * Same filters and ordering as listCards().
+	 *
+	 * Breaking out of the `for await` loop closes the Worker-side cursor.
+	 *
+	 * ```typescript
+	 * for await (const page of bridge.streamCards({ folder: 'Work' })) {
+	 *   renderRows(page);
+	 * }
+	 * ```
+	 *
+	 * @param options - Filter options (folder, status, card_type, source, limit)
+	 * @param pageSize - Rows per page (default CURSOR_PAGE_SIZE, capped by the Worker)
+	 */
+	streamCards(options?: CardListOptions, pageSize?: number): AsyncGenerator<Card[], void, undefined> {
+		const source: CursorSource = options !== undefined ? { kind: 'card:list', options } : { kind: 'card:list' };
+		return this.streamCursor(source, pageSize) as AsyncGenerator<Card[], void, undefined>;
+	}
+
+	/**
+	 * Stream the rows of a parameterized SELECT page by page.
+	 * Streaming counterpart of `send('db:query', ...)`.
+	 *
+	 * @param sql - Parameterized SELECT statement
+	 * @param params - Bound parameters
+	 * @param pageSize - Rows per page (default CURSOR_PAGE_SIZE, capped by the Worker)
+	 */
+	streamQuery(
+		sql: string,
+		params: unknown[],
+		pageSize?: number,
+	): AsyncGenerator<Record<string, unknown>[], void, undefined> {
+		return this.streamCursor({ kind: 'db:query', sql, params }, pageSize);
+	}
+
+	/**
+	 * Drive a Worker-side cursor: open, pull pages until done, and close the
+	 * cursor if the consumer stops early (break/return/throw).
+	 */
+	private async *streamCursor(
+		source: CursorSource,
+		pageSize?: number,
+	): AsyncGenerator<Record<string, unknown>[], void, undefined> {
+		const openPayload: WorkerPayloads['cursor:open'] = { source };
+		if (pageSize !== undefined) openPayload.pageSize = pageSize;
+
+		let page: CursorPage = await this.send('cursor:open', openPayload);
+		try {
+			while (true) {
+				if (page.rows.length > 0) yield page.rows;
+				if (page.done) return;
+				page = await this.send('cursor:next', { cursorId: page.cursorId });
+			}
+		} finally {
+			if (!page.done) {
+				// Consumer stopped early — release the Worker statement (best effort)
+				this.send('cursor:close', { cursorId: page.cursorId }).catch(() => {});
+			}
+		}
+	}
+
 	// ---------------------------------------------------------------------------
 	// ETL Operations (Phase 8)
 	// ---------------------------------------------------------------------------
@@ -369,28 +706,38 @@ export class WorkerBridge {
 	 *
 	 * @param source - Source type identifier
 	 * @param data - File content or file list JSON
-	 * @param options - Import options (bulk mode, filename)
+	 * @param options - Import options (bulk mode, filename, mapping profile id, Excel sheets)
 	 * @returns Import result with counts and inserted IDs
 	 */
 	async importFile(
 		source: SourceType,
 		data: string | ArrayBuffer,
-		options?: { isBulkImport?: boolean; filename?: string },
+		options?: { isBulkImport?: boolean; filename?: string; profileId?: string; sheets?: string[] },
 	): Promise<ImportResult> {
 		const payload: WorkerPayloads['etl:import'] = { source, data };
 		if (options !== undefined) payload.options = options;
 		return await this.send('etl:import', payload, ETL_TIMEOUT);
 	}
 
+	/**
+	 * List the sheets of an Excel workbook with their row counts, for the
+	 * sheet picker shown before import.
+	 *
+	 * @param data - Workbook ArrayBuffer, or base64 from the native shell
+	 */
+	async listExcelSheets(data: string | ArrayBuffer): Promise<ExcelSheetInfo[]> {
+		return this.send('etl:excel-sheets', { data }, ETL_TIMEOUT);
+	}
+
 	/**
 	 * Export cards to a specified format.
 	 *
-	 * @param format - Output format (markdown, json, csv)
+	 * @param format - Output format (markdown, json, csv, opml, ics)
 	 * @param cardIds - Optional card ID filter (from SelectionProvider)
 	 * @returns Export data and suggested filename
 	 */
 	async exportFile(
-		format: 'markdown' | 'json' | 'csv',
+		format: 'markdown' | 'json' | 'csv' | 'opml' | 'ics',
 		cardIds?: string[],
 	): Promise<{ data: string; filename: string }> {
 		const payload: WorkerPayloads['etl:export'] = { format };
@@ -420,7 +767,8 @@ export class WorkerBridge {
 	 * Multiple calls within one frame collapse to a single Worker request.
 	 * Stale responses (from requests superseded by newer ones within the same
 	 * rAF window) are silently discarded -- only the latest caller's promise
-	 * is fulfilled.
+	 * is fulfilled. A request from an earlier frame that is still in flight is
+	 * superseded on the 'supergrid:query' channel and rejects with CANCELLED.
 	 *
 	 * @param config - Column axes, row axes, WHERE clause, and params
 	 * @returns Promise resolving to CellDatum[] (the cells array from the response)
@@ -444,7 +792,8 @@ export class WorkerBridge {
 				this._pendingSuperGridResolve = null;
 				this._pendingSuperGridReject = null;
 
-				this.send('supergrid:query', latestConfig)
+				// Channel keeps at most one supergrid:query queued in the Worker across frames
+				this.send('supergrid:query', latestConfig, { channel: 'supergrid:query' })
 					.then((result) => latestResolve(result.cells))
 					.catch((e) => latestReject(e as Error));
 			});
@@ -511,6 +860,7 @@ export class WorkerBridge {
 		// Reject all pending requests
 		for (const [id, pending] of this.pending) {
 			clearTimeout(pending.timeoutId);
+			pending.cleanup?.();
 			pending.reject(new Error('WorkerBridge terminated'));
 			this.pending.delete(id);
 		}
@@ -534,25 +884,49 @@ export class WorkerBridge {
 	 * Public so that StateManager and MutationManager can use it directly
 	 * for ui:* and db:* operations without requiring dedicated wrapper methods.
 	 *
+	 * Cancellation: pass `{ signal }` to abort, or `{ channel }` for latest-wins
+	 * semantics (e.g. scrubbing a histogram). Cancelled requests reject with an
+	 * error whose code is 'CANCELLED' — check with isCancelledError().
+	 *
 	 * @param type - Request type
 	 * @param payload - Request payload
+	 * @param timeoutOrOptions - Timeout override (ms) or SendOptions
 	 * @returns Promise resolving to response data
 	 */
 	async send<T extends WorkerRequestType>(
 		type: T,
 		payload: WorkerPayloads[T],
-		timeoutOverride?: number,
+		timeoutOrOptions?: number | SendOptions,
 	): Promise<WorkerResponses[T]> {
+		const options: SendOptions =
+			typeof timeoutOrOptions === 'number' ? { timeout: timeoutOrOptions } : (timeoutOrOptions ?? {});
+		const { signal, channel } = options;
+
 		// Wait for worker to be ready
 		await this.isReady;
 
 		return new Promise<WorkerResponses[T]>((resolve, reject) => {
+			if (signal?.aborted) {
+				reject(createCancelledError(type, 'aborted'));
+				return;
+			}
+
 			const id = crypto.randomUUID();
 			const sentAt = Date.now();
-			const effectiveTimeout = timeoutOverride ?? this.config.timeout;
+			const effectiveTimeout = options.timeout ?? this.config.timeout;
+
+			// Latest-wins: a newer request on the same channel supersedes the previous one
+			if (channel !== undefined) {
+				const previousId = this.channels.get(channel);
+				if (previousId !== undefined) {
+					this.cancelPending(previousId, 'superseded');
+				}
+				this.channels.set(channel, id);
+			}
 
 			// Set up timeout
 			const timeoutId = setTimeout(() => {
+				this.pending.get(id)?.cleanup?.();
 				this.pending.delete(id);
 				const error = new Error(`Request ${type} timed out after ${effectiveTimeout}ms`);
 				(error as Error & { code: string }).code = 'TIMEOUT';
@@ -563,6 +937,14 @@ export class WorkerBridge {
 				}
 			}, effectiveTimeout);
 
+			const onAbort = () => {
+				if (this.cancelPending(id, 'aborted')) {
+					// Let the Worker drop the request if it has not started yet
+					this.worker.postMessage({ type: 'cancel', id } satisfies WorkerCancelMessage);
+				}
+			};
+			signal?.addEventListener('abort', onAbort, { once: true });
+
 			// Track pending request
 			const pending: PendingRequest<WorkerResponses[T]> = {
 				resolve: resolve as (value: unknown) => void,
@@ -570,11 +952,17 @@ export class WorkerBridge {
 				timeoutId,
 				type,
 				sentAt,
+				cleanup: () => {
+					signal?.removeEventListener('abort', onAbort);
+					if (channel !== undefined && this.channels.get(channel) === id) {
+						this.channels.delete(channel);
+					}
+				},
 			};
 			this.pending.set(id, pending as PendingRequest);
 
 			// Build and send request
-			const request: WorkerRequest<T> = { id, type, payload };
+			const request: WorkerRequest<T> = channel !== undefined ? { id, type, payload, channel } : { id, type, payload };
 			this.worker.postMessage(request);
 			startTrace(`wb:query:${type}`);
 
@@ -584,6 +972,27 @@ export class WorkerBridge {
 		});
 	}
 
+	/**
+	 * Reject a pending request with a CANCELLED error and stop tracking it.
+	 * Late responses for the request are ignored as "unknown request".
+	 *
+	 * @returns true if the request was still pending
+	 */
+	private cancelPending(id: string, reason: CancelReason): boolean {
+		const pending = this.pending.get(id);
+		if (!pending) return false;
+
+		clearTimeout(pending.timeoutId);
+		pending.cleanup?.();
+		this.pending.delete(id);
+		pending.reject(createCancelledError(pending.type, reason));
+
+		if (this.config.debug) {
+			console.log(`[WorkerBridge] Cancelled (${reason}): ${pending.type} (${id})`);
+		}
+		return true;
+	}
+
 	// ---------------------------------------------------------------------------
 	// Private: Message Handling
 	// ---------------------------------------------------------------------------
@@ -631,6 +1040,13 @@ export class WorkerBridge {
 			return;
 		}
 
+		// Handle cancelled notice — Worker dropped a queued/superseded request
+		// CRITICAL: Must come BEFORE isResponse — cancelled messages carry an `id`
+		if (isCancelledMessage(message)) {
+			this.cancelPending(message.id, message.reason);
+			return;
+		}
+
 		// Handle response
 		if (isResponse(message)) {
 			this.handleResponse(message);
@@ -659,6 +1075,7 @@ export class WorkerBridge {
 
 		// Clear timeout and remove from pending
 		clearTimeout(pending.timeoutId);
+		pending.cleanup?.();
 		this.pending.delete(response.id);
 
 		// Record high-res round-trip measurement via PerfTrace (PROF-01)
@@ -718,6 +1135,21 @@ export class WorkerBridge {
 	}
 }
 
+// ---------------------------------------------------------------------------
+// Cancellation Helpers
+// ---------------------------------------------------------------------------
+
+/**
+ * Build the error a cancelled request rejects with.
+ * code === 'CANCELLED' so callers can filter with isCancelledError().
+ */
+function createCancelledError(type: WorkerRequestType, reason: CancelReason): Error {
+	const error = new Error(`Request ${type} cancelled (${reason})`);
+	error.name = 'AbortError';
+	(error as Error & { code: string }).code = 'CANCELLED';
+	return error;
+}
+
 // ---------------------------------------------------------------------------
 // Singleton / Factory
 // ---------------------------------------------------------------------------
@@ -8,6 +8,7 @@
 
 import type { Database } from '../../database/Database';
 import { validateAxisField } from '../../providers/allowlist';
+import { fieldExpr } from '../../providers/properties';
 import type { WorkerPayloads, WorkerResponses } from '../protocol';
 
 // ---------------------------------------------------------------------------
@@ -35,6 +36,10 @@ export function handleChartQuery(db: Database, payload: WorkerPayloads['chart:qu
 		validateAxisField(yField);
 	}
 
+	// Custom property fields (prop_<key>) resolve to card_properties subqueries
+	const xExpr = fieldExpr(xField);
+	const yExpr = yField !== null ? fieldExpr(yField) : null;
+
 	// Step 2: Determine WHERE clause
 	const baseWhere = payload.where || 'deleted_at IS NULL';
 
@@ -47,26 +52,26 @@ export function handleChartQuery(db: Database, payload: WorkerPayloads['chart:qu
 	if (chartType === 'scatter') {
 		// Scatter: raw x/y pairs, no aggregation
 		sql =
-			`SELECT ${xField} AS x, ${yField} AS y FROM cards` +
-			` WHERE ${baseWhere} AND ${xField} IS NOT NULL AND ${yField} IS NOT NULL`;
+			`SELECT ${xExpr} AS x, ${yExpr} AS y FROM cards` +
+			` WHERE ${baseWhere} AND ${xExpr} IS NOT NULL AND ${yExpr} IS NOT NULL`;
 	} else if (chartType === 'line') {
 		// Line: always COUNT by x, ordered by x ASC for trends
 		sql =
-			`SELECT ${xField} AS label, COUNT(*) AS value FROM cards` +
-			` WHERE ${baseWhere} AND ${xField} IS NOT NULL` +
-			` GROUP BY ${xField} ORDER BY ${xField} ASC`;
+			`SELECT ${xExpr} AS label, COUNT(*) AS value FROM cards` +
+			` WHERE ${baseWhere} AND ${xExpr} IS NOT NULL` +
+			` GROUP BY ${xExpr} ORDER BY ${xExpr} ASC`;
 	} else if (yField !== null) {
 		// Bar with numeric y: SUM aggregation, ordered by x ASC
 		sql =
-			`SELECT ${xField} AS label, SUM(${yField}) AS value FROM cards` +
-			` WHERE ${baseWhere} AND ${xField} IS NOT NULL` +
-			` GROUP BY ${xField} ORDER BY ${xField} ASC`;
+			`SELECT ${xExpr} AS label, SUM(${yExpr}) AS value FROM cards` +
+			` WHERE ${baseWhere} AND ${xExpr} IS NOT NULL` +
+			` GROUP BY ${xExpr} ORDER BY ${xExpr} ASC`;
 	} else {
 		// Bar (count) or Pie: COUNT aggregation, ordered by value DESC
 		sql =
-			`SELECT ${xField} AS label, COUNT(*) AS value FROM cards` +
-			` WHERE ${baseWhere} AND ${xField} IS NOT NULL` +
-			` GROUP BY ${xField} ORDER BY value DESC`;
+			`SELECT ${xExpr} AS label, COUNT(*) AS value FROM cards` +
+			` WHERE ${baseWhere} AND ${xExpr} IS NOT NULL` +
+			` GROUP BY ${xExpr} ORDER BY value DESC`;
 	}
 
 	// Step 5: Append LIMIT if specified
@@ -27,6 +27,17 @@ export function handleConnectionGet(
 	return connections.getConnections(db, payload.cardId, payload.direction);
 }
 
+/**
+ * Handle connection:update request.
+ * Edits label, weight and/or via_card_id in place (id and created_at preserved).
+ */
+export function handleConnectionUpdate(
+	db: Database,
+	payload: WorkerPayloads['connection:update'],
+): WorkerResponses['connection:update'] {
+	return connections.updateConnection(db, payload.id, payload.updates);
+}
+
 /**
  * Handle connection:delete request.
  * Hard-deletes a connection.
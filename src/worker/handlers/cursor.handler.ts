@@ -0,0 +1,133 @@
+// Isometry v5 — Cursor Streaming Handler
+// Worker-side cursor registry for paged card:list and db:query responses.
+//
+// Large result sets (100k+ cards) sent as a single postMessage array freeze the
+// main thread during structuredClone and double peak memory. Instead the Worker
+// keeps a forward-only RowCursor per stream and hands out one page per request.
+//
+// Lifecycle:
+//   cursor:open  → prepares the statement, returns the first page
+//   cursor:next  → returns the next page
+//   cursor:close → frees the statement early (cancellation)
+// Exhausted cursors are released automatically; the oldest idle cursor is
+// evicted when MAX_OPEN_CURSORS is exceeded so abandoned streams cannot leak.
+// db:export closes every cursor, so streams interrupted by a checkpoint end
+// with NOT_FOUND on their next page.
+
+import type { SqlValue } from 'sql.js';
+import type { Database, RowCursor } from '../../database/Database';
+import { buildListCardsQuery } from '../../database/queries/cards';
+import { rowToCard } from '../../database/queries/helpers';
+import type { CursorPage, WorkerPayloads, WorkerResponses } from '../protocol';
+import { CURSOR_PAGE_SIZE } from '../protocol';
+
+/** Upper bound on rows per page regardless of what the caller requests */
+export const MAX_CURSOR_PAGE_SIZE = 5000;
+
+/** Maximum concurrently open cursors before the least recently used is evicted */
+export const MAX_OPEN_CURSORS = 8;
+
+interface OpenCursor {
+	cursor: RowCursor<Record<string, unknown>>;
+	/** Row mapper (card:list maps raw rows to Card) */
+	map: (row: Record<string, unknown>) => Record<string, unknown>;
+	pageSize: number;
+}
+
+/**
+ * Open cursors keyed by cursorId.
+ * Map iteration order doubles as LRU order — entries are re-inserted on use.
+ */
+const openCursors = new Map<string, OpenCursor>();
+
+const identity = (row: Record<string, unknown>): Record<string, unknown> => row;
+
+const toCardRow = (row: Record<string, unknown>): Record<string, unknown> =>
+	rowToCard(row as Record<string, SqlValue>) as unknown as Record<string, unknown>;
+
+function clampPageSize(pageSize: number | undefined): number {
+	if (pageSize === undefined || !Number.isFinite(pageSize)) return CURSOR_PAGE_SIZE;
+	return Math.min(MAX_CURSOR_PAGE_SIZE, Math.max(1, Math.floor(pageSize)));
+}
+
+function readPage(cursorId: string, entry: OpenCursor, pageSize: number): CursorPage {
+	const rows = entry.cursor.next(pageSize).map(entry.map);
+	const done = entry.cursor.done;
+	if (done) {
+		openCursors.delete(cursorId);
+	} else {
+		// Refresh LRU position
+		openCursors.delete(cursorId);
+		openCursors.set(cursorId, entry);
+	}
+	return { cursorId, columns: entry.cursor.columns, rows, done };
+}
+
+/**
+ * Handle cursor:open request.
+ * Prepares the backing statement and returns the first page.
+ */
+export function handleCursorOpen(db: Database, payload: WorkerPayloads['cursor:open']): WorkerResponses['cursor:open'] {
+	const { source } = payload;
+	const pageSize = clampPageSize(payload.pageSize);
+
+	let entry: OpenCursor;
+	if (source.kind === 'card:list') {
+		const { sql, params } = buildListCardsQuery(source.options);
+		entry = { cursor: db.openCursor(sql, params), map: toCardRow, pageSize };
+	} else {
+		entry = { cursor: db.openCursor(source.sql, source.params), map: identity, pageSize };
+	}
+
+	// Evict least recently used cursors beyond the cap
+	while (openCursors.size >= MAX_OPEN_CURSORS) {
+		const oldestId = openCursors.keys().next().value as string;
+		openCursors.get(oldestId)?.cursor.close();
+		openCursors.delete(oldestId);
+	}
+
+	const cursorId = crypto.randomUUID();
+	openCursors.set(cursorId, entry);
+	return readPage(cursorId, entry, pageSize);
+}
+
+/**
+ * Handle cursor:next request.
+ * Throws a "not found" error (→ NOT_FOUND) for closed, exhausted or evicted cursors.
+ */
+export function handleCursorNext(payload: WorkerPayloads['cursor:next']): WorkerResponses['cursor:next'] {
+	const entry = openCursors.get(payload.cursorId);
+	if (!entry) {
+		throw new Error(`Cursor ${payload.cursorId} not found`);
+	}
+	const pageSize = payload.pageSize !== undefined ? clampPageSize(payload.pageSize) : entry.pageSize;
+	return readPage(payload.cursorId, entry, pageSize);
+}
+
+/**
+ * Handle cursor:close request.
+ * Idempotent — closing an unknown cursor returns { closed: false }.
+ */
+export function handleCursorClose(payload: WorkerPayloads['cursor:close']): WorkerResponses['cursor:close'] {
+	const entry = openCursors.get(payload.cursorId);
+	if (!entry) return { closed: false };
+	entry.cursor.close();
+	openCursors.delete(payload.cursorId);
+	return { closed: true };
+}
+
+/**
+ * Close every open cursor. Called before db:export (sql.js export() frees every
+ * prepared statement) and by tests.
+ */
+export function closeAllCursors(): void {
+	for (const entry of openCursors.values()) {
+		entry.cursor.close();
+	}
+	openCursors.clear();
+}
+
+/** Number of currently open cursors (for tests/diagnostics) */
+export function openCursorCount(): number {
+	return openCursors.size;
+}
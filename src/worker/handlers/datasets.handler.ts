@@ -1,10 +1,12 @@
 // Isometry v5 — Phase 88 + Phase 125
 // Handlers for datasets:query, datasets:stats, datasets:vacuum, datasets:delete,
-// datasets:reimport, and datasets:commit-reimport Worker message types.
+// datasets:reimport, datasets:commit-reimport and datasets:files Worker message types.
 
 import type { Database } from '../../database/Database';
+import { type DatasetFile, getDatasetFiles, replaceDatasetFiles } from '../../database/queries/dataset-files';
 import { CatalogWriter } from '../../etl/CatalogWriter';
 import { DedupEngine, type DedupResult } from '../../etl/DedupEngine';
+import { diffManifest, type ReimportFile, type ReimportFileChange, relativeFilePath } from '../../etl/FileManifest';
 import { SQLiteWriter } from '../../etl/SQLiteWriter';
 import type { CanonicalCard, ImportResult } from '../../etl/types';
 import type { WorkerResponses } from '../protocol';
@@ -136,16 +138,29 @@ let pendingReimport: {
 	dedupResult: DedupResult;
 	sourceType: string;
 	directoryPath: string | null;
+	/** File manifest stored on commit; null when the re-import carried no file information */
+	manifest: DatasetFile[] | null;
 } | null = null;
 
+/**
+ * Return the stored file manifest of a dataset (sent to Swift so it can skip
+ * unchanged files on re-scan).
+ */
+export function handleDatasetsFiles(db: Database, payload: { datasetId: string }): WorkerResponses['datasets:files'] {
+	return getDatasetFiles(db, payload.datasetId).map(({ path, size, mtime, hash }) => ({ path, size, mtime, hash }));
+}
+
 /**
  * Phase 1 of two-phase re-import: parse+dedup without writing to DB.
  * Caches the DedupResult for the commit phase.
  * Returns a serializable summary of changes for UI display.
+ *
+ * With `files` (incremental re-scan) only the cards of changed files are
+ * classified; see planFileReimport(). With `cards` the whole dataset is diffed.
  */
 export function handleDatasetsReimport(
 	db: Database,
-	payload: { datasetId: string; cards: CanonicalCard[] },
+	payload: { datasetId: string; cards?: CanonicalCard[]; files?: ReimportFile[] },
 ): WorkerResponses['datasets:reimport'] {
 	// Concurrency guard: if a reimport is already pending (e.g., rapid double-click),
 	// clear it with a warning before starting a new one
@@ -161,7 +176,7 @@ export function handleDatasetsReimport(
 		)
 		.all(payload.datasetId);
 	if (dataset.length === 0) {
-		return { toInsert: [], toUpdate: [], deletedIds: [], deletedNames: [], unchanged: 0 };
+		return { toInsert: [], toUpdate: [], deletedIds: [], deletedNames: [], renamed: [], unchanged: 0, files: [] };
 	}
 
 	const { source_type, directory_path } = dataset[0]!;
@@ -169,9 +184,21 @@ export function handleDatasetsReimport(
 	// Determine dedup source (same normalization as import)
 	const dedupSource = source_type.startsWith('alto_index_') ? 'alto_index' : source_type;
 
+	if (payload.files) {
+		const plan = planFileReimport(db, payload.datasetId, payload.files, dedupSource, directory_path);
+		pendingReimport = {
+			datasetId: payload.datasetId,
+			dedupResult: plan.dedupResult,
+			sourceType: source_type,
+			directoryPath: directory_path,
+			manifest: plan.manifest,
+		};
+		return plan.summary;
+	}
+
 	// Run dedup without writing
 	const dedup = new DedupEngine(db);
-	const dedupResult = dedup.process(payload.cards, [], dedupSource);
+	const dedupResult = dedup.process(payload.cards ?? [], [], dedupSource);
 
 	// Cache for commit phase
 	pendingReimport = {
@@ -179,6 +206,7 @@ export function handleDatasetsReimport(
 		dedupResult,
 		sourceType: source_type,
 		directoryPath: directory_path,
+		manifest: null,
 	};
 
 	// Look up names for deleted cards
@@ -193,7 +221,180 @@ export function handleDatasetsReimport(
 		toUpdate: dedupResult.toUpdate.map((c) => ({ id: c.id, name: c.name })),
 		deletedIds: dedupResult.deletedIds,
 		deletedNames,
+		renamed: [],
 		unchanged: dedupResult.toSkip.length,
+		files: [],
+	};
+}
+
+/**
+ * Incremental re-import plan from a directory re-scan.
+ *
+ * - Files without cards are unchanged since the stored manifest; their cards are left alone
+ * - Cards of renamed files take over the existing cards of the old path in order
+ *   (same id, new source_id) instead of being deleted and re-inserted
+ * - Cards of changed files are updated even when their modified_at did not move
+ * - Deletions come only from removed files and cards a changed file no longer
+ *   produces. Without a stored manifest (first incremental re-import) every file
+ *   is parsed and dataset cards absent from the scan are deleted.
+ */
+function planFileReimport(
+	db: Database,
+	datasetId: string,
+	files: ReimportFile[],
+	dedupSource: string,
+	directoryPath: string | null,
+): { dedupResult: DedupResult; manifest: DatasetFile[]; summary: WorkerResponses['datasets:reimport'] } {
+	const previous = getDatasetFiles(db, datasetId);
+	const previousByPath = new Map(previous.map((file) => [file.path, file]));
+	const filesByPath = new Map(files.map((file) => [file.path, file]));
+	const diff = diffManifest(previous, files);
+	const renamedFrom = new Map(diff.renamed.map(({ from, to }) => [to, from]));
+	const addedPaths = new Set(diff.added);
+	const modifiedPaths = new Set(diff.modified);
+	const removedPaths = new Set(diff.removed);
+
+	const existing = new Map(
+		db
+			.prepare<{ id: string; source_id: string; name: string }>(
+				'SELECT id, source_id, name FROM cards WHERE source = ? AND source_id IS NOT NULL AND deleted_at IS NULL',
+			)
+			.all(dedupSource)
+			.map((row) => [row.source_id, row]),
+	);
+
+	// Card changes per file, keyed by absolute path ('' = no longer in any file)
+	const changes = new Map<string, ReimportFileChange['cards']>();
+	const record = (path: string, card: ReimportFileChange['cards'][number]) => {
+		const list = changes.get(path) ?? [];
+		list.push(card);
+		changes.set(path, list);
+	};
+
+	// Renames: re-key the old path's cards onto the new path's cards, in order
+	const renamed: CanonicalCard[] = [];
+	const deletedSourceIds = new Map<string, string>(); // source_id -> owning path
+	const fileOf = new Map<string, string>(); // incoming source_id -> path
+	const parsed: CanonicalCard[] = [];
+	for (const file of files) {
+		if (!file.cards) continue;
+		const from = renamedFrom.get(file.path);
+		const oldIds = from !== undefined ? previousByPath.get(from)!.source_ids : [];
+		file.cards.forEach((card, i) => {
+			fileOf.set(card.source_id, file.path);
+			const row = i < oldIds.length ? existing.get(oldIds[i]!) : undefined;
+			if (row) {
+				renamed.push({ ...card, id: row.id });
+				record(file.path, { id: row.id, name: card.name, change: 'renamed' });
+			} else {
+				parsed.push(card);
+			}
+		});
+		for (const oldId of oldIds.slice(file.cards.length)) deletedSourceIds.set(oldId, file.path);
+	}
+
+	const dedupResult = new DedupEngine(db).process(parsed, [], dedupSource);
+
+	// Content changed on disk: rewrite the card even if its modified_at did not move
+	const toSkip: CanonicalCard[] = [];
+	const forced: CanonicalCard[] = [];
+	for (const card of dedupResult.toSkip) {
+		const path = fileOf.get(card.source_id);
+		if (path !== undefined && modifiedPaths.has(path)) {
+			forced.push({ ...card, id: dedupResult.sourceIdMap.get(card.source_id)! });
+		} else {
+			toSkip.push(card);
+		}
+	}
+	const toUpdate = [...dedupResult.toUpdate, ...forced];
+	for (const card of dedupResult.toInsert) {
+		record(fileOf.get(card.source_id)!, { id: card.id, name: card.name, change: 'new' });
+	}
+	for (const card of toUpdate) {
+		record(fileOf.get(card.source_id)!, { id: card.id, name: card.name, change: 'modified' });
+	}
+
+	// Deletions: removed files, and cards a changed file no longer produces
+	for (const path of removedPaths) {
+		for (const id of previousByPath.get(path)!.source_ids) deletedSourceIds.set(id, path);
+	}
+	for (const path of modifiedPaths) {
+		const cards = filesByPath.get(path)!.cards;
+		if (!cards) continue;
+		const current = new Set(cards.map((card) => card.source_id));
+		for (const id of previousByPath.get(path)!.source_ids) {
+			if (!current.has(id)) deletedSourceIds.set(id, path);
+		}
+	}
+	if (previous.length === 0) {
+		const datasetCards = db
+			.prepare<{ source_id: string }>(
+				'SELECT source_id FROM cards WHERE dataset_id = ? AND source_id IS NOT NULL AND deleted_at IS NULL',
+			)
+			.all(datasetId);
+		for (const row of datasetCards) {
+			if (!fileOf.has(row.source_id)) deletedSourceIds.set(row.source_id, '');
+		}
+	}
+
+	const deleted: Array<{ id: string; name: string }> = [];
+	for (const [sourceId, path] of deletedSourceIds) {
+		const row = existing.get(sourceId);
+		// A card that moved into another scanned file is not deleted
+		if (!row || fileOf.has(sourceId)) continue;
+		deleted.push({ id: row.id, name: row.name });
+		record(path, { id: row.id, name: row.name, change: 'deleted' });
+	}
+
+	// Files without cards keep the source_ids of their stored entry
+	const manifest: DatasetFile[] = files.map(({ path, size, mtime, hash, cards }) => ({
+		path,
+		size,
+		mtime,
+		hash,
+		source_ids: cards
+			? cards.map((card) => card.source_id)
+			: (previousByPath.get(renamedFrom.get(path) ?? path)?.source_ids ?? []),
+	}));
+
+	const fileChanges: ReimportFileChange[] = [];
+	for (const [path, cards] of changes) {
+		const from = renamedFrom.get(path);
+		let change: ReimportFileChange['change'];
+		if (from !== undefined) change = 'renamed';
+		else if (path === '' || removedPaths.has(path)) change = 'removed';
+		else if (addedPaths.has(path) && cards.every((card) => card.change === 'new')) change = 'added';
+		else change = 'modified';
+		fileChanges.push({
+			path: relativeFilePath(path, directoryPath),
+			previousPath: from !== undefined ? relativeFilePath(from, directoryPath) : null,
+			change,
+			cards,
+		});
+	}
+	fileChanges.sort((a, b) => (a.path === '' ? 1 : b.path === '' ? -1 : a.path.localeCompare(b.path)));
+
+	const unchangedCards = files
+		.filter((file) => !file.cards)
+		.reduce((sum, file) => sum + (previousByPath.get(file.path)?.source_ids.length ?? 0), 0);
+
+	return {
+		dedupResult: {
+			...dedupResult,
+			toUpdate: [...toUpdate, ...renamed],
+			toSkip,
+			deletedIds: deleted.map((card) => card.id),
+		},
+		manifest,
+		summary: {
+			toInsert: dedupResult.toInsert.map((c) => ({ id: c.id, name: c.name })),
+			toUpdate: toUpdate.map((c) => ({ id: c.id, name: c.name })),
+			deletedIds: deleted.map((card) => card.id),
+			deletedNames: deleted.map((card) => card.name),
+			renamed: renamed.map((c) => ({ id: c.id, name: c.name })),
+			unchanged: toSkip.length + unchangedCards,
+			files: fileChanges,
+		},
 	};
 }
 
@@ -220,7 +421,7 @@ export async function handleDatasetsCommitReimport(
 		};
 	}
 
-	const { dedupResult, sourceType, directoryPath } = pendingReimport;
+	const { dedupResult, sourceType, directoryPath, manifest } = pendingReimport;
 	const startTime = new Date().toISOString();
 
 	// Write phase — same as etl-import-native handler
@@ -251,6 +452,11 @@ export async function handleDatasetsCommitReimport(
 		db.prepare<never>('UPDATE cards SET dataset_id = ? WHERE id = ?').run(payload.datasetId, card.id);
 	}
 
+	// Remember what was scanned so the next re-import only parses changed files
+	if (manifest !== null) {
+		replaceDatasetFiles(db, payload.datasetId, manifest);
+	}
+
 	// FTS optimize for incremental imports (>100 inserts, non-bulk path)
 	if (!isBulkImport && dedupResult.toInsert.length > 100) {
 		writer.optimizeFTS();
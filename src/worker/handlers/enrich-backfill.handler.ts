@@ -1,15 +1,62 @@
 // Isometry v5 — Enrich Backfill Handler
-// Retroactively populates enriched columns (folder_l1..folder_l4) on existing cards.
+// Retroactively populates enriched columns (folder_l1..folder_l4) on existing cards,
+// re-runs opt-in enrichers on request, and lists/saves enricher settings.
 // Runs in batches to avoid blocking the Worker thread.
 
 import type { Database } from '../../database/Database';
+import { listEnricherSettings, setEnricherSetting } from '../../database/queries/enricher-settings';
+import { listEnrichers } from '../../etl/enrichment';
 import { splitFolderPath } from '../../etl/enrichment/FolderHierarchyEnricher';
+import { ImportOrchestrator } from '../../etl/ImportOrchestrator';
+import type { WorkerPayloads, WorkerResponses } from '../protocol';
 
 const BATCH_SIZE = 1000;
 
 export interface BackfillResult {
 	updated: number;
 	skipped: number;
+	/** Set when enrichers were re-run: cards they created */
+	insertedIds?: string[];
+	/** Set when enrichers were re-run: existing cards they changed */
+	updatedIds?: string[];
+	/** Set when enrichers were re-run: card IDs changed, by enricher id */
+	enriched?: Record<string, string[]>;
+}
+
+/**
+ * Handle enrich:backfill requests.
+ * Always backfills folder levels. With a payload (the Re-run action, as
+ * opposed to the boot-time call) it also re-runs the enabled enrichers — or
+ * just `enricherIds` — over every imported card.
+ */
+export async function handleEnrichBackfill(
+	db: Database,
+	payload?: WorkerPayloads['enrich:backfill'],
+): Promise<WorkerResponses['enrich:backfill']> {
+	const result: BackfillResult = await backfillFolderLevels(db);
+	if (!payload) return result;
+
+	const rerun = await new ImportOrchestrator(db).reenrich(payload.enricherIds);
+	result.insertedIds = rerun.insertedIds;
+	result.updatedIds = rerun.updatedIds;
+	result.enriched = rerun.enriched;
+	return result;
+}
+
+/**
+ * Handle enrich:list requests.
+ * Returns the registered enrichers in run order with the saved settings.
+ */
+export function handleEnrichList(db: Database): WorkerResponses['enrich:list'] {
+	return { enrichers: listEnrichers(), settings: listEnricherSettings(db) };
+}
+
+/**
+ * Handle enrich:set requests.
+ * Switches an enricher on or off for a source type ('*' for all sources).
+ */
+export function handleEnrichSet(db: Database, payload: WorkerPayloads['enrich:set']): WorkerResponses['enrich:set'] {
+	setEnricherSetting(db, payload);
 }
 
 /**
@@ -19,7 +66,7 @@ export interface BackfillResult {
  * Uses pure SQL UPDATE with splitFolderPath logic applied in JS.
  * Processes in batches to yield to event loop.
  */
-export async function handleEnrichBackfill(db: Database): Promise<BackfillResult> {
+async function backfillFolderLevels(db: Database): Promise<BackfillResult> {
 	// Find all cards with folder but no folder_l1 (not yet enriched)
 	const rows = db
 		.prepare<{ id: string; folder: string }>(
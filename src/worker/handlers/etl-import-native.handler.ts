@@ -12,10 +12,11 @@
 // and backward (linked_from, 0.3) connections between the actual note cards.
 
 import type { Database } from '../../database/Database';
+import { listEnricherSettings } from '../../database/queries/enricher-settings';
 import { CatalogWriter } from '../../etl/CatalogWriter';
 import { DedupEngine } from '../../etl/DedupEngine';
 import { SQLiteWriter } from '../../etl/SQLiteWriter';
-import { runEnrichmentPipeline } from '../../etl/enrichment';
+import { resolveEnrichedIds, runEnrichment } from '../../etl/enrichment';
 import type { CanonicalConnection, ImportResult } from '../../etl/types';
 import type { WorkerNotification, WorkerPayloads, WorkerResponses } from '../protocol';
 
@@ -67,7 +68,7 @@ export async function handleETLImportNative(
 	}
 
 	// Step 1: Enrich (derive fields, normalize, split hierarchies)
-	runEnrichmentPipeline(payload.cards, payload.sourceType);
+	const enrichment = runEnrichment(payload.cards, payload.sourceType, listEnricherSettings(db));
 
 	// Step 2: Deduplicate against existing cards
 	// Per-directory alto imports (alto_index_notes, etc.) must dedup against the shared
@@ -75,7 +76,7 @@ export async function handleETLImportNative(
 	// The full sourceType is preserved for catalog entries (IMPT-02).
 	const dedupSource = payload.sourceType.startsWith('alto_index_') ? 'alto_index' : payload.sourceType;
 	const dedup = new DedupEngine(db);
-	const dedupResult = dedup.process(payload.cards, [], dedupSource);
+	const dedupResult = dedup.process(payload.cards, enrichment.connections, dedupSource);
 
 	// Step 2: Determine bulk import optimization
 	const totalCards = dedupResult.toInsert.length + dedupResult.toUpdate.length;
@@ -100,6 +101,7 @@ export async function handleETLImportNative(
 
 	await writer.writeCards(dedupResult.toInsert, isBulkImport, progressCallback);
 	await writer.updateCards(dedupResult.toUpdate);
+	await writer.writeProperties([...dedupResult.toInsert, ...dedupResult.toUpdate]);
 	await writer.writeConnections(dedupResult.connections);
 
 	// Phase 34 (CALR-02): Auto-create attendee connections for calendar imports.
@@ -189,6 +191,11 @@ export async function handleETLImportNative(
 		deletedIds: dedupResult.deletedIds,
 		errors_detail: [],
 	};
+	const enriched = resolveEnrichedIds(enrichment.changed, dedupResult.sourceIdMap, [
+		...result.insertedIds,
+		...result.updatedIds,
+	]);
+	if (Object.keys(enriched).length > 0) result.enriched = enriched;
 
 	// Step 5: Record in catalog
 	const catalog = new CatalogWriter(db);
@@ -2,7 +2,9 @@
 // Thin delegation to ImportOrchestrator with progress notification wiring.
 
 import type { Database } from '../../database/Database';
+import { getMappingProfile } from '../../database/queries/mapping-profiles';
 import { ImportOrchestrator } from '../../etl/ImportOrchestrator';
+import type { MappingProfile } from '../../etl/MappingProfile';
 import type { WorkerNotification, WorkerPayloads, WorkerResponses } from '../protocol';
 
 /**
@@ -32,13 +34,32 @@ export async function handleETLImport(
 	};
 
 	// Build options object, only including defined properties
-	const options: { isBulkImport?: boolean; filename?: string } = {};
+	const options: { isBulkImport?: boolean; filename?: string; profile?: MappingProfile; sheets?: string[] } = {};
 	if (payload.options?.isBulkImport !== undefined) {
 		options.isBulkImport = payload.options.isBulkImport;
 	}
 	if (payload.options?.filename !== undefined) {
 		options.filename = payload.options.filename;
 	}
+	if (payload.options?.profileId !== undefined) {
+		const profile = getMappingProfile(db, payload.options.profileId);
+		if (!profile) throw new Error(`Mapping profile not found: ${payload.options.profileId}`);
+		options.profile = profile;
+	}
+	if (payload.options?.sheets !== undefined) {
+		options.sheets = payload.options.sheets;
+	}
 
 	return orchestrator.import(payload.source, payload.data, options);
 }
+
+/**
+ * Handle etl:excel-sheets requests.
+ * Lists the workbook's sheets with row counts for the sheet picker.
+ */
+export async function handleETLExcelSheets(
+	db: Database,
+	payload: WorkerPayloads['etl:excel-sheets'],
+): Promise<WorkerResponses['etl:excel-sheets']> {
+	return new ImportOrchestrator(db).listExcelSheets(payload.data);
+}
@@ -3,12 +3,15 @@
 
 import type { Database } from '../../database/Database';
 import type { WorkerResponses } from '../protocol';
+import { closeAllCursors } from './cursor.handler';
 
 /**
  * Handle db:export request.
  * Returns the SQLite database as a Uint8Array.
  * Used by native shell for file system persistence.
+ * Open cursors are closed first: sql.js frees all prepared statements on export().
  */
 export function handleDbExport(db: Database): WorkerResponses['db:export'] {
+	closeAllCursors();
 	return db.export();
 }
@@ -0,0 +1,118 @@
+// Isometry v5 -- Formula Handler Tests
+// Unit tests for formula:define/list/preview compile checks and evaluation.
+//
+// Pattern: In-memory sql.js database (same as map.handler.test.ts).
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { Database } from '../../database/Database';
+import { defineFormula } from '../../database/queries/formulas';
+import { createPropertyDefinition, setCardProperty } from '../../database/queries/properties';
+import { ALLOWED_FILTER_FIELDS, setValidColumnNames } from '../../providers/allowlist';
+import {
+	compileStoredFormulas,
+	handleFormulaDefine,
+	handleFormulaDelete,
+	handleFormulaList,
+	handleFormulaPreview,
+} from './formulas.handler';
+
+// ---------------------------------------------------------------------------
+// Test Helpers
+// ---------------------------------------------------------------------------
+
+let db: Database;
+
+beforeEach(async () => {
+	db = new Database();
+	await db.initialize();
+});
+
+afterEach(() => {
+	setValidColumnNames(null);
+	db.close();
+});
+
+function insertCard(id: string, createdAt: string, priority: number, modifiedAt = createdAt) {
+	db.prepare(
+		`INSERT INTO cards (id, name, card_type, priority, created_at, modified_at, status, source)
+		 VALUES (?, ?, 'note', ?, ?, ?, 'active', 'manual')`,
+	).run(id, id, priority, createdAt, modifiedAt);
+}
+
+// ---------------------------------------------------------------------------
+// handleFormulaDefine / handleFormulaList
+// ---------------------------------------------------------------------------
+
+describe('handleFormulaDefine', () => {
+	it('saves a formula that compiles and returns its SQL', () => {
+		const info = handleFormulaDefine(db, {
+			input: { name: 'double_priority', expression: 'priority * 2', result_type: 'number' },
+		});
+
+		expect(info).toMatchObject({ field: 'fx_double_priority', sql: '(cards.priority * 2)', error: null });
+		expect(handleFormulaList(db)).toEqual([info]);
+	});
+
+	it('never saves an expression that does not compile', () => {
+		expect(() =>
+			handleFormulaDefine(db, { input: { name: 'bad', expression: 'nope + 1', result_type: 'number' } }),
+		).toThrow('Unknown field "nope"');
+		expect(handleFormulaList(db)).toEqual([]);
+	});
+
+	it('deletes by id', () => {
+		const info = handleFormulaDefine(db, { input: { name: 'one', expression: '1', result_type: 'number' } });
+		handleFormulaDelete(db, { id: info.id });
+		expect(handleFormulaList(db)).toEqual([]);
+	});
+});
+
+describe('compileStoredFormulas', () => {
+	it('reports formulas that no longer compile with a null sql', () => {
+		setValidColumnNames(new Set([...ALLOWED_FILTER_FIELDS, 'prop_budget']));
+		defineFormula(db, { name: 'half_budget', expression: 'prop_budget / 2', result_type: 'number' });
+		setValidColumnNames(new Set(ALLOWED_FILTER_FIELDS));
+
+		const [info] = compileStoredFormulas(db);
+
+		expect(info).toMatchObject({ name: 'half_budget', sql: null, error: 'Unknown field "prop_budget"' });
+	});
+});
+
+// ---------------------------------------------------------------------------
+// handleFormulaPreview
+// ---------------------------------------------------------------------------
+
+describe('handleFormulaPreview', () => {
+	it('evaluates the expression on the most recently modified cards', () => {
+		insertCard('old', '2026-01-01T00:00:00Z', 1);
+		insertCard('new', '2026-01-11T00:00:00Z', 3);
+
+		const result = handleFormulaPreview(db, {
+			expression: "julianday(created_at) - julianday('2026-01-01T00:00:00Z') + priority",
+		});
+
+		expect(result.error).toBeNull();
+		expect(result.rows).toEqual([
+			{ id: 'new', name: 'new', value: 13 },
+			{ id: 'old', name: 'old', value: 1 },
+		]);
+	});
+
+	it('returns the error and its position instead of throwing', () => {
+		const result = handleFormulaPreview(db, { expression: 'priority + ;' });
+
+		expect(result).toEqual({ sql: null, error: 'Unexpected character ";"', position: 11, rows: [] });
+	});
+
+	it('reads custom properties through their card_properties subquery', () => {
+		insertCard('a', '2026-01-01T00:00:00Z', 1);
+		createPropertyDefinition(db, { key: 'budget', label: 'Budget', type: 'number' });
+		setCardProperty(db, 'a', 'budget', 250);
+		setValidColumnNames(new Set([...ALLOWED_FILTER_FIELDS, 'prop_budget']));
+
+		const result = handleFormulaPreview(db, { expression: 'prop_budget / 2' });
+
+		expect(result.rows).toEqual([{ id: 'a', name: 'a', value: 125 }]);
+	});
+});
@@ -0,0 +1,121 @@
+// Isometry v5 — Formula Fields Handlers
+// Compiles stored formulas against the current field allowlist and wraps the
+// formulas query functions.
+//
+// formula:define and formula:delete change the set of valid fx_<name>
+// fields; the worker router refreshes its column allowlist after either
+// (refreshValidColumnNames → compileStoredFormulas → setCompiledFormulas).
+
+import type { SqlValue } from 'sql.js';
+import type { Database } from '../../database/Database';
+import * as formulas from '../../database/queries/formulas';
+import { isValidFilterField } from '../../providers/allowlist';
+import { compileFormula, FormulaError, type FormulaInfo, formulaField } from '../../providers/formulas';
+import { fieldExpr, isPropertyField } from '../../providers/properties';
+import type { WorkerPayloads, WorkerResponses } from '../protocol';
+
+/** Cards sampled by formula:preview. */
+const PREVIEW_ROWS = 5;
+
+/**
+ * Field resolver for formula expressions: allowlisted card columns become
+ * `cards.<column>`, custom properties their card_properties subquery.
+ */
+function resolveFormulaField(name: string): string | null {
+	if (!isValidFilterField(name)) return null;
+	return isPropertyField(name) ? fieldExpr(name) : `cards.${name}`;
+}
+
+/**
+ * Compile an expression and check that SQLite accepts it (catches fields
+ * that are allowlisted but not card columns, and bad function arguments).
+ *
+ * @throws {FormulaError} for language errors; SQLite errors are rethrown as FormulaError at position 0
+ */
+function compileChecked(db: Database, expression: string): string {
+	const sql = compileFormula(expression, resolveFormulaField);
+	try {
+		db.exec(`SELECT ${sql} FROM cards LIMIT 0`);
+	} catch (err) {
+		throw new FormulaError(err instanceof Error ? err.message : String(err), 0);
+	}
+	return sql;
+}
+
+/**
+ * Compile every stored formula. Formulas that no longer compile (e.g. a
+ * referenced property was deleted) carry an error and a null sql.
+ */
+export function compileStoredFormulas(db: Database): FormulaInfo[] {
+	return formulas.listFormulas(db).map((definition) => {
+		try {
+			const sql = compileChecked(db, definition.expression);
+			return { ...definition, field: formulaField(definition.name), sql, error: null };
+		} catch (err) {
+			const error = err instanceof Error ? err.message : String(err);
+			return { ...definition, field: formulaField(definition.name), sql: null, error };
+		}
+	});
+}
+
+/**
+ * Handle formula:list request.
+ * Returns all formulas with their current compile result.
+ */
+export function handleFormulaList(db: Database): WorkerResponses['formula:list'] {
+	return compileStoredFormulas(db);
+}
+
+/**
+ * Handle formula:define request.
+ * Compiles first — an expression that does not compile is never saved.
+ */
+export function handleFormulaDefine(
+	db: Database,
+	payload: WorkerPayloads['formula:define'],
+): WorkerResponses['formula:define'] {
+	const sql = compileChecked(db, payload.input.expression);
+	const definition = formulas.defineFormula(db, payload.input);
+	return { ...definition, field: formulaField(definition.name), sql, error: null };
+}
+
+/**
+ * Handle formula:delete request.
+ */
+export function handleFormulaDelete(
+	db: Database,
+	payload: WorkerPayloads['formula:delete'],
+): WorkerResponses['formula:delete'] {
+	formulas.deleteFormula(db, payload.id);
+}
+
+/**
+ * Handle formula:preview request.
+ * Compiles the expression and evaluates it on the most recently modified
+ * live cards (of the dataset, when given). Never throws for formula errors.
+ */
+export function handleFormulaPreview(
+	db: Database,
+	payload: WorkerPayloads['formula:preview'],
+): WorkerResponses['formula:preview'] {
+	let sql: string;
+	try {
+		sql = compileChecked(db, payload.expression);
+	} catch (err) {
+		const position = err instanceof FormulaError ? err.position : 0;
+		return { sql: null, error: err instanceof Error ? err.message : String(err), position, rows: [] };
+	}
+
+	const params: SqlValue[] = [];
+	let where = 'deleted_at IS NULL';
+	if (payload.datasetId) {
+		where += ' AND dataset_id = ?';
+		params.push(payload.datasetId);
+	}
+	const stmt = db.prepare<{ id: string; name: string; value: string | number | null }>(
+		`SELECT id, name, ${sql} AS value FROM cards WHERE ${where} ORDER BY modified_at DESC LIMIT ${PREVIEW_ROWS}`,
+	);
+	const rows = stmt.all(...params);
+	stmt.free();
+	return { sql, error: null, position: null, rows };
+}
@@ -9,6 +9,7 @@
 
 import type { Database } from '../../database/Database';
 import { validateFilterField } from '../../providers/allowlist';
+import { fieldExpr } from '../../providers/properties';
 import type { WorkerPayloads, WorkerResponses } from '../protocol';
 
 // ---------------------------------------------------------------------------
@@ -45,11 +46,14 @@ export function handleHistogramQuery(
 	// Step 3: Copy params to avoid mutation
 	const params: unknown[] = [...payload.params];
 
+	// Custom property fields (prop_<key>) resolve to a card_properties subquery
+	const expr = fieldExpr(field);
+
 	if (fieldType === 'date') {
-		return handleDateHistogram(db, field, baseWhere, params);
+		return handleDateHistogram(db, expr, baseWhere, params);
 	}
 
-	return handleNumericHistogram(db, field, baseWhere, params, bins);
+	return handleNumericHistogram(db, expr, baseWhere, params, bins);
 }
 
 // ---------------------------------------------------------------------------
@@ -3,17 +3,29 @@
 
 export * from './cards.handler';
 // Enrichment backfill handler
-export { handleEnrichBackfill } from './enrich-backfill.handler';
+export { handleEnrichBackfill, handleEnrichList, handleEnrichSet } from './enrich-backfill.handler';
 export * from './connections.handler';
+// Cursor streaming handlers
+export { closeAllCursors, handleCursorClose, handleCursorNext, handleCursorOpen } from './cursor.handler';
 export { handleETLExport } from './etl-export.handler';
 // ETL handlers (Phase 8/9)
-export { handleETLImport } from './etl-import.handler';
+export { handleETLExcelSheets, handleETLImport } from './etl-import.handler';
 // Native ETL handler (Phase 33)
 export { handleETLImportNative } from './etl-import-native.handler';
 export * from './export.handler';
+// Formula fields handlers
+export * from './formulas.handler';
 export * from './graph.handler';
 // Graph algorithm handler (Phase 114)
 export { handleGraphCompute, handleGraphMetricsClear, handleGraphMetricsRead } from './graph-algorithms.handler';
+// Import mapping profiles handlers
+export * from './mapping-profiles.handler';
+// Custom card properties handlers
+export * from './properties.handler';
+// Saved searches handlers
+export * from './saved-searches.handler';
 export * from './search.handler';
 export * from './simulate.handler';
+// Stories handlers
+export * from './stories.handler';
 export * from './ui-state.handler';
@@ -0,0 +1,111 @@
+// Isometry v5 -- Location Handler Tests
+// Unit tests for handleLocationQuery point aggregation and map bounds.
+//
+// Pattern: In-memory sql.js database (same as histogram.handler.test.ts).
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { Database } from '../../database/Database';
+import { handleLocationQuery } from './location.handler';
+
+// ---------------------------------------------------------------------------
+// Test Helpers
+// ---------------------------------------------------------------------------
+
+let db: Database;
+
+beforeEach(async () => {
+	db = new Database();
+	await db.initialize();
+});
+
+afterEach(() => {
+	db.close();
+});
+
+let seq = 0;
+
+function insertCard(latitude: number | null, longitude: number | null, locationName: string | null, folder = 'Inbox') {
+	const id = `card-${seq++}`;
+	db.prepare(
+		`INSERT INTO cards (id, name, card_type, latitude, longitude, location_name, folder, status, source)
+		 VALUES (?, ?, 'note', ?, ?, ?, ?, 'active', 'manual')`,
+	).run(id, id, latitude, longitude, locationName, folder);
+}
+
+const ALL = { where: 'deleted_at IS NULL', params: [] };
+
+// ---------------------------------------------------------------------------
+// handleLocationQuery
+// ---------------------------------------------------------------------------
+
+describe('handleLocationQuery', () => {
+	it('returns no points and null bounds when no card has coordinates', () => {
+		insertCard(null, null, 'Nowhere');
+		insertCard(51.5, null, null);
+
+		expect(handleLocationQuery(db, ALL)).toEqual({ points: [], bounds: null, total: 0 });
+	});
+
+	it('aggregates nearby cards into one point and keeps a shared place name', () => {
+		insertCard(51.5072, -0.1276, 'London');
+		insertCard(51.5074, -0.1278, 'London');
+		insertCard(48.8566, 2.3522, 'Paris');
+
+		const result = handleLocationQuery(db, ALL);
+
+		expect(result.total).toBe(3);
+		expect(result.points).toHaveLength(2);
+		// Densest first
+		expect(result.points[0]).toMatchObject({ count: 2, locationName: 'London' });
+		expect(result.points[0]!.latitude).toBeCloseTo(51.5073, 4);
+		expect(result.points[1]).toMatchObject({ count: 1, locationName: 'Paris' });
+	});
+
+	it('drops the place name when cards in a cell disagree', () => {
+		insertCard(40.7128, -74.006, 'New York');
+		insertCard(40.7129, -74.0061, 'Manhattan');
+
+		const [point] = handleLocationQuery(db, ALL).points;
+		expect(point).toMatchObject({ count: 2, locationName: null });
+	});
+
+	it('filters points with the compiled WHERE but keeps bounds unfiltered', () => {
+		insertCard(51.5, -0.12, 'London', 'Work');
+		insertCard(-33.87, 151.21, 'Sydney', 'Travel');
+
+		const result = handleLocationQuery(db, { where: 'deleted_at IS NULL AND folder = ?', params: ['Work'] });
+
+		expect(result.points).toHaveLength(1);
+		expect(result.points[0]!.locationName).toBe('London');
+		expect(result.total).toBe(1);
+		expect(result.bounds).toEqual({ south: -33.87, north: 51.5, west: -0.12, east: 151.21 });
+	});
+
+	it('ignores out-of-range coordinates', () => {
+		insertCard(95, 10, 'Bad latitude');
+		insertCard(10, 200, 'Bad longitude');
+		insertCard(10, 20, 'Good');
+
+		const result = handleLocationQuery(db, ALL);
+		expect(result.points.map((p) => p.locationName)).toEqual(['Good']);
+		expect(result.bounds).toEqual({ south: 10, north: 10, west: 20, east: 20 });
+	});
+
+	it('limit caps the point count while total still counts every match', () => {
+		insertCard(10, 10, 'A');
+		insertCard(20, 20, 'B');
+		insertCard(30, 30, 'C');
+
+		const result = handleLocationQuery(db, { ...ALL, limit: 2 });
+		expect(result.points).toHaveLength(2);
+		expect(result.total).toBe(3);
+	});
+
+	it('coarser precision merges more cards per point', () => {
+		insertCard(51.51, -0.12, 'London');
+		insertCard(51.54, -0.14, 'Camden');
+
+		expect(handleLocationQuery(db, { ...ALL, precision: 2 }).points).toHaveLength(2);
+		expect(handleLocationQuery(db, { ...ALL, precision: 0 }).points).toHaveLength(1);
+	});
+});
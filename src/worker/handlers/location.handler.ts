@@ -0,0 +1,122 @@
+// Isometry v5 -- Location Query Handler
+// Point aggregation for the LATCH Location explorer map.
+//
+// Follows the histogram.handler.ts pattern:
+//   - where/params come from FilterProvider.compile()
+//   - db.prepare() + all() + free() for parameterized query execution
+//   - Points are snapped to a ROUND() grid and aggregated so dense datasets
+//     stay small enough to render as SVG circles
+//   - bounds cover ALL located cards (unfiltered) so the map frame stays put
+//     while filters change which points are drawn
+
+import type { Database } from '../../database/Database';
+import type { WorkerPayloads, WorkerResponses } from '../protocol';
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+interface NullableBounds {
+	south: number | null;
+	north: number | null;
+	west: number | null;
+	east: number | null;
+}
+
+interface PointRow {
+	latitude: number;
+	longitude: number;
+	count: number;
+	location_name: string | null;
+	total: number;
+}
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/** Decimal places of the aggregation grid (2 = ~1.1 km at the equator). */
+const DEFAULT_PRECISION = 2;
+const MAX_PRECISION = 6;
+
+/** Maximum number of aggregated points returned (densest first). */
+const DEFAULT_LIMIT = 2000;
+
+/** Only cards with valid coordinates are plotted. */
+const LOCATED =
+	'latitude IS NOT NULL AND longitude IS NOT NULL' +
+	' AND latitude BETWEEN -90 AND 90 AND longitude BETWEEN -180 AND 180';
+
+// ---------------------------------------------------------------------------
+// Handler
+// ---------------------------------------------------------------------------
+
+/**
+ * Handle location:query request.
+ * Aggregates located cards matching the current filter into map points.
+ *
+ * Each point is the mean position of the cards in one grid cell, with the
+ * cell's card count and — when every card in the cell shares one — its
+ * location_name.
+ */
+export function handleLocationQuery(
+	db: Database,
+	payload: WorkerPayloads['location:query'],
+): WorkerResponses['location:query'] {
+	const baseWhere = payload.where || 'deleted_at IS NULL';
+	const params: unknown[] = [...payload.params];
+	const precision = clampInt(payload.precision ?? DEFAULT_PRECISION, 0, MAX_PRECISION);
+	const limit = clampInt(payload.limit ?? DEFAULT_LIMIT, 1, DEFAULT_LIMIT);
+
+	// Step 1: Frame — extent of every located card, ignoring the filter
+	const boundsSql =
+		'SELECT MIN(latitude) AS south, MAX(latitude) AS north, MIN(longitude) AS west, MAX(longitude) AS east' +
+		` FROM cards WHERE deleted_at IS NULL AND ${LOCATED}`;
+
+	const boundsStmt = db.prepare<NullableBounds>(boundsSql);
+	const boundsRow = boundsStmt.all()[0];
+	boundsStmt.free();
+
+	if (!boundsRow || boundsRow.south === null || boundsRow.north === null) {
+		return { points: [], bounds: null, total: 0 };
+	}
+	const bounds = {
+		south: boundsRow.south,
+		north: boundsRow.north,
+		west: boundsRow.west as number,
+		east: boundsRow.east as number,
+	};
+
+	// Step 2: Filtered points snapped to the grid
+	const sql =
+		'SELECT AVG(latitude) AS latitude, AVG(longitude) AS longitude, COUNT(*) AS count,' +
+		' CASE WHEN COUNT(DISTINCT location_name) = 1 AND COUNT(location_name) = COUNT(*)' +
+		' THEN MIN(location_name) END AS location_name,' +
+		' SUM(COUNT(*)) OVER () AS total' +
+		` FROM cards WHERE ${baseWhere} AND ${LOCATED}` +
+		' GROUP BY ROUND(latitude, ?), ROUND(longitude, ?)' +
+		' ORDER BY count DESC LIMIT ?';
+
+	const stmt = db.prepare<PointRow>(sql);
+	const rows = stmt.all(...params, precision, precision, limit);
+	stmt.free();
+
+	const points = rows.map((row) => ({
+		latitude: row.latitude,
+		longitude: row.longitude,
+		count: row.count,
+		locationName: row.location_name,
+	}));
+
+	// total counts every matching card, including cells cut off by the limit
+	return { points, bounds, total: rows[0]?.total ?? 0 };
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+function clampInt(value: number, min: number, max: number): number {
+	if (!Number.isFinite(value)) return min;
+	return Math.min(max, Math.max(min, Math.trunc(value)));
+}
@@ -0,0 +1,110 @@
+// Isometry v5 -- Map Handler Tests
+// Unit tests for handleMapQuery position resolution and handleGeocodeFill.
+//
+// Pattern: In-memory sql.js database (same as location.handler.test.ts).
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { Database } from '../../database/Database';
+import { seedGeocodePlaces } from '../../database/queries/geocode';
+import { handleGeocodeFill, handleMapQuery } from './map.handler';
+
+// ---------------------------------------------------------------------------
+// Test Helpers
+// ---------------------------------------------------------------------------
+
+let db: Database;
+
+beforeEach(async () => {
+	db = new Database();
+	await db.initialize();
+	seedGeocodePlaces(db);
+});
+
+afterEach(() => {
+	db.close();
+});
+
+function insertCard(id: string, latitude: number | null, longitude: number | null, locationName: string | null) {
+	db.prepare(
+		`INSERT INTO cards (id, name, card_type, latitude, longitude, location_name, status, source)
+		 VALUES (?, ?, 'note', ?, ?, ?, 'active', 'manual')`,
+	).run(id, id, latitude, longitude, locationName);
+}
+
+function connect(id: string, source: string, target: string, label: string | null = null) {
+	db.prepare('INSERT INTO connections (id, source_id, target_id, label) VALUES (?, ?, ?, ?)').run(
+		id,
+		source,
+		target,
+		label,
+	);
+}
+
+// ---------------------------------------------------------------------------
+// handleMapQuery
+// ---------------------------------------------------------------------------
+
+describe('handleMapQuery', () => {
+	it('returns an empty result for no cards', () => {
+		expect(handleMapQuery(db, { cardIds: [] })).toEqual({ points: [], connections: [], unlocated: 0 });
+	});
+
+	it('prefers card coordinates and geocodes the rest from location_name', () => {
+		insertCard('a', 51.52, -0.08, 'London');
+		insertCard('b', null, null, ' tokyo ');
+		insertCard('c', null, null, 'Atlantis');
+
+		const result = handleMapQuery(db, { cardIds: ['a', 'b', 'c'] });
+
+		const byId = new Map(result.points.map((p) => [p.id, p]));
+		expect(byId.get('a')).toEqual({ id: 'a', latitude: 51.52, longitude: -0.08, geocoded: false });
+		expect(byId.get('b')).toEqual({ id: 'b', latitude: 35.6762, longitude: 139.6503, geocoded: true });
+		expect(byId.has('c')).toBe(false);
+		expect(result.unlocated).toBe(1);
+	});
+
+	it('falls back to the gazetteer when stored coordinates are out of range', () => {
+		insertCard('a', 120, 10, 'Paris');
+
+		expect(handleMapQuery(db, { cardIds: ['a'] }).points[0]).toMatchObject({ latitude: 48.8566, geocoded: true });
+	});
+
+	it('only returns requested, non-deleted cards', () => {
+		insertCard('a', 10, 10, null);
+		insertCard('b', 20, 20, null);
+		insertCard('gone', 30, 30, null);
+		db.run("UPDATE cards SET deleted_at = '2026-01-01T00:00:00Z' WHERE id = 'gone'");
+
+		expect(handleMapQuery(db, { cardIds: ['a', 'gone'] }).points.map((p) => p.id)).toEqual(['a']);
+	});
+
+	it('returns connections only when both endpoints are placed and requested', () => {
+		insertCard('a', 10, 10, null);
+		insertCard('b', null, null, 'Berlin');
+		insertCard('c', null, null, null);
+		insertCard('d', 40, 40, null);
+		connect('ab', 'a', 'b', 'visited');
+		connect('ac', 'a', 'c');
+		connect('ad', 'a', 'd');
+		connect('aa', 'a', 'a');
+
+		const result = handleMapQuery(db, { cardIds: ['a', 'b', 'c'] });
+
+		expect(result.connections).toEqual([{ id: 'ab', sourceId: 'a', targetId: 'b', label: 'visited' }]);
+	});
+});
+
+// ---------------------------------------------------------------------------
+// handleGeocodeFill
+// ---------------------------------------------------------------------------
+
+describe('handleGeocodeFill', () => {
+	it('persists gazetteer coordinates and reports the update count', () => {
+		insertCard('a', null, null, 'Sydney');
+		insertCard('b', null, null, 'Rome');
+
+		expect(handleGeocodeFill(db, { cardIds: ['a'] })).toEqual({ updated: 1 });
+		expect(handleGeocodeFill(db, {})).toEqual({ updated: 1 });
+		expect(handleMapQuery(db, { cardIds: ['a', 'b'] }).points.every((p) => !p.geocoded)).toBe(true);
+	});
+});
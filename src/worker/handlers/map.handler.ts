@@ -0,0 +1,102 @@
+// Isometry v5 -- Map View Handlers
+// Card positions and connections for the geographic map view.
+//
+// Follows the location.handler.ts pattern:
+//   - db.prepare() + all() + free() for parameterized query execution
+//   - Only valid coordinates (latitude/longitude in range) are plotted
+//   - Card ids travel as one JSON parameter (json_each) so large views do not
+//     hit SQLite's bound-parameter limit
+//
+// Cards without coordinates fall back to the geocode_places gazetteer via
+// their location_name; such points are flagged `geocoded` so the view can
+// render them differently and offer to persist them (geocode:fill).
+
+import type { Database } from '../../database/Database';
+import { fillCoordinatesFromPlaces } from '../../database/queries/geocode';
+import type { WorkerPayloads, WorkerResponses } from '../protocol';
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+interface PositionRow {
+	id: string;
+	latitude: number | null;
+	longitude: number | null;
+	geocoded: number;
+}
+
+interface ConnectionRow {
+	id: string;
+	source_id: string;
+	target_id: string;
+	label: string | null;
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+/**
+ * Handle map:query request.
+ * Resolves a position for each requested card (own coordinates first, then
+ * the gazetteer) and returns the connections whose endpoints are both placed.
+ */
+export function handleMapQuery(db: Database, payload: WorkerPayloads['map:query']): WorkerResponses['map:query'] {
+	if (payload.cardIds.length === 0) return { points: [], connections: [], unlocated: 0 };
+	const ids = JSON.stringify(payload.cardIds);
+
+	const positionSql =
+		'SELECT c.id,' +
+		' CASE WHEN c.latitude BETWEEN -90 AND 90 AND c.longitude BETWEEN -180 AND 180' +
+		' THEN c.latitude ELSE g.latitude END AS latitude,' +
+		' CASE WHEN c.latitude BETWEEN -90 AND 90 AND c.longitude BETWEEN -180 AND 180' +
+		' THEN c.longitude ELSE g.longitude END AS longitude,' +
+		' CASE WHEN c.latitude BETWEEN -90 AND 90 AND c.longitude BETWEEN -180 AND 180' +
+		' THEN 0 ELSE 1 END AS geocoded' +
+		' FROM cards c' +
+		' LEFT JOIN geocode_places g ON g.name = TRIM(c.location_name)' +
+		' WHERE c.deleted_at IS NULL AND c.id IN (SELECT value FROM json_each(?))';
+
+	const positionStmt = db.prepare<PositionRow>(positionSql);
+	const rows = positionStmt.all(ids);
+	positionStmt.free();
+
+	const points: WorkerResponses['map:query']['points'] = [];
+	for (const row of rows) {
+		if (row.latitude === null || row.longitude === null) continue;
+		points.push({ id: row.id, latitude: row.latitude, longitude: row.longitude, geocoded: row.geocoded === 1 });
+	}
+
+	if (points.length < 2) return { points, connections: [], unlocated: rows.length - points.length };
+
+	// Connections between placed cards only — an arc needs both endpoints
+	const placed = JSON.stringify(points.map((p) => p.id));
+	const connectionSql =
+		'SELECT id, source_id, target_id, label FROM connections' +
+		' WHERE source_id IN (SELECT value FROM json_each(?))' +
+		' AND target_id IN (SELECT value FROM json_each(?))' +
+		' AND source_id != target_id';
+
+	const connectionStmt = db.prepare<ConnectionRow>(connectionSql);
+	const connections = connectionStmt.all(placed, placed).map((row) => ({
+		id: row.id,
+		sourceId: row.source_id,
+		targetId: row.target_id,
+		label: row.label,
+	}));
+	connectionStmt.free();
+
+	return { points, connections, unlocated: rows.length - points.length };
+}
+
+/**
+ * Handle geocode:fill request.
+ * Persists gazetteer coordinates into cards that only carry a location_name.
+ */
+export function handleGeocodeFill(
+	db: Database,
+	payload: WorkerPayloads['geocode:fill'],
+): WorkerResponses['geocode:fill'] {
+	return { updated: fillCoordinatesFromPlaces(db, payload.cardIds) };
+}
@@ -0,0 +1,53 @@
+// Isometry v5 — Mapping Profiles Handlers
+// Thin wrappers around the mapping_profiles query functions, plus header-based
+// profile suggestion for an import payload.
+
+import type { Database } from '../../database/Database';
+import * as mappingProfiles from '../../database/queries/mapping-profiles';
+import { ImportOrchestrator } from '../../etl/ImportOrchestrator';
+import { suggestMappingProfile } from '../../etl/MappingProfile';
+import type { WorkerPayloads, WorkerResponses } from '../protocol';
+
+/**
+ * Handle mapping-profile:list request.
+ * Returns all mapping profiles ordered by name.
+ */
+export function handleMappingProfileList(db: Database): WorkerResponses['mapping-profile:list'] {
+	return mappingProfiles.listMappingProfiles(db);
+}
+
+/**
+ * Handle mapping-profile:save request.
+ * Creates the profile, or replaces an existing one with the same name.
+ */
+export function handleMappingProfileSave(
+	db: Database,
+	payload: WorkerPayloads['mapping-profile:save'],
+): WorkerResponses['mapping-profile:save'] {
+	return mappingProfiles.saveMappingProfile(db, payload.input);
+}
+
+/**
+ * Handle mapping-profile:delete request.
+ */
+export function handleMappingProfileDelete(
+	db: Database,
+	payload: WorkerPayloads['mapping-profile:delete'],
+): WorkerResponses['mapping-profile:delete'] {
+	mappingProfiles.deleteMappingProfile(db, payload.id);
+}
+
+/**
+ * Handle mapping-profile:suggest request.
+ * Reads the header row of the import payload and matches it against saved
+ * profiles by fingerprint. Sources without headers return no suggestion.
+ */
+export async function handleMappingProfileSuggest(
+	db: Database,
+	payload: WorkerPayloads['mapping-profile:suggest'],
+): Promise<WorkerResponses['mapping-profile:suggest']> {
+	const orchestrator = new ImportOrchestrator(db);
+	const headers = await orchestrator.readHeaders(payload.source, payload.data, payload.filename, payload.sheet);
+	if (headers.length === 0) return { headers, suggestion: null };
+	return { headers, suggestion: suggestMappingProfile(headers, mappingProfiles.listMappingProfiles(db)) };
+}
@@ -0,0 +1,61 @@
+// Isometry v5 — Custom Card Properties Handlers
+// Thin wrappers around the card_properties query functions.
+//
+// property:define and property:delete change the set of valid prop_<key>
+// fields; the worker router refreshes its column allowlist after either.
+
+import type { Database } from '../../database/Database';
+import * as properties from '../../database/queries/properties';
+import type { WorkerPayloads, WorkerResponses } from '../protocol';
+
+/**
+ * Handle property:list request.
+ * Returns all property definitions ordered by label.
+ */
+export function handlePropertyList(db: Database): WorkerResponses['property:list'] {
+	return properties.listPropertyDefinitions(db);
+}
+
+/**
+ * Handle property:define request.
+ * Creates a new typed property; throws on invalid or duplicate key.
+ */
+export function handlePropertyDefine(
+	db: Database,
+	payload: WorkerPayloads['property:define'],
+): WorkerResponses['property:define'] {
+	return properties.createPropertyDefinition(db, payload.input);
+}
+
+/**
+ * Handle property:delete request.
+ * Removes the definition and all of its card values.
+ */
+export function handlePropertyDelete(
+	db: Database,
+	payload: WorkerPayloads['property:delete'],
+): WorkerResponses['property:delete'] {
+	properties.deletePropertyDefinition(db, payload.key);
+}
+
+/**
+ * Handle property:set request.
+ * Coerces and stores a card's value; empty values clear it.
+ */
+export function handlePropertySet(
+	db: Database,
+	payload: WorkerPayloads['property:set'],
+): WorkerResponses['property:set'] {
+	properties.setCardProperty(db, payload.cardId, payload.key, payload.value);
+}
+
+/**
+ * Handle property:get request.
+ * Returns a card's property values keyed by property key.
+ */
+export function handlePropertyGet(
+	db: Database,
+	payload: WorkerPayloads['property:get'],
+): WorkerResponses['property:get'] {
+	return properties.getCardProperties(db, payload.cardId);
+}
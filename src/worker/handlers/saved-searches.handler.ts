@@ -0,0 +1,45 @@
+// Isometry v5 — Saved Searches Handlers
+// Thin wrappers around the saved_searches query functions.
+
+import type { Database } from '../../database/Database';
+import * as savedSearches from '../../database/queries/saved-searches';
+import type { WorkerPayloads, WorkerResponses } from '../protocol';
+
+/**
+ * Handle saved-search:list request.
+ * Returns all saved searches ordered by name, with live card counts.
+ */
+export function handleSavedSearchList(db: Database): WorkerResponses['saved-search:list'] {
+	return savedSearches.listSavedSearches(db);
+}
+
+/**
+ * Handle saved-search:save request.
+ * Creates the saved search, or replaces the state of an existing one with the same name.
+ */
+export function handleSavedSearchSave(
+	db: Database,
+	payload: WorkerPayloads['saved-search:save'],
+): WorkerResponses['saved-search:save'] {
+	return savedSearches.saveSearch(db, payload.name, payload.state);
+}
+
+/**
+ * Handle saved-search:rename request.
+ */
+export function handleSavedSearchRename(
+	db: Database,
+	payload: WorkerPayloads['saved-search:rename'],
+): WorkerResponses['saved-search:rename'] {
+	savedSearches.renameSavedSearch(db, payload.id, payload.name);
+}
+
+/**
+ * Handle saved-search:delete request.
+ */
+export function handleSavedSearchDelete(
+	db: Database,
+	payload: WorkerPayloads['saved-search:delete'],
+): WorkerResponses['saved-search:delete'] {
+	savedSearches.deleteSavedSearch(db, payload.id);
+}
@@ -0,0 +1,53 @@
+// Isometry v5 — Stories Handlers
+// Thin wrappers around the stories query functions, plus the export payload.
+
+import type { Database } from '../../database/Database';
+import * as stories from '../../database/queries/stories';
+import type { WorkerPayloads, WorkerResponses } from '../protocol';
+
+/** Cards listed per slide in the HTML export. */
+const EXPORT_CARDS_PER_SLIDE = 25;
+
+/**
+ * Handle story:list request.
+ * Returns all stories ordered by name, each with its ordered slides.
+ */
+export function handleStoryList(db: Database): WorkerResponses['story:list'] {
+	return stories.listStories(db);
+}
+
+/**
+ * Handle story:save request.
+ * Creates the story (no id) or replaces its name and slides.
+ */
+export function handleStorySave(db: Database, payload: WorkerPayloads['story:save']): WorkerResponses['story:save'] {
+	return stories.saveStory(db, payload.input);
+}
+
+/**
+ * Handle story:delete request.
+ */
+export function handleStoryDelete(
+	db: Database,
+	payload: WorkerPayloads['story:delete'],
+): WorkerResponses['story:delete'] {
+	stories.deleteStory(db, payload.id);
+}
+
+/**
+ * Handle story:export request.
+ * Returns the story with live matching/selected cards for every slide.
+ *
+ * @throws {Error} if the story does not exist
+ */
+export function handleStoryExport(
+	db: Database,
+	payload: WorkerPayloads['story:export'],
+): WorkerResponses['story:export'] {
+	const story = stories.getStory(db, payload.id);
+	if (!story) throw new Error(`Story not found: ${payload.id}`);
+	return {
+		story,
+		slides: story.slides.map((slide) => stories.slideCards(db, slide.state, EXPORT_CARDS_PER_SLIDE)),
+	};
+}
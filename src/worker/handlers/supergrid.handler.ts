@@ -8,6 +8,7 @@
 
 import type { Database } from '../../database/Database';
 import { validateAxisField } from '../../providers/allowlist';
+import { fieldExpr } from '../../providers/properties';
 import { buildSuperGridCalcQuery, buildSuperGridQuery } from '../../views/supergrid/SuperGridQuery';
 import type { CellDatum, WorkerPayloads, WorkerResponses } from '../protocol';
 
@@ -164,8 +165,8 @@ export function handleSuperGridCellDetail(
 	const bindParams: unknown[] = [...params];
 
 	for (const [field, value] of Object.entries(axisValues)) {
-		// Phase 116: prefix metric columns with graph_metrics.
-		const qualified = METRIC_COLUMNS.has(field) ? `graph_metrics.${field}` : field;
+		// Phase 116: prefix metric columns with graph_metrics.; property fields become subqueries
+		const qualified = METRIC_COLUMNS.has(field) ? `graph_metrics.${field}` : fieldExpr(field);
 		// NULL axis values require IS NULL, string values use = ?
 		if (value === null || value === undefined) {
 			axisConditions.push(`${qualified} IS NULL`);
@@ -255,7 +256,9 @@ export function handleDistinctValues(
 	// Build query with optional WHERE filter
 	const whereClause = payload.where ? ` AND ${payload.where}` : '';
 
-	const sql = `SELECT DISTINCT ${payload.column} FROM cards WHERE deleted_at IS NULL${whereClause} ORDER BY ${payload.column} ASC`;
+	// Custom property fields (prop_<key>) resolve to a card_properties subquery
+	const expr = fieldExpr(payload.column);
+	const sql = `SELECT DISTINCT ${expr} AS ${payload.column} FROM cards WHERE deleted_at IS NULL${whereClause} ORDER BY ${payload.column} ASC`;
 	const params = payload.params ?? [];
 
 	// Execute and extract flat string array
@@ -21,7 +21,24 @@ export type {
 	Connection,
 	ConnectionDirection,
 	ConnectionInput,
+	ConnectionUpdate,
+	FormulaDefinition,
+	FormulaDefinitionInput,
+	FormulaInfo,
+	FormulaResultType,
+	PropertyDefinition,
+	PropertyDefinitionInput,
+	PropertyType,
+	PropertyValue,
+	SavedSearch,
 	SearchResult,
+	SendOptions,
+	SimilarCardResult,
+	Story,
+	StoryInput,
+	StorySlide,
+	StorySlideCards,
+	StorySlideState,
 	WorkerBridgeConfig,
 	WorkerError,
 	WorkerErrorCode,
@@ -35,6 +52,7 @@ export type {
 // Re-export type guards for advanced usage
 export {
 	DEFAULT_WORKER_CONFIG,
+	isCancelledError,
 	isErrorResponse,
 	isInitErrorMessage,
 	isReadyMessage,
@@ -16,10 +16,33 @@ import type {
 	Connection,
 	ConnectionDirection,
 	ConnectionInput,
+	ConnectionUpdate,
 	SearchResult,
+	SimilarCardResult,
 } from '../database/queries/types';
-
+import type { FormulaDefinition, FormulaDefinitionInput, FormulaResultType } from '../database/queries/formulas';
+import type { MappingProfileInput } from '../database/queries/mapping-profiles';
+import type {
+	PropertyDefinition,
+	PropertyDefinitionInput,
+	PropertyType,
+	PropertyValue,
+} from '../database/queries/properties';
+import type { SavedSearch } from '../database/queries/saved-searches';
+import type {
+	Story,
+	StoryInput,
+	StorySlide,
+	StorySlideCards,
+	StorySlideState,
+} from '../database/queries/stories';
+
+import type { EnricherInfo, EnricherSetting } from '../etl/enrichment/types';
+import type { ManifestEntry, ReimportFile, ReimportFileChange } from '../etl/FileManifest';
+import type { MappingProfile, MappingSuggestion } from '../etl/MappingProfile';
+import type { ExcelSheetInfo } from '../etl/parsers/ExcelParser';
 import type { CanonicalCard, ImportResult, SourceType } from '../etl/types';
+import type { CompiledFormula, FormulaInfo } from '../providers/formulas';
 import type { AggregationMode, AxisMapping, TimeGranularity } from '../providers/types';
 import type { SuperGridQueryConfig } from '../views/supergrid/SuperGridQuery';
 
@@ -31,11 +54,34 @@ export type {
 	CardType,
 	Connection,
 	ConnectionInput,
+	ConnectionUpdate,
 	ConnectionDirection,
 	SearchResult,
+	SimilarCardResult,
 	CardWithDepth,
 };
 
+// Re-export custom property types for consumers
+export type { PropertyDefinition, PropertyDefinitionInput, PropertyType, PropertyValue };
+
+// Re-export saved search type for consumers
+export type { SavedSearch };
+
+// Re-export mapping profile types for consumers
+export type { MappingProfile, MappingProfileInput, MappingSuggestion };
+
+// Re-export Excel sheet picker type for consumers
+export type { ExcelSheetInfo };
+
+// Re-export enricher settings types for consumers
+export type { EnricherInfo, EnricherSetting };
+
+// Re-export formula field types for consumers
+export type { CompiledFormula, FormulaDefinition, FormulaDefinitionInput, FormulaInfo, FormulaResultType };
+
+// Re-export story types for consumers
+export type { Story, StoryInput, StorySlide, StorySlideCards, StorySlideState };
+
 // Re-export ETL types for consumers
 export type { SourceType, ImportResult, CanonicalCard };
 
@@ -101,8 +147,8 @@ export interface WorkerNotification {
  *
  * Naming convention: `domain:action`
  *   - card:create, card:get, card:update, card:delete, card:undelete, card:list
- *   - connection:create, connection:get, connection:delete
- *   - search:cards
+ *   - connection:create, connection:get, connection:update, connection:delete
+ *   - search:cards, search:similar
  *   - graph:connected, graph:shortestPath
  *   - db:export
  */
@@ -117,9 +163,12 @@ export type WorkerRequestType =
 	// Connections (CONN-01..04)
 	| 'connection:create'
 	| 'connection:get'
+	| 'connection:update'
 	| 'connection:delete'
 	// Search (SRCH-01..04)
 	| 'search:cards'
+	// Similar-card search (TF-IDF vectors)
+	| 'search:similar'
 	// Graph (PERF-04)
 	| 'graph:connected'
 	| 'graph:shortestPath'
@@ -139,6 +188,7 @@ export type WorkerRequestType =
 	// ETL Operations (Phase 8)
 	| 'etl:import'
 	| 'etl:export'
+	| 'etl:excel-sheets'
 	// Native ETL Operations (Phase 33)
 	| 'etl:import-native'
 	// SuperGrid Operations (Phase 16)
@@ -152,6 +202,11 @@ export type WorkerRequestType =
 	| 'chart:query'
 	// Histogram Operations (Phase 66)
 	| 'histogram:query'
+	// Location explorer map points
+	| 'location:query'
+	// Map view positions + geocoding
+	| 'map:query'
+	| 'geocode:fill'
 	// Datasets Operations (Phase 88)
 	| 'datasets:query'
 	| 'datasets:stats'
@@ -162,12 +217,45 @@ export type WorkerRequestType =
 	| 'datasets:delete'
 	| 'datasets:reimport'
 	| 'datasets:commit-reimport'
+	| 'datasets:files'
 	// Graph Algorithm Operations (v9.0 Phase 114)
 	| 'graph:compute'
 	| 'graph:metrics-read'
 	| 'graph:metrics-clear'
 	// Enrichment Operations
-	| 'enrich:backfill';
+	| 'enrich:backfill'
+	| 'enrich:list'
+	| 'enrich:set'
+	// Cursor Streaming (paged card:list / db:query)
+	| 'cursor:open'
+	| 'cursor:next'
+	| 'cursor:close'
+	// Custom card properties (typed EAV fields)
+	| 'property:list'
+	| 'property:define'
+	| 'property:delete'
+	| 'property:set'
+	| 'property:get'
+	// Saved searches (named FilterProvider states)
+	| 'saved-search:list'
+	| 'saved-search:save'
+	| 'saved-search:rename'
+	| 'saved-search:delete'
+	// Import mapping profiles (CSV / Excel / JSON)
+	| 'mapping-profile:list'
+	| 'mapping-profile:save'
+	| 'mapping-profile:delete'
+	| 'mapping-profile:suggest'
+	// Formula fields (virtual fx_<name> columns)
+	| 'formula:list'
+	| 'formula:define'
+	| 'formula:delete'
+	| 'formula:preview'
+	// Stories (ordered slides of captured view states)
+	| 'story:list'
+	| 'story:save'
+	| 'story:delete'
+	| 'story:export';
 
 // ---------------------------------------------------------------------------
 // Phase 7 — Force Simulation Types (VIEW-08)
@@ -224,6 +312,31 @@ export interface NodePosition {
 	fy: number | null;
 }
 
+// ---------------------------------------------------------------------------
+// Cursor Streaming Types
+// ---------------------------------------------------------------------------
+
+/**
+ * Query backing a Worker-side cursor.
+ *   - card:list — same filters/ordering as 'card:list'; rows are mapped to Card
+ *   - db:query  — parameterized SELECT; rows are plain column→value objects
+ */
+export type CursorSource =
+	| { kind: 'card:list'; options?: CardListOptions }
+	| { kind: 'db:query'; sql: string; params: unknown[] };
+
+/**
+ * One page of rows from a Worker-side cursor.
+ * When `done` is true the Worker has already released the cursor —
+ * no cursor:close is needed.
+ */
+export interface CursorPage<T = Record<string, unknown>> {
+	cursorId: string;
+	columns: string[];
+	rows: T[];
+	done: boolean;
+}
+
 /**
  * Payload type map — keys are WorkerRequestType, values are payload shapes.
  * Each payload is a plain object that can cross the structuredClone boundary.
@@ -240,10 +353,12 @@ export interface WorkerPayloads {
 	// Connections
 	'connection:create': { input: ConnectionInput };
 	'connection:get': { cardId: string; direction?: ConnectionDirection };
+	'connection:update': { id: string; updates: ConnectionUpdate };
 	'connection:delete': { id: string };
 
 	// Search
 	'search:cards': { query: string; limit?: number };
+	'search:similar': { cardId: string; limit?: number };
 
 	// Graph
 	'graph:connected': { startId: string; maxDepth?: number };
@@ -274,12 +389,15 @@ export interface WorkerPayloads {
 		options?: {
 			isBulkImport?: boolean; // Enable FTS optimization for large imports
 			filename?: string; // Source filename for catalog
+			profileId?: string; // Mapping profile for csv/excel/json
+			sheets?: string[]; // Excel sheets to import together as one dataset
 		};
 	};
 	'etl:export': {
-		format: 'markdown' | 'json' | 'csv';
+		format: 'markdown' | 'json' | 'csv' | 'opml' | 'ics';
 		cardIds?: string[]; // Optional filter (from SelectionProvider)
 	};
+	'etl:excel-sheets': { data: string | ArrayBuffer }; // Sheet picker: row counts before commit
 
 	// Native ETL Operations (Phase 33 — pre-parsed cards from Swift adapters)
 	'etl:import-native': {
@@ -338,6 +456,22 @@ export interface WorkerPayloads {
 		params: unknown[]; // From FilterProvider.compile()
 	};
 
+	// Location explorer map points (LATCH Location section)
+	'location:query': {
+		where: string; // From FilterProvider.compile()
+		params: unknown[]; // From FilterProvider.compile()
+		precision?: number; // Aggregation grid in decimal places of a degree (default 2)
+		limit?: number; // Max aggregated points, densest first (default/cap 2000)
+	};
+
+	// Map view positions + geocoding
+	'map:query': {
+		cardIds: string[]; // Cards currently rendered by the map view
+	};
+	'geocode:fill': {
+		cardIds?: string[]; // Restrict to these cards; omit to fill every card
+	};
+
 	// Datasets Operations (Phase 88)
 	'datasets:query': Record<string, never>; // No payload — returns all rows
 	'datasets:stats': Record<string, never>; // No payload — returns card/connection/size counts
@@ -350,11 +484,13 @@ export interface WorkerPayloads {
 	'datasets:delete': { datasetId: string };
 	'datasets:reimport': {
 		datasetId: string;
-		cards: CanonicalCard[]; // Pre-parsed cards from Swift re-read
+		cards?: CanonicalCard[]; // Pre-parsed cards from Swift re-read
+		files?: ReimportFile[]; // Incremental re-scan: every file, cards only for changed ones
 	};
 	'datasets:commit-reimport': {
 		datasetId: string;
 	};
+	'datasets:files': { datasetId: string }; // Stored file manifest sent to Swift before a re-scan
 
 	// Graph Algorithm Operations (v9.0 Phase 114)
 	'graph:compute': {
@@ -376,7 +512,48 @@ export interface WorkerPayloads {
 	'graph:metrics-clear': Record<string, never>;
 
 	// Enrichment Operations
-	'enrich:backfill': Record<string, never>; // No payload — backfills all cards
+	/** Folder levels always; with enricherIds (or {}) also re-runs those (or all enabled) enrichers */
+	'enrich:backfill': { enricherIds?: string[] };
+	'enrich:list': Record<string, never>;
+	'enrich:set': EnricherSetting;
+
+	// Cursor Streaming — open returns the first page, next returns subsequent pages
+	'cursor:open': { source: CursorSource; pageSize?: number };
+	'cursor:next': { cursorId: string; pageSize?: number };
+	'cursor:close': { cursorId: string };
+
+	// Custom card properties
+	'property:list': Record<string, never>;
+	'property:define': { input: PropertyDefinitionInput };
+	'property:delete': { key: string };
+	'property:set': { cardId: string; key: string; value: unknown };
+	'property:get': { cardId: string };
+
+	// Saved searches — state is FilterProvider.toJSON()
+	'saved-search:list': Record<string, never>;
+	'saved-search:save': { name: string; state: string };
+	'saved-search:rename': { id: string; name: string };
+	'saved-search:delete': { id: string };
+
+	// Import mapping profiles — suggest reads the header row of an import payload
+	'mapping-profile:list': Record<string, never>;
+	'mapping-profile:save': { input: MappingProfileInput };
+	'mapping-profile:delete': { id: string };
+	'mapping-profile:suggest': { source: SourceType; data: string | ArrayBuffer; filename?: string; sheet?: string };
+
+	// Formula fields — expression is the right-hand side of `name = ...`
+	'formula:list': Record<string, never>;
+	'formula:define': { input: FormulaDefinitionInput };
+	'formula:delete': { id: string };
+	/** Compile an expression without saving it and evaluate it on a few cards */
+	'formula:preview': { expression: string; datasetId?: string | null };
+
+	// Stories — saving replaces the story's whole slide list
+	'story:list': Record<string, never>;
+	'story:save': { input: StoryInput };
+	'story:delete': { id: string };
+	/** Story plus live cards per slide, for the standalone HTML export */
+	'story:export': { id: string };
 }
 
 /**
@@ -393,9 +570,11 @@ export interface WorkerResponses {
 
 	'connection:create': Connection;
 	'connection:get': Connection[];
+	'connection:update': Connection;
 	'connection:delete': undefined;
 
 	'search:cards': SearchResult[];
+	'search:similar': SimilarCardResult[];
 
 	'graph:connected': CardWithDepth[];
 	'graph:shortestPath': string[] | null;
@@ -420,6 +599,7 @@ export interface WorkerResponses {
 	// ETL Operations (Phase 8)
 	'etl:import': ImportResult;
 	'etl:export': { data: string; filename: string };
+	'etl:excel-sheets': ExcelSheetInfo[];
 
 	// Native ETL Operations (Phase 33)
 	'etl:import-native': ImportResult;
@@ -455,6 +635,21 @@ export interface WorkerResponses {
 		bins: Array<{ binStart: number | string; binEnd: number | string; count: number }>;
 	};
 
+	// Location explorer map points
+	'location:query': {
+		points: Array<{ latitude: number; longitude: number; count: number; locationName: string | null }>;
+		bounds: { south: number; west: number; north: number; east: number } | null; // All located cards, unfiltered
+		total: number; // Located cards matching the filter
+	};
+
+	// Map view positions + geocoding
+	'map:query': {
+		points: Array<{ id: string; latitude: number; longitude: number; geocoded: boolean }>; // geocoded = from location_name
+		connections: Array<{ id: string; sourceId: string; targetId: string; label: string | null }>; // Both ends placed
+		unlocated: number; // Requested cards with no resolvable position
+	};
+	'geocode:fill': { updated: number };
+
 	// Datasets Operations (Phase 88)
 	'datasets:query': Array<{
 		id: string;
@@ -492,9 +687,12 @@ export interface WorkerResponses {
 		toUpdate: Array<{ id: string; name: string }>;
 		deletedIds: string[];
 		deletedNames: string[];
+		renamed: Array<{ id: string; name: string }>;
 		unchanged: number;
+		files: ReimportFileChange[]; // Changed files with their card changes; empty without a file re-scan
 	};
 	'datasets:commit-reimport': ImportResult;
+	'datasets:files': ManifestEntry[];
 
 	// Graph Algorithm Operations (v9.0 Phase 114)
 	'graph:compute': {
@@ -524,7 +722,64 @@ export interface WorkerResponses {
 	'graph:metrics-clear': { success: boolean };
 
 	// Enrichment Operations
-	'enrich:backfill': { updated: number; skipped: number };
+	'enrich:backfill': {
+		updated: number;
+		skipped: number;
+		/** Present when enrichers were re-run */
+		insertedIds?: string[];
+		updatedIds?: string[];
+		enriched?: Record<string, string[]>;
+	};
+	'enrich:list': { enrichers: EnricherInfo[]; settings: EnricherSetting[] };
+	'enrich:set': undefined;
+
+	// Cursor Streaming
+	'cursor:open': CursorPage;
+	'cursor:next': CursorPage;
+	'cursor:close': { closed: boolean };
+
+	// Custom card properties
+	'property:list': PropertyDefinition[];
+	'property:define': PropertyDefinition;
+	'property:delete': undefined;
+	'property:set': undefined;
+	/** Values keyed by property key (not prop_ field name) */
+	'property:get': Record<string, PropertyValue>;
+
+	// Saved searches
+	'saved-search:list': SavedSearch[];
+	'saved-search:save': SavedSearch;
+	'saved-search:rename': undefined;
+	'saved-search:delete': undefined;
+
+	// Import mapping profiles
+	'mapping-profile:list': MappingProfile[];
+	'mapping-profile:save': MappingProfile;
+	'mapping-profile:delete': undefined;
+	/** Header row of the import and the best matching profile, if any */
+	'mapping-profile:suggest': { headers: string[]; suggestion: MappingSuggestion | null };
+
+	// Formula fields
+	'formula:list': FormulaInfo[];
+	/** Saved definition with its compile result (rejected when it does not compile) */
+	'formula:define': FormulaInfo;
+	'formula:delete': undefined;
+	'formula:preview': {
+		/** Compiled SQL; null when the expression does not compile */
+		sql: string | null;
+		error: string | null;
+		/** Character offset of the compile error in the expression */
+		position: number | null;
+		/** Sample results for the first cards (of the dataset, when given) */
+		rows: Array<{ id: string; name: string; value: string | number | null }>;
+	};
+
+	// Stories
+	'story:list': Story[];
+	'story:save': Story;
+	'story:delete': undefined;
+	/** `slides` is parallel to `story.slides` */
+	'story:export': { story: Story; slides: StorySlideCards[] };
 }
 
 // ---------------------------------------------------------------------------
@@ -546,6 +801,13 @@ export interface WorkerRequest<T extends WorkerRequestType = WorkerRequestType>
 	type: T;
 	/** Payload shape depends on type — see WorkerPayloads */
 	payload: WorkerPayloads[T];
+	/**
+	 * Optional latest-wins channel key. The Worker runs one request per channel
+	 * at a time; when a newer request with the same channel arrives, the one
+	 * still waiting (not yet executing) is dropped and answered with a
+	 * WorkerCancelledMessage. Requests without a channel are never queued.
+	 */
+	channel?: string;
 }
 
 /**
@@ -613,6 +875,8 @@ export interface WorkerError {
  * NOT_FOUND         - Requested entity does not exist
  * CONSTRAINT_VIOLATION - FK, unique, or check constraint failed
  * TIMEOUT           - Request exceeded time limit (set by WorkerBridge)
+ * MIGRATION_FAILED  - A schema migration failed during initialization (init-error only)
+ * CANCELLED         - Request aborted via AbortSignal or superseded on its channel
  */
 export type WorkerErrorCode =
 	| 'UNKNOWN'
@@ -620,7 +884,9 @@ export type WorkerErrorCode =
 	| 'INVALID_REQUEST'
 	| 'NOT_FOUND'
 	| 'CONSTRAINT_VIOLATION'
-	| 'TIMEOUT';
+	| 'TIMEOUT'
+	| 'MIGRATION_FAILED'
+	| 'CANCELLED';
 
 // ---------------------------------------------------------------------------
 // Schema Metadata (Phase 70 — Dynamic Schema)
@@ -648,6 +914,12 @@ export interface ColumnInfo {
 	latchFamily: LatchFamily;
 	/** True for INTEGER and REAL columns (usable in numeric aggregations) */
 	isNumeric: boolean;
+	/** Set for user-defined card properties (virtual prop_<key> fields backed by card_properties) */
+	propertyType?: PropertyType;
+	/** Set for formula fields (virtual fx_<name> columns compiled from an expression) */
+	formulaType?: FormulaResultType;
+	/** Display label for user-defined properties and formulas */
+	label?: string;
 }
 
 // ---------------------------------------------------------------------------
@@ -666,6 +938,10 @@ export interface WorkerReadyMessage {
 	schema: {
 		cards: ColumnInfo[];
 		connections: ColumnInfo[];
+		/** User-defined card properties as virtual prop_<key> columns */
+		properties?: ColumnInfo[];
+		/** Formulas that compiled at init; main thread registers them for fieldExpr() */
+		formulas?: CompiledFormula[];
 	};
 }
 
@@ -678,11 +954,49 @@ export interface WorkerInitErrorMessage {
 	error: WorkerError;
 }
 
+// ---------------------------------------------------------------------------
+// Cancellation Messages
+// ---------------------------------------------------------------------------
+
+/**
+ * Why a request was cancelled.
+ *   aborted    - The caller's AbortSignal fired (main thread sent 'cancel')
+ *   superseded - A newer request on the same channel replaced it
+ */
+export type CancelReason = 'aborted' | 'superseded';
+
+/**
+ * Message posted by main thread to cancel an in-flight request.
+ * Queued requests are dropped before execution; a request that is already
+ * executing runs to completion but its result is discarded.
+ */
+export interface WorkerCancelMessage {
+	type: 'cancel';
+	/** Correlation ID of the request to cancel */
+	id: string;
+}
+
+/**
+ * Message posted by worker in place of a WorkerResponse when a request was
+ * cancelled. Carries the correlation ID but no `success` field.
+ */
+export interface WorkerCancelledMessage {
+	type: 'cancelled';
+	/** Correlation ID of the cancelled request */
+	id: string;
+	reason: CancelReason;
+}
+
 /**
  * Union of all possible messages the worker can post.
  * Used for type narrowing in WorkerBridge's onmessage handler.
  */
-export type WorkerMessage = WorkerReadyMessage | WorkerInitErrorMessage | WorkerResponse | WorkerNotification;
+export type WorkerMessage =
+	| WorkerReadyMessage
+	| WorkerInitErrorMessage
+	| WorkerResponse
+	| WorkerNotification
+	| WorkerCancelledMessage;
 
 // ---------------------------------------------------------------------------
 // Type Guards
@@ -715,6 +1029,29 @@ export function isNotification(msg: unknown): msg is WorkerNotification {
 	);
 }
 
+/**
+ * Type guard to check if a message is a cancelled-request notice.
+ * Must be checked BEFORE isResponse — cancelled messages have an `id` too.
+ */
+export function isCancelledMessage(msg: unknown): msg is WorkerCancelledMessage {
+	return (
+		typeof msg === 'object' &&
+		msg !== null &&
+		'type' in msg &&
+		(msg as WorkerCancelledMessage).type === 'cancelled' &&
+		typeof (msg as WorkerCancelledMessage).id === 'string'
+	);
+}
+
+/**
+ * Check whether an error rejected by WorkerBridge.send() is a cancellation
+ * (AbortSignal or channel supersession) rather than a real failure.
+ * Callers typically ignore these silently.
+ */
+export function isCancelledError(error: unknown): boolean {
+	return error instanceof Error && (error as Error & { code?: string }).code === 'CANCELLED';
+}
+
 /**
  * Type guard to check if a message is a response (has correlation id).
  */
@@ -771,6 +1108,24 @@ export interface PendingRequest<T = unknown> {
 	type: WorkerRequestType;
 	/** Timestamp when request was sent — for latency tracking */
 	sentAt: number;
+	/** Releases AbortSignal listener / channel slot once the request settles */
+	cleanup?: () => void;
+}
+
+/**
+ * Per-request options for WorkerBridge.send().
+ */
+export interface SendOptions {
+	/** Timeout override in milliseconds (defaults to WorkerBridgeConfig.timeout) */
+	timeout?: number;
+	/** Abort the request; the promise rejects with code CANCELLED */
+	signal?: AbortSignal;
+	/**
+	 * Latest-wins channel key. Sending a new request on the same channel
+	 * rejects the previous pending request (code CANCELLED) and lets the
+	 * Worker drop it before execution if it has not started yet.
+	 */
+	channel?: string;
 }
 
 // ---------------------------------------------------------------------------
@@ -819,7 +1174,7 @@ export interface WorkerBridgeConfig {
 	 * Phase 70: WorkerBridge passes through the schema — it does NOT store it.
 	 * The caller (main.ts) provides this callback to wire SchemaProvider.
 	 */
-	onSchema?: (schema: { cards: ColumnInfo[]; connections: ColumnInfo[] }) => void;
+	onSchema?: (schema: WorkerReadyMessage['schema']) => void;
 }
 
 /**
@@ -837,6 +1192,13 @@ export const DEFAULT_WORKER_CONFIG: Required<Pick<WorkerBridgeConfig, 'timeout'
  */
 export const ETL_TIMEOUT = 300_000; // 300 seconds
 
+/**
+ * Default number of rows per cursor page.
+ * Large enough to amortize postMessage overhead, small enough that
+ * structuredClone of a page never stalls the main thread noticeably.
+ */
+export const CURSOR_PAGE_SIZE = 500;
+
 /**
  * Extended timeout for graph algorithm computation (v9.0 Phase 114).
  * Large graphs with 10K+ nodes and betweenness centrality sampling may approach 60s.
@@ -22,19 +22,23 @@ if (typeof globalThis.Buffer === 'undefined') {
 //   - WKBR-04: All database operations execute here (off main thread)
 
 import { Database } from '../database/Database';
+import { MigrationError, runMigrations } from '../database/migrations';
 // Import v0.1 query modules (unchanged)
 import * as cards from '../database/queries/cards';
 import * as connections from '../database/queries/connections';
 import * as graph from '../database/queries/graph';
-// Import Phase 114 graph_metrics DDL
-import { GRAPH_METRICS_DDL } from '../database/queries/graph-metrics';
+import { listPropertyDefinitions } from '../database/queries/properties';
 import * as search from '../database/queries/search';
+import * as similarity from '../database/queries/similarity';
 // Import Phase 65 Chart handler
 import { handleChartQuery } from './handlers/chart.handler';
+// Import cursor streaming handlers (paged card:list / db:query)
+import { closeAllCursors, handleCursorClose, handleCursorNext, handleCursorOpen } from './handlers/cursor.handler';
 // Import Phase 88 Datasets handlers (extended Phase 125 with datasets:delete + reimport)
 import {
 	handleDatasetsCommitReimport,
 	handleDatasetsDelete,
+	handleDatasetsFiles,
 	handleDatasetsQuery,
 	handleDatasetsRecentCards,
 	handleDatasetsReimport,
@@ -42,10 +46,10 @@ import {
 	handleDatasetsVacuum,
 } from './handlers/datasets.handler';
 // Import Enrichment Backfill handler
-import { handleEnrichBackfill } from './handlers/enrich-backfill.handler';
+import { handleEnrichBackfill, handleEnrichList, handleEnrichSet } from './handlers/enrich-backfill.handler';
 import { handleETLExport } from './handlers/etl-export.handler';
 // Import Phase 8/9 ETL handlers
-import { handleETLImport } from './handlers/etl-import.handler';
+import { handleETLExcelSheets, handleETLImport } from './handlers/etl-import.handler';
 // Import Phase 33 Native ETL handler
 import { handleETLImportNative } from './handlers/etl-import-native.handler';
 // Import Phase 114 Graph Algorithm handlers
@@ -54,10 +58,51 @@ import {
 	handleGraphMetricsClear,
 	handleGraphMetricsRead,
 } from './handlers/graph-algorithms.handler';
+// Import formula fields handlers
+import {
+	compileStoredFormulas,
+	handleFormulaDefine,
+	handleFormulaDelete,
+	handleFormulaList,
+	handleFormulaPreview,
+} from './handlers/formulas.handler';
 // Import Phase 66 Histogram handler
 import { handleHistogramQuery } from './handlers/histogram.handler';
+// Import Location explorer handler
+import { handleLocationQuery } from './handlers/location.handler';
+// Import Map view handlers
+import { handleGeocodeFill, handleMapQuery } from './handlers/map.handler';
+// Import mapping profiles handlers
+import {
+	handleMappingProfileDelete,
+	handleMappingProfileList,
+	handleMappingProfileSave,
+	handleMappingProfileSuggest,
+} from './handlers/mapping-profiles.handler';
+// Import custom card properties handlers
+import {
+	handlePropertyDefine,
+	handlePropertyDelete,
+	handlePropertyGet,
+	handlePropertyList,
+	handlePropertySet,
+} from './handlers/properties.handler';
+// Import saved searches handlers
+import {
+	handleSavedSearchDelete,
+	handleSavedSearchList,
+	handleSavedSearchRename,
+	handleSavedSearchSave,
+} from './handlers/saved-searches.handler';
 // Import Phase 7 simulation handler
 import { handleGraphSimulate } from './handlers/simulate.handler';
+// Stories handlers
+import {
+	handleStoryDelete,
+	handleStoryExport,
+	handleStoryList,
+	handleStorySave,
+} from './handlers/stories.handler';
 // Import Phase 16 SuperGrid handlers (+ Phase 76 cell-detail)
 import {
 	handleDistinctValues,
@@ -75,6 +120,9 @@ import {
 	handleUiSet,
 } from './handlers/ui-state.handler';
 import type {
+	CancelReason,
+	WorkerCancelledMessage,
+	WorkerCancelMessage,
 	WorkerError,
 	WorkerErrorCode,
 	WorkerInitErrorMessage,
@@ -87,7 +135,9 @@ import type {
 	WorkerResponses,
 } from './protocol';
 // Import Phase 70 schema classifier
-import { setValidColumnNames } from '../providers/allowlist';
+import { setNumericColumnNames, setValidColumnNames } from '../providers/allowlist';
+import { compiledFormulasOf, formulaColumns, getCompiledFormulas, setCompiledFormulas } from '../providers/formulas';
+import { propertyColumnInfo } from '../providers/properties';
 import { classifyColumns } from './schema-classifier';
 
 // ---------------------------------------------------------------------------
@@ -100,9 +150,29 @@ let db: Database | null = null;
 /** Initialization state flag */
 let isInitialized = false;
 
-/** Queue for messages received before initialization completes */
+/**
+ * Queue for messages received before initialization completes.
+ * Requests still in this queue can be cancelled or superseded without ever executing.
+ */
 const pendingQueue: WorkerRequest[] = [];
 
+/**
+ * Latest waiting request per channel key (after init).
+ * Only channelled requests are serialized: each channel runs at most one
+ * request at a time, and a newer request replaces the one waiting here.
+ * Requests without a channel are dispatched immediately, as before.
+ */
+const channelSlots = new Map<string, WorkerRequest>();
+
+/** Channels with a drain scheduled or running */
+const busyChannels = new Set<string>();
+
+/** Correlation IDs of requests currently executing */
+const activeRequests = new Set<string>();
+
+/** Executing requests that were cancelled — their results are discarded on completion */
+const cancelledRequests = new Set<string>();
+
 /**
  * Worker-side valid column names Set.
  * Populated from PRAGMA table_info() at initialization time, before any handler runs.
@@ -110,6 +180,37 @@ const pendingQueue: WorkerRequest[] = [];
  */
 export let validColumnNames: Set<string> = new Set();
 
+/** PRAGMA-derived column names (cards + connections), excluding custom property fields */
+let physicalColumnNames: Set<string> = new Set();
+
+/** PRAGMA-derived INTEGER/REAL column names (cards + connections) */
+let physicalNumericColumnNames: Set<string> = new Set();
+
+/**
+ * Rebuild validColumnNames from the physical columns plus the current
+ * prop_<key> property fields and fx_<name> formula fields, and re-wire the
+ * allowlist (valid and numeric column names). Formulas compile against physical + property fields only, then
+ * their own names are added.
+ * Called at init and after any request that can create or delete properties or formulas.
+ */
+function refreshValidColumnNames(database: Database): void {
+	const propertyColumns = listPropertyDefinitions(database).map(propertyColumnInfo);
+	validColumnNames = new Set([...physicalColumnNames, ...propertyColumns.map((c) => c.name)]);
+	setValidColumnNames(validColumnNames);
+	const numericColumnNames = new Set([
+		...physicalNumericColumnNames,
+		...propertyColumns.filter((c) => c.isNumeric).map((c) => c.name),
+	]);
+	setNumericColumnNames(numericColumnNames);
+
+	const compiled = compiledFormulasOf(compileStoredFormulas(database));
+	setCompiledFormulas(compiled);
+	for (const column of formulaColumns(compiled)) {
+		validColumnNames.add(column.name);
+		if (column.isNumeric) numericColumnNames.add(column.name);
+	}
+}
+
 // ---------------------------------------------------------------------------
 // Initialization
 // ---------------------------------------------------------------------------
@@ -129,58 +230,10 @@ async function initialize(wasmBinary?: ArrayBuffer, dbData?: ArrayBuffer): Promi
 		db = new Database();
 		await db.initialize(wasmBinary, dbData);
 
-		// Schema migration: ensure tables added after v1.0 exist on hydrated databases.
-		// CREATE TABLE IF NOT EXISTS is idempotent — safe on both fresh and existing DBs.
-		if (dbData) {
-			db.run(`CREATE TABLE IF NOT EXISTS datasets (
-				id TEXT PRIMARY KEY NOT NULL,
-				name TEXT NOT NULL,
-				source_type TEXT NOT NULL,
-				card_count INTEGER NOT NULL DEFAULT 0,
-				connection_count INTEGER NOT NULL DEFAULT 0,
-				file_size_bytes INTEGER,
-				filename TEXT,
-				import_run_id TEXT,
-				source_id TEXT,
-				is_active INTEGER NOT NULL DEFAULT 0,
-				created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
-				last_imported_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
-			)`);
-			db.run(`CREATE UNIQUE INDEX IF NOT EXISTS idx_datasets_name_source ON datasets(name, source_type)`);
-			db.run(`CREATE INDEX IF NOT EXISTS idx_datasets_active ON datasets(is_active)`);
-		}
-
-		// Schema migration: ensure graph_metrics table exists (Phase 114, v9.0)
-		{
-			const ddlStatements = GRAPH_METRICS_DDL.split(';').filter((s) => s.trim());
-			for (const stmt of ddlStatements) {
-				db.run(stmt);
-			}
-		}
-
-		// Schema migration: add dataset_id to cards for per-dataset lifecycle (Phase 125)
-		try {
-			db.run('ALTER TABLE cards ADD COLUMN dataset_id TEXT');
-		} catch {
-			// Column already exists — safe to ignore
-		}
-		db.run('CREATE INDEX IF NOT EXISTS idx_cards_dataset_id ON cards(dataset_id)');
-
-		// Schema migration: add directory_path to datasets for re-import (Phase 125 DSET-03)
-		try {
-			db.run('ALTER TABLE datasets ADD COLUMN directory_path TEXT');
-		} catch {
-			// Column already exists — safe to ignore
-		}
-
-		// Schema migration: add folder hierarchy columns for enrichment pipeline
-		for (const col of ['folder_l1', 'folder_l2', 'folder_l3', 'folder_l4']) {
-			try {
-				db.run(`ALTER TABLE cards ADD COLUMN ${col} TEXT`);
-			} catch {
-				// Column already exists — safe to ignore
-			}
-		}
+		// Schema migrations: bring fresh and hydrated databases to the current version.
+		// Each pending migration runs in its own transaction; a failure throws
+		// MigrationError and surfaces as an init-error with code MIGRATION_FAILED.
+		runMigrations(db);
 
 		isInitialized = true;
 
@@ -201,20 +254,31 @@ async function initialize(wasmBinary?: ArrayBuffer, dbData?: ArrayBuffer): Promi
 			throw new Error('[Worker] PRAGMA table_info(cards) returned no columns — schema initialization failed');
 		}
 
+		// Custom card properties surface as virtual prop_<key> columns
+		const propertyColumns = listPropertyDefinitions(db).map(propertyColumnInfo);
+
 		// Populate Worker-side validation Set from classified column names (SCHM-06).
 		// Must be populated before processPendingQueue() so handlers can use it.
-		validColumnNames = new Set([...cardColumns.map((c) => c.name), ...connColumns.map((c) => c.name)]);
+		physicalColumnNames = new Set([...cardColumns.map((c) => c.name), ...connColumns.map((c) => c.name)]);
+		physicalNumericColumnNames = new Set(
+			[...cardColumns, ...connColumns].filter((c) => c.isNumeric).map((c) => c.name),
+		);
 
 		// Wire valid column names into allowlist module for Worker-side validation.
-		// This enables dynamic columns (e.g., folder_l1..l4) to pass validateAxisField()
-		// in Worker handlers without a full SchemaProvider instance.
-		setValidColumnNames(validColumnNames);
+		// This enables dynamic columns (e.g., folder_l1..l4, prop_<key>) to pass
+		// validateAxisField() in Worker handlers without a full SchemaProvider instance.
+		refreshValidColumnNames(db);
 
 		// Signal ready to main thread, including schema metadata
 		const readyMessage: WorkerReadyMessage = {
 			type: 'ready',
 			timestamp: Date.now(),
-			schema: { cards: cardColumns, connections: connColumns },
+			schema: {
+				cards: cardColumns,
+				connections: connColumns,
+				properties: propertyColumns,
+				formulas: [...getCompiledFormulas()],
+			},
 		};
 		self.postMessage(readyMessage);
 
@@ -231,14 +295,96 @@ async function initialize(wasmBinary?: ArrayBuffer, dbData?: ArrayBuffer): Promi
 }
 
 /**
- * Process messages that were queued during initialization.
+ * Dispatch requests that were queued during initialization.
  * Maintains FIFO order to preserve request semantics.
  */
 async function processPendingQueue(): Promise<void> {
 	while (pendingQueue.length > 0) {
 		const request = pendingQueue.shift()!;
+		await dispatchRequest(request);
+	}
+}
+
+/**
+ * Queue a request received before initialization. If it carries a channel
+ * key, drop any queued request on the same channel — latest wins.
+ */
+function enqueueRequest(request: WorkerRequest): void {
+	if (request.channel !== undefined) {
+		for (let i = pendingQueue.length - 1; i >= 0; i--) {
+			const queued = pendingQueue[i]!;
+			if (queued.channel === request.channel) {
+				pendingQueue.splice(i, 1);
+				postCancelled(queued.id, 'superseded');
+			}
+		}
+	}
+	pendingQueue.push(request);
+}
+
+/**
+ * Run a request now, or — when it carries a channel — park it in the
+ * channel's slot, superseding the request already waiting there.
+ */
+async function dispatchRequest(request: WorkerRequest): Promise<void> {
+	const { channel } = request;
+	if (channel === undefined) {
 		await handleRequest(request);
+		return;
+	}
+
+	const waiting = channelSlots.get(channel);
+	if (waiting) postCancelled(waiting.id, 'superseded');
+	channelSlots.set(channel, request);
+	if (!busyChannels.has(channel)) {
+		busyChannels.add(channel);
+		// Drain on a later task so that a burst of postMessage calls is superseded first
+		setTimeout(() => void drainChannel(channel), 0);
+	}
+}
+
+/**
+ * Execute the waiting request of a channel until none is left.
+ * Yields to the event loop between requests so that cancel messages and
+ * newer same-channel requests posted meanwhile are seen first.
+ */
+async function drainChannel(channel: string): Promise<void> {
+	try {
+		let request = channelSlots.get(channel);
+		while (request) {
+			channelSlots.delete(channel);
+			await handleRequest(request);
+			await new Promise((resolve) => setTimeout(resolve, 0));
+			request = channelSlots.get(channel);
+		}
+	} finally {
+		busyChannels.delete(channel);
+	}
+}
+
+/**
+ * Cancel a request by correlation ID.
+ * Waiting requests are removed and answered immediately; an executing request
+ * finishes but its result is replaced with a cancelled notice.
+ */
+function cancelRequest(id: string): void {
+	const index = pendingQueue.findIndex((r) => r.id === id);
+	if (index !== -1) {
+		pendingQueue.splice(index, 1);
+		postCancelled(id, 'aborted');
+		return;
 	}
+	for (const [channel, waiting] of channelSlots) {
+		if (waiting.id === id) {
+			channelSlots.delete(channel);
+			postCancelled(id, 'aborted');
+			return;
+		}
+	}
+	if (activeRequests.has(id)) {
+		cancelledRequests.add(id);
+	}
+	// Otherwise already completed — nothing to do
 }
 
 // ---------------------------------------------------------------------------
@@ -276,6 +422,12 @@ self.onmessage = async (event: MessageEvent) => {
 			await initialize(msg['wasmBinary'] as ArrayBuffer, dbData);
 			return;
 		}
+
+		// Handle cancel from main thread (AbortSignal fired in WorkerBridge.send)
+		if (msg['type'] === 'cancel' && typeof msg['id'] === 'string') {
+			cancelRequest((msg as unknown as WorkerCancelMessage).id);
+			return;
+		}
 	}
 
 	// Validate request shape
@@ -290,11 +442,11 @@ self.onmessage = async (event: MessageEvent) => {
 
 	if (!isInitialized) {
 		// Queue for later processing
-		pendingQueue.push(request);
+		enqueueRequest(request);
 		return;
 	}
 
-	await handleRequest(request);
+	await dispatchRequest(request);
 };
 
 /**
@@ -306,12 +458,24 @@ async function handleRequest(request: WorkerRequest): Promise<void> {
 		return;
 	}
 
+	activeRequests.add(request.id);
 	try {
 		const data = await routeRequest(db, request);
-		postSuccessResponse(request.id, data);
+		if (cancelledRequests.has(request.id)) {
+			postCancelled(request.id, 'aborted');
+		} else {
+			postSuccessResponse(request.id, data);
+		}
 	} catch (error) {
-		const workerError = createWorkerError(error);
-		postErrorResponse(request.id, workerError.code, workerError.message, workerError.stack);
+		if (cancelledRequests.has(request.id)) {
+			postCancelled(request.id, 'aborted');
+		} else {
+			const workerError = createWorkerError(error);
+			postErrorResponse(request.id, workerError.code, workerError.message, workerError.stack);
+		}
+	} finally {
+		activeRequests.delete(request.id);
+		cancelledRequests.delete(request.id);
 	}
 }
 
@@ -380,6 +544,11 @@ async function routeRequest(db: Database, request: WorkerRequest): Promise<Worke
 			return connections.getConnections(db, p.cardId, p.direction);
 		}
 
+		case 'connection:update': {
+			const p = payload as WorkerPayloads['connection:update'];
+			return connections.updateConnection(db, p.id, p.updates);
+		}
+
 		case 'connection:delete': {
 			const p = payload as WorkerPayloads['connection:delete'];
 			connections.deleteConnection(db, p.id);
@@ -394,6 +563,11 @@ async function routeRequest(db: Database, request: WorkerRequest): Promise<Worke
 			return search.searchCards(db, p.query, p.limit);
 		}
 
+		case 'search:similar': {
+			const p = payload as WorkerPayloads['search:similar'];
+			return similarity.findSimilarCards(db, p.cardId, p.limit);
+		}
+
 		// -------------------------------------------------------------------------
 		// Graph Operations (PERF-04)
 		// -------------------------------------------------------------------------
@@ -467,7 +641,10 @@ async function routeRequest(db: Database, request: WorkerRequest): Promise<Worke
 		// -------------------------------------------------------------------------
 		case 'etl:import': {
 			const p = payload as WorkerPayloads['etl:import'];
-			return handleETLImport(db, p);
+			// Imports may define new custom properties from unmapped columns
+			const result = await handleETLImport(db, p);
+			refreshValidColumnNames(db);
+			return result;
 		}
 
 		case 'etl:export': {
@@ -475,6 +652,11 @@ async function routeRequest(db: Database, request: WorkerRequest): Promise<Worke
 			return handleETLExport(db, p);
 		}
 
+		case 'etl:excel-sheets': {
+			const p = payload as WorkerPayloads['etl:excel-sheets'];
+			return handleETLExcelSheets(db, p);
+		}
+
 		// -------------------------------------------------------------------------
 		// Native ETL Operations (Phase 33)
 		// -------------------------------------------------------------------------
@@ -528,6 +710,26 @@ async function routeRequest(db: Database, request: WorkerRequest): Promise<Worke
 			return handleHistogramQuery(db, p);
 		}
 
+		// -------------------------------------------------------------------------
+		// Location Operations (LATCH Location explorer)
+		// -------------------------------------------------------------------------
+		case 'location:query': {
+			const p = payload as WorkerPayloads['location:query'];
+			return handleLocationQuery(db, p);
+		}
+
+		// -------------------------------------------------------------------------
+		// Map view Operations
+		// -------------------------------------------------------------------------
+		case 'map:query': {
+			const p = payload as WorkerPayloads['map:query'];
+			return handleMapQuery(db, p);
+		}
+		case 'geocode:fill': {
+			const p = payload as WorkerPayloads['geocode:fill'];
+			return handleGeocodeFill(db, p);
+		}
+
 		// -------------------------------------------------------------------------
 		// Datasets Operations (Phase 88)
 		// -------------------------------------------------------------------------
@@ -548,7 +750,10 @@ async function routeRequest(db: Database, request: WorkerRequest): Promise<Worke
 		}
 
 		case 'datasets:delete': {
-			return handleDatasetsDelete(db, payload as WorkerPayloads['datasets:delete']);
+			const result = handleDatasetsDelete(db, payload as WorkerPayloads['datasets:delete']);
+			// Dataset-scoped formulas are removed with the dataset (ON DELETE CASCADE)
+			refreshValidColumnNames(db);
+			return result;
 		}
 
 		case 'datasets:reimport': {
@@ -556,7 +761,13 @@ async function routeRequest(db: Database, request: WorkerRequest): Promise<Worke
 		}
 
 		case 'datasets:commit-reimport': {
-			return handleDatasetsCommitReimport(db, payload as WorkerPayloads['datasets:commit-reimport']);
+			const result = await handleDatasetsCommitReimport(db, payload as WorkerPayloads['datasets:commit-reimport']);
+			refreshValidColumnNames(db);
+			return result;
+		}
+
+		case 'datasets:files': {
+			return handleDatasetsFiles(db, payload as WorkerPayloads['datasets:files']);
 		}
 
 		// -------------------------------------------------------------------------
@@ -580,7 +791,164 @@ async function routeRequest(db: Database, request: WorkerRequest): Promise<Worke
 		// Enrichment Operations
 		// -------------------------------------------------------------------------
 		case 'enrich:backfill': {
-			return handleEnrichBackfill(db);
+			const p = payload as WorkerPayloads['enrich:backfill'];
+			return handleEnrichBackfill(db, p);
+		}
+
+		case 'enrich:list': {
+			return handleEnrichList(db);
+		}
+
+		case 'enrich:set': {
+			const p = payload as WorkerPayloads['enrich:set'];
+			handleEnrichSet(db, p);
+			return undefined as unknown as WorkerResponses['enrich:set'];
+		}
+
+		// -------------------------------------------------------------------------
+		// Cursor Streaming Operations
+		// -------------------------------------------------------------------------
+		case 'cursor:open': {
+			const p = payload as WorkerPayloads['cursor:open'];
+			return handleCursorOpen(db, p);
+		}
+
+		case 'cursor:next': {
+			const p = payload as WorkerPayloads['cursor:next'];
+			return handleCursorNext(p);
+		}
+
+		case 'cursor:close': {
+			const p = payload as WorkerPayloads['cursor:close'];
+			return handleCursorClose(p);
+		}
+
+		// -------------------------------------------------------------------------
+		// Custom Card Properties
+		// -------------------------------------------------------------------------
+		case 'property:list': {
+			return handlePropertyList(db);
+		}
+
+		case 'property:define': {
+			const p = payload as WorkerPayloads['property:define'];
+			const definition = handlePropertyDefine(db, p);
+			refreshValidColumnNames(db);
+			return definition;
+		}
+
+		case 'property:delete': {
+			const p = payload as WorkerPayloads['property:delete'];
+			handlePropertyDelete(db, p);
+			refreshValidColumnNames(db);
+			return undefined as unknown as WorkerResponses['property:delete'];
+		}
+
+		case 'property:set': {
+			const p = payload as WorkerPayloads['property:set'];
+			handlePropertySet(db, p);
+			return undefined as unknown as WorkerResponses['property:set'];
+		}
+
+		case 'property:get': {
+			const p = payload as WorkerPayloads['property:get'];
+			return handlePropertyGet(db, p);
+		}
+
+		// -------------------------------------------------------------------------
+		// Saved Searches
+		// -------------------------------------------------------------------------
+		case 'saved-search:list': {
+			return handleSavedSearchList(db);
+		}
+
+		case 'saved-search:save': {
+			const p = payload as WorkerPayloads['saved-search:save'];
+			return handleSavedSearchSave(db, p);
+		}
+
+		case 'saved-search:rename': {
+			const p = payload as WorkerPayloads['saved-search:rename'];
+			handleSavedSearchRename(db, p);
+			return undefined as unknown as WorkerResponses['saved-search:rename'];
+		}
+
+		case 'saved-search:delete': {
+			const p = payload as WorkerPayloads['saved-search:delete'];
+			handleSavedSearchDelete(db, p);
+			return undefined as unknown as WorkerResponses['saved-search:delete'];
+		}
+
+		// -------------------------------------------------------------------------
+		// Import Mapping Profiles
+		// -------------------------------------------------------------------------
+		case 'mapping-profile:list': {
+			return handleMappingProfileList(db);
+		}
+
+		case 'mapping-profile:save': {
+			const p = payload as WorkerPayloads['mapping-profile:save'];
+			return handleMappingProfileSave(db, p);
+		}
+
+		case 'mapping-profile:delete': {
+			const p = payload as WorkerPayloads['mapping-profile:delete'];
+			handleMappingProfileDelete(db, p);
+			return undefined as unknown as WorkerResponses['mapping-profile:delete'];
+		}
+
+		case 'mapping-profile:suggest': {
+			const p = payload as WorkerPayloads['mapping-profile:suggest'];
+			return handleMappingProfileSuggest(db, p);
+		}
+
+		// -------------------------------------------------------------------------
+		// Formula Fields
+		// -------------------------------------------------------------------------
+		case 'formula:list': {
+			return handleFormulaList(db);
+		}
+
+		case 'formula:define': {
+			const p = payload as WorkerPayloads['formula:define'];
+			const formula = handleFormulaDefine(db, p);
+			refreshValidColumnNames(db);
+			return formula;
+		}
+
+		case 'formula:delete': {
+			const p = payload as WorkerPayloads['formula:delete'];
+			handleFormulaDelete(db, p);
+			refreshValidColumnNames(db);
+			return undefined as unknown as WorkerResponses['formula:delete'];
+		}
+
+		case 'formula:preview': {
+			const p = payload as WorkerPayloads['formula:preview'];
+			return handleFormulaPreview(db, p);
+		}
+
+		// -------------------------------------------------------------------------
+		// Stories
+		// -------------------------------------------------------------------------
+		case 'story:list': {
+			return handleStoryList(db);
+		}
+
+		case 'story:save': {
+			const p = payload as WorkerPayloads['story:save'];
+			return handleStorySave(db, p);
+		}
+
+		case 'story:delete': {
+			const p = payload as WorkerPayloads['story:delete'];
+			handleStoryDelete(db, p);
+			return undefined as unknown as WorkerResponses['story:delete'];
+		}
+
+		case 'story:export': {
+			const p = payload as WorkerPayloads['story:export'];
+			return handleStoryExport(db, p);
 		}
 
 		// -------------------------------------------------------------------------
@@ -597,8 +965,11 @@ async function routeRequest(db: Database, request: WorkerRequest): Promise<Worke
 /**
  * Export the database as a Uint8Array.
  * Uses the Database.export() method added in Phase 3.
+ * sql.js frees every prepared statement on export(), so open cursors are closed
+ * first — a later cursor:next then reports NOT_FOUND instead of reading a freed statement.
  */
 function exportDatabase(db: Database): Uint8Array {
+	closeAllCursors();
 	return db.export();
 }
 
@@ -626,6 +997,14 @@ export function postNotification(notification: WorkerNotification): void {
 	self.postMessage(notification);
 }
 
+/**
+ * Post a cancelled notice in place of a response.
+ */
+function postCancelled(id: string, reason: CancelReason): void {
+	const message: WorkerCancelledMessage = { type: 'cancelled', id, reason };
+	self.postMessage(message);
+}
+
 /**
  * Post an error response to the main thread.
  */
@@ -667,6 +1046,11 @@ function createWorkerError(error: unknown, defaultCode: WorkerErrorCode = 'UNKNO
  * Classify an error based on its message to determine the appropriate error code.
  */
 function classifyError(error: Error, defaultCode: WorkerErrorCode): WorkerErrorCode {
+	// Schema migration failures (checked first — the wrapped cause may look like a constraint error)
+	if (error instanceof MigrationError) {
+		return 'MIGRATION_FAILED';
+	}
+
 	const message = error.message.toLowerCase();
 
 	// SQLite constraint violations
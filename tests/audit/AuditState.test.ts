@@ -107,6 +107,41 @@ describe('AuditState', () => {
 		});
 	});
 
+	describe('enrichers', () => {
+		it('records which enrichers changed each card across imports and re-runs', () => {
+			audit.addImportResult(
+				{
+					insertedIds: ['card-1', 'card-2'],
+					updatedIds: [],
+					deletedIds: [],
+					enriched: { 'date-extraction': ['card-1'] },
+				},
+				'markdown',
+			);
+			audit.addEnrichmentResult({
+				insertedIds: ['link-1'],
+				updatedIds: ['card-1'],
+				enriched: { 'url-extraction': ['link-1'], 'reading-time': ['card-1'] },
+			});
+
+			expect(audit.getEnrichers('card-1')).toEqual(['date-extraction', 'reading-time']);
+			expect(audit.getEnrichers('card-2')).toEqual([]);
+			expect(audit.getEnrichers('link-1')).toEqual(['url-extraction']);
+			expect(audit.getChangeStatus('card-1')).toBe('modified');
+			expect(audit.getChangeStatus('link-1')).toBe('new');
+		});
+
+		it('re-runs keep the source recorded at import and notify subscribers', () => {
+			let calls = 0;
+			audit.subscribe(() => calls++);
+			audit.addImportResult({ insertedIds: ['card-1'], updatedIds: [], deletedIds: [] }, 'csv');
+			audit.addEnrichmentResult({ insertedIds: [], updatedIds: ['card-1'], enriched: {} });
+
+			expect(audit.getCardSource('card-1')).toBe('csv');
+			expect(calls).toBe(2);
+		});
+	});
+
 	describe('getDominantSource', () => {
 		it('returns the most common source among given IDs', () => {
 			audit.addImportResult({ insertedIds: ['a', 'b', 'c'], updatedIds: [], deletedIds: [] }, 'apple_notes');
@@ -8,7 +8,13 @@
 import { afterEach, beforeEach, describe, expect, it } from 'vitest';
 import { Database } from '../../src/database/Database';
 import { createCard } from '../../src/database/queries/cards';
-import { createConnection, deleteConnection, getConnections } from '../../src/database/queries/connections';
+import {
+	createConnection,
+	deleteConnection,
+	getConnection,
+	getConnections,
+	updateConnection,
+} from '../../src/database/queries/connections';
 import type { Card } from '../../src/database/queries/types';
 
 // ---------------------------------------------------------------------------
@@ -378,3 +384,50 @@ describe('CONN-05: Cascade delete on card hard-delete', () => {
 		expect(row[colIdx]).toBeNull();
 	});
 });
+
+// ---------------------------------------------------------------------------
+// CONN-06: Update label, weight, and via card in place
+// ---------------------------------------------------------------------------
+
+describe('CONN-06: updateConnection', () => {
+	it('updates label and weight while preserving id and created_at', () => {
+		const conn = createConnection(db, { source_id: cardA.id, target_id: cardB.id, label: 'old' });
+
+		const updated = updateConnection(db, conn.id, { label: 'new', weight: 0.25 });
+
+		expect(updated.id).toBe(conn.id);
+		expect(updated.created_at).toBe(conn.created_at);
+		expect(updated.label).toBe('new');
+		expect(updated.weight).toBe(0.25);
+		expect(getConnection(db, conn.id)?.label).toBe('new');
+	});
+
+	it('sets and clears via_card_id', () => {
+		const conn = createConnection(db, { source_id: cardA.id, target_id: cardB.id });
+
+		expect(updateConnection(db, conn.id, { via_card_id: cardC.id }).via_card_id).toBe(cardC.id);
+		expect(updateConnection(db, conn.id, { via_card_id: null }).via_card_id).toBeNull();
+	});
+
+	it('leaves unspecified fields untouched', () => {
+		const conn = createConnection(db, { source_id: cardA.id, target_id: cardB.id, label: 'keep', weight: 0.4 });
+
+		const updated = updateConnection(db, conn.id, { weight: 0.9 });
+
+		expect(updated.label).toBe('keep');
+		expect(updated.weight).toBe(0.9);
+	});
+
+	it('rejects weight outside [0, 1]', () => {
+		const conn = createConnection(db, { source_id: cardA.id, target_id: cardB.id });
+		expect(() => updateConnection(db, conn.id, { weight: 2 })).toThrow();
+	});
+
+	it('throws for a non-existent connection', () => {
+		expect(() => updateConnection(db, 'missing', { label: 'x' })).toThrow(/not found/);
+	});
+
+	it('getConnection returns null for unknown ids', () => {
+		expect(getConnection(db, 'missing')).toBeNull();
+	});
+});
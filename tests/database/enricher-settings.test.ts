@@ -0,0 +1,36 @@
+// Isometry v5 — Enricher Settings Tests
+// Covers upsert per (enricher, source type) and listing order.
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { Database } from '../../src/database/Database';
+import { listEnricherSettings, setEnricherSetting } from '../../src/database/queries/enricher-settings';
+
+let db: Database;
+
+beforeEach(async () => {
+	db = new Database();
+	await db.initialize();
+});
+
+afterEach(() => {
+	db.close();
+});
+
+describe('enricher settings', () => {
+	it('starts empty', () => {
+		expect(listEnricherSettings(db)).toEqual([]);
+	});
+
+	it('saves one switch per enricher and source type, replacing earlier ones', () => {
+		setEnricherSetting(db, { enricher_id: 'reading-time', source_type: '*', enabled: true });
+		setEnricherSetting(db, { enricher_id: 'reading-time', source_type: 'csv', enabled: true });
+		setEnricherSetting(db, { enricher_id: 'language', source_type: 'markdown', enabled: true });
+		setEnricherSetting(db, { enricher_id: 'reading-time', source_type: 'csv', enabled: false });
+
+		expect(listEnricherSettings(db)).toEqual([
+			{ enricher_id: 'language', source_type: 'markdown', enabled: true },
+			{ enricher_id: 'reading-time', source_type: '*', enabled: true },
+			{ enricher_id: 'reading-time', source_type: 'csv', enabled: false },
+		]);
+	});
+});
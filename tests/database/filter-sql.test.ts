@@ -0,0 +1,91 @@
+// Isometry v5 — Filter SQL Compilation Tests
+// Pure compileFilterList() / compileFilterTree() / compileFilterState() output, independent of FilterProvider.
+
+import { describe, expect, it } from 'vitest';
+import {
+	compileFilterList,
+	compileFilterState,
+	compileFilterTree,
+	isGroupNode,
+	parseFilterState,
+} from '../../src/database/queries/filter-sql';
+import type { Filter, FilterGroup } from '../../src/providers/types';
+
+describe('compileFilterList', () => {
+	it('compiles each condition to a parameterized clause', () => {
+		const filters: Filter[] = [
+			{ field: 'status', operator: 'eq', value: 'done' },
+			{ field: 'name', operator: 'contains', value: 'plan' },
+			{ field: 'priority', operator: 'in', value: [1, 2] },
+		];
+
+		expect(compileFilterList(filters)).toEqual({
+			clauses: ['status = ?', 'name LIKE ?', 'priority IN (?, ?)'],
+			params: ['done', '%plan%', 1, 2],
+		});
+	});
+
+	it('rejects fields and operators outside the allowlist', () => {
+		expect(() => compileFilterList([{ field: 'password' as any, operator: 'eq', value: 'x' }])).toThrow(
+			/SQL safety violation/,
+		);
+		expect(() => compileFilterList([{ field: 'status', operator: 'DROP' as any, value: 'x' }])).toThrow(
+			/SQL safety violation/,
+		);
+	});
+});
+
+describe('compileFilterTree', () => {
+	it('joins children with the group combinator and guards NOT against NULL', () => {
+		const tree: FilterGroup = {
+			kind: 'group',
+			combinator: 'or',
+			negate: true,
+			children: [
+				{ field: 'folder', operator: 'eq', value: 'archive' },
+				{ field: 'status', operator: 'isNull', value: null },
+			],
+		};
+
+		expect(compileFilterTree(tree)).toEqual({
+			clause: 'NOT IFNULL((folder = ? OR status IS NULL), 0)',
+			params: ['archive'],
+		});
+		expect(isGroupNode(tree)).toBe(true);
+		expect(isGroupNode(tree.children[0]!)).toBe(false);
+	});
+
+	it('compiles an empty group to nothing', () => {
+		expect(compileFilterTree({ kind: 'group', combinator: 'and', negate: false, children: [] })).toBeNull();
+	});
+});
+
+describe('compileFilterState', () => {
+	it('compiles a serialized state without a FilterProvider', () => {
+		const state = parseFilterState(
+			JSON.stringify({
+				filters: [{ field: 'status', operator: 'eq', value: 'done' }],
+				searchQuery: null,
+				axisFilters: { folder: ['inbox', 'work'] },
+				rangeFilters: { priority: { min: 1, max: null } },
+			}),
+		);
+
+		expect(compileFilterState(state)).toEqual({
+			where: 'deleted_at IS NULL AND status = ? AND folder IN (?, ?) AND priority >= ?',
+			params: ['done', 'inbox', 'work', 1],
+		});
+	});
+
+	it('rejects corrupt JSON, bad shapes and fields outside the allowlist', () => {
+		expect(() => parseFilterState('{nope')).toThrow(/invalid JSON/);
+		expect(() => parseFilterState(JSON.stringify({ filters: 'x', searchQuery: null }))).toThrow(
+			/invalid state shape/,
+		);
+		expect(() =>
+			parseFilterState(
+				JSON.stringify({ filters: [{ field: 'password', operator: 'eq', value: 'x' }], searchQuery: null }),
+			),
+		).toThrow(/SQL safety violation/);
+	});
+});
@@ -0,0 +1,79 @@
+// Isometry v5 — Boolean Filter Tree Tests
+// Runs FilterProvider filter trees (AND/OR/NOT groups) against a real database
+// to pin the SQL semantics, including NULL handling under NOT.
+
+import type { SqlValue } from 'sql.js';
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { Database } from '../../src/database/Database';
+import { createCard } from '../../src/database/queries/cards';
+import { FilterProvider } from '../../src/providers/FilterProvider';
+import type { FilterGroup } from '../../src/providers/types';
+
+let db: Database;
+
+beforeEach(async () => {
+	db = new Database();
+	await db.initialize();
+});
+
+afterEach(() => {
+	db.close();
+});
+
+function matchingNames(tree: FilterGroup): string[] {
+	const filter = new FilterProvider();
+	filter.setFilterTree(tree);
+	const { where, params } = filter.compile();
+	const result = db.exec(`SELECT name FROM cards WHERE ${where} ORDER BY name`, params as SqlValue[]);
+	return (result[0]?.values ?? []).map((row) => row[0] as string);
+}
+
+describe('filter tree against SQLite', () => {
+	beforeEach(() => {
+		createCard(db, { name: 'A todo', status: 'todo', priority: 1, folder: 'work' });
+		createCard(db, { name: 'B urgent', status: 'done', priority: 3, folder: 'work' });
+		createCard(db, { name: 'C archived todo', status: 'todo', priority: 1, folder: 'archive' });
+		createCard(db, { name: 'D no folder', status: 'todo', priority: 0 });
+		createCard(db, { name: 'E done', status: 'done', priority: 1, folder: 'work' });
+	});
+
+	it('(status = todo OR priority > 2) AND NOT folder = archive', () => {
+		const tree: FilterGroup = {
+			kind: 'group',
+			combinator: 'and',
+			negate: false,
+			children: [
+				{
+					kind: 'group',
+					combinator: 'or',
+					negate: false,
+					children: [
+						{ field: 'status', operator: 'eq', value: 'todo' },
+						{ field: 'priority', operator: 'gt', value: 2 },
+					],
+				},
+				{
+					kind: 'group',
+					combinator: 'and',
+					negate: true,
+					children: [{ field: 'folder', operator: 'eq', value: 'archive' }],
+				},
+			],
+		};
+		// D has no folder — NOT treats the NULL comparison as false and keeps it
+		expect(matchingNames(tree)).toEqual(['A todo', 'B urgent', 'D no folder']);
+	});
+
+	it('negated OR group excludes every card matching any child', () => {
+		const tree: FilterGroup = {
+			kind: 'group',
+			combinator: 'or',
+			negate: true,
+			children: [
+				{ field: 'status', operator: 'eq', value: 'done' },
+				{ field: 'folder', operator: 'eq', value: 'archive' },
+			],
+		};
+		expect(matchingNames(tree)).toEqual(['A todo', 'D no folder']);
+	});
+});
@@ -0,0 +1,77 @@
+// Isometry v5 — Formula Definitions Tests
+// Covers define/upsert per (name, dataset), validation, ordering, delete and
+// cascade removal of dataset-scoped formulas.
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { Database } from '../../src/database/Database';
+import { defineFormula, deleteFormula, listFormulas } from '../../src/database/queries/formulas';
+
+let db: Database;
+
+beforeEach(async () => {
+	db = new Database();
+	await db.initialize();
+	db.run("INSERT INTO datasets (id, name, source_type) VALUES ('ds-1', 'Tasks', 'csv')");
+});
+
+afterEach(() => {
+	db.close();
+});
+
+const DAYS_OPEN = {
+	name: 'days_open',
+	expression: 'julianday(now) - julianday(created_at)',
+	result_type: 'number' as const,
+};
+
+describe('defineFormula', () => {
+	it('creates a global formula labelled with its name by default', () => {
+		const formula = defineFormula(db, DAYS_OPEN);
+
+		expect(formula).toMatchObject({ name: 'days_open', label: 'days_open', dataset_id: null, result_type: 'number' });
+		expect(listFormulas(db)).toEqual([formula]);
+	});
+
+	it('redefining the same name and dataset replaces the expression and keeps the id', () => {
+		const first = defineFormula(db, DAYS_OPEN);
+		const second = defineFormula(db, { ...DAYS_OPEN, expression: '1', label: 'Days' });
+
+		expect(second.id).toBe(first.id);
+		expect(listFormulas(db)).toHaveLength(1);
+		expect(listFormulas(db)[0]).toMatchObject({ expression: '1', label: 'Days' });
+	});
+
+	it('keeps global and per-dataset definitions of a name apart, global first', () => {
+		const scoped = defineFormula(db, { ...DAYS_OPEN, dataset_id: 'ds-1', expression: '2' });
+		const global = defineFormula(db, DAYS_OPEN);
+
+		expect(listFormulas(db).map((f) => f.id)).toEqual([global.id, scoped.id]);
+	});
+
+	it('rejects invalid names, result types and empty expressions', () => {
+		expect(() => defineFormula(db, { ...DAYS_OPEN, name: 'Days Open' })).toThrow(/Invalid formula name/);
+		expect(() => defineFormula(db, { ...DAYS_OPEN, result_type: 'bool' as never })).toThrow(/result type/);
+		expect(() => defineFormula(db, { ...DAYS_OPEN, expression: '  ' })).toThrow('Formula expression is required');
+	});
+
+	it('rejects unknown datasets', () => {
+		expect(() => defineFormula(db, { ...DAYS_OPEN, dataset_id: 'missing' })).toThrow(/FOREIGN KEY/);
+	});
+});
+
+describe('deleteFormula', () => {
+	it('removes a definition by id', () => {
+		const formula = defineFormula(db, DAYS_OPEN);
+		deleteFormula(db, formula.id);
+		expect(listFormulas(db)).toEqual([]);
+	});
+
+	it('dataset-scoped formulas are removed with their dataset', () => {
+		defineFormula(db, DAYS_OPEN);
+		defineFormula(db, { ...DAYS_OPEN, dataset_id: 'ds-1' });
+
+		db.run("DELETE FROM datasets WHERE id = 'ds-1'");
+
+		expect(listFormulas(db).map((f) => f.dataset_id)).toEqual([null]);
+	});
+});
@@ -0,0 +1,108 @@
+// Isometry v5 — Geocoding Tests
+// Covers the built-in gazetteer seed, case-insensitive lookup, user overrides,
+// and filling card coordinates from location_name.
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { Database } from '../../src/database/Database';
+import { createCard, getCard } from '../../src/database/queries/cards';
+import {
+	BUILTIN_PLACES,
+	fillCoordinatesFromPlaces,
+	lookupPlace,
+	seedGeocodePlaces,
+	upsertPlace,
+} from '../../src/database/queries/geocode';
+
+let db: Database;
+
+beforeEach(async () => {
+	db = new Database();
+	await db.initialize();
+	seedGeocodePlaces(db);
+});
+
+afterEach(() => {
+	db.close();
+});
+
+describe('seedGeocodePlaces', () => {
+	it('inserts every built-in place once and is safe to re-run', () => {
+		seedGeocodePlaces(db);
+		const count = db.exec('SELECT COUNT(*) FROM geocode_places')[0]?.values[0]?.[0];
+		expect(count).toBe(BUILTIN_PLACES.length);
+	});
+
+	it('keeps user overrides when re-seeding', () => {
+		upsertPlace(db, 'Paris', 33.6609, -95.5555); // Paris, Texas
+		seedGeocodePlaces(db);
+		expect(lookupPlace(db, 'Paris')).toMatchObject({ latitude: 33.6609, source: 'user' });
+	});
+});
+
+describe('lookupPlace', () => {
+	it('matches case-insensitively after trimming', () => {
+		expect(lookupPlace(db, '  tokyo ')).toEqual({
+			name: 'Tokyo',
+			latitude: 35.6762,
+			longitude: 139.6503,
+			source: 'builtin',
+		});
+	});
+
+	it('returns null for unknown or empty names', () => {
+		expect(lookupPlace(db, 'Atlantis')).toBeNull();
+		expect(lookupPlace(db, '   ')).toBeNull();
+	});
+});
+
+describe('upsertPlace', () => {
+	it('adds a user place', () => {
+		upsertPlace(db, 'Home', 10, 20);
+		expect(lookupPlace(db, 'home')).toEqual({ name: 'Home', latitude: 10, longitude: 20, source: 'user' });
+	});
+
+	it('rejects empty names and out-of-range coordinates', () => {
+		expect(() => upsertPlace(db, ' ', 0, 0)).toThrowError(/empty/);
+		expect(() => upsertPlace(db, 'Nowhere', 91, 0)).toThrowError(/out of range/);
+		expect(() => upsertPlace(db, 'Nowhere', 0, Number.NaN)).toThrowError(/out of range/);
+	});
+});
+
+describe('fillCoordinatesFromPlaces', () => {
+	it('fills cards that only have a known place name', () => {
+		const known = createCard(db, { name: 'Trip', location_name: 'london' });
+		const unknown = createCard(db, { name: 'Dream', location_name: 'Atlantis' });
+
+		expect(fillCoordinatesFromPlaces(db)).toBe(1);
+
+		expect(getCard(db, known.id)).toMatchObject({ latitude: 51.5072, longitude: -0.1276 });
+		expect(getCard(db, unknown.id)).toMatchObject({ latitude: null, longitude: null });
+	});
+
+	it('never overwrites explicit coordinates', () => {
+		const card = createCard(db, { name: 'Office', location_name: 'London', latitude: 51.52, longitude: -0.08 });
+
+		expect(fillCoordinatesFromPlaces(db)).toBe(0);
+		expect(getCard(db, card.id)).toMatchObject({ latitude: 51.52, longitude: -0.08 });
+	});
+
+	it('can be restricted to a set of cards', () => {
+		const a = createCard(db, { name: 'A', location_name: 'Paris' });
+		const b = createCard(db, { name: 'B', location_name: 'Rome' });
+
+		expect(fillCoordinatesFromPlaces(db, [a.id])).toBe(1);
+		expect(getCard(db, b.id)!.latitude).toBeNull();
+		expect(fillCoordinatesFromPlaces(db, [])).toBe(0);
+	});
+
+	it('restricts to large id sets and bumps modified_at of filled cards only', () => {
+		const filled = createCard(db, { name: 'Trip', location_name: 'Paris' });
+		const skipped = createCard(db, { name: 'Dream', location_name: 'Atlantis' });
+		db.run("UPDATE cards SET modified_at = '2000-01-01T00:00:00.000Z'");
+		const ids = [...Array.from({ length: 5000 }, (_, i) => `missing-${i}`), filled.id, skipped.id];
+
+		expect(fillCoordinatesFromPlaces(db, ids)).toBe(1);
+		expect(getCard(db, filled.id)!.modified_at).not.toBe('2000-01-01T00:00:00.000Z');
+		expect(getCard(db, skipped.id)!.modified_at).toBe('2000-01-01T00:00:00.000Z');
+	});
+});
@@ -0,0 +1,78 @@
+// Isometry v5 — Mapping Profiles Tests
+// Covers save/upsert by name, header normalization, lookup and delete.
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { Database } from '../../src/database/Database';
+import {
+	deleteMappingProfile,
+	getMappingProfile,
+	listMappingProfiles,
+	saveMappingProfile,
+} from '../../src/database/queries/mapping-profiles';
+import type { MappingRules } from '../../src/etl/MappingProfile';
+
+let db: Database;
+
+beforeEach(async () => {
+	db = new Database();
+	await db.initialize();
+});
+
+afterEach(() => {
+	db.close();
+});
+
+const rules: MappingRules = {
+	columns: { Deal: 'name', 'Close Date': 'due_at', Id: 'source_id' },
+	dateFormat: 'MM/DD/YYYY',
+	coercions: { Amount: 'number' },
+};
+
+describe('saveMappingProfile', () => {
+	it('persists rules with normalized headers and their fingerprint', () => {
+		const saved = saveMappingProfile(db, { name: ' CRM weekly ', headers: ['Id', 'Deal', 'Close_Date'], rules });
+
+		expect(saved).toMatchObject({
+			name: 'CRM weekly',
+			headers: ['close date', 'deal', 'id'],
+			fingerprint: 'close date|deal|id',
+			rules,
+		});
+		expect(getMappingProfile(db, saved.id)).toEqual(saved);
+		expect(listMappingProfiles(db).map((p) => p.id)).toEqual([saved.id]);
+	});
+
+	it('replaces an existing profile with the same name (case-insensitive) and keeps its id', () => {
+		const first = saveMappingProfile(db, { name: 'CRM', headers: ['Deal'], rules: { columns: {} } });
+		const second = saveMappingProfile(db, { name: 'crm', headers: ['Deal', 'Id'], rules });
+
+		expect(second.id).toBe(first.id);
+		expect(second.fingerprint).toBe('deal|id');
+		expect(second.rules).toEqual(rules);
+		expect(listMappingProfiles(db)).toHaveLength(1);
+	});
+
+	it('rejects empty names and invalid rules', () => {
+		expect(() => saveMappingProfile(db, { name: '  ', headers: [], rules })).toThrow(
+			'Mapping profile name is required',
+		);
+		expect(() =>
+			saveMappingProfile(db, { name: 'Bad', headers: [], rules: { columns: { A: 'owner' as never } } }),
+		).toThrow('Unknown card field');
+		expect(listMappingProfiles(db)).toEqual([]);
+	});
+});
+
+describe('listMappingProfiles / deleteMappingProfile', () => {
+	it('orders by name and deletes by id', () => {
+		const b = saveMappingProfile(db, { name: 'beta', headers: ['b'], rules: { columns: {} } });
+		const a = saveMappingProfile(db, { name: 'Alpha', headers: ['a'], rules: { columns: {} } });
+
+		expect(listMappingProfiles(db).map((p) => p.name)).toEqual(['Alpha', 'beta']);
+
+		deleteMappingProfile(db, a.id);
+		deleteMappingProfile(db, 'missing');
+		expect(listMappingProfiles(db).map((p) => p.id)).toEqual([b.id]);
+		expect(getMappingProfile(db, a.id)).toBeNull();
+	});
+});
@@ -0,0 +1,191 @@
+// Isometry v5 — Schema Migration Runner Tests
+// Verifies versioned migrations on fresh databases and on legacy checkpoints
+// that predate datasets, graph_metrics, dataset_id and folder_l1..l4.
+
+import { afterEach, describe, expect, it } from 'vitest';
+import { Database } from '../../src/database/Database';
+import {
+	getAppliedMigrations,
+	type Migration,
+	MIGRATIONS,
+	MigrationError,
+	runMigrations,
+} from '../../src/database/migrations';
+
+let db: Database;
+
+afterEach(() => {
+	db?.close();
+});
+
+function columnNames(database: Database, table: string): string[] {
+	const result = database.exec(`PRAGMA table_info(${table})`);
+	return (result[0]?.values ?? []).map((row) => row[1] as string);
+}
+
+function tableExists(database: Database, table: string): boolean {
+	const result = database.exec("SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?", [table]);
+	return (result[0]?.values.length ?? 0) > 0;
+}
+
+/**
+ * Build a checkpoint shaped like a pre-Phase 88 database:
+ * no datasets table, no graph_metrics, no dataset_id, no folder_l1..l4.
+ */
+async function legacyCheckpoint(): Promise<ArrayBuffer> {
+	const legacy = new Database();
+	await legacy.initialize();
+	legacy.run("INSERT INTO cards (id, name, folder) VALUES ('c1', 'Legacy card', 'Work/Projects')");
+	legacy.run('DROP TABLE formulas');
+	legacy.run('DROP TABLE story_slides');
+	legacy.run('DROP TABLE stories');
+	legacy.run('DROP TABLE datasets');
+	legacy.run('DROP TRIGGER card_vectors_au');
+	legacy.run('DROP TABLE card_vectors');
+	legacy.run('DROP TABLE card_vector_terms');
+	legacy.run('DROP TABLE saved_searches');
+	legacy.run('DROP TABLE geocode_places');
+	legacy.run('DROP INDEX idx_cards_dataset_id');
+	legacy.run('ALTER TABLE cards DROP COLUMN dataset_id');
+	for (const col of ['folder_l1', 'folder_l2', 'folder_l3', 'folder_l4']) {
+		legacy.run(`ALTER TABLE cards DROP COLUMN ${col}`);
+	}
+	const bytes = legacy.export();
+	legacy.close();
+	return bytes.buffer.slice(bytes.byteOffset, bytes.byteOffset + bytes.byteLength) as ArrayBuffer;
+}
+
+describe('MIGRATIONS', () => {
+	it('has strictly increasing versions', () => {
+		for (let i = 1; i < MIGRATIONS.length; i++) {
+			expect(MIGRATIONS[i]!.version).toBeGreaterThan(MIGRATIONS[i - 1]!.version);
+		}
+	});
+
+	it('has unique names', () => {
+		const names = MIGRATIONS.map((m) => m.name);
+		expect(new Set(names).size).toBe(names.length);
+	});
+});
+
+describe('runMigrations — fresh database', () => {
+	it('applies every migration and records it in schema_migrations', async () => {
+		db = new Database();
+		await db.initialize();
+
+		const applied = runMigrations(db);
+
+		expect(applied).toEqual(MIGRATIONS.map((m) => m.version));
+		expect(getAppliedMigrations(db).map((m) => m.version)).toEqual(applied);
+		expect(tableExists(db, 'graph_metrics')).toBe(true);
+	});
+
+	it('seeds the built-in geocode gazetteer', async () => {
+		db = new Database();
+		await db.initialize();
+		runMigrations(db);
+
+		const rows = db.exec("SELECT latitude, source FROM geocode_places WHERE name = 'london'");
+		expect(rows[0]?.values[0]).toEqual([51.5072, 'builtin']);
+	});
+
+	it('is a no-op on the second run', async () => {
+		db = new Database();
+		await db.initialize();
+		runMigrations(db);
+
+		expect(runMigrations(db)).toEqual([]);
+		expect(getAppliedMigrations(db)).toHaveLength(MIGRATIONS.length);
+	});
+
+	it('getAppliedMigrations returns [] before the tracking table exists', async () => {
+		db = new Database();
+		await db.initialize();
+		expect(getAppliedMigrations(db)).toEqual([]);
+	});
+});
+
+describe('runMigrations — legacy checkpoint', () => {
+	it('adds missing tables and columns without losing data', async () => {
+		const bytes = await legacyCheckpoint();
+		db = new Database();
+		await db.initialize(undefined, bytes);
+
+		expect(columnNames(db, 'cards')).not.toContain('folder_l1');
+		expect(tableExists(db, 'datasets')).toBe(false);
+
+		runMigrations(db);
+
+		const cardCols = columnNames(db, 'cards');
+		expect(cardCols).toContain('dataset_id');
+		expect(cardCols).toEqual(expect.arrayContaining(['folder_l1', 'folder_l2', 'folder_l3', 'folder_l4']));
+		expect(columnNames(db, 'datasets')).toContain('directory_path');
+		expect(tableExists(db, 'graph_metrics')).toBe(true);
+		expect(tableExists(db, 'property_definitions')).toBe(true);
+		expect(columnNames(db, 'card_properties')).toEqual(['card_id', 'key', 'value']);
+		expect(tableExists(db, 'card_vectors')).toBe(true);
+		expect(tableExists(db, 'card_vector_terms')).toBe(true);
+		expect(tableExists(db, 'saved_searches')).toBe(true);
+		expect(tableExists(db, 'geocode_places')).toBe(true);
+		expect(tableExists(db, 'formulas')).toBe(true);
+		expect(tableExists(db, 'stories')).toBe(true);
+		expect(tableExists(db, 'story_slides')).toBe(true);
+		expect(tableExists(db, 'dataset_files')).toBe(true);
+		expect(tableExists(db, 'mapping_profiles')).toBe(true);
+		expect(tableExists(db, 'enricher_settings')).toBe(true);
+
+		const rows = db.exec("SELECT name FROM cards WHERE id = 'c1'");
+		expect(rows[0]?.values[0]?.[0]).toBe('Legacy card');
+	});
+});
+
+describe('runMigrations — failures', () => {
+	it('rolls back the failing migration and throws MigrationError', async () => {
+		db = new Database();
+		await db.initialize();
+
+		const migrations: Migration[] = [
+			{ version: 1, name: 'ok', up: (d) => d.run('CREATE TABLE m_ok (id TEXT)') },
+			{
+				version: 2,
+				name: 'broken',
+				up: (d) => {
+					d.run('CREATE TABLE m_partial (id TEXT)');
+					d.run('ALTER TABLE no_such_table ADD COLUMN x TEXT');
+				},
+			},
+			{ version: 3, name: 'never_reached', up: (d) => d.run('CREATE TABLE m_later (id TEXT)') },
+		];
+
+		let caught: unknown;
+		try {
+			runMigrations(db, migrations);
+		} catch (err) {
+			caught = err;
+		}
+
+		expect(caught).toBeInstanceOf(MigrationError);
+		expect((caught as MigrationError).version).toBe(2);
+		expect((caught as MigrationError).message).toContain('broken');
+
+		// Migration 1 committed, migration 2 rolled back, migration 3 never ran
+		expect(getAppliedMigrations(db).map((m) => m.version)).toEqual([1]);
+		expect(tableExists(db, 'm_ok')).toBe(true);
+		expect(tableExists(db, 'm_partial')).toBe(false);
+		expect(tableExists(db, 'm_later')).toBe(false);
+	});
+
+	it('rejects misordered versions before applying anything', async () => {
+		db = new Database();
+		await db.initialize();
+
+		const migrations: Migration[] = [
+			{ version: 2, name: 'second', up: (d) => d.run('CREATE TABLE m_second (id TEXT)') },
+			{ version: 1, name: 'first', up: (d) => d.run('CREATE TABLE m_first (id TEXT)') },
+		];
+
+		expect(() => runMigrations(db, migrations)).toThrow(MigrationError);
+		expect(getAppliedMigrations(db)).toEqual([]);
+		expect(tableExists(db, 'm_second')).toBe(false);
+	});
+});
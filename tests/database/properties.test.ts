@@ -0,0 +1,190 @@
+// Isometry v5 — Custom Card Properties Tests
+// Covers key normalization, type inference, value coercion, definition CRUD,
+// per-card values, and cascade behavior of card_properties.
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { Database } from '../../src/database/Database';
+import { createCard, deleteCard } from '../../src/database/queries/cards';
+import {
+	coercePropertyValue,
+	createPropertyDefinition,
+	deletePropertyDefinition,
+	extendEnumValues,
+	getCardProperties,
+	getPropertyDefinition,
+	inferPropertyType,
+	listPropertyDefinitions,
+	normalizePropertyKey,
+	setCardProperty,
+} from '../../src/database/queries/properties';
+
+let db: Database;
+
+beforeEach(async () => {
+	db = new Database();
+	await db.initialize();
+});
+
+afterEach(() => {
+	db.close();
+});
+
+// ---------------------------------------------------------------------------
+// Pure helpers
+// ---------------------------------------------------------------------------
+
+describe('normalizePropertyKey', () => {
+	it('snake_cases labels and strips punctuation', () => {
+		expect(normalizePropertyKey('Annual Revenue ($)')).toBe('annual_revenue');
+		expect(normalizePropertyKey('  Due-Date ')).toBe('due_date');
+	});
+
+	it('strips diacritics', () => {
+		expect(normalizePropertyKey('Café Région')).toBe('cafe_region');
+	});
+
+	it('prefixes keys that would start with a digit', () => {
+		expect(normalizePropertyKey('2024 Q1')).toBe('p_2024_q1');
+	});
+
+	it('returns null for labels with no usable characters', () => {
+		expect(normalizePropertyKey('***')).toBeNull();
+		expect(normalizePropertyKey('')).toBeNull();
+	});
+});
+
+describe('inferPropertyType', () => {
+	it('infers number when every sample is numeric', () => {
+		expect(inferPropertyType(['1', '2.5', 3, ''])).toEqual({ type: 'number', enum_values: null });
+	});
+
+	it('infers date for ISO strings and Date objects', () => {
+		expect(inferPropertyType(['2024-01-05', new Date('2024-02-01')]).type).toBe('date');
+	});
+
+	it('infers url for http(s) links', () => {
+		expect(inferPropertyType(['https://a.example', 'http://b.example/x']).type).toBe('url');
+	});
+
+	it('infers enum for a small set of repeated values', () => {
+		expect(inferPropertyType(['High', 'Low', 'High', 'Medium'])).toEqual({
+			type: 'enum',
+			enum_values: ['High', 'Low', 'Medium'],
+		});
+	});
+
+	it('falls back to text for unique free-form values', () => {
+		expect(inferPropertyType(['alpha', 'beta', 'gamma']).type).toBe('text');
+		expect(inferPropertyType([]).type).toBe('text');
+	});
+});
+
+describe('coercePropertyValue', () => {
+	const numberDef = { key: 'k', type: 'number', enum_values: null } as const;
+	const dateDef = { key: 'k', type: 'date', enum_values: null } as const;
+	const urlDef = { key: 'k', type: 'url', enum_values: null } as const;
+	const enumDef = { key: 'k', type: 'enum' as const, enum_values: ['a'] };
+
+	it('returns null for empty input', () => {
+		expect(coercePropertyValue(numberDef, '  ')).toBeNull();
+		expect(coercePropertyValue(numberDef, null)).toBeNull();
+	});
+
+	it('coerces numbers and dates', () => {
+		expect(coercePropertyValue(numberDef, ' 42.5 ')).toBe(42.5);
+		expect(coercePropertyValue(dateDef, '2024-03-01T00:00:00Z')).toBe('2024-03-01T00:00:00.000Z');
+	});
+
+	it('rejects values that do not fit the type', () => {
+		expect(() => coercePropertyValue(numberDef, 'abc')).toThrow(/expects a number/);
+		expect(() => coercePropertyValue(urlDef, 'not a url')).toThrow(/URL/);
+		expect(() => coercePropertyValue(enumDef, 'b')).toThrow(/does not allow/);
+	});
+});
+
+// ---------------------------------------------------------------------------
+// Definitions
+// ---------------------------------------------------------------------------
+
+describe('property definitions', () => {
+	it('creates a definition with a key derived from the label', () => {
+		const def = createPropertyDefinition(db, { label: 'Deal Size', type: 'number' });
+		expect(def.key).toBe('deal_size');
+		expect(def.type).toBe('number');
+		expect(def.enum_values).toBeNull();
+		expect(getPropertyDefinition(db, 'deal_size')).toEqual(def);
+	});
+
+	it('lists definitions ordered by label', () => {
+		createPropertyDefinition(db, { label: 'Zeta', type: 'text' });
+		createPropertyDefinition(db, { label: 'alpha', type: 'text' });
+		expect(listPropertyDefinitions(db).map((d) => d.label)).toEqual(['alpha', 'Zeta']);
+	});
+
+	it('rejects duplicate keys and invalid keys', () => {
+		createPropertyDefinition(db, { label: 'Stage', type: 'text' });
+		expect(() => createPropertyDefinition(db, { label: 'stage', type: 'text' })).toThrow(/already exists/);
+		expect(() => createPropertyDefinition(db, { key: 'Bad Key', label: 'x', type: 'text' })).toThrow(
+			/Invalid property key/,
+		);
+	});
+
+	it('extends enum values without duplicating', () => {
+		createPropertyDefinition(db, { label: 'Stage', type: 'enum', enum_values: ['Lead'] });
+		extendEnumValues(db, 'stage', ['Lead', 'Won']);
+		expect(getPropertyDefinition(db, 'stage')?.enum_values).toEqual(['Lead', 'Won']);
+	});
+});
+
+// ---------------------------------------------------------------------------
+// Values
+// ---------------------------------------------------------------------------
+
+describe('card property values', () => {
+	it('stores coerced values and clears on empty input', () => {
+		const card = createCard(db, { name: 'Acme' });
+		createPropertyDefinition(db, { label: 'Revenue', type: 'number' });
+
+		setCardProperty(db, card.id, 'revenue', '1200');
+		expect(getCardProperties(db, card.id)).toEqual({ revenue: 1200 });
+
+		setCardProperty(db, card.id, 'revenue', '');
+		expect(getCardProperties(db, card.id)).toEqual({});
+	});
+
+	it('throws for unknown properties', () => {
+		const card = createCard(db, { name: 'Acme' });
+		expect(() => setCardProperty(db, card.id, 'missing', 'x')).toThrow(/not found/);
+	});
+
+	it('deleting a definition removes its values', () => {
+		const card = createCard(db, { name: 'Acme' });
+		createPropertyDefinition(db, { label: 'Region', type: 'text' });
+		setCardProperty(db, card.id, 'region', 'EMEA');
+
+		deletePropertyDefinition(db, 'region');
+
+		const rows = db.exec('SELECT COUNT(*) FROM card_properties');
+		expect(rows[0]?.values[0]?.[0]).toBe(0);
+	});
+
+	it('hard-deleting a card removes its values', () => {
+		const card = createCard(db, { name: 'Acme' });
+		createPropertyDefinition(db, { label: 'Region', type: 'text' });
+		setCardProperty(db, card.id, 'region', 'EMEA');
+
+		db.run('DELETE FROM cards WHERE id = ?', [card.id]);
+
+		expect(getCardProperties(db, card.id)).toEqual({});
+	});
+
+	it('soft-deleting a card keeps its values', () => {
+		const card = createCard(db, { name: 'Acme' });
+		createPropertyDefinition(db, { label: 'Region', type: 'text' });
+		setCardProperty(db, card.id, 'region', 'EMEA');
+
+		deleteCard(db, card.id);
+
+		expect(getCardProperties(db, card.id)).toEqual({ region: 'EMEA' });
+	});
+});
@@ -0,0 +1,112 @@
+// Isometry v5 — Saved Searches Tests
+// Covers save/upsert by name, rename, delete, and live counts compiled from
+// serialized FilterProvider state.
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { Database } from '../../src/database/Database';
+import { createCard, deleteCard, updateCard } from '../../src/database/queries/cards';
+import {
+	countSavedSearch,
+	deleteSavedSearch,
+	listSavedSearches,
+	renameSavedSearch,
+	saveSearch,
+} from '../../src/database/queries/saved-searches';
+import { FilterProvider } from '../../src/providers/FilterProvider';
+
+let db: Database;
+
+beforeEach(async () => {
+	db = new Database();
+	await db.initialize();
+});
+
+afterEach(() => {
+	db.close();
+});
+
+/** Serialized state referencing a field outside the allowlist */
+const INVALID_STATE = '{"filters":[{"field":"prop_gone","operator":"eq","value":1}],"searchQuery":null}';
+
+function stateFor(configure: (filter: FilterProvider) => void): string {
+	const filter = new FilterProvider();
+	configure(filter);
+	return filter.toJSON();
+}
+
+describe('saveSearch', () => {
+	it('persists the FilterProvider state and lists it with a live count', () => {
+		createCard(db, { name: 'Todo A', status: 'todo' });
+		createCard(db, { name: 'Done B', status: 'done' });
+		const state = stateFor((f) => f.setAxisFilter('status', ['todo']));
+
+		const saved = saveSearch(db, 'Open tasks', state);
+
+		expect(saved.name).toBe('Open tasks');
+		expect(saved.state).toBe(state);
+		expect(saved.count).toBe(1);
+		expect(listSavedSearches(db).map((s) => s.id)).toEqual([saved.id]);
+	});
+
+	it('overwrites an existing search with the same name (case-insensitive) and keeps its id', () => {
+		const first = saveSearch(db, 'Inbox', stateFor(() => {}));
+		const second = saveSearch(db, 'inbox', stateFor((f) => f.setSearchQuery('hello')));
+
+		expect(second.id).toBe(first.id);
+		expect(JSON.parse(second.state).searchQuery).toBe('hello');
+		expect(listSavedSearches(db)).toHaveLength(1);
+	});
+
+	it('rejects empty names and invalid states', () => {
+		expect(() => saveSearch(db, '  ', stateFor(() => {}))).toThrow('name is required');
+		expect(() => saveSearch(db, 'Bad', INVALID_STATE)).toThrow('SQL safety violation');
+	});
+
+	it('lists searches ordered by name', () => {
+		saveSearch(db, 'beta', stateFor(() => {}));
+		saveSearch(db, 'Alpha', stateFor(() => {}));
+		expect(listSavedSearches(db).map((s) => s.name)).toEqual(['Alpha', 'beta']);
+	});
+});
+
+describe('countSavedSearch', () => {
+	it('reflects card changes and excludes deleted cards', () => {
+		const a = createCard(db, { name: 'Garden plan', folder: 'Home' });
+		const b = createCard(db, { name: 'Garden budget', folder: 'Home' });
+		const state = stateFor((f) => f.addFilter({ field: 'folder', operator: 'eq', value: 'Home' }));
+
+		expect(countSavedSearch(db, state)).toBe(2);
+		deleteCard(db, a.id);
+		expect(countSavedSearch(db, state)).toBe(1);
+		updateCard(db, b.id, { folder: 'Work' });
+		expect(countSavedSearch(db, state)).toBe(0);
+	});
+
+	it('includes the search query in the count', () => {
+		createCard(db, { name: 'Garden plan' });
+		createCard(db, { name: 'Budget' });
+		expect(countSavedSearch(db, stateFor((f) => f.setSearchQuery('gard')))).toBe(1);
+	});
+
+	it('returns null for states that no longer compile', () => {
+		expect(countSavedSearch(db, 'not json')).toBeNull();
+		expect(countSavedSearch(db, INVALID_STATE)).toBeNull();
+	});
+});
+
+describe('renameSavedSearch / deleteSavedSearch', () => {
+	it('renames and deletes by id', () => {
+		const saved = saveSearch(db, 'Old', stateFor(() => {}));
+		renameSavedSearch(db, saved.id, 'New');
+		expect(listSavedSearches(db).map((s) => s.name)).toEqual(['New']);
+
+		deleteSavedSearch(db, saved.id);
+		expect(listSavedSearches(db)).toEqual([]);
+	});
+
+	it('rejects renaming onto an existing name', () => {
+		saveSearch(db, 'One', stateFor(() => {}));
+		const two = saveSearch(db, 'Two', stateFor(() => {}));
+		expect(() => renameSavedSearch(db, two.id, 'one')).toThrow();
+	});
+});
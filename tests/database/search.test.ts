@@ -1,7 +1,9 @@
 import { afterEach, beforeEach, describe, expect, it } from 'vitest';
 import { Database } from '../../src/database/Database';
 import { createCard, deleteCard } from '../../src/database/queries/cards';
+import { createPropertyDefinition, setCardProperty } from '../../src/database/queries/properties';
 import { searchCards } from '../../src/database/queries/search';
+import { ALLOWED_FILTER_FIELDS, setValidColumnNames } from '../../src/providers/allowlist';
 
 // ---------------------------------------------------------------------------
 // Shared setup: fresh DB per test, seeded with 4 cards
@@ -257,3 +259,60 @@ describe('FTS5 query syntax', () => {
 		expect(ids).toContain(cardCId);
 	});
 });
+
+// ---------------------------------------------------------------------------
+// Search query language (parseSearchQuery → FTS5 + field clauses)
+// ---------------------------------------------------------------------------
+
+describe('Search query language', () => {
+	it('name:knowledge scopes the term to the name column', () => {
+		const ids = searchCards(db, 'name:knowledge').map((r) => r.card.id);
+		expect(ids).toEqual([cardAId]);
+	});
+
+	it('-term excludes matches', () => {
+		const ids = searchCards(db, 'knowledge -project').map((r) => r.card.id);
+		expect(ids).toContain(cardAId);
+		expect(ids).not.toContain(cardBId);
+	});
+
+	it('combines full-text terms with field clauses', () => {
+		db.run("UPDATE cards SET status = 'done' WHERE id = ?", [cardBId]);
+		const ids = searchCards(db, 'knowledge status:done').map((r) => r.card.id);
+		expect(ids).toEqual([cardBId]);
+	});
+
+	it('field clauses alone return matching cards without FTS ranking', () => {
+		db.run("UPDATE cards SET status = 'done' WHERE id IN (?, ?)", [cardAId, cardDId]);
+		const results = searchCards(db, 'status:done');
+		expect(results.map((r) => r.card.id)).toEqual([cardAId]);
+		expect(results[0]!.snippet).toBe('');
+	});
+
+	it('matches text property and leading-zero values as strings', () => {
+		setValidColumnNames(new Set([...ALLOWED_FILTER_FIELDS, 'prop_code']));
+		try {
+			createPropertyDefinition(db, { key: 'code', label: 'Code', type: 'text' });
+			setCardProperty(db, cardAId, 'code', '2024');
+			setCardProperty(db, cardBId, 'code', '02139');
+			db.run("UPDATE cards SET folder = '001' WHERE id = ?", [cardCId]);
+
+			expect(searchCards(db, 'prop_code:2024').map((r) => r.card.id)).toEqual([cardAId]);
+			expect(searchCards(db, 'prop_code:02139').map((r) => r.card.id)).toEqual([cardBId]);
+			expect(searchCards(db, 'folder:001').map((r) => r.card.id)).toEqual([cardCId]);
+		} finally {
+			setValidColumnNames(null);
+		}
+	});
+
+	it('NEAR matches terms within the given distance', () => {
+		const ids = searchCards(db, 'NEAR(cooking recipes, 2)').map((r) => r.card.id);
+		expect(ids).toEqual([cardCId]);
+	});
+
+	it('malformed syntax never throws', () => {
+		expect(() => searchCards(db, 'knowledge "unclosed')).not.toThrow();
+		expect(() => searchCards(db, 'OR AND NOT')).not.toThrow();
+		expect(() => searchCards(db, 'bogus:field ^*(')).not.toThrow();
+	});
+});
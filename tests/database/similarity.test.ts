@@ -0,0 +1,176 @@
+// Isometry v5 — Similar Card Search Tests
+// Covers tokenization, hashed vectors, lazy indexing/invalidation, the
+// inverted bucket index and TF-IDF cosine ranking in findSimilarCards().
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { Database } from '../../src/database/Database';
+import { createCard, deleteCard, updateCard } from '../../src/database/queries/cards';
+import {
+	computeTermVector,
+	findSimilarCards,
+	hashTerm,
+	indexCardVectors,
+	tokenizeForSimilarity,
+	VECTOR_DIMENSIONS,
+} from '../../src/database/queries/similarity';
+
+let db: Database;
+
+beforeEach(async () => {
+	db = new Database();
+	await db.initialize();
+});
+
+afterEach(() => {
+	db.close();
+});
+
+function vectorCount(): number {
+	const result = db.exec('SELECT COUNT(*) FROM card_vectors');
+	return result[0]?.values[0]?.[0] as number;
+}
+
+function postingCount(cardId: string): number {
+	const result = db.exec('SELECT COUNT(*) FROM card_vector_terms WHERE card_id = ?', [cardId]);
+	return result[0]?.values[0]?.[0] as number;
+}
+
+// ---------------------------------------------------------------------------
+// Pure helpers
+// ---------------------------------------------------------------------------
+
+describe('tokenizeForSimilarity', () => {
+	it('lowercases, strips diacritics, stopwords, numbers and plural s', () => {
+		expect(tokenizeForSimilarity('The Café Recipes of 2024, and a Glass')).toEqual(['cafe', 'recipe', 'glass']);
+	});
+});
+
+describe('hashTerm / computeTermVector', () => {
+	it('hashes deterministically into the vector dimensions', () => {
+		expect(hashTerm('garden')).toBe(hashTerm('garden'));
+		expect(hashTerm('garden')).toBeGreaterThanOrEqual(0);
+		expect(hashTerm('garden')).toBeLessThan(VECTOR_DIMENSIONS);
+	});
+
+	it('boosts name and tag terms over content terms', () => {
+		const vector = new Map(computeTermVector({ name: 'garden', content: 'compost', tags: ['soil'] }));
+		expect(vector.get(hashTerm('garden'))).toBe(2);
+		expect(vector.get(hashTerm('soil'))).toBe(2);
+		expect(vector.get(hashTerm('compost'))).toBe(1);
+	});
+});
+
+// ---------------------------------------------------------------------------
+// Index maintenance
+// ---------------------------------------------------------------------------
+
+describe('indexCardVectors', () => {
+	it('vectorizes only cards without a vector', () => {
+		createCard(db, { name: 'One' });
+		createCard(db, { name: 'Two' });
+		expect(indexCardVectors(db)).toBe(2);
+		expect(indexCardVectors(db)).toBe(0);
+		expect(vectorCount()).toBe(2);
+	});
+
+	it('drops a vector when the card text changes', () => {
+		const card = createCard(db, { name: 'Garden plan' });
+		indexCardVectors(db);
+		updateCard(db, card.id, { status: 'done' });
+		expect(vectorCount()).toBe(1);
+		updateCard(db, card.id, { content: 'Now with compost' });
+		expect(vectorCount()).toBe(0);
+		expect(indexCardVectors(db)).toBe(1);
+	});
+
+	it('writes one posting per bucket and drops them with the vector', () => {
+		const card = createCard(db, { name: 'Garden compost', tags: ['soil'] });
+		indexCardVectors(db);
+		const result = db.exec('SELECT bucket, tf FROM card_vector_terms WHERE card_id = ? ORDER BY bucket', [card.id]);
+		expect(result[0]?.values).toEqual(computeTermVector({ name: 'Garden compost', content: null, tags: ['soil'] }));
+
+		updateCard(db, card.id, { name: 'Garden' });
+		expect(postingCount(card.id)).toBe(0);
+		indexCardVectors(db);
+		expect(postingCount(card.id)).toBe(1);
+	});
+});
+
+// ---------------------------------------------------------------------------
+// findSimilarCards
+// ---------------------------------------------------------------------------
+
+describe('findSimilarCards', () => {
+	it('ranks cards sharing distinctive terms first and excludes the card itself', () => {
+		const target = createCard(db, {
+			name: 'Vegetable garden layout',
+			content: 'Raised beds for tomatoes and compost bins',
+		});
+		const close = createCard(db, { name: 'Garden compost guide', content: 'Compost bins next to raised beds' });
+		const loose = createCard(db, { name: 'Tomato sauce recipe', content: 'Simmer tomatoes from the garden' });
+		createCard(db, { name: 'Quarterly budget', content: 'Revenue and expenses forecast' });
+
+		const results = findSimilarCards(db, target.id);
+		const ids = results.map((r) => r.card.id);
+
+		expect(ids[0]).toBe(close.id);
+		expect(ids).toContain(loose.id);
+		expect(ids).not.toContain(target.id);
+		expect(results).toHaveLength(2);
+		expect(results[0]!.score).toBeGreaterThan(results[1]!.score);
+		expect(results[0]!.score).toBeLessThanOrEqual(1);
+	});
+
+	it('excludes soft-deleted cards', () => {
+		const target = createCard(db, { name: 'Garden compost' });
+		const deleted = createCard(db, { name: 'Garden compost notes' });
+		deleteCard(db, deleted.id);
+		expect(findSimilarCards(db, target.id)).toEqual([]);
+	});
+
+	it('reflects edits made after the card was indexed', () => {
+		const target = createCard(db, { name: 'Garden compost' });
+		const other = createCard(db, { name: 'Quarterly budget' });
+		expect(findSimilarCards(db, target.id)).toEqual([]);
+
+		updateCard(db, other.id, { name: 'Compost budget' });
+		expect(findSimilarCards(db, target.id).map((r) => r.card.id)).toEqual([other.id]);
+	});
+
+	it('scores cards through shared buckets only, with IDF over the whole corpus', () => {
+		const target = createCard(db, { name: 'Garden compost' });
+		const match = createCard(db, { name: 'Compost heap' });
+		createCard(db, { name: 'Garden tools' });
+		createCard(db, { name: 'Garden shed' });
+		createCard(db, { name: 'Quarterly budget' });
+
+		const results = findSimilarCards(db, target.id);
+		expect(results).toHaveLength(3);
+		expect(results[0]!.card.id).toBe(match.id);
+
+		// 5 vectors: 'garden' in 3, 'compost' in 2, 'heap' in 1 (name terms share one tf weight)
+		const idf = (df: number) => Math.log(6 / (df + 1)) + 1;
+		const expected = idf(2) ** 2 / (Math.hypot(idf(3), idf(2)) * Math.hypot(idf(2), idf(1)));
+		expect(results[0]!.score).toBeCloseTo(expected, 10);
+	});
+
+	it('respects the limit', () => {
+		const target = createCard(db, { name: 'Garden compost' });
+		for (let i = 0; i < 5; i++) createCard(db, { name: `Garden note ${i}` });
+		expect(findSimilarCards(db, target.id, 3)).toHaveLength(3);
+	});
+
+	it('returns [] for unknown or text-less cards', () => {
+		expect(findSimilarCards(db, 'missing')).toEqual([]);
+		const empty = createCard(db, { name: 'the of and' });
+		createCard(db, { name: 'Garden' });
+		expect(findSimilarCards(db, empty.id)).toEqual([]);
+	});
+
+	it('removes vectors of hard-deleted cards via cascade', () => {
+		const card = createCard(db, { name: 'Garden' });
+		indexCardVectors(db);
+		db.run('DELETE FROM cards WHERE id = ?', [card.id]);
+		expect(vectorCount()).toBe(0);
+	});
+});
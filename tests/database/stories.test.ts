@@ -0,0 +1,136 @@
+// Isometry v5 — Stories Tests
+// Covers create/replace of stories with ordered slides, validation, cascade
+// delete, and live slide cards compiled from the stored filter state.
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { Database } from '../../src/database/Database';
+import { createCard } from '../../src/database/queries/cards';
+import {
+	deleteStory,
+	getStory,
+	listStories,
+	type StorySlideState,
+	saveStory,
+	slideCards,
+} from '../../src/database/queries/stories';
+
+let db: Database;
+
+beforeEach(async () => {
+	db = new Database();
+	await db.initialize();
+});
+
+afterEach(() => {
+	db.close();
+});
+
+function slideState(overrides: Partial<StorySlideState> = {}): StorySlideState {
+	return {
+		view: 'grid',
+		pafv: { viewType: 'grid', xAxis: null, yAxis: null, groupBy: null, colAxes: [], rowAxes: [] },
+		filter: { filters: [], searchQuery: null },
+		density: { timeField: 'created_at', granularity: 'month' },
+		selection: [],
+		...overrides,
+	};
+}
+
+describe('saveStory', () => {
+	it('creates a story with slides in order', () => {
+		const story = saveStory(db, {
+			name: ' Q3 findings ',
+			slides: [
+				{ caption: '# Overview', state: slideState() },
+				{ caption: 'Open tasks', state: slideState({ view: 'kanban' }) },
+			],
+		});
+
+		expect(story.name).toBe('Q3 findings');
+		expect(story.slides.map((s) => [s.caption, s.state.view])).toEqual([
+			['# Overview', 'grid'],
+			['Open tasks', 'kanban'],
+		]);
+		expect(listStories(db)).toEqual([story]);
+	});
+
+	it('replaces the name and slide list of an existing story, keeping given slide ids', () => {
+		const story = saveStory(db, {
+			name: 'Draft',
+			slides: [
+				{ caption: 'one', state: slideState() },
+				{ caption: 'two', state: slideState() },
+			],
+		});
+		const [first, second] = story.slides;
+
+		const updated = saveStory(db, {
+			id: story.id,
+			name: 'Final',
+			slides: [
+				{ ...second!, caption: 'two (edited)' },
+				{ ...first! },
+				{ caption: 'three', state: slideState({ view: 'map' }) },
+			],
+		});
+
+		expect(updated.id).toBe(story.id);
+		expect(updated.name).toBe('Final');
+		expect(updated.slides.map((s) => s.caption)).toEqual(['two (edited)', 'one', 'three']);
+		expect(updated.slides[0]!.id).toBe(second!.id);
+		expect(updated.slides[1]!.id).toBe(first!.id);
+	});
+
+	it('rejects empty names, malformed slide states and unknown ids without partial writes', () => {
+		expect(() => saveStory(db, { name: '  ', slides: [] })).toThrow('Story name is required');
+		expect(() =>
+			saveStory(db, { name: 'Bad', slides: [{ caption: '', state: { view: 'grid' } as unknown as StorySlideState }] }),
+		).toThrow('Invalid state for story slide 1');
+		expect(() => saveStory(db, { id: 'missing', name: 'Ghost', slides: [] })).toThrow('Story not found: missing');
+		expect(listStories(db)).toEqual([]);
+	});
+});
+
+describe('deleteStory', () => {
+	it('removes the story and its slides', () => {
+		const story = saveStory(db, { name: 'Gone', slides: [{ caption: '', state: slideState() }] });
+
+		deleteStory(db, story.id);
+
+		expect(getStory(db, story.id)).toBeNull();
+		expect(db.exec('SELECT COUNT(*) FROM story_slides')[0]?.values[0]?.[0]).toBe(0);
+	});
+});
+
+describe('slideCards', () => {
+	it('counts and lists cards matching the slide filter, plus live selected cards', () => {
+		const alpha = createCard(db, { name: 'Alpha', folder: 'Work' });
+		createCard(db, { name: 'Beta', folder: 'Home' });
+		const gamma = createCard(db, { name: 'Gamma', folder: 'Work' });
+
+		const cards = slideCards(
+			db,
+			slideState({
+				filter: { filters: [{ field: 'folder', operator: 'eq', value: 'Work' }], searchQuery: null },
+				selection: [gamma.id, 'deleted-card'],
+			}),
+			1,
+		);
+
+		expect(cards).toEqual({
+			count: 2,
+			cards: [{ id: alpha.id, name: 'Alpha' }],
+			selected: [{ id: gamma.id, name: 'Gamma' }],
+		});
+	});
+
+	it('reports a null count when the stored filter no longer compiles', () => {
+		const cards = slideCards(
+			db,
+			slideState({ filter: { filters: [{ field: 'prop_gone', operator: 'eq', value: 1 }], searchQuery: null } }),
+			10,
+		);
+
+		expect(cards).toEqual({ count: null, cards: [], selected: [] });
+	});
+});
@@ -5,6 +5,7 @@ import { afterEach, beforeEach, describe, expect, it } from 'vitest';
 import { Database } from '../../src/database/Database';
 import type { Card } from '../../src/database/queries/types';
 import { ExportOrchestrator } from '../../src/etl/ExportOrchestrator';
+import { ImportOrchestrator } from '../../src/etl/ImportOrchestrator';
 
 describe('ExportOrchestrator', () => {
 	let db: Database;
@@ -212,4 +213,39 @@ describe('ExportOrchestrator', () => {
 		// Markdown should include wikilinks
 		expect(mdResult.data).toContain('[[Card 2]]');
 	});
+
+	it('dispatches to OPMLExporter for format=opml, nesting imported outlines', async () => {
+		const opml = `<opml version="2.0"><head><title>Plan</title></head><body>
+			<outline text="Research"><outline text="Interviews"/><outline text="Surveys"/></outline>
+			<outline text="Ship"/>
+		</body></opml>`;
+		await new ImportOrchestrator(db).import('opml', opml, { filename: 'plan.opml' });
+
+		const result = orchestrator.export('opml');
+
+		expect(result.filename).toMatch(/^isometry-export-.*\.opml$/);
+		expect(result.cardCount).toBe(4);
+		const outlines = result.data.split('\n').filter((line) => line.includes('outline'));
+		expect(outlines.map((line) => line.replace(/ created="[^"]*"/, ''))).toEqual([
+			'    <outline text="Research">',
+			'      <outline text="Interviews"/>',
+			'      <outline text="Surveys"/>',
+			'    </outline>',
+			'    <outline text="Ship"/>',
+		]);
+	});
+
+	it('dispatches to ICSExporter for format=ics, counting only calendar cards', () => {
+		insertCard({ id: 'card-001', name: 'Plain note' });
+		insertCard({ id: 'card-002', card_type: 'task', name: 'Call back' });
+		db.run("UPDATE cards SET due_at = '2026-01-10T17:00:00Z' WHERE id = 'card-002'");
+
+		const result = orchestrator.export('ics');
+
+		expect(result.filename).toMatch(/^isometry-export-.*\.ics$/);
+		expect(result.cardCount).toBe(1);
+		expect(result.data).toContain('SUMMARY:Call back');
+		expect(result.data).toContain('DUE:20260110T170000Z');
+		expect(result.data).not.toContain('Plain note');
+	});
 });
@@ -0,0 +1,55 @@
+// Isometry v5 — File Manifest Tests
+// Re-scan classification: unchanged, modified, added, removed and renamed files.
+
+import { describe, expect, it } from 'vitest';
+import { diffManifest, type ManifestEntry, relativeFilePath } from '../../src/etl/FileManifest';
+
+const entry = (path: string, hash: string, mtime = '2026-01-01T00:00:00Z'): ManifestEntry => ({
+	path,
+	size: 10,
+	mtime,
+	hash,
+});
+
+describe('diffManifest', () => {
+	it('classifies paths by presence and content hash', () => {
+		const previous = [entry('/d/a.md', 'h1'), entry('/d/b.md', 'h2'), entry('/d/c.md', 'h3')];
+		const current = [entry('/d/a.md', 'h1', '2026-02-01T00:00:00Z'), entry('/d/b.md', 'h2b'), entry('/d/new.md', 'h4')];
+
+		expect(diffManifest(previous, current)).toEqual({
+			added: ['/d/new.md'],
+			modified: ['/d/b.md'],
+			removed: ['/d/c.md'],
+			renamed: [],
+			unchanged: ['/d/a.md'],
+		});
+	});
+
+	it('pairs a removed and an added path with the same hash as a rename', () => {
+		const previous = [entry('/d/old.md', 'h1'), entry('/d/x.md', 'same'), entry('/d/y.md', 'same')];
+		const current = [entry('/d/sub/old.md', 'h1'), entry('/d/z.md', 'same')];
+
+		const diff = diffManifest(previous, current);
+
+		expect(diff.renamed).toEqual([
+			{ from: '/d/old.md', to: '/d/sub/old.md' },
+			{ from: '/d/x.md', to: '/d/z.md' },
+		]);
+		// Each removed file is paired at most once
+		expect(diff.removed).toEqual(['/d/y.md']);
+		expect(diff.added).toEqual([]);
+	});
+
+	it('treats every file as added without a stored manifest', () => {
+		expect(diffManifest([], [entry('/d/a.md', 'h1')]).added).toEqual(['/d/a.md']);
+	});
+});
+
+describe('relativeFilePath', () => {
+	it('strips the dataset directory prefix', () => {
+		expect(relativeFilePath('/vault/notes/Work/a.md', '/vault/notes')).toBe('Work/a.md');
+		expect(relativeFilePath('/vault/notes/a.md', '/vault/notes/')).toBe('a.md');
+		expect(relativeFilePath('/elsewhere/a.md', '/vault/notes')).toBe('/elsewhere/a.md');
+		expect(relativeFilePath('/vault/notes/a.md', null)).toBe('/vault/notes/a.md');
+	});
+});
@@ -1,9 +1,13 @@
 // Isometry v5 — Phase 8 ImportOrchestrator Tests
 // Integration tests with real database
 
+import { readFileSync } from 'node:fs';
+import { join } from 'node:path';
 import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import * as XLSX from 'xlsx';
 import { Database } from '../../src/database/Database';
 import { ImportOrchestrator } from '../../src/etl/ImportOrchestrator';
+import type { MappingProfile } from '../../src/etl/MappingProfile';
 import type { ParsedFile } from '../../src/etl/parsers/AppleNotesParser';
 import { SQLiteWriter } from '../../src/etl/SQLiteWriter';
 
@@ -595,6 +599,308 @@ Some content here.
 		});
 	});
 
+	describe('obsidian vault import', () => {
+		const modified = '2026-01-02T00:00:00Z';
+		const vault = JSON.stringify([
+			{ path: 'Home.md', content: 'See [[Projects/Launch|launch]] and ![[diagram.png]]', modified },
+			{ path: 'Projects/Launch.md', content: '---\naliases: [Go-live]\n---\nBack to [[Home]]', modified },
+			{ path: 'attachments/diagram.png', content: '', modified },
+		]);
+
+		it('writes notes, media cards and typed link/embed connections', async () => {
+			const result = await orchestrator.import('obsidian', vault, { filename: 'My Vault' });
+
+			expect(result.inserted).toBe(3);
+			expect(result.connections_created).toBe(3);
+
+			const rows = db.exec(
+				`SELECT s.name, t.name, c.label FROM connections c
+				 JOIN cards s ON s.id = c.source_id JOIN cards t ON t.id = c.target_id
+				 ORDER BY s.name, t.name`,
+			)[0]?.values;
+			expect(rows).toEqual([
+				['Home', 'Launch', 'links_to'],
+				['Home', 'diagram.png', 'embeds'],
+				['Launch', 'Home', 'links_to'],
+			]);
+			const media = db.exec("SELECT card_type, source FROM cards WHERE name = 'diagram.png'")[0]?.values;
+			expect(media).toEqual([['media', 'obsidian']]);
+		});
+
+		it('leaves untouched files unchanged on re-import', async () => {
+			await orchestrator.import('obsidian', vault);
+			const second = await orchestrator.import('obsidian', vault);
+
+			expect(second).toMatchObject({ inserted: 0, updated: 0, unchanged: 3, connections_created: 0 });
+		});
+	});
+
+	describe('ics import', () => {
+		const ics = (summary: string, modified: string) =>
+			[
+				'BEGIN:VCALENDAR',
+				'BEGIN:VEVENT',
+				'UID:weekly@example.com',
+				`LAST-MODIFIED:${modified}`,
+				'DTSTART:20260105T090000Z',
+				'RRULE:FREQ=WEEKLY;COUNT=3',
+				`SUMMARY:${summary}`,
+				'END:VEVENT',
+				'END:VCALENDAR',
+			].join('\r\n');
+		const window = { windowStart: '2026-01-01T00:00:00Z', windowEnd: '2026-12-31T00:00:00Z' };
+
+		it('writes one event card per occurrence and updates them in place', async () => {
+			const first = await orchestrator.import('ics', ics('Sync', '20260101T000000Z'), window);
+			const second = await orchestrator.import('ics', ics('Weekly sync', '20260102T000000Z'), window);
+
+			expect(first.inserted).toBe(3);
+			expect(second).toMatchObject({ inserted: 0, updated: 3 });
+			const names = db.exec("SELECT DISTINCT name, card_type FROM cards WHERE source = 'ics'")[0]?.values;
+			expect(names).toEqual([['Weekly sync', 'event']]);
+		});
+	});
+
+	describe('vcard import', () => {
+		const vcf = (rev: string) =>
+			[
+				'BEGIN:VCARD',
+				'UID:ada',
+				'FN:Ada Lovelace',
+				'ORG:Engines Ltd',
+				'EMAIL:ada@engines.example',
+				`REV:${rev}`,
+				'END:VCARD',
+				'BEGIN:VCARD',
+				'UID:charles',
+				'FN:Charles Babbage',
+				'ORG:Engines Ltd',
+				'RELATED:urn:uuid:ada',
+				`REV:${rev}`,
+				'END:VCARD',
+			].join('\n');
+
+		it('writes people, a shared company card and relationship edges', async () => {
+			const result = await orchestrator.import('vcard', vcf('20260101T000000Z'));
+
+			expect(result.inserted).toBe(3);
+			expect(result.connections_created).toBe(3);
+			const rows = db.exec(
+				`SELECT s.name, t.name, c.label FROM connections c
+				 JOIN cards s ON s.id = c.source_id JOIN cards t ON t.id = c.target_id
+				 ORDER BY s.name, c.label`,
+			)[0]?.values;
+			expect(rows).toEqual([
+				['Ada Lovelace', 'Engines Ltd', 'works_at'],
+				['Charles Babbage', 'Ada Lovelace', 'related'],
+				['Charles Babbage', 'Engines Ltd', 'works_at'],
+			]);
+			const email = db.exec(
+				"SELECT p.value FROM card_properties p JOIN cards c ON c.id = p.card_id WHERE c.name = 'Ada Lovelace'",
+			)[0]?.values;
+			expect(email).toEqual([['ada@engines.example']]);
+		});
+
+		it('updates contacts in place by UID on re-import', async () => {
+			await orchestrator.import('vcard', vcf('20260101T000000Z'));
+			const second = await orchestrator.import('vcard', vcf('20260102T000000Z'));
+
+			// The company card follows its employees' revision time
+			expect(second).toMatchObject({ inserted: 0, updated: 3, connections_created: 0 });
+		});
+	});
+
+	describe('email import', () => {
+		const message = (id: string, headers: string[]) =>
+			[`Message-ID: <${id}>`, 'From: Ada <ada@engines.example>', ...headers, '', `Body of ${id}`].join('\n');
+
+		it('threads replies to parents imported earlier', async () => {
+			await orchestrator.import('email', message('m1@x', ['Subject: Plan', 'Date: Mon, 5 Jan 2026 09:00:00 +0000']));
+			const reply = await orchestrator.import(
+				'email',
+				message('m2@x', ['Subject: Re: Plan', 'In-Reply-To: <m1@x>', 'Date: Tue, 6 Jan 2026 09:00:00 +0000']),
+			);
+
+			expect(reply.inserted).toBe(2);
+			const rows = db.exec(
+				`SELECT s.name, c.label, t.name FROM connections c
+				 JOIN cards s ON s.id = c.source_id JOIN cards t ON t.id = c.target_id
+				 WHERE c.label = 'reply_to'`,
+			)[0]?.values;
+			expect(rows).toEqual([['Re: Plan', 'reply_to', 'Plan']]);
+		});
+	});
+
+	describe('bookmarks import', () => {
+		const bookmarks = (title: string, modified: string) =>
+			[
+				'<!DOCTYPE NETSCAPE-Bookmark-file-1>',
+				'<DL><p>',
+				'<DT><H3>Work</H3>',
+				'<DL><p>',
+				'<DT><H3>Research</H3>',
+				'<DL><p>',
+				`<DT><A HREF="https://example.com/paper" ADD_DATE="1767225600" LAST_MODIFIED="${modified}">${title}</A>`,
+				'</DL><p>',
+				'</DL><p>',
+				'</DL><p>',
+			].join('\n');
+
+		it('routes .html bookmark files to resource cards with folder levels', async () => {
+			const result = await orchestrator.import('html', bookmarks('Paper', '1767225600'), {
+				filename: 'bookmarks.html',
+			});
+
+			expect(result.inserted).toBe(1);
+			const rows = db.exec('SELECT name, card_type, source, url, folder_l1, folder_l2 FROM cards')[0]?.values;
+			expect(rows).toEqual([['Paper', 'resource', 'bookmarks', 'https://example.com/paper', 'Work', 'Research']]);
+			const sourceName = db.exec('SELECT name FROM import_sources')[0]?.values;
+			expect(sourceName).toEqual([['Browser Bookmarks - bookmarks.html']]);
+		});
+
+		it('updates bookmarks in place by URL on re-import', async () => {
+			await orchestrator.import('html', bookmarks('Paper', '1767225600'));
+			const second = await orchestrator.import('html', bookmarks('Paper (v2)', '1767312000'));
+
+			expect(second).toMatchObject({ inserted: 0, updated: 1 });
+		});
+	});
+
+	describe('mapping profile import', () => {
+		const profile: MappingProfile = {
+			id: 'crm',
+			name: 'CRM weekly',
+			headers: ['deal', 'id', 'stage', 'updated'],
+			fingerprint: 'deal|id|stage|updated',
+			rules: { columns: { Id: 'source_id', Deal: 'name', Stage: 'status', Updated: 'modified_at' } },
+			created_at: '2026-01-01T00:00:00Z',
+			updated_at: '2026-01-01T00:00:00Z',
+		};
+		const csv = (stage: string, updated: string) => `Id,Deal,Stage,Updated\n7,Acme renewal,${stage},${updated}\n`;
+
+		it('imports raw CSV text and updates keyed rows in place from a differently named export', async () => {
+			const first = await orchestrator.import('csv', csv('Open', '2026-01-05'), { filename: 'week-01.csv', profile });
+			const second = await orchestrator.import('csv', csv('Won', '2026-01-12'), { filename: 'week-02.csv', profile });
+
+			expect(first.inserted).toBe(1);
+			expect(second).toMatchObject({ inserted: 0, updated: 1 });
+			const rows = db.exec('SELECT name, status, source_id FROM cards')[0]?.values;
+			expect(rows).toEqual([['Acme renewal', 'Won', 'crm:7']]);
+		});
+
+		it('reads headers of tabular sources only', async () => {
+			expect(await orchestrator.readHeaders('csv', 'Id,Deal\n1,A', 'a.csv')).toEqual(['Id', 'Deal']);
+			expect(await orchestrator.readHeaders('json', '[{"a":1}]')).toEqual(['a']);
+			expect(await orchestrator.readHeaders('markdown', '[]')).toEqual([]);
+		});
+	});
+
+	describe('relationship import', () => {
+		const employeesCsv = readFileSync(join(__dirname, 'fixtures/northwind-employees.csv'), 'utf-8');
+		const mappingProfile = (id: string, rules: MappingProfile['rules']): MappingProfile => ({
+			id,
+			name: id,
+			headers: [],
+			fingerprint: '',
+			rules,
+			created_at: '2026-01-01T00:00:00Z',
+			updated_at: '2026-01-01T00:00:00Z',
+		});
+		const employees = mappingProfile('employees', {
+			columns: { employee_id: 'source_id', last_name: 'name', title: 'status' },
+			defaultCardType: 'person',
+			relationships: [{ column: 'manager_id', references: 'employee_id', label: 'reports_to' }],
+		});
+		const edges = (label: string) =>
+			db.exec(
+				`SELECT s.name, t.name FROM connections c
+				 JOIN cards s ON s.id = c.source_id
+				 JOIN cards t ON t.id = c.target_id
+				 WHERE c.label = ? ORDER BY s.name`,
+				[label],
+			)[0]?.values;
+
+		it('turns a manager_id column into reports_to connections without duplicating them on re-import', async () => {
+			const first = await orchestrator.import('csv', employeesCsv, { filename: 'employees.csv', profile: employees });
+			const second = await orchestrator.import('csv', employeesCsv, { filename: 'employees.csv', profile: employees });
+
+			expect(first).toMatchObject({ inserted: 9, connections_created: 8 });
+			expect(second.connections_created).toBe(0);
+			expect(edges('reports_to')).toEqual([
+				['Buchanan', 'Fuller'],
+				['Callahan', 'Fuller'],
+				['Davolio', 'Fuller'],
+				['Dodsworth', 'Buchanan'],
+				['King', 'Buchanan'],
+				['Leverling', 'Fuller'],
+				['Peacock', 'Fuller'],
+				['Suyama', 'Buchanan'],
+			]);
+		});
+
+		it('links rows to cards of an earlier import through the target profile', async () => {
+			const orders = mappingProfile('orders', {
+				columns: { order_id: 'source_id', customer: 'name' },
+				relationships: [
+					{ column: 'employee_id', references: 'employee_id', label: 'sold_by', targetProfileId: 'employees' },
+				],
+			});
+
+			await orchestrator.import('csv', employeesCsv, { filename: 'employees.csv', profile: employees });
+			const ordersCsv = 'order_id,customer,employee_id\n10248,VINET,5\n10249,TOMSP,42\n';
+			const result = await orchestrator.import('csv', ordersCsv, { filename: 'orders.csv', profile: orders });
+
+			// Employee 42 does not exist, so only one order is linked
+			expect(result.connections_created).toBe(1);
+			expect(edges('sold_by')).toEqual([['VINET', 'Buchanan']]);
+		});
+	});
+
+	describe('multi-sheet excel import', () => {
+		const workbook = (): ArrayBuffer => {
+			const book = XLSX.utils.book_new();
+			const customers = [
+				['CustomerID', 'CompanyName'],
+				['ALFKI', 'Alfreds Futterkiste'],
+			];
+			const orders = [
+				['OrderID', 'CustomerID'],
+				[10643, 'ALFKI'],
+				[10692, 'ALFKI'],
+			];
+			XLSX.utils.book_append_sheet(book, XLSX.utils.aoa_to_sheet(customers), 'Customers');
+			XLSX.utils.book_append_sheet(book, XLSX.utils.aoa_to_sheet(orders), 'Orders');
+			const buffer = XLSX.write(book, { type: 'buffer', bookType: 'xlsx' });
+			return buffer.buffer.slice(buffer.byteOffset, buffer.byteOffset + buffer.byteLength);
+		};
+		const sheets = ['Customers', 'Orders'];
+
+		it('imports the selected sheets as one dataset linked across sheets, updating in place on re-import', async () => {
+			const first = await orchestrator.import('excel', workbook(), { filename: 'northwind.xlsx', sheets });
+			const second = await orchestrator.import('excel', workbook(), { filename: 'northwind.xlsx', sheets });
+
+			expect(first).toMatchObject({ inserted: 3, connections_created: 2 });
+			expect(second).toMatchObject({ inserted: 0, connections_created: 0 });
+			const rows = db.exec(
+				`SELECT s.folder, s.name, c.label, t.name FROM connections c
+				 JOIN cards s ON s.id = c.source_id
+				 JOIN cards t ON t.id = c.target_id
+				 ORDER BY s.name`,
+			)[0]?.values;
+			expect(rows).toEqual([
+				['Orders', 'Orders 10643', 'customer', 'Alfreds Futterkiste'],
+				['Orders', 'Orders 10692', 'customer', 'Alfreds Futterkiste'],
+			]);
+		});
+
+		it('lists workbook sheets with row counts', async () => {
+			expect(await orchestrator.listExcelSheets(workbook())).toEqual([
+				{ name: 'Customers', rows: 1, headers: ['CustomerID', 'CompanyName'] },
+				{ name: 'Orders', rows: 2, headers: ['OrderID', 'CustomerID'] },
+			]);
+		});
+	});
+
 	describe('optimizeFTS for incremental imports', () => {
 		it('calls optimizeFTS after incremental import with >100 inserts', async () => {
 			// Create 150 unique notes (above 100 threshold)
@@ -0,0 +1,235 @@
+// Isometry v5 — Mapping Profile Tests
+// Header fingerprints, profile suggestion, date patterns and row mapping.
+
+import { describe, expect, it } from 'vitest';
+import {
+	extractRelationships,
+	headerFingerprint,
+	type MappingProfile,
+	type MappingRules,
+	mapRowWithProfile,
+	normalizeHeaders,
+	parseDateValue,
+	suggestMappingProfile,
+	validateMappingRules,
+} from '../../src/etl/MappingProfile';
+import type { CanonicalCard } from '../../src/etl/types';
+
+function profile(name: string, headers: string[], rules: MappingRules = { columns: {} }): MappingProfile {
+	return {
+		id: `p-${name}`,
+		name,
+		headers: normalizeHeaders(headers),
+		fingerprint: headerFingerprint(headers),
+		rules,
+		created_at: '2026-01-01T00:00:00Z',
+		updated_at: '2026-01-01T00:00:00Z',
+	};
+}
+
+const context = { index: 0, source: 'csv', mimeType: 'text/csv', sourceId: 'export.csv:0' };
+
+describe('headerFingerprint', () => {
+	it('ignores order, case, separators and PapaParse extras', () => {
+		expect(headerFingerprint(['Due_Date', 'Title', '__parsed_extra', ' '])).toBe('due date|title');
+		expect(headerFingerprint(['title', 'due-date'])).toBe(headerFingerprint(['Due Date', 'TITLE']));
+	});
+});
+
+describe('suggestMappingProfile', () => {
+	const crm = profile('CRM', ['Deal', 'Owner', 'Stage', 'Amount', 'Close Date', 'Notes', 'Id', 'Region']);
+	const tasks = profile('Tasks', ['Title', 'Due', 'Done']);
+
+	it('returns an exact fingerprint match with score 1', () => {
+		const suggestion = suggestMappingProfile(['done', 'DUE', 'title'], [crm, tasks]);
+		expect(suggestion).toEqual({ profile: tasks, score: 1 });
+	});
+
+	it('suggests near matches above the threshold and nothing below it', () => {
+		const withExtra = ['Deal', 'Owner', 'Stage', 'Amount', 'Close Date', 'Notes', 'Id', 'Region', 'Probability'];
+		expect(suggestMappingProfile(withExtra, [crm, tasks])?.profile.name).toBe('CRM');
+		expect(suggestMappingProfile(withExtra, [crm])?.score).toBeCloseTo(8 / 9);
+		expect(suggestMappingProfile(['Title', 'Due'], [tasks])).toBeNull();
+		expect(suggestMappingProfile([], [tasks])).toBeNull();
+	});
+});
+
+describe('parseDateValue', () => {
+	it('reads token patterns as UTC, with an optional trailing time', () => {
+		expect(parseDateValue('05/01/2026', 'DD/MM/YYYY')).toBe('2026-01-05T00:00:00.000Z');
+		expect(parseDateValue('1/5/26 14:30', 'M/D/YY')).toBe('2026-01-05T14:30:00.000Z');
+		expect(parseDateValue('2026.01.05 09:15:07', 'YYYY.MM.DD HH:mm:ss')).toBe('2026-01-05T09:15:07.000Z');
+	});
+
+	it('rejects impossible dates and non-matching cells, but keeps ISO cells', () => {
+		expect(parseDateValue('31/02/2026', 'DD/MM/YYYY')).toBeNull();
+		expect(parseDateValue('soon', 'DD/MM/YYYY')).toBeNull();
+		expect(parseDateValue('2026-01-05T10:00:00Z', 'DD/MM/YYYY')).toBe('2026-01-05T10:00:00.000Z');
+	});
+
+	it('handles unix timestamps, Date cells and blanks', () => {
+		expect(parseDateValue('1767571200', 'unix')).toBe('2026-01-05T00:00:00.000Z');
+		expect(parseDateValue(1767571200000, 'unix_ms')).toBe('2026-01-05T00:00:00.000Z');
+		expect(parseDateValue(1767571200000)).toBe('2026-01-05T00:00:00.000Z');
+		expect(parseDateValue(new Date('2026-01-05T00:00:00Z'), 'DD/MM/YYYY')).toBe('2026-01-05T00:00:00.000Z');
+		expect(parseDateValue('  ')).toBeNull();
+	});
+});
+
+describe('mapRowWithProfile', () => {
+	const rules: MappingRules = {
+		columns: {
+			Subject: 'name',
+			Body: 'content',
+			Notes: 'content',
+			Labels: 'tags',
+			Topics: 'tags',
+			Opened: 'created_at',
+			Due: 'due_at',
+			Ticket: 'source_id',
+			Internal: 'skip',
+		},
+		dateFormat: 'DD/MM/YYYY',
+		tagSeparator: '|',
+		coercions: { Estimate: 'number', Billable: 'boolean', Reviewed: 'date' },
+		cardTypeRules: [
+			{ column: 'Kind', equals: 'meeting', cardType: 'event' },
+			{ column: 'Kind', contains: 'bug', cardType: 'task' },
+		],
+		defaultCardType: 'reference',
+	};
+	const support = profile('Support', [], rules);
+
+	it('maps columns onto card fields by normalized header', () => {
+		const card = mapRowWithProfile(
+			{
+				subject: 'Login fails',
+				BODY: 'Steps to reproduce',
+				notes: 'Seen on Safari',
+				labels: 'auth | web',
+				topics: 'web|urgent',
+				opened: '05/01/2026',
+				due: '12/01/2026',
+				ticket: 'T-42',
+				internal: 'secret',
+				Kind: 'UI Bug',
+			},
+			support,
+			context,
+		);
+
+		expect(card).toMatchObject({
+			name: 'Login fails',
+			content: 'Steps to reproduce\n\nSeen on Safari',
+			tags: ['auth', 'web', 'urgent'],
+			created_at: '2026-01-05T00:00:00.000Z',
+			modified_at: '2026-01-05T00:00:00.000Z',
+			due_at: '2026-01-12T00:00:00.000Z',
+			card_type: 'task',
+			source: 'csv',
+			source_id: 'p-Support:T-42',
+			mime_type: 'text/csv',
+		});
+		// Rule columns are not mapped and stay properties
+		expect(card.properties).toEqual({ Kind: 'UI Bug' });
+	});
+
+	it('coerces unmapped columns kept as properties', () => {
+		const card = mapRowWithProfile(
+			{ Subject: 'A', Estimate: '1,250.5 h', Billable: 'Yes', Reviewed: '03/02/2026', Owner: 'Ada', Kind: 'x' },
+			support,
+			context,
+		);
+
+		expect(card.properties).toEqual({
+			Estimate: 1250.5,
+			Billable: true,
+			Reviewed: '2026-02-03T00:00:00.000Z',
+			Owner: 'Ada',
+			Kind: 'x',
+		});
+	});
+
+	it('falls back to row position, default timestamp and default card type', () => {
+		const card = mapRowWithProfile({ Opened: 'not a date', Kind: 'Meeting' }, support, {
+			...context,
+			index: 4,
+			defaultTimestamp: '2026-01-01T00:00:00Z',
+		});
+
+		expect(card).toMatchObject({
+			name: 'Row 5',
+			created_at: '2026-01-01T00:00:00Z',
+			card_type: 'event',
+			source_id: 'export.csv:0',
+			sort_order: 4,
+		});
+		expect(mapRowWithProfile({ Kind: 'other' }, support, context).card_type).toBe('reference');
+	});
+
+	it('prefers a card_type column holding a valid type', () => {
+		const typed = profile('Typed', [], { columns: { Type: 'card_type', Title: 'name' } });
+		expect(mapRowWithProfile({ Title: 'A', Type: 'Person' }, typed, context).card_type).toBe('person');
+		expect(mapRowWithProfile({ Title: 'B', Type: 'Company' }, typed, context).card_type).toBe('note');
+	});
+});
+
+describe('extractRelationships', () => {
+	const people = profile('People', [], {
+		columns: { id: 'source_id', name: 'name' },
+		relationships: [
+			{ column: 'manager_id', references: 'id', label: 'reports_to' },
+			{ column: 'buddy', references: 'email', label: 'buddy', separator: ';' },
+			{ column: 'team_id', references: 'id', label: 'member_of', targetProfileId: 'teams' },
+		],
+	});
+
+	function mapped(rows: Array<Record<string, unknown>>): Array<{ row: Record<string, unknown>; card: CanonicalCard }> {
+		return rows.map((row, index) => ({
+			row,
+			card: mapRowWithProfile(row, people, { ...context, index, sourceId: `people.csv:${index}` }),
+		}));
+	}
+
+	const edges = (rows: Array<Record<string, unknown>>) =>
+		extractRelationships(mapped(rows), people).map((c) => [c.source_id, c.label, c.target_id]);
+
+	it('links foreign-key columns to rows of the same import by the referenced column', () => {
+		expect(
+			edges([
+				{ id: '1', name: 'Ada', email: 'ada@x', manager_id: '', buddy: 'bob@x; cy@x' },
+				{ id: '2', name: 'Bob', email: 'bob@x', manager_id: '1', buddy: 'ada@x' },
+				{ id: '3', name: 'Cy', email: 'cy@x', manager_id: '1', buddy: 'cy@x;nobody@x' },
+			]),
+		).toEqual([
+			['p-People:2', 'reports_to', 'p-People:1'],
+			['p-People:3', 'reports_to', 'p-People:1'],
+			['p-People:1', 'buddy', 'p-People:2'],
+			['p-People:1', 'buddy', 'p-People:3'],
+			['p-People:2', 'buddy', 'p-People:1'],
+		]);
+	});
+
+	it('falls back to the key column for rows outside the import and targets other profiles by key', () => {
+		expect(edges([{ id: '4', name: 'Di', manager_id: 9, team_id: 'T1' }])).toEqual([
+			['p-People:4', 'reports_to', 'p-People:9'],
+			['p-People:4', 'member_of', 'teams:T1'],
+		]);
+	});
+});
+
+describe('validateMappingRules', () => {
+	it('rejects unknown fields, coercions and card types', () => {
+		expect(() => validateMappingRules({ columns: { A: 'name' } })).not.toThrow();
+		expect(() => validateMappingRules({ columns: { A: 'owner' as never } })).toThrow('Unknown card field "owner"');
+		expect(() => validateMappingRules({ columns: {}, coercions: { A: 'money' as never } })).toThrow(
+			'Unknown value type "money"',
+		);
+		expect(() =>
+			validateMappingRules({ columns: {}, cardTypeRules: [{ column: 'A', cardType: 'company' as never }] }),
+		).toThrow('Unknown card type "company"');
+		expect(() =>
+			validateMappingRules({ columns: {}, relationships: [{ column: 'manager_id', references: 'id', label: ' ' }] }),
+		).toThrow('Relationship on column "manager_id" needs a label');
+	});
+});
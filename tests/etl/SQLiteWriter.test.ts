@@ -371,6 +371,41 @@ describe('SQLiteWriter', () => {
 		});
 	});
 
+	describe('custom properties', () => {
+		it('creates inferred definitions and typed values from unmapped columns', async () => {
+			const cards = [
+				createCard('row-1', 'Acme', { properties: { Revenue: '1200', Stage: 'Won' } }),
+				createCard('row-2', 'Globex', { properties: { Revenue: '80.5', Stage: 'Won' } }),
+				createCard('row-3', 'Initech', { properties: { Revenue: '', Stage: 'Lost' } }),
+			];
+			await writer.writeCards(cards);
+
+			const created = await writer.writeProperties(cards);
+
+			expect(created).toBe(2);
+			const defs = db.exec('SELECT key, type, enum_values FROM property_definitions ORDER BY key');
+			expect(defs[0]!.values).toEqual([
+				['revenue', 'number', null],
+				['stage', 'enum', '["Lost","Won"]'],
+			]);
+			const values = db.exec("SELECT value FROM card_properties WHERE key = 'revenue' ORDER BY value");
+			expect(values[0]!.values.map((r) => r[0])).toEqual([80.5, 1200]);
+		});
+
+		it('keeps existing types and skips values that do not fit', async () => {
+			const first = [createCard('row-1', 'Acme', { properties: { Revenue: '10' } })];
+			await writer.writeCards(first);
+			await writer.writeProperties(first);
+
+			const second = [createCard('row-2', 'Globex', { properties: { Revenue: 'n/a' } })];
+			await writer.writeCards(second);
+
+			expect(await writer.writeProperties(second)).toBe(0);
+			const count = db.exec("SELECT COUNT(*) FROM card_properties WHERE key = 'revenue'");
+			expect(count[0]!.values[0]![0]).toBe(1);
+		});
+	});
+
 	// Phase 77-01: injectable batchSize + FTS PerfTrace spans
 	describe('injectable batchSize (Phase 77-01 - IMPT-01)', () => {
 		it('accepts custom batchSize in constructor and uses it for batching', async () => {
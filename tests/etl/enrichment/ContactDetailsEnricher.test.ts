@@ -0,0 +1,74 @@
+// Isometry v5 — ContactDetailsEnricher Tests
+// Email and phone detection into Emails / Phones properties.
+
+import { describe, expect, it } from 'vitest';
+import {
+	contactDetailsEnricher,
+	extractEmails,
+	extractPhones,
+} from '../../../src/etl/enrichment/ContactDetailsEnricher';
+import type { CanonicalCard } from '../../../src/etl/types';
+
+function makeCard(content: string | null): CanonicalCard {
+	return {
+		id: crypto.randomUUID(),
+		card_type: 'note',
+		name: 'Test',
+		content,
+		summary: null,
+		latitude: null,
+		longitude: null,
+		location_name: null,
+		created_at: '2026-03-04T15:30:00Z',
+		modified_at: '2026-03-04T15:30:00Z',
+		due_at: null,
+		completed_at: null,
+		event_start: null,
+		event_end: null,
+		folder: null,
+		tags: [],
+		status: null,
+		priority: 0,
+		sort_order: 0,
+		url: null,
+		mime_type: null,
+		is_collective: false,
+		source: 'test',
+		source_id: 'test-1',
+		source_url: null,
+		deleted_at: null,
+	};
+}
+
+describe('extractEmails', () => {
+	it('lowercases and de-duplicates addresses', () => {
+		expect(extractEmails('Mail Ada@Example.com or ada@example.com; cc bob.smith+x@mail.co.uk.')).toEqual([
+			'ada@example.com',
+			'bob.smith+x@mail.co.uk',
+		]);
+	});
+});
+
+describe('extractPhones', () => {
+	it('keeps 10-15 digit numbers and skips dates, times and short numbers', () => {
+		expect(
+			extractPhones('Call +1 (415) 555-2671 or 020 7946 0958. Met 2026-03-04 10:30, order 12345, PIN 12 34.'),
+		).toEqual(['+1 (415) 555-2671', '020 7946 0958']);
+	});
+});
+
+describe('contactDetailsEnricher', () => {
+	it('stores found details as properties and reports only changed cards', () => {
+		const both = makeCard('Reach me at ada@example.com or +44 20 7946 0958');
+		const none = makeCard('Nothing to see here');
+		const changed: CanonicalCard[] = [];
+		const context = { sourceType: 'test', connect: () => {}, markChanged: (c: CanonicalCard) => changed.push(c) };
+
+		contactDetailsEnricher.enrich([both, none], context);
+		contactDetailsEnricher.enrich([both, none], context);
+
+		expect(both.properties).toEqual({ Emails: 'ada@example.com', Phones: '+44 20 7946 0958' });
+		expect(none.properties).toBeUndefined();
+		expect(changed).toEqual([both]);
+	});
+});
@@ -0,0 +1,108 @@
+// Isometry v5 — DateExtractionEnricher Tests
+// Cued deadline phrases, year rollover, and never overwriting a source due date.
+
+import { describe, expect, it } from 'vitest';
+import { dateExtractionEnricher, extractDueDate } from '../../../src/etl/enrichment/DateExtractionEnricher';
+import type { CanonicalCard } from '../../../src/etl/types';
+
+// Wednesday
+const REFERENCE = '2026-03-04T15:30:00Z';
+
+function makeCard(overrides: Partial<CanonicalCard>): CanonicalCard {
+	return {
+		id: crypto.randomUUID(),
+		card_type: 'note',
+		name: 'Test',
+		content: null,
+		summary: null,
+		latitude: null,
+		longitude: null,
+		location_name: null,
+		created_at: REFERENCE,
+		modified_at: REFERENCE,
+		due_at: null,
+		completed_at: null,
+		event_start: null,
+		event_end: null,
+		folder: null,
+		tags: [],
+		status: null,
+		priority: 0,
+		sort_order: 0,
+		url: null,
+		mime_type: null,
+		is_collective: false,
+		source: 'test',
+		source_id: 'test-1',
+		source_url: null,
+		deleted_at: null,
+		...overrides,
+	};
+}
+
+describe('extractDueDate', () => {
+	it('resolves relative phrases against the reference day', () => {
+		expect(extractDueDate('Due: today', REFERENCE)).toBe('2026-03-04T00:00:00.000Z');
+		expect(extractDueDate('Report due tomorrow', REFERENCE)).toBe('2026-03-05T00:00:00.000Z');
+		expect(extractDueDate('Finish by Friday', REFERENCE)).toBe('2026-03-06T00:00:00.000Z');
+		expect(extractDueDate('due on Monday', REFERENCE)).toBe('2026-03-09T00:00:00.000Z');
+		expect(extractDueDate('deadline: next Wednesday', REFERENCE)).toBe('2026-03-11T00:00:00.000Z');
+		expect(extractDueDate('Ship before next week', REFERENCE)).toBe('2026-03-11T00:00:00.000Z');
+		expect(extractDueDate('due in 2 weeks', REFERENCE)).toBe('2026-03-18T00:00:00.000Z');
+		expect(extractDueDate('until in a month', REFERENCE)).toBe('2026-04-04T00:00:00.000Z');
+	});
+
+	it('reads absolute dates, rolling dates without a year forward', () => {
+		expect(extractDueDate('due 2026-05-01', REFERENCE)).toBe('2026-05-01T00:00:00.000Z');
+		expect(extractDueDate('Submit by March 3', REFERENCE)).toBe('2027-03-03T00:00:00.000Z');
+		expect(extractDueDate('Submit by Mar. 20th', REFERENCE)).toBe('2026-03-20T00:00:00.000Z');
+		expect(extractDueDate('by 15th April 2026', REFERENCE)).toBe('2026-04-15T00:00:00.000Z');
+		expect(extractDueDate('due September 1, 2025', REFERENCE)).toBe('2025-09-01T00:00:00.000Z');
+	});
+
+	it('ignores dates without a cue word and impossible dates', () => {
+		expect(extractDueDate('Met on March 3 with the team', REFERENCE)).toBeNull();
+		expect(extractDueDate('by the way, due Feb 30', REFERENCE)).toBeNull();
+		expect(extractDueDate('due tomorrow', 'not a date')).toBeNull();
+	});
+
+	it('clamps month offsets to the end of shorter months', () => {
+		expect(extractDueDate('due in 1 month', '2026-01-31T09:00:00Z')).toBe('2026-02-28T00:00:00.000Z');
+		expect(extractDueDate('due in a month', '2028-01-30T09:00:00Z')).toBe('2028-02-29T00:00:00.000Z');
+		expect(extractDueDate('due in 3 months', '2026-11-30T09:00:00Z')).toBe('2027-02-28T00:00:00.000Z');
+		expect(extractDueDate('due in two months', '2026-12-15T09:00:00Z')).toBe('2027-02-15T00:00:00.000Z');
+	});
+
+	it('ignores "by" in prose and possessive dates', () => {
+		expect(extractDueDate("Notes written by Monday's team", REFERENCE)).toBeNull();
+		expect(extractDueDate('by the way\u2026 see you Friday', REFERENCE)).toBeNull();
+		expect(extractDueDate('Minutes by: Friday', REFERENCE)).toBeNull();
+		expect(extractDueDate('Photo by\nFriday market', REFERENCE)).toBeNull();
+		expect(extractDueDate('Lunch before today\u2019s meeting', REFERENCE)).toBeNull();
+		expect(extractDueDate("Review by Friday's end, due tomorrow", REFERENCE)).toBe('2026-03-05T00:00:00.000Z');
+	});
+});
+
+describe('dateExtractionEnricher', () => {
+	it('fills due_at from name or content and keeps source due dates', () => {
+		const fromName = makeCard({ name: 'Taxes due April 15' });
+		const fromContent = makeCard({ content: 'Notes\n\nDeadline: tomorrow' });
+		const sourced = makeCard({ content: 'due tomorrow', due_at: '2026-12-01T00:00:00Z' });
+		const changed: CanonicalCard[] = [];
+
+		dateExtractionEnricher.enrich([fromName, fromContent, sourced], {
+			sourceType: 'test',
+			connect: () => {},
+			markChanged: (card) => changed.push(card),
+		});
+
+		expect(fromName.due_at).toBe('2026-04-15T00:00:00.000Z');
+		expect(fromContent.due_at).toBe('2026-03-05T00:00:00.000Z');
+		expect(sourced.due_at).toBe('2026-12-01T00:00:00Z');
+		expect(changed).toEqual([fromName, fromContent]);
+	});
+
+	it('is opt-in', () => {
+		expect(dateExtractionEnricher.defaultEnabled).toBe(false);
+	});
+});
@@ -0,0 +1,34 @@
+// Isometry v5 — LanguageEnricher Tests
+
+import { describe, expect, it } from 'vitest';
+import { detectLanguage, languageEnricher } from '../../../src/etl/enrichment/LanguageEnricher';
+import type { CanonicalCard } from '../../../src/etl/types';
+
+describe('detectLanguage', () => {
+	it('detects languages by their common words', () => {
+		expect(detectLanguage('The quick brown fox jumps over the lazy dog and it is not amused')).toBe('en');
+		expect(detectLanguage('El perro de mi vecino está muy cansado porque corre por el parque')).toBe('es');
+		expect(detectLanguage('Der Hund ist nicht müde und die Katze schläft auf dem Sofa')).toBe('de');
+		expect(detectLanguage('Nous avons pris le train pour aller dans les montagnes avec des amis')).toBe('fr');
+	});
+
+	it('returns null for short or unclear text', () => {
+		expect(detectLanguage('Hello there')).toBeNull();
+		expect(detectLanguage('Lorem ipsum dolor sit amet consectetur')).toBeNull();
+	});
+});
+
+describe('languageEnricher', () => {
+	it('sets the Language property on cards with detectable content', () => {
+		const card = {
+			source_id: 'n1',
+			content: 'Il gatto della vicina non mangia per niente, ma è anche molto pigro',
+		} as CanonicalCard;
+		const blank = { source_id: 'n2', content: null } as CanonicalCard;
+
+		languageEnricher.enrich([card, blank]);
+
+		expect(card.properties).toEqual({ Language: 'it' });
+		expect(blank.properties).toBeUndefined();
+	});
+});
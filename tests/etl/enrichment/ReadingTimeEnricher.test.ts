@@ -0,0 +1,59 @@
+// Isometry v5 — ReadingTimeEnricher Tests
+
+import { describe, expect, it } from 'vitest';
+import { countWords, readingTimeEnricher } from '../../../src/etl/enrichment/ReadingTimeEnricher';
+import type { CanonicalCard } from '../../../src/etl/types';
+
+function makeCard(content: string | null): CanonicalCard {
+	return {
+		id: crypto.randomUUID(),
+		card_type: 'note',
+		name: 'Test',
+		content,
+		summary: null,
+		latitude: null,
+		longitude: null,
+		location_name: null,
+		created_at: '2026-03-04T15:30:00Z',
+		modified_at: '2026-03-04T15:30:00Z',
+		due_at: null,
+		completed_at: null,
+		event_start: null,
+		event_end: null,
+		folder: null,
+		tags: [],
+		status: null,
+		priority: 0,
+		sort_order: 0,
+		url: null,
+		mime_type: null,
+		is_collective: false,
+		source: 'test',
+		source_id: 'test-1',
+		source_url: null,
+		deleted_at: null,
+	};
+}
+
+describe('countWords', () => {
+	it('counts words, keeping contractions and hyphenated words whole', () => {
+		expect(countWords("## Heading\n\n- It's a well-known fact — 3 words, maybe 4?")).toBe(9);
+		expect(countWords('  ')).toBe(0);
+	});
+});
+
+describe('readingTimeEnricher', () => {
+	it('sets word count and rounded-up reading time, once', () => {
+		const long = makeCard(Array.from({ length: 450 }, (_, i) => `word${i}`).join(' '));
+		const empty = makeCard(null);
+		const changed: CanonicalCard[] = [];
+		const context = { sourceType: 'test', connect: () => {}, markChanged: (c: CanonicalCard) => changed.push(c) };
+
+		readingTimeEnricher.enrich([long, empty], context);
+		readingTimeEnricher.enrich([long, empty], context);
+
+		expect(long.properties).toEqual({ 'Word Count': 450, 'Reading Time (min)': 3 });
+		expect(empty.properties).toBeUndefined();
+		expect(changed).toEqual([long]);
+	});
+});
@@ -0,0 +1,102 @@
+// Isometry v5 — UrlExtractionEnricher Tests
+// URL detection and linked resource cards shared across the batch.
+
+import { describe, expect, it } from 'vitest';
+import { extractUrls, urlExtractionEnricher } from '../../../src/etl/enrichment/UrlExtractionEnricher';
+import type { EnrichmentContext } from '../../../src/etl/enrichment/types';
+import type { CanonicalCard } from '../../../src/etl/types';
+
+function makeCard(sourceId: string, content: string | null): CanonicalCard {
+	return {
+		id: crypto.randomUUID(),
+		card_type: 'note',
+		name: sourceId,
+		content,
+		summary: null,
+		latitude: null,
+		longitude: null,
+		location_name: null,
+		created_at: '2026-03-04T15:30:00Z',
+		modified_at: '2026-03-05T09:00:00Z',
+		due_at: null,
+		completed_at: null,
+		event_start: null,
+		event_end: null,
+		folder: null,
+		tags: [],
+		status: null,
+		priority: 0,
+		sort_order: 0,
+		url: null,
+		mime_type: null,
+		is_collective: false,
+		source: 'markdown',
+		source_id: sourceId,
+		source_url: null,
+		deleted_at: null,
+	};
+}
+
+function recordingContext() {
+	const edges: Array<[string, string, string]> = [];
+	const changed: string[] = [];
+	const context: EnrichmentContext = {
+		sourceType: 'markdown',
+		connect: (sourceId, targetId, label) => edges.push([sourceId, label, targetId]),
+		markChanged: (card) => changed.push(card.source_id),
+	};
+	return { context, edges, changed };
+}
+
+describe('extractUrls', () => {
+	it('finds distinct http(s) links without surrounding punctuation', () => {
+		expect(
+			extractUrls(
+				'See https://example.com/docs/intro. Also [intro](https://example.com/docs/intro) and http://foo.org/a?b=1, ' +
+					'but not ftp://files.example.com or http://',
+			),
+		).toEqual(['https://example.com/docs/intro', 'http://foo.org/a?b=1']);
+	});
+});
+
+describe('urlExtractionEnricher', () => {
+	it('creates one resource card per URL and links every mentioning card to it', () => {
+		const cards = [
+			makeCard('a.md', 'Spec: https://example.com/docs/intro/'),
+			makeCard('b.md', 'Same spec https://example.com/docs/intro/ again'),
+			makeCard('c.md', null),
+		];
+		const { context, edges, changed } = recordingContext();
+
+		urlExtractionEnricher.enrich(cards, context);
+
+		expect(cards).toHaveLength(4);
+		expect(cards[3]).toMatchObject({
+			card_type: 'resource',
+			name: 'example.com/docs/intro',
+			url: 'https://example.com/docs/intro/',
+			source_url: 'https://example.com/docs/intro/',
+			source: 'markdown',
+			source_id: 'url:https://example.com/docs/intro/',
+			created_at: '2026-03-04T15:30:00Z',
+			modified_at: '2026-03-04T15:30:00Z',
+		});
+		expect(edges).toEqual([
+			['a.md', 'links_to', 'url:https://example.com/docs/intro/'],
+			['b.md', 'links_to', 'url:https://example.com/docs/intro/'],
+		]);
+		expect(changed).toEqual(['url:https://example.com/docs/intro/']);
+	});
+
+	it('reuses resource cards already in the batch on re-runs', () => {
+		const cards = [makeCard('a.md', 'https://example.com')];
+		urlExtractionEnricher.enrich(cards, recordingContext().context);
+
+		const rerun = recordingContext();
+		urlExtractionEnricher.enrich(cards, rerun.context);
+
+		expect(cards).toHaveLength(2);
+		expect(rerun.changed).toEqual([]);
+		expect(rerun.edges).toEqual([['a.md', 'links_to', 'url:https://example.com/']]);
+	});
+});
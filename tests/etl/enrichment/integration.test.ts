@@ -6,6 +6,7 @@
 
 import { afterEach, beforeEach, describe, expect, it } from 'vitest';
 import { Database } from '../../../src/database/Database';
+import { setEnricherSetting } from '../../../src/database/queries/enricher-settings';
 import { ImportOrchestrator } from '../../../src/etl/ImportOrchestrator';
 import type { ParsedFile } from '../../../src/etl/parsers/AppleNotesParser';
 
@@ -239,3 +240,97 @@ Content moved.
 		expect(after[0]!.folder_l2).toBe('Google');
 	});
 });
+
+describe('Enrichment Integration — opt-in enrichers', () => {
+	let db: Database;
+	let orchestrator: ImportOrchestrator;
+
+	const spec = {
+		path: 'Work/spec-review.md',
+		content: `---
+title: "Spec review"
+created: "2026-01-15T10:00:00Z"
+modified: "2026-01-15T10:00:00Z"
+---
+
+Comments due tomorrow. The draft is at https://example.com/spec and mirrors the reference design closely.
+`,
+	};
+
+	beforeEach(async () => {
+		db = new Database();
+		await db.initialize();
+		orchestrator = new ImportOrchestrator(db);
+	});
+
+	afterEach(() => {
+		db.close();
+	});
+
+	function card(sourceId: string) {
+		return db
+			.prepare<{ id: string; card_type: string; due_at: string | null }>(
+				'SELECT id, card_type, due_at FROM cards WHERE source_id = ?',
+			)
+			.all(sourceId)[0];
+	}
+
+	it('leaves cards alone until an enricher is switched on', async () => {
+		const result = await orchestrator.import('markdown', JSON.stringify([spec]));
+
+		expect(result.inserted).toBe(1);
+		expect(result.enriched).toBeUndefined();
+		expect(card(spec.path)?.due_at).toBeNull();
+	});
+
+	it('applies enabled enrichers during import and reports the cards they changed', async () => {
+		setEnricherSetting(db, { enricher_id: 'date-extraction', source_type: 'markdown', enabled: true });
+		setEnricherSetting(db, { enricher_id: 'url-extraction', source_type: '*', enabled: true });
+
+		const result = await orchestrator.import('markdown', JSON.stringify([spec]));
+
+		const note = card(spec.path)!;
+		const resource = card('url:https://example.com/spec')!;
+		expect(result.inserted).toBe(2);
+		expect(result.connections_created).toBe(1);
+		expect(result.enriched).toEqual({ 'date-extraction': [note.id], 'url-extraction': [resource.id] });
+		expect(note.due_at).toBe('2026-01-16T00:00:00.000Z');
+		expect(resource.card_type).toBe('resource');
+
+		const links = db
+			.prepare<{ source_id: string; target_id: string }>(
+				"SELECT source_id, target_id FROM connections WHERE label = 'links_to'",
+			)
+			.all();
+		expect(links).toEqual([{ source_id: note.id, target_id: resource.id }]);
+	});
+
+	it('re-runs enrichers over imported cards once, without duplicating links', async () => {
+		await orchestrator.import('markdown', JSON.stringify([spec]));
+		setEnricherSetting(db, { enricher_id: 'reading-time', source_type: '*', enabled: true });
+		setEnricherSetting(db, { enricher_id: 'url-extraction', source_type: '*', enabled: true });
+
+		const first = await orchestrator.reenrich();
+		const note = card(spec.path)!;
+		const resource = card('url:https://example.com/spec')!;
+		expect(first).toEqual({
+			insertedIds: [resource.id],
+			updatedIds: [note.id],
+			connections_created: 1,
+			enriched: { 'url-extraction': [resource.id], 'reading-time': [note.id] },
+		});
+		const properties = db
+			.prepare<{ key: string; value: number }>('SELECT key, value FROM card_properties WHERE card_id = ? ORDER BY key')
+			.all(note.id);
+		expect(properties).toEqual([
+			{ key: 'reading_time_min', value: 1 },
+			{ key: 'word_count', value: 17 },
+		]);
+
+		const second = await orchestrator.reenrich();
+		expect(second).toEqual({ insertedIds: [], updatedIds: [], connections_created: 0, enriched: {} });
+
+		const onlyDates = await orchestrator.reenrich(['date-extraction']);
+		expect(onlyDates.enriched).toEqual({});
+	});
+});
@@ -3,7 +3,10 @@
 import { afterEach, describe, expect, it, vi } from 'vitest';
 import {
 	getRegisteredEnricherIds,
+	isEnricherEnabled,
 	registerEnricher,
+	resolveEnrichedIds,
+	runEnrichment,
 	runEnrichmentPipeline,
 	unregisterEnricher,
 } from '../../../src/etl/enrichment/registry';
@@ -129,4 +132,80 @@ describe('Enrichment Registry', () => {
 			expect(result).toBe(cards);
 		});
 	});
+
+	describe('isEnricherEnabled', () => {
+		const optIn: Enricher = { ...makeTestEnricher('test-opt-in'), defaultEnabled: false };
+
+		it('falls back to defaultEnabled, then a * setting, then a source setting', () => {
+			expect(isEnricherEnabled(optIn, 'csv')).toBe(false);
+			expect(isEnricherEnabled(makeTestEnricher('test-default'), 'csv')).toBe(true);
+
+			const settings = [
+				{ enricher_id: 'test-opt-in', source_type: '*', enabled: true },
+				{ enricher_id: 'test-opt-in', source_type: 'json', enabled: false },
+			];
+			expect(isEnricherEnabled(optIn, 'csv', settings)).toBe(true);
+			expect(isEnricherEnabled(optIn, 'json', settings)).toBe(false);
+		});
+
+		it('never enables an enricher for a source it does not apply to', () => {
+			const csvOnly = makeTestEnricher('test-csv-only', ['csv']);
+			const settings = [{ enricher_id: csvOnly.id, source_type: 'json', enabled: true }];
+			expect(isEnricherEnabled(csvOnly, 'json', settings)).toBe(false);
+		});
+	});
+
+	describe('runEnrichment', () => {
+		function makeLinker(id: string, defaultEnabled?: boolean): Enricher {
+			return {
+				id,
+				description: `Linking enricher ${id}`,
+				appliesTo: '*',
+				...(defaultEnabled !== undefined ? { defaultEnabled } : {}),
+				enrich(cards, context) {
+					for (const card of cards) {
+						context?.connect(card.source_id, 'hub', id);
+						context?.markChanged(card);
+					}
+					return cards;
+				},
+			};
+		}
+		const cards = [{ source_id: 'a' }, { source_id: 'b' }] as CanonicalCard[];
+
+		it('collects connections and the cards each enricher changed', () => {
+			registerTest(makeLinker('test-linker'));
+
+			const result = runEnrichment(cards, 'csv');
+
+			expect(result.cards).toBe(cards);
+			expect(result.changed['test-linker']).toEqual(['a', 'b']);
+			expect(result.connections.filter((c) => c.label === 'test-linker')).toMatchObject([
+				{ source_id: 'a', target_id: 'hub', via_card_id: null, weight: 1 },
+				{ source_id: 'b', target_id: 'hub', via_card_id: null, weight: 1 },
+			]);
+		});
+
+		it('honours settings and restricts re-runs to the given enricher ids', () => {
+			registerTest(makeLinker('test-opt-in-linker', false));
+			registerTest(makeLinker('test-other-linker'));
+			const settings = [{ enricher_id: 'test-opt-in-linker', source_type: 'csv', enabled: true }];
+
+			expect(Object.keys(runEnrichment(cards, 'json').changed)).toContain('test-other-linker');
+			expect(Object.keys(runEnrichment(cards, 'json').changed)).not.toContain('test-opt-in-linker');
+			expect(Object.keys(runEnrichment(cards, 'csv', settings, ['test-opt-in-linker']).changed)).toEqual([
+				'test-opt-in-linker',
+			]);
+		});
+	});
+
+	describe('resolveEnrichedIds', () => {
+		it('maps source_ids to written card ids and drops unwritten cards', () => {
+			const ids = new Map([
+				['a', 'id-a'],
+				['b', 'id-b'],
+			]);
+			expect(resolveEnrichedIds({ one: ['a', 'b', 'c'], two: ['b'] }, ids, ['id-a'])).toEqual({ one: ['id-a'] });
+		});
+	});
 });
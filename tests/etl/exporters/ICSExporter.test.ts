@@ -0,0 +1,126 @@
+// Isometry v5 — ICSExporter Tests
+// Dated cards -> VEVENT / VTODO, and round-trip through ICSParser.
+
+import { describe, expect, it } from 'vitest';
+import type { Card } from '../../../src/database/queries/types';
+import { ICSExporter } from '../../../src/etl/exporters/ICSExporter';
+import { ICSParser } from '../../../src/etl/parsers/ICSParser';
+
+function card(id: string, name: string, overrides: Partial<Card> = {}): Card {
+	return {
+		id,
+		card_type: 'note',
+		name,
+		content: null,
+		summary: null,
+		latitude: null,
+		longitude: null,
+		location_name: null,
+		created_at: '2026-01-05T09:00:00Z',
+		modified_at: '2026-01-05T09:00:00Z',
+		due_at: null,
+		completed_at: null,
+		event_start: null,
+		event_end: null,
+		folder: null,
+		tags: [],
+		status: null,
+		priority: 0,
+		sort_order: 0,
+		url: null,
+		mime_type: null,
+		is_collective: false,
+		source: 'test',
+		source_id: id,
+		source_url: null,
+		deleted_at: null,
+		...overrides,
+	};
+}
+
+describe('ICSExporter', () => {
+	const exporter = new ICSExporter();
+
+	it('writes events and tasks, skipping undated cards', () => {
+		const ics = exporter.export([
+			card('a', 'Review; notes, v2', {
+				card_type: 'event',
+				event_start: '2026-01-05T09:00:00Z',
+				event_end: '2026-01-05T10:00:00Z',
+				location_name: 'Room 4',
+				latitude: 52.52,
+				longitude: 13.405,
+			}),
+			card('b', 'Holiday', { event_start: '2026-02-12', event_end: '2026-02-13' }),
+			card('c', 'File taxes', { card_type: 'task', due_at: '2026-04-15T17:00:00Z', priority: 3 }),
+			card('d', 'Just a note'),
+		]);
+		const lines = ics.split('\r\n');
+
+		expect(lines[0]).toBe('BEGIN:VCALENDAR');
+		expect(lines).toContain('UID:a@isometry');
+		expect(lines).toContain('SUMMARY:Review\\; notes\\, v2');
+		expect(lines).toContain('DTSTART:20260105T090000Z');
+		expect(lines).toContain('DTEND:20260105T100000Z');
+		expect(lines).toContain('GEO:52.52;13.405');
+		expect(lines).toContain('DTSTART;VALUE=DATE:20260212');
+		expect(lines).toContain('DUE:20260415T170000Z');
+		expect(lines).toContain('STATUS:NEEDS-ACTION');
+		expect(lines).toContain('PRIORITY:1');
+		expect(lines.filter((l) => l === 'BEGIN:VEVENT')).toHaveLength(2);
+		expect(lines.filter((l) => l === 'BEGIN:VTODO')).toHaveLength(1);
+		expect(ics).not.toContain('Just a note');
+		expect(ics.endsWith('END:VCALENDAR\r\n')).toBe(true);
+	});
+
+	it('folds long lines at 75 octets without splitting characters', () => {
+		const ics = exporter.export([card('a', 'x', { event_start: '2026-01-05T09:00:00Z', content: 'é'.repeat(100) })]);
+		const encoder = new TextEncoder();
+
+		for (const line of ics.split('\r\n')) {
+			expect(encoder.encode(line).length).toBeLessThanOrEqual(75);
+		}
+		expect(ics).toContain('\r\n é');
+	});
+
+	it('round-trips through ICSParser', () => {
+		const cards = [
+			card('a', 'Kickoff, Q1', {
+				card_type: 'event',
+				content: 'Agenda:\n- scope',
+				event_start: '2026-01-05T09:00:00Z',
+				event_end: '2026-01-05T10:00:00Z',
+				tags: ['work', 'a,b'],
+				status: 'tentative',
+				url: 'https://example.com',
+			}),
+			card('b', 'Offsite', { event_start: '2026-02-12', event_end: '2026-02-14' }),
+			card('c', 'Pay invoice', {
+				card_type: 'task',
+				due_at: '2026-01-10T17:00:00Z',
+				completed_at: '2026-01-09T08:00:00Z',
+				priority: 2,
+			}),
+		];
+		const parsed = new ICSParser().parse(exporter.export(cards));
+
+		expect(parsed.cards.map((c) => [c.source_id, c.card_type, c.name, c.event_start, c.event_end])).toEqual([
+			['a@isometry', 'event', 'Kickoff, Q1', '2026-01-05T09:00:00Z', '2026-01-05T10:00:00Z'],
+			['b@isometry', 'event', 'Offsite', '2026-02-12', '2026-02-14'],
+			['c@isometry', 'task', 'Pay invoice', null, null],
+		]);
+		expect(parsed.cards[0]).toMatchObject({
+			content: 'Agenda:\n- scope',
+			tags: ['work', 'a,b'],
+			status: 'tentative',
+			url: 'https://example.com',
+		});
+		expect(parsed.cards[2]).toMatchObject({
+			due_at: '2026-01-10T17:00:00Z',
+			completed_at: '2026-01-09T08:00:00Z',
+			status: 'done',
+			priority: 2,
+			created_at: '2026-01-05T09:00:00Z',
+		});
+	});
+});
@@ -0,0 +1,114 @@
+// Isometry v5 — OPMLExporter Tests
+// Export walks `contains` connections into nested outlines and round-trips
+// through OPMLParser.
+
+import { describe, expect, it } from 'vitest';
+import type { Card, Connection } from '../../../src/database/queries/types';
+import { OPMLExporter } from '../../../src/etl/exporters/OPMLExporter';
+import { OPMLParser } from '../../../src/etl/parsers/OPMLParser';
+
+function card(id: string, name: string, overrides: Partial<Card> = {}): Card {
+	return {
+		id,
+		card_type: 'note',
+		name,
+		content: null,
+		summary: null,
+		latitude: null,
+		longitude: null,
+		location_name: null,
+		created_at: '2026-01-05T09:00:00Z',
+		modified_at: '2026-01-05T09:00:00Z',
+		due_at: null,
+		completed_at: null,
+		event_start: null,
+		event_end: null,
+		folder: null,
+		tags: [],
+		status: null,
+		priority: 0,
+		sort_order: 0,
+		url: null,
+		mime_type: null,
+		is_collective: false,
+		source: 'test',
+		source_id: id,
+		source_url: null,
+		deleted_at: null,
+		...overrides,
+	};
+}
+
+function contains(source: string, target: string): Connection {
+	return {
+		id: `${source}-${target}`,
+		source_id: source,
+		target_id: target,
+		via_card_id: null,
+		label: 'contains',
+		weight: 1,
+		created_at: '2026-01-05T09:00:00Z',
+	};
+}
+
+describe('OPMLExporter', () => {
+	const exporter = new OPMLExporter();
+
+	it('nests children under their contains parent in sort_order', () => {
+		const opml = exporter.export(
+			[
+				card('a', 'Plan'),
+				card('c', 'Second', { sort_order: 1 }),
+				card('b', 'First', { sort_order: 0 }),
+				card('d', 'Loose', { sort_order: 1 }),
+			],
+			[contains('a', 'c'), contains('a', 'b'), { ...contains('d', 'a'), label: 'links_to' }],
+		);
+
+		const outlines = opml.split('\n').filter((line) => line.includes('outline'));
+		expect(outlines.map((line) => line.trim().replace(/ created="[^"]*"/, ''))).toEqual([
+			'<outline text="Plan">',
+			'<outline text="First"/>',
+			'<outline text="Second"/>',
+			'</outline>',
+			'<outline text="Loose"/>',
+		]);
+		expect(opml).toMatch(/^<\?xml version="1.0" encoding="UTF-8"\?>\n<opml version="2.0">/);
+	});
+
+	it('escapes attribute values and keeps note line breaks', () => {
+		const opml = exporter.export([
+			card('a', 'R&D "plans" <draft>', { content: 'line 1\nline 2', url: 'https://x.test/?a=1&b=2', tags: ['x', 'y'] }),
+		]);
+
+		expect(opml).toContain('text="R&amp;D &quot;plans&quot; &lt;draft&gt;" _note="line 1&#10;line 2"');
+		expect(opml).toContain('type="link" url="https://x.test/?a=1&amp;b=2" category="x,y"');
+	});
+
+	it('writes each card once when contains connections form a cycle', () => {
+		const opml = exporter.export([card('a', 'A'), card('b', 'B')], [contains('a', 'b'), contains('b', 'a')]);
+
+		expect(opml.match(/<outline /g)).toHaveLength(2);
+	});
+
+	it('round-trips through OPMLParser', () => {
+		const cards = [
+			card('root', 'Project', { content: 'Overview\nsecond line' }),
+			card('child', 'Task', { sort_order: 0, tags: ['urgent'], completed_at: '2026-01-06T00:00:00Z' }),
+			card('leaf', 'Subtask', { url: 'https://example.com' }),
+		];
+		const opml = exporter.export(cards, [contains('root', 'child'), contains('child', 'leaf')]);
+		const parsed = new OPMLParser().parse(opml, { filename: 'export.opml' });
+
+		expect(parsed.cards.map((c) => [c.name, c.content, c.tags, c.url, c.created_at])).toEqual([
+			['Project', 'Overview\nsecond line', [], null, '2026-01-05T09:00:00Z'],
+			['Task', null, ['urgent'], null, '2026-01-05T09:00:00Z'],
+			['Subtask', null, [], 'https://example.com', '2026-01-05T09:00:00Z'],
+		]);
+		expect(parsed.cards[1]?.completed_at).not.toBeNull();
+		expect(parsed.connections.map((c) => [c.source_id, c.target_id])).toEqual([
+			['export.opml#0', 'export.opml#0.0'],
+			['export.opml#0.0', 'export.opml#0.0.0'],
+		]);
+	});
+});
@@ -0,0 +1,73 @@
+// Isometry v5 — Bookmarks Parser Tests
+// Netscape bookmark files and Pocket exports -> resource cards with folder paths.
+
+import { readFileSync } from 'node:fs';
+import { join } from 'node:path';
+import { describe, expect, it } from 'vitest';
+import { BookmarksParser, isBookmarksDocument } from '../../../src/etl/parsers/BookmarksParser';
+
+const bookmarks = readFileSync(join(__dirname, '../fixtures/bookmarks.html'), 'utf-8');
+
+const pocket = `<!DOCTYPE html>
+<html><head><title>Pocket Export</title></head><body>
+<h1>Unread</h1>
+<ul>
+<li><a href="https://example.com/a" time_added="1767225600" tags="reading,long">Article A</a></li>
+<li><a href="https://example.com/b" time_added="1767312000" tags="">https://example.com/b</a></li>
+</ul>
+<h1>Read Archive</h1>
+<ul>
+<li><a href="https://example.com/c" time_added="1767398400" tags="">Article C</a></li>
+</ul>
+</body></html>`;
+
+describe('BookmarksParser', () => {
+	const parser = new BookmarksParser();
+
+	it('creates one resource card per unique URL with the nested folder path', () => {
+		const { cards, connections, errors } = parser.parse(bookmarks);
+
+		expect(errors).toEqual([]);
+		expect(connections).toEqual([]);
+		expect(cards.map((c) => [c.source_id, c.name, c.folder, c.sort_order])).toEqual([
+			['https://www.rust-lang.org/', 'Rust & Cargo', 'Bookmarks Bar', 0],
+			['https://docs.rs/', 'Docs.rs', 'Bookmarks Bar/Dev', 0],
+			['https://github.com/features/actions', 'GitHub Actions', 'Bookmarks Bar/Dev/CI∕CD', 0],
+			['https://news.ycombinator.com/', 'Hacker News', null, 0],
+		]);
+		expect(cards.every((c) => c.card_type === 'resource' && c.source === 'bookmarks')).toBe(true);
+	});
+
+	it('maps ADD_DATE, LAST_MODIFIED, TAGS and <DD> descriptions', () => {
+		const { cards } = parser.parse(bookmarks);
+		const docs = cards.find((c) => c.name === 'Docs.rs');
+
+		expect(docs).toMatchObject({
+			url: 'https://docs.rs/',
+			content: 'Documentation for every crate',
+			tags: ['rust', 'docs'],
+			created_at: '2026-01-03T00:00:00Z',
+			modified_at: '2026-01-04T00:00:00Z',
+		});
+		expect(cards[0]).toMatchObject({ created_at: '2026-01-01T00:00:00Z', modified_at: '2026-01-01T00:00:00Z' });
+		// Microsecond timestamps are scaled down to seconds
+		expect(cards[3]?.created_at).toBe('2026-01-07T00:00:00Z');
+	});
+
+	it('uses Pocket <h1> sections as folders', () => {
+		const { cards } = parser.parse(pocket);
+
+		expect(cards.map((c) => [c.name, c.folder, c.tags, c.created_at])).toEqual([
+			['Article A', 'Unread', ['reading', 'long'], '2026-01-01T00:00:00Z'],
+			['https://example.com/b', 'Unread', [], '2026-01-02T00:00:00Z'],
+			['Article C', 'Read Archive', [], '2026-01-03T00:00:00Z'],
+		]);
+	});
+
+	it('detects bookmark documents and rejects ordinary web pages', () => {
+		expect(isBookmarksDocument(bookmarks)).toBe(true);
+		expect(isBookmarksDocument(pocket)).toBe(true);
+		expect(isBookmarksDocument('<html><body><a href="https://example.com">x</a></body></html>')).toBe(false);
+		expect(() => parser.parse('<html><body>Clipping</body></html>')).toThrow('Not a bookmarks document');
+	});
+});
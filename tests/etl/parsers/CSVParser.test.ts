@@ -4,6 +4,7 @@
 import { readFileSync } from 'node:fs';
 import { join } from 'node:path';
 import { describe, expect, it } from 'vitest';
+import type { MappingProfile } from '../../../src/etl/MappingProfile';
 import { CSVParser } from '../../../src/etl/parsers/CSVParser';
 
 const fixturesPath = join(__dirname, '../fixtures');
@@ -172,6 +173,70 @@ content here"`;
 		});
 	});
 
+	describe('Unmapped columns', () => {
+		it('captures extra columns as custom properties keyed by header', () => {
+			const parser = new CSVParser();
+			const content = `title,Revenue,Stage,id
+"Acme",1200,Won,x1`;
+
+			const result = parser.parse([{ path: 'deals.csv', content }]);
+
+			expect(result.cards[0]?.properties).toEqual({ Revenue: '1200', Stage: 'Won' });
+		});
+
+		it('omits properties when every column is mapped', () => {
+			const parser = new CSVParser();
+			const content = `title,content
+"Note","Body"`;
+
+			const result = parser.parse([{ path: 'notes.csv', content }]);
+
+			expect(result.cards[0]).not.toHaveProperty('properties');
+		});
+	});
+
+	describe('Mapping profile', () => {
+		const profile: MappingProfile = {
+			id: 'crm',
+			name: 'CRM',
+			headers: ['amount', 'close date', 'deal', 'id', 'stage'],
+			fingerprint: 'amount|close date|deal|id|stage',
+			rules: {
+				columns: { Deal: 'name', 'Close Date': 'due_at', Id: 'source_id', Stage: 'status' },
+				dateFormat: 'MM/DD/YYYY',
+				coercions: { Amount: 'number' },
+				defaultCardType: 'task',
+			},
+			created_at: '2026-01-01T00:00:00Z',
+			updated_at: '2026-01-01T00:00:00Z',
+		};
+
+		it('maps rows through the profile instead of auto-detection', () => {
+			const parser = new CSVParser();
+			const content = `Id,Deal,Stage,Close Date,Amount,title
+7,Acme renewal,Won,01/31/2026,"12,000",ignored`;
+
+			const result = parser.parse([{ path: 'week-05.csv', content }], { profile });
+
+			expect(result.cards[0]).toMatchObject({
+				name: 'Acme renewal',
+				status: 'Won',
+				due_at: '2026-01-31T00:00:00.000Z',
+				card_type: 'task',
+				source: 'csv',
+				source_id: 'crm:7',
+				properties: { Amount: 12000 },
+			});
+		});
+
+		it('reads the header row for profile suggestion', () => {
+			const parser = new CSVParser();
+
+			expect(parser.readHeaders([{ path: 'a.csv', content: '\uFEFFId,Deal\n1,A\n2,B' }])).toEqual(['Id', 'Deal']);
+			expect(parser.readHeaders([{ path: 'empty.csv', content: '' }])).toEqual([]);
+		});
+	});
+
 	describe('Edge cases', () => {
 		it('handles empty file list', () => {
 			const parser = new CSVParser();
@@ -0,0 +1,86 @@
+// Isometry v5 — Email Parser Tests
+// mbox / .eml -> message cards with reply_to threads, from/to people and attachments.
+
+import { readFileSync } from 'node:fs';
+import { join } from 'node:path';
+import { describe, expect, it } from 'vitest';
+import { EmailParser } from '../../../src/etl/parsers/EmailParser';
+
+const mailbox = readFileSync(join(__dirname, '../fixtures/mailbox.mbox'), 'utf-8');
+
+describe('EmailParser', () => {
+	const parser = new EmailParser();
+
+	it('splits an mbox into message cards keyed by Message-ID', () => {
+		const { cards, errors } = parser.parse(mailbox, { filename: 'mailbox.mbox' });
+		const messages = cards.filter((c) => c.card_type === 'message');
+
+		expect(errors).toEqual([]);
+		expect(messages.map((c) => [c.source_id, c.name, c.created_at])).toEqual([
+			['m1@engines.example', 'Plan für Q1', '2026-01-05T09:00:00Z'],
+			['m2@example.com', 'Re: Plan', '2026-01-06T08:30:00Z'],
+			['mailbox.mbox#2', 'Notes', '2026-01-07T12:00:00Z'],
+		]);
+		expect(messages[0]).toMatchObject({ folder: 'mailbox', tags: ['Important', 'Work'], source: 'email' });
+	});
+
+	it('decodes quoted-printable, base64 and HTML-only bodies', () => {
+		const { cards } = parser.parse(mailbox);
+		const byName = new Map(cards.map((c) => [c.name, c]));
+
+		expect(byName.get('Plan für Q1')?.content).toBe('Hi all,\nhere is the plan – see below.');
+		expect(byName.get('Re: Plan')?.content).toBe('Looks good.');
+		// mboxrd `>From ` escapes are undone
+		expect(byName.get('Notes')?.content).toBe('Meeting notes\n\nFrom the archive: link\nDone & dusted');
+	});
+
+	it('creates one person card per address and from/to/reply_to edges', () => {
+		const { cards, connections } = parser.parse(mailbox, { filename: 'mailbox.mbox' });
+		const people = cards.filter((c) => c.card_type === 'person');
+
+		expect(people.map((c) => [c.source_id, c.name])).toEqual([
+			['mailto:ada@engines.example', 'Lovelace, Ada'],
+			['mailto:bob@example.com', 'Bob'],
+			['mailto:carol@example.com', 'carol@example.com'],
+			['mailto:dan@example.com', 'dan@example.com'],
+			['mailto:erin@example.com', 'erin@example.com'],
+		]);
+		expect(
+			connections.filter((c) => c.label !== 'attachment').map((c) => [c.source_id, c.label, c.target_id, c.weight]),
+		).toEqual([
+			['m1@engines.example', 'from', 'mailto:ada@engines.example', 1],
+			['m1@engines.example', 'to', 'mailto:bob@example.com', 1],
+			['m1@engines.example', 'to', 'mailto:carol@example.com', 1],
+			['m1@engines.example', 'to', 'mailto:dan@example.com', 0.5],
+			['m1@engines.example', 'to', 'mailto:erin@example.com', 0.5],
+			['m2@example.com', 'reply_to', 'm1@engines.example', 1],
+			['m2@example.com', 'from', 'mailto:bob@example.com', 1],
+			['m2@example.com', 'to', 'mailto:ada@engines.example', 1],
+			['mailbox.mbox#2', 'from', 'mailto:carol@example.com', 1],
+			['mailbox.mbox#2', 'to', 'mailto:ada@engines.example', 1],
+		]);
+	});
+
+	it('turns attachments into resource and media cards', () => {
+		const { cards, connections } = parser.parse(mailbox);
+		const files = cards.filter((c) => c.card_type === 'media' || c.card_type === 'resource');
+
+		expect(files.map((c) => [c.source_id, c.card_type, c.name, c.mime_type])).toEqual([
+			['m2@example.com#0:budget.pdf', 'resource', 'budget.pdf', 'application/pdf'],
+			['m2@example.com#1:chart ü.png', 'media', 'chart ü.png', 'image/png'],
+		]);
+		expect(connections.filter((c) => c.label === 'attachment').map((c) => c.target_id)).toEqual([
+			'm2@example.com#0:budget.pdf',
+			'm2@example.com#1:chart ü.png',
+		]);
+	});
+
+	it('parses a single .eml and reports text without headers', () => {
+		const eml = 'Subject: Hello\r\nFrom: a@b.example\r\nReferences: <p1@x> <p2@x>\r\n\r\nBody';
+		const { cards, connections } = parser.parse(eml, { defaultTimestamp: '2026-01-01T00:00:00Z' });
+
+		expect(cards[0]).toMatchObject({ name: 'Hello', content: 'Body', created_at: '2026-01-01T00:00:00Z' });
+		expect(connections[0]).toMatchObject({ label: 'reply_to', target_id: 'p2@x' });
+		expect(parser.parse('just some text').errors).toMatchObject([{ index: 0, message: 'Message has no headers' }]);
+	});
+});
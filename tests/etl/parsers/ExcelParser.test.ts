@@ -3,6 +3,7 @@
 
 import { beforeAll, beforeEach, describe, expect, it } from 'vitest';
 import * as XLSX from 'xlsx';
+import type { MappingProfile } from '../../../src/etl/MappingProfile';
 import { ExcelParser } from '../../../src/etl/parsers/ExcelParser';
 
 describe('ExcelParser', () => {
@@ -197,4 +198,129 @@ describe('ExcelParser', () => {
 		expect(result.cards[0]?.name).toBe('Custom Note');
 		expect(result.cards[0]?.content).toBe('Custom content');
 	});
+
+	it('maps rows through a mapping profile and reads the header row', async () => {
+		const workbook = XLSX.utils.book_new();
+		const sheet = XLSX.utils.aoa_to_sheet([
+			['Ticket', 'Summary', 'Opened', 'Kind'],
+			['T-1', 'Printer jam', new Date('2026-01-05T00:00:00Z'), 'Bug report'],
+		]);
+		XLSX.utils.book_append_sheet(workbook, sheet, 'Tickets');
+		const buffer = XLSX.write(workbook, { type: 'buffer', bookType: 'xlsx' });
+		const ticketBuffer = buffer.buffer.slice(buffer.byteOffset, buffer.byteOffset + buffer.byteLength);
+		const profile: MappingProfile = {
+			id: 'helpdesk',
+			name: 'Helpdesk',
+			headers: ['kind', 'opened', 'summary', 'ticket'],
+			fingerprint: 'kind|opened|summary|ticket',
+			rules: {
+				columns: { Ticket: 'source_id', Summary: 'name', Opened: 'created_at' },
+				cardTypeRules: [{ column: 'Kind', contains: 'bug', cardType: 'task' }],
+			},
+			created_at: '2026-01-01T00:00:00Z',
+			updated_at: '2026-01-01T00:00:00Z',
+		};
+
+		expect(await parser.readHeaders(ticketBuffer)).toEqual(['Ticket', 'Summary', 'Opened', 'Kind']);
+
+		const result = await parser.parse(ticketBuffer, { profile });
+		expect(result.cards[0]).toMatchObject({
+			name: 'Printer jam',
+			card_type: 'task',
+			source: 'excel',
+			source_id: 'helpdesk:T-1',
+			properties: { Kind: 'Bug report' },
+		});
+		expect(result.cards[0]?.created_at).toContain('2026-01-05');
+	});
+
+	describe('multi-sheet workbooks', () => {
+		let northwind: ArrayBuffer;
+
+		beforeAll(() => {
+			const workbook = XLSX.utils.book_new();
+			const append = (name: string, rows: unknown[][]) =>
+				XLSX.utils.book_append_sheet(workbook, XLSX.utils.aoa_to_sheet(rows), name);
+			append('Customers', [
+				['CustomerID', 'CompanyName', 'City'],
+				['ALFKI', 'Alfreds Futterkiste', 'Berlin'],
+				['VINET', 'Vins et alcools Chevalier', 'Reims'],
+			]);
+			append('Orders', [
+				['OrderID', 'CustomerID', 'EmployeeID'],
+				[10248, 'VINET', 5],
+				[10249, 'ALFKI', 6],
+				[10250, 'NOPE', 4],
+			]);
+			append('Order Details', [
+				['OrderID', 'ProductID', 'Quantity'],
+				[10248, 11, 12],
+				[10248, 42, 10],
+				[10249, 11, 9],
+			]);
+			append('Products', [
+				['ProductID', 'ProductName'],
+				[11, 'Queso Cabrales'],
+				[42, 'Singaporean Hokkien Fried Mee'],
+			]);
+			append('Notes', [['Scratch']]);
+			const buffer = XLSX.write(workbook, { type: 'buffer', bookType: 'xlsx' });
+			northwind = buffer.buffer.slice(buffer.byteOffset, buffer.byteOffset + buffer.byteLength);
+		});
+
+		const allSheets = ['Customers', 'Orders', 'Order Details', 'Products'];
+
+		it('lists sheets with row counts and header rows', async () => {
+			expect(await parser.listSheets(northwind)).toEqual([
+				{ name: 'Customers', rows: 2, headers: ['CustomerID', 'CompanyName', 'City'] },
+				{ name: 'Orders', rows: 3, headers: ['OrderID', 'CustomerID', 'EmployeeID'] },
+				{ name: 'Order Details', rows: 3, headers: ['OrderID', 'ProductID', 'Quantity'] },
+				{ name: 'Products', rows: 2, headers: ['ProductID', 'ProductName'] },
+				{ name: 'Notes', rows: 0, headers: ['Scratch'] },
+			]);
+		});
+
+		it('imports one folder per sheet, keyed by each sheet id column', async () => {
+			const result = await parser.parse(northwind, { sheets: allSheets });
+
+			expect(result.errors).toEqual([]);
+			expect(result.cards.map((c) => [c.folder, c.source_id, c.name])).toEqual([
+				['Customers', 'Customers:ALFKI', 'Alfreds Futterkiste'],
+				['Customers', 'Customers:VINET', 'Vins et alcools Chevalier'],
+				['Orders', 'Orders:10248', 'Orders 10248'],
+				['Orders', 'Orders:10249', 'Orders 10249'],
+				['Orders', 'Orders:10250', 'Orders 10250'],
+				['Order Details', 'Order Details:0', 'Order Details 0'],
+				['Order Details', 'Order Details:1', 'Order Details 1'],
+				['Order Details', 'Order Details:2', 'Order Details 2'],
+				['Products', 'Products:11', 'Queso Cabrales'],
+				['Products', 'Products:42', 'Singaporean Hokkien Fried Mee'],
+			]);
+		});
+
+		it('turns lookup columns holding another sheet ids into connections', async () => {
+			const result = await parser.parse(northwind, { sheets: allSheets });
+
+			expect(result.connections.map((c) => [c.source_id, c.label, c.target_id])).toEqual([
+				['Orders:10248', 'customer', 'Customers:VINET'],
+				['Orders:10249', 'customer', 'Customers:ALFKI'],
+				['Order Details:0', 'order', 'Orders:10248'],
+				['Order Details:1', 'order', 'Orders:10248'],
+				['Order Details:2', 'order', 'Orders:10249'],
+				['Order Details:0', 'product', 'Products:11'],
+				['Order Details:1', 'product', 'Products:42'],
+				['Order Details:2', 'product', 'Products:11'],
+			]);
+		});
+
+		it('reports missing sheets and treats a single selected sheet as a plain import', async () => {
+			const missing = await parser.parse(northwind, { sheets: ['Customers', 'Suppliers'] });
+			expect(missing.errors[0]?.message).toBe('Sheet "Suppliers" not found in workbook');
+			expect(missing.cards).toHaveLength(2);
+
+			const single = await parser.parse(northwind, { sheets: ['Products'] });
+			expect(single.cards.map((c) => c.source_id)).toEqual(['0', '1']);
+			expect(single.connections).toEqual([]);
+		});
+	});
 });
@@ -0,0 +1,146 @@
+// Isometry v5 — iCalendar Parser Tests
+// VEVENT -> event cards, VTODO -> task cards, RRULE expansion within a window.
+
+import { readFileSync } from 'node:fs';
+import { join } from 'node:path';
+import { describe, expect, it } from 'vitest';
+import { ICSParser } from '../../../src/etl/parsers/ICSParser';
+
+const team = readFileSync(join(__dirname, '../fixtures/team.ics'), 'utf-8');
+const window = { windowStart: '2026-01-01T00:00:00Z', windowEnd: '2026-12-31T00:00:00Z' };
+
+function calendar(...body: string[]): string {
+	return ['BEGIN:VCALENDAR', 'VERSION:2.0', ...body, 'END:VCALENDAR'].join('\r\n');
+}
+
+function event(uid: string, ...props: string[]): string[] {
+	return ['BEGIN:VEVENT', `UID:${uid}`, 'SUMMARY:Event', ...props, 'END:VEVENT'];
+}
+
+describe('ICSParser', () => {
+	const parser = new ICSParser();
+
+	it('maps VEVENT fields, resolving TZID times to UTC', () => {
+		const { cards, errors } = parser.parse(team, window);
+		const kickoff = cards.find((c) => c.source_id === 'kickoff@example.com');
+
+		expect(errors).toEqual([]);
+		expect(kickoff).toMatchObject({
+			card_type: 'event',
+			name: 'Project kickoff, Q1',
+			content: 'Agenda:\n- scope\n- owners',
+			location_name: 'Room 4',
+			latitude: 52.52,
+			longitude: 13.405,
+			event_start: '2026-01-05T08:00:00Z',
+			event_end: '2026-01-05T09:30:00Z',
+			tags: ['work', 'planning'],
+			status: 'confirmed',
+			url: 'https://example.com/kickoff',
+			folder: 'Team',
+			is_collective: true,
+			source: 'ics',
+			created_at: '2025-12-20T08:00:00Z',
+			modified_at: '2025-12-22T09:30:00Z',
+		});
+	});
+
+	it('keeps all-day events as dates', () => {
+		const { cards } = parser.parse(team, window);
+
+		expect(cards.find((c) => c.name === 'Offsite')).toMatchObject({
+			event_start: '2026-02-12',
+			event_end: '2026-02-14',
+		});
+	});
+
+	it('expands RRULE with COUNT, EXDATE, overrides and DST-stable wall times', () => {
+		const { cards } = parser.parse(team, window);
+		const standups = cards.filter((c) => c.source_id.startsWith('standup@example.com'));
+
+		expect(standups.map((c) => [c.source_id, c.name, c.event_start, c.event_end])).toEqual([
+			['standup@example.com#2026-03-16T08:30:00Z', 'Standup', '2026-03-16T08:30:00Z', '2026-03-16T08:45:00Z'],
+			['standup@example.com#2026-03-23T08:30:00Z', 'Standup', '2026-03-23T08:30:00Z', '2026-03-23T08:45:00Z'],
+			[
+				'standup@example.com#2026-03-25T08:30:00Z',
+				'Standup (moved)',
+				'2026-03-25T10:00:00Z',
+				'2026-03-25T10:15:00Z',
+			],
+			// Berlin switches to summer time on 2026-03-29: 09:30 local is now 07:30 UTC
+			['standup@example.com#2026-03-30T07:30:00Z', 'Standup', '2026-03-30T07:30:00Z', '2026-03-30T07:45:00Z'],
+			['standup@example.com#2026-04-01T07:30:00Z', 'Standup', '2026-04-01T07:30:00Z', '2026-04-01T07:45:00Z'],
+		]);
+	});
+
+	it('maps VTODO to tasks with status and priority', () => {
+		const { cards } = parser.parse(team, window);
+		const byName = new Map(cards.map((c) => [c.name, c]));
+
+		expect(byName.get('Send report')).toMatchObject({
+			card_type: 'task',
+			due_at: '2026-01-10T17:00:00Z',
+			status: 'active',
+			priority: 3,
+			completed_at: null,
+		});
+		expect(byName.get('Pay invoice')).toMatchObject({
+			status: 'done',
+			priority: 2,
+			completed_at: '2026-01-03T10:00:00Z',
+		});
+	});
+
+	it('clips unbounded rules to the window', () => {
+		const ics = calendar(...event('daily', 'DTSTART:20250101T120000Z', 'RRULE:FREQ=DAILY'));
+		const { cards } = parser.parse(ics, { windowStart: '2026-03-01T00:00:00Z', windowEnd: '2026-03-07T23:00:00Z' });
+
+		expect(cards.map((c) => c.event_start)).toEqual([
+			'2026-03-01T12:00:00Z',
+			'2026-03-02T12:00:00Z',
+			'2026-03-03T12:00:00Z',
+			'2026-03-04T12:00:00Z',
+			'2026-03-05T12:00:00Z',
+			'2026-03-06T12:00:00Z',
+			'2026-03-07T12:00:00Z',
+		]);
+	});
+
+	it('handles monthly ordinals, month-day gaps, UNTIL, INTERVAL and RDATE', () => {
+		const ics = calendar(
+			...event('last-friday', 'DTSTART:20260130T100000Z', 'RRULE:FREQ=MONTHLY;BYDAY=-1FR;COUNT=3'),
+			...event('thirty-first', 'DTSTART;VALUE=DATE:20260131', 'RRULE:FREQ=MONTHLY;UNTIL=20260601'),
+			...event(
+				'fortnightly',
+				'DTSTART:20260105T100000Z',
+				'RRULE:FREQ=WEEKLY;INTERVAL=2;UNTIL=20260202T100000Z',
+				'RDATE:20260110T100000Z',
+			),
+		);
+		const { cards } = parser.parse(ics, window);
+		const starts = (uid: string) =>
+			cards.filter((c) => c.source_id.startsWith(`${uid}#`)).map((c) => c.event_start?.slice(0, 10));
+
+		expect(starts('last-friday')).toEqual(['2026-01-30', '2026-02-27', '2026-03-27']);
+		expect(starts('thirty-first')).toEqual(['2026-01-31', '2026-03-31', '2026-05-31']);
+		expect(starts('fortnightly')).toEqual(['2026-01-05', '2026-01-10', '2026-01-19', '2026-02-02']);
+	});
+
+	it('uses defaults for missing fields and the filename as folder', () => {
+		const ics = calendar('BEGIN:VEVENT', 'DTSTART:20260105T090000', 'END:VEVENT');
+		const { cards } = parser.parse(ics, { filename: 'home.ics', defaultTimestamp: '2026-01-01T00:00:00Z' });
+
+		expect(cards[0]).toMatchObject({
+			name: 'Untitled Event',
+			folder: 'home',
+			source_id: 'home.ics#0',
+			event_start: '2026-01-05T09:00:00Z',
+			event_end: '2026-01-05T09:00:00Z',
+			created_at: '2026-01-01T00:00:00Z',
+		});
+	});
+
+	it('rejects documents that are not iCalendar', () => {
+		expect(() => parser.parse('BEGIN:VCARD\nEND:VCARD')).toThrow('Not an iCalendar document');
+	});
+});
@@ -2,6 +2,7 @@
 // TDD tests for JSON array parsing with field auto-detection
 
 import { beforeEach, describe, expect, it } from 'vitest';
+import type { MappingProfile } from '../../../src/etl/MappingProfile';
 import { JSONParser } from '../../../src/etl/parsers/JSONParser';
 
 describe('JSONParser', () => {
@@ -280,4 +281,42 @@ describe('JSONParser', () => {
 			expect(msg).toContain('gamma');
 		});
 	});
+
+	describe('mapping profile', () => {
+		const profile: MappingProfile = {
+			id: 'reading',
+			name: 'Reading list',
+			headers: ['added', 'book', 'labels', 'read'],
+			fingerprint: 'added|book|labels|read',
+			rules: {
+				columns: { book: 'name', labels: 'tags', added: 'created_at' },
+				dateFormat: 'unix',
+				coercions: { read: 'boolean' },
+				defaultCardType: 'reference',
+			},
+			created_at: '2026-01-01T00:00:00Z',
+			updated_at: '2026-01-01T00:00:00Z',
+		};
+		const input = JSON.stringify({
+			items: [
+				{ book: 'Dune', labels: ['sf', 'classic'], added: 1767571200, read: 'yes' },
+				{ book: 'Emma', added: 1767657600, read: 'no', pages: 474 },
+			],
+		});
+
+		it('maps items through the profile', () => {
+			const result = parser.parse(input, { profile });
+
+			expect(result.cards.map((c) => [c.name, c.card_type, c.tags, c.created_at, c.properties])).toEqual([
+				['Dune', 'reference', ['sf', 'classic'], '2026-01-05T00:00:00.000Z', { read: true }],
+				['Emma', 'reference', [], '2026-01-06T00:00:00.000Z', { read: false, pages: 474 }],
+			]);
+			expect(result.cards[1]?.source_id).toBe('1');
+		});
+
+		it('reads the union of item keys as headers', () => {
+			expect(parser.readHeaders(input)).toEqual(['book', 'labels', 'added', 'read', 'pages']);
+			expect(parser.readHeaders('not json')).toEqual([]);
+		});
+	});
 });
@@ -0,0 +1,77 @@
+// Isometry v5 — OPML Parser Tests
+// Outline -> nested cards with `contains` connections and sibling sort_order.
+
+import { readFileSync } from 'node:fs';
+import { join } from 'node:path';
+import { describe, expect, it } from 'vitest';
+import { OPMLParser } from '../../../src/etl/parsers/OPMLParser';
+
+const outline = readFileSync(join(__dirname, '../fixtures/outline.opml'), 'utf-8');
+
+describe('OPMLParser', () => {
+	const parser = new OPMLParser();
+
+	it('creates one card per outline with index-path source ids and sibling order', () => {
+		const { cards, errors } = parser.parse(outline, { filename: 'plan.opml' });
+
+		expect(errors).toEqual([]);
+		expect(cards.map((c) => [c.source_id, c.name, c.sort_order])).toEqual([
+			['plan.opml#0', 'Research', 0],
+			['plan.opml#0.0', 'Interviews', 0],
+			['plan.opml#0.1', 'Competitor list', 1],
+			['plan.opml#1', 'Build', 1],
+			['plan.opml#1.0', 'API & SDK', 0],
+			['plan.opml#1.0.0', 'Auth', 0],
+			['plan.opml#2', 'Ship', 2],
+		]);
+	});
+
+	it('joins parents to children with contains connections', () => {
+		const { connections } = parser.parse(outline, { filename: 'plan.opml' });
+
+		expect(connections.map((c) => [c.source_id, c.target_id, c.label])).toEqual([
+			['plan.opml#0', 'plan.opml#0.0', 'contains'],
+			['plan.opml#0', 'plan.opml#0.1', 'contains'],
+			['plan.opml#1', 'plan.opml#1.0', 'contains'],
+			['plan.opml#1.0', 'plan.opml#1.0.0', 'contains'],
+		]);
+	});
+
+	it('maps notes, links, categories, completion and dates', () => {
+		const { cards } = parser.parse(outline);
+		const byName = new Map(cards.map((c) => [c.name, c]));
+
+		expect(byName.get('Research')).toMatchObject({
+			content: 'Talk to users\nSummarize findings',
+			is_collective: true,
+			folder: 'Launch Plan',
+			source: 'opml',
+			created_at: '2026-01-05T09:00:00Z',
+			modified_at: '2026-01-06T10:30:00Z',
+		});
+		expect(byName.get('Interviews')).toMatchObject({ tags: ['ux', 'research'], is_collective: false });
+		expect(byName.get('Competitor list')).toMatchObject({
+			card_type: 'resource',
+			url: 'https://example.com/compare?a=1&b=2',
+		});
+		expect(byName.get('Auth')).toMatchObject({
+			created_at: '2026-01-07T08:00:00Z',
+			completed_at: '2026-01-06T10:30:00Z',
+		});
+		// Without a filename, the head title keys the source ids
+		expect(byName.get('Ship')?.source_id).toBe('Launch Plan#2');
+	});
+
+	it('handles single-quoted attributes, missing text and an empty body', () => {
+		const feeds = "<opml><body><outline title='Feeds'><outline xmlUrl='https://x.test/rss'/></outline></body></opml>";
+		const { cards } = parser.parse(feeds, { defaultTimestamp: '2026-01-01T00:00:00Z' });
+
+		expect(cards.map((c) => c.name)).toEqual(['Feeds', 'https://x.test/rss']);
+		expect(cards[0]?.created_at).toBe('2026-01-01T00:00:00Z');
+		expect(parser.parse('<opml><body></body></opml>').cards).toEqual([]);
+	});
+
+	it('rejects documents that are not OPML', () => {
+		expect(() => parser.parse('<html><body></body></html>')).toThrow('Not an OPML document');
+	});
+});
@@ -0,0 +1,208 @@
+// Isometry v5 — Obsidian Vault Parser Tests
+// Link graph fidelity: aliases, heading/block/display links, embeds, nested
+// tags, attachments as media cards, and Obsidian-style name resolution.
+
+import { describe, expect, it } from 'vitest';
+import { ObsidianParser, type VaultFile } from '../../../src/etl/parsers/ObsidianParser';
+import { isHiddenVaultPath, readVaultFiles } from '../../../src/etl/parsers/vault';
+
+const TS = '2026-01-01T00:00:00Z';
+
+function parse(files: VaultFile[]) {
+	return new ObsidianParser().parse(files, { defaultTimestamp: TS });
+}
+
+/** Connections as "source -> target (label)" for compact assertions. */
+function edges(files: VaultFile[]): string[] {
+	return parse(files)
+		.connections.map((c) => `${c.source_id} -> ${c.target_id} (${c.label})`)
+		.sort();
+}
+
+describe('ObsidianParser', () => {
+	describe('notes', () => {
+		it('titles notes by filename unless frontmatter sets a title', () => {
+			const { cards } = parse([
+				{ path: 'Projects/Launch plan.md', content: '# A heading\n\nBody' },
+				{ path: 'Other.md', content: '---\ntitle: Custom\n---\nBody' },
+			]);
+
+			expect(cards.map((c) => c.name)).toEqual(['Launch plan', 'Custom']);
+			expect(cards[0]).toMatchObject({
+				card_type: 'note',
+				folder: 'Projects',
+				source: 'obsidian',
+				source_id: 'Projects/Launch plan.md',
+				mime_type: 'text/markdown',
+			});
+			expect(cards[1]?.folder).toBeNull();
+		});
+
+		it('uses frontmatter dates, then the file mtime', () => {
+			const { cards } = parse([
+				{ path: 'a.md', content: '---\ncreated: 2025-03-01T09:00:00Z\n---\nx', modified: '2025-06-01T00:00:00Z' },
+				{ path: 'b.md', content: 'x' },
+			]);
+
+			expect(cards[0]).toMatchObject({ created_at: '2025-03-01T09:00:00Z', modified_at: '2025-06-01T00:00:00Z' });
+			expect(cards[1]).toMatchObject({ created_at: TS, modified_at: TS });
+		});
+
+		it('keeps aliases and other frontmatter keys as properties', () => {
+			const { cards } = parse([
+				{ path: 'a.md', content: '---\naliases: [Alpha, First]\nrating: 4\ncssclasses: [wide]\n---\nx' },
+			]);
+
+			expect(cards[0]?.properties).toEqual({ rating: 4, aliases: 'Alpha, First' });
+		});
+
+		it('merges frontmatter and inline tags, keeping nested tags whole', () => {
+			const { cards } = parse([
+				{
+					path: 'a.md',
+					content:
+						'---\ntags: [project]\n---\n# Heading\n\nWork on #area/home and #status/in-progress.\n`#notatag` #2026 x#no',
+				},
+			]);
+
+			expect(cards[0]?.tags).toEqual(['project', 'area/home', 'status/in-progress']);
+		});
+
+		it('skips hidden folders such as .obsidian and .trash', () => {
+			const { cards } = parse([
+				{ path: '.obsidian/app.json', content: '' },
+				{ path: '.trash/old.md', content: 'x' },
+				{ path: 'note.md', content: 'x' },
+			]);
+
+			expect(cards.map((c) => c.source_id)).toEqual(['note.md']);
+		});
+	});
+
+	describe('attachments', () => {
+		it('creates media cards for non-markdown files', () => {
+			const { cards } = parse([{ path: 'attachments/Diagram.PNG', content: '', modified: '2025-02-02T00:00:00Z' }]);
+
+			expect(cards[0]).toMatchObject({
+				card_type: 'media',
+				name: 'Diagram.PNG',
+				folder: 'attachments',
+				mime_type: 'image/png',
+				content: null,
+				source_id: 'attachments/Diagram.PNG',
+				modified_at: '2025-02-02T00:00:00Z',
+			});
+		});
+	});
+
+	describe('links', () => {
+		it('strips headings, block references and display text from link targets', () => {
+			expect(
+				edges([
+					{ path: 'a.md', content: 'See [[b#Section]], [[b#^block-1|that line]] and [[c|C note]]. [[#Local]]' },
+					{ path: 'b.md', content: 'Text ^block-1' },
+					{ path: 'c.md', content: '' },
+				]),
+			).toEqual(['a.md -> b.md (links_to)', 'a.md -> c.md (links_to)']);
+		});
+
+		it('labels embeds separately from links, for notes and attachments', () => {
+			expect(
+				edges([
+					{ path: 'a.md', content: '![[b]]\n![[diagram.png|300]]\n![alt](attachments/photo%201.jpg)\n[[b]]' },
+					{ path: 'b.md', content: '' },
+					{ path: 'attachments/diagram.png', content: '' },
+					{ path: 'attachments/photo 1.jpg', content: '' },
+				]),
+			).toEqual([
+				'a.md -> attachments/diagram.png (embeds)',
+				'a.md -> attachments/photo 1.jpg (embeds)',
+				'a.md -> b.md (embeds)',
+				'a.md -> b.md (links_to)',
+			]);
+		});
+
+		it('resolves links by alias, case-insensitively', () => {
+			expect(
+				edges([
+					{ path: 'a.md', content: 'Ask [[JD]] or [[john doe]]' },
+					{ path: 'People/John Doe.md', content: '---\naliases:\n  - JD\n---\n' },
+				]),
+			).toEqual(['a.md -> People/John Doe.md (links_to)']);
+		});
+
+		it('prefers a same-folder note for ambiguous names, then the shortest path', () => {
+			const files: VaultFile[] = [
+				{ path: 'Work/a.md', content: '[[Index]]' },
+				{ path: 'Home/b.md', content: '[[Index]]' },
+				{ path: 'Work/Index.md', content: '' },
+				{ path: 'Archive/Old/Index.md', content: '' },
+				{ path: 'Index.md', content: '' },
+			];
+
+			expect(edges(files)).toEqual(['Home/b.md -> Index.md (links_to)', 'Work/a.md -> Work/Index.md (links_to)']);
+		});
+
+		it('resolves path links absolutely, relatively and by suffix', () => {
+			expect(
+				edges([
+					{ path: 'Work/a.md', content: '[[Work/Sub/b]] [[../Home/c.md]] [link](Sub/b.md) [[Old/d]]' },
+					{ path: 'Work/Sub/b.md', content: '' },
+					{ path: 'Home/c.md', content: '' },
+					{ path: 'Archive/Old/d.md', content: '' },
+				]),
+			).toEqual([
+				'Work/a.md -> Archive/Old/d.md (links_to)',
+				'Work/a.md -> Home/c.md (links_to)',
+				'Work/a.md -> Work/Sub/b.md (links_to)',
+			]);
+		});
+
+		it('links from frontmatter properties and ignores code, URLs and unresolved targets', () => {
+			expect(
+				edges([
+					{
+						path: 'a.md',
+						content: '---\nrelated: "[[b]]"\n---\n`[[c]]`\n```\n[[c]]\n```\n[site](https://example.com) [[missing]]',
+					},
+					{ path: 'b.md', content: '' },
+					{ path: 'c.md', content: '' },
+				]),
+			).toEqual(['a.md -> b.md (links_to)']);
+		});
+
+		it('treats dotted names as notes when no attachment matches', () => {
+			expect(
+				edges([
+					{ path: 'a.md', content: '[[Release v1.2]]' },
+					{ path: 'Release v1.2.md', content: '' },
+				]),
+			).toEqual(['a.md -> Release v1.2.md (links_to)']);
+		});
+	});
+});
+
+describe('readVaultFiles', () => {
+	it('strips the picked folder, reads only markdown and skips hidden files', async () => {
+		const file = (relativePath: string, text: string) => {
+			const f = new File([text], relativePath.split('/').pop()!, { lastModified: Date.UTC(2025, 0, 2) });
+			Object.defineProperty(f, 'webkitRelativePath', { value: relativePath });
+			return f;
+		};
+
+		const vault = await readVaultFiles([
+			file('My Vault/Notes/a.md', '# A'),
+			file('My Vault/img.png', 'binary'),
+			file('My Vault/.obsidian/app.json', '{}'),
+		]);
+
+		expect(vault).toEqual({
+			name: 'My Vault',
+			files: [
+				{ path: 'Notes/a.md', content: '# A', modified: '2025-01-02T00:00:00Z' },
+				{ path: 'img.png', content: '', modified: '2025-01-02T00:00:00Z' },
+			],
+		});
+		expect(isHiddenVaultPath('Notes/.trash/x.md')).toBe(true);
+	});
+});
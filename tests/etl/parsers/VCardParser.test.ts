@@ -0,0 +1,83 @@
+// Isometry v5 — vCard Parser Tests
+// Contacts -> person cards, companies -> collective cards, works_at/related edges.
+
+import { readFileSync } from 'node:fs';
+import { join } from 'node:path';
+import { describe, expect, it } from 'vitest';
+import { VCardParser } from '../../../src/etl/parsers/VCardParser';
+
+const contacts = readFileSync(join(__dirname, '../fixtures/contacts.vcf'), 'utf-8');
+
+describe('VCardParser', () => {
+	const parser = new VCardParser();
+
+	it('creates person cards keyed by UID, plus cards for companies without a vCard', () => {
+		const { cards, errors } = parser.parse(contacts, { filename: 'contacts.vcf' });
+
+		expect(errors).toEqual([]);
+		expect(cards.map((c) => [c.source_id, c.name, c.card_type, c.is_collective])).toEqual([
+			['ada-0001', 'Ada Lovelace', 'person', false],
+			['william-0002', 'William King', 'person', false],
+			['engines-org', 'Analytical Engines Ltd', 'person', true],
+			['contacts.vcf#Jürgen Müller', 'Jürgen Müller', 'person', false],
+			['org:rheinwerk', 'Rheinwerk', 'person', true],
+		]);
+	});
+
+	it('maps organization, preferred address, geo, notes and contact details', () => {
+		const { cards } = parser.parse(contacts);
+		const ada = cards.find((c) => c.source_id === 'ada-0001');
+
+		expect(ada).toMatchObject({
+			folder: 'Analytical Engines Ltd',
+			location_name: '1 Engine Row, London, EC1A 1BB, United Kingdom',
+			latitude: 51.5074,
+			longitude: -0.1278,
+			content: 'Wrote the first\nprogram',
+			tags: ['family', 'science'],
+			source: 'vcard',
+			created_at: '2026-01-05T09:00:00Z',
+			modified_at: '2026-01-05T09:00:00Z',
+		});
+		expect(ada?.properties).toEqual({
+			Email: 'ada@engines.example, ada@home.example',
+			Phone: '+44 20 7946 0000',
+			'Job Title': 'Mathematician',
+			Birthday: '1815-12-10',
+		});
+		expect(cards.find((c) => c.source_id === 'william-0002')).toMatchObject({
+			latitude: 51.4,
+			longitude: -0.3,
+			location_name: null,
+			modified_at: '2026-01-06T10:00:00Z',
+		});
+	});
+
+	it('links people to their company and to related contacts by UID or name', () => {
+		const { connections } = parser.parse(contacts, { filename: 'contacts.vcf' });
+
+		expect(connections.map((c) => [c.source_id, c.target_id, c.label])).toEqual([
+			['ada-0001', 'engines-org', 'works_at'],
+			['ada-0001', 'william-0002', 'related'],
+			['william-0002', 'engines-org', 'works_at'],
+			['william-0002', 'ada-0001', 'related'],
+			['contacts.vcf#Jürgen Müller', 'org:rheinwerk', 'works_at'],
+		]);
+	});
+
+	it('decodes vCard 2.1 quoted-printable values and bare TYPE parameters', () => {
+		const { cards } = parser.parse(contacts);
+		const juergen = cards.find((c) => c.name === 'Jürgen Müller');
+
+		expect(juergen?.content).toBe('First line\r\nSecond line');
+		expect(juergen?.properties).toEqual({ Phone: '+49 30 1234' });
+	});
+
+	it('reports vCards without any name and rejects non-vCard input', () => {
+		const { cards, errors } = parser.parse('BEGIN:VCARD\nVERSION:3.0\nTEL:123\nEND:VCARD');
+
+		expect(cards).toEqual([]);
+		expect(errors).toMatchObject([{ index: 0, message: 'vCard has no name' }]);
+		expect(() => parser.parse('BEGIN:VCALENDAR\nEND:VCALENDAR')).toThrow('Not a vCard document');
+	});
+});
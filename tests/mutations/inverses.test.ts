@@ -11,6 +11,7 @@ import {
 	deleteCardMutation,
 	deleteConnectionMutation,
 	updateCardMutation,
+	updateConnectionMutation,
 } from '../../src/mutations/inverses';
 
 // ---------------------------------------------------------------------------
@@ -317,6 +318,40 @@ describe('createConnectionMutation', () => {
 	});
 });
 
+// ---------------------------------------------------------------------------
+// updateConnectionMutation
+// ---------------------------------------------------------------------------
+
+describe('updateConnectionMutation', () => {
+	it('forward has UPDATE connections SET for changed fields only', () => {
+		const m = updateConnectionMutation(FULL_CONNECTION, { label: 'depends on' });
+		expect(m.forward[0]!.sql).toBe('UPDATE connections SET label = ? WHERE id = ?');
+		expect(m.forward[0]!.params).toEqual(['depends on', 'conn-001']);
+	});
+
+	it('inverse restores previous values from before', () => {
+		const m = updateConnectionMutation(FULL_CONNECTION, { weight: 0.5, via_card_id: null });
+		expect(m.inverse[0]!.sql).toBe('UPDATE connections SET weight = ?, via_card_id = ? WHERE id = ?');
+		expect(m.inverse[0]!.params).toEqual([1.5, 'card-003', 'conn-001']);
+	});
+
+	it('never touches id, endpoints or created_at', () => {
+		const m = updateConnectionMutation(FULL_CONNECTION, { label: 'x', weight: 0.2, via_card_id: 'card-004' });
+		expect(m.forward[0]!.sql).not.toMatch(/source_id|target_id|created_at/);
+	});
+
+	it('returns an identity mutation when nothing changes', () => {
+		const m = updateConnectionMutation(FULL_CONNECTION, {});
+		expect(m.forward).toEqual([]);
+		expect(m.inverse).toEqual([]);
+	});
+
+	it('has description mentioning update connection', () => {
+		const m = updateConnectionMutation(FULL_CONNECTION, { label: 'x' });
+		expect(m.description).toMatch(/Update connection/i);
+	});
+});
+
 // ---------------------------------------------------------------------------
 // deleteConnectionMutation
 // ---------------------------------------------------------------------------
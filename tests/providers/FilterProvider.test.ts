@@ -5,8 +5,8 @@
 // TDD Phase: RED → GREEN → REFACTOR
 
 import { describe, expect, it, vi } from 'vitest';
-import { FilterProvider } from '../../src/providers/FilterProvider';
-import type { Filter, MembershipFilter } from '../../src/providers/types';
+import { countFilterConditions, FilterProvider, MAX_FILTER_TREE_DEPTH } from '../../src/providers/FilterProvider';
+import type { Filter, FilterGroup, MembershipFilter } from '../../src/providers/types';
 
 // ---------------------------------------------------------------------------
 // compile() — base behavior
@@ -237,6 +237,23 @@ describe('FilterProvider.setSearchQuery()', () => {
 		expect(result.where).toBe('deleted_at IS NULL');
 		expect(result.where).not.toContain('cards_fts');
 	});
+
+	it('field clauses in the query compile to filter clauses alongside FTS', () => {
+		const provider = new FilterProvider();
+		provider.setSearchQuery('hello status:done');
+		const result = provider.compile();
+		expect(result.where).toContain('status = ?');
+		expect(result.where).toContain('cards_fts MATCH ?');
+		expect(result.params).toEqual(['"hello"*', 'done']);
+	});
+
+	it('a query with only field clauses adds no FTS clause', () => {
+		const provider = new FilterProvider();
+		provider.setSearchQuery('type:note');
+		const result = provider.compile();
+		expect(result.where).toBe('deleted_at IS NULL AND card_type = ?');
+		expect(result.params).toEqual(['note']);
+	});
 });
 
 // ---------------------------------------------------------------------------
@@ -388,6 +405,20 @@ describe('FilterProvider serialization', () => {
 		expect(provider2.getFilters()[0]).toEqual({ field: 'folder', operator: 'eq', value: 'Work' });
 	});
 
+	it('applyState() restores state and notifies subscribers', async () => {
+		const provider = new FilterProvider();
+		provider.addFilter({ field: 'folder', operator: 'eq', value: 'Work' });
+		const state = JSON.parse(provider.toJSON());
+
+		const provider2 = new FilterProvider();
+		const cb = vi.fn();
+		provider2.subscribe(cb);
+		provider2.applyState(state);
+		await Promise.resolve();
+		expect(cb).toHaveBeenCalledTimes(1);
+		expect(provider2.getFilters()).toEqual([{ field: 'folder', operator: 'eq', value: 'Work' }]);
+	});
+
 	it('resetToDefaults() clears all state', () => {
 		const provider = new FilterProvider();
 		provider.addFilter({ field: 'folder', operator: 'eq', value: 'Work' });
@@ -1079,6 +1110,158 @@ describe('FilterProvider — multi-field membership filter (TFLT-03)', () => {
 	});
 });
 
+// ---------------------------------------------------------------------------
+// Boolean filter tree (AND/OR/NOT groups)
+// ---------------------------------------------------------------------------
+
+/** (status = todo OR priority > 2) AND NOT folder = archive */
+const SAMPLE_TREE: FilterGroup = {
+	kind: 'group',
+	combinator: 'and',
+	negate: false,
+	children: [
+		{
+			kind: 'group',
+			combinator: 'or',
+			negate: false,
+			children: [
+				{ field: 'status', operator: 'eq', value: 'todo' },
+				{ field: 'priority', operator: 'gt', value: 2 },
+			],
+		},
+		{
+			kind: 'group',
+			combinator: 'and',
+			negate: true,
+			children: [{ field: 'folder', operator: 'eq', value: 'archive' }],
+		},
+	],
+};
+
+describe('FilterProvider — boolean filter tree', () => {
+	it('compiles nested groups to one parenthesized clause with params in order', () => {
+		const provider = new FilterProvider();
+		provider.setFilterTree(SAMPLE_TREE);
+		const result = provider.compile();
+		expect(result.where).toBe(
+			'deleted_at IS NULL AND ((status = ? OR priority > ?) AND NOT IFNULL((folder = ?), 0))',
+		);
+		expect(result.params).toEqual(['todo', 2, 'archive']);
+	});
+
+	it('is AND-joined after flat filters and before FTS', () => {
+		const provider = new FilterProvider();
+		provider.addFilter({ field: 'card_type', operator: 'eq', value: 'note' });
+		provider.setFilterTree(SAMPLE_TREE);
+		provider.setSearchQuery('hello');
+		const result = provider.compile();
+		expect(result.where.indexOf('card_type = ?')).toBeLessThan(result.where.indexOf('(status = ?'));
+		expect(result.where.indexOf('(status = ?')).toBeLessThan(result.where.indexOf('cards_fts'));
+		expect(result.params).toEqual(['note', 'todo', 2, 'archive', '"hello"*']);
+	});
+
+	it('empty groups compile to nothing', () => {
+		const provider = new FilterProvider();
+		provider.setFilterTree({
+			kind: 'group',
+			combinator: 'or',
+			negate: true,
+			children: [{ kind: 'group', combinator: 'and', negate: false, children: [] }],
+		});
+		expect(provider.compile().where).toBe('deleted_at IS NULL');
+		expect(provider.hasFilterTree()).toBe(false);
+		expect(provider.hasActiveFilters()).toBe(false);
+	});
+
+	it('rejects unknown fields and operators anywhere in the tree', () => {
+		const provider = new FilterProvider();
+		const badField: FilterGroup = {
+			kind: 'group',
+			combinator: 'and',
+			negate: false,
+			children: [{ kind: 'group', combinator: 'or', negate: false, children: [{ field: 'evil', operator: 'eq', value: 1 }] }],
+		};
+		expect(() => provider.setFilterTree(badField)).toThrowError(/SQL safety violation/);
+		const badOp = { ...SAMPLE_TREE, children: [{ field: 'status', operator: 'DROP', value: 1 }] } as unknown as FilterGroup;
+		expect(() => provider.setFilterTree(badOp)).toThrowError(/SQL safety violation/);
+		expect(provider.getFilterTree()).toBeNull();
+	});
+
+	it('rejects malformed and too deeply nested trees', () => {
+		const provider = new FilterProvider();
+		const malformed = { kind: 'group', combinator: 'xor', negate: false, children: [] } as unknown as FilterGroup;
+		expect(() => provider.setFilterTree(malformed)).toThrowError(/invalid filter tree/);
+
+		let deep: FilterGroup = { kind: 'group', combinator: 'and', negate: false, children: [] };
+		for (let i = 0; i < MAX_FILTER_TREE_DEPTH; i++) {
+			deep = { kind: 'group', combinator: 'and', negate: false, children: [deep] };
+		}
+		expect(() => provider.setFilterTree(deep)).toThrowError(/invalid filter tree/);
+	});
+
+	it('getFilterTree() returns a defensive copy', () => {
+		const provider = new FilterProvider();
+		provider.setFilterTree(SAMPLE_TREE);
+		const copy = provider.getFilterTree()!;
+		copy.children = [];
+		expect(provider.getFilterTree()).toEqual(SAMPLE_TREE);
+	});
+
+	it('round-trips through toJSON/fromJSON', () => {
+		const provider = new FilterProvider();
+		provider.setFilterTree(SAMPLE_TREE);
+		const restored = FilterProvider.fromJSON(provider.toJSON());
+		expect(restored.getFilterTree()).toEqual(SAMPLE_TREE);
+		expect(restored.compile()).toEqual(provider.compile());
+	});
+
+	it('setState without filterTree defaults to null (backward compat)', () => {
+		const provider = new FilterProvider();
+		provider.setFilterTree(SAMPLE_TREE);
+		provider.setState({ filters: [], searchQuery: null });
+		expect(provider.getFilterTree()).toBeNull();
+	});
+
+	it('setState rejects a persisted tree with a non-allowlisted field', () => {
+		const provider = new FilterProvider();
+		const state = {
+			filters: [],
+			searchQuery: null,
+			filterTree: { kind: 'group', combinator: 'and', negate: false, children: [{ field: 'evil', operator: 'eq', value: 1 }] },
+		};
+		expect(() => provider.setState(state)).toThrowError(/SQL safety violation/);
+	});
+
+	it('clearFilters(), clearFilterTree() and resetToDefaults() remove the tree', () => {
+		const provider = new FilterProvider();
+		provider.setFilterTree(SAMPLE_TREE);
+		expect(provider.hasActiveFilters()).toBe(true);
+		provider.clearFilters();
+		expect(provider.getFilterTree()).toBeNull();
+
+		provider.setFilterTree(SAMPLE_TREE);
+		provider.clearFilterTree();
+		expect(provider.hasFilterTree()).toBe(false);
+
+		provider.setFilterTree(SAMPLE_TREE);
+		provider.resetToDefaults();
+		expect(provider.compile().where).toBe('deleted_at IS NULL');
+	});
+
+	it('countFilterConditions() counts leaf conditions only', () => {
+		expect(countFilterConditions(SAMPLE_TREE)).toBe(3);
+	});
+
+	it('subscriber notification fires on setFilterTree', async () => {
+		const provider = new FilterProvider();
+		const cb = vi.fn();
+		provider.subscribe(cb);
+		provider.setFilterTree(SAMPLE_TREE);
+		await Promise.resolve();
+		expect(cb).toHaveBeenCalledTimes(1);
+	});
+});
+
 // Suppress unused import warning for MembershipFilter — used by tests above
 const _mf: MembershipFilter | null = null;
 void _mf;
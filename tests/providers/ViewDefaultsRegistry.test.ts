@@ -27,14 +27,20 @@ function makeSchema(validColumns: string[]): SchemaProvider {
 // ---------------------------------------------------------------------------
 
 describe('VIEW_DEFAULTS_REGISTRY', () => {
-	it('has exactly 21 entries (9 format + 3 native + 11 alto dataset + 1 alto catch-all)', () => {
-		expect(VIEW_DEFAULTS_REGISTRY.size).toBe(21);
+	it('has exactly 27 entries (15 SourceType values + 11 alto dataset + 1 alto catch-all)', () => {
+		expect(VIEW_DEFAULTS_REGISTRY.size).toBe(27);
 	});
 
-	it('contains all 9 SourceType values plus alto_index catch-all', () => {
+	it('contains all 15 SourceType values plus alto_index catch-all', () => {
 		const expectedKeys = [
 			'apple_notes',
 			'markdown',
+			'obsidian',
+			'opml',
+			'ics',
+			'vcard',
+			'email',
+			'bookmarks',
 			'excel',
 			'csv',
 			'json',
@@ -124,6 +124,7 @@ describe('ALLOWED_AXIS_FIELDS', () => {
 		'priority',
 		'sort_order',
 		'name',
+		'location_name',
 	];
 
 	for (const field of expected) {
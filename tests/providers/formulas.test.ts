@@ -0,0 +1,301 @@
+// Isometry v5 — Formula fields
+// Tests for the formula expression language and fx_<name> columns flowing
+// through SchemaProvider, the allowlist, FilterProvider.compile() and SuperGridQuery.
+//
+// Tests cover:
+//   - compileFormula() operators, literals, keywords and whitelisted functions
+//   - rejection of unknown fields/functions, formula references and bad syntax (with positions)
+//   - parseFormulaDefinition() `name = expression` splitting
+//   - setCompiledFormulas() / formulaExpr() per-dataset CASE compilation
+//   - fieldExpr() resolving fx_ fields, SchemaProvider formula columns
+//   - filters, axes, sort keys and calc aggregates over a formula field
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { isValidAxisField, setSchemaProvider } from '../../src/providers/allowlist';
+import { FilterProvider } from '../../src/providers/FilterProvider';
+import {
+	type CompiledFormula,
+	compileFormula,
+	compiledFormulasOf,
+	FormulaError,
+	formulaColumnInfo,
+	formulaColumns,
+	formulaExpr,
+	formulaNameOf,
+	parseFormulaDefinition,
+	setCompiledFormulas,
+} from '../../src/providers/formulas';
+import { fieldExpr } from '../../src/providers/properties';
+import { SchemaProvider } from '../../src/providers/SchemaProvider';
+import { buildSuperGridCalcQuery, buildSuperGridQuery } from '../../src/views/supergrid/SuperGridQuery';
+import type { ColumnInfo } from '../../src/worker/protocol';
+
+// ---------------------------------------------------------------------------
+// Test fixtures
+// ---------------------------------------------------------------------------
+
+const FIELDS = new Set(['created_at', 'completed_at', 'priority', 'name', 'status']);
+const resolve = (name: string): string | null => (FIELDS.has(name) ? `cards.${name}` : null);
+
+const NOW = "strftime('%Y-%m-%dT%H:%M:%SZ', 'now')";
+
+function errorOf(expression: string): FormulaError {
+	try {
+		compileFormula(expression, resolve);
+	} catch (err) {
+		if (err instanceof FormulaError) return err;
+		throw err;
+	}
+	throw new Error(`expected "${expression}" to fail`);
+}
+
+const DAYS_OPEN: CompiledFormula = {
+	name: 'days_open',
+	label: 'Days open',
+	datasetId: null,
+	sql: '(julianday(cards.completed_at) - julianday(cards.created_at))',
+	resultType: 'number',
+};
+
+afterEach(() => {
+	setCompiledFormulas([]);
+});
+
+// ---------------------------------------------------------------------------
+// compileFormula
+// ---------------------------------------------------------------------------
+
+describe('compileFormula', () => {
+	it('compiles the days_open example', () => {
+		expect(compileFormula('julianday(coalesce(completed_at, now)) - julianday(created_at)', resolve)).toBe(
+			`(julianday(coalesce(cards.completed_at, ${NOW})) - julianday(cards.created_at))`,
+		);
+	});
+
+	it('respects precedence and parentheses', () => {
+		expect(compileFormula('1 + 2 * priority', resolve)).toBe('(1 + (2 * cards.priority))');
+		expect(compileFormula('(1 + 2) * priority', resolve)).toBe('((1 + 2) * cards.priority)');
+		expect(compileFormula('-priority % 3', resolve)).toBe('((-cards.priority) % 3)');
+	});
+
+	it('always compiles / as real division', () => {
+		expect(compileFormula('priority / 2', resolve)).toBe('(CAST(cards.priority AS REAL) / 2)');
+	});
+
+	it('compiles comparison, boolean and concatenation operators', () => {
+		expect(compileFormula("status == 'done' and not priority <> 0", resolve)).toBe(
+			"((cards.status = 'done') AND (NOT (cards.priority != 0)))",
+		);
+		expect(compileFormula("name || ' (' || status || ')'", resolve)).toBe(
+			"(((cards.name || ' (') || cards.status) || ')')",
+		);
+	});
+
+	it('re-quotes string literals and maps keywords', () => {
+		expect(compileFormula("'it''s'", resolve)).toBe("'it''s'");
+		expect(compileFormula('if(true, today, null)', resolve)).toBe("(CASE WHEN 1 THEN date('now') ELSE NULL END)");
+		expect(compileFormula('FALSE', resolve)).toBe('0');
+	});
+
+	it('accepts whitelisted functions case-insensitively', () => {
+		expect(compileFormula('ROUND(priority, 1)', resolve)).toBe('round(cards.priority, 1)');
+		expect(compileFormula('max(priority, 1, 2)', resolve)).toBe('max(cards.priority, 1, 2)');
+	});
+
+	it('rejects unknown fields with their position', () => {
+		const err = errorOf('priority + budget');
+		expect(err.message).toBe('Unknown field "budget"');
+		expect(err.position).toBe(11);
+	});
+
+	it('rejects functions outside the whitelist, including aggregates', () => {
+		expect(errorOf('load_extension(name)').message).toBe('Unknown function "load_extension"');
+		expect(errorOf('sum(priority)').message).toBe('Unknown function "sum"');
+		expect(errorOf('max(priority)').message).toBe('max() expects 2 to 16 arguments, got 1');
+	});
+
+	it('rejects references to other formulas', () => {
+		expect(errorOf('fx_days_open * 2').message).toMatch(/cannot reference other formulas/);
+	});
+
+	it('rejects SQL syntax that is not part of the language', () => {
+		expect(errorOf('priority; DROP TABLE cards').message).toBe('Unexpected character ";"');
+		expect(errorOf('(SELECT 1)').message).toBe('Unknown field "SELECT"');
+		expect(errorOf('"name"').position).toBe(0);
+		expect(errorOf("'open").message).toBe('Unterminated string');
+	});
+
+	it('reports structural errors', () => {
+		expect(errorOf('').message).toBe('Formula expression is empty');
+		expect(errorOf('priority +').message).toBe('Unexpected end of formula');
+		expect(errorOf('round(priority').message).toBe('Expected "," but found end of formula');
+		expect(errorOf('1 < 2 < 3').message).toMatch(/Chained comparisons/);
+		expect(errorOf('priority priority').message).toBe('Unexpected "priority"');
+		expect(errorOf(`${'('.repeat(40)}1${')'.repeat(40)}`).message).toBe('Formula is nested too deeply');
+	});
+});
+
+// ---------------------------------------------------------------------------
+// parseFormulaDefinition
+// ---------------------------------------------------------------------------
+
+describe('parseFormulaDefinition', () => {
+	it('splits name and expression', () => {
+		expect(parseFormulaDefinition('  days_open =  julianday(now) - julianday(created_at) ')).toEqual({
+			name: 'days_open',
+			expression: 'julianday(now) - julianday(created_at)',
+		});
+	});
+
+	it('keeps == inside the expression', () => {
+		expect(parseFormulaDefinition("is_done = status == 'done'").expression).toBe("status == 'done'");
+	});
+
+	it('rejects missing names and invalid identifiers', () => {
+		expect(() => parseFormulaDefinition('priority * 2')).toThrow('Write formulas as "name = expression"');
+		expect(() => parseFormulaDefinition('Days Open = 1')).toThrow(FormulaError);
+		expect(() => parseFormulaDefinition('score =')).toThrow('Formula expression is empty');
+	});
+});
+
+// ---------------------------------------------------------------------------
+// Registry
+// ---------------------------------------------------------------------------
+
+describe('compiled formula registry', () => {
+	it('formulaExpr returns the SQL of a global formula', () => {
+		setCompiledFormulas([DAYS_OPEN]);
+		expect(formulaExpr('fx_days_open')).toBe(DAYS_OPEN.sql);
+		expect(formulaExpr('fx_missing')).toBeNull();
+		expect(formulaExpr('days_open')).toBeNull();
+	});
+
+	it('switches on cards.dataset_id for per-dataset definitions', () => {
+		setCompiledFormulas([
+			DAYS_OPEN,
+			{ ...DAYS_OPEN, datasetId: "ds-'1", sql: '(1)' },
+			{ ...DAYS_OPEN, name: 'score', datasetId: 'ds-2', sql: '(2)' },
+		]);
+		expect(formulaExpr('fx_days_open')).toBe(
+			`(CASE cards.dataset_id WHEN 'ds-''1' THEN (1) ELSE ${DAYS_OPEN.sql} END)`,
+		);
+		expect(formulaExpr('fx_score')).toBe("(CASE cards.dataset_id WHEN 'ds-2' THEN (2) ELSE NULL END)");
+	});
+
+	it('fieldExpr resolves formula fields before anything else', () => {
+		setCompiledFormulas([DAYS_OPEN]);
+		expect(fieldExpr('fx_days_open')).toBe(DAYS_OPEN.sql);
+		expect(fieldExpr('priority')).toBe('priority');
+	});
+
+	it('compiledFormulasOf skips formulas that do not compile', () => {
+		const base = {
+			id: 'f1',
+			name: 'days_open',
+			label: 'Days open',
+			dataset_id: null,
+			expression: 'x',
+			result_type: 'number' as const,
+			created_at: '',
+			updated_at: '',
+			field: 'fx_days_open',
+		};
+		expect(
+			compiledFormulasOf([
+				{ ...base, sql: '(1)', error: null },
+				{ ...base, id: 'f2', name: 'broken', sql: null, error: 'Unknown field "gone"' },
+			]),
+		).toEqual([{ name: 'days_open', label: 'Days open', datasetId: null, sql: '(1)', resultType: 'number' }]);
+	});
+
+	it('formulaColumns emits one column per name, preferring the global definition', () => {
+		const columns = formulaColumns([
+			{ ...DAYS_OPEN, datasetId: 'ds-1', label: 'Scoped', resultType: 'text' },
+			DAYS_OPEN,
+		]);
+		expect(columns).toEqual([formulaColumnInfo(DAYS_OPEN)]);
+		expect(columns[0]).toMatchObject({
+			name: 'fx_days_open',
+			type: 'REAL',
+			latchFamily: 'Hierarchy',
+			isNumeric: true,
+			formulaType: 'number',
+			label: 'Days open',
+		});
+	});
+
+	it('formulaNameOf rejects malformed formula fields', () => {
+		expect(formulaNameOf('fx_days_open')).toBe('days_open');
+		expect(formulaNameOf('fx_Days')).toBeNull();
+		expect(formulaNameOf('prop_days')).toBeNull();
+	});
+});
+
+// ---------------------------------------------------------------------------
+// SchemaProvider + query builders
+// ---------------------------------------------------------------------------
+
+describe('fx_ fields in SchemaProvider and compiled SQL', () => {
+	const CARD_COLUMNS: ColumnInfo[] = [
+		{ name: 'card_type', type: 'TEXT', notnull: true, latchFamily: 'Category', isNumeric: false },
+		{ name: 'priority', type: 'INTEGER', notnull: true, latchFamily: 'Hierarchy', isNumeric: true },
+	];
+	let sp: SchemaProvider;
+
+	beforeEach(() => {
+		sp = new SchemaProvider();
+		sp.initialize({ cards: CARD_COLUMNS, connections: [], formulas: [DAYS_OPEN] });
+		setSchemaProvider(sp);
+		setCompiledFormulas([DAYS_OPEN]);
+	});
+
+	afterEach(() => {
+		setSchemaProvider(null);
+	});
+
+	it('initialize exposes formulas from the ready message as numeric columns', () => {
+		expect(isValidAxisField('fx_days_open')).toBe(true);
+		expect(sp.getNumericColumns().map((c) => c.name)).toContain('fx_days_open');
+		expect(sp.getHeuristicFamily('fx_days_open')).toBe('Hierarchy');
+	});
+
+	it('setFormulaColumns replaces the formula set', () => {
+		sp.setFormulaColumns([]);
+		expect(isValidAxisField('fx_days_open')).toBe(false);
+		expect(sp.getFormulaColumns()).toEqual([]);
+	});
+
+	it('FilterProvider.compile() filters on the formula expression', () => {
+		const provider = new FilterProvider();
+		provider.addFilter({ field: 'fx_days_open' as any, operator: 'gt', value: 30 });
+		const { where, params } = provider.compile();
+
+		expect(where).toContain(`${DAYS_OPEN.sql} > ?`);
+		expect(params).toContain(30);
+	});
+
+	it('buildSuperGridQuery uses a formula as axis and sort key', () => {
+		const { sql } = buildSuperGridQuery({
+			colAxes: [{ field: 'fx_days_open' as any, direction: 'asc' }],
+			rowAxes: [{ field: 'card_type' as any, direction: 'asc' }],
+			where: 'deleted_at IS NULL',
+			params: [],
+			sortOverrides: [{ field: 'fx_days_open' as any, direction: 'desc' }],
+		});
+
+		expect(sql).toContain(`${DAYS_OPEN.sql} AS fx_days_open`);
+		expect(sql).toContain(`${DAYS_OPEN.sql} DESC`);
+	});
+
+	it('buildSuperGridCalcQuery aggregates a formula in the footer', () => {
+		const { sql } = buildSuperGridCalcQuery({
+			rowAxes: [{ field: 'card_type' as any, direction: 'asc' }],
+			where: '',
+			params: [],
+			aggregates: { fx_days_open: 'avg' },
+			numericFields: ['priority', 'fx_days_open'],
+		});
+
+		expect(sql).toContain(`AVG(${DAYS_OPEN.sql}) AS "__agg__fx_days_open"`);
+	});
+});
@@ -19,7 +19,7 @@ import {
 import type { AxisField } from '../../src/providers/types';
 
 // ---------------------------------------------------------------------------
-// LATCH_FAMILIES — maps all 9 AxisField values to LatchFamily letters
+// LATCH_FAMILIES — maps all 10 AxisField values to LatchFamily letters
 // ---------------------------------------------------------------------------
 
 describe('LATCH_FAMILIES', () => {
@@ -59,7 +59,11 @@ describe('LATCH_FAMILIES', () => {
 		expect(LATCH_FAMILIES['sort_order']).toBe('H');
 	});
 
-	it('covers all 9 AxisField values', () => {
+	it('maps location_name to Location (L)', () => {
+		expect(LATCH_FAMILIES['location_name']).toBe('L');
+	});
+
+	it('covers all 10 AxisField values', () => {
 		const allFields: AxisField[] = [
 			'created_at',
 			'modified_at',
@@ -70,11 +74,12 @@ describe('LATCH_FAMILIES', () => {
 			'priority',
 			'sort_order',
 			'name',
+			'location_name',
 		];
 		for (const field of allFields) {
 			expect(LATCH_FAMILIES[field]).toBeDefined();
 		}
-		expect(Object.keys(LATCH_FAMILIES)).toHaveLength(9);
+		expect(Object.keys(LATCH_FAMILIES)).toHaveLength(10);
 	});
 
 	it('is frozen (immutable)', () => {
@@ -0,0 +1,227 @@
+// Isometry v5 — Relative Date Expressions
+// Tests for parseRelativeDate / formatRelativeDate / resolveRelativeDate and
+// FilterProvider relative date filters (resolved at compile time).
+
+import { afterAll, afterEach, beforeAll, beforeEach, describe, expect, it, vi } from 'vitest';
+import { FilterProvider } from '../../src/providers/FilterProvider';
+import {
+	formatRelativeDate,
+	isRelativeDateExpr,
+	parseRelativeDate,
+	resolveRelativeDate,
+} from '../../src/providers/relative-dates';
+
+// Wednesday 2026-03-18, local time
+const NOW = new Date(2026, 2, 18, 15, 30);
+
+/** ISO instant of local midnight on a 'YYYY-MM-DD' calendar day. */
+function midnight(day: string): string {
+	const [y, m, d] = day.split('-').map(Number) as [number, number, number];
+	return new Date(y, m - 1, d).toISOString();
+}
+
+// ---------------------------------------------------------------------------
+// parseRelativeDate / formatRelativeDate
+// ---------------------------------------------------------------------------
+
+describe('parseRelativeDate', () => {
+	it('parses day words and overdue', () => {
+		expect(parseRelativeDate('Today')).toEqual({ kind: 'period', offset: 0, unit: 'day' });
+		expect(parseRelativeDate('yesterday')).toEqual({ kind: 'period', offset: -1, unit: 'day' });
+		expect(parseRelativeDate(' overdue ')).toEqual({ kind: 'overdue' });
+	});
+
+	it('parses calendar periods', () => {
+		expect(parseRelativeDate('next quarter')).toEqual({ kind: 'period', offset: 1, unit: 'quarter' });
+		expect(parseRelativeDate('last-week')).toEqual({ kind: 'period', offset: -1, unit: 'week' });
+		expect(parseRelativeDate('this year')).toEqual({ kind: 'period', offset: 0, unit: 'year' });
+	});
+
+	it('parses rolling windows with singular or plural units', () => {
+		expect(parseRelativeDate('last 14 days')).toEqual({ kind: 'rolling', direction: 'past', amount: 14, unit: 'day' });
+		expect(parseRelativeDate('past 1 month')).toEqual({ kind: 'rolling', direction: 'past', amount: 1, unit: 'month' });
+		expect(parseRelativeDate('NEXT 2 WEEKS')).toEqual({ kind: 'rolling', direction: 'future', amount: 2, unit: 'week' });
+	});
+
+	it('returns null for anything else', () => {
+		for (const text of ['', 'someday', 'next days', 'last 0 days', 'last 1.5 days', 'last 14 fortnights', 'this 2 weeks']) {
+			expect(parseRelativeDate(text)).toBeNull();
+		}
+	});
+
+	it('formatRelativeDate output parses back to the same expression', () => {
+		for (const text of ['today', 'tomorrow', 'overdue', 'last month', 'next quarter', 'last 14 days', 'next 1 year']) {
+			const expr = parseRelativeDate(text)!;
+			expect(parseRelativeDate(formatRelativeDate(expr))).toEqual(expr);
+		}
+		expect(formatRelativeDate({ kind: 'rolling', direction: 'past', amount: 14, unit: 'day' })).toBe('Last 14 days');
+	});
+
+	it('isRelativeDateExpr rejects malformed expressions', () => {
+		expect(isRelativeDateExpr({ kind: 'period', offset: 0, unit: 'week' })).toBe(true);
+		expect(isRelativeDateExpr({ kind: 'period', offset: 5, unit: 'week' })).toBe(false);
+		expect(isRelativeDateExpr({ kind: 'rolling', direction: 'past', amount: 0, unit: 'day' })).toBe(false);
+		expect(isRelativeDateExpr({ kind: 'rolling', direction: 'up', amount: 3, unit: 'day' })).toBe(false);
+		expect(isRelativeDateExpr({ kind: 'soon' })).toBe(false);
+		expect(isRelativeDateExpr(null)).toBe(false);
+	});
+});
+
+// ---------------------------------------------------------------------------
+// resolveRelativeDate
+// ---------------------------------------------------------------------------
+
+describe('resolveRelativeDate', () => {
+	const resolve = (text: string) => resolveRelativeDate(parseRelativeDate(text)!, NOW);
+
+	it('resolves calendar periods to [start, end) local midnights', () => {
+		expect(resolve('today')).toEqual({ start: midnight('2026-03-18'), end: midnight('2026-03-19') });
+		expect(resolve('yesterday')).toEqual({ start: midnight('2026-03-17'), end: midnight('2026-03-18') });
+		expect(resolve('this week')).toEqual({ start: midnight('2026-03-15'), end: midnight('2026-03-22') });
+		expect(resolve('last month')).toEqual({ start: midnight('2026-02-01'), end: midnight('2026-03-01') });
+		expect(resolve('next quarter')).toEqual({ start: midnight('2026-04-01'), end: midnight('2026-07-01') });
+		expect(resolve('last year')).toEqual({ start: midnight('2025-01-01'), end: midnight('2026-01-01') });
+	});
+
+	it('resolves rolling windows in whole days including today', () => {
+		expect(resolve('last 14 days')).toEqual({ start: midnight('2026-03-05'), end: midnight('2026-03-19') });
+		expect(resolve('next 2 weeks')).toEqual({ start: midnight('2026-03-18'), end: midnight('2026-04-01') });
+	});
+
+	it('clamps month arithmetic at month end', () => {
+		const jan31 = new Date(2026, 0, 31);
+		expect(resolveRelativeDate({ kind: 'rolling', direction: 'future', amount: 1, unit: 'month' }, jan31)).toEqual({
+			start: midnight('2026-01-31'),
+			end: midnight('2026-02-28'),
+		});
+	});
+
+	it('resolves overdue to an open range ending today', () => {
+		expect(resolve('overdue')).toEqual({ start: null, end: midnight('2026-03-18') });
+	});
+});
+
+// ---------------------------------------------------------------------------
+// Non-UTC time zone
+// ---------------------------------------------------------------------------
+
+describe('resolveRelativeDate outside UTC', () => {
+	const originalTz = process.env['TZ'];
+
+	beforeAll(() => {
+		// UTC-7 in October (PDT)
+		process.env['TZ'] = 'America/Los_Angeles';
+	});
+
+	afterAll(() => {
+		if (originalTz === undefined) delete process.env['TZ'];
+		else process.env['TZ'] = originalTz;
+	});
+
+	it('bounds "today" by local midnight, not the UTC day', () => {
+		const now = new Date(2026, 9, 18, 12, 0);
+		expect(now.getTimezoneOffset()).toBe(420);
+
+		const { start, end } = resolveRelativeDate({ kind: 'period', offset: 0, unit: 'day' }, now);
+		expect({ start, end }).toEqual({ start: '2026-10-18T07:00:00.000Z', end: '2026-10-19T07:00:00.000Z' });
+
+		// Due 20:00 local on Oct 17 — yesterday, not today
+		expect('2026-10-18T03:00:00Z' >= start!).toBe(false);
+		// Due 18:00 local on Oct 18 — today
+		expect('2026-10-19T01:00:00Z' >= start! && '2026-10-19T01:00:00Z' < end).toBe(true);
+	});
+
+	it('ends overdue and rolling windows at local midnight', () => {
+		const now = new Date(2026, 9, 18, 23, 30);
+		expect(resolveRelativeDate({ kind: 'overdue' }, now)).toEqual({ start: null, end: '2026-10-18T07:00:00.000Z' });
+		expect(resolveRelativeDate({ kind: 'rolling', direction: 'past', amount: 14, unit: 'day' }, now)).toEqual({
+			start: '2026-10-05T07:00:00.000Z',
+			end: '2026-10-19T07:00:00.000Z',
+		});
+	});
+});
+
+// ---------------------------------------------------------------------------
+// FilterProvider relative date filters
+// ---------------------------------------------------------------------------
+
+describe('FilterProvider relative date filters', () => {
+	beforeEach(() => {
+		vi.useFakeTimers();
+		vi.setSystemTime(NOW);
+	});
+
+	afterEach(() => {
+		vi.useRealTimers();
+	});
+
+	it('compiles to local-midnight bounds resolved at compile time', () => {
+		const provider = new FilterProvider();
+		provider.setRelativeDateFilter('due_at', { kind: 'rolling', direction: 'past', amount: 14, unit: 'day' });
+		expect(provider.compile()).toEqual({
+			where: 'deleted_at IS NULL AND due_at >= ? AND due_at < ?',
+			params: [midnight('2026-03-05'), midnight('2026-03-19')],
+		});
+
+		// The same stored state resolves differently the next day
+		vi.setSystemTime(new Date(2026, 2, 19, 9, 0));
+		expect(provider.compile().params).toEqual([midnight('2026-03-06'), midnight('2026-03-20')]);
+	});
+
+	it('overdue compiles to before today and not completed', () => {
+		const provider = new FilterProvider();
+		provider.setRelativeDateFilter('due_at', { kind: 'overdue' });
+		expect(provider.compile()).toEqual({
+			where: 'deleted_at IS NULL AND due_at < ? AND completed_at IS NULL',
+			params: [midnight('2026-03-18')],
+		});
+	});
+
+	it('works for any allowlisted time column and rejects unknown fields', () => {
+		const provider = new FilterProvider();
+		provider.setRelativeDateFilter('event_start', { kind: 'period', offset: 1, unit: 'week' });
+		provider.setRelativeDateFilter('completed_at', { kind: 'period', offset: -1, unit: 'month' });
+		const { where } = provider.compile();
+		expect(where).toContain('event_start >= ?');
+		expect(where).toContain('completed_at < ?');
+		expect(() => provider.setRelativeDateFilter('evil', { kind: 'overdue' })).toThrowError(/SQL safety violation/);
+	});
+
+	it('stores the expression symbolically in toJSON and restores it', () => {
+		const provider = new FilterProvider();
+		provider.setRelativeDateFilter('due_at', { kind: 'period', offset: 1, unit: 'quarter' });
+		const parsed = JSON.parse(provider.toJSON());
+		expect(parsed.relativeDateFilters).toEqual({ due_at: { kind: 'period', offset: 1, unit: 'quarter' } });
+
+		const restored = FilterProvider.fromJSON(provider.toJSON());
+		expect(restored.getRelativeDateFilter('due_at')).toEqual({ kind: 'period', offset: 1, unit: 'quarter' });
+		expect(restored.compile()).toEqual(provider.compile());
+	});
+
+	it('setState without relativeDateFilters restores none (backward compat) and rejects malformed ones', () => {
+		const provider = new FilterProvider();
+		provider.setRelativeDateFilter('due_at', { kind: 'overdue' });
+		provider.setState({ filters: [], searchQuery: null });
+		expect(provider.hasRelativeDateFilter('due_at')).toBe(false);
+
+		expect(() =>
+			provider.setState({ filters: [], searchQuery: null, relativeDateFilters: { due_at: { kind: 'soon' } } }),
+		).toThrowError(/invalid state shape/);
+	});
+
+	it('null / clearRelativeDateFilter / clearFilters remove the filter', () => {
+		const provider = new FilterProvider();
+		provider.setRelativeDateFilter('due_at', { kind: 'overdue' });
+		expect(provider.hasActiveFilters()).toBe(true);
+		provider.setRelativeDateFilter('due_at', null);
+		expect(provider.hasRelativeDateFilter('due_at')).toBe(false);
+
+		provider.setRelativeDateFilter('due_at', { kind: 'overdue' });
+		provider.clearRelativeDateFilter('due_at');
+		expect(provider.getRelativeDateFilter('due_at')).toBeNull();
+
+		provider.setRelativeDateFilter('due_at', { kind: 'overdue' });
+		provider.clearFilters();
+		expect(provider.compile().where).toBe('deleted_at IS NULL');
+	});
+});
@@ -0,0 +1,165 @@
+// Isometry v5 — Custom card property fields
+// Tests for prop_<key> columns flowing through SchemaProvider, the allowlist,
+// FilterProvider.compile() and SuperGridQuery.
+//
+// Tests cover:
+//   - propertyColumnInfo() type → LATCH family / numeric mapping
+//   - setPropertyColumns() adds, replaces and removes valid columns
+//   - fieldExpr() compiles prop_ fields to a card_properties subquery
+//   - FilterProvider.compile() uses the subquery with bound values
+//   - buildSuperGridQuery() groups by a property axis
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { isValidFilterField, setSchemaProvider } from '../../src/providers/allowlist';
+import { FilterProvider } from '../../src/providers/FilterProvider';
+import { fieldExpr, isPropertyField, propertyColumnInfo, propertyKeyOf } from '../../src/providers/properties';
+import { SchemaProvider } from '../../src/providers/SchemaProvider';
+import { buildSuperGridQuery } from '../../src/views/supergrid/SuperGridQuery';
+import type { ColumnInfo } from '../../src/worker/protocol';
+
+// ---------------------------------------------------------------------------
+// Test fixtures
+// ---------------------------------------------------------------------------
+
+const CARD_COLUMNS: ColumnInfo[] = [
+	{ name: 'name', type: 'TEXT', notnull: true, latchFamily: 'Alphabet', isNumeric: false },
+	{ name: 'card_type', type: 'TEXT', notnull: true, latchFamily: 'Category', isNumeric: false },
+	{ name: 'priority', type: 'INTEGER', notnull: true, latchFamily: 'Hierarchy', isNumeric: true },
+];
+
+const CONN_COLUMNS: ColumnInfo[] = [
+	{ name: 'source_id', type: 'TEXT', notnull: true, latchFamily: 'Alphabet', isNumeric: false },
+];
+
+const REVENUE = propertyColumnInfo({ key: 'revenue', label: 'Revenue', type: 'number' });
+const STAGE = propertyColumnInfo({ key: 'stage', label: 'Deal Stage', type: 'enum' });
+const CLOSES = propertyColumnInfo({ key: 'closes_on', label: 'Closes On', type: 'date' });
+
+let sp: SchemaProvider;
+
+beforeEach(() => {
+	sp = new SchemaProvider();
+	sp.initialize({ cards: CARD_COLUMNS, connections: CONN_COLUMNS });
+	setSchemaProvider(sp);
+});
+
+afterEach(() => {
+	setSchemaProvider(null);
+});
+
+// ---------------------------------------------------------------------------
+// Field helpers
+// ---------------------------------------------------------------------------
+
+describe('property field helpers', () => {
+	it('propertyColumnInfo maps type to LATCH family and numeric flag', () => {
+		expect(REVENUE).toMatchObject({
+			name: 'prop_revenue',
+			latchFamily: 'Hierarchy',
+			isNumeric: true,
+			label: 'Revenue',
+		});
+		expect(STAGE).toMatchObject({ name: 'prop_stage', latchFamily: 'Category', isNumeric: false });
+		expect(propertyColumnInfo({ key: 'due', label: 'Due', type: 'date' }).latchFamily).toBe('Time');
+	});
+
+	it('propertyKeyOf rejects malformed property fields', () => {
+		expect(propertyKeyOf('prop_revenue')).toBe('revenue');
+		expect(propertyKeyOf("prop_x'; DROP TABLE cards; --")).toBeNull();
+		expect(isPropertyField('priority')).toBe(false);
+	});
+
+	it('fieldExpr leaves physical columns unchanged', () => {
+		expect(fieldExpr('folder')).toBe('folder');
+		expect(fieldExpr('prop_revenue')).toContain("card_properties.key = 'revenue'");
+	});
+});
+
+// ---------------------------------------------------------------------------
+// SchemaProvider
+// ---------------------------------------------------------------------------
+
+describe('SchemaProvider property columns', () => {
+	it('setPropertyColumns makes prop_ fields valid and visible', () => {
+		sp.setPropertyColumns([REVENUE, STAGE]);
+
+		expect(sp.isValidColumn('prop_revenue')).toBe(true);
+		expect(isValidFilterField('prop_stage')).toBe(true);
+		expect(sp.getAxisColumns().map((c) => c.name)).toEqual(['name', 'card_type', 'priority', 'prop_revenue', 'prop_stage']);
+		expect(sp.getNumericColumns().map((c) => c.name)).toContain('prop_revenue');
+		expect(sp.getHeuristicFamily('prop_stage')).toBe('Category');
+	});
+
+	it('replacing the set removes stale properties', () => {
+		sp.setPropertyColumns([REVENUE, STAGE]);
+		sp.setPropertyColumns([STAGE]);
+
+		expect(sp.isValidColumn('prop_revenue')).toBe(false);
+		expect(sp.isValidColumn('prop_stage')).toBe(true);
+		expect(sp.getPropertyColumns()).toHaveLength(1);
+	});
+
+	it('initialize accepts property columns from the ready message', () => {
+		const fresh = new SchemaProvider();
+		fresh.initialize({ cards: CARD_COLUMNS, connections: CONN_COLUMNS, properties: [REVENUE] });
+		expect(fresh.isValidColumn('prop_revenue')).toBe(true);
+	});
+});
+
+// ---------------------------------------------------------------------------
+// Query compilation
+// ---------------------------------------------------------------------------
+
+describe('prop_ fields in compiled SQL', () => {
+	beforeEach(() => {
+		sp.setPropertyColumns([REVENUE, STAGE, CLOSES]);
+	});
+
+	it('FilterProvider.compile() filters through the property subquery', () => {
+		const provider = new FilterProvider();
+		provider.addFilter({ field: 'prop_revenue' as any, operator: 'gte', value: 1000 });
+		const { where, params } = provider.compile();
+
+		expect(where).toContain(`${fieldExpr('prop_revenue')} >= ?`);
+		expect(params).toContain(1000);
+	});
+
+	it('FilterProvider.compile() rejects unknown property fields', () => {
+		const provider = new FilterProvider();
+		expect(() => provider.addFilter({ field: 'prop_missing' as any, operator: 'eq', value: 'x' })).toThrow(
+			/SQL safety violation/,
+		);
+	});
+
+	it('buildSuperGridQuery groups by a property axis', () => {
+		const { sql } = buildSuperGridQuery({
+			colAxes: [{ field: 'prop_stage' as any, direction: 'asc' }],
+			rowAxes: [{ field: 'card_type' as any, direction: 'asc' }],
+			where: 'deleted_at IS NULL',
+			params: [],
+		});
+
+		expect(sql).toContain(`${fieldExpr('prop_stage')} AS prop_stage`);
+		expect(sql).toContain('FROM cards');
+		expect(sql).not.toContain('JOIN');
+	});
+
+	it('buildSuperGridQuery buckets a date property on a time axis', () => {
+		const timeFields = sp.getFieldsByFamily('Time').map((c) => c.name);
+		const { sql } = buildSuperGridQuery({
+			colAxes: [{ field: 'prop_closes_on' as any, direction: 'asc' }],
+			rowAxes: [{ field: 'card_type' as any, direction: 'asc' }],
+			where: 'deleted_at IS NULL',
+			params: [],
+			granularity: 'quarter',
+			timeFields,
+		});
+
+		const bucket = `strftime('%Y', ${fieldExpr('prop_closes_on')})`;
+		expect(timeFields).toContain('prop_closes_on');
+		expect(sql).toContain(`COALESCE(${bucket}`);
+		expect(sql).toContain(') AS prop_closes_on');
+		expect(sql).toMatch(/GROUP BY COALESCE\(strftime/);
+		expect(sql).toContain(`CASE WHEN COALESCE(${bucket}`);
+	});
+});
@@ -0,0 +1,179 @@
+// Isometry v5 — Search Query Language
+// Tests for parseSearchQuery(): FTS5 compilation, field clauses, error recovery.
+
+import { afterEach, describe, expect, it } from 'vitest';
+import { ALLOWED_FILTER_FIELDS, setNumericColumnNames, setValidColumnNames } from '../../src/providers/allowlist';
+import { matchesSearchTerms, parseSearchQuery } from '../../src/providers/search-query';
+
+// ---------------------------------------------------------------------------
+// Full-text terms
+// ---------------------------------------------------------------------------
+
+describe('parseSearchQuery() — full-text terms', () => {
+	it('quotes bare terms as an implicit AND', () => {
+		const parsed = parseSearchQuery('budget report');
+		expect(parsed.fts).toBe('"budget" "report"');
+		expect(parsed.terms).toEqual(['budget', 'report']);
+		expect(parsed.errors).toEqual([]);
+	});
+
+	it('prefix option appends * to bare terms but not phrases', () => {
+		expect(parseSearchQuery('hello world', { prefix: true }).fts).toBe('"hello"* "world"*');
+		expect(parseSearchQuery('"exact phrase" go', { prefix: true }).fts).toBe('"exact phrase" "go"*');
+	});
+
+	it('explicit trailing * is a prefix match', () => {
+		expect(parseSearchQuery('plan*').fts).toBe('"plan"*');
+	});
+
+	it('FTS5 operators inside terms cannot inject syntax', () => {
+		const parsed = parseSearchQuery('a^b c* (d) x:y:z');
+		expect(parsed.fts).toBe('"a^b" "c"* "(d)" "x:y:z"');
+	});
+
+	it('OR joins adjacent terms', () => {
+		expect(parseSearchQuery('alpha OR beta').fts).toBe('"alpha" OR "beta"');
+		expect(parseSearchQuery('x alpha OR beta').fts).toBe('"x" ("alpha" OR "beta")');
+	});
+
+	it('-term and NOT term exclude', () => {
+		expect(parseSearchQuery('roadmap -draft').fts).toBe('"roadmap" NOT "draft"');
+		expect(parseSearchQuery('roadmap NOT draft').fts).toBe('"roadmap" NOT "draft"');
+		expect(parseSearchQuery('a b -c -d').fts).toBe('("a" "b") NOT ("c" OR "d")');
+		expect(parseSearchQuery('roadmap -draft').terms).toEqual(['roadmap']);
+	});
+
+	it('NEAR() compiles with a default or explicit distance', () => {
+		expect(parseSearchQuery('NEAR(alpha beta)').fts).toBe('NEAR("alpha" "beta", 10)');
+		expect(parseSearchQuery('NEAR(alpha "b c", 3)').fts).toBe('NEAR("alpha" "b c", 3)');
+	});
+
+	it('column scopes limit a term to one FTS column', () => {
+		expect(parseSearchQuery('name:roadmap').fts).toBe('name : "roadmap"');
+		expect(parseSearchQuery('folder:"Work Stuff"').fts).toBe('folder : "Work Stuff"');
+	});
+
+	it('URLs are searched as text, not parsed as field clauses', () => {
+		const parsed = parseSearchQuery('https://example.com');
+		expect(parsed.fts).toBe('"https://example.com"');
+		expect(parsed.filters).toEqual([]);
+	});
+});
+
+// ---------------------------------------------------------------------------
+// Field clauses
+// ---------------------------------------------------------------------------
+
+describe('parseSearchQuery() — field clauses', () => {
+	it('field:value becomes an eq filter', () => {
+		const parsed = parseSearchQuery('status:done');
+		expect(parsed.fts).toBe('');
+		expect(parsed.filters).toEqual([{ field: 'status', operator: 'eq', value: 'done' }]);
+	});
+
+	it('comparison prefixes map to operators and aliases resolve', () => {
+		expect(parseSearchQuery('due:<2026-01-01').filters).toEqual([
+			{ field: 'due_at', operator: 'lt', value: '2026-01-01' },
+		]);
+		expect(parseSearchQuery('priority:>=3').filters).toEqual([{ field: 'priority', operator: 'gte', value: 3 }]);
+		expect(parseSearchQuery('status:!done').filters).toEqual([{ field: 'status', operator: 'neq', value: 'done' }]);
+	});
+
+	it('comma lists become membership filters; negated lists become neq clauses', () => {
+		expect(parseSearchQuery('status:todo,doing').filters).toEqual([
+			{ field: 'status', operator: 'in', value: ['todo', 'doing'] },
+		]);
+		expect(parseSearchQuery('-status:todo,doing').filters).toEqual([
+			{ field: 'status', operator: 'neq', value: 'todo' },
+			{ field: 'status', operator: 'neq', value: 'doing' },
+		]);
+	});
+
+	it('negation inverts comparison operators', () => {
+		expect(parseSearchQuery('-status:done').filters).toEqual([{ field: 'status', operator: 'neq', value: 'done' }]);
+		expect(parseSearchQuery('-due:<2026-01-01').filters).toEqual([
+			{ field: 'due_at', operator: 'gte', value: '2026-01-01' },
+		]);
+	});
+
+	it('has:field and -has:field test for presence', () => {
+		expect(parseSearchQuery('has:due').filters).toEqual([{ field: 'due_at', operator: 'isNotNull', value: null }]);
+		expect(parseSearchQuery('-has:due').filters).toEqual([{ field: 'due_at', operator: 'isNull', value: null }]);
+	});
+
+	it('unknown fields report an error and fall back to text search', () => {
+		const parsed = parseSearchQuery('bogus:value');
+		expect(parsed.filters).toEqual([]);
+		expect(parsed.fts).toBe('"bogus:value"');
+		expect(parsed.errors[0]!.message).toContain('Unknown field "bogus"');
+	});
+
+	it('keeps numeric-looking values as strings on text fields', () => {
+		expect(parseSearchQuery('folder:001').filters).toEqual([{ field: 'folder', operator: 'eq', value: '001' }]);
+		expect(parseSearchQuery('status:1,02').filters).toEqual([{ field: 'status', operator: 'in', value: ['1', '02'] }]);
+	});
+
+	it('mixes terms and clauses', () => {
+		const parsed = parseSearchQuery('roadmap status:done');
+		expect(parsed.fts).toBe('"roadmap"');
+		expect(parsed.filters).toEqual([{ field: 'status', operator: 'eq', value: 'done' }]);
+	});
+});
+
+describe('parseSearchQuery() — custom property values', () => {
+	afterEach(() => {
+		setValidColumnNames(null);
+		setNumericColumnNames(null);
+	});
+
+	it('binds numbers only for numeric property fields', () => {
+		setValidColumnNames(new Set([...ALLOWED_FILTER_FIELDS, 'prop_code', 'prop_zip', 'prop_budget']));
+		setNumericColumnNames(new Set(['priority', 'prop_budget']));
+
+		expect(parseSearchQuery('prop_code:2024').filters).toEqual([{ field: 'prop_code', operator: 'eq', value: '2024' }]);
+		expect(parseSearchQuery('prop_zip:02139').filters).toEqual([{ field: 'prop_zip', operator: 'eq', value: '02139' }]);
+		expect(parseSearchQuery('prop_budget:>=1500').filters).toEqual([
+			{ field: 'prop_budget', operator: 'gte', value: 1500 },
+		]);
+	});
+});
+
+// ---------------------------------------------------------------------------
+// Error recovery
+// ---------------------------------------------------------------------------
+
+describe('parseSearchQuery() — errors', () => {
+	it('unterminated quote is reported and searched as a phrase', () => {
+		const parsed = parseSearchQuery('say "hello there');
+		expect(parsed.errors).toEqual([{ message: 'Unterminated quote', position: 4 }]);
+		expect(parsed.fts).toBe('"say" "hello there"');
+	});
+
+	it('exclusions without a positive term produce no FTS expression', () => {
+		const parsed = parseSearchQuery('-draft');
+		expect(parsed.fts).toBe('');
+		expect(parsed.errors[0]!.message).toBe('Exclusions need at least one search term');
+	});
+
+	it('dangling OR / NOT and unclosed NEAR are reported', () => {
+		expect(parseSearchQuery('alpha OR').errors[0]!.message).toBe('OR needs a term on both sides');
+		expect(parseSearchQuery('alpha NOT').errors[0]!.message).toBe('NOT needs a term after it');
+		expect(parseSearchQuery('NEAR(alpha beta').errors[0]!.message).toContain('Unclosed NEAR');
+	});
+
+	it('empty input parses to nothing', () => {
+		expect(parseSearchQuery('   ')).toEqual({ fts: '', filters: [], terms: [], errors: [] });
+	});
+});
+
+// ---------------------------------------------------------------------------
+// matchesSearchTerms
+// ---------------------------------------------------------------------------
+
+describe('matchesSearchTerms()', () => {
+	it('requires every positive term, case-insensitively', () => {
+		const parsed = parseSearchQuery('Alpha "beta gamma" -delta');
+		expect(matchesSearchTerms('ALPHA and Beta Gamma', parsed)).toBe(true);
+		expect(matchesSearchTerms('alpha only', parsed)).toBe(false);
+	});
+});
@@ -15,9 +15,6 @@ import { describe, expect, it, vi } from 'vitest';
 import { PanelRegistry } from '../../../src/ui/panels/PanelRegistry';
 import { PanelManager } from '../../../src/ui/panels/PanelManager';
 import type { PanelHook, PanelMeta, SlotConfig, CouplingGroup } from '../../../src/ui/panels';
-import { FORMULAS_PANEL_META } from '../../../src/ui/panels/FormulasPanelStub';
-import { MAPS_PANEL_META } from '../../../src/ui/panels/MapsPanelStub';
-import { STORIES_PANEL_META } from '../../../src/ui/panels/StoriesPanelStub';
 import { DOCK_DEFS } from '../../../src/ui/section-defs';
 
 // ---------------------------------------------------------------------------
@@ -32,13 +29,8 @@ const INLINE_PANEL_METAS: PanelMeta[] = [
 	{ id: 'notebook', name: 'Notebook', icon: 'notebook-pen', description: 'Notebook Explorer', dependencies: [], defaultEnabled: false },
 	{ id: 'calc', name: 'Calculations', icon: 'sigma', description: 'Calc Explorer', dependencies: [], defaultEnabled: false },
 	{ id: 'algorithm', name: 'Algorithm', icon: 'brain', description: 'Algorithm Explorer', dependencies: [], defaultEnabled: false },
-];
-
-/** All META-exported stub panels. */
-const STUB_PANEL_METAS: PanelMeta[] = [
-	MAPS_PANEL_META,
-	FORMULAS_PANEL_META,
-	STORIES_PANEL_META,
+	{ id: 'formulas', name: 'Formulas', icon: 'code', description: 'Formulas Explorer', dependencies: [], defaultEnabled: false },
+	{ id: 'stories', name: 'Stories', icon: 'book-open', description: 'Stories Explorer', dependencies: [], defaultEnabled: false },
 ];
 
 /** PanelManager slot config matching main.ts lines ~1729-1736. */
@@ -46,7 +38,7 @@ const PRODUCTION_SLOTS: SlotConfig[] = [
 	{ id: 'properties', container: document.createElement('div'), slot: 'top' },
 	{ id: 'projection', container: document.createElement('div'), slot: 'top' },
 	{ id: 'latch', container: document.createElement('div'), slot: 'bottom' },
-	{ id: 'formulas-stub', container: document.createElement('div'), slot: 'bottom' },
+	{ id: 'formulas', container: document.createElement('div'), slot: 'bottom' },
 ];
 
 /** PanelManager groups matching main.ts line ~1737. */
@@ -60,7 +52,7 @@ const PRODUCTION_GROUPS: CouplingGroup[] = [
  */
 const DOCK_TO_PANEL_MAP: Record<string, string> = {
 	'activate:notebook': 'notebook',
-	'activate:stories': 'stories-stub',
+	'activate:stories': 'stories',
 };
 
 function stubFactory(): PanelHook {
@@ -76,9 +68,6 @@ function buildProductionWiring() {
 	for (const meta of INLINE_PANEL_METAS) {
 		registry.register(meta, () => stubFactory());
 	}
-	for (const meta of STUB_PANEL_METAS) {
-		registry.register(meta, () => stubFactory());
-	}
 
 	// Fresh containers for each test
 	const slots: SlotConfig[] = PRODUCTION_SLOTS.map((s) => ({
@@ -176,16 +165,16 @@ describe('Dock → PanelManager wiring', () => {
 	// -----------------------------------------------------------------------
 	// Behavioral: analyze:formula click toggles formulas panel
 	// -----------------------------------------------------------------------
-	it('analyze:formula — toggle("formulas-stub") toggles formulas panel', () => {
+	it('analyze:formula — toggle("formulas") toggles formulas panel', () => {
 		const { manager } = buildProductionWiring();
 
-		expect(manager.isVisible('formulas-stub')).toBe(false);
+		expect(manager.isVisible('formulas')).toBe(false);
 
-		manager.toggle('formulas-stub');
-		expect(manager.isVisible('formulas-stub')).toBe(true);
+		manager.toggle('formulas');
+		expect(manager.isVisible('formulas')).toBe(true);
 
-		manager.toggle('formulas-stub');
-		expect(manager.isVisible('formulas-stub')).toBe(false);
+		manager.toggle('formulas');
+		expect(manager.isVisible('formulas')).toBe(false);
 	});
 
 	// -----------------------------------------------------------------------
@@ -271,9 +260,6 @@ describe('Dock → PanelManager wiring', () => {
 		for (const meta of INLINE_PANEL_METAS) {
 			registry.register(meta, () => stubFactory());
 		}
-		for (const meta of STUB_PANEL_METAS) {
-			registry.register(meta, () => stubFactory());
-		}
 		const slots: SlotConfig[] = PRODUCTION_SLOTS.map((s) => ({
 			...s,
 			container: document.createElement('div'),
@@ -291,7 +277,7 @@ describe('Dock → PanelManager wiring', () => {
 				sidecarVisible = mgr.isVisible('properties')
 					|| mgr.isVisible('projection')
 					|| mgr.isVisible('latch')
-					|| mgr.isVisible('formulas-stub');
+					|| mgr.isVisible('formulas');
 			},
 		});
 
@@ -0,0 +1,200 @@
+// Isometry v5 — Stories
+// StoryManager unit tests: slide capture from real providers, editing through
+// a mocked story:* bridge, and slide playback (view switch + state restore).
+
+import { beforeEach, describe, expect, it, vi } from 'vitest';
+import type { Story, StoryInput } from '../../src/database/queries/stories';
+import { DensityProvider } from '../../src/providers/DensityProvider';
+import { FilterProvider } from '../../src/providers/FilterProvider';
+import { PAFVProvider } from '../../src/providers/PAFVProvider';
+import { SelectionProvider } from '../../src/providers/SelectionProvider';
+import type { ViewType } from '../../src/providers/types';
+import { StoryManager } from '../../src/stories/StoryManager';
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/** In-memory story:* bridge mirroring saveStory() id handling. */
+function makeBridge() {
+	const stories = new Map<string, Story>();
+	let nextId = 1;
+	return {
+		stories,
+		listStories: vi.fn(async () => [...stories.values()]),
+		saveStory: vi.fn(async (input: StoryInput) => {
+			const id = input.id ?? `story-${nextId++}`;
+			const story: Story = {
+				id,
+				name: input.name,
+				slides: input.slides.map((s) => ({ id: s.id ?? `slide-${nextId++}`, caption: s.caption, state: s.state })),
+				created_at: '2026-01-01T00:00:00Z',
+				updated_at: '2026-01-01T00:00:00Z',
+			};
+			stories.set(id, story);
+			return story;
+		}),
+		deleteStory: vi.fn(async (id: string) => {
+			stories.delete(id);
+		}),
+	};
+}
+
+let bridge: ReturnType<typeof makeBridge>;
+let pafv: PAFVProvider;
+let filter: FilterProvider;
+let density: DensityProvider;
+let selection: SelectionProvider;
+let switchView: ReturnType<typeof vi.fn>;
+let onApplied: ReturnType<typeof vi.fn>;
+let manager: StoryManager;
+
+beforeEach(() => {
+	bridge = makeBridge();
+	pafv = new PAFVProvider();
+	filter = new FilterProvider();
+	density = new DensityProvider();
+	selection = new SelectionProvider();
+	switchView = vi.fn(async (viewType: ViewType) => pafv.setViewType(viewType));
+	onApplied = vi.fn();
+	manager = new StoryManager({ bridge, pafv, filter, density, selection, switchView, onApplied });
+});
+
+// ---------------------------------------------------------------------------
+// Capture
+// ---------------------------------------------------------------------------
+
+describe('StoryManager.capture', () => {
+	it('snapshots the view type, provider states and selection', () => {
+		pafv.setViewType('grid');
+		filter.addFilter({ field: 'folder', operator: 'eq', value: 'Work' });
+		density.setGranularity('week');
+		selection.selectAll(['a', 'b']);
+
+		const state = manager.capture();
+
+		expect(state.view).toBe('grid');
+		expect(state.pafv).toEqual(JSON.parse(pafv.toJSON()));
+		expect(state.filter['filters']).toEqual([{ field: 'folder', operator: 'eq', value: 'Work' }]);
+		expect(state.density).toEqual({ timeField: 'created_at', granularity: 'week' });
+		expect(state.selection).toEqual(['a', 'b']);
+	});
+});
+
+// ---------------------------------------------------------------------------
+// Editing
+// ---------------------------------------------------------------------------
+
+describe('StoryManager editing', () => {
+	it('creates a story with the current view as its first slide and notifies subscribers', async () => {
+		const listener = vi.fn();
+		manager.subscribe(listener);
+
+		const story = await manager.create('Findings');
+
+		expect(story.slides).toHaveLength(1);
+		expect(manager.list()).toEqual([story]);
+		expect(listener).toHaveBeenCalled();
+	});
+
+	it('adds, captions, moves and removes slides through story:save', async () => {
+		const story = await manager.create('Findings', false);
+		pafv.setViewType('kanban');
+		await manager.addSlide(story.id, 'first');
+		pafv.setViewType('map');
+		const withTwo = await manager.addSlide(story.id, 'second');
+		const [first, second] = withTwo.slides;
+
+		await manager.setCaption(story.id, first!.id, 'first (edited)');
+		const moved = await manager.moveSlide(story.id, second!.id, -5);
+		expect(moved.slides.map((s) => [s.caption, s.state.view])).toEqual([
+			['second', 'map'],
+			['first (edited)', 'kanban'],
+		]);
+
+		const removed = await manager.removeSlide(story.id, second!.id);
+		expect(removed.slides.map((s) => s.id)).toEqual([first!.id]);
+		expect(bridge.saveStory).toHaveBeenLastCalledWith(expect.objectContaining({ id: story.id, name: 'Findings' }));
+	});
+
+	it('recapture replaces a slide state and keeps its caption', async () => {
+		const story = await manager.create('Findings');
+		const slide = story.slides[0]!;
+		await manager.setCaption(story.id, slide.id, 'Keep me');
+		pafv.setViewType('timeline');
+
+		const updated = await manager.recapture(story.id, slide.id);
+
+		expect(updated.slides[0]).toMatchObject({ id: slide.id, caption: 'Keep me', state: { view: 'timeline' } });
+	});
+
+	it('rejects edits to unknown stories', async () => {
+		await expect(manager.addSlide('missing')).rejects.toThrow('Story not found: missing');
+	});
+});
+
+// ---------------------------------------------------------------------------
+// Playback
+// ---------------------------------------------------------------------------
+
+describe('StoryManager.applySlide', () => {
+	it('switches view, then restores provider states and selection', async () => {
+		pafv.setViewType('grid');
+		filter.addFilter({ field: 'status', operator: 'eq', value: 'done' });
+		density.setGranularity('year');
+		selection.selectAll(['x']);
+		const story = await manager.create('Findings');
+
+		pafv.setViewType('list');
+		filter.resetToDefaults();
+		density.resetToDefaults();
+		selection.clear();
+
+		const applied = await manager.applySlide(story.slides[0]!);
+
+		expect(applied).toBe(true);
+		expect(switchView).toHaveBeenCalledWith('grid');
+		expect(pafv.getState().viewType).toBe('grid');
+		expect(JSON.parse(filter.toJSON())['filters']).toEqual([{ field: 'status', operator: 'eq', value: 'done' }]);
+		expect(density.getState().granularity).toBe('year');
+		expect(selection.getSelectedIds()).toEqual(['x']);
+		expect(onApplied).toHaveBeenCalledOnce();
+	});
+
+	it('does not switch view when the slide uses the active view', async () => {
+		const story = await manager.create('Findings');
+		await manager.applySlide(story.slides[0]!);
+		expect(switchView).not.toHaveBeenCalled();
+	});
+
+	it('returns false and skips the re-render when a stored state no longer validates', async () => {
+		const story = await manager.create('Findings');
+		const slide = story.slides[0]!;
+		const filterState = { filters: [{ field: 'prop_gone', operator: 'eq', value: 1 }], searchQuery: null };
+		const broken = { ...slide, state: { ...slide.state, filter: filterState } };
+
+		expect(await manager.applySlide(broken)).toBe(false);
+		expect(onApplied).not.toHaveBeenCalled();
+	});
+
+	it('leaves view and every provider untouched when a later state is stale', async () => {
+		pafv.setViewType('grid');
+		const story = await manager.create('Findings');
+		const slide = story.slides[0]!;
+		const broken = {
+			...slide,
+			state: {
+				...slide.state,
+				filter: { filters: [{ field: 'status', operator: 'eq', value: 'done' }], searchQuery: null },
+				density: { ...slide.state.density, timeField: 'prop_gone' },
+			},
+		};
+
+		pafv.setViewType('list');
+		const before = { pafv: pafv.toJSON(), filter: filter.toJSON(), density: density.toJSON() };
+
+		expect(await manager.applySlide(broken)).toBe(false);
+		expect(switchView).not.toHaveBeenCalled();
+		expect({ pafv: pafv.toJSON(), filter: filter.toJSON(), density: density.toJSON() }).toEqual(before);
+	});
+});
@@ -0,0 +1,119 @@
+// @vitest-environment jsdom
+// Isometry v5 — Stories
+// Standalone HTML export tests: caption sanitization, escaping, state
+// summaries and file naming.
+
+import { describe, expect, it } from 'vitest';
+import type { Story, StorySlideState } from '../../src/database/queries/stories';
+import { describeSlideState, renderStoryHtml, storyExportFilename } from '../../src/stories/storyExport';
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+function slideState(overrides: Partial<StorySlideState> = {}): StorySlideState {
+	return {
+		view: 'supergrid',
+		pafv: {
+			viewType: 'supergrid',
+			groupBy: null,
+			colAxes: [{ field: 'status', direction: 'asc' }],
+			rowAxes: [{ field: 'folder', direction: 'asc' }],
+		},
+		filter: {
+			filters: [{ field: 'priority', operator: 'gte', value: 3 }],
+			searchQuery: 'launch',
+			rangeFilters: { created_at: { min: '2026-01-01', max: '2026-03-31' } },
+		},
+		density: { timeField: 'created_at', granularity: 'month' },
+		selection: [],
+		...overrides,
+	};
+}
+
+function story(overrides: Partial<Story> = {}): Story {
+	return {
+		id: 's1',
+		name: 'Q1 <Launch> Review',
+		slides: [
+			{ id: 'a', caption: '## Where we are\n\n**Most** tasks are done.<script>alert(1)</script>', state: slideState() },
+			{ id: 'b', caption: '', state: slideState({ view: 'list', pafv: { viewType: 'list' }, filter: {} }) },
+		],
+		created_at: '2026-01-01T00:00:00Z',
+		updated_at: '2026-01-01T00:00:00Z',
+		...overrides,
+	};
+}
+
+// ---------------------------------------------------------------------------
+// describeSlideState
+// ---------------------------------------------------------------------------
+
+describe('describeSlideState', () => {
+	it('summarizes view, axes, search and filters', () => {
+		expect(describeSlideState(slideState())).toEqual([
+			['View', 'Supergrid'],
+			['Columns', 'status'],
+			['Rows', 'folder'],
+			['Search', 'launch'],
+			['Filters', 'priority gte 3; created_at 2026-01-01–2026-03-31'],
+		]);
+	});
+
+	it('tolerates states missing optional parts', () => {
+		expect(describeSlideState(slideState({ view: 'list', pafv: {}, filter: {} }))).toEqual([['View', 'List']]);
+	});
+});
+
+// ---------------------------------------------------------------------------
+// renderStoryHtml
+// ---------------------------------------------------------------------------
+
+describe('renderStoryHtml', () => {
+	const cards = [
+		{
+			count: 12,
+			cards: [
+				{ id: 'c1', name: 'Ship <beta>' },
+				{ id: 'c2', name: 'Docs' },
+			],
+			selected: [{ id: 'c2', name: 'Docs' }],
+		},
+		{ count: null, cards: [], selected: [] },
+	];
+
+	it('renders one section per slide with sanitized markdown captions', () => {
+		const doc = new DOMParser().parseFromString(renderStoryHtml(story(), cards), 'text/html');
+
+		const sections = doc.querySelectorAll('section.slide');
+		expect(sections).toHaveLength(2);
+		expect(sections[0]!.querySelector('.caption h2')!.textContent).toBe('Where we are');
+		expect(sections[0]!.querySelector('.caption strong')!.textContent).toBe('Most');
+		expect(sections[0]!.querySelector('.caption script')).toBeNull();
+	});
+
+	it('escapes names and lists live and selected cards', () => {
+		const html = renderStoryHtml(story(), cards);
+		const doc = new DOMParser().parseFromString(html, 'text/html');
+
+		expect(doc.title).toBe('Q1 <Launch> Review');
+		expect(html).toContain('Ship &lt;beta&gt;');
+		const [first, second] = doc.querySelectorAll('section.slide');
+		expect(first!.textContent).toContain('12 matching cards');
+		expect(first!.querySelector('li.selected')!.textContent).toBe('Docs');
+		expect(second!.textContent).toContain('Filter no longer applies');
+	});
+
+	it('includes inline navigation so the file works on its own', () => {
+		const html = renderStoryHtml(story(), cards);
+		expect(html).toMatch(/<script>[\s\S]*ArrowRight[\s\S]*<\/script>/);
+		expect(html).not.toMatch(/<script src=|<link /);
+	});
+});
+
+describe('storyExportFilename', () => {
+	it('slugifies the story name', () => {
+		expect(storyExportFilename({ name: 'Q1 <Launch> Review' })).toBe('q1-launch-review.html');
+		expect(storyExportFilename({ name: '✨' })).toBe('story.html');
+	});
+});
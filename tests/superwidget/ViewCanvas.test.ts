@@ -54,7 +54,7 @@ vi.mock('../../src/superwidget/registry', () => ({
 // ---------------------------------------------------------------------------
 
 const VALID_VIEW_TYPES: ViewType[] = [
-  'list', 'grid', 'kanban', 'calendar', 'timeline', 'gallery', 'network', 'tree', 'supergrid',
+  'list', 'grid', 'kanban', 'calendar', 'timeline', 'gallery', 'network', 'tree', 'supergrid', 'map',
 ];
 
 function makeViewFactory(): Record<ViewType, () => IView> {
@@ -95,11 +95,11 @@ function makeProjection(activeTabId: string): Projection {
 // ---------------------------------------------------------------------------
 
 describe('VIEW_DISPLAY_NAMES', () => {
-  it('has exactly 9 entries', () => {
-    expect(Object.keys(VIEW_DISPLAY_NAMES)).toHaveLength(9);
+  it('has exactly 10 entries', () => {
+    expect(Object.keys(VIEW_DISPLAY_NAMES)).toHaveLength(10);
   });
 
-  it('maps all 9 ViewType literals', () => {
+  it('maps all 10 ViewType literals', () => {
     for (const vt of VALID_VIEW_TYPES) {
       expect(VIEW_DISPLAY_NAMES[vt]).toBeTruthy();
     }
@@ -0,0 +1,132 @@
+// @vitest-environment jsdom
+// Isometry v5 — CardPropertyFields Tests
+// Connection rows: reload after undo/redo, and the via card picker for duplicate names.
+
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import type { Mutation } from '../../src/mutations/types';
+import { CardPropertyFields } from '../../src/ui/CardPropertyFields';
+
+interface FakeDb {
+	label: string | null;
+	viaCandidates: Array<Record<string, unknown>>;
+}
+
+let db: FakeDb;
+let notify: () => void;
+let executed: Mutation[];
+let fields: CardPropertyFields;
+let container: HTMLElement;
+
+function connectionRow(): Record<string, unknown> {
+	return {
+		id: 'conn-1',
+		source_id: 'card-1',
+		target_id: 'card-2',
+		via_card_id: null,
+		label: db.label,
+		weight: 1,
+		created_at: '2026-01-01T00:00:00Z',
+		source_name: 'Card one',
+		target_name: 'Card two',
+		via_name: null,
+	};
+}
+
+const flush = () => new Promise((resolve) => setTimeout(resolve, 0));
+const input = (placeholder: string) =>
+	container.querySelector<HTMLInputElement>(`.cpf-conn input[placeholder="${placeholder}"]`)!;
+
+function commit(el: HTMLInputElement, value: string): void {
+	el.value = value;
+	el.dispatchEvent(new Event('blur'));
+}
+
+beforeEach(() => {
+	db = { label: 'A', viaCandidates: [] };
+	executed = [];
+	notify = () => {};
+
+	const bridge = {
+		send: vi.fn(async (type: string, payload: { sql: string }) => {
+			if (type !== 'db:query') return undefined;
+			if (payload.sql.includes('FROM connections')) return { rows: [connectionRow()] };
+			if (payload.sql.includes('WHERE name = ?')) return { rows: db.viaCandidates };
+			return { rows: [] };
+		}),
+		findSimilarCards: vi.fn(async () => []),
+	};
+	const mutations = {
+		execute: vi.fn(async (mutation: Mutation) => {
+			executed.push(mutation);
+		}),
+		subscribe: vi.fn((callback: () => void) => {
+			notify = callback;
+			return () => {
+				notify = () => {};
+			};
+		}),
+	};
+
+	fields = new CardPropertyFields({ bridge: bridge as any, mutations: mutations as any });
+	container = document.createElement('div');
+	fields.mount(container);
+	fields.update({ id: 'card-1', name: 'Card one', tags: [] } as any);
+});
+
+afterEach(() => {
+	fields.destroy();
+});
+
+describe('CardPropertyFields connections', () => {
+	it('reloads connection rows after undo so the next edit inverts to the restored value', async () => {
+		await flush();
+		expect(input('Label').value).toBe('A');
+
+		commit(input('Label'), 'B');
+		await flush();
+		expect(executed[0]!.inverse[0]!.params).toEqual(['A', 'conn-1']);
+
+		// Undo restores A in the database and notifies subscribers
+		db.label = 'A';
+		notify();
+		await flush();
+		expect(input('Label').value).toBe('A');
+
+		commit(input('Label'), 'C');
+		await flush();
+		expect(executed).toHaveLength(2);
+		expect(executed[1]!.forward[0]!.params).toEqual(['C', 'conn-1']);
+		expect(executed[1]!.inverse[0]!.params).toEqual(['A', 'conn-1']);
+	});
+
+	it('offers a picker when several cards share the via name and commits the picked id', async () => {
+		db.viaCandidates = [
+			{ id: 'via-1', name: 'Meeting', folder: 'Work', created_at: '2026-01-02T00:00:00Z' },
+			{ id: 'via-2', name: 'Meeting', folder: null, created_at: '2026-02-03T00:00:00Z' },
+		];
+		await flush();
+
+		commit(input('Via card name'), 'Meeting');
+		await flush();
+		expect(executed).toHaveLength(0);
+
+		const picker = container.querySelector<HTMLSelectElement>('.cpf-conn__via-picker')!;
+		expect([...picker.options].map((o) => o.value)).toEqual(['', 'via-1', 'via-2']);
+		expect(picker.options[2]!.textContent).toBe('Meeting \u2014 No folder, 2026-02-03');
+
+		picker.value = 'via-2';
+		picker.dispatchEvent(new Event('change'));
+		await flush();
+		expect(executed[0]!.forward[0]!.params).toEqual(['via-2', 'conn-1']);
+		expect(container.querySelector('.cpf-conn__via-picker')).toBeNull();
+	});
+
+	it('commits a unique via name directly', async () => {
+		db.viaCandidates = [{ id: 'via-1', name: 'Meeting', folder: null, created_at: '2026-01-02T00:00:00Z' }];
+		await flush();
+
+		commit(input('Via card name'), 'Meeting');
+		await flush();
+		expect(executed[0]!.forward[0]!.params).toEqual(['via-1', 'conn-1']);
+	});
+});
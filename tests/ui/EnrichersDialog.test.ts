@@ -0,0 +1,84 @@
+// @vitest-environment jsdom
+// Isometry v5 — EnrichersDialog Tests
+// Per-source checkbox state, persisted switches, and the re-run result.
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import type { EnricherInfo, EnricherSetting } from '../../src/etl/enrichment/types';
+import { EnrichersDialog } from '../../src/ui/EnrichersDialog';
+
+// jsdom does not implement HTMLDialogElement.showModal()/close()
+const originalCreateElement = document.createElement.bind(document);
+beforeEach(() => {
+	document.createElement = (<K extends keyof HTMLElementTagNameMap>(
+		tagName: K,
+		options?: ElementCreationOptions,
+	): HTMLElementTagNameMap[K] => {
+		const el = originalCreateElement(tagName, options);
+		if (tagName === 'dialog') {
+			const dialog = el as HTMLDialogElement;
+			dialog.showModal = () => dialog.setAttribute('open', '');
+			dialog.close = () => dialog.removeAttribute('open');
+		}
+		return el;
+	}) as typeof document.createElement;
+});
+
+afterEach(() => {
+	document.createElement = originalCreateElement;
+	document.body.querySelectorAll('dialog').forEach((d) => d.remove());
+});
+
+const enrichers: EnricherInfo[] = [
+	{ id: 'folder-hierarchy', description: 'Folder levels', appliesTo: '*', defaultEnabled: true },
+	{ id: 'reading-time', description: 'Reading time', appliesTo: '*', defaultEnabled: false },
+	{ id: 'contacts-only', description: 'vCard only', appliesTo: ['vcard'], defaultEnabled: false },
+];
+
+const checkboxes = () => [...document.body.querySelectorAll<HTMLInputElement>('.enrichers-list input')];
+const sourceSelect = () => document.body.querySelector<HTMLSelectElement>('.enrichers-modal__source select')!;
+
+function pickSource(value: string): void {
+	sourceSelect().value = value;
+	sourceSelect().dispatchEvent(new Event('change'));
+}
+
+describe('EnrichersDialog', () => {
+	it('shows defaults for all sources and per-source overrides', () => {
+		const settings: EnricherSetting[] = [{ enricher_id: 'reading-time', source_type: 'csv', enabled: true }];
+		void EnrichersDialog.show({ enrichers, settings, onChange: () => {} });
+
+		expect(checkboxes().map((c) => c.checked)).toEqual([true, false, false]);
+
+		pickSource('csv');
+		expect(checkboxes().map((c) => c.checked)).toEqual([true, true, false]);
+		expect(checkboxes().map((c) => c.disabled)).toEqual([false, false, true]);
+	});
+
+	it('persists each switch for the picked source', () => {
+		const changes: EnricherSetting[] = [];
+		void EnrichersDialog.show({ enrichers, settings: [], onChange: (s) => void changes.push(s) });
+
+		pickSource('markdown');
+		const readingTime = checkboxes()[1]!;
+		readingTime.checked = true;
+		readingTime.dispatchEvent(new Event('change'));
+
+		// The local copy follows, so switching away and back keeps the new state
+		pickSource('csv');
+		expect(checkboxes()[1]!.checked).toBe(false);
+		pickSource('markdown');
+		expect(checkboxes()[1]!.checked).toBe(true);
+		expect(changes).toEqual([{ enricher_id: 'reading-time', source_type: 'markdown', enabled: true }]);
+	});
+
+	it('resolves true for re-run and false on close', async () => {
+		const rerun = EnrichersDialog.show({ enrichers, settings: [], onChange: () => {} });
+		document.body.querySelector<HTMLButtonElement>('.app-dialog__btn--confirm')!.click();
+		expect(await rerun).toBe(true);
+		expect(document.body.querySelector('dialog')).toBeNull();
+
+		const closed = EnrichersDialog.show({ enrichers, settings: [], onChange: () => {} });
+		document.body.querySelector<HTMLButtonElement>('.app-dialog__btn--cancel')!.click();
+		expect(await closed).toBe(false);
+	});
+});
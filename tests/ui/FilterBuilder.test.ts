@@ -0,0 +1,120 @@
+// @vitest-environment jsdom
+// Isometry v5 — FilterBuilder
+// Tests for the AND/OR/NOT filter tree builder: draft editing, commit to
+// FilterProvider, and reload on external tree changes.
+
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import { FilterProvider } from '../../src/providers/FilterProvider';
+import type { FilterGroup } from '../../src/providers/types';
+import { FilterBuilder } from '../../src/ui/FilterBuilder';
+
+let container: HTMLElement;
+let filter: FilterProvider;
+let builder: FilterBuilder;
+
+beforeEach(() => {
+	vi.useFakeTimers();
+	container = document.createElement('div');
+	document.body.appendChild(container);
+	filter = new FilterProvider();
+	builder = new FilterBuilder({ filter });
+	builder.mount(container);
+});
+
+afterEach(() => {
+	builder.destroy();
+	container.remove();
+	vi.useRealTimers();
+});
+
+function click(label: string, index = 0): void {
+	const buttons = [...container.querySelectorAll<HTMLButtonElement>('button')].filter((b) => b.textContent === label);
+	buttons[index]!.click();
+}
+
+function setSelect(selector: string, value: string, index = 0): void {
+	const select = container.querySelectorAll<HTMLSelectElement>(selector)[index]!;
+	select.value = value;
+	select.dispatchEvent(new Event('change'));
+}
+
+function typeValue(value: string, index = 0): void {
+	const input = container.querySelectorAll<HTMLInputElement>('.filter-builder__value')[index]!;
+	input.value = value;
+	input.dispatchEvent(new Event('input'));
+	vi.advanceTimersByTime(300);
+}
+
+describe('FilterBuilder', () => {
+	it('renders an empty root group', () => {
+		expect(container.querySelectorAll('.filter-builder__group')).toHaveLength(1);
+		expect(container.querySelector('.filter-builder__empty')?.textContent).toBe('No conditions');
+	});
+
+	it('does not commit a condition until it has a value', () => {
+		click('+ Condition');
+		expect(container.querySelectorAll('.filter-builder__condition')).toHaveLength(1);
+		expect(filter.getFilterTree()).toBeNull();
+
+		setSelect('.filter-builder__field', 'status');
+		typeValue('todo');
+		expect(filter.getFilterTree()).toEqual({
+			kind: 'group',
+			combinator: 'and',
+			negate: false,
+			children: [{ field: 'status', operator: 'eq', value: 'todo' }],
+		});
+	});
+
+	it('builds nested OR groups and NOT', () => {
+		click('+ Group');
+		// Nested group's actions render before the root group's
+		click('+ Condition', 0);
+		setSelect('.filter-builder__field', 'status');
+		typeValue('todo');
+		click('Not', 1);
+
+		const tree = filter.getFilterTree()!;
+		expect(tree.children).toHaveLength(1);
+		const nested = tree.children[0] as FilterGroup;
+		expect(nested.combinator).toBe('or');
+		expect(nested.negate).toBe(true);
+		expect(filter.compile().where).toContain('NOT IFNULL((status = ?), 0)');
+	});
+
+	it('valueless operators commit without a value and "is one of" splits on commas', () => {
+		click('+ Condition');
+		setSelect('.filter-builder__operator', 'isNull');
+		expect(container.querySelector('.filter-builder__value')).toBeNull();
+		expect(filter.compile().where).toContain('IS NULL');
+
+		setSelect('.filter-builder__operator', 'in');
+		setSelect('.filter-builder__field', 'card_type');
+		typeValue('note, task');
+		expect(filter.compile().params).toEqual(['note', 'task']);
+	});
+
+	it('removing the last condition clears the provider tree', () => {
+		click('+ Condition');
+		typeValue('x');
+		expect(filter.hasFilterTree()).toBe(true);
+		container.querySelector<HTMLButtonElement>('.filter-builder__condition .filter-builder__remove')!.click();
+		expect(filter.getFilterTree()).toBeNull();
+	});
+
+	it('reloads the draft when the tree changes outside the builder', async () => {
+		filter.setFilterTree({
+			kind: 'group',
+			combinator: 'or',
+			negate: false,
+			children: [{ field: 'folder', operator: 'eq', value: 'Work' }],
+		});
+		await Promise.resolve();
+		expect(container.querySelector<HTMLSelectElement>('.filter-builder__combinator')!.value).toBe('or');
+		expect(container.querySelector<HTMLInputElement>('.filter-builder__value')!.value).toBe('Work');
+
+		filter.clearFilters();
+		await Promise.resolve();
+		expect(container.querySelectorAll('.filter-builder__condition')).toHaveLength(0);
+	});
+});
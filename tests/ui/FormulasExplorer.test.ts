@@ -0,0 +1,192 @@
+// @vitest-environment jsdom
+// Isometry v5 — Formulas Explorer
+// Tests for FormulasExplorer: list rendering, save/delete round-trips through
+// the bridge, and the debounced live preview.
+
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import type { FormulaInfo } from '../../src/providers/formulas';
+import { FormulasExplorer } from '../../src/ui/FormulasExplorer';
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+function formula(overrides: Partial<FormulaInfo> = {}): FormulaInfo {
+	return {
+		id: 'f1',
+		name: 'days_open',
+		label: 'days_open',
+		dataset_id: null,
+		expression: 'julianday(now) - julianday(created_at)',
+		result_type: 'number',
+		created_at: '2026-01-01T00:00:00Z',
+		updated_at: '2026-01-01T00:00:00Z',
+		field: 'fx_days_open',
+		sql: "(julianday(strftime('%Y-%m-%dT%H:%M:%SZ', 'now')) - julianday(cards.created_at))",
+		error: null,
+		...overrides,
+	};
+}
+
+function createMockBridge(formulas: FormulaInfo[] = []) {
+	return {
+		send: vi.fn().mockResolvedValue([{ id: 'ds-1', name: 'Tasks' }]),
+		listFormulas: vi.fn().mockResolvedValue(formulas),
+		defineFormula: vi.fn().mockResolvedValue(formula()),
+		deleteFormula: vi.fn().mockResolvedValue(undefined),
+		previewFormula: vi.fn().mockResolvedValue({ sql: '1', error: null, position: null, rows: [] }),
+	};
+}
+
+let container: HTMLDivElement;
+let bridge: ReturnType<typeof createMockBridge>;
+let onFormulasChange: ReturnType<typeof vi.fn>;
+
+beforeEach(() => {
+	container = document.createElement('div');
+	document.body.appendChild(container);
+	onFormulasChange = vi.fn();
+});
+
+afterEach(() => {
+	container.remove();
+	vi.useRealTimers();
+	vi.restoreAllMocks();
+});
+
+async function mountExplorer(formulas: FormulaInfo[] = []): Promise<FormulasExplorer> {
+	bridge = createMockBridge(formulas);
+	const explorer = new FormulasExplorer({ bridge: bridge as any, container, onFormulasChange });
+	await explorer.mount();
+	return explorer;
+}
+
+function typeFormula(text: string): HTMLInputElement {
+	const input = container.querySelector<HTMLInputElement>('.formulas-explorer__input')!;
+	input.value = text;
+	input.dispatchEvent(new Event('input'));
+	return input;
+}
+
+function preview(): HTMLElement {
+	return container.querySelector<HTMLElement>('.formulas-explorer__preview')!;
+}
+
+// ---------------------------------------------------------------------------
+// Rendering
+// ---------------------------------------------------------------------------
+
+describe('FormulasExplorer — list', () => {
+	it('shows an empty state without formulas', async () => {
+		const explorer = await mountExplorer();
+		expect(container.querySelector('.formulas-explorer__empty')!.textContent).toBe('No formulas yet');
+		explorer.destroy();
+		expect(container.querySelector('.formulas-explorer')).toBeNull();
+	});
+
+	it('lists formulas with field, type, scope and compile errors', async () => {
+		await mountExplorer([
+			formula(),
+			formula({ id: 'f2', name: 'half', field: 'fx_half', dataset_id: 'ds-1', sql: null, error: 'Unknown field "x"' }),
+		]);
+
+		const items = container.querySelectorAll('.formulas-explorer__item');
+		expect(items).toHaveLength(2);
+		expect(items[0]!.querySelector('.formulas-explorer__name')!.textContent).toBe('fx_days_open');
+		expect(items[0]!.querySelector('.formulas-explorer__meta')!.textContent).toBe('number · All datasets');
+		expect(items[1]!.classList.contains('formulas-explorer__item--error')).toBe(true);
+		expect(items[1]!.querySelector('.formulas-explorer__meta')!.textContent).toBe('number · Tasks');
+		expect(items[1]!.querySelector('.formulas-explorer__error')!.textContent).toBe('Unknown field "x"');
+	});
+
+	it('offers every dataset as a scope', async () => {
+		await mountExplorer();
+		const scopes = [...container.querySelectorAll('.formulas-explorer__scope option')].map((o) => o.textContent);
+		expect(scopes).toEqual(['All datasets', 'Tasks']);
+	});
+});
+
+// ---------------------------------------------------------------------------
+// Save / delete
+// ---------------------------------------------------------------------------
+
+describe('FormulasExplorer — save and delete', () => {
+	it('saves "name = expression" with the chosen type and scope, then notifies', async () => {
+		await mountExplorer();
+		const input = typeFormula('late = due_at < today');
+		container.querySelector<HTMLSelectElement>('.formulas-explorer__type')!.value = 'text';
+		container.querySelector<HTMLSelectElement>('.formulas-explorer__scope')!.value = 'ds-1';
+		bridge.listFormulas.mockResolvedValue([formula()]);
+
+		container.querySelector('form')!.dispatchEvent(new Event('submit', { cancelable: true }));
+
+		await vi.waitFor(() => expect(onFormulasChange).toHaveBeenCalledWith([formula()]));
+		expect(bridge.defineFormula).toHaveBeenCalledWith({
+			name: 'late',
+			expression: 'due_at < today',
+			dataset_id: 'ds-1',
+			result_type: 'text',
+		});
+		expect(input.value).toBe('');
+	});
+
+	it('shows parse errors without calling the bridge', async () => {
+		await mountExplorer();
+		typeFormula('just an expression');
+
+		container.querySelector('form')!.dispatchEvent(new Event('submit', { cancelable: true }));
+
+		await vi.waitFor(() => expect(preview().textContent).toBe('Write formulas as "name = expression" (at 1)'));
+		expect(preview().classList.contains('formulas-explorer__preview--error')).toBe(true);
+		expect(bridge.defineFormula).not.toHaveBeenCalled();
+	});
+
+	it('deletes a formula and notifies with the reloaded list', async () => {
+		await mountExplorer([formula()]);
+		bridge.listFormulas.mockResolvedValue([]);
+
+		container.querySelector<HTMLButtonElement>('.formulas-explorer__delete')!.click();
+
+		await vi.waitFor(() => expect(onFormulasChange).toHaveBeenCalledWith([]));
+		expect(bridge.deleteFormula).toHaveBeenCalledWith('f1');
+	});
+});
+
+// ---------------------------------------------------------------------------
+// Preview
+// ---------------------------------------------------------------------------
+
+describe('FormulasExplorer — preview', () => {
+	it('previews sample values after the debounce', async () => {
+		await mountExplorer();
+		vi.useFakeTimers();
+		bridge.previewFormula.mockResolvedValue({
+			sql: '(cards.priority * 2)',
+			error: null,
+			position: null,
+			rows: [
+				{ id: 'a', name: 'Alpha', value: 4 },
+				{ id: 'b', name: 'Beta', value: null },
+			],
+		});
+
+		typeFormula('double = priority * 2');
+		expect(bridge.previewFormula).not.toHaveBeenCalled();
+		await vi.advanceTimersByTimeAsync(300);
+
+		expect(bridge.previewFormula).toHaveBeenCalledWith('priority * 2', null);
+		const values = [...preview().querySelectorAll('dd')].map((d) => d.textContent);
+		expect(values).toEqual(['4', '—']);
+	});
+
+	it('reports compile errors at their position in the typed text', async () => {
+		await mountExplorer();
+		vi.useFakeTimers();
+		bridge.previewFormula.mockResolvedValue({ sql: null, error: 'Unknown field "nope"', position: 0, rows: [] });
+
+		typeFormula('x = nope + 1');
+		await vi.advanceTimersByTimeAsync(300);
+
+		expect(preview().textContent).toBe('Unknown field "nope" (at 5)');
+	});
+});
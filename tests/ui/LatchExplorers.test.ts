@@ -6,7 +6,7 @@
 // TDD Phase: RED -> GREEN -> REFACTOR
 
 import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
-import type { Filter } from '../../src/providers/types';
+import type { RelativeDateExpr } from '../../src/providers/types';
 
 // ---------------------------------------------------------------------------
 // Mock types
@@ -27,6 +27,11 @@ interface MockFilterProvider {
 	hasRangeFilter: ReturnType<typeof vi.fn>;
 	clearRangeFilter: ReturnType<typeof vi.fn>;
 	setRangeFilter: ReturnType<typeof vi.fn>;
+	// Relative date filter API
+	setRelativeDateFilter: ReturnType<typeof vi.fn>;
+	getRelativeDateFilter: ReturnType<typeof vi.fn>;
+	clearRelativeDateFilter: ReturnType<typeof vi.fn>;
+	hasRelativeDateFilter: ReturnType<typeof vi.fn>;
 	compile: ReturnType<typeof vi.fn>;
 }
 
@@ -44,6 +49,7 @@ interface MockCoordinator {
 
 function createMockFilter(): MockFilterProvider {
 	const subscribers = new Set<() => void>();
+	const relative = new Map<string, RelativeDateExpr>();
 	return {
 		addFilter: vi.fn(),
 		removeFilter: vi.fn(),
@@ -62,6 +68,14 @@ function createMockFilter(): MockFilterProvider {
 		hasRangeFilter: vi.fn().mockReturnValue(false),
 		clearRangeFilter: vi.fn(),
 		setRangeFilter: vi.fn(),
+		// Relative date filters are stateful so preset toggling can be observed
+		setRelativeDateFilter: vi.fn((field: string, expr: RelativeDateExpr | null) => {
+			if (expr === null) relative.delete(field);
+			else relative.set(field, expr);
+		}),
+		getRelativeDateFilter: vi.fn((field: string) => relative.get(field) ?? null),
+		clearRelativeDateFilter: vi.fn((field: string) => relative.delete(field)),
+		hasRelativeDateFilter: vi.fn((field: string) => relative.has(field)),
 		compile: vi.fn().mockReturnValue({ where: 'deleted_at IS NULL', params: [] }),
 	};
 }
@@ -78,6 +92,7 @@ function createMockBridge(): MockBridge {
 	return {
 		send: vi.fn().mockImplementation((type: string) => {
 			if (type === 'histogram:query') return Promise.resolve({ bins: [] });
+			if (type === 'location:query') return Promise.resolve({ points: [], bounds: null, total: 0 });
 			return Promise.resolve({ rows: [] });
 		}),
 	};
@@ -179,7 +194,7 @@ describe('LatchExplorers — mount DOM structure', () => {
 });
 
 // ---------------------------------------------------------------------------
-// Location section — empty state
+// Location section — map + place-name chips
 // ---------------------------------------------------------------------------
 
 describe('LatchExplorers — Location section', () => {
@@ -200,7 +215,7 @@ describe('LatchExplorers — Location section', () => {
 		container.remove();
 	});
 
-	it('Location section shows empty state placeholder', () => {
+	it('Location section mounts the map and a location_name chip group', async () => {
 		const explorers = new LatchExplorers({
 			filter: filter as any,
 			bridge: bridge as any,
@@ -208,9 +223,34 @@ describe('LatchExplorers — Location section', () => {
 		});
 		explorers.mount(container);
 
-		const emptyEl = container.querySelector('.latch-explorers__empty');
-		expect(emptyEl).not.toBeNull();
-		expect(emptyEl!.textContent).toContain('No location properties available');
+		expect(container.querySelector('.latch-explorers__location-map svg')).not.toBeNull();
+		expect(container.querySelector('.latch-explorers__chip-list[data-field="location_name"]')).not.toBeNull();
+		await vi.waitFor(() => {
+			expect(bridge.send).toHaveBeenCalledWith('location:query', expect.anything(), { channel: 'location' });
+		});
+
+		explorers.destroy();
+	});
+
+	it('L badge counts an active map selection and Clear all removes it', () => {
+		filter.hasRangeFilter.mockImplementation((field: string) => field === 'latitude' || field === 'longitude');
+		const explorers = new LatchExplorers({
+			filter: filter as any,
+			bridge: bridge as any,
+			coordinator: coordinator as any,
+		});
+		explorers.mount(container);
+		notifyFilterSubscribers(filter);
+
+		// Location is the first LATCH section
+		const badge = container.querySelector('.collapsible-section__count') as HTMLElement | null;
+		expect(badge?.textContent).toBe('(1)');
+
+		const clearAll = container.querySelector('.latch-explorers__clear-all') as HTMLButtonElement;
+		expect(clearAll.style.display).toBe('');
+		clearAll.click();
+		expect(filter.clearRangeFilter).toHaveBeenCalledWith('latitude');
+		expect(filter.clearRangeFilter).toHaveBeenCalledWith('longitude');
 
 		explorers.destroy();
 	});
@@ -540,7 +580,7 @@ describe('LatchExplorers — Time preset buttons', () => {
 		explorers.destroy();
 	});
 
-	it('clicking a time preset calls addFilter with gte and lte', () => {
+	it('clicking a time preset sets a relative date filter (no absolute gte/lte)', () => {
 		const explorers = new LatchExplorers({
 			filter: filter as any,
 			bridge: bridge as any,
@@ -551,11 +591,12 @@ describe('LatchExplorers — Time preset buttons', () => {
 		const firstPreset = container.querySelector('.latch-explorers__time-preset') as HTMLButtonElement;
 		firstPreset.click();
 
-		// Should have 2 calls: gte and lte
-		expect(filter.addFilter).toHaveBeenCalledTimes(2);
-		const calls = filter.addFilter.mock.calls as Array<[Filter]>;
-		expect(calls[0]![0].operator).toBe('gte');
-		expect(calls[1]![0].operator).toBe('lte');
+		expect(filter.addFilter).not.toHaveBeenCalled();
+		expect(filter.setRelativeDateFilter).toHaveBeenCalledWith(firstPreset.dataset['field'], {
+			kind: 'period',
+			offset: 0,
+			unit: 'day',
+		});
 
 		explorers.destroy();
 	});
@@ -590,6 +631,43 @@ describe('LatchExplorers — Time preset buttons', () => {
 		// Should have called removeFilter to clear existing gte/lte, but NOT added new ones
 		expect(filter.removeFilter).toHaveBeenCalled();
 		expect(filter.addFilter).not.toHaveBeenCalled();
+		expect(filter.setRelativeDateFilter).toHaveBeenLastCalledWith(firstPreset.dataset['field'], null);
+		expect(firstPreset.classList.contains('latch-explorers__time-preset--active')).toBe(false);
+
+		explorers.destroy();
+	});
+
+	it('relative date input applies parsed expressions and flags invalid text', () => {
+		const explorers = new LatchExplorers({
+			filter: filter as any,
+			bridge: bridge as any,
+			coordinator: coordinator as any,
+		});
+		explorers.mount(container);
+
+		const input = container.querySelector('.latch-explorers__relative-input') as HTMLInputElement;
+		const field = input.dataset['field']!;
+
+		input.value = 'someday';
+		input.dispatchEvent(new KeyboardEvent('keydown', { key: 'Enter' }));
+		expect(filter.setRelativeDateFilter).not.toHaveBeenCalled();
+		expect(input.getAttribute('aria-invalid')).toBe('true');
+
+		input.value = 'last 14 days';
+		input.dispatchEvent(new Event('input'));
+		input.dispatchEvent(new KeyboardEvent('keydown', { key: 'Enter' }));
+		expect(input.hasAttribute('aria-invalid')).toBe(false);
+		expect(filter.setRelativeDateFilter).toHaveBeenCalledWith(field, {
+			kind: 'rolling',
+			direction: 'past',
+			amount: 14,
+			unit: 'day',
+		});
+		expect(input.value).toBe('Last 14 days');
+
+		input.value = '';
+		input.dispatchEvent(new KeyboardEvent('keydown', { key: 'Enter' }));
+		expect(filter.setRelativeDateFilter).toHaveBeenLastCalledWith(field, null);
 
 		explorers.destroy();
 	});
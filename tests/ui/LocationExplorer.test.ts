@@ -0,0 +1,169 @@
+// @vitest-environment jsdom
+// Isometry v5 — LATCH Location explorer
+// Tests for LocationExplorer: offline map rendering, box/radius selection
+// compiled to latitude/longitude range filters, and the radius bounding box.
+
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import { LocationExplorer, radiusToBounds } from '../../src/ui/LocationExplorer';
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+function createMockFilter() {
+	return {
+		compile: vi.fn().mockReturnValue({ where: 'deleted_at IS NULL', params: [] }),
+		setRangeFilter: vi.fn(),
+		clearRangeFilter: vi.fn(),
+	};
+}
+
+const RESPONSE = {
+	points: [
+		{ latitude: 51.5073, longitude: -0.1277, count: 3, locationName: 'London' },
+		{ latitude: 48.8566, longitude: 2.3522, count: 1, locationName: null },
+	],
+	bounds: { south: 48.8566, west: -0.1277, north: 51.5073, east: 2.3522 },
+	total: 4,
+};
+
+let container: HTMLDivElement;
+let filter: ReturnType<typeof createMockFilter>;
+
+beforeEach(() => {
+	container = document.createElement('div');
+	document.body.appendChild(container);
+	filter = createMockFilter();
+});
+
+afterEach(() => {
+	container.remove();
+	vi.restoreAllMocks();
+});
+
+function mountExplorer(response: unknown = RESPONSE): LocationExplorer {
+	const bridge = { send: vi.fn().mockResolvedValue(response) };
+	const explorer = new LocationExplorer({ filter: filter as any, bridge: bridge as any });
+	explorer.mount(container);
+	return explorer;
+}
+
+// ---------------------------------------------------------------------------
+// Rendering
+// ---------------------------------------------------------------------------
+
+describe('LocationExplorer — rendering', () => {
+	it('draws an offline outline, graticule and one circle per point', async () => {
+		const explorer = mountExplorer();
+
+		await vi.waitFor(() => {
+			expect(container.querySelectorAll('.latch-explorers__location-point')).toHaveLength(2);
+		});
+		expect(container.querySelector('.latch-explorers__location-outline')!.getAttribute('d')).toBeTruthy();
+		expect(container.querySelector('.latch-explorers__location-graticule')!.getAttribute('d')).toBeTruthy();
+		expect(container.querySelector('.latch-explorers__location-summary')!.textContent).toBe('4 located cards');
+
+		const titles = [...container.querySelectorAll('.latch-explorers__location-point title')].map((t) => t.textContent);
+		expect(titles).toEqual(['London: 3', '48.857, 2.352: 1']);
+
+		explorer.destroy();
+	});
+
+	it('shows an empty state when no card has coordinates', async () => {
+		const explorer = mountExplorer({ points: [], bounds: null, total: 0 });
+
+		await vi.waitFor(() => {
+			expect(container.querySelector('.latch-explorers__location-summary')!.textContent).toBe(
+				'No cards with coordinates',
+			);
+		});
+		expect(container.querySelector('.latch-explorers__location-map--empty')).not.toBeNull();
+
+		explorer.destroy();
+	});
+
+	it('shows a retry error when the query fails', async () => {
+		vi.spyOn(console, 'error').mockImplementation(() => {});
+		const bridge = { send: vi.fn().mockRejectedValue(new Error('DB error')) };
+		const explorer = new LocationExplorer({ filter: filter as any, bridge: bridge as any });
+		explorer.mount(container);
+
+		await vi.waitFor(() => {
+			expect(container.querySelector('.latch-explorers__scrubber-error')).not.toBeNull();
+		});
+
+		explorer.destroy();
+	});
+});
+
+// ---------------------------------------------------------------------------
+// Selection
+// ---------------------------------------------------------------------------
+
+describe('LocationExplorer — selection', () => {
+	it('selectBox sets latitude and longitude range filters', () => {
+		const explorer = mountExplorer();
+
+		explorer.selectBox({ south: 48, west: -1, north: 52, east: 3 });
+
+		expect(filter.setRangeFilter).toHaveBeenCalledWith('latitude', 48, 52);
+		expect(filter.setRangeFilter).toHaveBeenCalledWith('longitude', -1, 3);
+
+		explorer.destroy();
+	});
+
+	it('selectRadius compiles to the bounding box of the circle', () => {
+		const explorer = mountExplorer();
+
+		explorer.selectRadius(0, 0, 111.19);
+
+		const [latCall, lngCall] = filter.setRangeFilter.mock.calls as Array<[string, number, number]>;
+		expect(latCall![0]).toBe('latitude');
+		expect(latCall![1]).toBeCloseTo(-1, 2);
+		expect(latCall![2]).toBeCloseTo(1, 2);
+		expect(lngCall![0]).toBe('longitude');
+		expect(lngCall![1]).toBeCloseTo(-1, 2);
+
+		explorer.destroy();
+	});
+
+	it('setMode toggles the active mode button', () => {
+		const explorer = mountExplorer();
+		const active = () => container.querySelector('.latch-explorers__location-mode--active')?.textContent;
+
+		expect(active()).toBe('Box');
+		explorer.setMode('radius');
+		expect(explorer.getMode()).toBe('radius');
+		expect(active()).toBe('Radius');
+
+		explorer.destroy();
+	});
+
+	it('clearSelection is visual only', () => {
+		const explorer = mountExplorer();
+		explorer.selectRadius(51.5, 0, 10);
+		explorer.clearSelection();
+
+		expect(filter.clearRangeFilter).not.toHaveBeenCalled();
+		expect((container.querySelector('.latch-explorers__location-radius') as SVGElement).style.display).toBe('none');
+
+		explorer.destroy();
+	});
+});
+
+// ---------------------------------------------------------------------------
+// radiusToBounds
+// ---------------------------------------------------------------------------
+
+describe('radiusToBounds', () => {
+	it('widens longitude away from the equator', () => {
+		const b = radiusToBounds(60, 10, 111.19);
+		expect(b.north - b.south).toBeCloseTo(2, 2);
+		// cos(60°) = 0.5 — twice as many degrees of longitude
+		expect(b.east - b.west).toBeCloseTo(4, 2);
+	});
+
+	it('spans all longitudes when the circle reaches a pole', () => {
+		expect(radiusToBounds(89.5, 10, 200)).toEqual({ south: expect.closeTo(87.7, 1), west: -180, north: 90, east: 180 });
+	});
+});
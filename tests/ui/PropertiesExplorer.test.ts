@@ -112,7 +112,7 @@ describe('PropertiesExplorer — LATCH columns', () => {
 		explorer.destroy();
 	});
 
-	it('L column shows 0 properties with "(0/0)" badge and empty state text', () => {
+	it('L column shows 1 property (location_name) with "(1/1)" badge', () => {
 		const explorer = new PropertiesExplorer({ alias, container });
 		explorer.mount();
 
@@ -120,13 +120,11 @@ describe('PropertiesExplorer — LATCH columns', () => {
 		expect(lColumn).not.toBeNull();
 
 		const badge = lColumn!.querySelector('.properties-explorer__badge');
-		expect(badge!.textContent).toBe('(0/0)');
-
-		const empty = lColumn!.querySelector('.properties-explorer__empty');
-		expect(empty).not.toBeNull();
+		expect(badge!.textContent).toBe('(1/1)');
 
 		const props = lColumn!.querySelectorAll('.properties-explorer__property');
-		expect(props.length).toBe(0);
+		expect(props.length).toBe(1);
+		expect(props[0]!.getAttribute('data-field')).toBe('location_name');
 
 		explorer.destroy();
 	});
@@ -305,7 +303,7 @@ describe('PropertiesExplorer — toggle checkbox', () => {
 		explorer.mount();
 
 		const checkboxes = container.querySelectorAll('.properties-explorer__property input[type="checkbox"]');
-		expect(checkboxes.length).toBe(9);
+		expect(checkboxes.length).toBe(10);
 		for (const cb of checkboxes) {
 			expect((cb as HTMLInputElement).checked).toBe(true);
 		}
@@ -340,7 +338,7 @@ describe('PropertiesExplorer — toggle checkbox', () => {
 		firstCheckbox.click();
 
 		const props = container.querySelectorAll('.properties-explorer__property');
-		expect(props.length).toBe(9); // All still visible
+		expect(props.length).toBe(10); // All still visible
 
 		explorer.destroy();
 	});
@@ -367,7 +365,7 @@ describe('PropertiesExplorer — toggle checkbox', () => {
 		const explorer = new PropertiesExplorer({ alias, container });
 		explorer.mount();
 
-		expect(explorer.getEnabledFields().size).toBe(9);
+		expect(explorer.getEnabledFields().size).toBe(10);
 
 		// Toggle off one field
 		const aColumn = container.querySelector('.properties-explorer__column[data-family="A"]') as HTMLElement;
@@ -376,7 +374,7 @@ describe('PropertiesExplorer — toggle checkbox', () => {
 		) as HTMLInputElement;
 		aCheckbox.click();
 
-		expect(explorer.getEnabledFields().size).toBe(8);
+		expect(explorer.getEnabledFields().size).toBe(9);
 		expect(explorer.getEnabledFields().has('name')).toBe(false);
 
 		explorer.destroy();
@@ -0,0 +1,86 @@
+// @vitest-environment jsdom
+// Isometry v5 — SheetPickerDialog Tests
+// Sheet rows with counts, default selection, and the picked sheet names.
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { SheetPickerDialog } from '../../src/ui/SheetPickerDialog';
+
+// jsdom does not implement HTMLDialogElement.showModal()/close()
+const originalCreateElement = document.createElement.bind(document);
+beforeEach(() => {
+	document.createElement = (<K extends keyof HTMLElementTagNameMap>(
+		tagName: K,
+		options?: ElementCreationOptions,
+	): HTMLElementTagNameMap[K] => {
+		const el = originalCreateElement(tagName, options);
+		if (tagName === 'dialog') {
+			const dialog = el as HTMLDialogElement;
+			dialog.showModal = () => dialog.setAttribute('open', '');
+			dialog.close = () => dialog.removeAttribute('open');
+		}
+		return el;
+	}) as typeof document.createElement;
+});
+
+afterEach(() => {
+	document.createElement = originalCreateElement;
+	document.body.querySelectorAll('dialog').forEach((d) => d.remove());
+});
+
+const data = {
+	filename: 'northwind.xlsx',
+	sheets: [
+		{ name: 'Customers', rows: 91, headers: ['CustomerID', 'CompanyName', 'ContactName', 'City', 'Country', 'Phone'] },
+		{ name: 'Orders', rows: 1, headers: ['OrderID', 'CustomerID'] },
+		{ name: 'Notes', rows: 0, headers: [] },
+	],
+};
+
+const checkboxes = () => [...document.body.querySelectorAll<HTMLInputElement>('.sheet-picker-list input')];
+const importBtn = () => document.body.querySelector<HTMLButtonElement>('.app-dialog__btn--confirm')!;
+
+describe('SheetPickerDialog', () => {
+	it('lists each sheet with its row count and a header preview', () => {
+		void SheetPickerDialog.show(data);
+
+		const rows = [...document.body.querySelectorAll('.sheet-picker-list__item')];
+		expect(rows.map((r) => r.querySelector('.sheet-picker-list__count')?.textContent)).toEqual([
+			'91 rows',
+			'1 row',
+			'0 rows',
+		]);
+		expect(rows[0]?.querySelector('.sheet-picker-list__headers')?.textContent).toBe(
+			'CustomerID, CompanyName, ContactName, City, Country, …',
+		);
+		expect(rows[2]?.querySelector('.sheet-picker-list__headers')).toBeNull();
+	});
+
+	it('starts with non-empty sheets checked and resolves the checked names', async () => {
+		const promise = SheetPickerDialog.show(data);
+
+		expect(checkboxes().map((c) => c.checked)).toEqual([true, true, false]);
+		expect(importBtn().textContent).toBe('Import 2 Sheets');
+
+		const orders = checkboxes()[1]!;
+		orders.checked = false;
+		orders.dispatchEvent(new Event('change'));
+		expect(importBtn().textContent).toBe('Import 1 Sheet');
+
+		importBtn().click();
+		expect(await promise).toEqual(['Customers']);
+		expect(document.body.querySelector('dialog')).toBeNull();
+	});
+
+	it('disables import with nothing checked and resolves null on cancel', async () => {
+		const promise = SheetPickerDialog.show(data);
+
+		for (const checkbox of checkboxes()) {
+			checkbox.checked = false;
+			checkbox.dispatchEvent(new Event('change'));
+		}
+		expect(importBtn().disabled).toBe(true);
+
+		document.body.querySelector<HTMLButtonElement>('.app-dialog__btn--cancel')!.click();
+		expect(await promise).toBeNull();
+	});
+});
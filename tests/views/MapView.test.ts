@@ -0,0 +1,222 @@
+// @vitest-environment jsdom
+// Isometry v5 — MapView Tests
+// Tests for the geographic map view: offline basemap, clustering at low zoom,
+// great-circle connection arcs, selection and saving geocoded coordinates.
+
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import { SelectionProvider } from '../../src/providers/SelectionProvider';
+import type { CountryFeature } from '../../src/views/map/basemap';
+import { MapView } from '../../src/views/MapView';
+import type { CardDatum, WorkerBridgeLike } from '../../src/views/types';
+import type { WorkerResponses } from '../../src/worker/protocol';
+
+// ---------------------------------------------------------------------------
+// Test fixtures
+// ---------------------------------------------------------------------------
+
+function makeCards(ids: string[]): CardDatum[] {
+	return ids.map((id, i) => ({
+		id,
+		name: `Card ${id}`,
+		folder: null,
+		status: null,
+		card_type: 'note',
+		created_at: '2026-01-01T10:00:00Z',
+		modified_at: '2026-01-01T12:00:00Z',
+		priority: 0,
+		sort_order: i,
+		due_at: null,
+		body_text: null,
+		source: null,
+	}));
+}
+
+const CARDS = makeCards(['london-1', 'london-2', 'sydney']);
+
+const RESULT: WorkerResponses['map:query'] = {
+	points: [
+		{ id: 'london-1', latitude: 51.5072, longitude: -0.1276, geocoded: false },
+		{ id: 'london-2', latitude: 51.5073, longitude: -0.1277, geocoded: true },
+		{ id: 'sydney', latitude: -33.8688, longitude: 151.2093, geocoded: false },
+	],
+	connections: [
+		{ id: 'c1', sourceId: 'london-1', targetId: 'sydney', label: 'flight' },
+		{ id: 'c2', sourceId: 'london-1', targetId: 'london-2', label: null },
+	],
+	unlocated: 1,
+};
+
+const SQUARE: CountryFeature = {
+	type: 'Feature',
+	id: '1',
+	properties: { name: 'Square' },
+	geometry: {
+		type: 'Polygon',
+		coordinates: [
+			[
+				[0, 0],
+				[0, 10],
+				[10, 10],
+				[10, 0],
+				[0, 0],
+			],
+		],
+	},
+};
+
+function makeBridge(result: WorkerResponses['map:query'] = RESULT) {
+	return {
+		send: vi.fn().mockImplementation(async (type: string) => {
+			if (type === 'map:query') return result;
+			if (type === 'geocode:fill') return { updated: 1 };
+			return null;
+		}),
+	};
+}
+
+const markerIds = (view: MapView) =>
+	view
+		.getMarkers()
+		.map((m) => m.cardIds.join('+'))
+		.sort();
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+describe('MapView', () => {
+	let container: HTMLElement;
+	let bridge: ReturnType<typeof makeBridge>;
+	let selection: SelectionProvider;
+	let view: MapView;
+
+	beforeEach(() => {
+		container = document.createElement('div');
+		Object.defineProperty(container, 'clientWidth', { configurable: true, value: 800 });
+		Object.defineProperty(container, 'clientHeight', { configurable: true, value: 500 });
+		document.body.appendChild(container);
+		bridge = makeBridge();
+		selection = new SelectionProvider();
+		view = new MapView({
+			bridge: bridge as WorkerBridgeLike,
+			selectionProvider: selection,
+			loadBasemap: () => Promise.resolve([SQUARE]),
+		});
+		view.mount(container);
+	});
+
+	afterEach(() => {
+		view.destroy();
+		container.remove();
+	});
+
+	it('draws the sphere, graticule and bundled countries', async () => {
+		expect(container.querySelector('.map-view__sphere')!.getAttribute('d')).toBeTruthy();
+		expect(container.querySelector('.map-view__graticule')!.getAttribute('d')).toBeTruthy();
+
+		await vi.waitFor(() => {
+			expect(container.querySelectorAll('.map-view__country')).toHaveLength(1);
+		});
+	});
+
+	it('still renders when the basemap is unavailable', async () => {
+		view.destroy();
+		view = new MapView({ bridge: bridge as WorkerBridgeLike, loadBasemap: () => Promise.resolve(null) });
+		view.mount(container);
+		await view.render(CARDS);
+
+		expect(container.querySelectorAll('.map-view__country')).toHaveLength(0);
+		expect(container.querySelectorAll('.map-view__marker').length).toBeGreaterThan(0);
+	});
+
+	it('queries positions for the rendered cards and reports the status', async () => {
+		await view.render(CARDS);
+
+		expect(bridge.send).toHaveBeenCalledWith('map:query', { cardIds: ['london-1', 'london-2', 'sydney'] });
+		expect(container.querySelector('svg')!.getAttribute('aria-label')).toBe('Map view, 3 cards');
+		expect(container.querySelector('.map-view__status span')!.textContent).toBe(
+			'3 placed \u00B7 1 from place names \u00B7 1 without location',
+		);
+	});
+
+	it('clusters nearby cards at world zoom and splits them when zoomed in', async () => {
+		await view.render(CARDS);
+
+		expect(markerIds(view)).toEqual(['london-1+london-2', 'sydney']);
+		const cluster = container.querySelector('.map-view__marker--cluster')!;
+		expect(cluster.querySelector('.map-view__count')!.textContent).toBe('2');
+
+		expect(view.getMarkers().find((m) => m.cardIds.length > 1)!.id).toMatch(/^cluster:/);
+
+		for (let i = 0; i < 4; i++) view.zoomToMarker(view.getMarkers().find((m) => m.cardIds.includes('london-1'))!.id);
+
+		expect(markerIds(view)).toEqual(['london-1', 'london-2', 'sydney']);
+		expect(container.querySelector('[data-id="london-2"]')!.classList).toContain('map-view__marker--geocoded');
+	});
+
+	it('draws connections between markers as great-circle arcs', async () => {
+		await view.render(CARDS);
+
+		// The London pair share a cluster, so only the London → Sydney arc remains
+		const arcs = container.querySelectorAll('.map-view__arc');
+		expect(arcs).toHaveLength(1);
+		expect(arcs[0]!.querySelector('title')!.textContent).toBe('flight');
+		// Resampled along the sphere: many more vertices than a straight segment
+		expect(arcs[0]!.getAttribute('d')!.split('L').length).toBeGreaterThan(3);
+	});
+
+	it('selects single cards through SelectionProvider', async () => {
+		await view.render(CARDS);
+		const sydney = container.querySelector('[data-id="sydney"]')!;
+
+		sydney.dispatchEvent(new MouseEvent('click', { bubbles: true }));
+		expect(selection.getSelectedIds()).toEqual(['sydney']);
+
+		await Promise.resolve();
+		expect(sydney.classList).toContain('map-view__marker--selected');
+
+		sydney.dispatchEvent(new MouseEvent('click', { bubbles: true, shiftKey: true }));
+		expect(selection.getSelectedIds()).toEqual([]);
+	});
+
+	it('clicking a cluster zooms in instead of selecting', async () => {
+		await view.render(CARDS);
+		const layer = container.querySelector('.map-view__layer')!;
+		const scale = () => Number(/scale\(([\d.]+)\)/.exec(layer.getAttribute('transform') ?? '')?.[1] ?? 1);
+		const before = scale();
+
+		container.querySelector('.map-view__marker--cluster')!.dispatchEvent(new MouseEvent('click', { bubbles: true }));
+
+		expect(selection.getSelectedIds()).toEqual([]);
+		expect(scale()).toBeCloseTo(before * 2, 5);
+	});
+
+	it('saves geocoded coordinates and re-queries', async () => {
+		await view.render(CARDS);
+
+		(container.querySelector('.map-view__geocode') as HTMLButtonElement).click();
+
+		await vi.waitFor(() => {
+			expect(bridge.send).toHaveBeenCalledWith('geocode:fill', { cardIds: ['london-2'] });
+			expect(bridge.send.mock.calls.filter(([type]) => type === 'map:query')).toHaveLength(2);
+		});
+	});
+
+	it('clears markers for an empty card list without querying', async () => {
+		await view.render(CARDS);
+		bridge.send.mockClear();
+
+		await view.render([]);
+
+		expect(bridge.send).not.toHaveBeenCalled();
+		expect(container.querySelectorAll('.map-view__marker')).toHaveLength(0);
+	});
+
+	it('destroy removes the map and the status bar', async () => {
+		await view.render(CARDS);
+		view.destroy();
+
+		expect(container.querySelector('svg')).toBeNull();
+		expect(container.querySelector('.map-view__status')).toBeNull();
+	});
+});
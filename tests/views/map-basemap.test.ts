@@ -0,0 +1,129 @@
+// Isometry v5 — Map View Basemap + Clustering Tests
+// Covers the minimal TopoJSON decoder and screen-space grid clustering.
+
+import { describe, expect, it } from 'vitest';
+import { type Topology, topologyToFeatures } from '../../src/views/map/basemap';
+import { clusterPoints, type MapPoint } from '../../src/views/map/cluster';
+
+// ---------------------------------------------------------------------------
+// topologyToFeatures
+// ---------------------------------------------------------------------------
+
+describe('topologyToFeatures', () => {
+	// Two squares sharing the arc x = 1; arc 1 is used reversed (~1) by the second polygon
+	const topology: Topology = {
+		type: 'Topology',
+		transform: { scale: [1, 1], translate: [0, 0] },
+		arcs: [
+			[
+				[1, 0],
+				[-1, 0],
+				[0, 1],
+				[1, 0],
+			],
+			[
+				[1, 1],
+				[0, -1],
+			],
+			[
+				[1, 1],
+				[1, 0],
+				[0, -1],
+				[-1, 0],
+			],
+		],
+		objects: {
+			countries: {
+				type: 'GeometryCollection',
+				geometries: [
+					{ type: 'Polygon', id: '001', properties: { name: 'West' }, arcs: [[0, 1]] },
+					{ type: 'MultiPolygon', id: '002', properties: { name: 'East' }, arcs: [[[2, ~1]]] },
+					{ type: null },
+				],
+			},
+		},
+	};
+
+	it('decodes delta-encoded arcs into closed rings', () => {
+		const [west] = topologyToFeatures(topology, 'countries');
+
+		expect(west).toEqual({
+			type: 'Feature',
+			id: '001',
+			properties: { name: 'West' },
+			geometry: {
+				type: 'Polygon',
+				coordinates: [
+					[
+						[1, 0],
+						[0, 0],
+						[0, 1],
+						[1, 1],
+						[1, 0],
+					],
+				],
+			},
+		});
+	});
+
+	it('reverses negative arc indices and skips null geometries', () => {
+		const features = topologyToFeatures(topology, 'countries');
+
+		expect(features).toHaveLength(2);
+		expect(features[1]!.geometry).toEqual({
+			type: 'MultiPolygon',
+			coordinates: [
+				[
+					[
+						[1, 1],
+						[2, 1],
+						[2, 0],
+						[1, 0],
+						[1, 1],
+					],
+				],
+			],
+		});
+	});
+
+	it('returns [] for a missing object', () => {
+		expect(topologyToFeatures(topology, 'land')).toEqual([]);
+	});
+});
+
+// ---------------------------------------------------------------------------
+// clusterPoints
+// ---------------------------------------------------------------------------
+
+describe('clusterPoints', () => {
+	const point = (id: string, x: number, y: number, geocoded = false): MapPoint => ({
+		id,
+		latitude: y,
+		longitude: x,
+		geocoded,
+	});
+	const identity = (p: MapPoint): [number, number] => [p.longitude, p.latitude];
+
+	it('merges points in the same cell at their mean position', () => {
+		const markers = clusterPoints([point('a', 1, 1), point('b', 3, 5), point('c', 25, 5)], identity, 10);
+
+		expect(markers).toEqual([
+			{ id: 'cluster:0:0', x: 2, y: 3, cardIds: ['a', 'b'], geocoded: false },
+			{ id: 'c', x: 25, y: 5, cardIds: ['c'], geocoded: false },
+		]);
+	});
+
+	it('marks a cluster geocoded only when every member is', () => {
+		const [mixed] = clusterPoints([point('a', 1, 1, true), point('b', 2, 2)], identity, 10);
+		const [all] = clusterPoints([point('a', 1, 1, true), point('b', 2, 2, true)], identity, 10);
+		expect(mixed!.geocoded).toBe(false);
+		expect(all!.geocoded).toBe(true);
+	});
+
+	it('cell size 0 disables clustering and unprojectable points are skipped', () => {
+		const markers = clusterPoints([point('a', 1, 1), point('b', 1, 1), point('gone', 0, 0)], (p) =>
+			p.id === 'gone' ? null : identity(p),
+		0);
+		expect(markers.map((m) => m.id)).toEqual(['a', 'b']);
+	});
+});
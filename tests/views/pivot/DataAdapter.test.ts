@@ -244,6 +244,18 @@ describe('BridgeDataAdapter', () => {
 		expect(callArg).toHaveProperty('params', ['active']);
 	});
 
+	it('Test 6c: fetchData() passes schema Time fields so date properties are bucketed', async () => {
+		adapter.setSchemaProvider({
+			getFieldsByFamily: (family: string) =>
+				family === 'Time' ? [{ name: 'created_at' }, { name: 'prop_closes_on' }] : [],
+		});
+
+		await adapter.fetchData(adapter.getRowDimensions(), adapter.getColDimensions());
+
+		const callArg = (bridge.superGridQuery as ReturnType<typeof vi.fn>).mock.calls[0]?.[0];
+		expect(callArg).toHaveProperty('timeFields', ['created_at', 'prop_closes_on']);
+	});
+
 	it('Test 7: getRowDimensions() reads from provider.getStackedGroupBySQL().rowAxes', () => {
 		const rowDims = adapter.getRowDimensions();
 		expect(rowDims).toHaveLength(2); // folder, status
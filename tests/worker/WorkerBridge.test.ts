@@ -572,3 +572,198 @@ describe('getWorkerBridge singleton', () => {
 		module.resetWorkerBridge();
 	});
 });
+
+// ---------------------------------------------------------------------------
+// Tests: Cursor streaming (streamCards / streamQuery)
+// ---------------------------------------------------------------------------
+
+describe('WorkerBridge — cursor streaming', () => {
+	let createWorkerBridgeLocal: typeof import('../../src/worker/WorkerBridge').createWorkerBridge;
+
+	beforeEach(async () => {
+		const module = await getWorkerBridgeModule();
+		createWorkerBridgeLocal = module.createWorkerBridge;
+	});
+
+	afterEach(() => {
+		vi.clearAllMocks();
+	});
+
+	/** Serve `total` rows in pages of `pageSize` via cursor:open / cursor:next. */
+	function serveCursor(mockWorker: MockWorker, total: number, pageSize: number): WorkerRequest[] {
+		const requests: WorkerRequest[] = [];
+		let offset = 0;
+		mockWorker.setMessageHandler((request) => {
+			requests.push(request);
+			if (request.type === 'cursor:close') {
+				mockWorker.simulateMessage(createSuccessResponse<'cursor:close'>(request.id, { closed: true }));
+				return;
+			}
+			const rows = Array.from({ length: Math.min(pageSize, total - offset) }, (_, i) => ({ n: offset + i }));
+			offset += rows.length;
+			mockWorker.simulateMessage(
+				createSuccessResponse<'cursor:open'>(request.id, {
+					cursorId: 'cur-1',
+					columns: ['n'],
+					rows,
+					done: offset >= total,
+				}),
+			);
+		});
+		return requests;
+	}
+
+	it('streamQuery yields every page until done', async () => {
+		const bridge = createWorkerBridgeLocal();
+		await bridge.isReady;
+		const mockWorker = (bridge as unknown as { worker: MockWorker }).worker;
+		const requests = serveCursor(mockWorker, 7, 3);
+
+		const pages: number[][] = [];
+		for await (const page of bridge.streamQuery('SELECT n FROM t', [], 3)) {
+			pages.push(page.map((r) => r['n'] as number));
+		}
+
+		expect(pages).toEqual([[0, 1, 2], [3, 4, 5], [6]]);
+		expect(requests.map((r) => r.type)).toEqual(['cursor:open', 'cursor:next', 'cursor:next']);
+		bridge.terminate();
+	});
+
+	it('breaking out early sends cursor:close', async () => {
+		const bridge = createWorkerBridgeLocal();
+		await bridge.isReady;
+		const mockWorker = (bridge as unknown as { worker: MockWorker }).worker;
+		const requests = serveCursor(mockWorker, 100, 10);
+
+		for await (const _page of bridge.streamCards(undefined, 10)) {
+			break;
+		}
+		await wait(0);
+
+		expect(requests.map((r) => r.type)).toEqual(['cursor:open', 'cursor:close']);
+		expect(requests[0]!.payload).toEqual({ source: { kind: 'card:list' }, pageSize: 10 });
+		bridge.terminate();
+	});
+});
+
+// ---------------------------------------------------------------------------
+// Tests: Cancellation (AbortSignal + latest-wins channels)
+// ---------------------------------------------------------------------------
+
+describe('WorkerBridge — cancellation', () => {
+	let createWorkerBridgeLocal: typeof import('../../src/worker/WorkerBridge').createWorkerBridge;
+
+	beforeEach(async () => {
+		const module = await getWorkerBridgeModule();
+		createWorkerBridgeLocal = module.createWorkerBridge;
+	});
+
+	afterEach(() => {
+		vi.clearAllMocks();
+	});
+
+	it('rejects with CANCELLED and posts cancel when the signal aborts', async () => {
+		const bridge = createWorkerBridgeLocal();
+		await bridge.isReady;
+		const mockWorker = (bridge as unknown as { worker: MockWorker }).worker;
+
+		const posted: Array<{ type: string; id: string }> = [];
+		mockWorker.setMessageHandler((msg) => {
+			posted.push(msg as unknown as { type: string; id: string });
+		});
+
+		const controller = new AbortController();
+		const promise = bridge.send('card:list', {}, { signal: controller.signal });
+		await wait(0);
+		controller.abort();
+
+		await expect(promise).rejects.toMatchObject({ code: 'CANCELLED' });
+		expect(posted.map((m) => m.type)).toEqual(['card:list', 'cancel']);
+		expect(posted[1]!.id).toBe(posted[0]!.id);
+		bridge.terminate();
+	});
+
+	it('rejects immediately without posting when the signal is already aborted', async () => {
+		const bridge = createWorkerBridgeLocal();
+		await bridge.isReady;
+		const mockWorker = (bridge as unknown as { worker: MockWorker }).worker;
+		const handler = vi.fn();
+		mockWorker.setMessageHandler(handler);
+
+		const controller = new AbortController();
+		controller.abort();
+
+		await expect(bridge.send('card:list', {}, { signal: controller.signal })).rejects.toMatchObject({
+			code: 'CANCELLED',
+		});
+		expect(handler).not.toHaveBeenCalled();
+		bridge.terminate();
+	});
+
+	it('supersedes the previous pending request on the same channel', async () => {
+		const bridge = createWorkerBridgeLocal();
+		await bridge.isReady;
+		const mockWorker = (bridge as unknown as { worker: MockWorker }).worker;
+
+		const requests: WorkerRequest[] = [];
+		mockWorker.setMessageHandler((request) => {
+			requests.push(request);
+		});
+
+		const first = bridge.send(
+			'histogram:query',
+			{ field: 'priority', fieldType: 'numeric', bins: 10, where: '', params: [] },
+			{ channel: 'histogram:priority' },
+		);
+		await wait(0);
+		const second = bridge.send(
+			'histogram:query',
+			{ field: 'priority', fieldType: 'numeric', bins: 5, where: '', params: [] },
+			{ channel: 'histogram:priority' },
+		);
+		await wait(0);
+
+		await expect(first).rejects.toMatchObject({ code: 'CANCELLED' });
+		expect(requests.every((r) => r.channel === 'histogram:priority')).toBe(true);
+
+		mockWorker.simulateMessage(createSuccessResponse<'histogram:query'>(requests[1]!.id, { bins: [] }));
+		await expect(second).resolves.toEqual({ bins: [] });
+		bridge.terminate();
+	});
+
+	it('sends card searches on the search:cards channel so keystrokes supersede', async () => {
+		const bridge = createWorkerBridgeLocal();
+		await bridge.isReady;
+		const mockWorker = (bridge as unknown as { worker: MockWorker }).worker;
+
+		const requests: WorkerRequest[] = [];
+		mockWorker.setMessageHandler((request) => {
+			requests.push(request);
+		});
+
+		const first = bridge.searchCards('te', 5);
+		await wait(0);
+		const second = bridge.searchCards('test', 5);
+		await wait(0);
+
+		await expect(first).rejects.toMatchObject({ code: 'CANCELLED' });
+		expect(requests.map((r) => r.channel)).toEqual(['search:cards', 'search:cards']);
+
+		mockWorker.simulateMessage(createSuccessResponse<'search:cards'>(requests[1]!.id, []));
+		await expect(second).resolves.toEqual([]);
+		bridge.terminate();
+	});
+
+	it('rejects a pending request when the worker reports it cancelled', async () => {
+		const bridge = createWorkerBridgeLocal();
+		await bridge.isReady;
+		const mockWorker = (bridge as unknown as { worker: MockWorker }).worker;
+
+		mockWorker.setMessageHandler((request) => {
+			mockWorker.simulateMessage({ type: 'cancelled', id: request.id, reason: 'superseded' });
+		});
+
+		await expect(bridge.listCards()).rejects.toMatchObject({ code: 'CANCELLED' });
+		bridge.terminate();
+	});
+});
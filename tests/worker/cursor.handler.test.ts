@@ -0,0 +1,162 @@
+// Isometry v5 — Cursor Streaming Handler Tests
+// Tests for cursor:open / cursor:next / cursor:close paging of card:list and db:query.
+//
+// Pattern: Uses a real Database instance (no Worker needed).
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { Database } from '../../src/database/Database';
+import { createCard, listCards } from '../../src/database/queries/cards';
+import {
+	closeAllCursors,
+	handleCursorClose,
+	handleCursorNext,
+	handleCursorOpen,
+	MAX_OPEN_CURSORS,
+	openCursorCount,
+} from '../../src/worker/handlers/cursor.handler';
+import { handleDbExport } from '../../src/worker/handlers/export.handler';
+
+let db: Database;
+
+beforeEach(async () => {
+	db = new Database();
+	await db.initialize();
+	for (let i = 0; i < 25; i++) {
+		createCard(db, { name: `Card ${i}`, folder: i % 2 === 0 ? 'Even' : 'Odd', tags: [`t${i}`] });
+	}
+});
+
+afterEach(() => {
+	closeAllCursors();
+	db.close();
+});
+
+describe('handleCursorOpen — card:list', () => {
+	it('returns the first page mapped to Card objects', () => {
+		const page = handleCursorOpen(db, { source: { kind: 'card:list' }, pageSize: 10 });
+
+		expect(page.rows).toHaveLength(10);
+		expect(page.done).toBe(false);
+		expect(page.cursorId).toBeTruthy();
+		// tags JSON is parsed like listCards()
+		expect(Array.isArray(page.rows[0]!['tags'])).toBe(true);
+	});
+
+	it('pages through every card in listCards() order', () => {
+		const expected = listCards(db).map((c) => c.id);
+		const seen: string[] = [];
+
+		let page = handleCursorOpen(db, { source: { kind: 'card:list' }, pageSize: 10 });
+		seen.push(...page.rows.map((r) => r['id'] as string));
+		while (!page.done) {
+			page = handleCursorNext({ cursorId: page.cursorId });
+			seen.push(...page.rows.map((r) => r['id'] as string));
+		}
+
+		expect(seen).toEqual(expected);
+		expect(openCursorCount()).toBe(0);
+	});
+
+	it('applies CardListOptions filters', () => {
+		const page = handleCursorOpen(db, { source: { kind: 'card:list', options: { folder: 'Odd' } }, pageSize: 100 });
+		expect(page.rows).toHaveLength(12);
+		expect(page.done).toBe(true);
+	});
+
+	it('marks done on the page that returns the final row', () => {
+		const page = handleCursorOpen(db, { source: { kind: 'card:list' }, pageSize: 25 });
+		expect(page.rows).toHaveLength(25);
+		expect(page.done).toBe(true);
+	});
+});
+
+describe('handleCursorOpen — db:query', () => {
+	it('streams a parameterized SELECT with columns', () => {
+		const page = handleCursorOpen(db, {
+			source: { kind: 'db:query', sql: 'SELECT id, name FROM cards WHERE folder = ? ORDER BY name', params: ['Even'] },
+			pageSize: 5,
+		});
+
+		expect(page.columns).toEqual(['id', 'name']);
+		expect(page.rows).toHaveLength(5);
+		expect(Object.keys(page.rows[0]!)).toEqual(['id', 'name']);
+	});
+
+	it('returns an empty, done page for an empty result set', () => {
+		const page = handleCursorOpen(db, {
+			source: { kind: 'db:query', sql: 'SELECT id FROM cards WHERE folder = ?', params: ['Nope'] },
+		});
+		expect(page.rows).toEqual([]);
+		expect(page.done).toBe(true);
+		expect(openCursorCount()).toBe(0);
+	});
+
+	it('clamps non-positive page sizes to at least one row', () => {
+		const page = handleCursorOpen(db, { source: { kind: 'card:list' }, pageSize: 0 });
+		expect(page.rows).toHaveLength(1);
+	});
+});
+
+describe('handleCursorClose / handleCursorNext', () => {
+	it('closes an open cursor and rejects further reads', () => {
+		const page = handleCursorOpen(db, { source: { kind: 'card:list' }, pageSize: 5 });
+
+		expect(handleCursorClose({ cursorId: page.cursorId })).toEqual({ closed: true });
+		expect(() => handleCursorNext({ cursorId: page.cursorId })).toThrow(/not found/);
+	});
+
+	it('is idempotent for unknown cursors', () => {
+		expect(handleCursorClose({ cursorId: 'missing' })).toEqual({ closed: false });
+	});
+
+	it('evicts the least recently used cursor beyond MAX_OPEN_CURSORS', () => {
+		const first = handleCursorOpen(db, { source: { kind: 'card:list' }, pageSize: 1 });
+		for (let i = 0; i < MAX_OPEN_CURSORS; i++) {
+			handleCursorOpen(db, { source: { kind: 'card:list' }, pageSize: 1 });
+		}
+
+		expect(openCursorCount()).toBe(MAX_OPEN_CURSORS);
+		expect(() => handleCursorNext({ cursorId: first.cursorId })).toThrow(/not found/);
+	});
+});
+
+describe('db:export', () => {
+	it('closes open cursors before sql.js frees their statements', () => {
+		const page = handleCursorOpen(db, { source: { kind: 'card:list' }, pageSize: 5 });
+
+		handleDbExport(db);
+
+		expect(openCursorCount()).toBe(0);
+		expect(() => handleCursorNext({ cursorId: page.cursorId })).toThrow(/not found/);
+	});
+});
+
+describe('Database.openCursor', () => {
+	function spyOnPreparedFree(): { freed: () => boolean } {
+		const raw = (db as unknown as { db: { prepare: (sql: string) => { free: () => boolean } } }).db;
+		const prepare = raw.prepare.bind(raw);
+		let freed = false;
+		raw.prepare = (sql: string) => {
+			const stmt = prepare(sql);
+			const free = stmt.free.bind(stmt);
+			stmt.free = () => {
+				freed = true;
+				return free();
+			};
+			return stmt;
+		};
+		return { freed: () => freed };
+	}
+
+	it('frees the statement when bind() throws', () => {
+		const spy = spyOnPreparedFree();
+		expect(() => db.openCursor('SELECT id FROM cards WHERE name = ?', ['a', 'b'])).toThrow();
+		expect(spy.freed()).toBe(true);
+	});
+
+	it('frees the statement when the first step() throws', () => {
+		const spy = spyOnPreparedFree();
+		expect(() => db.openCursor("SELECT json_extract(?, '$')", ['not json'])).toThrow(/malformed JSON/);
+		expect(spy.freed()).toBe(true);
+	});
+});
@@ -0,0 +1,157 @@
+// Isometry v5 — Incremental Directory Re-import Tests
+// datasets:reimport with a file re-scan: manifest bootstrap, unchanged files
+// skipped, renames re-keyed in place, removed files deleted, changes grouped by file.
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { Database } from '../../../src/database/Database';
+import { getDatasetFiles } from '../../../src/database/queries/dataset-files';
+import type { ReimportFile } from '../../../src/etl/FileManifest';
+import type { CanonicalCard } from '../../../src/etl/types';
+import {
+	handleDatasetsCommitReimport,
+	handleDatasetsFiles,
+	handleDatasetsReimport,
+} from '../../../src/worker/handlers/datasets.handler';
+
+const DIR = '/vault/notes';
+
+function card(file: string, name: string, modified = '2026-01-01T00:00:00Z'): CanonicalCard {
+	return {
+		id: crypto.randomUUID(),
+		card_type: 'note',
+		name,
+		content: `Body of ${name}`,
+		summary: null,
+		latitude: null,
+		longitude: null,
+		location_name: null,
+		created_at: '2026-01-01T00:00:00Z',
+		modified_at: modified,
+		due_at: null,
+		completed_at: null,
+		event_start: null,
+		event_end: null,
+		folder: null,
+		tags: [],
+		status: null,
+		priority: 0,
+		sort_order: 0,
+		url: null,
+		mime_type: null,
+		is_collective: false,
+		source: 'alto_index',
+		source_id: `${DIR}/${file}`,
+		source_url: null,
+		deleted_at: null,
+	};
+}
+
+function file(name: string, hash: string, cards?: CanonicalCard[]): ReimportFile {
+	return { path: `${DIR}/${name}`, size: 10, mtime: '2026-01-01T00:00:00Z', hash, ...(cards ? { cards } : {}) };
+}
+
+let db: Database;
+
+beforeEach(async () => {
+	db = new Database();
+	await db.initialize();
+	db.run('INSERT INTO datasets (id, name, source_type, directory_path) VALUES (?, ?, ?, ?)', [
+		'ds1',
+		'Alto Index: notes',
+		'alto_index_notes',
+		DIR,
+	]);
+});
+
+afterEach(() => {
+	db.close();
+});
+
+async function bootstrap(): Promise<void> {
+	handleDatasetsReimport(db, {
+		datasetId: 'ds1',
+		files: [
+			file('a.md', 'ha', [card('a.md', 'A')]),
+			file('b.md', 'hb', [card('b.md', 'B')]),
+			file('c.md', 'hc', [card('c.md', 'C')]),
+			file('f.md', 'hf', [card('f.md', 'F')]),
+		],
+	});
+	await handleDatasetsCommitReimport(db, { datasetId: 'ds1' });
+}
+
+describe('datasets:reimport with a file re-scan', () => {
+	it('seeds the manifest on the first re-scan', async () => {
+		const preview = handleDatasetsReimport(db, {
+			datasetId: 'ds1',
+			files: [file('a.md', 'ha', [card('a.md', 'A')])],
+		});
+		expect(preview.toInsert.map((c) => c.name)).toEqual(['A']);
+		expect(preview.files).toMatchObject([{ path: 'a.md', change: 'added', cards: [{ name: 'A', change: 'new' }] }]);
+
+		await handleDatasetsCommitReimport(db, { datasetId: 'ds1' });
+
+		expect(getDatasetFiles(db, 'ds1')).toEqual([
+			{ path: `${DIR}/a.md`, size: 10, mtime: '2026-01-01T00:00:00Z', hash: 'ha', source_ids: [`${DIR}/a.md`] },
+		]);
+		expect(handleDatasetsFiles(db, { datasetId: 'ds1' })).toEqual([
+			{ path: `${DIR}/a.md`, size: 10, mtime: '2026-01-01T00:00:00Z', hash: 'ha' },
+		]);
+	});
+
+	it('classifies only changed files and groups the changes by file', async () => {
+		await bootstrap();
+		const cId = db.exec("SELECT id FROM cards WHERE name = 'C'")[0]?.values[0]?.[0];
+
+		const preview = handleDatasetsReimport(db, {
+			datasetId: 'ds1',
+			files: [
+				file('a.md', 'ha'), // unchanged — not parsed
+				file('b.md', 'hb2', [card('b.md', 'B edited')]), // same modified_at, new content
+				file('d.md', 'hc', [card('d.md', 'C')]), // c.md renamed
+				file('e.md', 'he', [card('e.md', 'E')]),
+			],
+		});
+
+		expect(preview).toMatchObject({
+			toInsert: [{ name: 'E' }],
+			toUpdate: [{ name: 'B edited' }],
+			renamed: [{ id: cId, name: 'C' }],
+			deletedNames: ['F'],
+			unchanged: 1,
+		});
+		expect(preview.files.map((f) => [f.path, f.change, f.previousPath, f.cards.map((c) => c.change)])).toEqual([
+			['b.md', 'modified', null, ['modified']],
+			['d.md', 'renamed', 'c.md', ['renamed']],
+			['e.md', 'added', null, ['new']],
+			['f.md', 'removed', null, ['deleted']],
+		]);
+
+		const result = await handleDatasetsCommitReimport(db, { datasetId: 'ds1' });
+
+		expect(result).toMatchObject({ inserted: 1, updated: 2 });
+		const live = db.exec(
+			"SELECT id, name, source_id FROM cards WHERE source = 'alto_index' AND deleted_at IS NULL ORDER BY source_id",
+		)[0]?.values;
+		expect(live?.map((row) => row[1])).toEqual(['A', 'B edited', 'C', 'E']);
+		// The renamed file's card keeps its id under the new path
+		expect(live?.[2]).toEqual([cId, 'C', `${DIR}/d.md`]);
+		expect(getDatasetFiles(db, 'ds1').map((f) => f.path)).toEqual([
+			`${DIR}/a.md`,
+			`${DIR}/b.md`,
+			`${DIR}/d.md`,
+			`${DIR}/e.md`,
+		]);
+	});
+
+	it('reports nothing to do when no file changed', async () => {
+		await bootstrap();
+
+		const preview = handleDatasetsReimport(db, {
+			datasetId: 'ds1',
+			files: [file('a.md', 'ha'), file('b.md', 'hb'), file('c.md', 'hc'), file('f.md', 'hf')],
+		});
+
+		expect(preview).toMatchObject({ toInsert: [], toUpdate: [], deletedIds: [], renamed: [], unchanged: 4, files: [] });
+	});
+});
@@ -0,0 +1,51 @@
+// Isometry v5 — Mapping Profile Suggestion Tests
+// mapping-profile:suggest reads the header row of an import payload and
+// matches it against saved profiles by fingerprint.
+
+import { afterEach, beforeEach, describe, expect, it } from 'vitest';
+import { Database } from '../../../src/database/Database';
+import { saveMappingProfile } from '../../../src/database/queries/mapping-profiles';
+import { handleMappingProfileSuggest } from '../../../src/worker/handlers/mapping-profiles.handler';
+
+let db: Database;
+
+beforeEach(async () => {
+	db = new Database();
+	await db.initialize();
+});
+
+afterEach(() => {
+	db.close();
+});
+
+describe('handleMappingProfileSuggest', () => {
+	it('suggests the profile whose headers match a CSV file', async () => {
+		const saved = saveMappingProfile(db, {
+			name: 'CRM weekly',
+			headers: ['Id', 'Deal', 'Stage'],
+			rules: { columns: { Deal: 'name' } },
+		});
+
+		const result = await handleMappingProfileSuggest(db, {
+			source: 'csv',
+			data: 'stage,deal,id\nWon,Acme,7',
+			filename: 'week-02.csv',
+		});
+
+		expect(result.headers).toEqual(['stage', 'deal', 'id']);
+		expect(result.suggestion).toMatchObject({ profile: { id: saved.id }, score: 1 });
+	});
+
+	it('returns no suggestion without a match or for sources without headers', async () => {
+		saveMappingProfile(db, { name: 'CRM weekly', headers: ['Id', 'Deal', 'Stage'], rules: { columns: {} } });
+
+		expect(await handleMappingProfileSuggest(db, { source: 'json', data: '[{"title":"A"}]' })).toEqual({
+			headers: ['title'],
+			suggestion: null,
+		});
+		expect(await handleMappingProfileSuggest(db, { source: 'opml', data: '<opml/>' })).toEqual({
+			headers: [],
+			suggestion: null,
+		});
+	});
+});
@@ -6,6 +6,8 @@ import {
 	DEFAULT_WORKER_CONFIG,
 	ETL_TIMEOUT,
 	type ImportProgressPayload,
+	isCancelledError,
+	isCancelledMessage,
 	isErrorResponse,
 	isInitErrorMessage,
 	isNotification,
@@ -484,3 +486,36 @@ describe('Worker Protocol - Notification Types', () => {
 		});
 	});
 });
+
+describe('Worker Protocol - Cancellation', () => {
+	describe('isCancelledMessage type guard', () => {
+		it('returns true for a cancelled notice', () => {
+			expect(isCancelledMessage({ type: 'cancelled', id: 'req-1', reason: 'superseded' })).toBe(true);
+		});
+
+		it('returns false for responses, notifications and garbage', () => {
+			expect(isCancelledMessage(createSuccessResponse<'card:list'>('req-1', []))).toBe(false);
+			expect(isCancelledMessage({ type: 'import_progress', payload: {} })).toBe(false);
+			expect(isCancelledMessage({ type: 'cancelled' })).toBe(false);
+			expect(isCancelledMessage(null)).toBe(false);
+		});
+
+		it('cancelled notices are not mistaken for responses', () => {
+			expect(isResponse({ type: 'cancelled', id: 'req-1', reason: 'aborted' })).toBe(false);
+		});
+	});
+
+	describe('isCancelledError', () => {
+		it('matches errors with code CANCELLED', () => {
+			const error = Object.assign(new Error('cancelled'), { code: 'CANCELLED' });
+			expect(isCancelledError(error)).toBe(true);
+		});
+
+		it('does not match other errors', () => {
+			const timeout = Object.assign(new Error('timed out'), { code: 'TIMEOUT' });
+			expect(isCancelledError(timeout)).toBe(false);
+			expect(isCancelledError(new Error('plain'))).toBe(false);
+			expect(isCancelledError('CANCELLED')).toBe(false);
+		});
+	});
+});
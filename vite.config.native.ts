@@ -23,6 +23,11 @@ export default defineConfig({
           src: 'src/assets/sql-wasm-fts5.wasm',
           dest: 'assets',
         },
+        {
+          // Offline map basemap (MapView) — world countries TopoJSON, 1:110m
+          src: 'node_modules/world-atlas/countries-110m.json',
+          dest: 'assets',
+        },
       ],
     }),
   ],